@@ -1,6 +1,6 @@
 //! Example that displays information about the caches.
 extern crate raw_cpuid;
-use raw_cpuid::{CacheType, CpuId};
+use raw_cpuid::{Associativity, CacheType, CpuId};
 
 fn main() {
     let cpuid = CpuId::new();
@@ -8,10 +8,7 @@ fn main() {
         || println!("No cache parameter information available"),
         |cparams| {
             for cache in cparams {
-                let size = cache.associativity()
-                    * cache.physical_line_partitions()
-                    * cache.coherency_line_size()
-                    * cache.sets();
+                let size = cache.size();
 
                 let typ = match cache.cache_type() {
                     CacheType::Data => "Instruction-Cache",
@@ -20,10 +17,9 @@ fn main() {
                     _ => "Unknown cache type",
                 };
 
-                let associativity = if cache.is_fully_associative() {
-                    "fully associative".to_string()
-                } else {
-                    format!("{}-way associativity", cache.associativity())
+                let associativity = match cache.associativity() {
+                    Associativity::Full => "fully associative".to_string(),
+                    other => format!("{} associativity", other),
                 };
 
                 let size_repr = if size > 1024 * 1024 {
@@ -2,7 +2,6 @@
 extern crate raw_cpuid;
 
 #[allow(clippy::option_map_unit_fn)]
-
 fn main() {
     let cpuid = raw_cpuid::CpuId::new();
 
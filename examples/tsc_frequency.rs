@@ -23,11 +23,11 @@ fn main() {
     let cpuid = raw_cpuid::CpuId::new();
     let has_tsc = cpuid
         .get_feature_info()
-        .map_or(false, |finfo| finfo.has_tsc());
+        .is_some_and(|finfo| finfo.has_tsc());
 
     let has_invariant_tsc = cpuid
         .get_advanced_power_mgmt_info()
-        .map_or(false, |efinfo| efinfo.has_invariant_tsc());
+        .is_some_and(|efinfo| efinfo.has_invariant_tsc());
 
     let tsc_frequency_hz = cpuid.get_tsc_info().map(|tinfo| {
         if tinfo.nominal_frequency() != 0 {
@@ -0,0 +1,55 @@
+/*
+ * C interface of the raw-cpuid crate (requires the `ffi` feature).
+ *
+ * See src/ffi.rs for the documentation of the individual functions.
+ * Unknown values are reported as 0.
+ */
+#ifndef RAW_CPUID_H
+#define RAW_CPUID_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Sizes (in bytes) of the caches of the current CPU. */
+typedef struct RawCpuIdCacheSizes {
+    uint64_t l1d;
+    uint64_t l1i;
+    uint64_t l2;
+    uint64_t l3;
+} RawCpuIdCacheSizes;
+
+/* Topology of the physical package of the current CPU. */
+typedef struct RawCpuIdTopology {
+    uint32_t logical_cpus;
+    uint32_t cores;
+    uint32_t threads_per_core;
+    uint32_t apic_id;
+} RawCpuIdTopology;
+
+/*
+ * The string functions write a NUL-terminated (and possibly truncated) string
+ * to buf and return the length of the full string like snprintf. buf may be
+ * NULL to query the required size.
+ */
+size_t raw_cpuid_vendor(char *buf, size_t len);
+size_t raw_cpuid_brand_string(char *buf, size_t len);
+size_t raw_cpuid_features(char *buf, size_t len);
+
+/* name is the flag name used in /proc/cpuinfo, e.g., "avx2". */
+bool raw_cpuid_has_feature(const char *name);
+size_t raw_cpuid_feature_count(void);
+size_t raw_cpuid_feature_name(size_t index, char *buf, size_t len);
+
+RawCpuIdCacheSizes raw_cpuid_cache_sizes(void);
+RawCpuIdTopology raw_cpuid_topology(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RAW_CPUID_H */
@@ -4,7 +4,7 @@ use std::str::FromStr;
 use clap::{AppSettings, Clap};
 use raw_cpuid::{
     cpuid, Associativity, CacheType, CpuId, CpuIdResult, DatType, ExtendedRegisterStateLocation,
-    SgxSectionInfo, SoCVendorBrand, TopologyType,
+    Report, SgxSectionInfo, SoCVendorBrand, TopologyType,
 };
 use termimad::{minimad::TextTemplate, minimad::TextTemplateExpander, MadSkin};
 
@@ -12,6 +12,7 @@ enum OutputFormat {
     Raw,
     Json,
     Cli,
+    Report,
 }
 
 impl FromStr for OutputFormat {
@@ -22,6 +23,7 @@ impl FromStr for OutputFormat {
             "raw" => Ok(OutputFormat::Raw),
             "json" => Ok(OutputFormat::Json),
             "cli" => Ok(OutputFormat::Cli),
+            "report" => Ok(OutputFormat::Report),
             _ => Err("no match"),
         }
     }
@@ -33,7 +35,7 @@ impl FromStr for OutputFormat {
 #[clap(setting = AppSettings::ColoredHelp)]
 struct Opts {
     /// Configures the output format.
-    #[clap(short, long, default_value = "cli", possible_values = &["raw", "json", "cli", ])]
+    #[clap(short, long, default_value = "cli", possible_values = &["raw", "json", "cli", "report"])]
     format: OutputFormat,
 }
 
@@ -43,9 +45,14 @@ fn main() {
         OutputFormat::Raw => raw(opts),
         OutputFormat::Json => json(opts),
         OutputFormat::Cli => markdown(opts),
+        OutputFormat::Report => report(opts),
     };
 }
 
+fn report(_opts: Opts) {
+    print!("{}", Report::new(&CpuId::new()));
+}
+
 fn raw(_opts: Opts) {
     let _leafs_with_subleafs = &[0x04, 0x0d, 0x0f, 0x10, 0x12];
 
@@ -455,7 +462,7 @@ fn markdown(_opts: Opts) {
             &[
                 RowGen::tuple("processor APIC physical id", info.initial_local_apic_id()),
                 RowGen::tuple("max. cpus", info.max_logical_processor_ids()),
-                RowGen::tuple("CLFLUSH line size", info.cflush_cache_line_size()),
+                RowGen::tuple("CLFLUSH line size", info.cache_line_flush_size_bytes()),
                 RowGen::tuple("brand index", info.brand_index()),
             ],
         );
@@ -561,14 +568,12 @@ fn markdown(_opts: Opts) {
     }
 
     if let Some(iter) = cpuid.get_cache_parameters() {
-        print_title(&skin, "deterministic cache parameters (0x04):");
+        let title = format!("deterministic cache parameters ({:#x}):", iter.leaf());
+        print_title(&skin, title.as_str());
         for cache in iter {
             print_subtitle(&skin, format!("L{} Cache:", cache.level()).as_str());
 
-            let size = (cache.associativity()
-                * cache.physical_line_partitions()
-                * cache.coherency_line_size()
-                * cache.sets()) as u64;
+            let size = cache.size() as u64;
 
             table2(
                 &skin,
@@ -584,7 +589,7 @@ fn markdown(_opts: Opts) {
                     RowGen::tuple("processor cores on this die", cache.max_cores_for_package()),
                     RowGen::tuple("system coherency line size", cache.coherency_line_size()),
                     RowGen::tuple("physical line partitions", cache.physical_line_partitions()),
-                    RowGen::tuple("ways of associativity", cache.associativity()),
+                    RowGen::tuple("ways of associativity", cache.ways()),
                     RowGen::tuple(
                         "WBINVD/INVD acts on lower caches",
                         cache.is_write_back_invalidate(),
@@ -824,7 +829,11 @@ fn markdown(_opts: Opts) {
     }
 
     if let Some(info) = cpuid.get_extended_topology_info() {
-        print_title(&skin, "x2APIC features / processor topology (0x0b):");
+        let title = format!(
+            "x2APIC features / processor topology ({:#04x}):",
+            info.leaf()
+        );
+        print_title(&skin, title.as_str());
 
         for level in info {
             print_subtitle(&skin, format!("level {}:", level.level_number()).as_str());
@@ -1310,6 +1319,8 @@ fn markdown(_opts: Opts) {
                 RowGen::tuple("MCA overflow recovery", info.has_mca_overflow_recovery()),
                 RowGen::tuple("SUCCOR", info.has_succor()),
                 RowGen::tuple("HWA: hardware assert", info.has_hwa()),
+                RowGen::tuple("Scalable MCA", info.has_scalable_mca()),
+                RowGen::tuple("PFEH: platform first error handling", info.has_pfeh()),
             ],
         );
 
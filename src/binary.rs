@@ -0,0 +1,148 @@
+//! A compact, versioned binary encoding of snapshots, e.g., to embed them into
+//! crash dumps or telemetry where JSON is too bulky.
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{CpuIdResult, CpuIdSnapshot, SnapshotDecodeError};
+
+/// First four bytes of an encoded snapshot.
+pub const SNAPSHOT_MAGIC: [u8; 4] = *b"RCID";
+
+/// Version of the encoding written by [`CpuIdSnapshot::to_bytes`].
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+const HEADER_LEN: usize = 12;
+const ENTRY_LEN: usize = 24;
+
+impl CpuIdSnapshot {
+    /// Encodes the snapshot in a compact binary format.
+    ///
+    /// All values are little-endian. The encoding consists of a 12-byte
+    /// header followed by one 24-byte entry per leaf, sorted by leaf and
+    /// subleaf:
+    ///
+    /// | Offset | Size | Content                                  |
+    /// |--------|------|------------------------------------------|
+    /// | 0      | 4    | Magic, [`SNAPSHOT_MAGIC`]                |
+    /// | 4      | 2    | Version, [`SNAPSHOT_VERSION`]            |
+    /// | 6      | 2    | Reserved, zero                           |
+    /// | 8      | 4    | Number of entries                        |
+    /// | 12     | 24×n | Leaf, subleaf, EAX, EBX, ECX, EDX (u32s) |
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuIdSnapshot;
+    ///
+    /// let snapshot = CpuIdSnapshot::from_host();
+    /// let bytes = snapshot.to_bytes();
+    /// assert_eq!(CpuIdSnapshot::from_bytes(&bytes), Ok(snapshot));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.len() * ENTRY_LEN);
+        bytes.extend_from_slice(&SNAPSHOT_MAGIC);
+        bytes.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&(self.len() as u32).to_le_bytes());
+
+        for (&(leaf, subleaf), res) in self.leafs() {
+            for value in [leaf, subleaf, res.eax, res.ebx, res.ecx, res.edx] {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+
+        bytes
+    }
+
+    /// Decodes a snapshot encoded with [`CpuIdSnapshot::to_bytes`].
+    ///
+    /// `bytes` must contain exactly one snapshot. If a leaf occurs more than
+    /// once, the last entry wins.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotDecodeError> {
+        let count = decode_header(bytes)?;
+        let entries = &bytes[HEADER_LEN..];
+        let len = count
+            .checked_mul(ENTRY_LEN)
+            .ok_or(SnapshotDecodeError::Truncated)?;
+        if entries.len() < len {
+            return Err(SnapshotDecodeError::Truncated);
+        }
+        if entries.len() > len {
+            return Err(SnapshotDecodeError::TrailingBytes);
+        }
+
+        Ok(decode_entries(entries))
+    }
+
+    /// Writes the binary encoding (see [`CpuIdSnapshot::to_bytes`]) to `w`.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: std::io::Write>(&self, mut w: W) -> std::io::Result<()> {
+        w.write_all(&self.to_bytes())
+    }
+
+    /// Reads a snapshot in the binary encoding from `r`.
+    ///
+    /// Only the bytes of the snapshot are consumed, so it can be followed by
+    /// other data. Invalid encodings are reported with
+    /// [`std::io::ErrorKind::InvalidData`].
+    #[cfg(feature = "std")]
+    pub fn read_from<R: std::io::Read>(mut r: R) -> std::io::Result<Self> {
+        use std::io::Read;
+
+        let invalid =
+            |e: SnapshotDecodeError| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+
+        let mut header = [0u8; HEADER_LEN];
+        r.read_exact(&mut header)?;
+        let count = decode_header(&header).map_err(invalid)?;
+
+        let len = count as u64 * ENTRY_LEN as u64;
+        let mut entries = Vec::new();
+        r.take(len).read_to_end(&mut entries)?;
+        if (entries.len() as u64) < len {
+            return Err(invalid(SnapshotDecodeError::Truncated));
+        }
+
+        Ok(decode_entries(&entries))
+    }
+}
+
+/// Checks the header and returns the number of entries.
+fn decode_header(bytes: &[u8]) -> Result<usize, SnapshotDecodeError> {
+    if bytes.len() < HEADER_LEN {
+        return Err(SnapshotDecodeError::Truncated);
+    }
+    if bytes[0..4] != SNAPSHOT_MAGIC {
+        return Err(SnapshotDecodeError::InvalidMagic);
+    }
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    if version != SNAPSHOT_VERSION {
+        return Err(SnapshotDecodeError::UnsupportedVersion(version));
+    }
+
+    Ok(u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize)
+}
+
+fn decode_entries(entries: &[u8]) -> CpuIdSnapshot {
+    let leafs: BTreeMap<_, _> = entries
+        .chunks_exact(ENTRY_LEN)
+        .map(|entry| {
+            let value = |idx: usize| {
+                u32::from_le_bytes([
+                    entry[idx * 4],
+                    entry[idx * 4 + 1],
+                    entry[idx * 4 + 2],
+                    entry[idx * 4 + 3],
+                ])
+            };
+            let res = CpuIdResult {
+                eax: value(2),
+                ebx: value(3),
+                ecx: value(4),
+                edx: value(5),
+            };
+            ((value(0), value(1)), res)
+        })
+        .collect();
+
+    CpuIdSnapshot::from(leafs)
+}
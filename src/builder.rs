@@ -0,0 +1,458 @@
+//! Synthesize CPUID tables, e.g., to expose a (filtered) view of the host CPU
+//! to a virtual machine.
+use alloc::collections::BTreeMap;
+
+use crate::{
+    get_bits, hypervisor_block_max_leaf, CpuIdReader, CpuIdReaderNative, CpuIdResult, Feature,
+    VendorInfo, EAX_ARCH_PERFMON_EXT_INFO, EAX_AVX10_INFO, EAX_CACHE_PARAMETERS,
+    EAX_CACHE_PARAMETERS_AMD, EAX_CENTAUR_FUNCTION_INFO,
+    EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO, EAX_EXTENDED_BRAND_STRING,
+    EAX_EXTENDED_CPU_TOPOLOGY, EAX_EXTENDED_FUNCTION_INFO, EAX_EXTENDED_STATE_INFO,
+    EAX_EXTENDED_TOPOLOGY_INFO, EAX_EXTENDED_TOPOLOGY_INFO_V2, EAX_FEATURE_INFO, EAX_HRESET_INFO,
+    EAX_HYPERVISOR_INFO, EAX_HYPERVISOR_LAST_BLOCK, EAX_PCONFIG_INFO, EAX_PROCESSOR_CAPACITY_INFO,
+    EAX_RDT_ALLOCATION, EAX_RDT_MONITORING, EAX_SGX, EAX_SOC_VENDOR_INFO,
+    EAX_STRUCTURED_EXTENDED_FEATURE_INFO, EAX_TMUL_INFO, EAX_TRACE_INFO,
+    EAX_TRANSMETA_FUNCTION_INFO, EAX_VENDOR_INFO, HYPERVISOR_BLOCK_SIZE,
+};
+
+/// Upper bound for the number of leafs we read in each leaf region
+/// (basic, hypervisor, extended).
+///
+/// Protects against bogus maximum leaf values (e.g., in a broken hypervisor).
+const MAX_LEAFS_PER_REGION: u32 = 0x100;
+
+/// Upper bound for the number of subleafs we read for a single leaf.
+const MAX_SUBLEAFS: u32 = 64;
+
+/// Calls `f` for every (leaf, subleaf) combination that is reported by `read`.
+///
+/// All hypervisor interfaces are included (e.g., Hyper-V at 0x4000_0000 and
+/// KVM at 0x4000_0100), see [`crate::CpuId::get_hypervisor_interfaces`].
+/// Leafs with subleafs are enumerated according to their leaf specific rules.
+/// For subleafs that are terminated by an invalid entry (e.g., LEAF=0x04 or
+/// LEAF=0x0B), the terminating entry is included as well.
+pub(crate) fn for_each_leaf<R: CpuIdReader, F: FnMut(u32, u32, CpuIdResult)>(read: &R, mut f: F) {
+    let regions = [
+        EAX_VENDOR_INFO,
+        EAX_HYPERVISOR_INFO,
+        EAX_EXTENDED_FUNCTION_INFO,
+        EAX_TRANSMETA_FUNCTION_INFO,
+        EAX_CENTAUR_FUNCTION_INFO,
+    ];
+    for base in regions {
+        let res = read.cpuid1(base);
+        if base == EAX_HYPERVISOR_INFO {
+            for block in (EAX_HYPERVISOR_INFO..=EAX_HYPERVISOR_LAST_BLOCK)
+                .step_by(HYPERVISOR_BLOCK_SIZE as usize)
+            {
+                let res = if block == base {
+                    res
+                } else {
+                    read.cpuid1(block)
+                };
+                if let Some(max) = hypervisor_block_max_leaf(block, &res) {
+                    for leaf in block..=max {
+                        for_each_subleaf(read, leaf, &mut f);
+                    }
+                }
+            }
+            continue;
+        }
+        if base != EAX_VENDOR_INFO
+            && base != EAX_EXTENDED_FUNCTION_INFO
+            && (res.eax < base || res.eax - base >= MAX_LEAFS_PER_REGION)
+        {
+            // No Transmeta (or Centaur) leafs present.
+            continue;
+        }
+        let max = match base {
+            EAX_VENDOR_INFO => core::cmp::min(res.eax, MAX_LEAFS_PER_REGION - 1),
+            _ if res.eax >= base => core::cmp::min(res.eax, base + MAX_LEAFS_PER_REGION - 1),
+            _ => continue,
+        };
+
+        for leaf in base..=max {
+            for_each_subleaf(read, leaf, &mut f);
+        }
+    }
+}
+
+fn for_each_subleaf<R: CpuIdReader, F: FnMut(u32, u32, CpuIdResult)>(
+    read: &R,
+    leaf: u32,
+    f: &mut F,
+) {
+    let res0 = read.cpuid2(leaf, 0);
+    f(leaf, 0, res0);
+
+    match leaf {
+        // Subleafs terminated by an entry with cache type `Null`:
+        EAX_CACHE_PARAMETERS | EAX_CACHE_PARAMETERS_AMD => {
+            if get_bits(res0.eax, 0, 4) == 0 {
+                return;
+            }
+            for subleaf in 1..MAX_SUBLEAFS {
+                let res = read.cpuid2(leaf, subleaf);
+                f(leaf, subleaf, res);
+                if get_bits(res.eax, 0, 4) == 0 {
+                    break;
+                }
+            }
+        }
+        // Subleafs terminated by an entry with level type `Invalid`:
+        EAX_EXTENDED_TOPOLOGY_INFO | EAX_EXTENDED_TOPOLOGY_INFO_V2 | EAX_EXTENDED_CPU_TOPOLOGY => {
+            if get_bits(res0.ecx, 8, 15) == 0 {
+                return;
+            }
+            for subleaf in 1..MAX_SUBLEAFS {
+                let res = read.cpuid2(leaf, subleaf);
+                f(leaf, subleaf, res);
+                if get_bits(res.ecx, 8, 15) == 0 {
+                    break;
+                }
+            }
+        }
+        // Subleafs terminated by an entry with sub-leaf type 0:
+        EAX_SGX => {
+            for subleaf in 1..MAX_SUBLEAFS {
+                let res = read.cpuid2(leaf, subleaf);
+                f(leaf, subleaf, res);
+                if subleaf >= 2 && get_bits(res.eax, 0, 3) == 0 {
+                    break;
+                }
+            }
+        }
+        EAX_PCONFIG_INFO => {
+            if get_bits(res0.eax, 0, 11) == 0 {
+                return;
+            }
+            for subleaf in 1..MAX_SUBLEAFS {
+                let res = read.cpuid2(leaf, subleaf);
+                f(leaf, subleaf, res);
+                if get_bits(res.eax, 0, 11) == 0 {
+                    break;
+                }
+            }
+        }
+        // Valid subleafs are reported as a bitmap in EAX of subleaf 0:
+        EAX_ARCH_PERFMON_EXT_INFO => {
+            for subleaf in 1..32 {
+                if res0.eax & (1 << subleaf) != 0 {
+                    f(leaf, subleaf, read.cpuid2(leaf, subleaf));
+                }
+            }
+        }
+        // Maximum subleaf is reported in EAX of subleaf 0:
+        EAX_STRUCTURED_EXTENDED_FEATURE_INFO
+        | EAX_TRACE_INFO
+        | EAX_SOC_VENDOR_INFO
+        | EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO
+        | EAX_TMUL_INFO
+        | EAX_HRESET_INFO
+        | EAX_AVX10_INFO => {
+            for subleaf in 1..=core::cmp::min(res0.eax, MAX_SUBLEAFS - 1) {
+                f(leaf, subleaf, read.cpuid2(leaf, subleaf));
+            }
+        }
+        // Sparse subleafs, we only keep the ones that are non-zero:
+        EAX_EXTENDED_STATE_INFO | EAX_RDT_MONITORING | EAX_RDT_ALLOCATION => {
+            for subleaf in 1..MAX_SUBLEAFS {
+                let res = read.cpuid2(leaf, subleaf);
+                if !res.all_zero() {
+                    f(leaf, subleaf, res);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Does `leaf` have subleafs (i.e., does its result depend on ECX)?
+///
+/// These are the leafs [`for_each_leaf`] enumerates more than one subleaf for.
+#[cfg(any(test, feature = "kvm"))]
+pub(crate) fn has_subleafs(leaf: u32) -> bool {
+    matches!(
+        leaf,
+        EAX_CACHE_PARAMETERS
+            | EAX_CACHE_PARAMETERS_AMD
+            | EAX_EXTENDED_TOPOLOGY_INFO
+            | EAX_EXTENDED_TOPOLOGY_INFO_V2
+            | EAX_EXTENDED_CPU_TOPOLOGY
+            | EAX_SGX
+            | EAX_PCONFIG_INFO
+            | EAX_STRUCTURED_EXTENDED_FEATURE_INFO
+            | EAX_TRACE_INFO
+            | EAX_SOC_VENDOR_INFO
+            | EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO
+            | EAX_TMUL_INFO
+            | EAX_HRESET_INFO
+            | EAX_ARCH_PERFMON_EXT_INFO
+            | EAX_AVX10_INFO
+            | EAX_EXTENDED_STATE_INFO
+            | EAX_RDT_MONITORING
+            | EAX_RDT_ALLOCATION
+    )
+}
+
+/// Builds a table of CPUID results, e.g., to inject it into a guest VM.
+///
+/// The builder starts either from a blank template ([`CpuIdBuilder::new`]) or
+/// from the leafs of the host ([`CpuIdBuilder::from_host`]). Individual leafs,
+/// feature flags and fields can then be overridden before the final table is
+/// emitted with [`CpuIdBuilder::build`].
+///
+/// # Example
+/// ```rust
+/// use raw_cpuid::{CpuIdBuilder, Feature};
+///
+/// let mut builder = CpuIdBuilder::from_host();
+/// builder
+///     .set_feature(Feature::Hypervisor, true)
+///     .set_feature(Feature::Avx512f, false)
+///     .set_brand_string("Virtual CPU");
+/// let table = builder.build();
+/// assert!(table.contains_key(&(0x0, 0x0)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CpuIdBuilder {
+    leafs: BTreeMap<(u32, u32), CpuIdResult>,
+}
+
+impl CpuIdBuilder {
+    /// Creates a builder without any leafs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a builder that contains all leafs of the CPU we're running on.
+    pub fn from_host() -> Self {
+        Self::with_cpuid_reader(CpuIdReaderNative)
+    }
+
+    /// Creates a builder that contains all leafs reported by `read`.
+    pub fn with_cpuid_reader<R: CpuIdReader>(read: R) -> Self {
+        let mut leafs = BTreeMap::new();
+        for_each_leaf(&read, |leaf, subleaf, res| {
+            leafs.insert((leaf, subleaf), res);
+        });
+        Self { leafs }
+    }
+
+    /// Returns the current value of a leaf.
+    pub fn get(&self, leaf: u32, subleaf: u32) -> Option<CpuIdResult> {
+        self.leafs.get(&(leaf, subleaf)).copied()
+    }
+
+    /// Overrides a leaf with `res`.
+    pub fn set(&mut self, leaf: u32, subleaf: u32, res: CpuIdResult) -> &mut Self {
+        self.leafs.insert((leaf, subleaf), res);
+        self
+    }
+
+    /// Removes a leaf.
+    pub fn remove(&mut self, leaf: u32, subleaf: u32) -> &mut Self {
+        self.leafs.remove(&(leaf, subleaf));
+        self
+    }
+
+    /// Applies `f` to a leaf, a missing leaf is created with all registers set
+    /// to zero.
+    pub fn update<F: FnOnce(&mut CpuIdResult)>(
+        &mut self,
+        leaf: u32,
+        subleaf: u32,
+        f: F,
+    ) -> &mut Self {
+        f(self.leafs.entry((leaf, subleaf)).or_insert(CpuIdResult {
+            eax: 0,
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
+        }));
+        self
+    }
+
+    /// Is `feature` currently set?
+    pub fn has_feature(&self, feature: Feature) -> bool {
+        let loc = feature.location();
+        self.get(loc.leaf, loc.subleaf)
+            .is_some_and(|res| loc.is_set(&res))
+    }
+
+    /// Sets or clears a feature flag.
+    pub fn set_feature(&mut self, feature: Feature, enabled: bool) -> &mut Self {
+        let loc = feature.location();
+        self.update(loc.leaf, loc.subleaf, |res| loc.set(res, enabled))
+    }
+
+    /// Sets the 12-byte vendor identification string (LEAF=0x00), e.g.,
+    /// `b"GenuineIntel"`.
+    pub fn set_vendor(&mut self, vendor: &[u8; 12]) -> &mut Self {
+        let vendor = VendorInfo::from_bytes(vendor).as_cpuid_result();
+        self.update(EAX_VENDOR_INFO, 0, |res| {
+            res.ebx = vendor.ebx;
+            res.edx = vendor.edx;
+            res.ecx = vendor.ecx;
+        })
+    }
+
+    /// Sets the processor brand string (LEAF=0x8000_000{2..4}).
+    ///
+    /// Strings longer than 47 bytes are truncated.
+    pub fn set_brand_string(&mut self, brand: &str) -> &mut Self {
+        let mut bytes = [0u8; 48];
+        let len = core::cmp::min(brand.len(), bytes.len() - 1);
+        bytes[..len].copy_from_slice(&brand.as_bytes()[..len]);
+
+        for (idx, chunk) in bytes.chunks(16).enumerate() {
+            let reg = |offset: usize| {
+                u32::from_le_bytes([
+                    chunk[offset],
+                    chunk[offset + 1],
+                    chunk[offset + 2],
+                    chunk[offset + 3],
+                ])
+            };
+            self.set(
+                EAX_EXTENDED_BRAND_STRING + idx as u32,
+                0,
+                CpuIdResult {
+                    eax: reg(0),
+                    ebx: reg(4),
+                    ecx: reg(8),
+                    edx: reg(12),
+                },
+            );
+        }
+        self
+    }
+
+    /// Sets the (display) family, model and stepping (LEAF=0x01).
+    ///
+    /// The values are split into base and extended fields as described in the
+    /// Intel and AMD manuals.
+    pub fn set_family_model_stepping(&mut self, family: u8, model: u8, stepping: u8) -> &mut Self {
+        let (base_family, extended_family) = if family >= 0xf {
+            (0xf, family - 0xf)
+        } else {
+            (family, 0)
+        };
+        let (base_model, extended_model) = (model & 0xf, model >> 4);
+
+        self.update(EAX_FEATURE_INFO, 0, |res| {
+            res.eax = (res.eax & 0xf000_3000)
+                | (extended_family as u32) << 20
+                | (extended_model as u32) << 16
+                | (base_family as u32) << 8
+                | (base_model as u32) << 4
+                | (stepping as u32 & 0xf);
+        })
+    }
+
+    /// Sets the initial APIC ID (LEAF=0x01) and the x2APIC ID reported by the
+    /// extended topology leafs (LEAF=0x0B, LEAF=0x1F).
+    pub fn set_apic_id(&mut self, apic_id: u32) -> &mut Self {
+        self.update(EAX_FEATURE_INFO, 0, |res| {
+            res.ebx = (res.ebx & 0x00ff_ffff) | (apic_id & 0xff) << 24;
+        });
+        for (&(leaf, _), res) in self.leafs.iter_mut() {
+            if leaf == EAX_EXTENDED_TOPOLOGY_INFO || leaf == EAX_EXTENDED_TOPOLOGY_INFO_V2 {
+                res.edx = apic_id;
+            }
+        }
+        self
+    }
+
+    /// Sets the physical and linear address size in bits (LEAF=0x8000_0008).
+    pub fn set_address_sizes(&mut self, physical_bits: u8, linear_bits: u8) -> &mut Self {
+        self.update(EAX_PROCESSOR_CAPACITY_INFO, 0, |res| {
+            res.eax = (res.eax & 0xffff_0000) | (linear_bits as u32) << 8 | physical_bits as u32;
+        })
+    }
+
+    /// Advertises a hypervisor with the given 12-byte signature, e.g.,
+    /// `b"KVMKVMKVM\0\0\0"` (LEAF=0x4000_0000).
+    ///
+    /// This also sets [`Feature::Hypervisor`].
+    pub fn set_hypervisor(&mut self, signature: &[u8; 12]) -> &mut Self {
+        let reg = |idx: usize| {
+            u32::from_le_bytes([
+                signature[idx],
+                signature[idx + 1],
+                signature[idx + 2],
+                signature[idx + 3],
+            ])
+        };
+        self.set_feature(Feature::Hypervisor, true)
+            .update(EAX_HYPERVISOR_INFO, 0, |res| {
+                res.eax = core::cmp::max(res.eax, EAX_HYPERVISOR_INFO);
+                res.ebx = reg(0);
+                res.ecx = reg(4);
+                res.edx = reg(8);
+            })
+    }
+
+    /// Emits the final table.
+    ///
+    /// The maximum leaf values reported in LEAF=0x00, LEAF=0x8000_0000,
+    /// LEAF=0x8086_0000, LEAF=0xC000_0000 and LEAF=0x07 (maximum subleaf) are
+    /// adjusted to cover all leafs in the table.
+    pub fn build(&self) -> BTreeMap<(u32, u32), CpuIdResult> {
+        let mut leafs = self.leafs.clone();
+
+        let max_leaf = |start: u32, end: u32| {
+            leafs
+                .range((start, 0)..(end, 0))
+                .map(|(&(leaf, _), _)| leaf)
+                .next_back()
+        };
+        let max_basic = max_leaf(EAX_VENDOR_INFO, EAX_HYPERVISOR_INFO);
+        let max_extended = max_leaf(EAX_EXTENDED_FUNCTION_INFO, EAX_TRANSMETA_FUNCTION_INFO);
+        let max_transmeta = max_leaf(
+            EAX_TRANSMETA_FUNCTION_INFO,
+            EAX_TRANSMETA_FUNCTION_INFO + 0x1_0000,
+        );
+        let max_centaur = max_leaf(EAX_CENTAUR_FUNCTION_INFO, u32::MAX);
+        let max_leaf7_subleaf = leafs
+            .range(
+                (EAX_STRUCTURED_EXTENDED_FEATURE_INFO, 0)
+                    ..(EAX_STRUCTURED_EXTENDED_FEATURE_INFO + 1, 0),
+            )
+            .map(|(&(_, subleaf), _)| subleaf)
+            .next_back();
+
+        let zero = CpuIdResult {
+            eax: 0,
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
+        };
+        if let Some(max) = max_basic {
+            let res = leafs.entry((EAX_VENDOR_INFO, 0)).or_insert(zero);
+            res.eax = core::cmp::max(res.eax, max);
+        }
+        if let Some(max) = max_extended {
+            let res = leafs.entry((EAX_EXTENDED_FUNCTION_INFO, 0)).or_insert(zero);
+            res.eax = core::cmp::max(res.eax, max);
+        }
+        if let Some(max) = max_transmeta {
+            let res = leafs
+                .entry((EAX_TRANSMETA_FUNCTION_INFO, 0))
+                .or_insert(zero);
+            res.eax = core::cmp::max(res.eax, max);
+        }
+        if let Some(max) = max_centaur {
+            let res = leafs.entry((EAX_CENTAUR_FUNCTION_INFO, 0)).or_insert(zero);
+            res.eax = core::cmp::max(res.eax, max);
+        }
+        if let Some(max) = max_leaf7_subleaf {
+            let res = leafs
+                .entry((EAX_STRUCTURED_EXTENDED_FEATURE_INFO, 0))
+                .or_insert(zero);
+            res.eax = core::cmp::max(res.eax, max);
+        }
+
+        leafs
+    }
+}
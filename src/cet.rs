@@ -0,0 +1,81 @@
+//! Summarize the Control-flow Enforcement Technology (CET) support: shadow
+//! stacks and indirect branch tracking.
+use crate::{CpuId, CpuIdReader};
+
+/// CET features and the XSAVE state components that hold their state, see
+/// [`CpuId::cet_support`].
+///
+/// # Note
+/// Whether CET is enabled for a process is decided by the OS (e.g., with
+/// `arch_prctl(ARCH_SHSTK_ENABLE)` on Linux) and isn't visible through
+/// `cpuid`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CetSupport {
+    /// Shadow stacks are supported (CET_SS, `CPUID.(EAX=07H,ECX=0):ECX[7]`).
+    pub shadow_stack: bool,
+    /// Indirect branch tracking is supported (CET_IBT,
+    /// `CPUID.(EAX=07H,ECX=0):EDX[20]`).
+    pub indirect_branch_tracking: bool,
+    /// Supervisor shadow stacks can be used (CET_SSS,
+    /// `CPUID.(EAX=07H,ECX=1):EDX[18]`).
+    pub supervisor_shadow_stack: bool,
+    /// XSAVES manages the user-mode CET state (CET_U, `CPUID.(EAX=0DH,ECX=1):ECX[11]`).
+    pub xsave_user_state: bool,
+    /// XSAVES manages the supervisor-mode CET state (CET_S,
+    /// `CPUID.(EAX=0DH,ECX=1):ECX[12]`).
+    pub xsave_supervisor_state: bool,
+}
+
+impl CetSupport {
+    /// Can user-mode code run with shadow stacks?
+    ///
+    /// Besides CET_SS the OS needs XSAVES support for the user-mode CET state
+    /// to switch the shadow stack pointer between tasks.
+    pub fn has_user_shadow_stack(&self) -> bool {
+        self.shadow_stack && self.xsave_user_state
+    }
+
+    /// Can user-mode code run with indirect branch tracking?
+    ///
+    /// ENDBR32 and ENDBR64 execute as NOPs on CPUs without IBT, so emitting
+    /// them is always safe. This tells whether they are enforced (if the OS
+    /// enables IBT).
+    pub fn has_user_ibt(&self) -> bool {
+        self.indirect_branch_tracking && self.xsave_user_state
+    }
+
+    /// Is any CET feature supported?
+    pub fn is_supported(&self) -> bool {
+        self.shadow_stack || self.indirect_branch_tracking
+    }
+}
+
+impl<R: CpuIdReader> CpuId<R> {
+    /// Summarizes the CET support, which is spread over leafs 0x07 and 0x0D.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// let cet = CpuId::new().cet_support();
+    /// if cet.has_user_shadow_stack() {
+    ///     println!("Shadow stacks are available");
+    /// }
+    /// ```
+    ///
+    /// # Platforms
+    /// ✅ AMD (shadow stacks) ✅ Intel
+    pub fn cet_support(&self) -> CetSupport {
+        let features = self.get_extended_feature_info();
+        let state = self.get_extended_state_info();
+
+        CetSupport {
+            shadow_stack: features.as_ref().is_some_and(|f| f.has_cet_ss()),
+            indirect_branch_tracking: features.as_ref().is_some_and(|f| f.has_cet_ibt()),
+            supervisor_shadow_stack: features.as_ref().is_some_and(|f| f.has_cet_sss()),
+            xsave_user_state: state.as_ref().is_some_and(|s| s.ia32_xss_supports_cet_u()),
+            xsave_supervisor_state: state.as_ref().is_some_and(|s| s.ia32_xss_supports_cet_s()),
+        }
+    }
+}
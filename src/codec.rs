@@ -0,0 +1,55 @@
+//! Text encodings of snapshots (JSON, YAML and TOML), e.g., to keep dumps in
+//! a repository and review changes to them.
+//!
+//! All encodings use the serde representation of [`CpuIdSnapshot`], a list of
+//! `(leaf, subleaf, registers)` entries.
+use alloc::string::String;
+
+use crate::CpuIdSnapshot;
+
+impl CpuIdSnapshot {
+    /// Encodes the snapshot as pretty-printed JSON.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuIdSnapshot;
+    ///
+    /// let snapshot = CpuIdSnapshot::from_host();
+    /// let json = snapshot.to_json().unwrap();
+    /// assert_eq!(CpuIdSnapshot::from_json(&json).unwrap(), snapshot);
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Decodes a snapshot from JSON (see [`CpuIdSnapshot::to_json`]).
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Encodes the snapshot as YAML.
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Decodes a snapshot from YAML (see [`CpuIdSnapshot::to_yaml`]).
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+
+    /// Encodes the snapshot as TOML.
+    #[cfg(feature = "toml")]
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        toml::to_string(self)
+    }
+
+    /// Decodes a snapshot from TOML (see [`CpuIdSnapshot::to_toml`]).
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(toml)
+    }
+}
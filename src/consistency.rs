@@ -0,0 +1,125 @@
+//! Check that all cores of a system report the same CPUID information.
+//!
+//! Hybrid CPUs (e.g., with performance and efficiency cores) or firmware bugs
+//! can lead to cores that disagree on supported features.
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{
+    CpuIdDiff, CpuIdResult, CpuIdSnapshot, EAX_EXTENDED_TOPOLOGY_INFO,
+    EAX_EXTENDED_TOPOLOGY_INFO_V2, EAX_FEATURE_INFO, EAX_PROCESSOR_TOPOLOGY_INFO,
+};
+
+/// Differences of all cores compared to a reference core.
+///
+/// Fields that are expected to differ between cores (e.g., the APIC IDs) are
+/// ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ConsistencyReport {
+    /// The core all other cores are compared with.
+    pub reference_core: usize,
+    /// `(core, diff)` for every core that differs from the reference core.
+    pub differences: Vec<(usize, CpuIdDiff)>,
+}
+
+impl ConsistencyReport {
+    /// Compares the `(core, snapshot)` pairs with the first one.
+    ///
+    /// Returns `None` if `snapshots` is empty.
+    pub fn from_snapshots(snapshots: &[(usize, CpuIdSnapshot)]) -> Option<Self> {
+        let ((reference_core, reference), others) = snapshots.split_first()?;
+        let reference = without_per_core_fields(reference);
+
+        let differences = others
+            .iter()
+            .filter_map(|(core, snapshot)| {
+                let diff = reference.diff(&without_per_core_fields(snapshot));
+                (!diff.is_empty()).then_some((*core, diff))
+            })
+            .collect();
+
+        Some(ConsistencyReport {
+            reference_core: *reference_core,
+            differences,
+        })
+    }
+
+    /// Takes a snapshot on every online core and compares them.
+    #[cfg(feature = "std")]
+    pub fn from_all_cores() -> std::io::Result<Self> {
+        let snapshots = CpuIdSnapshot::from_all_cores()?;
+        Self::from_snapshots(&snapshots)
+            .ok_or_else(|| std::io::Error::other("no online cores found"))
+    }
+
+    /// Do all cores report the same information?
+    pub fn is_consistent(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+impl fmt::Display for ConsistencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (core, diff) in &self.differences {
+            writeln!(
+                f,
+                "Core {} (compared to core {}):",
+                core, self.reference_core
+            )?;
+            for line in diff.features.iter() {
+                writeln!(f, "  {}", line)?;
+            }
+            for line in diff.fields.iter() {
+                writeln!(f, "  {}", line)?;
+            }
+            for change in diff.leafs.iter() {
+                writeln!(
+                    f,
+                    "  Leaf {:#x} subleaf {}: {:?} → {:?}",
+                    change.leaf, change.subleaf, change.old, change.new
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl CpuIdSnapshot {
+    /// Takes a snapshot on every online core.
+    ///
+    /// Returns `(core, snapshot)` pairs, see [`crate::CpuId::for_each_core`].
+    pub fn from_all_cores() -> std::io::Result<Vec<(usize, CpuIdSnapshot)>> {
+        let mut snapshots = Vec::new();
+        crate::CpuId::for_each_core(|core, _cpuid| {
+            snapshots.push((core, CpuIdSnapshot::from_host()));
+        })?;
+        Ok(snapshots)
+    }
+}
+
+/// Clears the fields that identify the core a leaf was read on.
+fn without_per_core_fields(snapshot: &CpuIdSnapshot) -> CpuIdSnapshot {
+    let mut leafs = snapshot.leafs().clone();
+    for (&(leaf, _), res) in leafs.iter_mut() {
+        mask_per_core_fields(leaf, res);
+    }
+    CpuIdSnapshot::from(leafs)
+}
+
+fn mask_per_core_fields(leaf: u32, res: &mut CpuIdResult) {
+    match leaf {
+        // Initial APIC ID
+        EAX_FEATURE_INFO => res.ebx &= 0x00ff_ffff,
+        // x2APIC ID
+        EAX_EXTENDED_TOPOLOGY_INFO | EAX_EXTENDED_TOPOLOGY_INFO_V2 => res.edx = 0,
+        // Extended APIC ID, compute unit and node ID
+        EAX_PROCESSOR_TOPOLOGY_INFO => {
+            res.eax = 0;
+            res.ebx &= !0xff;
+            res.ecx &= !0xff;
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,123 @@
+//! Compare the features reported by CPUID with the runtime detection of the
+//! standard library (`std::arch::is_x86_feature_detected!`).
+//!
+//! The standard library only reports features that are usable, e.g., it
+//! reports AVX as missing if the OS didn't enable the AVX state in XCR0,
+//! while CPUID still reports the CPU's capabilities.
+use core::fmt;
+use std::arch::is_x86_feature_detected;
+use std::vec::Vec;
+
+use crate::{CpuId, CpuIdReader, Feature};
+
+/// A feature CPUID and `std::arch::is_x86_feature_detected!` disagree on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectionMismatch {
+    /// The feature.
+    pub feature: Feature,
+    /// Is it reported by CPUID?
+    pub cpuid: bool,
+    /// Is it detected by the standard library?
+    pub std: bool,
+}
+
+impl fmt::Display for DetectionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let yes_no = |b: bool| if b { "yes" } else { "no" };
+        write!(
+            f,
+            "{}: cpuid={}, std={}",
+            self.feature.name(),
+            yes_no(self.cpuid),
+            yes_no(self.std)
+        )
+    }
+}
+
+macro_rules! std_detected {
+    ($($feature:ident => $name:tt),* $(,)?) => {
+        [$((Feature::$feature, is_x86_feature_detected!($name))),*]
+    };
+}
+
+/// Features known to both this library and the standard library, and whether
+/// the standard library detects them on the CPU we're running on.
+pub fn std_detected_features() -> Vec<(Feature, bool)> {
+    std_detected!(
+        Fxsr => "fxsr",
+        Mmx => "mmx",
+        Sse => "sse",
+        Sse2 => "sse2",
+        Sse3 => "sse3",
+        Ssse3 => "ssse3",
+        Sse41 => "sse4.1",
+        Sse42 => "sse4.2",
+        Sse4a => "sse4a",
+        Popcnt => "popcnt",
+        Lzcnt => "lzcnt",
+        Tbm => "tbm",
+        Bmi1 => "bmi1",
+        Bmi2 => "bmi2",
+        Adx => "adx",
+        Movbe => "movbe",
+        Cmpxchg16b => "cmpxchg16b",
+        Tsc => "tsc",
+        Rdrand => "rdrand",
+        Rdseed => "rdseed",
+        Aesni => "aes",
+        Pclmulqdq => "pclmulqdq",
+        Sha => "sha",
+        Xsave => "xsave",
+        Avx => "avx",
+        Avx2 => "avx2",
+        Fma => "fma",
+        F16c => "f16c",
+        Avx512f => "avx512f",
+        Avx512cd => "avx512cd",
+        Avx512bw => "avx512bw",
+        Avx512dq => "avx512dq",
+        Avx512vl => "avx512vl",
+        Avx512ifma => "avx512ifma",
+        Avx512vbmi => "avx512vbmi",
+        Avx512vbmi2 => "avx512vbmi2",
+        Avx512vnni => "avx512vnni",
+        Avx512bitalg => "avx512bitalg",
+        Avx512vpopcntdq => "avx512vpopcntdq",
+        Gfni => "gfni",
+        Vaes => "vaes",
+        Vpclmulqdq => "vpclmulqdq",
+    )
+    .to_vec()
+}
+
+impl<R: CpuIdReader> CpuId<R> {
+    /// Features this CPU reports that the standard library doesn't detect, or
+    /// the other way around (see [`std_detected_features`]).
+    ///
+    /// A feature reported by CPUID but not detected by the standard library
+    /// is usually disabled by the OS (e.g., the AVX or AVX-512 state isn't
+    /// enabled in XCR0) and can't be used. This is only meaningful if `self`
+    /// reads from the CPU we're running on.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// for mismatch in CpuId::new().std_detection_mismatches() {
+    ///     println!("{}", mismatch);
+    /// }
+    /// ```
+    pub fn std_detection_mismatches(&self) -> Vec<DetectionMismatch> {
+        std_detected_features()
+            .into_iter()
+            .filter_map(|(feature, std)| {
+                let cpuid = self.has_feature(feature);
+                (cpuid != std).then_some(DetectionMismatch {
+                    feature,
+                    cpuid,
+                    std,
+                })
+            })
+            .collect()
+    }
+}
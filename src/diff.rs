@@ -0,0 +1,271 @@
+//! Compare two [`CpuIdSnapshot`]s, e.g., to find out why a virtual machine lost
+//! a feature after it was migrated to another host.
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{CpuIdResult, CpuIdSnapshot, Feature};
+
+/// A feature flag that differs between two snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FeatureChange {
+    /// The flag is only set in the new snapshot.
+    Added(Feature),
+    /// The flag is only set in the old snapshot.
+    Removed(Feature),
+}
+
+impl FeatureChange {
+    /// The feature that changed.
+    pub fn feature(&self) -> Feature {
+        match *self {
+            FeatureChange::Added(feature) => feature,
+            FeatureChange::Removed(feature) => feature,
+        }
+    }
+}
+
+impl fmt::Display for FeatureChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = self.feature().name().to_ascii_uppercase();
+        match self {
+            FeatureChange::Added(_) => write!(f, "{}: absent → present", name),
+            FeatureChange::Removed(_) => write!(f, "{}: present → absent", name),
+        }
+    }
+}
+
+/// A decoded field (e.g., the L3 cache size) that differs between two
+/// snapshots.
+///
+/// Values are formatted for humans; `None` means the field isn't reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct FieldChange {
+    /// Name of the field.
+    pub name: &'static str,
+    /// Value in the old snapshot.
+    pub old: Option<String>,
+    /// Value in the new snapshot.
+    pub new: Option<String>,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} → {}",
+            self.name,
+            self.old.as_deref().unwrap_or("n/a"),
+            self.new.as_deref().unwrap_or("n/a")
+        )
+    }
+}
+
+/// A raw `(leaf, subleaf)` entry that differs between two snapshots.
+///
+/// `None` means the entry is not part of the snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LeafChange {
+    /// Leaf (EAX input value).
+    pub leaf: u32,
+    /// Subleaf (ECX input value).
+    pub subleaf: u32,
+    /// Value in the old snapshot.
+    pub old: Option<CpuIdResult>,
+    /// Value in the new snapshot.
+    pub new: Option<CpuIdResult>,
+}
+
+/// Differences between two snapshots as returned by [`CpuIdSnapshot::diff`].
+///
+/// The `Display` implementation prints one change per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CpuIdDiff {
+    /// Feature flags that were added or removed.
+    pub features: Vec<FeatureChange>,
+    /// Decoded fields with different values.
+    pub fields: Vec<FieldChange>,
+    /// All raw entries with different values.
+    pub leafs: Vec<LeafChange>,
+}
+
+impl CpuIdDiff {
+    /// Returns `true` if both snapshots were identical.
+    pub fn is_empty(&self) -> bool {
+        self.leafs.is_empty()
+    }
+
+    /// Features that are only present in the new snapshot.
+    pub fn added_features(&self) -> impl Iterator<Item = Feature> + '_ {
+        self.features.iter().filter_map(|change| match change {
+            FeatureChange::Added(feature) => Some(*feature),
+            FeatureChange::Removed(_) => None,
+        })
+    }
+
+    /// Features that are only present in the old snapshot.
+    pub fn removed_features(&self) -> impl Iterator<Item = Feature> + '_ {
+        self.features.iter().filter_map(|change| match change {
+            FeatureChange::Removed(feature) => Some(*feature),
+            FeatureChange::Added(_) => None,
+        })
+    }
+}
+
+impl fmt::Display for CpuIdDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for change in &self.features {
+            writeln!(f, "{}", change)?;
+        }
+        for change in &self.fields {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+impl CpuIdSnapshot {
+    /// Compares this (old) snapshot with `other` (new).
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{CpuIdBuilder, CpuIdSnapshot, Feature};
+    ///
+    /// let host = CpuIdSnapshot::from_host();
+    /// let mut builder = CpuIdBuilder::with_cpuid_reader(&host);
+    /// builder.set_feature(Feature::Sse2, false);
+    /// let guest = CpuIdSnapshot::from(builder.build());
+    ///
+    /// let diff = host.diff(&guest);
+    /// assert_eq!(diff.removed_features().collect::<Vec<_>>(), [Feature::Sse2]);
+    /// assert_eq!(diff.to_string(), "SSE2: present → absent\n");
+    /// ```
+    pub fn diff(&self, other: &CpuIdSnapshot) -> CpuIdDiff {
+        let mut diff = CpuIdDiff::default();
+
+        for feature in Feature::ALL {
+            let loc = feature.location();
+            let is_set = |snapshot: &CpuIdSnapshot| {
+                snapshot
+                    .get(loc.leaf, loc.subleaf)
+                    .is_some_and(|res| loc.is_set(&res))
+            };
+            match (is_set(self), is_set(other)) {
+                (false, true) => diff.features.push(FeatureChange::Added(*feature)),
+                (true, false) => diff.features.push(FeatureChange::Removed(*feature)),
+                _ => {}
+            }
+        }
+
+        let old = fields(self);
+        let new = fields(other);
+        for ((name, old), (_, new)) in old.into_iter().zip(new) {
+            if old != new {
+                diff.fields.push(FieldChange { name, old, new });
+            }
+        }
+
+        let keys = self.leafs().keys().chain(other.leafs().keys());
+        let mut keys: Vec<(u32, u32)> = keys.copied().collect();
+        keys.sort_unstable();
+        keys.dedup();
+        for (leaf, subleaf) in keys {
+            let old = self.get(leaf, subleaf);
+            let new = other.get(leaf, subleaf);
+            if old != new {
+                diff.leafs.push(LeafChange {
+                    leaf,
+                    subleaf,
+                    old,
+                    new,
+                });
+            }
+        }
+
+        diff
+    }
+}
+
+/// Formats a size given in bytes using the largest unit that represents it
+/// exactly (e.g., "32 KiB" or "1280 KiB").
+pub(crate) fn format_size(bytes: u64) -> String {
+    crate::ByteSize(bytes).to_string()
+}
+
+/// The decoded fields we compare, in display order.
+fn fields(snapshot: &CpuIdSnapshot) -> [(&'static str, Option<String>); 17] {
+    let cpuid = &snapshot.cpuid();
+    let finfo = cpuid.get_feature_info();
+    let capacity = cpuid.get_processor_capacity_feature_info();
+
+    let [l1d, l1i, l2, l3] = cpuid.cache_sizes();
+
+    [
+        (
+            "Vendor",
+            cpuid.get_vendor_info().map(|vi| vi.as_str().to_string()),
+        ),
+        (
+            "Brand string",
+            cpuid
+                .get_processor_brand_string()
+                .map(|bs| bs.as_str().to_string()),
+        ),
+        (
+            "Family",
+            finfo.as_ref().map(|f| format!("{:#x}", f.family_id())),
+        ),
+        (
+            "Model",
+            finfo.as_ref().map(|f| format!("{:#x}", f.model_id())),
+        ),
+        (
+            "Stepping",
+            finfo.as_ref().map(|f| format!("{:#x}", f.stepping_id())),
+        ),
+        (
+            "Max basic leaf",
+            Some(format!("{:#x}", cpuid.max_basic_leaf())),
+        ),
+        (
+            "Max extended leaf",
+            cpuid.max_extended_leaf().map(|max| format!("{:#x}", max)),
+        ),
+        (
+            "Logical CPUs",
+            cpuid.num_logical_cpus().map(|n| n.to_string()),
+        ),
+        ("Cores", cpuid.num_cores().map(|n| n.to_string())),
+        (
+            "Threads per core",
+            cpuid.threads_per_core().map(|n| n.to_string()),
+        ),
+        ("L1d size", l1d.map(format_size)),
+        ("L1i size", l1i.map(format_size)),
+        ("L2 size", l2.map(format_size)),
+        ("L3 size", l3.map(format_size)),
+        (
+            "Physical address bits",
+            capacity
+                .as_ref()
+                .map(|c| c.physical_address_bits().to_string()),
+        ),
+        (
+            "Linear address bits",
+            capacity
+                .as_ref()
+                .map(|c| c.linear_address_bits().to_string()),
+        ),
+        (
+            "Hypervisor",
+            cpuid
+                .get_hypervisor_info()
+                .map(|hv| format!("{:?}", hv.identify())),
+        ),
+    ]
+}
@@ -0,0 +1,131 @@
+//! Errors reported by the fallible functions of this library.
+use core::fmt;
+
+use crate::Feature;
+
+/// Why CPUID information couldn't be obtained.
+///
+/// Returned by [`CpuId::try_new`](crate::CpuId::try_new),
+/// [`CpuId::read_leaf`](crate::CpuId::read_leaf) and the `try_get_*`
+/// accessors of [`CpuId`](crate::CpuId).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum CpuIdError {
+    /// The CPU doesn't implement the `cpuid` instruction (e.g., i386 and
+    /// early i486 processors).
+    Unavailable,
+    /// The leaf is larger than the maximum leaf the CPU reports for its
+    /// range (basic, hypervisor or extended leafs).
+    LeafUnsupported {
+        /// The requested leaf.
+        leaf: u32,
+    },
+    /// The leaf is below the maximum leaf, but the vendor of this CPU doesn't
+    /// implement it (e.g., leafs reserved on AMD).
+    VendorMismatch {
+        /// The requested leaf.
+        leaf: u32,
+    },
+    /// The leaf is only valid if a feature flag is set, which it isn't.
+    FeatureMissing {
+        /// The requested leaf.
+        leaf: u32,
+        /// The feature flag that gates the leaf.
+        feature: Feature,
+    },
+    /// The leaf is supported, but reports no or invalid data (e.g., all
+    /// zeros).
+    InvalidData {
+        /// The requested leaf.
+        leaf: u32,
+    },
+}
+
+impl fmt::Display for CpuIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CpuIdError::Unavailable => f.write_str("the cpuid instruction is not available"),
+            CpuIdError::LeafUnsupported { leaf } => {
+                write!(f, "leaf {:#x} is not supported by this CPU", leaf)
+            }
+            CpuIdError::VendorMismatch { leaf } => {
+                write!(f, "leaf {:#x} is not implemented by this vendor", leaf)
+            }
+            CpuIdError::FeatureMissing { leaf, feature } => {
+                write!(f, "leaf {:#x} requires the {} feature", leaf, feature)
+            }
+            CpuIdError::InvalidData { leaf } => {
+                write!(f, "leaf {:#x} reports invalid data", leaf)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CpuIdError {}
+
+/// Why [`CpuIdSnapshot::from_bytes`](crate::CpuIdSnapshot::from_bytes)
+/// couldn't decode a snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SnapshotDecodeError {
+    /// The data doesn't start with
+    /// [`SNAPSHOT_MAGIC`](crate::SNAPSHOT_MAGIC).
+    InvalidMagic,
+    /// The data was written by an unknown version of the encoding.
+    UnsupportedVersion(u16),
+    /// The data ends before the header or an entry is complete.
+    Truncated,
+    /// There are more bytes than the header announces.
+    TrailingBytes,
+}
+
+impl fmt::Display for SnapshotDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotDecodeError::InvalidMagic => f.write_str("not an encoded CPUID snapshot"),
+            SnapshotDecodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {}", version)
+            }
+            SnapshotDecodeError::Truncated => f.write_str("snapshot is truncated"),
+            SnapshotDecodeError::TrailingBytes => f.write_str("trailing bytes after snapshot"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SnapshotDecodeError {}
+
+/// Why a snapshot couldn't be converted to a `kvm_bindings::CpuId`.
+#[cfg(all(feature = "kvm", target_arch = "x86_64"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum KvmCpuIdError {
+    /// The snapshot has more entries than KVM accepts
+    /// (`kvm_bindings::KVM_MAX_CPUID_ENTRIES`).
+    TooManyEntries {
+        /// Number of entries of the snapshot.
+        entries: usize,
+        /// Maximum number of entries.
+        max: usize,
+    },
+}
+
+#[cfg(all(feature = "kvm", target_arch = "x86_64"))]
+impl fmt::Display for KvmCpuIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            KvmCpuIdError::TooManyEntries { entries, max } => {
+                write!(
+                    f,
+                    "{} CPUID entries exceed the KVM limit of {}",
+                    entries, max
+                )
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "kvm", target_arch = "x86_64", feature = "std"))]
+impl std::error::Error for KvmCpuIdError {}
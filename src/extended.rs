@@ -1,16 +1,16 @@
 //! Data-structures / interpretation for extended leafs (>= 0x8000_0000)
-use core::fmt::{self, Debug, Display, Formatter};
-use core::mem::size_of;
-use core::slice;
+use core::fmt::{self, Debug, Display, Formatter, Write};
+use core::ops::RangeInclusive;
 use core::str;
 
-use crate::{get_bits, CpuIdResult, Vendor};
+use crate::{get_bits, set_bits, CacheDescription, CacheType, CpuIdResult, Vendor, VendorInfo};
 
 /// Extended Processor and Processor Feature Identifiers (LEAF=0x8000_0001)
 ///
 /// # Platforms
 /// ✅ AMD 🟡 Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ExtendedProcessorFeatureIdentifiers {
     vendor: Vendor,
     eax: u32,
@@ -25,11 +25,17 @@ impl ExtendedProcessorFeatureIdentifiers {
             vendor,
             eax: data.eax,
             ebx: data.ebx,
-            ecx: ExtendedFunctionInfoEcx::from_bits_truncate(data.ecx),
-            edx: ExtendedFunctionInfoEdx::from_bits_truncate(data.edx),
+            ecx: ExtendedFunctionInfoEcx { bits: data.ecx },
+            edx: ExtendedFunctionInfoEdx { bits: data.edx },
         }
     }
 
+    /// Decodes the registers of LEAF=0x8000_0001, the vendor decides how
+    /// vendor specific bits are interpreted.
+    pub fn from_registers(vendor: &VendorInfo, data: CpuIdResult) -> Self {
+        Self::new(Vendor::from_vendor_leaf(vendor.as_cpuid_result()), data)
+    }
+
     /// Extended Processor Signature.
     ///
     /// # AMD
@@ -214,6 +220,28 @@ impl ExtendedProcessorFeatureIdentifiers {
         self.vendor == Vendor::Amd && self.ecx.contains(ExtendedFunctionInfoEcx::FMA4)
     }
 
+    /// Translation cache extension support.
+    ///
+    /// Indicates that `EFER.TCE` can be set to make INVLPG only invalidate
+    /// the upper-level page table entries needed for the given address.
+    ///
+    /// # Platform
+    /// ✅ AMD ❌ Intel (will return false)
+    pub fn has_tce(&self) -> bool {
+        self.vendor == Vendor::Amd && self.ecx.contains(ExtendedFunctionInfoEcx::TCE)
+    }
+
+    /// Node ID MSR support.
+    ///
+    /// Indicates support for `MSRC001_100C` (NodeId) on older processor
+    /// families.
+    ///
+    /// # Platform
+    /// ✅ AMD ❌ Intel (will return false)
+    pub fn has_node_id_msr(&self) -> bool {
+        self.vendor == Vendor::Amd && self.ecx.contains(ExtendedFunctionInfoEcx::NODEID_MSR)
+    }
+
     /// Trailing bit manipulation instruction support.
     ///
     /// # Platform
@@ -367,6 +395,225 @@ impl ExtendedProcessorFeatureIdentifiers {
     pub fn has_3dnow(&self) -> bool {
         self.vendor == Vendor::Amd && self.edx.contains(ExtendedFunctionInfoEdx::THREEDNOW)
     }
+
+    /// Encode this struct back into the registers of leaf 0x8000_0001.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx.bits(),
+            edx: self.edx.bits(),
+        }
+    }
+
+    /// Bits set in ECX and EDX that this library doesn't interpret, i.e.,
+    /// reserved bits or flags it doesn't know about yet.
+    ///
+    /// On AMD, this includes the flags of EDX that mirror the ones of
+    /// LEAF=0x01. EAX and EBX only contain values and always read as zero.
+    pub fn reserved_bits(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: 0,
+            ebx: 0,
+            ecx: self.ecx.bits() & !ExtendedFunctionInfoEcx::all().bits(),
+            edx: self.edx.bits() & !ExtendedFunctionInfoEdx::all().bits(),
+        }
+    }
+
+    /// Set the value reported by [`Self::extended_signature`].
+    pub fn set_extended_signature(&mut self, value: u32) {
+        self.eax = value;
+    }
+
+    /// Set the value reported by [`Self::pkg_type`].
+    pub fn set_pkg_type(&mut self, value: u32) {
+        set_bits(&mut self.ebx, 28, 31, value);
+    }
+
+    /// Set the value reported by [`Self::brand_id`].
+    pub fn set_brand_id(&mut self, value: u32) {
+        set_bits(&mut self.ebx, 0, 15, value);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_lahf_sahf`].
+    pub fn set_lahf_sahf(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::LAHF_SAHF, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_cmp_legacy`].
+    pub fn set_cmp_legacy(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::CMP_LEGACY, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_svm`].
+    pub fn set_svm(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::SVM, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ext_apic_space`].
+    pub fn set_ext_apic_space(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::EXT_APIC_SPACE, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_alt_mov_cr8`].
+    pub fn set_alt_mov_cr8(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::ALTMOVCR8, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_lzcnt`].
+    pub fn set_lzcnt(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::LZCNT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_sse4a`].
+    pub fn set_sse4a(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::SSE4A, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_misaligned_sse_mode`].
+    pub fn set_misaligned_sse_mode(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::MISALIGNSSE, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_prefetchw`].
+    pub fn set_prefetchw(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::PREFETCHW, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_osvw`].
+    pub fn set_osvw(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::OSVW, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ibs`].
+    pub fn set_ibs(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::IBS, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_xop`].
+    pub fn set_xop(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::XOP, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_skinit`].
+    pub fn set_skinit(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::SKINIT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_wdt`].
+    pub fn set_wdt(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::WDT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_lwp`].
+    pub fn set_lwp(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::LWP, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_fma4`].
+    pub fn set_fma4(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::FMA4, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_tce`].
+    pub fn set_tce(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::TCE, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_node_id_msr`].
+    pub fn set_node_id_msr(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::NODEID_MSR, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_tbm`].
+    pub fn set_tbm(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::TBM, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_topology_extensions`].
+    pub fn set_topology_extensions(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::TOPEXT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_perf_cntr_extensions`].
+    pub fn set_perf_cntr_extensions(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::PERFCTREXT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_nb_perf_cntr_extensions`].
+    pub fn set_nb_perf_cntr_extensions(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::PERFCTREXTNB, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_data_access_bkpt_extension`].
+    pub fn set_data_access_bkpt_extension(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::DATABRKPEXT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_perf_tsc`].
+    pub fn set_perf_tsc(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::PERFTSC, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_perf_cntr_llc_extensions`].
+    pub fn set_perf_cntr_llc_extensions(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::PERFCTREXTLLC, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_monitorx_mwaitx`].
+    pub fn set_monitorx_mwaitx(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::MONITORX, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_addr_mask_extension`].
+    pub fn set_addr_mask_extension(&mut self, bit: bool) {
+        self.ecx.set(ExtendedFunctionInfoEcx::ADDRMASKEXT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_syscall_sysret`].
+    pub fn set_syscall_sysret(&mut self, bit: bool) {
+        self.edx.set(ExtendedFunctionInfoEdx::SYSCALL_SYSRET, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_execute_disable`].
+    pub fn set_execute_disable(&mut self, bit: bool) {
+        self.edx.set(ExtendedFunctionInfoEdx::EXECUTE_DISABLE, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_mmx_extensions`].
+    pub fn set_mmx_extensions(&mut self, bit: bool) {
+        self.edx.set(ExtendedFunctionInfoEdx::MMXEXT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_fast_fxsave_fxstor`].
+    pub fn set_fast_fxsave_fxstor(&mut self, bit: bool) {
+        self.edx.set(ExtendedFunctionInfoEdx::FFXSR, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_1gib_pages`].
+    pub fn set_1gib_pages(&mut self, bit: bool) {
+        self.edx.set(ExtendedFunctionInfoEdx::GIB_PAGES, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_rdtscp`].
+    pub fn set_rdtscp(&mut self, bit: bool) {
+        self.edx.set(ExtendedFunctionInfoEdx::RDTSCP, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_64bit_mode`].
+    pub fn set_64bit_mode(&mut self, bit: bool) {
+        self.edx.set(ExtendedFunctionInfoEdx::I64BIT_MODE, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_amd_3dnow_extensions`].
+    pub fn set_amd_3dnow_extensions(&mut self, bit: bool) {
+        self.edx.set(ExtendedFunctionInfoEdx::THREEDNOWEXT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_3dnow`].
+    pub fn set_3dnow(&mut self, bit: bool) {
+        self.edx.set(ExtendedFunctionInfoEdx::THREEDNOW, bit);
+    }
 }
 
 impl Debug for ExtendedProcessorFeatureIdentifiers {
@@ -385,7 +632,7 @@ impl Debug for ExtendedProcessorFeatureIdentifiers {
 }
 
 bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     struct ExtendedFunctionInfoEcx: u32 {
         const LAHF_SAHF = 1 << 0;
         const CMP_LEGACY =  1 << 1;
@@ -403,6 +650,8 @@ bitflags! {
         const WDT = 1 << 13;
         const LWP = 1 << 15;
         const FMA4 = 1 << 16;
+        const TCE = 1 << 17;
+        const NODEID_MSR = 1 << 19;
         const TBM = 1 << 21;
         const TOPEXT = 1 << 22;
         const PERFCTREXT = 1 << 23;
@@ -416,7 +665,7 @@ bitflags! {
 }
 
 bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     struct ExtendedFunctionInfoEdx: u32 {
         const SYSCALL_SYSRET = 1 << 11;
         const EXECUTE_DISABLE = 1 << 20;
@@ -436,13 +685,15 @@ bitflags! {
 ///
 /// # Platforms
 /// ✅ AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProcessorBrandString {
     data: [CpuIdResult; 3],
 }
 
 impl ProcessorBrandString {
-    pub(crate) fn new(data: [CpuIdResult; 3]) -> Self {
+    /// Decodes the registers of LEAF=0x8000_0002 to LEAF=0x8000_0004.
+    pub fn from_registers(data: [CpuIdResult; 3]) -> Self {
         Self { data }
     }
 
@@ -450,22 +701,91 @@ impl ProcessorBrandString {
     ///
     /// For example:
     /// "11th Gen Intel(R) Core(TM) i7-1165G7 @ 2.80GHz".
+    ///
+    /// The string borrows the 48 bytes stored in this struct, so this doesn't
+    /// allocate and works in `no_std` environments. Leading and trailing
+    /// padding (NUL bytes and spaces) is removed.
+    ///
+    /// Returns "Invalid Processor Brand String" if the brand isn't valid
+    /// UTF-8, use [`ProcessorBrandString::brand_bytes`] or the `Display`
+    /// implementation (which replaces invalid bytes with U+FFFD) instead.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// if let Some(brand) = CpuId::new().get_processor_brand_string() {
+    ///     // Same as `brand.as_str()`:
+    ///     println!("CPU: {}", brand);
+    /// }
+    /// ```
     pub fn as_str(&self) -> &str {
-        // Safety: CpuIdResult is laid out with repr(C), and the array
-        // self.data contains 3 contiguous elements.
-        let slice: &[u8] = unsafe {
-            slice::from_raw_parts(
-                self.data.as_ptr() as *const u8,
-                self.data.len() * size_of::<CpuIdResult>(),
-            )
-        };
-
-        // Brand terminated at nul byte or end, whichever comes first.
-        let slice = slice.split(|&x| x == 0).next().unwrap();
-        str::from_utf8(slice)
+        str::from_utf8(self.brand_bytes())
             .unwrap_or("Invalid Processor Brand String")
             .trim()
     }
+
+    /// All 48 bytes of the brand string as reported by the CPU, including
+    /// padding.
+    pub fn as_bytes(&self) -> &[u8; 48] {
+        // Safety: CpuIdResult is laid out with repr(C) without padding, and
+        // the array self.data contains 3 contiguous elements.
+        unsafe { &*(&self.data as *const [CpuIdResult; 3] as *const [u8; 48]) }
+    }
+
+    /// The bytes of the brand without padding, also if they aren't valid
+    /// UTF-8 (e.g., on engineering samples or with buggy hypervisors).
+    pub fn brand_bytes(&self) -> &[u8] {
+        let bytes = self.as_bytes();
+        // Some CPUs right-justify the brand with leading padding. The brand is
+        // terminated at the next nul byte or end, whichever comes first.
+        let start = bytes
+            .iter()
+            .position(|&x| x != 0 && x != b' ')
+            .unwrap_or(bytes.len());
+        let bytes = bytes[start..].split(|&x| x == 0).next().unwrap();
+        let end = bytes
+            .iter()
+            .rposition(|x| !x.is_ascii_whitespace())
+            .map_or(0, |end| end + 1);
+        &bytes[..end]
+    }
+
+    /// The brand with invalid UTF-8 sequences replaced by U+FFFD, see
+    /// [`String::from_utf8_lossy`](alloc::string::String::from_utf8_lossy).
+    #[cfg(any(test, feature = "alloc"))]
+    pub fn to_string_lossy(&self) -> alloc::borrow::Cow<'_, str> {
+        alloc::string::String::from_utf8_lossy(self.brand_bytes())
+    }
+
+    /// Nominal frequency in Hz as stated at the end of the brand string, e.g.,
+    /// 3.6 GHz for "Intel(R) Core(TM) i7-4790 CPU @ 3.60GHz".
+    ///
+    /// Returns `None` if the brand string doesn't end with a frequency (most
+    /// AMD CPUs). This is the fallback Linux uses for CPUs without leaf 0x16,
+    /// see also [`CpuId::frequency`](crate::CpuId::frequency).
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// let cpuid = CpuId::new();
+    /// if let Some(hz) = cpuid
+    ///     .get_processor_brand_string()
+    ///     .and_then(|brand| brand.frequency())
+    /// {
+    ///     println!("Nominal frequency: {} MHz", hz / 1_000_000);
+    /// }
+    /// ```
+    pub fn frequency(&self) -> Option<u64> {
+        crate::frequency::parse_brand_frequency(self.as_str())
+    }
+
+    /// Encode the brand string back into the registers of leafs 0x8000_0002 to
+    /// 0x8000_0004.
+    pub fn as_cpuid_results(&self) -> [CpuIdResult; 3] {
+        self.data
+    }
 }
 
 impl Debug for ProcessorBrandString {
@@ -476,12 +796,37 @@ impl Debug for ProcessorBrandString {
     }
 }
 
+/// Writes the brand (see [`ProcessorBrandString::as_str`]), invalid UTF-8
+/// sequences are replaced by U+FFFD.
+impl Display for ProcessorBrandString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut bytes = self.brand_bytes();
+        loop {
+            match str::from_utf8(bytes) {
+                Ok(valid) => return f.write_str(valid),
+                Err(error) => {
+                    let (valid, rest) = bytes.split_at(error.valid_up_to());
+                    f.write_str(str::from_utf8(valid).unwrap_or_default())?;
+                    f.write_char(char::REPLACEMENT_CHARACTER)?;
+                    bytes = &rest[error.error_len().unwrap_or(rest.len())..];
+                }
+            }
+        }
+    }
+}
+
+impl AsRef<str> for ProcessorBrandString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 /// L1 Cache and TLB Information (LEAF=0x8000_0005).
 ///
 /// # Availability
 /// ✅ AMD ❌ Intel (reserved=0)
 #[derive(PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct L1CacheTlbInfo {
     eax: u32,
     ebx: u32,
@@ -490,7 +835,8 @@ pub struct L1CacheTlbInfo {
 }
 
 impl L1CacheTlbInfo {
-    pub(crate) fn new(data: CpuIdResult) -> Self {
+    /// Decodes the registers of LEAF=0x8000_0005.
+    pub fn from_registers(data: CpuIdResult) -> Self {
         Self {
             eax: data.eax,
             ebx: data.ebx,
@@ -592,6 +938,91 @@ impl L1CacheTlbInfo {
     pub fn icache_line_size(&self) -> u8 {
         get_bits(self.edx, 0, 7) as u8
     }
+
+    /// Summary of the L1 data cache, e.g. `L1 Data, 32 KiB, 8-way, 64 B lines`.
+    pub fn dcache_description(&self) -> CacheDescription {
+        CacheDescription {
+            level: 1,
+            cache_type: CacheType::Data,
+            size: self.dcache_size() as u64 * 1024,
+            associativity: self.dcache_associativity(),
+            line_size: self.dcache_line_size() as u16,
+            shared_by: None,
+        }
+    }
+
+    /// Summary of the L1 instruction cache, e.g.
+    /// `L1 Instruction, 32 KiB, 8-way, 64 B lines`.
+    pub fn icache_description(&self) -> CacheDescription {
+        CacheDescription {
+            level: 1,
+            cache_type: CacheType::Instruction,
+            size: self.icache_size() as u64 * 1024,
+            associativity: self.icache_associativity(),
+            line_size: self.icache_line_size() as u16,
+            shared_by: None,
+        }
+    }
+
+    /// Encode this struct back into the registers of leaf 0x8000_0005.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
+
+    /// Set the value reported by [`Self::dtlb_2m_4m_size`].
+    pub fn set_dtlb_2m_4m_size(&mut self, value: u8) {
+        set_bits(&mut self.eax, 16, 23, value as u32);
+    }
+
+    /// Set the value reported by [`Self::itlb_2m_4m_size`].
+    pub fn set_itlb_2m_4m_size(&mut self, value: u8) {
+        set_bits(&mut self.eax, 0, 7, value as u32);
+    }
+
+    /// Set the value reported by [`Self::dtlb_4k_size`].
+    pub fn set_dtlb_4k_size(&mut self, value: u8) {
+        set_bits(&mut self.ebx, 16, 23, value as u32);
+    }
+
+    /// Set the value reported by [`Self::itlb_4k_size`].
+    pub fn set_itlb_4k_size(&mut self, value: u8) {
+        set_bits(&mut self.ebx, 0, 7, value as u32);
+    }
+
+    /// Set the value reported by [`Self::dcache_size`].
+    pub fn set_dcache_size(&mut self, value: u8) {
+        set_bits(&mut self.ecx, 24, 31, value as u32);
+    }
+
+    /// Set the value reported by [`Self::dcache_lines_per_tag`].
+    pub fn set_dcache_lines_per_tag(&mut self, value: u8) {
+        set_bits(&mut self.ecx, 8, 15, value as u32);
+    }
+
+    /// Set the value reported by [`Self::dcache_line_size`].
+    pub fn set_dcache_line_size(&mut self, value: u8) {
+        set_bits(&mut self.ecx, 0, 7, value as u32);
+    }
+
+    /// Set the value reported by [`Self::icache_size`].
+    pub fn set_icache_size(&mut self, value: u8) {
+        set_bits(&mut self.edx, 24, 31, value as u32);
+    }
+
+    /// Set the value reported by [`Self::icache_lines_per_tag`].
+    pub fn set_icache_lines_per_tag(&mut self, value: u8) {
+        set_bits(&mut self.edx, 8, 15, value as u32);
+    }
+
+    /// Set the value reported by [`Self::icache_line_size`].
+    pub fn set_icache_line_size(&mut self, value: u8) {
+        set_bits(&mut self.edx, 0, 7, value as u32);
+    }
 }
 
 /// L2/L3 Cache and TLB Information (LEAF=0x8000_0006).
@@ -599,7 +1030,7 @@ impl L1CacheTlbInfo {
 /// # Availability
 /// ✅ AMD 🟡 Intel
 #[derive(PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct L2And3CacheTlbInfo {
     eax: u32,
     ebx: u32,
@@ -608,7 +1039,8 @@ pub struct L2And3CacheTlbInfo {
 }
 
 impl L2And3CacheTlbInfo {
-    pub(crate) fn new(data: CpuIdResult) -> Self {
+    /// Decodes the registers of LEAF=0x8000_0006.
+    pub fn from_registers(data: CpuIdResult) -> Self {
         Self {
             eax: data.eax,
             ebx: data.ebx,
@@ -760,16 +1192,210 @@ impl L2And3CacheTlbInfo {
     pub fn l3cache_size(&self) -> u16 {
         get_bits(self.edx, 18, 31) as u16
     }
+
+    /// Summary of the L2 cache, e.g. `L2 Unified, 512 KiB, 8-way, 64 B lines`.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn l2cache_description(&self) -> CacheDescription {
+        CacheDescription {
+            level: 2,
+            cache_type: CacheType::Unified,
+            size: self.l2cache_size() as u64 * 1024,
+            associativity: self.l2cache_associativity(),
+            line_size: self.l2cache_line_size() as u16,
+            shared_by: None,
+        }
+    }
+
+    /// Summary of the L3 cache, e.g. `L3 Unified, 32 MiB, 16-way, 64 B lines`.
+    ///
+    /// The size is the lower bound of the range reported by
+    /// [`Self::l3cache_size`].
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=0)
+    pub fn l3cache_description(&self) -> CacheDescription {
+        CacheDescription {
+            level: 3,
+            cache_type: CacheType::Unified,
+            size: self.l3cache_size() as u64 * 512 * 1024,
+            associativity: self.l3cache_associativity(),
+            line_size: self.l3cache_line_size() as u16,
+            shared_by: None,
+        }
+    }
+
+    /// Encode this struct back into the registers of leaf 0x8000_0006.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
+
+    /// Set the value reported by [`Self::dtlb_2m_4m_size`].
+    pub fn set_dtlb_2m_4m_size(&mut self, value: u16) {
+        set_bits(&mut self.eax, 16, 27, value as u32);
+    }
+
+    /// Set the value reported by [`Self::itlb_2m_4m_size`].
+    pub fn set_itlb_2m_4m_size(&mut self, value: u16) {
+        set_bits(&mut self.eax, 0, 11, value as u32);
+    }
+
+    /// Set the value reported by [`Self::dtlb_4k_size`].
+    pub fn set_dtlb_4k_size(&mut self, value: u16) {
+        set_bits(&mut self.ebx, 16, 27, value as u32);
+    }
+
+    /// Set the value reported by [`Self::itlb_4k_size`].
+    pub fn set_itlb_4k_size(&mut self, value: u16) {
+        set_bits(&mut self.ebx, 0, 11, value as u32);
+    }
+
+    /// Set the value reported by [`Self::l2cache_line_size`].
+    pub fn set_l2cache_line_size(&mut self, value: u8) {
+        set_bits(&mut self.ecx, 0, 7, value as u32);
+    }
+
+    /// Set the value reported by [`Self::l2cache_lines_per_tag`].
+    pub fn set_l2cache_lines_per_tag(&mut self, value: u8) {
+        set_bits(&mut self.ecx, 8, 11, value as u32);
+    }
+
+    /// Set the value reported by [`Self::l2cache_size`].
+    pub fn set_l2cache_size(&mut self, value: u16) {
+        set_bits(&mut self.ecx, 16, 31, value as u32);
+    }
+
+    /// Set the value reported by [`Self::l3cache_line_size`].
+    pub fn set_l3cache_line_size(&mut self, value: u8) {
+        set_bits(&mut self.edx, 0, 7, value as u32);
+    }
+
+    /// Set the value reported by [`Self::l3cache_lines_per_tag`].
+    pub fn set_l3cache_lines_per_tag(&mut self, value: u8) {
+        set_bits(&mut self.edx, 8, 11, value as u32);
+    }
+
+    /// Set the value reported by [`Self::l3cache_size`].
+    pub fn set_l3cache_size(&mut self, value: u16) {
+        set_bits(&mut self.edx, 18, 31, value as u32);
+    }
 }
 
-/// Info about cache Associativity.
+/// TLB Characteristics for 1GB pages (LEAF=0x8000_0019).
+///
+/// # Platforms
+/// ✅ AMD ❌ Intel (reserved=0)
 #[derive(PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Tlb1gPageInfo {
+    eax: u32,
+    ebx: u32,
+}
+
+impl Tlb1gPageInfo {
+    /// Decodes the registers of LEAF=0x8000_0019.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+        }
+    }
+
+    /// L1 Data TLB associativity for 1-GB pages.
+    pub fn l1_dtlb_1g_associativity(&self) -> Associativity {
+        Associativity::for_l2(get_bits(self.eax, 28, 31) as u8)
+    }
+
+    /// L1 Data TLB number of entries for 1-GB pages.
+    pub fn l1_dtlb_1g_size(&self) -> u16 {
+        get_bits(self.eax, 16, 27) as u16
+    }
+
+    /// L1 Instruction TLB associativity for 1-GB pages.
+    pub fn l1_itlb_1g_associativity(&self) -> Associativity {
+        Associativity::for_l2(get_bits(self.eax, 12, 15) as u8)
+    }
+
+    /// L1 Instruction TLB number of entries for 1-GB pages.
+    pub fn l1_itlb_1g_size(&self) -> u16 {
+        get_bits(self.eax, 0, 11) as u16
+    }
+
+    /// L2 Data TLB associativity for 1-GB pages.
+    pub fn l2_dtlb_1g_associativity(&self) -> Associativity {
+        Associativity::for_l2(get_bits(self.ebx, 28, 31) as u8)
+    }
+
+    /// L2 Data TLB number of entries for 1-GB pages.
+    pub fn l2_dtlb_1g_size(&self) -> u16 {
+        get_bits(self.ebx, 16, 27) as u16
+    }
+
+    /// L2 Instruction TLB associativity for 1-GB pages.
+    pub fn l2_itlb_1g_associativity(&self) -> Associativity {
+        Associativity::for_l2(get_bits(self.ebx, 12, 15) as u8)
+    }
+
+    /// L2 Instruction TLB number of entries for 1-GB pages.
+    pub fn l2_itlb_1g_size(&self) -> u16 {
+        get_bits(self.ebx, 0, 11) as u16
+    }
+
+    /// Encode this struct back into the registers of leaf 0x8000_0019.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: 0,
+            edx: 0,
+        }
+    }
+
+    /// Set the value reported by [`Self::l1_dtlb_1g_size`].
+    pub fn set_l1_dtlb_1g_size(&mut self, value: u16) {
+        set_bits(&mut self.eax, 16, 27, value as u32);
+    }
+
+    /// Set the value reported by [`Self::l1_itlb_1g_size`].
+    pub fn set_l1_itlb_1g_size(&mut self, value: u16) {
+        set_bits(&mut self.eax, 0, 11, value as u32);
+    }
+
+    /// Set the value reported by [`Self::l2_dtlb_1g_size`].
+    pub fn set_l2_dtlb_1g_size(&mut self, value: u16) {
+        set_bits(&mut self.ebx, 16, 27, value as u32);
+    }
+
+    /// Set the value reported by [`Self::l2_itlb_1g_size`].
+    pub fn set_l2_itlb_1g_size(&mut self, value: u16) {
+        set_bits(&mut self.ebx, 0, 11, value as u32);
+    }
+}
+
+/// Associativity of a cache or TLB.
+///
+/// Decoded from the associativity encodings of the AMD cache and TLB leafs
+/// (LEAF=0x8000_0005, 0x8000_0006 and 0x8000_0019), and from the number of ways and the
+/// fully associative flag of the Intel leafs (LEAF=0x04 and 0x18).
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Associativity {
+    /// The cache or TLB is disabled (or not present).
     Disabled,
-    DirectMapped,
-    NWay(u8),
-    FullyAssociative,
+    /// Direct mapped (1-way).
+    Direct,
+    /// N-way set associative.
+    NWay(u16),
+    /// Fully associative.
+    Full,
+    /// A reserved encoding, or (on AMD) the value has to be determined from
+    /// `Fn8000_001D`.
     Unknown,
 }
 
@@ -777,11 +1403,11 @@ impl Display for Associativity {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let s = match self {
             Associativity::Disabled => "Disabled",
-            Associativity::DirectMapped => "Direct mapped",
+            Associativity::Direct => "Direct mapped",
             Associativity::NWay(n) => {
-                return write!(f, "NWay({})", n);
+                return write!(f, "{}-way", n);
             }
-            Associativity::FullyAssociative => "Fully associative",
+            Associativity::Full => "Fully associative",
             Associativity::Unknown => "Unknown (check leaf 0x8000_001d)",
         };
         f.write_str(s)
@@ -789,13 +1415,35 @@ impl Display for Associativity {
 }
 
 impl Associativity {
+    /// Number of ways, or `None` if the structure is disabled, fully
+    /// associative or the associativity is unknown.
+    pub fn ways(&self) -> Option<u16> {
+        match self {
+            Associativity::Direct => Some(1),
+            Associativity::NWay(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Constructor for the Intel leafs, which report the number of ways and
+    /// whether the structure is fully associative.
+    pub(crate) fn for_ways(ways: usize, fully_associative: bool) -> Associativity {
+        match ways {
+            _ if fully_associative => Associativity::Full,
+            0 => Associativity::Disabled,
+            1 => Associativity::Direct,
+            n if n <= u16::MAX as usize => Associativity::NWay(n as u16),
+            _ => Associativity::Unknown,
+        }
+    }
+
     /// Constructor for L1 Cache and TLB Associativity Field Encodings
-    fn for_l1(n: u8) -> Associativity {
+    pub(crate) fn for_l1(n: u8) -> Associativity {
         match n {
             0x0 => Associativity::Disabled, // Intel only, AMD is reserved
-            0x1 => Associativity::DirectMapped,
-            0x2..=0xfe => Associativity::NWay(n),
-            0xff => Associativity::FullyAssociative,
+            0x1 => Associativity::Direct,
+            0x2..=0xfe => Associativity::NWay(n as u16),
+            0xff => Associativity::Full,
         }
     }
 
@@ -803,7 +1451,7 @@ impl Associativity {
     fn for_l2(n: u8) -> Associativity {
         match n {
             0x0 => Associativity::Disabled,
-            0x1 => Associativity::DirectMapped,
+            0x1 => Associativity::Direct,
             0x2 => Associativity::NWay(2),
             0x4 => Associativity::NWay(4),
             0x5 => Associativity::NWay(6), // Reserved on Intel
@@ -815,7 +1463,7 @@ impl Associativity {
             0xc => Associativity::NWay(64),
             0xd => Associativity::NWay(96),
             0xe => Associativity::NWay(128),
-            0xF => Associativity::FullyAssociative,
+            0xF => Associativity::Full,
             _ => Associativity::Unknown,
         }
     }
@@ -831,7 +1479,7 @@ impl Associativity {
 /// # Platforms
 /// ✅ AMD 🟡 Intel
 #[derive(Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ApmInfo {
     /// Reserved on AMD and Intel.
     _eax: u32,
@@ -841,12 +1489,13 @@ pub struct ApmInfo {
 }
 
 impl ApmInfo {
-    pub(crate) fn new(data: CpuIdResult) -> Self {
+    /// Decodes the registers of LEAF=0x8000_0007.
+    pub fn from_registers(data: CpuIdResult) -> Self {
         Self {
             _eax: data.eax,
-            ebx: RasCapabilities::from_bits_truncate(data.ebx),
+            ebx: RasCapabilities { bits: data.ebx },
             ecx: data.ecx,
-            edx: ApmInfoEdx::from_bits_truncate(data.edx),
+            edx: ApmInfoEdx { bits: data.edx },
         }
     }
 
@@ -884,6 +1533,28 @@ impl ApmInfo {
         self.ebx.contains(RasCapabilities::HWA)
     }
 
+    /// Has Scalable MCA?
+    ///
+    /// The machine check banks use the scalable MCA register layout
+    /// (`MCA_IPID`, `MCA_SYND`, ...) instead of the legacy one.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_scalable_mca(&self) -> bool {
+        self.ebx.contains(RasCapabilities::SCALABLE_MCA)
+    }
+
+    /// Has Platform First Error Handling?
+    ///
+    /// Machine check events can be handled by the platform firmware before
+    /// (or instead of) the OS.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_pfeh(&self) -> bool {
+        self.ebx.contains(RasCapabilities::PFEH)
+    }
+
     /// Specifies the ratio of the compute unit power accumulator sample period
     /// to the TSC counter period.
     ///
@@ -1006,18 +1677,132 @@ impl ApmInfo {
     pub fn has_power_reporting_iface(&self) -> bool {
         self.edx.contains(ApmInfoEdx::PROCPWRREPORT)
     }
-}
 
-bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-    struct ApmInfoEdx: u32 {
-        const TS = 1 << 0;
-        const FID = 1 << 1;
-        const VID = 1 << 2;
-        const TTP = 1 << 3;
-        const TM = 1 << 4;
-        const MHZSTEPS100 = 1 << 6;
-        const HWPSTATE = 1 << 7;
+    /// Encode this struct back into the registers of leaf 0x8000_0007.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self._eax,
+            ebx: self.ebx.bits(),
+            ecx: self.ecx,
+            edx: self.edx.bits(),
+        }
+    }
+
+    /// Bits set in EBX and EDX that this library doesn't interpret, i.e.,
+    /// reserved bits or flags it doesn't know about yet.
+    ///
+    /// EAX is reserved and ECX only contains a value, both always read as
+    /// zero.
+    pub fn reserved_bits(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: 0,
+            ebx: self.ebx.bits() & !RasCapabilities::all().bits(),
+            ecx: 0,
+            edx: self.edx.bits() & !ApmInfoEdx::all().bits(),
+        }
+    }
+
+    /// Set or clear the flag reported by [`Self::has_mca_overflow_recovery`].
+    pub fn set_mca_overflow_recovery(&mut self, bit: bool) {
+        self.ebx.set(RasCapabilities::MCAOVFLRECOV, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_succor`].
+    pub fn set_succor(&mut self, bit: bool) {
+        self.ebx.set(RasCapabilities::SUCCOR, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_hwa`].
+    pub fn set_hwa(&mut self, bit: bool) {
+        self.ebx.set(RasCapabilities::HWA, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_scalable_mca`].
+    pub fn set_scalable_mca(&mut self, bit: bool) {
+        self.ebx.set(RasCapabilities::SCALABLE_MCA, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_pfeh`].
+    pub fn set_pfeh(&mut self, bit: bool) {
+        self.ebx.set(RasCapabilities::PFEH, bit);
+    }
+
+    /// Set the value reported by [`Self::cpu_pwr_sample_time_ratio`].
+    pub fn set_cpu_pwr_sample_time_ratio(&mut self, value: u32) {
+        self.ecx = value;
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ts`].
+    pub fn set_ts(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::TS, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_freq_id_ctrl`].
+    pub fn set_freq_id_ctrl(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::FID, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_volt_id_ctrl`].
+    pub fn set_volt_id_ctrl(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::VID, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_thermtrip`].
+    pub fn set_thermtrip(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::TTP, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_tm`].
+    pub fn set_tm(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::TM, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_100mhz_steps`].
+    pub fn set_100mhz_steps(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::MHZSTEPS100, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_hw_pstate`].
+    pub fn set_hw_pstate(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::HWPSTATE, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_invariant_tsc`].
+    pub fn set_invariant_tsc(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::INVTSC, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_cpb`].
+    pub fn set_cpb(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::CPB, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ro_effective_freq_iface`].
+    pub fn set_ro_effective_freq_iface(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::EFFFREQRO, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_feedback_iface`].
+    pub fn set_feedback_iface(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::PROCFEEDBACKIF, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_power_reporting_iface`].
+    pub fn set_power_reporting_iface(&mut self, bit: bool) {
+        self.edx.set(ApmInfoEdx::PROCPWRREPORT, bit);
+    }
+}
+
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    struct ApmInfoEdx: u32 {
+        const TS = 1 << 0;
+        const FID = 1 << 1;
+        const VID = 1 << 2;
+        const TTP = 1 << 3;
+        const TM = 1 << 4;
+        const MHZSTEPS100 = 1 << 6;
+        const HWPSTATE = 1 << 7;
         const INVTSC = 1 << 8;
         const CPB = 1 << 9;
         const EFFFREQRO = 1 << 10;
@@ -1027,11 +1812,13 @@ bitflags! {
 }
 
 bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     struct RasCapabilities: u32 {
         const MCAOVFLRECOV = 1 << 0;
         const SUCCOR = 1 << 1;
         const HWA = 1 << 2;
+        const SCALABLE_MCA = 1 << 3;
+        const PFEH = 1 << 4;
     }
 }
 
@@ -1045,7 +1832,7 @@ bitflags! {
 /// # Platforms
 /// ✅ AMD 🟡 Intel
 #[derive(Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProcessorCapacityAndFeatureInfo {
     eax: u32,
     ebx: ProcessorCapacityAndFeatureEbx,
@@ -1054,10 +1841,11 @@ pub struct ProcessorCapacityAndFeatureInfo {
 }
 
 impl ProcessorCapacityAndFeatureInfo {
-    pub(crate) fn new(data: CpuIdResult) -> Self {
+    /// Decodes the registers of LEAF=0x8000_0008.
+    pub fn from_registers(data: CpuIdResult) -> Self {
         Self {
             eax: data.eax,
-            ebx: ProcessorCapacityAndFeatureEbx::from_bits_truncate(data.ebx),
+            ebx: ProcessorCapacityAndFeatureEbx { bits: data.ebx },
             ecx: data.ecx,
             edx: data.edx,
         }
@@ -1176,6 +1964,134 @@ impl ProcessorCapacityAndFeatureInfo {
             .contains(ProcessorCapacityAndFeatureEbx::INVLPGB_NESTED)
     }
 
+    /// Indirect Branch Prediction Barrier (IBPB) is supported if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_ibpb(&self) -> bool {
+        self.ebx.contains(ProcessorCapacityAndFeatureEbx::IBPB)
+    }
+
+    /// Indirect Branch Restricted Speculation (IBRS) is supported if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_ibrs(&self) -> bool {
+        self.ebx.contains(ProcessorCapacityAndFeatureEbx::IBRS)
+    }
+
+    /// Single Thread Indirect Branch Predictor (STIBP) is supported if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_stibp(&self) -> bool {
+        self.ebx.contains(ProcessorCapacityAndFeatureEbx::STIBP)
+    }
+
+    /// IBRS is preferred to be always on (set once instead of on every
+    /// privilege level change) if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_ibrs_always_on(&self) -> bool {
+        self.ebx
+            .contains(ProcessorCapacityAndFeatureEbx::IBRS_ALWAYS_ON)
+    }
+
+    /// STIBP is preferred to be always on if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_stibp_always_on(&self) -> bool {
+        self.ebx
+            .contains(ProcessorCapacityAndFeatureEbx::STIBP_ALWAYS_ON)
+    }
+
+    /// IBRS is preferred over software mitigations (e.g., retpolines) if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_ibrs_preferred(&self) -> bool {
+        self.ebx
+            .contains(ProcessorCapacityAndFeatureEbx::IBRS_PREFERRED)
+    }
+
+    /// IBRS also protects against branch targets of the same privilege
+    /// level if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_ibrs_same_mode(&self) -> bool {
+        self.ebx
+            .contains(ProcessorCapacityAndFeatureEbx::IBRS_SAME_MODE)
+    }
+
+    /// Protected Processor Inventory Number (PPIN) is supported if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_ppin(&self) -> bool {
+        self.ebx.contains(ProcessorCapacityAndFeatureEbx::PPIN)
+    }
+
+    /// Speculative Store Bypass Disable (SSBD) through SPEC_CTRL is supported
+    /// if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_ssbd(&self) -> bool {
+        self.ebx.contains(ProcessorCapacityAndFeatureEbx::SSBD)
+    }
+
+    /// Speculative Store Bypass Disable through VIRT_SPEC_CTRL is supported
+    /// if set (in virtual machines).
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_virt_ssbd(&self) -> bool {
+        self.ebx.contains(ProcessorCapacityAndFeatureEbx::VIRT_SSBD)
+    }
+
+    /// The processor is not affected by Speculative Store Bypass if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_ssb_no(&self) -> bool {
+        self.ebx.contains(ProcessorCapacityAndFeatureEbx::SSB_NO)
+    }
+
+    /// Collaborative Processor Performance Control (CPPC) is supported if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_cppc(&self) -> bool {
+        self.ebx.contains(ProcessorCapacityAndFeatureEbx::CPPC)
+    }
+
+    /// Predictive Store Forwarding Disable (PSFD) is supported if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_psfd(&self) -> bool {
+        self.ebx.contains(ProcessorCapacityAndFeatureEbx::PSFD)
+    }
+
+    /// The processor is not affected by Branch Type Confusion if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_btc_no(&self) -> bool {
+        self.ebx.contains(ProcessorCapacityAndFeatureEbx::BTC_NO)
+    }
+
+    /// IBPB also clears the return address predictor if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_ibpb_ret(&self) -> bool {
+        self.ebx.contains(ProcessorCapacityAndFeatureEbx::IBPB_RET)
+    }
+
     /// Performance time-stamp counter size (in bits).
     ///
     /// Indicates the size of `MSRC001_0280[PTSC]`.  
@@ -1241,10 +2157,229 @@ impl ProcessorCapacityAndFeatureInfo {
     pub fn max_rdpru_id(&self) -> u16 {
         get_bits(self.edx, 16, 31) as u16
     }
+
+    /// Can the register with the given ECX value be read with RDPRU?
+    ///
+    /// Requires [`Self::has_rdpru`] and `id` to be at most
+    /// [`Self::max_rdpru_id`].
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn is_rdpru_register_supported(&self, id: u16) -> bool {
+        self.has_rdpru() && id <= self.max_rdpru_id()
+    }
+
+    /// Can MPERF be read with RDPRU (ECX=0)?
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_rdpru_mperf(&self) -> bool {
+        self.is_rdpru_register_supported(0)
+    }
+
+    /// Can APERF be read with RDPRU (ECX=1)?
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved=false)
+    pub fn has_rdpru_aperf(&self) -> bool {
+        self.is_rdpru_register_supported(1)
+    }
+
+    /// Encode this struct back into the registers of leaf 0x8000_0008.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx.bits(),
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
+
+    /// Bits set in EBX that this library doesn't interpret, i.e., reserved
+    /// bits or flags it doesn't know about yet.
+    ///
+    /// EAX, ECX and EDX only contain values and always read as zero.
+    pub fn reserved_bits(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: 0,
+            ebx: self.ebx.bits() & !ProcessorCapacityAndFeatureEbx::all().bits(),
+            ecx: 0,
+            edx: 0,
+        }
+    }
+
+    /// Set the value reported by [`Self::physical_address_bits`].
+    pub fn set_physical_address_bits(&mut self, value: u8) {
+        set_bits(&mut self.eax, 0, 7, value as u32);
+    }
+
+    /// Set the value reported by [`Self::linear_address_bits`].
+    pub fn set_linear_address_bits(&mut self, value: u8) {
+        set_bits(&mut self.eax, 8, 15, value as u32);
+    }
+
+    /// Set the value reported by [`Self::guest_physical_address_bits`].
+    pub fn set_guest_physical_address_bits(&mut self, value: u8) {
+        set_bits(&mut self.eax, 16, 23, value as u32);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_cl_zero`].
+    pub fn set_cl_zero(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::CLZERO, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_inst_ret_cntr_msr`].
+    pub fn set_inst_ret_cntr_msr(&mut self, bit: bool) {
+        self.ebx
+            .set(ProcessorCapacityAndFeatureEbx::INST_RETCNT_MSR, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_restore_fp_error_ptrs`].
+    pub fn set_restore_fp_error_ptrs(&mut self, bit: bool) {
+        self.ebx
+            .set(ProcessorCapacityAndFeatureEbx::RSTR_FP_ERR_PTRS, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_invlpgb`].
+    pub fn set_invlpgb(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::INVLPGB, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_rdpru`].
+    pub fn set_rdpru(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::RDPRU, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_mcommit`].
+    pub fn set_mcommit(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::MCOMMIT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_wbnoinvd`].
+    pub fn set_wbnoinvd(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::WBNOINVD, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_int_wbinvd`].
+    pub fn set_int_wbinvd(&mut self, bit: bool) {
+        self.ebx
+            .set(ProcessorCapacityAndFeatureEbx::INT_WBINVD, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_unsupported_efer_lmsle`].
+    pub fn set_unsupported_efer_lmsle(&mut self, bit: bool) {
+        self.ebx
+            .set(ProcessorCapacityAndFeatureEbx::EFER_LMSLE_UNSUPP, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_invlpgb_nested`].
+    pub fn set_invlpgb_nested(&mut self, bit: bool) {
+        self.ebx
+            .set(ProcessorCapacityAndFeatureEbx::INVLPGB_NESTED, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ibpb`].
+    pub fn set_ibpb(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::IBPB, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ibrs`].
+    pub fn set_ibrs(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::IBRS, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_stibp`].
+    pub fn set_stibp(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::STIBP, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ibrs_always_on`].
+    pub fn set_ibrs_always_on(&mut self, bit: bool) {
+        self.ebx
+            .set(ProcessorCapacityAndFeatureEbx::IBRS_ALWAYS_ON, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_stibp_always_on`].
+    pub fn set_stibp_always_on(&mut self, bit: bool) {
+        self.ebx
+            .set(ProcessorCapacityAndFeatureEbx::STIBP_ALWAYS_ON, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ibrs_preferred`].
+    pub fn set_ibrs_preferred(&mut self, bit: bool) {
+        self.ebx
+            .set(ProcessorCapacityAndFeatureEbx::IBRS_PREFERRED, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ibrs_same_mode`].
+    pub fn set_ibrs_same_mode(&mut self, bit: bool) {
+        self.ebx
+            .set(ProcessorCapacityAndFeatureEbx::IBRS_SAME_MODE, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ppin`].
+    pub fn set_ppin(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::PPIN, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ssbd`].
+    pub fn set_ssbd(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::SSBD, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_virt_ssbd`].
+    pub fn set_virt_ssbd(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::VIRT_SSBD, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ssb_no`].
+    pub fn set_ssb_no(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::SSB_NO, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_cppc`].
+    pub fn set_cppc(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::CPPC, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_psfd`].
+    pub fn set_psfd(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::PSFD, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_btc_no`].
+    pub fn set_btc_no(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::BTC_NO, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_ibpb_ret`].
+    pub fn set_ibpb_ret(&mut self, bit: bool) {
+        self.ebx.set(ProcessorCapacityAndFeatureEbx::IBPB_RET, bit);
+    }
+
+    /// Set the value reported by [`Self::apic_id_size`].
+    pub fn set_apic_id_size(&mut self, value: u8) {
+        set_bits(&mut self.ecx, 12, 15, value as u32);
+    }
+
+    /// Set the value reported by [`Self::num_phys_threads`] (must be at least 1).
+    pub fn set_num_phys_threads(&mut self, value: usize) {
+        set_bits(&mut self.ecx, 0, 7, (value as u32).saturating_sub(1));
+    }
+
+    /// Set the value reported by [`Self::invlpgb_max_pages`].
+    pub fn set_invlpgb_max_pages(&mut self, value: u16) {
+        set_bits(&mut self.edx, 0, 15, value as u32);
+    }
+
+    /// Set the value reported by [`Self::max_rdpru_id`].
+    pub fn set_max_rdpru_id(&mut self, value: u16) {
+        set_bits(&mut self.edx, 16, 31, value as u32);
+    }
 }
 
 bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     struct ProcessorCapacityAndFeatureEbx: u32 {
         const CLZERO = 1 << 0;
         const INST_RETCNT_MSR = 1 << 1;
@@ -1253,9 +2388,124 @@ bitflags! {
         const RDPRU = 1 << 4;
         const MCOMMIT = 1 << 8;
         const WBNOINVD = 1 << 9;
+        const IBPB = 1 << 12;
         const INT_WBINVD = 1 << 13;
+        const IBRS = 1 << 14;
+        const STIBP = 1 << 15;
+        const IBRS_ALWAYS_ON = 1 << 16;
+        const STIBP_ALWAYS_ON = 1 << 17;
+        const IBRS_PREFERRED = 1 << 18;
+        const IBRS_SAME_MODE = 1 << 19;
         const EFER_LMSLE_UNSUPP = 1 << 20;
         const INVLPGB_NESTED = 1 << 21;
+        const PPIN = 1 << 23;
+        const SSBD = 1 << 24;
+        const VIRT_SSBD = 1 << 25;
+        const SSB_NO = 1 << 26;
+        const CPPC = 1 << 27;
+        const PSFD = 1 << 28;
+        const BTC_NO = 1 << 29;
+        const IBPB_RET = 1 << 30;
+    }
+}
+
+/// Processor Topology Information (LEAF=0x8000_001E).
+///
+/// Only valid with topology extensions
+/// ([`ExtendedProcessorFeatureIdentifiers::has_topology_extensions`]).
+///
+/// # Platforms
+/// ✅ AMD ❌ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessorTopologyInfo {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+}
+
+impl ProcessorTopologyInfo {
+    /// Decodes the registers of LEAF=0x8000_001E.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+        }
+    }
+
+    /// Extended (32-bit) APIC ID of the logical processor.
+    pub fn extended_apic_id(&self) -> u32 {
+        self.eax
+    }
+
+    /// ID of the core (compute unit on family 15h) within the package.
+    pub fn core_id(&self) -> u8 {
+        get_bits(self.ebx, 0, 7) as u8
+    }
+
+    /// Number of threads per core (compute unit on family 15h).
+    pub fn threads_per_core(&self) -> u8 {
+        get_bits(self.ebx, 8, 15) as u8 + 1
+    }
+
+    /// ID of the node the logical processor belongs to, unique in the system.
+    pub fn node_id(&self) -> u8 {
+        get_bits(self.ecx, 0, 7) as u8
+    }
+
+    /// Number of nodes per processor (package).
+    pub fn nodes_per_processor(&self) -> u8 {
+        get_bits(self.ecx, 8, 10) as u8 + 1
+    }
+
+    /// Encode this struct back into the registers of leaf 0x8000_001E.
+    ///
+    /// EDX is reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: 0,
+        }
+    }
+
+    /// Set the value reported by [`Self::extended_apic_id`].
+    pub fn set_extended_apic_id(&mut self, value: u32) {
+        self.eax = value;
+    }
+
+    /// Set the value reported by [`Self::core_id`].
+    pub fn set_core_id(&mut self, value: u8) {
+        set_bits(&mut self.ebx, 0, 7, value as u32);
+    }
+
+    /// Set the value reported by [`Self::threads_per_core`] (at least 1).
+    pub fn set_threads_per_core(&mut self, value: u8) {
+        set_bits(&mut self.ebx, 8, 15, value.saturating_sub(1) as u32);
+    }
+
+    /// Set the value reported by [`Self::node_id`].
+    pub fn set_node_id(&mut self, value: u8) {
+        set_bits(&mut self.ecx, 0, 7, value as u32);
+    }
+
+    /// Set the value reported by [`Self::nodes_per_processor`] (1 to 8).
+    pub fn set_nodes_per_processor(&mut self, value: u8) {
+        set_bits(&mut self.ecx, 8, 10, value.saturating_sub(1) as u32);
+    }
+}
+
+impl Debug for ProcessorTopologyInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProcessorTopologyInfo")
+            .field("extended_apic_id", &self.extended_apic_id())
+            .field("core_id", &self.core_id())
+            .field("threads_per_core", &self.threads_per_core())
+            .field("node_id", &self.node_id())
+            .field("nodes_per_processor", &self.nodes_per_processor())
+            .finish()
     }
 }
 
@@ -1264,7 +2514,7 @@ bitflags! {
 /// # Platforms
 /// ✅ AMD ❌ Intel
 #[derive(Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MemoryEncryptionInfo {
     eax: MemoryEncryptionInfoEax,
     ebx: u32,
@@ -1273,9 +2523,10 @@ pub struct MemoryEncryptionInfo {
 }
 
 impl MemoryEncryptionInfo {
-    pub(crate) fn new(data: CpuIdResult) -> Self {
+    /// Decodes the registers of LEAF=0x8000_001F.
+    pub fn from_registers(data: CpuIdResult) -> Self {
         Self {
-            eax: MemoryEncryptionInfoEax::from_bits_truncate(data.eax),
+            eax: MemoryEncryptionInfoEax { bits: data.eax },
             ebx: data.ebx,
             ecx: data.ecx,
             edx: data.edx,
@@ -1307,11 +2558,16 @@ impl MemoryEncryptionInfo {
         self.eax.contains(MemoryEncryptionInfoEax::SEV_SNP)
     }
 
-    /// VM Permission Levels supported if set.
+    /// VM Permission Levels supported if set, see [`Self::num_vmpl`].
     pub fn has_vmpl(&self) -> bool {
         self.eax.contains(MemoryEncryptionInfoEax::VMPL)
     }
 
+    /// Secure TSC supported for SEV-SNP guests if set.
+    pub fn has_secure_tsc(&self) -> bool {
+        self.eax.contains(MemoryEncryptionInfoEax::SECURE_TSC)
+    }
+
     /// Hardware cache coherency across encryption domains enforced if set.
     pub fn has_hw_enforced_cache_coh(&self) -> bool {
         self.eax.contains(MemoryEncryptionInfoEax::HWENFCACHECOH)
@@ -1347,6 +2603,11 @@ impl MemoryEncryptionInfo {
         self.eax.contains(MemoryEncryptionInfoEax::VTE)
     }
 
+    /// VMSA register protection supported for SEV-SNP guests if set.
+    pub fn has_vmsa_reg_protection(&self) -> bool {
+        self.eax.contains(MemoryEncryptionInfoEax::VMSAREGPROT)
+    }
+
     /// C-bit location in page table entry
     pub fn c_bit_position(&self) -> u8 {
         get_bits(self.ebx, 0, 5) as u8
@@ -1357,6 +2618,11 @@ impl MemoryEncryptionInfo {
         get_bits(self.ebx, 6, 11) as u8
     }
 
+    /// Number of VM Permission Levels supported (if [`Self::has_vmpl`]).
+    pub fn num_vmpl(&self) -> u8 {
+        get_bits(self.ebx, 12, 15) as u8
+    }
+
     /// Number of encrypted guests supported simultaneouslys
     pub fn max_encrypted_guests(&self) -> u32 {
         self.ecx
@@ -1366,10 +2632,183 @@ impl MemoryEncryptionInfo {
     pub fn min_sev_no_es_asid(&self) -> u32 {
         self.edx
     }
+
+    /// Smallest ASID of SEV guests without SEV-ES (same as
+    /// [`Self::min_sev_no_es_asid`]).
+    pub fn min_sev_asid(&self) -> u32 {
+        self.edx
+    }
+
+    /// Largest ASID of SEV guests (same as [`Self::max_encrypted_guests`]).
+    ///
+    /// ASIDs `1..=max_sev_asid` are available for encrypted guests, split
+    /// into [`Self::sev_es_asid_range`] and [`Self::sev_asid_range`].
+    pub fn max_sev_asid(&self) -> u32 {
+        self.ecx
+    }
+
+    /// ASIDs of SEV guests without SEV-ES, or `None` if there are none.
+    pub fn sev_asid_range(&self) -> Option<RangeInclusive<u32>> {
+        let min = core::cmp::max(self.min_sev_asid(), 1);
+        (self.has_sev() && min <= self.max_sev_asid()).then_some(min..=self.max_sev_asid())
+    }
+
+    /// ASIDs of SEV-ES (and SEV-SNP) guests, or `None` if there are none.
+    ///
+    /// These are the ASIDs below [`Self::min_sev_asid`].
+    pub fn sev_es_asid_range(&self) -> Option<RangeInclusive<u32>> {
+        let max = core::cmp::min(self.min_sev_asid(), self.max_sev_asid().saturating_add(1));
+        (self.has_sev_es() && max > 1).then_some(1..=max - 1)
+    }
+
+    /// Check if `sev_guests` SEV guests without SEV-ES and `sev_es_guests`
+    /// SEV-ES (or SEV-SNP) guests can run at the same time, i.e., each of
+    /// them gets its own ASID.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// let cpuid = CpuId::new();
+    /// if let Some(info) = cpuid.get_memory_encryption_info() {
+    ///     println!("Room for 16 SEV-ES guests: {}", info.fits_sev_guests(0, 16));
+    /// }
+    /// ```
+    pub fn fits_sev_guests(&self, sev_guests: u32, sev_es_guests: u32) -> bool {
+        let len = |range: Option<RangeInclusive<u32>>| {
+            range.map_or(0, |range| range.end() - range.start() + 1)
+        };
+        sev_guests <= len(self.sev_asid_range()) && sev_es_guests <= len(self.sev_es_asid_range())
+    }
+
+    /// Encode this struct back into the registers of leaf 0x8000_001F.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax.bits(),
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
+
+    /// Bits set in EAX that this library doesn't interpret, i.e., reserved
+    /// bits or flags it doesn't know about yet.
+    ///
+    /// EBX, ECX and EDX only contain values and always read as zero.
+    pub fn reserved_bits(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax.bits() & !MemoryEncryptionInfoEax::all().bits(),
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
+        }
+    }
+
+    /// Set or clear the flag reported by [`Self::has_sme`].
+    pub fn set_sme(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::SME, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_sev`].
+    pub fn set_sev(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::SEV, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_page_flush_msr`].
+    pub fn set_page_flush_msr(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::PAGE_FLUSH_MSR, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_sev_es`].
+    pub fn set_sev_es(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::SEV_ES, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_sev_snp`].
+    pub fn set_sev_snp(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::SEV_SNP, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_vmpl`].
+    pub fn set_vmpl(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::VMPL, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_secure_tsc`].
+    pub fn set_secure_tsc(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::SECURE_TSC, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_hw_enforced_cache_coh`].
+    pub fn set_hw_enforced_cache_coh(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::HWENFCACHECOH, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_64bit_mode`].
+    pub fn set_64bit_mode(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::HOST64, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_restricted_injection`].
+    pub fn set_restricted_injection(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::RESTINJECT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_alternate_injection`].
+    pub fn set_alternate_injection(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::ALTINJECT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_debug_swap`].
+    pub fn set_debug_swap(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::DBGSWP, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_prevent_host_ibs`].
+    pub fn set_prevent_host_ibs(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::PREVHOSTIBS, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_vte`].
+    pub fn set_vte(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::VTE, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_vmsa_reg_protection`].
+    pub fn set_vmsa_reg_protection(&mut self, bit: bool) {
+        self.eax.set(MemoryEncryptionInfoEax::VMSAREGPROT, bit);
+    }
+
+    /// Set the value reported by [`Self::c_bit_position`].
+    pub fn set_c_bit_position(&mut self, value: u8) {
+        set_bits(&mut self.ebx, 0, 5, value as u32);
+    }
+
+    /// Set the value reported by [`Self::physical_address_reduction`].
+    pub fn set_physical_address_reduction(&mut self, value: u8) {
+        set_bits(&mut self.ebx, 6, 11, value as u32);
+    }
+
+    /// Set the value reported by [`Self::num_vmpl`].
+    pub fn set_num_vmpl(&mut self, value: u8) {
+        set_bits(&mut self.ebx, 12, 15, value as u32);
+    }
+
+    /// Set the value reported by [`Self::max_encrypted_guests`] and
+    /// [`Self::max_sev_asid`].
+    pub fn set_max_encrypted_guests(&mut self, value: u32) {
+        self.ecx = value;
+    }
+
+    /// Set the value reported by [`Self::min_sev_no_es_asid`] and
+    /// [`Self::min_sev_asid`].
+    pub fn set_min_sev_no_es_asid(&mut self, value: u32) {
+        self.edx = value;
+    }
 }
 
 bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     struct MemoryEncryptionInfoEax: u32 {
         const SME = 1 << 0;
         const SEV = 1 << 1;
@@ -1377,6 +2816,7 @@ bitflags! {
         const SEV_ES = 1 << 3;
         const SEV_SNP = 1 << 4;
         const VMPL = 1 << 5;
+        const SECURE_TSC = 1 << 8;
         const HWENFCACHECOH = 1 << 10;
         const HOST64 = 1 << 11;
         const RESTINJECT = 1 << 12;
@@ -1384,6 +2824,7 @@ bitflags! {
         const DBGSWP = 1 << 14;
         const PREVHOSTIBS = 1 << 15;
         const VTE = 1 << 16;
+        const VMSAREGPROT = 1 << 24;
     }
 }
 
@@ -1396,7 +2837,7 @@ bitflags! {
 /// # Platforms
 /// ✅ AMD ❌ Intel
 #[derive(PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SvmFeatures {
     eax: u32,
     ebx: u32,
@@ -1406,12 +2847,13 @@ pub struct SvmFeatures {
 }
 
 impl SvmFeatures {
-    pub(crate) fn new(data: CpuIdResult) -> Self {
+    /// Decodes the registers of LEAF=0x8000_000A.
+    pub fn from_registers(data: CpuIdResult) -> Self {
         Self {
             eax: data.eax,
             ebx: data.ebx,
             _ecx: data.ecx,
-            edx: SvmFeaturesEdx::from_bits_truncate(data.edx),
+            edx: SvmFeaturesEdx { bits: data.edx },
         }
     }
 
@@ -1519,10 +2961,134 @@ impl SvmFeatures {
     pub fn has_tlb_ctrl(&self) -> bool {
         self.edx.contains(SvmFeaturesEdx::TLB_CTL)
     }
+
+    /// Encode this struct back into the registers of leaf 0x8000_000A.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self._ecx,
+            edx: self.edx.bits(),
+        }
+    }
+
+    /// Bits set in EDX that this library doesn't interpret, i.e., reserved
+    /// bits or flags it doesn't know about yet.
+    ///
+    /// EAX and EBX only contain values and ECX is reserved, they always read
+    /// as zero.
+    pub fn reserved_bits(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: 0,
+            ebx: 0,
+            ecx: 0,
+            edx: self.edx.bits() & !SvmFeaturesEdx::all().bits(),
+        }
+    }
+
+    /// Set the value reported by [`Self::revision`].
+    pub fn set_revision(&mut self, value: u8) {
+        set_bits(&mut self.eax, 0, 7, value as u32);
+    }
+
+    /// Set the value reported by [`Self::supported_asids`].
+    pub fn set_supported_asids(&mut self, value: u32) {
+        self.ebx = value;
+    }
+
+    /// Set or clear the flag reported by [`Self::has_nested_paging`].
+    pub fn set_nested_paging(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::NP, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_lbr_virtualization`].
+    pub fn set_lbr_virtualization(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::LBR_VIRT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_svm_lock`].
+    pub fn set_svm_lock(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::SVML, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_nrip`].
+    pub fn set_nrip(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::NRIPS, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_tsc_rate_msr`].
+    pub fn set_tsc_rate_msr(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::TSC_RATE_MSR, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_vmcb_clean_bits`].
+    pub fn set_vmcb_clean_bits(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::VMCB_CLEAN, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_flush_by_asid`].
+    pub fn set_flush_by_asid(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::FLUSH_BY_ASID, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_decode_assists`].
+    pub fn set_decode_assists(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::DECODE_ASSISTS, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_pause_filter`].
+    pub fn set_pause_filter(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::PAUSE_FILTER, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_pause_filter_threshold`].
+    pub fn set_pause_filter_threshold(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::PAUSE_FILTER_THRESHOLD, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_avic`].
+    pub fn set_avic(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::AVIC, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_vmsave_virtualization`].
+    pub fn set_vmsave_virtualization(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::VMSAVE_VIRT, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_gif`].
+    pub fn set_gif(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::VGIF, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_gmet`].
+    pub fn set_gmet(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::GMET, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_sss_check`].
+    pub fn set_sss_check(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::SSS_CHECK, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_spec_ctrl`].
+    pub fn set_spec_ctrl(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::SPEC_CTRL, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_host_mce_override`].
+    pub fn set_host_mce_override(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::HOST_MCE_OVERRIDE, bit);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_tlb_ctrl`].
+    pub fn set_tlb_ctrl(&mut self, bit: bool) {
+        self.edx.set(SvmFeaturesEdx::TLB_CTL, bit);
+    }
 }
 
 bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     struct SvmFeaturesEdx: u32 {
         const NP = 1 << 0;
         const LBR_VIRT = 1 << 1;
@@ -1544,3 +3110,136 @@ bitflags! {
         const TLB_CTL = 1 << 24;
     }
 }
+
+/// Extended Performance Monitoring and Debug (LEAF=0x8000_0022).
+///
+/// # Platforms
+/// ✅ AMD ❌ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PerformanceMonitoringDebugInfo {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+}
+
+impl PerformanceMonitoringDebugInfo {
+    /// Decodes the registers of LEAF=0x8000_0022.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+        }
+    }
+
+    /// Performance Monitoring Version 2 is supported if set.
+    ///
+    /// The counter numbers of this leaf are only valid if set.
+    pub fn has_perfmon_v2(&self) -> bool {
+        get_bits(self.eax, 0, 0) == 1
+    }
+
+    /// Last Branch Record Stack (LbrV2) is supported if set.
+    pub fn has_lbr_stack(&self) -> bool {
+        get_bits(self.eax, 1, 1) == 1
+    }
+
+    /// Freezing core performance counters and the LBR stack on a counter
+    /// overflow is supported if set.
+    pub fn has_lbr_and_pmc_freeze(&self) -> bool {
+        get_bits(self.eax, 2, 2) == 1
+    }
+
+    /// Number of core performance counters.
+    pub fn num_core_counters(&self) -> u8 {
+        get_bits(self.ebx, 0, 3) as u8
+    }
+
+    /// Number of entries in the LBR stack.
+    pub fn lbr_stack_size(&self) -> u8 {
+        get_bits(self.ebx, 4, 9) as u8
+    }
+
+    /// Number of Northbridge (Data Fabric) performance counters.
+    pub fn num_northbridge_counters(&self) -> u8 {
+        get_bits(self.ebx, 10, 15) as u8
+    }
+
+    /// Number of Unified Memory Controller (UMC) performance counters.
+    pub fn num_umc_counters(&self) -> u8 {
+        get_bits(self.ebx, 16, 21) as u8
+    }
+
+    /// Bitmap of the active UMCs (one bit per UMC).
+    pub fn active_umc_mask(&self) -> u32 {
+        self.ecx
+    }
+
+    /// Encode this struct back into the registers of leaf 0x8000_0022.
+    ///
+    /// EDX is reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: 0,
+        }
+    }
+
+    /// Set or clear the flag reported by [`Self::has_perfmon_v2`].
+    pub fn set_perfmon_v2(&mut self, bit: bool) {
+        set_bits(&mut self.eax, 0, 0, bit as u32);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_lbr_stack`].
+    pub fn set_lbr_stack(&mut self, bit: bool) {
+        set_bits(&mut self.eax, 1, 1, bit as u32);
+    }
+
+    /// Set or clear the flag reported by [`Self::has_lbr_and_pmc_freeze`].
+    pub fn set_lbr_and_pmc_freeze(&mut self, bit: bool) {
+        set_bits(&mut self.eax, 2, 2, bit as u32);
+    }
+
+    /// Set the value reported by [`Self::num_core_counters`].
+    pub fn set_num_core_counters(&mut self, value: u8) {
+        set_bits(&mut self.ebx, 0, 3, value as u32);
+    }
+
+    /// Set the value reported by [`Self::lbr_stack_size`].
+    pub fn set_lbr_stack_size(&mut self, value: u8) {
+        set_bits(&mut self.ebx, 4, 9, value as u32);
+    }
+
+    /// Set the value reported by [`Self::num_northbridge_counters`].
+    pub fn set_num_northbridge_counters(&mut self, value: u8) {
+        set_bits(&mut self.ebx, 10, 15, value as u32);
+    }
+
+    /// Set the value reported by [`Self::num_umc_counters`].
+    pub fn set_num_umc_counters(&mut self, value: u8) {
+        set_bits(&mut self.ebx, 16, 21, value as u32);
+    }
+
+    /// Set the value reported by [`Self::active_umc_mask`].
+    pub fn set_active_umc_mask(&mut self, value: u32) {
+        self.ecx = value;
+    }
+}
+
+impl Debug for PerformanceMonitoringDebugInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PerformanceMonitoringDebugInfo")
+            .field("has_perfmon_v2", &self.has_perfmon_v2())
+            .field("has_lbr_stack", &self.has_lbr_stack())
+            .field("has_lbr_and_pmc_freeze", &self.has_lbr_and_pmc_freeze())
+            .field("num_core_counters", &self.num_core_counters())
+            .field("lbr_stack_size", &self.lbr_stack_size())
+            .field("num_northbridge_counters", &self.num_northbridge_counters())
+            .field("num_umc_counters", &self.num_umc_counters())
+            .field("active_umc_mask", &self.active_umc_mask())
+            .finish()
+    }
+}
@@ -0,0 +1,147 @@
+//! Synthetic CPUs to exercise code paths that depend on CPUID, e.g., in tests
+//! of downstream crates.
+use crate::{CpuId, CpuIdBuilder, CpuIdReader, CpuIdSnapshot, CpuModel, Feature};
+
+/// A fabricated CPU with a chosen set of features.
+///
+/// Start from a preset (e.g., [`FakeCpu::skylake`]), a [`CpuModel`] or a
+/// real dump and enable or disable individual features. Only the feature bits
+/// are changed, features that depend on a disabled one (e.g., AVX512VL on
+/// AVX512F) stay as they are.
+///
+/// # Example
+/// ```
+/// use raw_cpuid::{FakeCpu, Feature};
+///
+/// let cpuid = FakeCpu::skylake_server().without(Feature::Avx512f).cpuid();
+/// assert!(cpuid.has_feature(Feature::Avx2));
+/// assert!(!cpuid.has_feature(Feature::Avx512f));
+///
+/// // Every combination of AVX2 and BMI2:
+/// for cpu in FakeCpu::skylake().variations(&[Feature::Avx2, Feature::Bmi2]) {
+///     let cpuid = cpu.cpuid();
+///     assert!(cpuid.has_feature(Feature::Sse2));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FakeCpu {
+    builder: CpuIdBuilder,
+}
+
+impl FakeCpu {
+    /// A CPU of `vendor` (e.g., `b"GenuineIntel"`) without any features.
+    pub fn new(vendor: &[u8; 12]) -> Self {
+        let mut builder = CpuIdBuilder::new();
+        builder.set_vendor(vendor);
+        Self { builder }
+    }
+
+    /// A CPU with the vendor and all features of `model`.
+    pub fn from_model(model: &CpuModel) -> Self {
+        let mut vendor = [0u8; 12];
+        vendor.copy_from_slice(model.vendor.as_bytes());
+
+        let mut cpu = Self::new(&vendor);
+        for feature in model.features() {
+            cpu.builder.set_feature(feature, true);
+        }
+        cpu
+    }
+
+    /// A CPU with all leafs reported by `read`, e.g., a
+    /// [`testdata`](crate::testdata) dump.
+    pub fn with_cpuid_reader<R: CpuIdReader>(read: R) -> Self {
+        Self {
+            builder: CpuIdBuilder::with_cpuid_reader(read),
+        }
+    }
+
+    /// Intel Skylake desktop CPU (`Skylake-Client`, family 6 model 0x5e).
+    pub fn skylake() -> Self {
+        Self::preset("Skylake-Client", 0x6, 0x5e, 3)
+    }
+
+    /// Intel Skylake server CPU with AVX-512 (`Skylake-Server`, family 6
+    /// model 0x55).
+    pub fn skylake_server() -> Self {
+        Self::preset("Skylake-Server", 0x6, 0x55, 4)
+    }
+
+    /// Intel Ice Lake server CPU (`Icelake-Server`, family 6 model 0x6a).
+    pub fn icelake_server() -> Self {
+        Self::preset("Icelake-Server", 0x6, 0x6a, 6)
+    }
+
+    /// AMD Zen 2 server CPU (`EPYC-Rome`, family 0x17 model 0x31).
+    pub fn epyc_rome() -> Self {
+        Self::preset("EPYC-Rome", 0x17, 0x31, 0)
+    }
+
+    /// AMD Zen 3 server CPU (`EPYC-Milan`, family 0x19 model 0x01).
+    pub fn epyc_milan() -> Self {
+        Self::preset("EPYC-Milan", 0x19, 0x01, 1)
+    }
+
+    fn preset(model: &str, family: u8, model_id: u8, stepping: u8) -> Self {
+        let mut cpu = Self::from_model(CpuModel::from_name(model).unwrap());
+        cpu.builder
+            .set_family_model_stepping(family, model_id, stepping);
+        cpu
+    }
+
+    /// Enables `feature`.
+    pub fn with(mut self, feature: Feature) -> Self {
+        self.builder.set_feature(feature, true);
+        self
+    }
+
+    /// Disables `feature`.
+    pub fn without(mut self, feature: Feature) -> Self {
+        self.builder.set_feature(feature, false);
+        self
+    }
+
+    /// Applies `f` to the underlying builder, e.g., to change leafs that
+    /// aren't feature flags.
+    pub fn modify<F: FnOnce(&mut CpuIdBuilder)>(mut self, f: F) -> Self {
+        f(&mut self.builder);
+        self
+    }
+
+    /// All `2^n` combinations of enabling and disabling the `n` given
+    /// features, starting with all of them disabled.
+    ///
+    /// # Panics
+    /// If more than 16 features are given.
+    pub fn variations<'a>(&'a self, features: &'a [Feature]) -> impl Iterator<Item = Self> + 'a {
+        assert!(features.len() <= 16, "too many features to combine");
+        (0u32..1 << features.len()).map(move |mask| {
+            let mut cpu = self.clone();
+            for (idx, &feature) in features.iter().enumerate() {
+                cpu.builder.set_feature(feature, mask & (1 << idx) != 0);
+            }
+            cpu
+        })
+    }
+
+    /// Is `feature` enabled?
+    pub fn has_feature(&self, feature: Feature) -> bool {
+        self.builder.has_feature(feature)
+    }
+
+    /// Builds a snapshot of all leafs of this CPU.
+    pub fn snapshot(&self) -> CpuIdSnapshot {
+        CpuIdSnapshot::from(self.builder.build())
+    }
+
+    /// Returns a [`CpuId`] that reads from this CPU.
+    pub fn cpuid(&self) -> CpuId<CpuIdSnapshot> {
+        CpuId::with_cpuid_reader(self.snapshot())
+    }
+}
+
+impl From<FakeCpu> for CpuIdSnapshot {
+    fn from(cpu: FakeCpu) -> Self {
+        cpu.snapshot()
+    }
+}
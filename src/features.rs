@@ -0,0 +1,1046 @@
+//! A uniform way to refer to individual feature flags across all leafs.
+//!
+//! Every info struct already provides a `has_*` method for its flags. The
+//! [`Feature`] enum additionally records *where* a flag lives (leaf, subleaf,
+//! register and bit) which is required to test, set or clear flags in raw
+//! [`CpuIdResult`] values.
+use core::fmt;
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign};
+
+use crate::{CpuId, CpuIdReader, CpuIdResult};
+
+/// One of the four registers returned by the CPUID instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Register {
+    Eax,
+    Ebx,
+    Ecx,
+    Edx,
+}
+
+impl Register {
+    /// Read the value of this register from `res`.
+    pub fn get(&self, res: &CpuIdResult) -> u32 {
+        match self {
+            Register::Eax => res.eax,
+            Register::Ebx => res.ebx,
+            Register::Ecx => res.ecx,
+            Register::Edx => res.edx,
+        }
+    }
+
+    /// Get a mutable reference to this register in `res`.
+    pub fn get_mut<'a>(&self, res: &'a mut CpuIdResult) -> &'a mut u32 {
+        match self {
+            Register::Eax => &mut res.eax,
+            Register::Ebx => &mut res.ebx,
+            Register::Ecx => &mut res.ecx,
+            Register::Edx => &mut res.edx,
+        }
+    }
+}
+
+impl fmt::Display for Register {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data = match self {
+            Register::Eax => "EAX",
+            Register::Ebx => "EBX",
+            Register::Ecx => "ECX",
+            Register::Edx => "EDX",
+        };
+
+        f.write_str(data)
+    }
+}
+
+/// The position of a feature flag in the CPUID leaf space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FeatureLocation {
+    /// Leaf (EAX input value).
+    pub leaf: u32,
+    /// Subleaf (ECX input value).
+    pub subleaf: u32,
+    /// Register that contains the flag.
+    pub register: Register,
+    /// Bit position of the flag in `register`.
+    pub bit: u8,
+}
+
+impl FeatureLocation {
+    /// Is the flag set in `res` (which has to be the result for `leaf` and
+    /// `subleaf`)?
+    pub fn is_set(&self, res: &CpuIdResult) -> bool {
+        self.register.get(res) & (1 << self.bit) > 0
+    }
+
+    /// Set or clear the flag in `res` (which has to be the result for `leaf`
+    /// and `subleaf`).
+    pub fn set(&self, res: &mut CpuIdResult, value: bool) {
+        let reg = self.register.get_mut(res);
+        if value {
+            *reg |= 1 << self.bit;
+        } else {
+            *reg &= !(1 << self.bit);
+        }
+    }
+}
+
+/// Position of a flag in the capability bitmap of the Linux kernel, i.e., an
+/// `X86_FEATURE_*` value (`word * 32 + bit`) from
+/// `arch/x86/include/asm/cpufeatures.h`.
+///
+/// Linux stores most CPUID registers one-to-one as a 32-bit word of the
+/// bitmap (see [`FeatureLocation::linux_bit`]). Flags from other registers
+/// are "scattered" into Linux-defined words and can't be mapped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LinuxFeatureBit {
+    /// Word of the bitmap (`NCAPINTS` index).
+    pub word: u8,
+    /// Bit within the word.
+    pub bit: u8,
+}
+
+impl LinuxFeatureBit {
+    /// The `X86_FEATURE_*` value, `word * 32 + bit`.
+    pub fn index(&self) -> u16 {
+        self.word as u16 * 32 + self.bit as u16
+    }
+
+    /// Splits an `X86_FEATURE_*` value into word and bit.
+    pub fn from_index(index: u16) -> Self {
+        LinuxFeatureBit {
+            word: (index / 32) as u8,
+            bit: (index % 32) as u8,
+        }
+    }
+}
+
+impl fmt::Display for LinuxFeatureBit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}*32+{}", self.word, self.bit)
+    }
+}
+
+/// The words of the Linux capability bitmap that are a copy of a CPUID
+/// register: `(word, leaf, subleaf, register)`.
+const LINUX_CPUID_WORDS: &[(u8, u32, u32, Register)] = &[
+    (0, 0x1, 0, Register::Edx),
+    (1, 0x8000_0001, 0, Register::Edx),
+    (2, 0x8086_0001, 0, Register::Edx),
+    (4, 0x1, 0, Register::Ecx),
+    (5, 0xC000_0001, 0, Register::Edx),
+    (6, 0x8000_0001, 0, Register::Ecx),
+    (9, 0x7, 0, Register::Ebx),
+    (10, 0xD, 1, Register::Eax),
+    (12, 0x7, 1, Register::Eax),
+    (13, 0x8000_0008, 0, Register::Ebx),
+    (14, 0x6, 0, Register::Eax),
+    (15, 0x8000_000A, 0, Register::Edx),
+    (16, 0x7, 0, Register::Ecx),
+    (17, 0x8000_0007, 0, Register::Ebx),
+    (18, 0x7, 0, Register::Edx),
+    (19, 0x8000_001F, 0, Register::Eax),
+    (20, 0x8000_0021, 0, Register::Eax),
+];
+
+impl FeatureLocation {
+    /// Position of this flag in the Linux capability bitmap, or `None` if
+    /// Linux doesn't copy the register one-to-one.
+    pub fn linux_bit(&self) -> Option<LinuxFeatureBit> {
+        LINUX_CPUID_WORDS
+            .iter()
+            .find(|&&(_, leaf, subleaf, register)| {
+                (leaf, subleaf, register) == (self.leaf, self.subleaf, self.register)
+            })
+            .map(|&(word, ..)| LinuxFeatureBit {
+                word,
+                bit: self.bit,
+            })
+    }
+
+    /// The CPUID flag at position `bit` of the Linux capability bitmap, or
+    /// `None` if it's in a Linux-defined word.
+    pub fn from_linux_bit(bit: LinuxFeatureBit) -> Option<FeatureLocation> {
+        LINUX_CPUID_WORDS
+            .iter()
+            .find(|&&(word, ..)| word == bit.word)
+            .filter(|_| bit.bit < 32)
+            .map(|&(_, leaf, subleaf, register)| FeatureLocation {
+                leaf,
+                subleaf,
+                register,
+                bit: bit.bit,
+            })
+    }
+}
+
+macro_rules! features {
+    ($($(#[$doc:meta])* $variant:ident => ($leaf:expr, $subleaf:expr, $reg:ident, $bit:expr, $name:expr),)*) => {
+        /// An individual CPUID feature flag.
+        ///
+        /// The [`Feature::name`] of a flag is the name used by Linux in
+        /// `/proc/cpuinfo` if there is one.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        #[non_exhaustive]
+        pub enum Feature {
+            $($(#[$doc])* $variant,)*
+        }
+
+        impl Feature {
+            /// All feature flags known to this library.
+            pub const ALL: &'static [Feature] = &[$(Feature::$variant,)*];
+
+            /// Where the flag is located.
+            pub fn location(&self) -> FeatureLocation {
+                match self {
+                    $(Feature::$variant => FeatureLocation {
+                        leaf: $leaf,
+                        subleaf: $subleaf,
+                        register: Register::$reg,
+                        bit: $bit,
+                    },)*
+                }
+            }
+
+            /// Short (lower-case) name of the flag, e.g., `avx512f`.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Feature::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
+
+features! {
+    /// Streaming SIMD Extensions 3 (SSE3).
+    Sse3 => (0x1, 0, Ecx, 0, "pni"),
+    /// PCLMULQDQ instruction.
+    Pclmulqdq => (0x1, 0, Ecx, 1, "pclmulqdq"),
+    /// 64-bit DS Area.
+    Dtes64 => (0x1, 0, Ecx, 2, "dtes64"),
+    /// MONITOR/MWAIT instructions.
+    Monitor => (0x1, 0, Ecx, 3, "monitor"),
+    /// CPL Qualified Debug Store.
+    DsCpl => (0x1, 0, Ecx, 4, "ds_cpl"),
+    /// Virtual Machine Extensions.
+    Vmx => (0x1, 0, Ecx, 5, "vmx"),
+    /// Safer Mode Extensions.
+    Smx => (0x1, 0, Ecx, 6, "smx"),
+    /// Enhanced Intel SpeedStep® technology.
+    Eist => (0x1, 0, Ecx, 7, "est"),
+    /// Thermal Monitor 2.
+    Tm2 => (0x1, 0, Ecx, 8, "tm2"),
+    /// Supplemental Streaming SIMD Extensions 3 (SSSE3).
+    Ssse3 => (0x1, 0, Ecx, 9, "ssse3"),
+    /// L1 Context ID.
+    CnxtId => (0x1, 0, Ecx, 10, "cid"),
+    /// FMA extensions using YMM state.
+    Fma => (0x1, 0, Ecx, 12, "fma"),
+    /// CMPXCHG16B instruction.
+    Cmpxchg16b => (0x1, 0, Ecx, 13, "cx16"),
+    /// Perfmon and Debug Capability.
+    Pdcm => (0x1, 0, Ecx, 15, "pdcm"),
+    /// Process-context identifiers.
+    Pcid => (0x1, 0, Ecx, 17, "pcid"),
+    /// Direct cache access.
+    Dca => (0x1, 0, Ecx, 18, "dca"),
+    /// SSE4.1
+    Sse41 => (0x1, 0, Ecx, 19, "sse4_1"),
+    /// SSE4.2
+    Sse42 => (0x1, 0, Ecx, 20, "sse4_2"),
+    /// x2APIC
+    X2apic => (0x1, 0, Ecx, 21, "x2apic"),
+    /// MOVBE instruction.
+    Movbe => (0x1, 0, Ecx, 22, "movbe"),
+    /// POPCNT instruction.
+    Popcnt => (0x1, 0, Ecx, 23, "popcnt"),
+    /// Local APIC timer supports TSC deadline mode.
+    TscDeadline => (0x1, 0, Ecx, 24, "tsc_deadline_timer"),
+    /// AESNI instruction extensions.
+    Aesni => (0x1, 0, Ecx, 25, "aes"),
+    /// XSAVE/XRSTOR processor extended states.
+    Xsave => (0x1, 0, Ecx, 26, "xsave"),
+    /// OS has enabled XSETBV/XGETBV.
+    Osxsave => (0x1, 0, Ecx, 27, "osxsave"),
+    /// AVX instruction extensions.
+    Avx => (0x1, 0, Ecx, 28, "avx"),
+    /// 16-bit floating-point conversion instructions.
+    F16c => (0x1, 0, Ecx, 29, "f16c"),
+    /// RDRAND instruction.
+    Rdrand => (0x1, 0, Ecx, 30, "rdrand"),
+    /// Running under a hypervisor.
+    Hypervisor => (0x1, 0, Ecx, 31, "hypervisor"),
+    /// Floating Point Unit On-Chip.
+    Fpu => (0x1, 0, Edx, 0, "fpu"),
+    /// Virtual 8086 Mode Enhancements.
+    Vme => (0x1, 0, Edx, 1, "vme"),
+    /// Debugging Extensions.
+    De => (0x1, 0, Edx, 2, "de"),
+    /// Page Size Extension.
+    Pse => (0x1, 0, Edx, 3, "pse"),
+    /// Time Stamp Counter.
+    Tsc => (0x1, 0, Edx, 4, "tsc"),
+    /// RDMSR and WRMSR instructions.
+    Msr => (0x1, 0, Edx, 5, "msr"),
+    /// Physical Address Extension.
+    Pae => (0x1, 0, Edx, 6, "pae"),
+    /// Machine Check Exception.
+    Mce => (0x1, 0, Edx, 7, "mce"),
+    /// CMPXCHG8B instruction.
+    Cx8 => (0x1, 0, Edx, 8, "cx8"),
+    /// APIC On-Chip.
+    Apic => (0x1, 0, Edx, 9, "apic"),
+    /// SYSENTER and SYSEXIT instructions.
+    Sep => (0x1, 0, Edx, 11, "sep"),
+    /// Memory Type Range Registers.
+    Mtrr => (0x1, 0, Edx, 12, "mtrr"),
+    /// Page Global Bit.
+    Pge => (0x1, 0, Edx, 13, "pge"),
+    /// Machine Check Architecture.
+    Mca => (0x1, 0, Edx, 14, "mca"),
+    /// Conditional Move Instructions.
+    Cmov => (0x1, 0, Edx, 15, "cmov"),
+    /// Page Attribute Table.
+    Pat => (0x1, 0, Edx, 16, "pat"),
+    /// 36-Bit Page Size Extension.
+    Pse36 => (0x1, 0, Edx, 17, "pse36"),
+    /// Processor Serial Number.
+    Psn => (0x1, 0, Edx, 18, "pn"),
+    /// CLFLUSH instruction.
+    Clflush => (0x1, 0, Edx, 19, "clflush"),
+    /// Debug Store.
+    Ds => (0x1, 0, Edx, 21, "dts"),
+    /// Thermal Monitor and Software Controlled Clock Facilities.
+    Acpi => (0x1, 0, Edx, 22, "acpi"),
+    /// Intel MMX Technology.
+    Mmx => (0x1, 0, Edx, 23, "mmx"),
+    /// FXSAVE and FXRSTOR instructions.
+    Fxsr => (0x1, 0, Edx, 24, "fxsr"),
+    /// SSE extensions.
+    Sse => (0x1, 0, Edx, 25, "sse"),
+    /// SSE2 extensions.
+    Sse2 => (0x1, 0, Edx, 26, "sse2"),
+    /// Self Snoop.
+    Ss => (0x1, 0, Edx, 27, "ss"),
+    /// Max APIC IDs reserved field is valid.
+    Htt => (0x1, 0, Edx, 28, "ht"),
+    /// Thermal Monitor.
+    Tm => (0x1, 0, Edx, 29, "tm"),
+    /// Pending Break Enable.
+    Pbe => (0x1, 0, Edx, 31, "pbe"),
+    /// APIC timer always running (Always Running APIC Timer).
+    Arat => (0x6, 0, Eax, 2, "arat"),
+    /// RDFSBASE/RDGSBASE/WRFSBASE/WRGSBASE instructions.
+    Fsgsbase => (0x7, 0, Ebx, 0, "fsgsbase"),
+    /// IA32_TSC_ADJUST MSR.
+    TscAdjust => (0x7, 0, Ebx, 1, "tsc_adjust"),
+    /// Software Guard Extensions.
+    Sgx => (0x7, 0, Ebx, 2, "sgx"),
+    /// Bit Manipulation Instruction Set 1.
+    Bmi1 => (0x7, 0, Ebx, 3, "bmi1"),
+    /// Hardware Lock Elision.
+    Hle => (0x7, 0, Ebx, 4, "hle"),
+    /// Advanced Vector Extensions 2.
+    Avx2 => (0x7, 0, Ebx, 5, "avx2"),
+    /// x87 FPU Data Pointer updated only on x87 exceptions.
+    FdpExcptnOnly => (0x7, 0, Ebx, 6, "fdp_excptn_only"),
+    /// Supervisor-Mode Execution Prevention.
+    Smep => (0x7, 0, Ebx, 7, "smep"),
+    /// Bit Manipulation Instruction Set 2.
+    Bmi2 => (0x7, 0, Ebx, 8, "bmi2"),
+    /// Enhanced REP MOVSB/STOSB.
+    Erms => (0x7, 0, Ebx, 9, "erms"),
+    /// INVPCID instruction.
+    Invpcid => (0x7, 0, Ebx, 10, "invpcid"),
+    /// Restricted Transactional Memory.
+    Rtm => (0x7, 0, Ebx, 11, "rtm"),
+    /// Intel Resource Director Technology Monitoring.
+    RdtM => (0x7, 0, Ebx, 12, "cqm"),
+    /// FPU CS and FPU DS values are deprecated.
+    ZeroFcsFds => (0x7, 0, Ebx, 13, "zero_fcs_fds"),
+    /// Memory Protection Extensions.
+    Mpx => (0x7, 0, Ebx, 14, "mpx"),
+    /// Intel Resource Director Technology Allocation.
+    RdtA => (0x7, 0, Ebx, 15, "rdt_a"),
+    /// AVX-512 Foundation.
+    Avx512f => (0x7, 0, Ebx, 16, "avx512f"),
+    /// AVX-512 Doubleword and Quadword Instructions.
+    Avx512dq => (0x7, 0, Ebx, 17, "avx512dq"),
+    /// RDSEED instruction.
+    Rdseed => (0x7, 0, Ebx, 18, "rdseed"),
+    /// ADCX and ADOX instructions.
+    Adx => (0x7, 0, Ebx, 19, "adx"),
+    /// Supervisor-Mode Access Prevention.
+    Smap => (0x7, 0, Ebx, 20, "smap"),
+    /// AVX-512 Integer Fused Multiply-Add Instructions.
+    Avx512ifma => (0x7, 0, Ebx, 21, "avx512ifma"),
+    /// CLFLUSHOPT instruction.
+    Clflushopt => (0x7, 0, Ebx, 23, "clflushopt"),
+    /// CLWB instruction.
+    Clwb => (0x7, 0, Ebx, 24, "clwb"),
+    /// Intel Processor Trace.
+    IntelPt => (0x7, 0, Ebx, 25, "intel_pt"),
+    /// AVX-512 Prefetch Instructions.
+    Avx512pf => (0x7, 0, Ebx, 26, "avx512pf"),
+    /// AVX-512 Exponential and Reciprocal Instructions.
+    Avx512er => (0x7, 0, Ebx, 27, "avx512er"),
+    /// AVX-512 Conflict Detection Instructions.
+    Avx512cd => (0x7, 0, Ebx, 28, "avx512cd"),
+    /// SHA extensions.
+    Sha => (0x7, 0, Ebx, 29, "sha_ni"),
+    /// AVX-512 Byte and Word Instructions.
+    Avx512bw => (0x7, 0, Ebx, 30, "avx512bw"),
+    /// AVX-512 Vector Length Extensions.
+    Avx512vl => (0x7, 0, Ebx, 31, "avx512vl"),
+    /// PREFETCHWT1 instruction.
+    Prefetchwt1 => (0x7, 0, Ecx, 0, "prefetchwt1"),
+    /// AVX-512 Vector Bit Manipulation Instructions.
+    Avx512vbmi => (0x7, 0, Ecx, 1, "avx512vbmi"),
+    /// User-Mode Instruction Prevention.
+    Umip => (0x7, 0, Ecx, 2, "umip"),
+    /// Protection Keys for User-mode pages.
+    Pku => (0x7, 0, Ecx, 3, "pku"),
+    /// OS has set CR4.PKE to enable protection keys.
+    Ospke => (0x7, 0, Ecx, 4, "ospke"),
+    /// User-level wait (TPAUSE, UMONITOR, UMWAIT).
+    Waitpkg => (0x7, 0, Ecx, 5, "waitpkg"),
+    /// AVX-512 Vector Byte Manipulation Instructions 2.
+    Avx512vbmi2 => (0x7, 0, Ecx, 6, "avx512_vbmi2"),
+    /// CET shadow stacks.
+    CetSs => (0x7, 0, Ecx, 7, "shstk"),
+    /// Galois Field instructions.
+    Gfni => (0x7, 0, Ecx, 8, "gfni"),
+    /// Vector AES instructions.
+    Vaes => (0x7, 0, Ecx, 9, "vaes"),
+    /// Vector carry-less multiplication (VPCLMULQDQ).
+    Vpclmulqdq => (0x7, 0, Ecx, 10, "vpclmulqdq"),
+    /// AVX-512 Vector Neural Network Instructions.
+    Avx512vnni => (0x7, 0, Ecx, 11, "avx512_vnni"),
+    /// AVX-512 Bit Algorithms.
+    Avx512bitalg => (0x7, 0, Ecx, 12, "avx512_bitalg"),
+    /// Total Memory Encryption.
+    Tme => (0x7, 0, Ecx, 13, "tme"),
+    /// AVX-512 Vector Population Count for doublewords and quadwords.
+    Avx512vpopcntdq => (0x7, 0, Ecx, 14, "avx512_vpopcntdq"),
+    /// 57-bit linear addresses and five-level paging.
+    La57 => (0x7, 0, Ecx, 16, "la57"),
+    /// RDPID instruction.
+    Rdpid => (0x7, 0, Ecx, 22, "rdpid"),
+    /// Key Locker (AES wrapping keys).
+    Kl => (0x7, 0, Ecx, 23, "kl"),
+    /// CLDEMOTE instruction (cache line demote).
+    Cldemote => (0x7, 0, Ecx, 25, "cldemote"),
+    /// Direct store of a doubleword or quadword.
+    Movdiri => (0x7, 0, Ecx, 27, "movdiri"),
+    /// Direct store of 64 bytes.
+    Movdir64b => (0x7, 0, Ecx, 28, "movdir64b"),
+    /// Enqueue stores.
+    Enqcmd => (0x7, 0, Ecx, 29, "enqcmd"),
+    /// SGX Launch Configuration.
+    SgxLc => (0x7, 0, Ecx, 30, "sgx_lc"),
+    /// Protection Keys for Supervisor-mode pages.
+    Pks => (0x7, 0, Ecx, 31, "pks"),
+    /// AVX-512 4-iteration Vector Neural Network Instructions (Knights Mill).
+    Avx5124vnniw => (0x7, 0, Edx, 2, "avx512_4vnniw"),
+    /// AVX-512 4-iteration Multiply Accumulation Single precision (Knights
+    /// Mill).
+    Avx5124fmaps => (0x7, 0, Edx, 3, "avx512_4fmaps"),
+    /// User interrupts.
+    Uintr => (0x7, 0, Edx, 5, "uintr"),
+    /// AVX-512 VP2INTERSECT instructions.
+    Avx512vp2intersect => (0x7, 0, Edx, 8, "avx512_vp2intersect"),
+    /// All RTM transactions abort.
+    RtmAlwaysAbort => (0x7, 0, Edx, 11, "rtm_always_abort"),
+    /// TSX_FORCE_ABORT MSR.
+    TsxForceAbort => (0x7, 0, Edx, 13, "tsx_force_abort"),
+    /// SERIALIZE instruction.
+    Serialize => (0x7, 0, Edx, 14, "serialize"),
+    /// Hybrid processor with different core types.
+    Hybrid => (0x7, 0, Edx, 15, "hybrid_cpu"),
+    /// TSX suspend load address tracking.
+    Tsxldtrk => (0x7, 0, Edx, 16, "tsxldtrk"),
+    /// PCONFIG instruction.
+    Pconfig => (0x7, 0, Edx, 18, "pconfig"),
+    /// Architectural last branch records.
+    ArchLbr => (0x7, 0, Edx, 19, "arch_lbr"),
+    /// CET indirect branch tracking.
+    CetIbt => (0x7, 0, Edx, 20, "ibt"),
+    /// AMX tile operations on BF16 numbers.
+    AmxBf16 => (0x7, 0, Edx, 22, "amx_bf16"),
+    /// AVX-512 FP16 instructions.
+    Avx512fp16 => (0x7, 0, Edx, 23, "avx512_fp16"),
+    /// AMX tile architecture.
+    AmxTile => (0x7, 0, Edx, 24, "amx_tile"),
+    /// AMX tile operations on 8-bit integers.
+    AmxInt8 => (0x7, 0, Edx, 25, "amx_int8"),
+    /// Remote atomic operations.
+    RaoInt => (0x7, 1, Eax, 3, "rao_int"),
+    /// VEX-encoded Vector Neural Network Instructions.
+    AvxVnni => (0x7, 1, Eax, 4, "avx_vnni"),
+    /// AVX-512 BFLOAT16 instructions.
+    Avx512bf16 => (0x7, 1, Eax, 5, "avx512_bf16"),
+    /// Compare and add if condition is met.
+    Cmpccxadd => (0x7, 1, Eax, 7, "cmpccxadd"),
+    /// Architectural performance monitoring extended leaf.
+    ArchPerfmonExt => (0x7, 1, Eax, 8, "arch_perfmon_ext"),
+    /// Non-serializing WRMSR.
+    Wrmsrns => (0x7, 1, Eax, 19, "wrmsrns"),
+    /// AMX tile operations on FP16 numbers.
+    AmxFp16 => (0x7, 1, Eax, 21, "amx_fp16"),
+    /// History reset instruction.
+    Hreset => (0x7, 1, Eax, 22, "hreset"),
+    /// RDMSRLIST and WRMSRLIST instructions.
+    Msrlist => (0x7, 1, Eax, 27, "msrlist"),
+    /// Protected Processor Inventory Number.
+    Ppin => (0x7, 1, Ebx, 0, "intel_ppin"),
+    /// PBNDKB instruction.
+    Pbndkb => (0x7, 1, Ebx, 1, "pbndkb"),
+    /// VEX-encoded dot products of signed/unsigned bytes.
+    AvxVnniInt8 => (0x7, 1, Edx, 4, "avx_vnni_int8"),
+    /// VEX-encoded BF16/FP16 conversions without exceptions.
+    AvxNeConvert => (0x7, 1, Edx, 5, "avx_ne_convert"),
+    /// AMX tile operations on complex FP16 numbers.
+    AmxComplex => (0x7, 1, Edx, 8, "amx_complex"),
+    /// VEX-encoded dot products of signed/unsigned words.
+    AvxVnniInt16 => (0x7, 1, Edx, 10, "avx_vnni_int16"),
+    /// PREFETCHIT0/1 instructions.
+    Prefetchi => (0x7, 1, Edx, 14, "prefetchi"),
+    /// AVX10 converged vector ISA.
+    Avx10 => (0x7, 1, Edx, 19, "avx10"),
+    /// Advanced Performance Extensions Foundation.
+    ApxF => (0x7, 1, Edx, 21, "apx_f"),
+    /// Predictive store forwarding disable.
+    Psfd => (0x7, 2, Edx, 0, "psfd"),
+    /// Indirect branch predictor controls.
+    IpredCtrl => (0x7, 2, Edx, 1, "ipred_ctrl"),
+    /// Restricted RSB alternate controls.
+    RrsbaCtrl => (0x7, 2, Edx, 2, "rrsba_ctrl"),
+    /// Data dependent prefetcher disable.
+    DdpdU => (0x7, 2, Edx, 3, "ddpd_u"),
+    /// Branch history injection controls.
+    BhiCtrl => (0x7, 2, Edx, 4, "bhi_ctrl"),
+    /// Not affected by MXCSR configuration dependent timing.
+    McdtNo => (0x7, 2, Edx, 5, "mcdt_no"),
+    /// XSAVEOPT instruction.
+    Xsaveopt => (0xD, 1, Eax, 0, "xsaveopt"),
+    /// XSAVEC instruction (compacted XSAVE).
+    Xsavec => (0xD, 1, Eax, 1, "xsavec"),
+    /// XGETBV with ECX=1 (XINUSE state).
+    Xgetbv1 => (0xD, 1, Eax, 2, "xgetbv1"),
+    /// XSAVES/XRSTORS instructions and the IA32_XSS MSR.
+    Xsaves => (0xD, 1, Eax, 3, "xsaves"),
+    /// LAHF/SAHF available in 64-bit mode.
+    LahfLm => (0x8000_0001, 0, Ecx, 0, "lahf_lm"),
+    /// Core multi-processing legacy mode.
+    CmpLegacy => (0x8000_0001, 0, Ecx, 1, "cmp_legacy"),
+    /// Secure Virtual Machine.
+    Svm => (0x8000_0001, 0, Ecx, 2, "svm"),
+    /// Extended APIC space.
+    ExtApic => (0x8000_0001, 0, Ecx, 3, "extapic"),
+    /// LOCK MOV CR0 means MOV CR8.
+    Cr8Legacy => (0x8000_0001, 0, Ecx, 4, "cr8_legacy"),
+    /// LZCNT instruction.
+    Lzcnt => (0x8000_0001, 0, Ecx, 5, "abm"),
+    /// SSE4A instructions.
+    Sse4a => (0x8000_0001, 0, Ecx, 6, "sse4a"),
+    /// Misaligned SSE mode.
+    MisalignSse => (0x8000_0001, 0, Ecx, 7, "misalignsse"),
+    /// PREFETCH and PREFETCHW instructions.
+    Prefetchw => (0x8000_0001, 0, Ecx, 8, "3dnowprefetch"),
+    /// OS Visible Work-around.
+    Osvw => (0x8000_0001, 0, Ecx, 9, "osvw"),
+    /// Instruction Based Sampling.
+    Ibs => (0x8000_0001, 0, Ecx, 10, "ibs"),
+    /// Extended operation support.
+    Xop => (0x8000_0001, 0, Ecx, 11, "xop"),
+    /// SKINIT and STGI instructions.
+    Skinit => (0x8000_0001, 0, Ecx, 12, "skinit"),
+    /// Watchdog timer.
+    Wdt => (0x8000_0001, 0, Ecx, 13, "wdt"),
+    /// Lightweight profiling.
+    Lwp => (0x8000_0001, 0, Ecx, 15, "lwp"),
+    /// Four-operand FMA instructions.
+    Fma4 => (0x8000_0001, 0, Ecx, 16, "fma4"),
+    /// Translation cache extension.
+    Tce => (0x8000_0001, 0, Ecx, 17, "tce"),
+    /// Node ID MSR.
+    NodeIdMsr => (0x8000_0001, 0, Ecx, 19, "nodeid_msr"),
+    /// Trailing bit manipulation.
+    Tbm => (0x8000_0001, 0, Ecx, 21, "tbm"),
+    /// Topology extensions.
+    TopoExt => (0x8000_0001, 0, Ecx, 22, "topoext"),
+    /// Processor performance counter extensions.
+    PerfCtrCore => (0x8000_0001, 0, Ecx, 23, "perfctr_core"),
+    /// NB performance counter extensions.
+    PerfCtrNb => (0x8000_0001, 0, Ecx, 24, "perfctr_nb"),
+    /// Data access breakpoint extension.
+    BpExt => (0x8000_0001, 0, Ecx, 26, "bpext"),
+    /// Performance time-stamp counter.
+    PerfTsc => (0x8000_0001, 0, Ecx, 27, "ptsc"),
+    /// L3 performance counter extension.
+    PerfCtrLlc => (0x8000_0001, 0, Ecx, 28, "perfctr_llc"),
+    /// MWAITX and MONITORX instructions.
+    MwaitX => (0x8000_0001, 0, Ecx, 29, "mwaitx"),
+    /// Breakpoint addressing masking extended to bit 31.
+    AddrMaskExt => (0x8000_0001, 0, Ecx, 30, "addr_mask_ext"),
+    /// SYSCALL and SYSRET instructions.
+    Syscall => (0x8000_0001, 0, Edx, 11, "syscall"),
+    /// No-execute page protection.
+    Nx => (0x8000_0001, 0, Edx, 20, "nx"),
+    /// AMD extensions to MMX instructions.
+    MmxExt => (0x8000_0001, 0, Edx, 22, "mmxext"),
+    /// FXSAVE and FXRSTOR instruction optimizations.
+    FxsrOpt => (0x8000_0001, 0, Edx, 25, "fxsr_opt"),
+    /// 1-GByte pages.
+    Pdpe1gb => (0x8000_0001, 0, Edx, 26, "pdpe1gb"),
+    /// RDTSCP instruction.
+    Rdtscp => (0x8000_0001, 0, Edx, 27, "rdtscp"),
+    /// 64-bit long mode.
+    Lm => (0x8000_0001, 0, Edx, 29, "lm"),
+    /// AMD extensions to 3DNow! instructions.
+    ThreeDNowExt => (0x8000_0001, 0, Edx, 30, "3dnowext"),
+    /// 3DNow! instructions.
+    ThreeDNow => (0x8000_0001, 0, Edx, 31, "3dnow"),
+    /// Invariant TSC.
+    InvariantTsc => (0x8000_0007, 0, Edx, 8, "invtsc"),
+    /// CLZERO instruction.
+    Clzero => (0x8000_0008, 0, Ebx, 0, "clzero"),
+    /// Instruction Retired Counter MSR.
+    IrPerf => (0x8000_0008, 0, Ebx, 1, "irperf"),
+    /// FP Error Pointers Restored by XRSTOR.
+    XsaveErPtr => (0x8000_0008, 0, Ebx, 2, "xsaveerptr"),
+    /// INVLPGB and TLBSYNC instructions.
+    Invlpgb => (0x8000_0008, 0, Ebx, 3, "invlpgb"),
+    /// RDPRU instruction.
+    Rdpru => (0x8000_0008, 0, Ebx, 4, "rdpru"),
+    /// MCOMMIT instruction.
+    Mcommit => (0x8000_0008, 0, Ebx, 8, "mcommit"),
+    /// WBNOINVD instruction.
+    Wbnoinvd => (0x8000_0008, 0, Ebx, 9, "wbnoinvd"),
+    /// Indirect Branch Prediction Barrier.
+    AmdIbpb => (0x8000_0008, 0, Ebx, 12, "amd_ibpb"),
+    /// WBINVD/WBNOINVD are interruptible.
+    IntWbinvd => (0x8000_0008, 0, Ebx, 13, "int_wbinvd"),
+    /// Indirect Branch Restricted Speculation.
+    AmdIbrs => (0x8000_0008, 0, Ebx, 14, "amd_ibrs"),
+    /// Single Thread Indirect Branch Predictor.
+    AmdStibp => (0x8000_0008, 0, Ebx, 15, "amd_stibp"),
+    /// IBRS is preferred to be always on.
+    AmdIbrsAlwaysOn => (0x8000_0008, 0, Ebx, 16, "amd_ibrs_always_on"),
+    /// STIBP is preferred to be always on.
+    AmdStibpAlwaysOn => (0x8000_0008, 0, Ebx, 17, "amd_stibp_always_on"),
+    /// IBRS is preferred over software mitigations.
+    AmdIbrsPreferred => (0x8000_0008, 0, Ebx, 18, "amd_ibrs_preferred"),
+    /// IBRS protects against same privilege level branch targets.
+    AmdIbrsSameMode => (0x8000_0008, 0, Ebx, 19, "amd_ibrs_same_mode"),
+    /// EFER.LMSLE is unsupported.
+    EferLmsleUnsupported => (0x8000_0008, 0, Ebx, 20, "efer_lmsle_unsupported"),
+    /// INVLPGB support for invalidating guest nested translations.
+    InvlpgbNested => (0x8000_0008, 0, Ebx, 21, "invlpgb_nested"),
+    /// Protected Processor Inventory Number.
+    AmdPpin => (0x8000_0008, 0, Ebx, 23, "amd_ppin"),
+    /// Speculative Store Bypass Disable.
+    AmdSsbd => (0x8000_0008, 0, Ebx, 24, "amd_ssbd"),
+    /// Speculative Store Bypass Disable through VIRT_SPEC_CTRL.
+    VirtSsbd => (0x8000_0008, 0, Ebx, 25, "virt_ssbd"),
+    /// Not affected by Speculative Store Bypass.
+    AmdSsbNo => (0x8000_0008, 0, Ebx, 26, "amd_ssb_no"),
+    /// Collaborative Processor Performance Control.
+    Cppc => (0x8000_0008, 0, Ebx, 27, "cppc"),
+    /// Predictive Store Forwarding Disable.
+    AmdPsfd => (0x8000_0008, 0, Ebx, 28, "amd_psfd"),
+    /// Not affected by Branch Type Confusion.
+    BtcNo => (0x8000_0008, 0, Ebx, 29, "btc_no"),
+    /// IBPB clears the return address predictor.
+    AmdIbpbRet => (0x8000_0008, 0, Ebx, 30, "amd_ibpb_ret"),
+    /// Transmeta processor is running the recovery Code Morphing Software.
+    Recovery => (0x8086_0001, 0, Edx, 0, "recovery"),
+    /// Transmeta LongRun power management.
+    LongRun => (0x8086_0001, 0, Edx, 1, "longrun"),
+    /// Transmeta LongRun Table Interface.
+    Lrti => (0x8086_0001, 0, Edx, 3, "lrti"),
+    /// PadLock random number generator (XSTORE) present.
+    PadLockRng => (0xC000_0001, 0, Edx, 2, "rng"),
+    /// PadLock random number generator enabled.
+    PadLockRngEnabled => (0xC000_0001, 0, Edx, 3, "rng_en"),
+    /// PadLock Advanced Cryptography Engine (XCRYPT) present.
+    PadLockAce => (0xC000_0001, 0, Edx, 6, "ace"),
+    /// PadLock Advanced Cryptography Engine enabled.
+    PadLockAceEnabled => (0xC000_0001, 0, Edx, 7, "ace_en"),
+    /// PadLock Advanced Cryptography Engine 2 present.
+    PadLockAce2 => (0xC000_0001, 0, Edx, 8, "ace2"),
+    /// PadLock Advanced Cryptography Engine 2 enabled.
+    PadLockAce2Enabled => (0xC000_0001, 0, Edx, 9, "ace2_en"),
+    /// PadLock Hash Engine (SHA-1/SHA-256) present.
+    PadLockPhe => (0xC000_0001, 0, Edx, 10, "phe"),
+    /// PadLock Hash Engine enabled.
+    PadLockPheEnabled => (0xC000_0001, 0, Edx, 11, "phe_en"),
+    /// PadLock Montgomery Multiplier present.
+    PadLockPmm => (0xC000_0001, 0, Edx, 12, "pmm"),
+    /// PadLock Montgomery Multiplier enabled.
+    PadLockPmmEnabled => (0xC000_0001, 0, Edx, 13, "pmm_en"),
+}
+
+impl Feature {
+    /// Look up a feature by its [`Feature::name`].
+    pub fn from_name(name: &str) -> Option<Feature> {
+        Feature::ALL.iter().copied().find(|f| f.name() == name)
+    }
+
+    /// Position of the flag in the Linux capability bitmap (the
+    /// `X86_FEATURE_*` value), see [`FeatureLocation::linux_bit`].
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::Feature;
+    ///
+    /// // X86_FEATURE_AVX512F is (9*32+16).
+    /// assert_eq!(Feature::Avx512f.linux_bit().unwrap().index(), 9 * 32 + 16);
+    /// ```
+    pub fn linux_bit(&self) -> Option<LinuxFeatureBit> {
+        self.location().linux_bit()
+    }
+
+    /// Look up a feature by its position in the Linux capability bitmap.
+    ///
+    /// Returns `None` for Linux-defined flags and for CPUID flags this
+    /// library doesn't know.
+    pub fn from_linux_bit(bit: LinuxFeatureBit) -> Option<Feature> {
+        let location = FeatureLocation::from_linux_bit(bit)?;
+        Feature::ALL
+            .iter()
+            .copied()
+            .find(|f| f.location() == location)
+    }
+}
+
+impl<R: CpuIdReader> CpuId<R> {
+    /// Is `feature` supported?
+    ///
+    /// Returns `false` if the leaf that contains the flag isn't supported.
+    pub fn has_feature(&self, feature: Feature) -> bool {
+        let loc = feature.location();
+        self.leaf_is_supported(loc.leaf) && loc.is_set(&self.read.cpuid2(loc.leaf, loc.subleaf))
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Number of `u64` words needed for one bit per [`Feature`].
+const FEATURE_WORDS: usize = Feature::ALL.len().div_ceil(64);
+
+/// A compact set of [`Feature`]s with one bit per feature.
+///
+/// Useful to store the capabilities of many machines and to compute the
+/// features they have in common.
+///
+/// With the `serde` feature the set is serialized as a list of flag names
+/// (see [`flag_names`]), so stored sets stay valid when features are added
+/// to this library.
+///
+/// # Example
+/// ```
+/// use raw_cpuid::{CpuFeatures, CpuId, Feature};
+///
+/// let host = CpuId::new().features();
+/// let baseline: CpuFeatures = [Feature::Sse2, Feature::Avx2].into_iter().collect();
+/// let common = host & baseline;
+/// assert!(common.is_subset(&baseline));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CpuFeatures {
+    /// Indexed by the position of the feature in [`Feature::ALL`], which
+    /// changes between releases. Never persist it.
+    bits: [u64; FEATURE_WORDS],
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CpuFeatures {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        flag_names::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CpuFeatures {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        flag_names::deserialize(deserializer)
+    }
+}
+
+impl CpuFeatures {
+    /// An empty set.
+    pub const fn new() -> Self {
+        CpuFeatures {
+            bits: [0; FEATURE_WORDS],
+        }
+    }
+
+    /// The set of all features known to this library ([`Feature::ALL`]).
+    pub fn all() -> Self {
+        Feature::ALL.iter().copied().collect()
+    }
+
+    fn position(feature: Feature) -> (usize, u64) {
+        let index = feature as usize;
+        (index / 64, 1 << (index % 64))
+    }
+
+    /// Is `feature` in the set?
+    pub fn contains(&self, feature: Feature) -> bool {
+        let (word, mask) = Self::position(feature);
+        self.bits[word] & mask != 0
+    }
+
+    /// Adds `feature` to the set.
+    pub fn insert(&mut self, feature: Feature) {
+        self.set(feature, true);
+    }
+
+    /// Removes `feature` from the set.
+    pub fn remove(&mut self, feature: Feature) {
+        self.set(feature, false);
+    }
+
+    /// Adds (`value == true`) or removes `feature`.
+    pub fn set(&mut self, feature: Feature, value: bool) {
+        let (word, mask) = Self::position(feature);
+        if value {
+            self.bits[word] |= mask;
+        } else {
+            self.bits[word] &= !mask;
+        }
+    }
+
+    /// Number of features in the set.
+    pub fn len(&self) -> usize {
+        self.bits.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Is the set empty?
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&w| w == 0)
+    }
+
+    /// Features in both `self` and `other`.
+    pub fn intersection(&self, other: &CpuFeatures) -> CpuFeatures {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    /// Features in `self` or `other`.
+    pub fn union(&self, other: &CpuFeatures) -> CpuFeatures {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    /// Features in `self` but not in `other`.
+    pub fn difference(&self, other: &CpuFeatures) -> CpuFeatures {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    /// Are all features of `self` also in `other`?
+    pub fn is_subset(&self, other: &CpuFeatures) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    fn zip_with(&self, other: &CpuFeatures, op: impl Fn(u64, u64) -> u64) -> CpuFeatures {
+        let mut result = *self;
+        for (word, &other) in result.bits.iter_mut().zip(other.bits.iter()) {
+            *word = op(*word, other);
+        }
+        result
+    }
+
+    /// Iterates over the features in the set (in the order of
+    /// [`Feature::ALL`]).
+    pub fn iter(&self) -> CpuFeaturesIter {
+        CpuFeaturesIter {
+            features: *self,
+            index: 0,
+        }
+    }
+}
+
+impl fmt::Debug for CpuFeatures {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl From<Feature> for CpuFeatures {
+    fn from(feature: Feature) -> Self {
+        let mut features = CpuFeatures::new();
+        features.insert(feature);
+        features
+    }
+}
+
+impl FromIterator<Feature> for CpuFeatures {
+    fn from_iter<I: IntoIterator<Item = Feature>>(iter: I) -> Self {
+        let mut features = CpuFeatures::new();
+        features.extend(iter);
+        features
+    }
+}
+
+impl Extend<Feature> for CpuFeatures {
+    fn extend<I: IntoIterator<Item = Feature>>(&mut self, iter: I) {
+        for feature in iter {
+            self.insert(feature);
+        }
+    }
+}
+
+impl IntoIterator for CpuFeatures {
+    type Item = Feature;
+    type IntoIter = CpuFeaturesIter;
+
+    fn into_iter(self) -> CpuFeaturesIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &CpuFeatures {
+    type Item = Feature;
+    type IntoIter = CpuFeaturesIter;
+
+    fn into_iter(self) -> CpuFeaturesIter {
+        self.iter()
+    }
+}
+
+macro_rules! cpu_features_op {
+    ($trait:ident, $fun:ident, $assign_trait:ident, $assign_fun:ident, $method:ident) => {
+        impl $trait for CpuFeatures {
+            type Output = CpuFeatures;
+
+            fn $fun(self, other: CpuFeatures) -> CpuFeatures {
+                self.$method(&other)
+            }
+        }
+
+        impl $assign_trait for CpuFeatures {
+            fn $assign_fun(&mut self, other: CpuFeatures) {
+                *self = self.$method(&other);
+            }
+        }
+    };
+}
+
+cpu_features_op!(BitAnd, bitand, BitAndAssign, bitand_assign, intersection);
+cpu_features_op!(BitOr, bitor, BitOrAssign, bitor_assign, union);
+cpu_features_op!(Sub, sub, SubAssign, sub_assign, difference);
+
+/// Iterator over the features in a [`CpuFeatures`] set.
+#[derive(Debug, Clone)]
+pub struct CpuFeaturesIter {
+    features: CpuFeatures,
+    index: usize,
+}
+
+impl Iterator for CpuFeaturesIter {
+    type Item = Feature;
+
+    fn next(&mut self) -> Option<Feature> {
+        while let Some(&feature) = Feature::ALL.get(self.index) {
+            self.index += 1;
+            if self.features.contains(feature) {
+                return Some(feature);
+            }
+        }
+        None
+    }
+}
+
+impl<R: CpuIdReader> CpuId<R> {
+    /// The set of all supported features, see [`CpuId::has_feature`].
+    pub fn features(&self) -> CpuFeatures {
+        Feature::ALL
+            .iter()
+            .copied()
+            .filter(|&feature| self.has_feature(feature))
+            .collect()
+    }
+}
+
+/// Serializes a [`CpuFeatures`] set as a list of canonical flag names (see
+/// [`Feature::name`]), e.g., `["sse2", "avx2"]`.
+///
+/// This is the format of the `Serialize` implementation of [`CpuFeatures`].
+/// Unknown names are rejected on deserialization.
+#[cfg(feature = "serde")]
+pub mod flag_names {
+    use core::fmt;
+
+    use serde::de::{self, SeqAccess, Unexpected, Visitor};
+    use serde::{Deserializer, Serializer};
+
+    use super::{CpuFeatures, Feature};
+
+    /// Serializes `features` as a list of feature names.
+    pub fn serialize<S: Serializer>(
+        features: &CpuFeatures,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(features.iter().map(|feature| feature.name()))
+    }
+
+    /// Deserializes a list of feature names.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CpuFeatures, D::Error> {
+        deserializer.deserialize_seq(FlagNamesVisitor)
+    }
+
+    struct FlagNamesVisitor;
+
+    impl<'de> Visitor<'de> for FlagNamesVisitor {
+        type Value = CpuFeatures;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a list of feature names")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<CpuFeatures, A::Error> {
+            let mut features = CpuFeatures::new();
+            while let Some(FlagName(feature)) = seq.next_element()? {
+                features.insert(feature);
+            }
+            Ok(features)
+        }
+    }
+
+    /// A single [`Feature`], deserialized from its name.
+    struct FlagName(Feature);
+
+    impl<'de> de::Deserialize<'de> for FlagName {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_str(FlagNameVisitor)
+        }
+    }
+
+    struct FlagNameVisitor;
+
+    impl<'de> Visitor<'de> for FlagNameVisitor {
+        type Value = FlagName;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a feature name")
+        }
+
+        fn visit_str<E: de::Error>(self, name: &str) -> Result<FlagName, E> {
+            Feature::from_name(name)
+                .map(FlagName)
+                .ok_or_else(|| E::invalid_value(Unexpected::Str(name), &self))
+        }
+    }
+}
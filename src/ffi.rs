@@ -0,0 +1,189 @@
+//! C interface for the most common queries about the CPU we're running on.
+//!
+//! Build the crate as a static or dynamic library to link it into C or C++
+//! code, e.g., with `cargo rustc --release --features ffi --crate-type
+//! staticlib`. The matching declarations are in `include/raw_cpuid.h`.
+//!
+//! All functions query the current CPU with the `cpuid` instruction.
+//! Unknown values are reported as `0`.
+use core::ffi::{c_char, CStr};
+use core::{ptr, slice};
+
+use crate::{CpuId, Feature};
+
+/// Sizes (in bytes) of the caches of the current CPU.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct RawCpuIdCacheSizes {
+    /// Size of the L1 data cache.
+    pub l1d: u64,
+    /// Size of the L1 instruction cache.
+    pub l1i: u64,
+    /// Size of the L2 cache.
+    pub l2: u64,
+    /// Size of the L3 cache.
+    pub l3: u64,
+}
+
+/// Topology of the physical package of the current CPU.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct RawCpuIdTopology {
+    /// Number of logical processors, see [`CpuId::num_logical_cpus`].
+    pub logical_cpus: u32,
+    /// Number of cores, see [`CpuId::num_cores`].
+    pub cores: u32,
+    /// Number of logical processors per core, see [`CpuId::threads_per_core`].
+    pub threads_per_core: u32,
+    /// (x2)APIC ID of the logical processor, see [`CpuId::x2apic_id`].
+    pub apic_id: u32,
+}
+
+/// Copies `s` into `buf` (truncating it if necessary) and NUL-terminates it.
+///
+/// Returns the length of `s` like `snprintf` does.
+///
+/// # Safety
+/// `buf` has to be `NULL` or valid for writes of `len` bytes.
+unsafe fn copy_str(s: &str, buf: *mut c_char, len: usize) -> usize {
+    if !buf.is_null() && len > 0 {
+        let n = core::cmp::min(s.len(), len - 1);
+        ptr::copy_nonoverlapping(s.as_ptr(), buf.cast::<u8>(), n);
+        *buf.add(n) = 0;
+    }
+    s.len()
+}
+
+/// Writes the NUL-terminated vendor string (e.g., "GenuineIntel") to `buf`.
+///
+/// Returns the length of the vendor string (without the NUL byte), or `0` if
+/// it isn't available. The string is truncated if `len` is too small.
+///
+/// # Safety
+/// `buf` has to be `NULL` or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn raw_cpuid_vendor(buf: *mut c_char, len: usize) -> usize {
+    match CpuId::new().get_vendor_info() {
+        Some(vi) => copy_str(vi.as_str(), buf, len),
+        None => 0,
+    }
+}
+
+/// Writes the NUL-terminated processor brand string to `buf`.
+///
+/// Returns the length of the brand string (without the NUL byte), or `0` if
+/// it isn't available. The string is truncated if `len` is too small.
+///
+/// # Safety
+/// `buf` has to be `NULL` or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn raw_cpuid_brand_string(buf: *mut c_char, len: usize) -> usize {
+    match CpuId::new().get_processor_brand_string() {
+        Some(bs) => copy_str(bs.as_str(), buf, len),
+        None => 0,
+    }
+}
+
+/// Is the feature flag called `name` supported?
+///
+/// `name` is the name Linux uses in `/proc/cpuinfo` (see [`Feature::name`]),
+/// e.g., "avx2". Returns `false` for unknown names.
+///
+/// # Safety
+/// `name` has to point to a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn raw_cpuid_has_feature(name: *const c_char) -> bool {
+    if name.is_null() {
+        return false;
+    }
+    CStr::from_ptr(name)
+        .to_str()
+        .ok()
+        .and_then(Feature::from_name)
+        .is_some_and(|feature| CpuId::new().has_feature(feature))
+}
+
+/// Number of feature flags known to this library, see
+/// [`raw_cpuid_feature_name`].
+#[no_mangle]
+pub extern "C" fn raw_cpuid_feature_count() -> usize {
+    Feature::ALL.len()
+}
+
+/// Writes the NUL-terminated name of the `index`-th known feature flag to
+/// `buf`.
+///
+/// Returns the length of the name (without the NUL byte), or `0` if `index`
+/// is out of range.
+///
+/// # Safety
+/// `buf` has to be `NULL` or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn raw_cpuid_feature_name(
+    index: usize,
+    buf: *mut c_char,
+    len: usize,
+) -> usize {
+    match Feature::ALL.get(index) {
+        Some(feature) => copy_str(feature.name(), buf, len),
+        None => 0,
+    }
+}
+
+/// Writes the names of all supported features, separated by spaces, to `buf`
+/// (like the `flags` line of `/proc/cpuinfo`).
+///
+/// Returns the length of the complete list (without the NUL byte). Call it
+/// with `buf = NULL` to find out how large the buffer has to be.
+///
+/// # Safety
+/// `buf` has to be `NULL` or valid for writes of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn raw_cpuid_features(buf: *mut c_char, len: usize) -> usize {
+    let cpuid = CpuId::new();
+    let out: &mut [u8] = if buf.is_null() || len == 0 {
+        &mut []
+    } else {
+        slice::from_raw_parts_mut(buf.cast::<u8>(), len)
+    };
+
+    let mut total = 0;
+    for feature in Feature::ALL.iter().filter(|f| cpuid.has_feature(**f)) {
+        let sep: &[u8] = if total > 0 { b" " } else { b"" };
+        for &b in sep.iter().chain(feature.name().as_bytes()) {
+            // Keep the last byte for the NUL terminator.
+            if total + 1 < out.len() {
+                out[total] = b;
+            }
+            total += 1;
+        }
+    }
+    if let Some(last) = out.len().checked_sub(1) {
+        out[core::cmp::min(total, last)] = 0;
+    }
+    total
+}
+
+/// Sizes of the L1d, L1i, L2 and L3 caches of the current CPU.
+#[no_mangle]
+pub extern "C" fn raw_cpuid_cache_sizes() -> RawCpuIdCacheSizes {
+    let [l1d, l1i, l2, l3] = CpuId::new().cache_sizes();
+    RawCpuIdCacheSizes {
+        l1d: l1d.unwrap_or(0),
+        l1i: l1i.unwrap_or(0),
+        l2: l2.unwrap_or(0),
+        l3: l3.unwrap_or(0),
+    }
+}
+
+/// Topology of the physical package of the current CPU.
+#[no_mangle]
+pub extern "C" fn raw_cpuid_topology() -> RawCpuIdTopology {
+    let cpuid = CpuId::new();
+    RawCpuIdTopology {
+        logical_cpus: cpuid.num_logical_cpus().unwrap_or(0) as u32,
+        cores: cpuid.num_cores().unwrap_or(0) as u32,
+        threads_per_core: cpuid.threads_per_core().unwrap_or(0) as u32,
+        apic_id: cpuid.x2apic_id().unwrap_or(0),
+    }
+}
@@ -0,0 +1,275 @@
+//! Determine the base, maximum and TSC frequency of the CPU from the best
+//! source that is available, and summarize the TSC, boost and effective
+//! frequency capabilities.
+use crate::{CpuId, CpuIdReader};
+
+/// Where a frequency reported by [`CpuId::frequency`] was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrequencySource {
+    /// Time Stamp Counter and Nominal Core Crystal Clock leaf (LEAF=0x15).
+    TscLeaf,
+    /// Processor Frequency Information leaf (LEAF=0x16).
+    FrequencyLeaf,
+    /// Hypervisor timing leaf (LEAF=0x4000_0010).
+    Hypervisor,
+    /// Nominal frequency in the processor brand string, e.g., `@ 3.60GHz`.
+    BrandString,
+}
+
+/// A frequency and the source it was taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frequency {
+    /// Frequency in Hz.
+    pub hz: u64,
+    /// Where the frequency was taken from.
+    pub source: FrequencySource,
+}
+
+impl Frequency {
+    fn new(hz: u64, source: FrequencySource) -> Option<Frequency> {
+        (hz != 0).then_some(Frequency { hz, source })
+    }
+}
+
+/// Frequencies of the CPU, see [`CpuId::frequency`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrequencyInfo {
+    /// Base (nominal) frequency of the processor.
+    pub base: Option<Frequency>,
+    /// Maximum (turbo) frequency of the processor.
+    pub max: Option<Frequency>,
+    /// Frequency of the time stamp counter.
+    pub tsc: Option<Frequency>,
+}
+
+/// Capabilities of the time stamp counter, see [`CpuId::tsc_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TscCapabilities {
+    /// The TSC and the RDTSC instruction are available (`CPUID.01H:EDX[4]`).
+    pub present: bool,
+    /// The RDTSCP instruction is available (`CPUID.80000001H:EDX[27]`).
+    pub rdtscp: bool,
+    /// The TSC runs at a constant rate in all ACPI P-, C- and T-states
+    /// (`CPUID.80000007H:EDX[8]`).
+    pub invariant: bool,
+    /// The local APIC timer supports the TSC deadline mode
+    /// (`CPUID.01H:ECX[24]`).
+    pub deadline_timer: bool,
+    /// `(numerator, denominator)` of the TSC / core crystal clock ratio
+    /// (LEAF=0x15).
+    pub crystal_ratio: Option<(u32, u32)>,
+    /// Nominal frequency of the core crystal clock in Hz (LEAF=0x15).
+    pub crystal_hz: Option<u32>,
+    /// Frequency of the TSC from the best available source, see
+    /// [`CpuId::frequency`].
+    pub frequency: Option<Frequency>,
+}
+
+/// How the effective frequency of a core can be measured, see
+/// [`CpuId::effective_frequency_interface`].
+///
+/// The effective frequency is the ratio of the APERF (actual cycles) and
+/// MPERF (cycles at the nominal frequency) counters times the nominal
+/// frequency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EffectiveFrequencyInterface {
+    /// The IA32_MPERF and IA32_APERF MSRs are available
+    /// (`CPUID.06H:ECX[0]`).
+    pub mperf_aperf: bool,
+    /// The read-only MPerfReadOnly and APerfReadOnly MSRs are available
+    /// (`CPUID.80000007H:EDX[10]`).
+    pub read_only: bool,
+    /// MPERF and APERF can be read with RDPRU, also from user space
+    /// (`CPUID.80000008H:EBX[4]` and `EDX[31:16]`).
+    pub rdpru: bool,
+}
+
+impl EffectiveFrequencyInterface {
+    /// Can the effective frequency be measured at all?
+    pub fn is_available(&self) -> bool {
+        self.mperf_aperf || self.read_only || self.rdpru
+    }
+}
+
+impl<R: CpuIdReader> CpuId<R> {
+    /// Check if the processor can run cores above the base frequency, i.e.,
+    /// Intel Turbo Boost (`CPUID.06H:EAX[1]`) or AMD Core Performance Boost
+    /// (`CPUID.80000007H:EDX[9]`).
+    ///
+    /// # Note
+    /// Boosting can still be disabled by the BIOS or the OS (e.g., in
+    /// IA32_MISC_ENABLE on Intel), which isn't visible through `cpuid`.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_performance_boost(&self) -> bool {
+        self.get_thermal_power_info()
+            .is_some_and(|info| info.has_turbo_boost())
+            || self
+                .get_advanced_power_mgmt_info()
+                .is_some_and(|info| info.has_cpb())
+    }
+
+    /// Summarizes how the effective frequency of a core can be measured,
+    /// which is spread over leafs 0x06, 0x8000_0007 and 0x8000_0008.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// if CpuId::new().effective_frequency_interface().is_available() {
+    ///     println!("APERF/MPERF can be used to measure the effective frequency");
+    /// }
+    /// ```
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn effective_frequency_interface(&self) -> EffectiveFrequencyInterface {
+        EffectiveFrequencyInterface {
+            mperf_aperf: self
+                .get_thermal_power_info()
+                .is_some_and(|info| info.has_hw_coord_feedback()),
+            read_only: self
+                .get_advanced_power_mgmt_info()
+                .is_some_and(|info| info.has_ro_effective_freq_iface()),
+            rdpru: self
+                .get_processor_capacity_feature_info()
+                .is_some_and(|info| info.has_rdpru_mperf() && info.has_rdpru_aperf()),
+        }
+    }
+
+    /// Summarizes the capabilities of the time stamp counter, which are
+    /// spread over leafs 0x01, 0x15, 0x8000_0001 and 0x8000_0007.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// let tsc = CpuId::new().tsc_info();
+    /// if tsc.present && tsc.invariant {
+    ///     println!("TSC is usable as a clock source");
+    /// }
+    /// ```
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn tsc_info(&self) -> TscCapabilities {
+        let finfo = self.get_feature_info();
+        let leaf15 = self.get_tsc_info();
+
+        TscCapabilities {
+            present: finfo.as_ref().is_some_and(|f| f.has_tsc()),
+            rdtscp: self.has_rdtscp(),
+            invariant: self
+                .get_advanced_power_mgmt_info()
+                .is_some_and(|f| f.has_invariant_tsc()),
+            deadline_timer: finfo.as_ref().is_some_and(|f| f.has_tsc_deadline()),
+            crystal_ratio: leaf15
+                .as_ref()
+                .filter(|info| info.numerator() != 0 && info.denominator() != 0)
+                .map(|info| (info.numerator(), info.denominator())),
+            crystal_hz: leaf15
+                .as_ref()
+                .map(|info| info.nominal_frequency())
+                .filter(|&hz| hz != 0),
+            frequency: self.frequency().tsc,
+        }
+    }
+
+    /// Determines the base, maximum and TSC frequency from the best available
+    /// source.
+    ///
+    /// The sources are tried in this order:
+    ///
+    /// 1. Leaf 0x15 (TSC only) and leaf 0x16 (base, max and, as an
+    ///    approximation, TSC).
+    /// 2. The hypervisor timing leaf 0x4000_0010 (TSC only).
+    /// 3. The nominal frequency in the processor brand string (base and, as an
+    ///    approximation, TSC).
+    ///
+    /// The base frequency approximates the TSC frequency because CPUs with an
+    /// invariant TSC run it at the nominal frequency. Each reported
+    /// [`Frequency`] tells which source it was taken from.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// let cpuid = CpuId::new();
+    /// if let Some(tsc) = cpuid.frequency().tsc {
+    ///     println!("TSC runs at {} Hz (from {:?})", tsc.hz, tsc.source);
+    /// }
+    /// ```
+    pub fn frequency(&self) -> FrequencyInfo {
+        use FrequencySource::*;
+
+        let leaf16 = self.get_processor_frequency_info();
+        let mhz = |mhz: u16, source| Frequency::new(mhz as u64 * 1_000_000, source);
+        let leaf16_base = leaf16
+            .as_ref()
+            .and_then(|info| mhz(info.processor_base_frequency(), FrequencyLeaf));
+        let leaf16_max = leaf16
+            .as_ref()
+            .and_then(|info| mhz(info.processor_max_frequency(), FrequencyLeaf));
+        let leaf15_tsc = self
+            .get_tsc_info()
+            .and_then(|info| info.tsc_frequency())
+            .and_then(|hz| Frequency::new(hz, TscLeaf));
+        let hypervisor_tsc = self
+            .get_hypervisor_info()
+            .and_then(|info| info.tsc_frequency())
+            .and_then(|khz| Frequency::new(khz as u64 * 1000, Hypervisor));
+        let brand = self
+            .get_processor_brand_string()
+            .and_then(|brand| brand.frequency())
+            .and_then(|hz| Frequency::new(hz, BrandString));
+
+        FrequencyInfo {
+            base: leaf16_base.or(brand),
+            max: leaf16_max,
+            tsc: leaf15_tsc.or(leaf16_base).or(hypervisor_tsc).or(brand),
+        }
+    }
+}
+
+/// Parses the nominal frequency at the end of a processor brand string
+/// (e.g., `Intel(R) Core(TM) i7-4790 CPU @ 3.60GHz` or `... @ 2.40 GHz`) and
+/// returns it in Hz.
+pub(crate) fn parse_brand_frequency(brand: &str) -> Option<u64> {
+    // The unit may be separated from the number by spaces:
+    let (rest, unit) = split_suffix(brand.trim_end(), |c| c.is_ascii_alphabetic());
+    let (rest, number) = split_suffix(rest.trim_end(), |c| c.is_ascii_digit() || c == '.');
+    if number.is_empty()
+        || !(rest.is_empty() || rest.ends_with(|c: char| c == '@' || c.is_ascii_whitespace()))
+    {
+        return None;
+    }
+
+    let multiplier: u64 = if unit.eq_ignore_ascii_case("THz") {
+        1_000_000_000_000
+    } else if unit.eq_ignore_ascii_case("GHz") {
+        1_000_000_000
+    } else if unit.eq_ignore_ascii_case("MHz") {
+        1_000_000
+    } else {
+        return None;
+    };
+
+    let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+    let mut hz = integer.parse::<u64>().ok()?.checked_mul(multiplier)?;
+    let mut scale = multiplier;
+    for digit in fraction.bytes() {
+        if !digit.is_ascii_digit() {
+            return None;
+        }
+        scale /= 10;
+        hz = hz.checked_add((digit - b'0') as u64 * scale)?;
+    }
+
+    (hz != 0).then_some(hz)
+}
+
+/// Splits `s` before its longest suffix of characters matching `pred`.
+fn split_suffix(s: &str, pred: impl Fn(char) -> bool) -> (&str, &str) {
+    s.split_at(s.trim_end_matches(pred).len())
+}
@@ -0,0 +1,213 @@
+//! Summarize the features supported by the different core types of hybrid
+//! CPUs.
+//!
+//! Code that may run on any core (e.g., JIT-compiled code) must only use
+//! features that all core types support.
+use alloc::vec::Vec;
+
+use crate::{CoreType, CpuFeatures, CpuIdSnapshot, Feature};
+
+/// Features supported by the cores of one core type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct CoreTypeFeatures {
+    /// The core type, `None` for cores that don't report one (LEAF=0x1A).
+    pub core_type: Option<CoreType>,
+    /// The cores of this type.
+    pub cores: Vec<usize>,
+    /// Features supported by all cores of this type.
+    pub features: Vec<Feature>,
+}
+
+/// Features supported by the performance cores, the efficiency cores and all
+/// cores of a (hybrid) CPU.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "std")]
+/// # {
+/// use raw_cpuid::{CoreType, HybridSummary};
+///
+/// let summary = HybridSummary::from_all_cores().unwrap();
+/// if let Some(e_cores) = summary.core_type(CoreType::Atom) {
+///     println!("E-cores: {:?}", e_cores.features);
+/// }
+/// println!("Safe for all cores: {:?}", summary.common);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct HybridSummary {
+    /// Features per core type, ordered by core type.
+    pub core_types: Vec<CoreTypeFeatures>,
+    /// Features supported by all cores (the intersection of all core types).
+    pub common: Vec<Feature>,
+}
+
+impl HybridSummary {
+    /// Groups the `(core, snapshot)` pairs by core type.
+    ///
+    /// Use [`CpuIdSnapshot::intersection`] to get a snapshot with the
+    /// features of all cores if more than the [`Feature`] flags are needed.
+    pub fn from_snapshots(snapshots: &[(usize, CpuIdSnapshot)]) -> Self {
+        let mut core_types: Vec<CoreTypeFeatures> = Vec::new();
+        for (core, snapshot) in snapshots {
+            let cpuid = snapshot.cpuid();
+            let core_type = cpuid.get_hybrid_info().map(|info| info.core_type());
+            let features = Feature::ALL
+                .iter()
+                .copied()
+                .filter(|feature| cpuid.has_feature(*feature));
+
+            match core_types.iter_mut().find(|t| t.core_type == core_type) {
+                Some(entry) => {
+                    entry.cores.push(*core);
+                    entry.features.retain(|f| cpuid.has_feature(*f));
+                }
+                None => core_types.push(CoreTypeFeatures {
+                    core_type,
+                    cores: alloc::vec![*core],
+                    features: features.collect(),
+                }),
+            }
+        }
+        core_types.sort_by_key(|t| t.core_type);
+
+        let mut common = core_types
+            .first()
+            .map_or_else(Vec::new, |t| t.features.clone());
+        for other in core_types.iter().skip(1) {
+            common.retain(|f| other.features.contains(f));
+        }
+
+        HybridSummary { core_types, common }
+    }
+
+    /// Takes a snapshot on every online core and summarizes them.
+    #[cfg(feature = "std")]
+    pub fn from_all_cores() -> std::io::Result<Self> {
+        let snapshots = CpuIdSnapshot::from_all_cores()?;
+        Ok(Self::from_snapshots(&snapshots))
+    }
+
+    /// Does the CPU have more than one core type?
+    pub fn is_hybrid(&self) -> bool {
+        self.core_types.len() > 1
+    }
+
+    /// Features of the given core type, e.g., [`CoreType::Core`] for the
+    /// performance cores and [`CoreType::Atom`] for the efficiency cores.
+    pub fn core_type(&self, core_type: CoreType) -> Option<&CoreTypeFeatures> {
+        self.core_types
+            .iter()
+            .find(|t| t.core_type == Some(core_type))
+    }
+
+    /// Features supported by some, but not all cores.
+    pub fn asymmetric(&self) -> Vec<Feature> {
+        let mut features: Vec<Feature> = Vec::new();
+        for t in &self.core_types {
+            for f in &t.features {
+                if !self.common.contains(f) && !features.contains(f) {
+                    features.push(*f);
+                }
+            }
+        }
+        features.sort();
+        features
+    }
+}
+
+/// A feature that some cores advertise and others don't, see
+/// [`HybridIsaCheck`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct AsymmetricFeature {
+    /// The feature.
+    pub feature: Feature,
+    /// Cores that advertise the feature.
+    pub present: Vec<usize>,
+    /// Cores that don't advertise the feature.
+    pub missing: Vec<usize>,
+}
+
+/// Checks that all cores advertise the same ISA.
+///
+/// A thread can migrate to any core, so code that uses a feature only some
+/// cores advertise (e.g., AVX-512 on the performance cores of a hybrid CPU)
+/// can crash with an illegal instruction. Unlike [`HybridSummary`], which
+/// groups the cores by core type, this compares every core, so it also finds
+/// cores of the same type that differ (e.g., due to a BIOS or microcode
+/// bug).
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "std")]
+/// # {
+/// use raw_cpuid::HybridIsaCheck;
+///
+/// let check = HybridIsaCheck::from_all_cores().unwrap();
+/// for asymmetric in &check.asymmetric {
+///     println!("{} is missing on cores {:?}", asymmetric.feature, asymmetric.missing);
+/// }
+/// println!("Safe for all cores: {:?}", check.common);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct HybridIsaCheck {
+    /// Features advertised by all cores, i.e., the ISA that is safe to use on
+    /// any core.
+    pub common: CpuFeatures,
+    /// Features advertised by some, but not all cores, ordered by feature.
+    pub asymmetric: Vec<AsymmetricFeature>,
+}
+
+impl HybridIsaCheck {
+    /// Compares the features of the `(core, snapshot)` pairs.
+    pub fn from_snapshots(snapshots: &[(usize, CpuIdSnapshot)]) -> Self {
+        let features: Vec<(usize, CpuFeatures)> = snapshots
+            .iter()
+            .map(|(core, snapshot)| (*core, snapshot.cpuid().features()))
+            .collect();
+
+        let mut common = features.first().map_or_else(CpuFeatures::new, |f| f.1);
+        let mut any = CpuFeatures::new();
+        for (_, f) in &features {
+            common &= *f;
+            any |= *f;
+        }
+
+        let asymmetric = (any - common)
+            .iter()
+            .map(|feature| {
+                let (present, missing): (Vec<_>, Vec<_>) =
+                    features.iter().partition(|(_, f)| f.contains(feature));
+                AsymmetricFeature {
+                    feature,
+                    present: present.iter().map(|(core, _)| *core).collect(),
+                    missing: missing.iter().map(|(core, _)| *core).collect(),
+                }
+            })
+            .collect();
+
+        HybridIsaCheck { common, asymmetric }
+    }
+
+    /// Takes a snapshot on every online core and compares them.
+    #[cfg(feature = "std")]
+    pub fn from_all_cores() -> std::io::Result<Self> {
+        let snapshots = CpuIdSnapshot::from_all_cores()?;
+        Ok(Self::from_snapshots(&snapshots))
+    }
+
+    /// Do all cores advertise the same features?
+    pub fn is_consistent(&self) -> bool {
+        self.asymmetric.is_empty()
+    }
+
+    /// Is `feature` safe to use on any core?
+    pub fn is_safe(&self, feature: Feature) -> bool {
+        self.common.contains(feature)
+    }
+}
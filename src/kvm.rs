@@ -0,0 +1,187 @@
+//! Convert snapshots to and from the entry format of the `KVM_GET_CPUID2`,
+//! `KVM_GET_SUPPORTED_CPUID` and `KVM_SET_CPUID2` ioctls.
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::builder::has_subleafs;
+#[cfg(all(feature = "kvm", target_arch = "x86_64"))]
+use crate::KvmCpuIdError;
+use crate::{CpuIdResult, CpuIdSnapshot};
+
+/// `index` of a [`KvmCpuidEntry2`] is relevant (the leaf has subleafs).
+pub const KVM_CPUID_FLAG_SIGNIFCANT_INDEX: u32 = 1 << 0;
+
+/// A CPUID entry as used by KVM (`struct kvm_cpuid_entry2`).
+///
+/// The layout matches the kernel and `kvm_bindings::kvm_cpuid_entry2`. With
+/// the `kvm` feature on x86_64 entries convert from and to
+/// `kvm_bindings::kvm_cpuid_entry2`, and snapshots from and to
+/// `kvm_bindings::CpuId`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct KvmCpuidEntry2 {
+    /// Leaf (EAX input value).
+    pub function: u32,
+    /// Subleaf (ECX input value).
+    pub index: u32,
+    /// See [`KVM_CPUID_FLAG_SIGNIFCANT_INDEX`].
+    pub flags: u32,
+    pub eax: u32,
+    pub ebx: u32,
+    pub ecx: u32,
+    pub edx: u32,
+    pub padding: [u32; 3],
+}
+
+impl KvmCpuidEntry2 {
+    /// The registers of this entry.
+    pub fn result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
+}
+
+impl CpuIdSnapshot {
+    /// Entries for `KVM_SET_CPUID2`, sorted by leaf and subleaf.
+    ///
+    /// [`KVM_CPUID_FLAG_SIGNIFCANT_INDEX`] is set for all leafs that have
+    /// subleafs. KVM accepts at most `KVM_MAX_CPUID_ENTRIES` entries, which
+    /// the conversion to `kvm_bindings::CpuId` checks.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{CpuIdBuilder, CpuIdSnapshot, Feature};
+    ///
+    /// let mut builder = CpuIdBuilder::from_host();
+    /// builder.set_feature(Feature::Hypervisor, true);
+    /// let entries = CpuIdSnapshot::from(builder.build()).to_kvm_entries();
+    /// assert!(entries.iter().any(|e| e.function == 0x1 && e.ecx & (1 << 31) != 0));
+    /// ```
+    pub fn to_kvm_entries(&self) -> Vec<KvmCpuidEntry2> {
+        self.leafs()
+            .iter()
+            .map(|(&(leaf, subleaf), res)| KvmCpuidEntry2 {
+                function: leaf,
+                index: subleaf,
+                flags: if has_subleafs(leaf) || subleaf > 0 {
+                    KVM_CPUID_FLAG_SIGNIFCANT_INDEX
+                } else {
+                    0
+                },
+                eax: res.eax,
+                ebx: res.ebx,
+                ecx: res.ecx,
+                edx: res.edx,
+                padding: [0; 3],
+            })
+            .collect()
+    }
+
+    /// Creates a snapshot from entries returned by `KVM_GET_CPUID2` or
+    /// `KVM_GET_SUPPORTED_CPUID`.
+    ///
+    /// The subleaf of entries without [`KVM_CPUID_FLAG_SIGNIFCANT_INDEX`] is
+    /// ignored (it is reported as subleaf 0).
+    pub fn from_kvm_entries(entries: &[KvmCpuidEntry2]) -> Self {
+        let leafs: BTreeMap<(u32, u32), CpuIdResult> = entries
+            .iter()
+            .map(|entry| {
+                let subleaf = if entry.flags & KVM_CPUID_FLAG_SIGNIFCANT_INDEX != 0 {
+                    entry.index
+                } else {
+                    0
+                };
+                ((entry.function, subleaf), entry.result())
+            })
+            .collect();
+        CpuIdSnapshot::from(leafs)
+    }
+}
+
+#[cfg(all(feature = "kvm", target_arch = "x86_64"))]
+impl From<&kvm_bindings::kvm_cpuid_entry2> for KvmCpuidEntry2 {
+    fn from(entry: &kvm_bindings::kvm_cpuid_entry2) -> Self {
+        KvmCpuidEntry2 {
+            function: entry.function,
+            index: entry.index,
+            flags: entry.flags,
+            eax: entry.eax,
+            ebx: entry.ebx,
+            ecx: entry.ecx,
+            edx: entry.edx,
+            padding: entry.padding,
+        }
+    }
+}
+
+#[cfg(all(feature = "kvm", target_arch = "x86_64"))]
+impl From<&KvmCpuidEntry2> for kvm_bindings::kvm_cpuid_entry2 {
+    fn from(entry: &KvmCpuidEntry2) -> Self {
+        kvm_bindings::kvm_cpuid_entry2 {
+            function: entry.function,
+            index: entry.index,
+            flags: entry.flags,
+            eax: entry.eax,
+            ebx: entry.ebx,
+            ecx: entry.ecx,
+            edx: entry.edx,
+            padding: entry.padding,
+        }
+    }
+}
+
+/// Entries for `KVM_SET_CPUID2`, see [`CpuIdSnapshot::to_kvm_entries`].
+///
+/// # Example
+/// ```
+/// use raw_cpuid::{CpuIdResult, CpuIdSnapshot, KvmCpuIdError};
+/// use kvm_bindings::KVM_MAX_CPUID_ENTRIES;
+///
+/// // A snapshot with `n` subleafs of leaf 0xD:
+/// let leaf_d = |n: usize| {
+///     let zero = CpuIdResult { eax: 0, ebx: 0, ecx: 0, edx: 0 };
+///     CpuIdSnapshot::from((0..n as u32).map(|subleaf| ((0xd, subleaf), zero)).collect::<std::collections::BTreeMap<_, _>>())
+/// };
+///
+/// let snapshot = leaf_d(4);
+/// let cpuid = kvm_bindings::CpuId::try_from(&snapshot).unwrap();
+/// assert_eq!(cpuid.as_slice().len(), 4);
+/// assert_eq!(CpuIdSnapshot::from(&cpuid), snapshot);
+///
+/// assert!(matches!(
+///     kvm_bindings::CpuId::try_from(&leaf_d(KVM_MAX_CPUID_ENTRIES + 1)),
+///     Err(KvmCpuIdError::TooManyEntries { .. })
+/// ));
+/// ```
+#[cfg(all(feature = "kvm", target_arch = "x86_64"))]
+impl TryFrom<&CpuIdSnapshot> for kvm_bindings::CpuId {
+    type Error = KvmCpuIdError;
+
+    fn try_from(snapshot: &CpuIdSnapshot) -> Result<Self, Self::Error> {
+        let entries: Vec<kvm_bindings::kvm_cpuid_entry2> =
+            snapshot.to_kvm_entries().iter().map(Into::into).collect();
+        let too_many = KvmCpuIdError::TooManyEntries {
+            entries: entries.len(),
+            max: kvm_bindings::KVM_MAX_CPUID_ENTRIES,
+        };
+        if entries.len() > kvm_bindings::KVM_MAX_CPUID_ENTRIES {
+            return Err(too_many);
+        }
+        kvm_bindings::CpuId::from_entries(&entries).map_err(|_| too_many)
+    }
+}
+
+/// Snapshot of the entries returned by `KVM_GET_CPUID2` or
+/// `KVM_GET_SUPPORTED_CPUID`, see [`CpuIdSnapshot::from_kvm_entries`].
+#[cfg(all(feature = "kvm", target_arch = "x86_64"))]
+impl From<&kvm_bindings::CpuId> for CpuIdSnapshot {
+    fn from(cpuid: &kvm_bindings::CpuId) -> Self {
+        let entries: Vec<KvmCpuidEntry2> = cpuid.as_slice().iter().map(Into::into).collect();
+        CpuIdSnapshot::from_kvm_entries(&entries)
+    }
+}
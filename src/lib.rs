@@ -18,7 +18,7 @@
 //!
 //! if let Some(cparams) = cpuid.get_cache_parameters() {
 //!     for cache in cparams {
-//!         let size = cache.associativity() * cache.physical_line_partitions() * cache.coherency_line_size() * cache.sets();
+//!         let size = cache.ways() * cache.physical_line_partitions() * cache.coherency_line_size() * cache.sets();
 //!         println!("L{}-Cache size is {}", cache.level(), size);
 //!     }
 //! } else {
@@ -43,6 +43,30 @@
 //! Note that the presence of a ✅ does not guarantee that a specific feature
 //! will exist for your CPU -- just that it is potentially supported by the
 //! vendor on some of its chips. You will still have to query it at runtime.
+//!
+//! Hygon CPUs are AMD-derived and follow the AMD column.
+//!
+//! # Cargo features
+//!
+//! Without any features the crate only needs `core`, e.g., for kernels.
+//! Optional functionality is enabled with:
+//!
+//! - `alloc`: Functionality that needs a heap, e.g., `CpuIdBuilder` and
+//!   `CpuIdSnapshot`.
+//! - `std`: Functionality that needs the standard library, e.g., reading all
+//!   cores with `CpuId::for_each_core` (implies `alloc`).
+//! - `serde`: Serialization of the decoded structs and snapshots (`serialize`
+//!   is a deprecated alias).
+//! - `display`: Human readable reports (`Report`, implies `alloc`).
+//! - `json`, `yaml` and `toml`: Text encodings of snapshots.
+//! - `kvm`: Conversion of snapshots from and to the `KVM_SET_CPUID2` format
+//!   and `kvm_bindings::CpuId`.
+//! - `linux` and `windows`: Cross-checks with the topology reported by the OS.
+//! - `ffi`: A C interface (implies `std`).
+//! - `log`: Logs every executed `cpuid` instruction.
+//! - `test-util`: Fake CPUs and real CPUID dumps (`testdata`) for tests of
+//!   downstream crates.
+//! - `cli`: Only used to build the `cpuid` binary.
 
 #![no_std]
 #![crate_name = "raw_cpuid"]
@@ -51,22 +75,87 @@
 #[cfg(test)]
 #[macro_use]
 extern crate std;
+#[cfg(all(not(test), feature = "std"))]
+extern crate std;
 
+#[cfg(any(test, feature = "alloc"))]
+extern crate alloc;
+
+#[cfg(any(test, feature = "alloc"))]
+mod binary;
+#[cfg(any(test, feature = "alloc"))]
+mod builder;
+mod cet;
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+mod codec;
+#[cfg(any(test, feature = "alloc"))]
+mod consistency;
+#[cfg(all(
+    any(test, feature = "std"),
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+mod detect;
+#[cfg(any(test, feature = "alloc"))]
+mod diff;
+mod error;
 mod extended;
+#[cfg(any(test, feature = "test-util"))]
+mod fake;
+mod features;
+#[cfg(any(test, feature = "ffi"))]
+pub mod ffi;
+mod frequency;
+#[cfg(any(test, feature = "alloc"))]
+mod hybrid;
+#[cfg(any(test, feature = "kvm"))]
+mod kvm;
+#[cfg(any(test, feature = "alloc"))]
+mod migration;
+mod models;
+#[cfg(any(test, feature = "alloc"))]
+mod numa;
+mod qemu;
+mod query;
+#[cfg(any(test, feature = "display"))]
+mod report;
+mod rustc;
+#[cfg(any(test, feature = "alloc"))]
+mod snapshot;
+mod summary;
+#[cfg(all(any(test, feature = "linux"), target_os = "linux"))]
+mod sysfs;
+#[cfg(any(test, feature = "alloc"))]
+mod system_topology;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testdata;
 #[cfg(test)]
 mod tests;
-#[cfg(feature = "serialize")]
+#[cfg(any(test, feature = "alloc"))]
+mod tlb;
+mod topology;
+mod transmeta;
+#[cfg(any(test, all(feature = "windows", target_os = "windows")))]
+mod windows;
+#[cfg(feature = "serde")]
 #[macro_use]
 extern crate serde_derive;
 
-#[cfg(feature = "serialize")]
+#[cfg(feature = "serde")]
 extern crate serde;
 
 #[macro_use]
 extern crate bitflags;
 
 /// Uses Rust's `cpuid` function from the `arch` module.
+///
+/// On targets without a `cpuid` instruction (e.g., aarch64) the crate still
+/// compiles: reads are answered by a mock reader (see [`set_mock_reader`] and
+/// [`CpuIdSnapshot::install_as_native`](crate::CpuIdSnapshot::install_as_native))
+/// or return all zeros if none is installed.
 pub mod native_cpuid {
+    use core::ptr;
+    use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
     use crate::CpuIdResult;
 
     #[cfg(all(target_arch = "x86", not(target_env = "sgx")))]
@@ -74,32 +163,243 @@ pub mod native_cpuid {
     #[cfg(all(target_arch = "x86_64", not(target_env = "sgx")))]
     use core::arch::x86_64 as arch;
 
+    /// Does the CPU implement the `cpuid` instruction?
+    ///
+    /// On 32-bit x86 this checks whether the ID flag (bit 21) in EFLAGS can be
+    /// toggled. All x86_64 CPUs implement `cpuid`, other architectures don't.
+    /// Always true while a mock reader is installed.
+    pub fn has_cpuid() -> bool {
+        #[cfg(all(target_arch = "x86", not(target_env = "sgx")))]
+        let native = self::arch::has_cpuid();
+        #[cfg(all(target_arch = "x86_64", not(target_env = "sgx")))]
+        let native = true;
+        #[cfg(not(all(
+            any(target_arch = "x86", target_arch = "x86_64"),
+            not(target_env = "sgx")
+        )))]
+        let native = false;
+
+        #[cfg(feature = "std")]
+        let native = native || thread_mock_reader().is_some();
+
+        native || mock_reader().is_some()
+    }
+
+    /// A function that answers `cpuid` requests (leaf, subleaf) in place of
+    /// the instruction.
+    pub type MockReader = fn(u32, u32) -> CpuIdResult;
+
+    static MOCK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+    /// Answer all `cpuid` requests of [`CpuIdReaderNative`](crate::CpuIdReaderNative)
+    /// (and thus [`CpuId::new`](crate::CpuId::new)) with `reader` instead of
+    /// executing the instruction, or go back to the instruction with `None`.
+    ///
+    /// This affects all threads of the process (use
+    /// [`set_thread_mock_reader`] to only affect the current thread). It's
+    /// meant for tests, e.g., to run code that uses `CpuId::new` on CI hosts
+    /// that aren't x86.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{native_cpuid, CpuId, CpuIdResult};
+    ///
+    /// fn no_leafs(_leaf: u32, _subleaf: u32) -> CpuIdResult {
+    ///     CpuIdResult { eax: 0, ebx: 0, ecx: 0, edx: 0 }
+    /// }
+    ///
+    /// native_cpuid::set_mock_reader(Some(no_leafs));
+    /// assert!(native_cpuid::has_cpuid());
+    /// assert!(CpuId::new().get_feature_info().is_none());
+    /// native_cpuid::set_mock_reader(None);
+    /// ```
+    pub fn set_mock_reader(reader: Option<MockReader>) {
+        let reader = reader.map_or(ptr::null_mut(), |reader| reader as *mut ());
+        MOCK.store(reader, Ordering::Release);
+    }
+
+    /// The mock reader installed for all threads, if any.
+    pub fn mock_reader() -> Option<MockReader> {
+        let mock = MOCK.load(Ordering::Acquire);
+        // Safety: Only `set_mock_reader` stores non-null pointers, which are
+        // always a `MockReader`.
+        (!mock.is_null()).then(|| unsafe { core::mem::transmute::<*mut (), MockReader>(mock) })
+    }
+
+    #[cfg(feature = "std")]
+    std::thread_local! {
+        static THREAD_MOCK: core::cell::Cell<Option<MockReader>> = const { core::cell::Cell::new(None) };
+    }
+
+    /// Like [`set_mock_reader`], but only for requests made by the current
+    /// thread. A reader installed for the thread takes precedence over the
+    /// one of the process, so tests running in parallel don't see each
+    /// other's mocks.
+    #[cfg(feature = "std")]
+    pub fn set_thread_mock_reader(reader: Option<MockReader>) {
+        THREAD_MOCK.with(|mock| mock.set(reader));
+    }
+
+    /// The mock reader installed for the current thread, if any.
+    #[cfg(feature = "std")]
+    pub fn thread_mock_reader() -> Option<MockReader> {
+        THREAD_MOCK.with(|mock| mock.get())
+    }
+
+    /// A function called after every `cpuid` instruction executed by this
+    /// crate, with the leaf, subleaf and result.
+    pub type InvocationHook = fn(u32, u32, CpuIdResult);
+
+    static INVOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+    /// Number of `cpuid` instructions executed by this crate so far (in
+    /// this process, on all threads), including requests answered by a mock
+    /// reader.
+    pub fn invocation_count() -> usize {
+        INVOCATIONS.load(Ordering::Relaxed)
+    }
+
+    /// Register a `hook` that is called after every `cpuid` instruction
+    /// executed by this crate, or remove it with `None`.
+    ///
+    /// Readers other than the native one (e.g., snapshots) don't execute
+    /// `cpuid` and don't call the hook.
+    pub fn set_invocation_hook(hook: Option<InvocationHook>) {
+        let hook = hook.map_or(ptr::null_mut(), |hook| hook as *mut ());
+        HOOK.store(hook, Ordering::Release);
+    }
+
     pub fn cpuid_count(a: u32, c: u32) -> CpuIdResult {
+        #[cfg(feature = "std")]
+        let mock = thread_mock_reader().or_else(mock_reader);
+        #[cfg(not(feature = "std"))]
+        let mock = mock_reader();
+        let result = match mock {
+            Some(mock) => mock(a, c),
+            None => execute(a, c),
+        };
+
+        INVOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let hook = HOOK.load(Ordering::Acquire);
+        if !hook.is_null() {
+            // Safety: Only `set_invocation_hook` stores non-null pointers,
+            // which are always an `InvocationHook`.
+            let hook = unsafe { core::mem::transmute::<*mut (), InvocationHook>(hook) };
+            hook(a, c, result);
+        }
+
+        #[cfg(feature = "log")]
+        log::trace!("cpuid({:#x}, {:#x}) = {:#x?}", a, c, result);
+
+        result
+    }
+
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        not(target_env = "sgx")
+    ))]
+    pub(crate) fn execute(a: u32, c: u32) -> CpuIdResult {
         // Safety: CPUID is supported on all x86_64 CPUs and all x86 CPUs with
         // SSE, but not by SGX.
-        let result = unsafe { self::arch::__cpuid_count(a, c) };
+        #[allow(unused_unsafe)]
+        unsafe { self::arch::__cpuid_count(a, c) }.into()
+    }
 
+    #[cfg(not(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        not(target_env = "sgx")
+    )))]
+    pub(crate) fn execute(_a: u32, _c: u32) -> CpuIdResult {
         CpuIdResult {
-            eax: result.eax,
-            ebx: result.ebx,
-            ecx: result.ecx,
-            edx: result.edx,
+            eax: 0,
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
+        }
+    }
+
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        not(target_env = "sgx")
+    ))]
+    impl From<arch::CpuidResult> for CpuIdResult {
+        fn from(result: arch::CpuidResult) -> Self {
+            CpuIdResult {
+                eax: result.eax,
+                ebx: result.ebx,
+                ecx: result.ecx,
+                edx: result.edx,
+            }
+        }
+    }
+
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        not(target_env = "sgx")
+    ))]
+    impl From<CpuIdResult> for arch::CpuidResult {
+        fn from(result: CpuIdResult) -> Self {
+            arch::CpuidResult {
+                eax: result.eax,
+                ebx: result.ebx,
+                ecx: result.ecx,
+                edx: result.edx,
+            }
         }
     }
 }
 
 use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
 use core::mem::size_of;
+use core::ops::RangeInclusive;
 use core::slice;
 use core::str;
 
+#[cfg(any(test, feature = "alloc"))]
+pub use binary::*;
+#[cfg(any(test, feature = "alloc"))]
+pub use builder::*;
+pub use cet::*;
+#[cfg(any(test, feature = "alloc"))]
+pub use consistency::*;
+#[cfg(all(
+    any(test, feature = "std"),
+    any(target_arch = "x86", target_arch = "x86_64")
+))]
+pub use detect::*;
+#[cfg(any(test, feature = "alloc"))]
+pub use diff::*;
+pub use error::*;
 pub use extended::*;
-
-#[cfg(not(test))]
-mod std {
-    pub use core::ops;
-    pub use core::option;
-}
+#[cfg(any(test, feature = "test-util"))]
+pub use fake::*;
+pub use features::*;
+pub use frequency::*;
+#[cfg(any(test, feature = "alloc"))]
+pub use hybrid::*;
+#[cfg(any(test, feature = "kvm"))]
+pub use kvm::*;
+pub use models::*;
+#[cfg(any(test, feature = "alloc"))]
+pub use numa::*;
+pub use query::*;
+#[cfg(any(test, feature = "display"))]
+pub use report::*;
+#[cfg(any(test, feature = "alloc"))]
+pub use snapshot::*;
+pub use summary::*;
+#[cfg(all(any(test, feature = "linux"), target_os = "linux"))]
+pub use sysfs::*;
+#[cfg(any(test, feature = "alloc"))]
+pub use system_topology::*;
+#[cfg(any(test, feature = "alloc"))]
+pub use tlb::*;
+pub use topology::*;
+pub use transmeta::*;
+#[cfg(any(test, all(feature = "windows", target_os = "windows")))]
+pub use windows::*;
 
 /// Macro which queries cpuid directly.
 ///
@@ -129,6 +429,86 @@ fn get_bits(r: u32, from: u32, to: u32) -> u32 {
     (r & mask) >> from
 }
 
+/// Upper bound for the number of subleafs of a leaf that is terminated by an
+/// invalid entry.
+const MAX_TERMINATED_SUBLEAFS: u32 = 64;
+
+/// How many subleafs [`Subleafs`] keeps, later subleafs are read again when
+/// they are needed.
+const CACHED_SUBLEAFS: usize = 16;
+
+/// The subleafs of a leaf that is terminated by an invalid entry, read once
+/// when an iterator is created.
+///
+/// Each `cpuid` can cause a VM exit under a hypervisor, so the registers read
+/// while looking for the end are kept instead of reading them again in
+/// `next()`.
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Subleafs {
+    leaf: u32,
+    /// The first subleaf.
+    start: u32,
+    /// The first subleaf at or after `start` that is invalid.
+    end: u32,
+    regs: [CpuIdResult; CACHED_SUBLEAFS],
+}
+
+impl Subleafs {
+    /// Reads the subleafs of `leaf` from `start` up to the first one
+    /// `is_valid` rejects.
+    fn read<R: CpuIdReader>(
+        read: &R,
+        leaf: u32,
+        start: u32,
+        is_valid: impl Fn(CpuIdResult) -> bool,
+    ) -> Self {
+        let mut subleafs = Subleafs {
+            leaf,
+            start,
+            end: start,
+            regs: [CpuIdResult {
+                eax: 0,
+                ebx: 0,
+                ecx: 0,
+                edx: 0,
+            }; CACHED_SUBLEAFS],
+        };
+        while subleafs.end < MAX_TERMINATED_SUBLEAFS {
+            let res = read.cpuid2(leaf, subleafs.end);
+            if !is_valid(res) {
+                break;
+            }
+            if let Some(regs) = subleafs.regs.get_mut((subleafs.end - start) as usize) {
+                *regs = res;
+            }
+            subleafs.end += 1;
+        }
+        subleafs
+    }
+
+    /// The registers of `subleaf`, which must be in `start..end`.
+    fn get<R: CpuIdReader>(&self, read: &R, subleaf: u32) -> CpuIdResult {
+        match self.regs.get((subleaf - self.start) as usize) {
+            Some(regs) => *regs,
+            None => read.cpuid2(self.leaf, subleaf),
+        }
+    }
+}
+
+fn set_bits(r: &mut u32, from: u32, to: u32, value: u32) {
+    assert!(from <= 31);
+    assert!(to <= 31);
+    assert!(from <= to);
+
+    let mask = match to - from {
+        31 => 0xffffffff,
+        bits => (1 << (bits + 1)) - 1,
+    };
+
+    *r = (*r & !(mask << from)) | ((value & mask) << from);
+}
+
 macro_rules! check_flag {
     ($doc:meta, $fun:ident, $flags:ident, $flag:expr) => {
         #[$doc]
@@ -138,6 +518,15 @@ macro_rules! check_flag {
     };
 }
 
+macro_rules! set_flag {
+    ($fun:ident, $getter:ident, $flags:ident, $flag:expr) => {
+        #[doc = concat!("Set or clear the flag reported by [`Self::", stringify!($getter), "`].")]
+        pub fn $fun(&mut self, bit: bool) {
+            self.$flags.set($flag, bit);
+        }
+    };
+}
+
 macro_rules! is_bit_set {
     ($field:expr, $bit:expr) => {
         $field & (1 << $bit) > 0
@@ -153,41 +542,97 @@ macro_rules! check_bit_fn {
     };
 }
 
-/// Implements function to read/write cpuid.
-/// This allows to conveniently swap out the underlying cpuid implementation
-/// with one that returns data that is deterministic (for unit-testing).
-#[derive(Debug, Clone, Copy)]
-struct CpuIdReader {
-    cpuid_fn: fn(u32, u32) -> CpuIdResult,
+macro_rules! set_bit_fn {
+    ($fun:ident, $getter:ident, $field:ident, $bit:expr) => {
+        #[doc = concat!("Set or clear the bit reported by [`Self::", stringify!($getter), "`].")]
+        pub fn $fun(&mut self, bit: bool) {
+            set_bits(&mut self.$field, $bit, $bit, bit as u32);
+        }
+    };
 }
 
-impl CpuIdReader {
-    fn new(cpuid_fn: fn(u32, u32) -> CpuIdResult) -> Self {
-        Self { cpuid_fn }
-    }
+macro_rules! set_bits_fn {
+    ($fun:ident, $getter:ident, $field:ident, $from:expr, $to:expr, $t:ty) => {
+        #[doc = concat!("Set the value reported by [`Self::", stringify!($getter), "`].")]
+        pub fn $fun(&mut self, value: $t) {
+            set_bits(&mut self.$field, $from, $to, value as u32);
+        }
+    };
+}
+
+/// Implements `PartialEq` and `Eq` for info structs that keep a reader to
+/// query further subleafs: only the register fields are compared.
+macro_rules! eq_without_reader {
+    ($name:ident, $($field:ident),+) => {
+        impl<R: CpuIdReader> PartialEq for $name<R> {
+            fn eq(&self, other: &Self) -> bool {
+                $(self.$field == other.$field)&&+
+            }
+        }
+
+        impl<R: CpuIdReader> Eq for $name<R> {}
+    };
+}
+
+/// Like `set_bits_fn` but for fields that encode a count minus one.
+macro_rules! set_count_fn {
+    ($fun:ident, $getter:ident, $field:ident, $from:expr, $to:expr, $t:ty) => {
+        #[doc = concat!(
+            "Set the value reported by [`Self::", stringify!($getter), "`] (must be at least 1)."
+        )]
+        pub fn $fun(&mut self, value: $t) {
+            set_bits(&mut self.$field, $from, $to, (value as u32).saturating_sub(1));
+        }
+    };
+}
 
+/// Implements function to read/write cpuid.
+/// This allows to conveniently swap out the underlying cpuid implementation
+/// with one that returns data that is deterministic (for unit-testing).
+pub trait CpuIdReader: Clone {
+    /// Execute cpuid with the given leaf (`eax`) and subleaf 0.
     fn cpuid1(&self, eax: u32) -> CpuIdResult {
-        (self.cpuid_fn)(eax, 0)
+        self.cpuid2(eax, 0)
     }
 
+    /// Execute cpuid with the given leaf (`eax`) and subleaf (`ecx`).
+    fn cpuid2(&self, eax: u32, ecx: u32) -> CpuIdResult;
+}
+
+impl<F> CpuIdReader for F
+where
+    F: Fn(u32, u32) -> CpuIdResult + Clone,
+{
     fn cpuid2(&self, eax: u32, ecx: u32) -> CpuIdResult {
-        (self.cpuid_fn)(eax, ecx)
+        self(eax, ecx)
     }
 }
 
-impl Default for CpuIdReader {
-    fn default() -> Self {
-        Self {
-            cpuid_fn: native_cpuid::cpuid_count,
-        }
+/// A [`CpuIdReader`] that executes the `cpuid` instruction on the current core.
+///
+/// The instruction can be replaced with a mock, see
+/// [`native_cpuid::set_mock_reader`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuIdReaderNative;
+
+impl CpuIdReader for CpuIdReaderNative {
+    fn cpuid2(&self, eax: u32, ecx: u32) -> CpuIdResult {
+        native_cpuid::cpuid_count(eax, ecx)
     }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 enum Vendor {
     Intel,
     Amd,
+    /// AMD-derived CPUs (e.g., Hygon Dhyana) that implement AMD's leafs.
+    Hygon,
+    /// Centaur (VIA) and Zhaoxin CPUs, which implement the Centaur leafs.
+    Centaur,
+    /// Transmeta CPUs, which implement the Transmeta leafs.
+    Transmeta,
     Unknown(u32, u32, u32),
 }
 
@@ -202,6 +647,9 @@ impl Vendor {
         match vi.as_str() {
             "GenuineIntel" => Vendor::Intel,
             "AuthenticAMD" => Vendor::Amd,
+            "HygonGenuine" => Vendor::Hygon,
+            "CentaurHauls" | "  Shanghai  " => Vendor::Centaur,
+            "GenuineTMx86" | "TransmetaCPU" => Vendor::Transmeta,
             _ => Vendor::Unknown(res.ebx, res.ecx, res.edx),
         }
     }
@@ -210,28 +658,40 @@ impl Vendor {
 /// The main type used to query information about the CPU we're running on.
 ///
 /// Other structs can be accessed by going through this type.
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Copy)]
-pub struct CpuId {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
+pub struct CpuId<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
     /// CPU vendor to differntiate cases where logic needs to differ in code .
     vendor: Vendor,
     /// How many basic leafs are supported (EAX < EAX_HYPERVISOR_INFO)
     supported_leafs: u32,
     /// How many extended leafs are supported (e.g., leafs with EAX > EAX_EXTENDED_FUNCTION_INFO)
     supported_extended_leafs: u32,
-}
-
-impl Default for CpuId {
-    fn default() -> CpuId {
-        CpuId::with_cpuid_fn(native_cpuid::cpuid_count)
+    /// Highest supported hypervisor leaf (`None` if not running on a hypervisor)
+    #[cfg_attr(feature = "serde", serde(default))]
+    supported_hypervisor_leafs: Option<u32>,
+    /// EAX of LEAF=0xC000_0000 (`None` if the vendor can't have Centaur leafs)
+    #[cfg_attr(feature = "serde", serde(default))]
+    supported_centaur_leafs: Option<u32>,
+    /// EAX of LEAF=0x8086_0000 (`None` if the vendor can't have Transmeta leafs)
+    #[cfg_attr(feature = "serde", serde(default))]
+    supported_transmeta_leafs: Option<u32>,
+    /// Trust AMD-defined leafs and bits on vendors other than Intel, AMD and Hygon.
+    #[cfg_attr(feature = "serde", serde(default))]
+    permissive_vendor: bool,
+}
+
+impl Default for CpuId<CpuIdReaderNative> {
+    fn default() -> Self {
+        CpuId::with_cpuid_reader(CpuIdReaderNative)
     }
 }
 
 /// Low-level data-structure to store result of cpuid instruction.
 #[derive(Copy, Clone, Eq, PartialEq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[repr(C)]
 pub struct CpuIdResult {
     /// Return value EAX register
@@ -261,6 +721,41 @@ impl Debug for CpuIdResult {
     }
 }
 
+/// Formats all four registers as `eax=... ebx=... ecx=... edx=...`.
+///
+/// Each register is zero-padded to its full width, the alternate flag (e.g.,
+/// `{:#x}`) adds the `0x`/`0b` prefix.
+macro_rules! register_fmt {
+    ($trait:ident, $digits:expr, $plain:literal, $alternate:literal) => {
+        impl fmt::$trait for CpuIdResult {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                let registers = [
+                    ("eax", self.eax),
+                    ("ebx", self.ebx),
+                    ("ecx", self.ecx),
+                    ("edx", self.edx),
+                ];
+                for (i, (name, value)) in registers.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(" ")?;
+                    }
+                    write!(f, "{}=", name)?;
+                    if f.alternate() {
+                        write!(f, $alternate, value, width = $digits + 2)?;
+                    } else {
+                        write!(f, $plain, value, width = $digits)?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+register_fmt!(LowerHex, 8, "{:0width$x}", "{:#0width$x}");
+register_fmt!(UpperHex, 8, "{:0width$X}", "{:#0width$X}");
+register_fmt!(Binary, 32, "{:0width$b}", "{:#0width$b}");
+
 //
 // Normal leafs:
 //
@@ -284,9 +779,26 @@ const EAX_TIME_STAMP_COUNTER_INFO: u32 = 0x15;
 const EAX_FREQUENCY_INFO: u32 = 0x16;
 const EAX_SOC_VENDOR_INFO: u32 = 0x17;
 const EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO: u32 = 0x18;
+const EAX_HYBRID_INFO: u32 = 0x1A;
+const EAX_PCONFIG_INFO: u32 = 0x1B;
+const EAX_LBR_INFO: u32 = 0x1C;
+const EAX_TMUL_INFO: u32 = 0x1E;
+const EAX_EXTENDED_TOPOLOGY_INFO_V2: u32 = 0x1F;
+const EAX_HRESET_INFO: u32 = 0x20;
+const EAX_TDX_INFO: u32 = 0x21;
+const EAX_ARCH_PERFMON_EXT_INFO: u32 = 0x23;
+const EAX_AVX10_INFO: u32 = 0x24;
 
 /// Hypervisor leaf
 const EAX_HYPERVISOR_INFO: u32 = 0x4000_0000;
+const EAX_HYPERV_INTERFACE: u32 = 0x4000_0001;
+const EAX_HYPERV_FEATURES: u32 = 0x4000_0003;
+const EAX_HYPERV_NESTED_FEATURES: u32 = 0x4000_000A;
+const EAX_HYPERVISOR_TIMING_INFO: u32 = 0x4000_0010;
+/// Hypervisors can implement several interfaces, each in its own block of
+/// 0x100 leafs starting at 0x4000_0000 + N * 0x100.
+const HYPERVISOR_BLOCK_SIZE: u32 = 0x100;
+const EAX_HYPERVISOR_LAST_BLOCK: u32 = 0x4000_ff00;
 
 //
 // Extended leafs:
@@ -301,41 +813,401 @@ const EAX_ADVANCED_POWER_MGMT_INFO: u32 = 0x8000_0007;
 const EAX_PROCESSOR_CAPACITY_INFO: u32 = 0x8000_0008;
 const EAX_MEMORY_ENCRYPTION_INFO: u32 = 0x8000_001F;
 const EAX_SVM_FEATURES: u32 = 0x8000_000A;
+const EAX_TLB_1G_PAGE_INFO: u32 = 0x8000_0019;
+const EAX_CACHE_PARAMETERS_AMD: u32 = 0x8000_001D;
+const EAX_PROCESSOR_TOPOLOGY_INFO: u32 = 0x8000_001E;
+const EAX_PERFORMANCE_MONITORING_DEBUG_INFO: u32 = 0x8000_0022;
+const EAX_EXTENDED_CPU_TOPOLOGY: u32 = 0x8000_0026;
+
+//
+// Transmeta leafs:
+//
+const EAX_TRANSMETA_FUNCTION_INFO: u32 = 0x8086_0000;
+const EAX_TRANSMETA_PROCESSOR_INFO: u32 = 0x8086_0001;
+const EAX_TRANSMETA_CMS_VERSION: u32 = 0x8086_0002;
+const EAX_TRANSMETA_INFO_STRING: u32 = 0x8086_0003;
+
+//
+// Centaur leafs:
+//
+const EAX_CENTAUR_FUNCTION_INFO: u32 = 0xC000_0000;
+const EAX_CENTAUR_FEATURE_INFO: u32 = 0xC000_0001;
 
-impl CpuId {
+impl CpuId<CpuIdReaderNative> {
     /// Return new CpuId struct.
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Return new CpuId struct, or an error if the CPU doesn't implement the
+    /// `cpuid` instruction.
+    ///
+    /// [`CpuId::new`] faults on such CPUs (i386 and early i486 processors).
+    /// On 32-bit x86 targets this checks whether the ID flag in EFLAGS can be
+    /// toggled before executing `cpuid`. It always succeeds on x86_64.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// match CpuId::try_new() {
+    ///     Ok(cpuid) => println!("{:?}", cpuid.get_vendor_info()),
+    ///     Err(e) => println!("No CPUID: {}", e),
+    /// }
+    /// ```
+    pub fn try_new() -> Result<Self, CpuIdError> {
+        if native_cpuid::has_cpuid() {
+            Ok(Self::new())
+        } else {
+            Err(CpuIdError::Unavailable)
+        }
+    }
+
+    /// Calls `f` with the id and a `CpuId` for every online core.
+    ///
+    /// `f` runs on a separate thread that is pinned to the respective core,
+    /// so all (per-core) values like the APIC ID returned by the `CpuId` are
+    /// the ones of that core. Cores are visited one after another.
+    ///
+    /// Returns an error if the online cores can't be determined.
+    ///
+    /// # Note
+    /// On macOS there is no guarantee that a thread really executes on the core
+    /// it is pinned to.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// let mut apic_ids = Vec::new();
+    /// CpuId::for_each_core(|_core_id, cpuid| {
+    ///     apic_ids.push(cpuid.x2apic_id());
+    /// })
+    /// .expect("Can't determine cores");
+    /// assert!(!apic_ids.is_empty());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn for_each_core<F>(mut f: F) -> std::io::Result<()>
+    where
+        F: FnMut(usize, &CpuId) + Send,
+    {
+        let core_ids = core_affinity::get_core_ids()
+            .ok_or_else(|| std::io::Error::other("can't determine online cores"))?;
+
+        for core_id in core_ids {
+            let f = &mut f;
+            std::thread::scope(|s| {
+                let pinned = s.spawn(move || {
+                    core_affinity::set_for_current(core_id);
+                    f(core_id.id, &CpuId::new());
+                });
+                if let Err(e) = pinned.join() {
+                    std::panic::resume_unwind(e);
+                }
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: CpuIdReader> CpuId<R> {
     /// Return new CpuId struct with custom reader function.
     ///
     /// This is useful for example when testing code or if we want to interpose
     /// on the CPUID calls this library makes.
-    pub fn with_cpuid_fn(cpuid_fn: fn(u32, u32) -> CpuIdResult) -> Self {
-        let read = CpuIdReader::new(cpuid_fn);
-        let vendor_leaf = read.cpuid1(EAX_VENDOR_INFO);
-        let extended_leaf = read.cpuid1(EAX_EXTENDED_FUNCTION_INFO);
-        CpuId {
-            supported_leafs: vendor_leaf.eax,
-            supported_extended_leafs: extended_leaf.eax,
-            vendor: Vendor::from_vendor_leaf(vendor_leaf),
+    pub fn with_cpuid_fn(cpuid_fn: R) -> Self {
+        Self::with_cpuid_reader(cpuid_fn)
+    }
+
+    /// Return new CpuId struct that reads the leafs through `read`.
+    ///
+    /// This can be a closure, a `CpuIdSnapshot` or any other type
+    /// implementing [`CpuIdReader`].
+    pub fn with_cpuid_reader(read: R) -> Self {
+        let mut cpuid = CpuId {
+            supported_leafs: 0,
+            supported_extended_leafs: 0,
+            supported_hypervisor_leafs: None,
+            supported_centaur_leafs: None,
+            supported_transmeta_leafs: None,
+            vendor: Vendor::Unknown(0, 0, 0),
+            permissive_vendor: false,
             read,
+        };
+        cpuid.refresh();
+        cpuid
+    }
+
+    /// Re-reads the vendor and the maximum basic, extended, hypervisor,
+    /// Centaur and Transmeta leaf, which are the only values `CpuId` caches.
+    ///
+    /// Everything else is read from the [`CpuIdReader`] on every call, so
+    /// after a refresh all accessors reflect the current state of the CPU.
+    /// Structs returned by earlier calls (e.g., [`ExtendedFeatures`]) are
+    /// copies of the registers at the time of the call and are not updated.
+    ///
+    /// The contents of `cpuid` can change while a program runs, e.g., after
+    /// a microcode update, when TSX is disabled at runtime, on CPU hotplug or
+    /// when a virtual machine is migrated to a different host. Long-running
+    /// programs that cache the results should refresh and query again in
+    /// these cases.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// let mut cpuid = CpuId::new();
+    /// // ... a microcode update was applied ...
+    /// cpuid.refresh();
+    /// let features = cpuid.get_extended_feature_info();
+    /// ```
+    pub fn refresh(&mut self) {
+        let vendor_leaf = self.read.cpuid1(EAX_VENDOR_INFO);
+        let extended_leaf = self.read.cpuid1(EAX_EXTENDED_FUNCTION_INFO);
+        self.supported_leafs = vendor_leaf.eax;
+        self.supported_extended_leafs = extended_leaf.eax;
+        self.vendor = Vendor::from_vendor_leaf(vendor_leaf);
+
+        // Without the hypervisor flag the hypervisor leafs return the values
+        // of the highest basic leaf.
+        let has_hypervisor = self
+            .get_feature_info()
+            .is_some_and(|fi| fi.has_hypervisor());
+        self.supported_hypervisor_leafs = has_hypervisor
+            .then(|| self.read.cpuid1(EAX_HYPERVISOR_INFO))
+            .and_then(|res| hypervisor_block_max_leaf(EAX_HYPERVISOR_INFO, &res));
+
+        // Unknown vendors may use the leafs once they are made permissive.
+        let is_unknown = matches!(self.vendor, Vendor::Unknown(..));
+        self.supported_centaur_leafs = (is_unknown || self.vendor == Vendor::Centaur)
+            .then(|| self.read.cpuid1(EAX_CENTAUR_FUNCTION_INFO).eax);
+        self.supported_transmeta_leafs = (is_unknown || self.vendor == Vendor::Transmeta)
+            .then(|| self.read.cpuid1(EAX_TRANSMETA_FUNCTION_INFO).eax);
+    }
+
+    /// Trust the leafs and feature bits that are only defined by AMD (e.g.,
+    /// LEAF=0x8000_0005 or SSE4A) on vendors other than Intel, AMD and Hygon.
+    ///
+    /// By default, these accessors return `None` (or `false`) unless the
+    /// vendor is `AuthenticAMD` or `HygonGenuine`, even if the maximum leaf
+    /// says the leaf is present. Other vendors with AMD-compatible leafs or
+    /// new vendors can enable this to rely on the max-leaf enumeration only.
+    ///
+    /// Unknown vendors also get access to the Centaur and Transmeta leafs
+    /// (see [`CpuId::max_centaur_leaf`] and [`CpuId::max_transmeta_leaf`]).
+    pub fn set_permissive_vendor(&mut self, permissive: bool) {
+        self.permissive_vendor = permissive;
+    }
+
+    /// Are AMD-defined leafs trusted on vendors other than Intel and AMD?
+    ///
+    /// See [`CpuId::set_permissive_vendor`].
+    pub fn is_permissive_vendor(&self) -> bool {
+        self.permissive_vendor
+    }
+
+    /// Can the AMD-defined leafs and bits be used on this CPU?
+    fn is_amd_compatible(&self) -> bool {
+        match self.vendor {
+            Vendor::Amd | Vendor::Hygon => true,
+            Vendor::Intel => false,
+            Vendor::Centaur | Vendor::Transmeta | Vendor::Unknown(..) => self.permissive_vendor,
         }
     }
 
     /// Check if a non extended leaf  (`val`) is supported.
     fn leaf_is_supported(&self, val: u32) -> bool {
-        // Exclude reserved functions/leafs on AMD
-        if self.vendor == Vendor::Amd && ((0x2..=0x4).contains(&val) || (0x8..=0xa).contains(&val))
-        {
-            return false;
+        !self.leaf_is_reserved_for_vendor(val) && self.leaf_is_in_range(val)
+    }
+
+    /// Is `val` a leaf the vendor of this CPU doesn't implement, even though it
+    /// is below the maximum leaf?
+    fn leaf_is_reserved_for_vendor(&self, val: u32) -> bool {
+        // Exclude reserved functions/leafs on AMD (and Hygon)
+        matches!(self.vendor, Vendor::Amd | Vendor::Hygon)
+            && ((0x2..=0x4).contains(&val) || (0x8..=0xa).contains(&val))
+    }
+
+    /// Is `val` below the maximum leaf of its range?
+    fn leaf_is_in_range(&self, val: u32) -> bool {
+        if val < EAX_HYPERVISOR_INFO {
+            val <= self.max_basic_leaf()
+        } else if val < EAX_EXTENDED_FUNCTION_INFO {
+            self.max_hypervisor_leaf().is_some_and(|max| val <= max)
+        } else if get_bits(val, 16, 31) == get_bits(EAX_TRANSMETA_FUNCTION_INFO, 16, 31) {
+            self.max_transmeta_leaf().is_some_and(|max| val <= max)
+        } else if val < EAX_CENTAUR_FUNCTION_INFO {
+            self.max_extended_leaf().is_some_and(|max| val <= max)
+        } else {
+            self.max_centaur_leaf().is_some_and(|max| val <= max)
+        }
+    }
+
+    /// Highest supported basic leaf (EAX of LEAF=0x00).
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn max_basic_leaf(&self) -> u32 {
+        self.supported_leafs
+    }
+
+    /// Highest supported extended leaf (EAX of LEAF=0x8000_0000).
+    ///
+    /// Returns `None` if the CPU doesn't implement extended leafs, i.e., the
+    /// value isn't in the range 0x8000_0000..=0x8000_FFFF (CPUs without
+    /// extended leafs may return the values of the highest basic leaf).
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn max_extended_leaf(&self) -> Option<u32> {
+        (get_bits(self.supported_extended_leafs, 16, 31) == 0x8000)
+            .then_some(self.supported_extended_leafs)
+    }
+
+    /// Highest supported hypervisor leaf (EAX of LEAF=0x4000_0000).
+    ///
+    /// Returns `None` if the hypervisor flag (`CPUID.01H:ECX[31]`) is clear
+    /// or the value isn't in the range 0x4000_0000..=0x4000_00FF. Without the
+    /// flag the hypervisor leafs return the values of the highest basic leaf.
+    /// Old KVM versions report 0, which means 0x4000_0001.
+    ///
+    /// # Platforms
+    /// Needs to be a virtual CPU to be supported.
+    pub fn max_hypervisor_leaf(&self) -> Option<u32> {
+        self.supported_hypervisor_leafs
+    }
+
+    /// Highest supported Centaur leaf (EAX of LEAF=0xC000_0000).
+    ///
+    /// Returns `None` if the vendor isn't Centaur (VIA) or Zhaoxin (unless
+    /// [`CpuId::set_permissive_vendor`] is enabled for other unknown vendors)
+    /// or the value isn't in the range 0xC000_0000..=0xC000_FFFF.
+    ///
+    /// # Platforms
+    /// ❌ AMD ❌ Intel ✅ Centaur ✅ Zhaoxin
+    pub fn max_centaur_leaf(&self) -> Option<u32> {
+        let has_centaur_leafs = match self.vendor {
+            Vendor::Centaur => true,
+            Vendor::Unknown(..) => self.permissive_vendor,
+            _ => false,
+        };
+        if !has_centaur_leafs {
+            return None;
+        }
+        self.supported_centaur_leafs
+            .filter(|max| get_bits(*max, 16, 31) == 0xC000)
+    }
+
+    /// Highest supported Transmeta leaf (EAX of LEAF=0x8086_0000).
+    ///
+    /// Returns `None` if the vendor isn't Transmeta (unless
+    /// [`CpuId::set_permissive_vendor`] is enabled for other unknown vendors)
+    /// or the value isn't in the range 0x8086_0000..=0x8086_FFFF.
+    ///
+    /// # Platforms
+    /// ❌ AMD ❌ Intel ✅ Transmeta
+    pub fn max_transmeta_leaf(&self) -> Option<u32> {
+        let has_transmeta_leafs = match self.vendor {
+            Vendor::Transmeta => true,
+            Vendor::Unknown(..) => self.permissive_vendor,
+            _ => false,
+        };
+        if !has_transmeta_leafs {
+            return None;
         }
+        self.supported_transmeta_leafs
+            .filter(|max| get_bits(*max, 16, 31) == 0x8086)
+    }
 
-        if val < EAX_EXTENDED_FUNCTION_INFO {
-            val <= self.supported_leafs
+    /// Read the raw registers of `leaf` and `subleaf`, or return
+    /// [`CpuIdError::LeafUnsupported`] if `leaf` is larger than the maximum
+    /// leaf of its range (basic, hypervisor or extended).
+    ///
+    /// Reading an unsupported leaf doesn't necessarily return zeros: many
+    /// CPUs return the values of the highest basic leaf instead.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{CpuId, CpuIdError};
+    ///
+    /// let cpuid = CpuId::new();
+    /// assert!(cpuid.read_leaf(0x0, 0).is_ok());
+    /// assert_eq!(
+    ///     cpuid.read_leaf(0x8fff_ffff, 0),
+    ///     Err(CpuIdError::LeafUnsupported { leaf: 0x8fff_ffff })
+    /// );
+    /// ```
+    pub fn read_leaf(&self, leaf: u32, subleaf: u32) -> Result<CpuIdResult, CpuIdError> {
+        if self.leaf_is_supported(leaf) {
+            Ok(self.read.cpuid2(leaf, subleaf))
         } else {
-            val <= self.supported_extended_leafs
+            Err(CpuIdError::LeafUnsupported { leaf })
+        }
+    }
+
+    /// Is `subleaf` of `leaf` enumerated by this CPU?
+    ///
+    /// The leaf has to be within the maximum of its range (see
+    /// [`CpuId::max_basic_leaf`], [`CpuId::max_extended_leaf`] and
+    /// [`CpuId::max_hypervisor_leaf`]). Subleaf 0 is valid for every
+    /// supported leaf. Other subleafs are checked with the rules of the leafs
+    /// that have subleafs, e.g., the maximum subleaf in EAX of LEAF=0x07, the
+    /// termination entry of LEAF=0x04 and 0x0B, or the supported state
+    /// components of LEAF=0x0D. Leafs without subleafs only enumerate
+    /// subleaf 0.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// let cpuid = CpuId::new();
+    /// assert!(cpuid.supports_leaf(0x0, 0));
+    /// assert!(!cpuid.supports_leaf(0x0, 1));
+    /// ```
+    pub fn supports_leaf(&self, leaf: u32, subleaf: u32) -> bool {
+        if !self.leaf_is_supported(leaf) {
+            return false;
+        }
+        if subleaf == 0 {
+            return true;
+        }
+
+        let res0 = self.read.cpuid2(leaf, 0);
+        let res = || self.read.cpuid2(leaf, subleaf);
+        let bit = |value: u64| subleaf < 64 && (value >> subleaf) & 1 == 1;
+        match leaf {
+            // Terminated by an entry with cache type `Null`:
+            EAX_CACHE_PARAMETERS | EAX_CACHE_PARAMETERS_AMD => get_bits(res().eax, 0, 4) != 0,
+            // Terminated by an entry with level type `Invalid`:
+            EAX_EXTENDED_TOPOLOGY_INFO
+            | EAX_EXTENDED_TOPOLOGY_INFO_V2
+            | EAX_EXTENDED_CPU_TOPOLOGY => get_bits(res().ecx, 8, 15) != 0,
+            // Maximum subleaf in EAX of subleaf 0:
+            EAX_STRUCTURED_EXTENDED_FEATURE_INFO
+            | EAX_TRACE_INFO
+            | EAX_SOC_VENDOR_INFO
+            | EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO
+            | 0x1D
+            | EAX_TMUL_INFO
+            | EAX_HRESET_INFO
+            | EAX_AVX10_INFO => subleaf <= res0.eax,
+            // Subleaf 1 and the supported XCR0 and IA32_XSS state components:
+            EAX_EXTENDED_STATE_INFO => {
+                let res1 = self.read.cpuid2(leaf, 1);
+                subleaf == 1
+                    || bit((res0.edx as u64) << 32 | res0.eax as u64)
+                    || bit((res1.edx as u64) << 32 | res1.ecx as u64)
+            }
+            // Resource types reported as bitmap in subleaf 0:
+            EAX_RDT_MONITORING => bit(res0.edx as u64),
+            EAX_RDT_ALLOCATION => bit(res0.ebx as u64),
+            EAX_ARCH_PERFMON_EXT_INFO => bit(res0.eax as u64),
+            // Subleaf 1 (SGX attributes) and EPC sections with a valid type:
+            EAX_SGX => subleaf == 1 || get_bits(res().eax, 0, 3) != 0,
+            // Terminated by an entry with target type 0:
+            EAX_PCONFIG_INFO => get_bits(res().eax, 0, 11) != 0,
+            _ => false,
         }
     }
 
@@ -417,23 +1289,52 @@ impl CpuId {
         }
     }
 
-    /// Retrieve more elaborate information about caches (LEAF=0x04).
+    /// Retrieve more elaborate information about caches (LEAF=0x04 or
+    /// LEAF=0x8000_001D).
     ///
     /// As opposed to [get_cache_info](CpuId::get_cache_info), this will tell us
     /// about associativity, set size, line size of each level in the cache
     /// hierarchy.
     ///
+    /// On AMD (with topology extensions) the cache properties leaf
+    /// 0x8000_001D is used instead of leaf 0x04, it has the same layout (see
+    /// [`CacheParametersIter::leaf`]).
+    ///
     /// # Platforms
-    /// ❌ AMD ✅ Intel
-    pub fn get_cache_parameters(&self) -> Option<CacheParametersIter> {
-        if self.leaf_is_supported(EAX_CACHE_PARAMETERS) {
-            Some(CacheParametersIter {
-                read: self.read,
-                current: 0,
-            })
+    /// ✅ AMD ✅ Intel
+    pub fn get_cache_parameters(&self) -> Option<CacheParametersIter<R>> {
+        let leaf = if self.has_amd_cache_parameters() {
+            EAX_CACHE_PARAMETERS_AMD
+        } else if self.leaf_is_supported(EAX_CACHE_PARAMETERS) {
+            EAX_CACHE_PARAMETERS
         } else {
-            None
-        }
+            return None;
+        };
+
+        Some(CacheParametersIter::new(self.read.clone(), leaf))
+    }
+
+    /// Is the AMD cache properties leaf (LEAF=0x8000_001D) available?
+    fn has_amd_cache_parameters(&self) -> bool {
+        self.is_amd_compatible()
+            && self
+                .get_extended_processor_and_feature_identifiers()
+                .is_some_and(|f| f.has_topology_extensions())
+            && self.leaf_is_supported(EAX_CACHE_PARAMETERS_AMD)
+    }
+
+    /// Determine which logical processors share each cache with the current
+    /// logical processor (LEAF=0x04 or LEAF=0x8000_001D).
+    ///
+    /// This combines the cache parameters with the APIC ID of the logical
+    /// processor we are currently running on.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn get_cache_sharing_info(&self) -> Option<CacheSharingIter<R>> {
+        let apic_id = self.x2apic_id()?;
+        self.get_cache_parameters()
+            .map(|caches| CacheSharingIter { caches, apic_id })
     }
 
     /// Information about how monitor/mwait works on this CPU (LEAF=0x05).
@@ -465,7 +1366,7 @@ impl CpuId {
                 eax: ThermalPowerFeaturesEax { bits: res.eax },
                 ebx: res.ebx,
                 ecx: ThermalPowerFeaturesEcx { bits: res.ecx },
-                _edx: res.edx,
+                edx: res.edx,
             })
         } else {
             None
@@ -479,23 +1380,70 @@ impl CpuId {
     pub fn get_extended_feature_info(&self) -> Option<ExtendedFeatures> {
         if self.leaf_is_supported(EAX_STRUCTURED_EXTENDED_FEATURE_INFO) {
             let res = self.read.cpuid1(EAX_STRUCTURED_EXTENDED_FEATURE_INFO);
+            // EAX of subleaf 0 reports the maximum supported subleaf.
+            let res1 = if res.eax >= 1 {
+                self.read.cpuid2(EAX_STRUCTURED_EXTENDED_FEATURE_INFO, 1)
+            } else {
+                CpuIdResult {
+                    eax: 0,
+                    ebx: 0,
+                    ecx: 0,
+                    edx: 0,
+                }
+            };
+            let edx2 = if res.eax >= 2 {
+                self.read
+                    .cpuid2(EAX_STRUCTURED_EXTENDED_FEATURE_INFO, 2)
+                    .edx
+            } else {
+                0
+            };
             Some(ExtendedFeatures {
                 _eax: res.eax,
                 ebx: ExtendedFeaturesEbx { bits: res.ebx },
                 ecx: ExtendedFeaturesEcx { bits: res.ecx },
-                _edx: res.edx,
+                edx: res.edx,
+                eax1: res1.eax,
+                ebx1: res1.ebx,
+                ecx1: res1.ecx,
+                edx1: res1.edx,
+                edx2,
             })
         } else {
             None
         }
     }
 
+    /// Can RTM transactions (XBEGIN/XEND) succeed?
+    ///
+    /// Many CPUs still enumerate RTM (and HLE) after TSX was disabled by a
+    /// microcode update, but set RTM_ALWAYS_ABORT to tell that every
+    /// transaction aborts. Lock elision should only be used if this returns
+    /// `true`; with HLE alone XACQUIRE/XRELEASE are hints that never elide a
+    /// lock.
+    ///
+    /// # Note
+    /// The OS can also force aborts with the TSX_FORCE_ABORT MSR (see
+    /// [`ExtendedFeatures::has_tsx_force_abort`]), which isn't visible through
+    /// `cpuid`. Disabling TSX with the IA32_TSX_CTRL MSR usually clears RTM
+    /// and HLE, so it is detected.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn transactional_memory_usable(&self) -> bool {
+        self.get_extended_feature_info()
+            .is_some_and(|f| f.has_rtm() && !f.has_rtm_always_abort())
+    }
+
     /// Direct cache access info (LEAF=0x09).
     ///
+    /// Only available if [`FeatureInfo::has_dca`] is set.
+    ///
     /// # Platforms
     /// ❌ AMD ✅ Intel
     pub fn get_direct_cache_access_info(&self) -> Option<DirectCacheAccessInfo> {
-        if self.leaf_is_supported(EAX_DIRECT_CACHE_ACCESS_INFO) {
+        let has_dca = self.get_feature_info().is_some_and(|info| info.has_dca());
+        if has_dca && self.leaf_is_supported(EAX_DIRECT_CACHE_ACCESS_INFO) {
             let res = self.read.cpuid1(EAX_DIRECT_CACHE_ACCESS_INFO);
             Some(DirectCacheAccessInfo { eax: res.eax })
         } else {
@@ -513,7 +1461,7 @@ impl CpuId {
             Some(PerformanceMonitoringInfo {
                 eax: res.eax,
                 ebx: PerformanceMonitoringFeaturesEbx { bits: res.ebx },
-                _ecx: res.ecx,
+                ecx: res.ecx,
                 edx: res.edx,
             })
         } else {
@@ -521,31 +1469,44 @@ impl CpuId {
         }
     }
 
-    /// Information about topology (LEAF=0x0B).
+    /// Information about topology (LEAF=0x1F or LEAF=0x0B).
+    ///
+    /// The V2 extended topology leaf (LEAF=0x1F) is used if the CPU
+    /// implements it, otherwise this falls back to the extended topology leaf
+    /// (LEAF=0x0B). Both have the same layout, but LEAF=0x1F can report
+    /// additional level types like [`TopologyType::Die`]. Use
+    /// [`ExtendedTopologyIter::leaf`] to find out which one was used.
     ///
     /// # Platforms
     /// ✅ AMD ✅ Intel
-    pub fn get_extended_topology_info(&self) -> Option<ExtendedTopologyIter> {
-        if self.leaf_is_supported(EAX_EXTENDED_TOPOLOGY_INFO) {
-            Some(ExtendedTopologyIter {
-                read: self.read,
-                level: 0,
+    pub fn get_extended_topology_info(&self) -> Option<ExtendedTopologyIter<R>> {
+        let leaf = self.extended_topology_leaf().or_else(|| {
+            self.leaf_is_supported(EAX_EXTENDED_TOPOLOGY_INFO)
+                .then_some(EAX_EXTENDED_TOPOLOGY_INFO)
+        })?;
+        Some(ExtendedTopologyIter::new(self.read.clone(), leaf))
+    }
+
+    /// The first of LEAF=0x1F and LEAF=0x0B that is implemented, i.e.,
+    /// reports a valid level type in subleaf 0.
+    fn extended_topology_leaf(&self) -> Option<u32> {
+        [EAX_EXTENDED_TOPOLOGY_INFO_V2, EAX_EXTENDED_TOPOLOGY_INFO]
+            .into_iter()
+            .find(|&leaf| {
+                self.leaf_is_supported(leaf) && get_bits(self.read.cpuid2(leaf, 0).ecx, 8, 15) != 0
             })
-        } else {
-            None
-        }
     }
 
     /// Information for saving/restoring extended register state (LEAF=0x0D).
     ///
     /// # Platforms
     /// ✅ AMD ✅ Intel
-    pub fn get_extended_state_info(&self) -> Option<ExtendedStateInfo> {
+    pub fn get_extended_state_info(&self) -> Option<ExtendedStateInfo<R>> {
         if self.leaf_is_supported(EAX_EXTENDED_STATE_INFO) {
             let res = self.read.cpuid2(EAX_EXTENDED_STATE_INFO, 0);
             let res1 = self.read.cpuid2(EAX_EXTENDED_STATE_INFO, 1);
             Some(ExtendedStateInfo {
-                read: self.read,
+                read: self.read.clone(),
                 eax: ExtendedStateInfoXCR0Flags { bits: res.eax },
                 ebx: res.ebx,
                 ecx: res.ecx,
@@ -564,12 +1525,12 @@ impl CpuId {
     ///
     /// # Platforms
     /// ❌ AMD ✅ Intel
-    pub fn get_rdt_monitoring_info(&self) -> Option<RdtMonitoringInfo> {
+    pub fn get_rdt_monitoring_info(&self) -> Option<RdtMonitoringInfo<R>> {
         let res = self.read.cpuid1(EAX_RDT_MONITORING);
 
         if self.leaf_is_supported(EAX_RDT_MONITORING) {
             Some(RdtMonitoringInfo {
-                read: self.read,
+                read: self.read.clone(),
                 ebx: res.ebx,
                 edx: res.edx,
             })
@@ -582,12 +1543,12 @@ impl CpuId {
     ///
     /// # Platforms
     /// ❌ AMD ✅ Intel
-    pub fn get_rdt_allocation_info(&self) -> Option<RdtAllocationInfo> {
+    pub fn get_rdt_allocation_info(&self) -> Option<RdtAllocationInfo<R>> {
         let res = self.read.cpuid1(EAX_RDT_ALLOCATION);
 
         if self.leaf_is_supported(EAX_RDT_ALLOCATION) {
             Some(RdtAllocationInfo {
-                read: self.read,
+                read: self.read.clone(),
                 ebx: res.ebx,
             })
         } else {
@@ -595,18 +1556,36 @@ impl CpuId {
         }
     }
 
-    /// Information about secure enclave support (LEAF=0x12).
+    /// Check if SGX Flexible Launch Control is available, i.e., the launch
+    /// enclave's public key hash (IA32_SGXLEPUBKEYHASHn) can be configured.
     ///
-    /// # Platforms
+    /// This requires SGX and SGX_LC in leaf 7 and SGX1 in leaf 0x12.
+    ///
+    /// # Note
+    /// The BIOS still has to unlock the `IA32_SGXLEPUBKEYHASHn` MSRs, which
+    /// isn't visible through `cpuid`.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_sgx_flexible_launch_control(&self) -> bool {
+        let has_sgx_lc = self
+            .get_extended_feature_info()
+            .is_some_and(|info| info.has_sgx_lc());
+        has_sgx_lc && self.get_sgx_info().is_some_and(|sgx| sgx.has_sgx1())
+    }
+
+    /// Information about secure enclave support (LEAF=0x12).
+    ///
+    /// # Platforms
     /// ❌ AMD ✅ Intel
-    pub fn get_sgx_info(&self) -> Option<SgxInfo> {
+    pub fn get_sgx_info(&self) -> Option<SgxInfo<R>> {
         // Leaf 12H sub-leaf 0 (ECX = 0) is supported if CPUID.(EAX=07H, ECX=0H):EBX[SGX] = 1.
         self.get_extended_feature_info().and_then(|info| {
             if self.leaf_is_supported(EAX_SGX) && info.has_sgx() {
                 let res = self.read.cpuid2(EAX_SGX, 0);
                 let res1 = self.read.cpuid2(EAX_SGX, 1);
                 Some(SgxInfo {
-                    read: self.read,
+                    read: self.read.clone(),
                     eax: res.eax,
                     ebx: res.ebx,
                     _ecx: res.ecx,
@@ -685,11 +1664,11 @@ impl CpuId {
     ///
     /// # Platforms
     /// ❌ AMD ✅ Intel
-    pub fn get_soc_vendor_info(&self) -> Option<SoCVendorInfo> {
+    pub fn get_soc_vendor_info(&self) -> Option<SoCVendorInfo<R>> {
         if self.leaf_is_supported(EAX_SOC_VENDOR_INFO) {
             let res = self.read.cpuid1(EAX_SOC_VENDOR_INFO);
             Some(SoCVendorInfo {
-                read: self.read,
+                read: self.read.clone(),
                 eax: res.eax,
                 ebx: res.ebx,
                 ecx: res.ecx,
@@ -704,13 +1683,13 @@ impl CpuId {
     ///
     /// # Platforms
     /// ❌ AMD ✅ Intel
-    pub fn get_deterministic_address_translation_info(&self) -> Option<DatIter> {
+    pub fn get_deterministic_address_translation_info(&self) -> Option<DatIter<R>> {
         if self.leaf_is_supported(EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO) {
             let res = self
                 .read
                 .cpuid2(EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO, 0);
             Some(DatIter {
-                read: self.read,
+                read: self.read.clone(),
                 current: 0,
                 count: res.eax,
             })
@@ -719,22 +1698,204 @@ impl CpuId {
         }
     }
 
+    /// Core type and native model ID of the core we're running on
+    /// (LEAF=0x1A).
+    ///
+    /// Only returned if the processor is a hybrid part, i.e., it enumerates
+    /// `CPUID.(EAX=07H, ECX=0H):EDX[15]`. The result differs between cores,
+    /// see [`HybridSummary`] to gather it for all cores.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn get_hybrid_info(&self) -> Option<HybridInfo> {
+        let has_hybrid = self
+            .get_extended_feature_info()
+            .is_some_and(|info| info.has_hybrid());
+        if has_hybrid && self.leaf_is_supported(EAX_HYBRID_INFO) {
+            let res = self.read.cpuid2(EAX_HYBRID_INFO, 0);
+            Some(HybridInfo { eax: res.eax })
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the targets supported by the PCONFIG instruction
+    /// (LEAF=0x1B).
+    ///
+    /// Only returned if the processor enumerates PCONFIG support in
+    /// `CPUID.(EAX=07H, ECX=0H):EDX[18]`.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn get_pconfig_info(&self) -> Option<PconfigIter<R>> {
+        let has_pconfig = self
+            .get_extended_feature_info()
+            .is_some_and(|info| info.has_pconfig());
+        if has_pconfig && self.leaf_is_supported(EAX_PCONFIG_INFO) {
+            Some(PconfigIter {
+                read: self.read.clone(),
+                current: 0,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Architectural last branch record (LBR) capabilities (LEAF=0x1C).
+    ///
+    /// Only returned if the processor enumerates architectural LBRs in
+    /// `CPUID.(EAX=07H, ECX=0H):EDX[19]`.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn get_lbr_info(&self) -> Option<LbrInfo> {
+        let has_arch_lbr = self
+            .get_extended_feature_info()
+            .is_some_and(|info| info.has_arch_lbr());
+        if has_arch_lbr && self.leaf_is_supported(EAX_LBR_INFO) {
+            let res = self.read.cpuid2(EAX_LBR_INFO, 0);
+            Some(LbrInfo {
+                eax: res.eax,
+                ebx: res.ebx,
+                ecx: res.ecx,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// AMX tile matrix multiply (TMUL) limits and AMX features (LEAF=0x1E).
+    ///
+    /// Only returned if the processor enumerates AMX-TILE support in
+    /// `CPUID.(EAX=07H, ECX=0H):EDX[24]`.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn get_tmul_info(&self) -> Option<TmulInfo> {
+        let has_amx_tile = self
+            .get_extended_feature_info()
+            .is_some_and(|info| info.has_amx_tile());
+        if has_amx_tile && self.leaf_is_supported(EAX_TMUL_INFO) {
+            let res = self.read.cpuid2(EAX_TMUL_INFO, 0);
+            let res1 = (res.eax >= 1).then(|| self.read.cpuid2(EAX_TMUL_INFO, 1));
+            Some(TmulInfo::from_registers(res, res1))
+        } else {
+            None
+        }
+    }
+
+    /// Processor history reset capabilities (LEAF=0x20).
+    ///
+    /// Only returned if the processor enumerates HRESET support in
+    /// `CPUID.(EAX=07H, ECX=01H):EAX[22]`.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn get_hreset_info(&self) -> Option<HresetInfo> {
+        let has_hreset = self
+            .get_extended_feature_info()
+            .is_some_and(|info| info.has_hreset());
+        if has_hreset && self.leaf_is_supported(EAX_HRESET_INFO) {
+            let res = self.read.cpuid2(EAX_HRESET_INFO, 0);
+            Some(HresetInfo {
+                eax: res.eax,
+                ebx: res.ebx,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Check if we're running inside an Intel TDX trust domain (TD guest).
+    ///
+    /// The TDX module reports the vendor string "IntelTDX    " in EBX, EDX
+    /// and ECX of leaf 0x21, subleaf 0.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn is_tdx_guest(&self) -> bool {
+        if !self.leaf_is_supported(EAX_TDX_INFO) {
+            return false;
+        }
+        let res = self.read.cpuid2(EAX_TDX_INFO, 0);
+        let mut signature = [0u8; 12];
+        signature[0..4].copy_from_slice(&res.ebx.to_le_bytes());
+        signature[4..8].copy_from_slice(&res.edx.to_le_bytes());
+        signature[8..12].copy_from_slice(&res.ecx.to_le_bytes());
+        &signature == b"IntelTDX    "
+    }
+
+    /// Architectural performance monitoring extensions (LEAF=0x23).
+    ///
+    /// Only returned if the processor enumerates this leaf in
+    /// `CPUID.(EAX=07H, ECX=01H):EAX[8]`.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn get_arch_perfmon_ext_info(&self) -> Option<ArchPerfmonExtInfo> {
+        let has_arch_perfmon_ext = self
+            .get_extended_feature_info()
+            .is_some_and(|info| info.has_arch_perfmon_ext());
+        if has_arch_perfmon_ext && self.leaf_is_supported(EAX_ARCH_PERFMON_EXT_INFO) {
+            let res = self.read.cpuid2(EAX_ARCH_PERFMON_EXT_INFO, 0);
+            let subleaf = |n: u32| {
+                if is_bit_set!(res.eax, n) {
+                    Some(self.read.cpuid2(EAX_ARCH_PERFMON_EXT_INFO, n))
+                } else {
+                    None
+                }
+            };
+            Some(ArchPerfmonExtInfo {
+                eax: res.eax,
+                ebx: res.ebx,
+                ecx: res.ecx,
+                leaf1: subleaf(1),
+                leaf3: subleaf(3),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Intel AVX10 Converged Vector ISA information (LEAF=0x24).
+    ///
+    /// Only returned if the processor enumerates AVX10 support in
+    /// `CPUID.(EAX=07H, ECX=01H):EDX[19]`.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn get_avx10_info(&self) -> Option<Avx10Info> {
+        let has_avx10 = self
+            .get_extended_feature_info()
+            .is_some_and(|info| info.has_avx10());
+        if has_avx10 && self.leaf_is_supported(EAX_AVX10_INFO) {
+            let res = self.read.cpuid2(EAX_AVX10_INFO, 0);
+            Some(Avx10Info {
+                eax: res.eax,
+                ebx: res.ebx,
+            })
+        } else {
+            None
+        }
+    }
+
     /// Returns information provided by the hypervisor, if running
     /// in a virtual environment (LEAF=0x4000_00xx).
     ///
     /// # Platform
     /// Needs to be a virtual CPU to be supported.
-    pub fn get_hypervisor_info(&self) -> Option<HypervisorInfo> {
+    pub fn get_hypervisor_info(&self) -> Option<HypervisorInfo<R>> {
         // We only fetch HypervisorInfo, if the Hypervisor-Flag is set.
         // See https://github.com/gz/rust-cpuid/issues/52
         self.get_feature_info()
             .filter(|fi| fi.has_hypervisor())
             .and_then(|_| {
                 let res = self.read.cpuid1(EAX_HYPERVISOR_INFO);
-                if res.eax > 0 {
+                if hypervisor_block_max_leaf(EAX_HYPERVISOR_INFO, &res).is_some() {
                     Some(HypervisorInfo {
-                        read: self.read,
+                        read: self.read.clone(),
                         res,
+                        base: EAX_HYPERVISOR_INFO,
                     })
                 } else {
                     None
@@ -742,6 +1903,39 @@ impl CpuId {
             })
     }
 
+    /// Returns all hypervisor interfaces, if running in a virtual environment
+    /// (LEAF=0x4000_0000 + N * 0x100).
+    ///
+    /// Hypervisors can implement the interfaces of several products, each in
+    /// its own block of leafs, e.g., KVM with Hyper-V enlightenments reports
+    /// `Microsoft Hv` at 0x4000_0000 and `KVMKVMKVM` at 0x4000_0100. A block
+    /// is present if its EAX (the highest leaf of the block) lies within the
+    /// block, or if it is 0 and the signature is KVM's (as reported by old
+    /// KVM versions). [`CpuId::get_hypervisor_info`] only returns the first
+    /// block.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{CpuId, Hypervisor};
+    ///
+    /// let cpuid = CpuId::new();
+    /// let has_kvm = cpuid
+    ///     .get_hypervisor_interfaces()
+    ///     .is_some_and(|mut iter| iter.any(|hv| hv.identify() == Hypervisor::KVM));
+    /// println!("KVM interface present: {}", has_kvm);
+    /// ```
+    ///
+    /// # Platform
+    /// Needs to be a virtual CPU to be supported.
+    pub fn get_hypervisor_interfaces(&self) -> Option<HypervisorInterfacesIter<R>> {
+        self.get_feature_info()
+            .filter(|fi| fi.has_hypervisor())
+            .map(|_| HypervisorInterfacesIter {
+                read: self.read.clone(),
+                base: EAX_HYPERVISOR_INFO,
+            })
+    }
+
     /// Extended Processor and Processor Feature Identifiers (LEAF=0x8000_0001).
     ///
     /// # Platforms
@@ -750,8 +1944,13 @@ impl CpuId {
         &self,
     ) -> Option<ExtendedProcessorFeatureIdentifiers> {
         if self.leaf_is_supported(EAX_EXTENDED_PROCESSOR_AND_FEATURE_IDENTIFIERS) {
+            let vendor = if self.is_amd_compatible() {
+                Vendor::Amd
+            } else {
+                self.vendor
+            };
             Some(ExtendedProcessorFeatureIdentifiers::new(
-                self.vendor,
+                vendor,
                 self.read
                     .cpuid1(EAX_EXTENDED_PROCESSOR_AND_FEATURE_IDENTIFIERS),
             ))
@@ -769,7 +1968,7 @@ impl CpuId {
             && self.leaf_is_supported(EAX_EXTENDED_BRAND_STRING + 1)
             && self.leaf_is_supported(EAX_EXTENDED_BRAND_STRING + 2)
         {
-            Some(ProcessorBrandString::new([
+            Some(ProcessorBrandString::from_registers([
                 self.read.cpuid1(EAX_EXTENDED_BRAND_STRING),
                 self.read.cpuid1(EAX_EXTENDED_BRAND_STRING + 1),
                 self.read.cpuid1(EAX_EXTENDED_BRAND_STRING + 2),
@@ -784,8 +1983,10 @@ impl CpuId {
     /// # Platforms
     /// ✅ AMD ❌ Intel (reserved)
     pub fn get_l1_cache_and_tlb_info(&self) -> Option<L1CacheTlbInfo> {
-        if self.vendor == Vendor::Amd && self.leaf_is_supported(EAX_L1_CACHE_INFO) {
-            Some(L1CacheTlbInfo::new(self.read.cpuid1(EAX_L1_CACHE_INFO)))
+        if self.is_amd_compatible() && self.leaf_is_supported(EAX_L1_CACHE_INFO) {
+            Some(L1CacheTlbInfo::from_registers(
+                self.read.cpuid1(EAX_L1_CACHE_INFO),
+            ))
         } else {
             None
         }
@@ -797,7 +1998,7 @@ impl CpuId {
     /// ✅ AMD 🟡 Intel
     pub fn get_l2_l3_cache_and_tlb_info(&self) -> Option<L2And3CacheTlbInfo> {
         if self.leaf_is_supported(EAX_L2_L3_CACHE_INFO) {
-            Some(L2And3CacheTlbInfo::new(
+            Some(L2And3CacheTlbInfo::from_registers(
                 self.read.cpuid1(EAX_L2_L3_CACHE_INFO),
             ))
         } else {
@@ -805,13 +2006,29 @@ impl CpuId {
         }
     }
 
+    /// TLB characteristics for 1GB pages (LEAF=0x8000_0019).
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved)
+    pub fn get_tlb_1g_page_info(&self) -> Option<Tlb1gPageInfo> {
+        if self.is_amd_compatible() && self.leaf_is_supported(EAX_TLB_1G_PAGE_INFO) {
+            Some(Tlb1gPageInfo::from_registers(
+                self.read.cpuid1(EAX_TLB_1G_PAGE_INFO),
+            ))
+        } else {
+            None
+        }
+    }
+
     /// Advanced Power Management Information (LEAF=0x8000_0007).
     ///
     /// # Availability
     /// ✅ AMD 🟡 Intel
     pub fn get_advanced_power_mgmt_info(&self) -> Option<ApmInfo> {
         if self.leaf_is_supported(EAX_ADVANCED_POWER_MGMT_INFO) {
-            Some(ApmInfo::new(self.read.cpuid1(EAX_ADVANCED_POWER_MGMT_INFO)))
+            Some(ApmInfo::from_registers(
+                self.read.cpuid1(EAX_ADVANCED_POWER_MGMT_INFO),
+            ))
         } else {
             None
         }
@@ -823,7 +2040,7 @@ impl CpuId {
     /// ✅ AMD 🟡 Intel
     pub fn get_processor_capacity_feature_info(&self) -> Option<ProcessorCapacityAndFeatureInfo> {
         if self.leaf_is_supported(EAX_PROCESSOR_CAPACITY_INFO) {
-            Some(ProcessorCapacityAndFeatureInfo::new(
+            Some(ProcessorCapacityAndFeatureInfo::from_registers(
                 self.read.cpuid1(EAX_PROCESSOR_CAPACITY_INFO),
             ))
         } else {
@@ -831,6 +2048,159 @@ impl CpuId {
         }
     }
 
+    /// Check if the CPU supports five-level paging (LA57), i.e., 57-bit
+    /// linear addresses.
+    ///
+    /// # Note
+    /// Whether the OS actually enabled five-level paging (CR4.LA57) isn't
+    /// visible through `cpuid`.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn supports_5_level_paging(&self) -> bool {
+        self.get_extended_feature_info()
+            .is_some_and(|info| info.has_la57())
+    }
+
+    /// Check if the no-execute page protection (NX/XD bit) is supported.
+    ///
+    /// Shorthand for [`ExtendedProcessorFeatureIdentifiers::has_execute_disable`],
+    /// `false` if leaf 0x8000_0001 isn't available.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_nx(&self) -> bool {
+        self.get_extended_processor_and_feature_identifiers()
+            .is_some_and(|info| info.has_execute_disable())
+    }
+
+    /// Check if 1-GiB pages are supported.
+    ///
+    /// Shorthand for [`ExtendedProcessorFeatureIdentifiers::has_1gib_pages`],
+    /// `false` if leaf 0x8000_0001 isn't available.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_1gib_pages(&self) -> bool {
+        self.get_extended_processor_and_feature_identifiers()
+            .is_some_and(|info| info.has_1gib_pages())
+    }
+
+    /// Check if RDTSCP and IA32_TSC_AUX are supported.
+    ///
+    /// Shorthand for [`ExtendedProcessorFeatureIdentifiers::has_rdtscp`],
+    /// `false` if leaf 0x8000_0001 isn't available.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_rdtscp(&self) -> bool {
+        self.get_extended_processor_and_feature_identifiers()
+            .is_some_and(|info| info.has_rdtscp())
+    }
+
+    /// Check if SYSCALL/SYSRET are supported.
+    ///
+    /// Shorthand for [`ExtendedProcessorFeatureIdentifiers::has_syscall_sysret`],
+    /// `false` if leaf 0x8000_0001 isn't available.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_syscall_sysret(&self) -> bool {
+        self.get_extended_processor_and_feature_identifiers()
+            .is_some_and(|info| info.has_syscall_sysret())
+    }
+
+    /// Check if 64-bit mode (long mode, Intel 64) is supported.
+    ///
+    /// Shorthand for [`ExtendedProcessorFeatureIdentifiers::has_64bit_mode`],
+    /// `false` if leaf 0x8000_0001 isn't available.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_64bit_mode(&self) -> bool {
+        self.get_extended_processor_and_feature_identifiers()
+            .is_some_and(|info| info.has_64bit_mode())
+    }
+
+    /// Maximum number of physical address bits supported by the CPU.
+    ///
+    /// Uses the value reported in leaf 0x8000_0008. If that leaf isn't
+    /// available, this falls back to 36 bits if PAE or PSE-36 is supported
+    /// and to 32 bits otherwise.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn physical_address_bits(&self) -> u8 {
+        match self
+            .get_processor_capacity_feature_info()
+            .map_or(0, |info| info.physical_address_bits())
+        {
+            0 if self
+                .get_feature_info()
+                .is_some_and(|info| info.has_pae() || info.has_pse36()) =>
+            {
+                36
+            }
+            0 => 32,
+            reported => reported,
+        }
+    }
+
+    /// Maximum number of guest physical address bits usable with nested
+    /// paging.
+    ///
+    /// Uses the value reported in leaf 0x8000_0008, and falls back to
+    /// [`CpuId::physical_address_bits`] if it is zero (always the case on
+    /// Intel).
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn guest_physical_address_bits(&self) -> u8 {
+        match self
+            .get_processor_capacity_feature_info()
+            .map_or(0, |info| info.guest_physical_address_bits())
+        {
+            0 => self.physical_address_bits(),
+            reported => reported,
+        }
+    }
+
+    /// Maximum number of linear address bits supported by the CPU.
+    ///
+    /// Same as [`CpuId::linear_address_bits`].
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn max_linear_address_bits(&self) -> u8 {
+        self.linear_address_bits()
+    }
+
+    /// Maximum number of linear address bits supported by the CPU.
+    ///
+    /// Uses the value reported in leaf 0x8000_0008, but never less than 57 if
+    /// [`CpuId::supports_5_level_paging`] (some hypervisors don't update leaf
+    /// 0x8000_0008). If leaf 0x8000_0008 isn't available, this falls back to
+    /// 57, 48 or 32 bits depending on LA57 and 64-bit mode support.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn linear_address_bits(&self) -> u8 {
+        let la57 = self.supports_5_level_paging();
+        let reported = self
+            .get_processor_capacity_feature_info()
+            .map_or(0, |info| info.linear_address_bits());
+
+        if la57 {
+            core::cmp::max(reported, 57)
+        } else if reported != 0 {
+            reported
+        } else if self.has_64bit_mode() {
+            48
+        } else {
+            32
+        }
+    }
+
     /// This function provides information about the SVM features that the processory
     /// supports.
     ///
@@ -842,9 +2212,34 @@ impl CpuId {
     pub fn get_svm_info(&self) -> Option<SvmFeatures> {
         let has_svm = self
             .get_extended_processor_and_feature_identifiers()
-            .map_or(false, |f| f.has_svm());
+            .is_some_and(|f| f.has_svm());
         if has_svm && self.leaf_is_supported(EAX_SVM_FEATURES) {
-            Some(SvmFeatures::new(self.read.cpuid1(EAX_SVM_FEATURES)))
+            Some(SvmFeatures::from_registers(
+                self.read.cpuid1(EAX_SVM_FEATURES),
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Processor topology information (LEAF=0x8000_001E): extended APIC ID,
+    /// core ID and node ID of the logical processor we're running on.
+    ///
+    /// Only returned on AMD-compatible CPUs with topology extensions.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel
+    pub fn get_processor_topology_info(&self) -> Option<ProcessorTopologyInfo> {
+        let has_topology_extensions = self
+            .get_extended_processor_and_feature_identifiers()
+            .is_some_and(|f| f.has_topology_extensions());
+        if self.is_amd_compatible()
+            && has_topology_extensions
+            && self.leaf_is_supported(EAX_PROCESSOR_TOPOLOGY_INFO)
+        {
+            Some(ProcessorTopologyInfo::from_registers(
+                self.read.cpuid1(EAX_PROCESSOR_TOPOLOGY_INFO),
+            ))
         } else {
             None
         }
@@ -856,374 +2251,1135 @@ impl CpuId {
     /// ✅ AMD ❌ Intel (reserved)
     pub fn get_memory_encryption_info(&self) -> Option<MemoryEncryptionInfo> {
         if self.leaf_is_supported(EAX_MEMORY_ENCRYPTION_INFO) {
-            Some(MemoryEncryptionInfo::new(
+            Some(MemoryEncryptionInfo::from_registers(
                 self.read.cpuid1(EAX_MEMORY_ENCRYPTION_INFO),
             ))
         } else {
             None
         }
     }
-}
-
-impl Debug for CpuId {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("CpuId")
-            .field("vendor", &self.vendor)
-            // .field("supported_leafs", &(self.supported_leafs as *const u32))
-            // .field("supported_extended_leafs", &(self.supported_extended_leafs as *const u32))
-            .field("vendor_info", &self.get_vendor_info())
-            .field("feature_info", &self.get_feature_info())
-            .field("cache_info", &self.get_cache_info())
-            .field("processor_serial", &self.get_processor_serial())
-            .field("cache_parameters", &self.get_cache_parameters())
-            .field("monitor_mwait_info", &self.get_monitor_mwait_info())
-            .field("thermal_power_info", &self.get_thermal_power_info())
-            .field("extended_feature_info", &self.get_extended_feature_info())
-            .field(
-                "direct_cache_access_info",
-                &self.get_direct_cache_access_info(),
-            )
-            .field(
-                "performance_monitoring_info",
-                &self.get_performance_monitoring_info(),
-            )
-            .field("extended_topology_info", &self.get_extended_topology_info())
-            .field("extended_state_info", &self.get_extended_state_info())
-            .field("rdt_monitoring_info", &self.get_rdt_monitoring_info())
-            .field("rdt_allocation_info", &self.get_rdt_allocation_info())
-            .field("sgx_info", &self.get_sgx_info())
-            .field("processor_trace_info", &self.get_processor_trace_info())
-            .field("tsc_info", &self.get_tsc_info())
-            .field(
-                "processor_frequency_info",
-                &self.get_processor_frequency_info(),
-            )
-            .field(
-                "deterministic_address_translation_info",
-                &self.get_deterministic_address_translation_info(),
-            )
-            .field("soc_vendor_info", &self.get_soc_vendor_info())
-            .field("hypervisor_info", &self.get_hypervisor_info())
-            .field(
-                "extended_processor_and_feature_identifiers",
-                &self.get_extended_processor_and_feature_identifiers(),
-            )
-            .field("processor_brand_string", &self.get_processor_brand_string())
-            .field("memory_encryption_info", &self.get_memory_encryption_info())
-            .finish()
-    }
-}
 
-/// Vendor Info String (LEAF=0x0)
-///
-/// A string that can be for example "AuthenticAMD" or "GenuineIntel".
-///
-/// # Technical Background
-///
-/// The vendor info is a 12-byte (96 bit) long string stored in `ebx`, `edx` and
-/// `ecx` by the corresponding `cpuid` instruction.
-///
-/// # Platforms
-/// ✅ AMD ✅ Intel
-#[derive(PartialEq, Eq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-#[repr(C)]
-pub struct VendorInfo {
-    ebx: u32,
-    edx: u32,
-    ecx: u32,
-}
+    /// Check if the CPU can run SVM guests with nested paging (NPT), i.e.,
+    /// SVM is supported and leaf 0x8000_000A reports nested paging.
+    ///
+    /// # Note
+    /// The BIOS can still disable SVM in the VM_CR MSR, which isn't visible
+    /// through `cpuid`.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel
+    pub fn can_run_nested_paging_guests(&self) -> bool {
+        self.get_svm_info()
+            .is_some_and(|svm| svm.has_nested_paging())
+    }
 
-impl VendorInfo {
-    /// Return vendor identification as human readable string.
-    pub fn as_str(&self) -> &str {
-        let brand_string_start = self as *const VendorInfo as *const u8;
-        let slice = unsafe {
-            // Safety: VendorInfo is laid out with repr(C) and exactly
-            // 12 byte long without any padding.
-            slice::from_raw_parts(brand_string_start, size_of::<VendorInfo>())
-        };
+    /// Check if SVM guests can use the advanced virtual interrupt controller
+    /// (AVIC).
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel
+    pub fn supports_avic(&self) -> bool {
+        self.get_svm_info().is_some_and(|svm| svm.has_avic())
+    }
 
-        str::from_utf8(slice).unwrap_or("InvalidVendorString")
+    /// Check if SVM guests with Secure Encrypted Virtualization (SEV) can be
+    /// launched, i.e., SVM and SEV are supported and there is at least one
+    /// ASID for SEV guests ([`MemoryEncryptionInfo::sev_asid_range`]).
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel
+    pub fn supports_sev_guests(&self) -> bool {
+        self.get_svm_info().is_some()
+            && self
+                .get_memory_encryption_info()
+                .is_some_and(|mem| mem.sev_asid_range().is_some())
     }
 
-    #[deprecated(
-        since = "10.0.0",
-        note = "Use idiomatic function name `as_str` instead"
-    )]
-    pub fn as_string(&self) -> &str {
-        self.as_str()
+    /// Check if SVM guests with SEV Encrypted State (SEV-ES) can be launched,
+    /// i.e., SVM and SEV-ES are supported and there is at least one ASID for
+    /// SEV-ES guests ([`MemoryEncryptionInfo::sev_es_asid_range`]).
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel
+    pub fn supports_sev_es_guests(&self) -> bool {
+        self.get_svm_info().is_some()
+            && self
+                .get_memory_encryption_info()
+                .is_some_and(|mem| mem.has_sev() && mem.sev_es_asid_range().is_some())
     }
-}
 
-impl Debug for VendorInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("VendorInfo")
-            .field("brand_string", &self.as_str())
-            .finish()
+    /// Check if SVM guests with SEV Secure Nested Paging (SEV-SNP) can be
+    /// launched. SEV-SNP guests use the SEV-ES ASIDs, see
+    /// [`CpuId::supports_sev_es_guests`].
+    ///
+    /// # Note
+    /// SEV-SNP also needs the reverse map table (RMP) to be set up by the
+    /// BIOS, which isn't visible through `cpuid`.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel
+    pub fn supports_sev_snp_guests(&self) -> bool {
+        self.supports_sev_es_guests()
+            && self
+                .get_memory_encryption_info()
+                .is_some_and(|mem| mem.has_sev_snp())
     }
-}
 
-impl fmt::Display for VendorInfo {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.as_str())
+    /// Extended Performance Monitoring and Debug (LEAF=0x8000_0022).
+    ///
+    /// See also [`CpuId::amd_core_performance_counters`].
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved)
+    pub fn get_performance_monitoring_debug_info(&self) -> Option<PerformanceMonitoringDebugInfo> {
+        if self.leaf_is_supported(EAX_PERFORMANCE_MONITORING_DEBUG_INFO) {
+            Some(PerformanceMonitoringDebugInfo::from_registers(
+                self.read.cpuid1(EAX_PERFORMANCE_MONITORING_DEBUG_INFO),
+            ))
+        } else {
+            None
+        }
     }
-}
 
-/// Iterates over cache information (LEAF=0x02).
-///
-/// This will just return an index into a static table of cache descriptions
-/// (see [CACHE_INFO_TABLE](crate::CACHE_INFO_TABLE)).
-///
-/// # Platforms
-/// ❌ AMD ✅ Intel
-#[derive(PartialEq, Eq, Clone)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct CacheInfoIter {
-    current: u32,
-    eax: u32,
-    ebx: u32,
-    ecx: u32,
-    edx: u32,
-}
+    /// Number of core performance counters of AMD CPUs.
+    ///
+    /// This is the number reported in LEAF=0x8000_0022 with Performance
+    /// Monitoring Version 2. Older CPUs have six counters with the
+    /// performance counter extensions
+    /// ([`ExtendedProcessorFeatureIdentifiers::has_perf_cntr_extensions`])
+    /// and four legacy counters otherwise.
+    ///
+    /// Returns `None` if the CPU isn't AMD-compatible.
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (use [`CpuId::get_performance_monitoring_info`])
+    pub fn amd_core_performance_counters(&self) -> Option<u8> {
+        if !self.is_amd_compatible() {
+            return None;
+        }
+        if let Some(info) = self
+            .get_performance_monitoring_debug_info()
+            .filter(|info| info.has_perfmon_v2())
+        {
+            return Some(info.num_core_counters());
+        }
+        let has_extensions = self
+            .get_extended_processor_and_feature_identifiers()
+            .is_some_and(|f| f.has_perf_cntr_extensions());
+        Some(if has_extensions { 6 } else { 4 })
+    }
 
-impl Iterator for CacheInfoIter {
-    type Item = CacheInfo;
+    /// Extended CPU topology of AMD CPUs (LEAF=0x8000_0026).
+    ///
+    /// Like the extended topology leafs 0x0B and 0x1F, but with core, complex,
+    /// die (CCD) and socket levels and additional information about
+    /// heterogeneous and asymmetric topologies (e.g., Zen 4 and Zen 4c cores
+    /// in the same package).
+    ///
+    /// # Platforms
+    /// ✅ AMD ❌ Intel (reserved)
+    pub fn get_extended_cpu_topology_info(&self) -> Option<ExtendedCpuTopologyIter<R>> {
+        if self.leaf_is_supported(EAX_EXTENDED_CPU_TOPOLOGY) {
+            Some(ExtendedCpuTopologyIter::new(self.read.clone()))
+        } else {
+            None
+        }
+    }
 
-    /// Iterate over all cache information.
-    fn next(&mut self) -> Option<CacheInfo> {
-        // Every byte of the 4 register values returned by cpuid
-        // can contain information about a cache (except the
-        // very first one).
-        if self.current >= 4 * 4 {
-            return None;
+    /// Processor information of Transmeta CPUs (LEAF=0x8086_0001).
+    ///
+    /// # Platforms
+    /// ❌ AMD ❌ Intel ✅ Transmeta
+    pub fn get_transmeta_processor_info(&self) -> Option<TransmetaProcessorInfo> {
+        if self.leaf_is_supported(EAX_TRANSMETA_PROCESSOR_INFO) {
+            Some(TransmetaProcessorInfo::from_registers(
+                self.read.cpuid1(EAX_TRANSMETA_PROCESSOR_INFO),
+            ))
+        } else {
+            None
         }
-        let reg_index = self.current % 4;
-        let byte_index = self.current / 4;
+    }
 
-        let reg = match reg_index {
-            0 => self.eax,
-            1 => self.ebx,
-            2 => self.ecx,
-            3 => self.edx,
-            _ => unreachable!(),
-        };
+    /// Code Morphing Software version of Transmeta CPUs (LEAF=0x8086_0002).
+    ///
+    /// # Platforms
+    /// ❌ AMD ❌ Intel ✅ Transmeta
+    pub fn get_transmeta_cms_version(&self) -> Option<TransmetaCmsVersion> {
+        if self.leaf_is_supported(EAX_TRANSMETA_CMS_VERSION) {
+            Some(TransmetaCmsVersion::from_registers(
+                self.read.cpuid1(EAX_TRANSMETA_CMS_VERSION),
+            ))
+        } else {
+            None
+        }
+    }
 
-        let byte = match byte_index {
-            0 => reg,
-            1 => reg >> 8,
-            2 => reg >> 16,
-            3 => reg >> 24,
-            _ => unreachable!(),
-        } as u8;
+    /// Code Morphing Software information string of Transmeta CPUs
+    /// (LEAF=0x8086_000{3..6}).
+    ///
+    /// # Platforms
+    /// ❌ AMD ❌ Intel ✅ Transmeta
+    pub fn get_transmeta_info_string(&self) -> Option<TransmetaInfoString> {
+        if self.leaf_is_supported(EAX_TRANSMETA_INFO_STRING + 3) {
+            Some(TransmetaInfoString::from_registers([
+                self.read.cpuid1(EAX_TRANSMETA_INFO_STRING),
+                self.read.cpuid1(EAX_TRANSMETA_INFO_STRING + 1),
+                self.read.cpuid1(EAX_TRANSMETA_INFO_STRING + 2),
+                self.read.cpuid1(EAX_TRANSMETA_INFO_STRING + 3),
+            ]))
+        } else {
+            None
+        }
+    }
 
-        if byte == 0 {
-            self.current += 1;
-            return self.next();
+    /// PadLock security engines of Centaur (VIA) and Zhaoxin CPUs
+    /// (LEAF=0xC000_0001).
+    ///
+    /// # Platforms
+    /// ❌ AMD ❌ Intel ✅ Centaur ✅ Zhaoxin
+    pub fn get_padlock_info(&self) -> Option<PadLockInfo> {
+        if self.leaf_is_supported(EAX_CENTAUR_FEATURE_INFO) {
+            Some(PadLockInfo {
+                edx: self.read.cpuid1(EAX_CENTAUR_FEATURE_INFO).edx,
+            })
+        } else {
+            None
         }
+    }
 
-        for cache_info in CACHE_INFO_TABLE.iter() {
-            if cache_info.num == byte {
-                self.current += 1;
-                return Some(*cache_info);
+    /// x2APIC ID (the initial 32-bit APIC ID) of the logical processor we are
+    /// currently running on.
+    ///
+    /// This reads EDX of the V2 extended topology leaf (LEAF=0x1F) or the
+    /// extended topology leaf (LEAF=0x0B), whichever is available first. AMD
+    /// CPUs without these leafs report the extended APIC ID in
+    /// `Fn8000_001E_EAX` if they have topology extensions. Otherwise the 8-bit
+    /// initial APIC ID of LEAF=0x01 is returned.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn x2apic_id(&self) -> Option<u32> {
+        if let Some(leaf) = self.extended_topology_leaf() {
+            return Some(self.read.cpuid2(leaf, 0).edx);
+        }
+
+        if let Some(info) = self.get_processor_topology_info() {
+            return Some(info.extended_apic_id());
+        }
+
+        self.get_feature_info()
+            .map(|finfo| finfo.initial_local_apic_id() as u32)
+    }
+
+    /// Number of logical processors (hardware threads) in the physical package.
+    ///
+    /// This is determined with the extended topology leaf (LEAF=0x0B) if
+    /// available. Otherwise it falls back to `Fn8000_0008_ECX[NC]` on AMD and
+    /// to `CPUID.01H:EBX[23:16]` on Intel.
+    ///
+    /// # Note
+    /// The legacy Intel fallback reports the maximum number of addressable
+    /// IDs, which can be larger than the number of logical processors that
+    /// are actually present.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn num_logical_cpus(&self) -> Option<usize> {
+        // The last (highest) level of the topology spans the whole package.
+        let from_topology = self
+            .get_extended_topology_info()
+            .and_then(|mut levels| levels.rfind(|level| level.processors() > 0))
+            .map(|level| level.processors() as usize);
+        if from_topology.is_some() {
+            return from_topology;
+        }
+
+        if self.is_amd_compatible() {
+            if let Some(info) = self.get_processor_capacity_feature_info() {
+                return Some(info.num_phys_threads());
             }
         }
 
-        None
+        self.get_feature_info().map(|finfo| {
+            if finfo.has_htt() {
+                core::cmp::max(finfo.max_logical_processor_ids() as usize, 1)
+            } else {
+                1
+            }
+        })
     }
-}
 
-impl Debug for CacheInfoIter {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        let mut debug = f.debug_list();
-        self.clone().for_each(|ref item| {
-            debug.entry(item);
+    /// Number of cores in the physical package.
+    ///
+    /// Computed as [`CpuId::num_logical_cpus`] divided by
+    /// [`CpuId::threads_per_core`].
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn num_cores(&self) -> Option<usize> {
+        let logical = self.num_logical_cpus()?;
+        let threads = self.threads_per_core()?;
+        Some(core::cmp::max(logical / threads, 1))
+    }
+
+    /// Number of logical processors per core (the SMT width).
+    ///
+    /// This is determined with the SMT level of the extended topology leaf
+    /// (LEAF=0x0B) if available. Otherwise it falls back to
+    /// `Fn8000_001E_EBX[ThreadsPerCore]` on AMD and to the ratio of
+    /// `CPUID.01H:EBX[23:16]` and `CPUID.04H:EAX[31:26]` on Intel.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn threads_per_core(&self) -> Option<usize> {
+        let from_topology = self
+            .get_extended_topology_info()
+            .and_then(|mut levels| levels.find(|level| level.level_type() == TopologyType::SMT))
+            .filter(|level| level.processors() > 0)
+            .map(|level| level.processors() as usize);
+        if from_topology.is_some() {
+            return from_topology;
+        }
+
+        if self.is_amd_compatible() {
+            if let Some(info) = self.get_processor_topology_info() {
+                return Some(info.threads_per_core() as usize);
+            }
+            // AMD processors without topology extensions do not implement SMT.
+            return Some(1);
+        }
+
+        let logical = self.num_logical_cpus()?;
+        let cores = self
+            .get_cache_parameters()
+            .and_then(|mut caches| caches.next())
+            .map_or(1, |cache| cache.max_cores_for_package());
+        Some(core::cmp::max(logical / cores, 1))
+    }
+
+    /// Size of a cache line of the L1 data cache in bytes, e.g., to pad data
+    /// structures that are shared between threads.
+    ///
+    /// Uses the deterministic cache parameters (LEAF=0x04 or 0x8000_001D) if
+    /// available. Otherwise it falls back to the CLFLUSH line size of LEAF=0x01
+    /// and then to the AMD L1 cache leaf (LEAF=0x8000_0005).
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// let line = CpuId::new().cache_line_size().unwrap_or(64);
+    /// println!("Padding to {} bytes", line);
+    /// ```
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn cache_line_size(&self) -> Option<usize> {
+        let from_parameters = self.get_cache_parameters().and_then(|mut caches| {
+            caches.find_map(|cache| {
+                (cache.level() == 1
+                    && matches!(cache.cache_type(), CacheType::Data | CacheType::Unified))
+                .then(|| cache.coherency_line_size())
+            })
         });
-        debug.finish()
+        let from_clflush = || {
+            self.get_feature_info()
+                .filter(|finfo| finfo.has_clflush())
+                .map(|finfo| finfo.cache_line_flush_size_bytes() as usize)
+        };
+        let from_amd_l1 = || {
+            self.get_l1_cache_and_tlb_info()
+                .map(|l1| l1.dcache_line_size() as usize)
+        };
+
+        from_parameters
+            .filter(|&size| size > 0)
+            .or_else(|| from_clflush().filter(|&size| size > 0))
+            .or_else(|| from_amd_l1().filter(|&size| size > 0))
     }
-}
 
-/// What type of cache are we dealing with?
-#[derive(Copy, Clone, Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub enum CacheInfoType {
-    General,
-    Cache,
-    TLB,
-    STLB,
-    DTLB,
-    Prefetch,
+    /// Sizes (in bytes) of the L1d, L1i, L2 and L3 cache.
+    ///
+    /// Uses the deterministic cache parameters (LEAF=0x04) if available and
+    /// falls back to the AMD cache leafs (LEAF=0x8000_0005 and 0x8000_0006).
+    #[cfg(any(test, feature = "alloc"))]
+    pub(crate) fn cache_sizes(&self) -> [Option<u64>; 4] {
+        let from_parameters = |level: u8, types: &[CacheType]| {
+            self.get_cache_parameters()?.find_map(|cache| {
+                (cache.level() == level && types.contains(&cache.cache_type()))
+                    .then(|| cache.size() as u64)
+            })
+        };
+        let l1 = self.get_l1_cache_and_tlb_info();
+        let l2_l3 = self.get_l2_l3_cache_and_tlb_info();
+
+        let l1d = from_parameters(1, &[CacheType::Data, CacheType::Unified])
+            .or_else(|| l1.as_ref().map(|l1| l1.dcache_size() as u64 * 1024));
+        let l1i = from_parameters(1, &[CacheType::Instruction])
+            .or_else(|| l1.as_ref().map(|l1| l1.icache_size() as u64 * 1024));
+        let l2 = from_parameters(2, &[CacheType::Unified]).or_else(|| {
+            l2_l3
+                .as_ref()
+                .map(|c| c.l2cache_size() as u64 * 1024)
+                .filter(|&size| size > 0)
+        });
+        let l3 = from_parameters(3, &[CacheType::Unified]).or_else(|| {
+            l2_l3
+                .as_ref()
+                .map(|c| c.l3cache_size() as u64 * 512 * 1024)
+                .filter(|&size| size > 0)
+        });
+
+        [l1d, l1i, l2, l3]
+    }
 }
 
-/// Describes any kind of cache (TLB, Data and Instruction caches plus prefetchers).
-#[derive(Copy, Clone)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct CacheInfo {
-    /// Number as retrieved from cpuid
-    pub num: u8,
-    /// Cache type
-    pub typ: CacheInfoType,
+/// Generates a `try_get_*` variant of a `get_*` accessor that reports why the
+/// information isn't available.
+///
+/// `$leaf` is the leaf the accessor reads and `$feature` the feature flags
+/// that gate it. With `nonzero`, a leaf that reads as all zeros is reported
+/// as [`CpuIdError::InvalidData`] since it doesn't enumerate anything.
+macro_rules! try_get_fn {
+    ($try:ident, $get:ident, $t:ty, $leaf:expr, nonzero) => {
+        #[doc = concat!(
+            "Like [`CpuId::", stringify!($get), "`], but returns the reason ",
+            "([`CpuIdError`]) if the information isn't available.\n\n",
+            "A leaf that reads as all zeros is reported as ",
+            "[`CpuIdError::InvalidData`]."
+        )]
+        pub fn $try(&self) -> Result<$t, CpuIdError> {
+            let info = self.$get().ok_or_else(|| self.unavailable_reason($leaf, &[]))?;
+            if self.read.cpuid1($leaf).all_zero() {
+                return Err(CpuIdError::InvalidData { leaf: $leaf });
+            }
+            Ok(info)
+        }
+    };
+    ($try:ident, $get:ident, $t:ty, $leaf:expr $(, $feature:ident)*) => {
+        #[doc = concat!(
+            "Like [`CpuId::", stringify!($get), "`], but returns the reason ",
+            "([`CpuIdError`]) if the information isn't available."
+        )]
+        pub fn $try(&self) -> Result<$t, CpuIdError> {
+            self.$get()
+                .ok_or_else(|| self.unavailable_reason($leaf, &[$(Feature::$feature),*]))
+        }
+    };
 }
 
-impl CacheInfo {
-    /// Description of the cache (from Intel Manual)
-    pub fn desc(&self) -> &'static str {
-        match self.num {
-            0x00 => "Null descriptor, this byte contains no information",
-            0x01 => "Instruction TLB: 4 KByte pages, 4-way set associative, 32 entries",
-            0x02 => "Instruction TLB: 4 MByte pages, fully associative, 2 entries",
-            0x03 => "Data TLB: 4 KByte pages, 4-way set associative, 64 entries",
-            0x04 => "Data TLB: 4 MByte pages, 4-way set associative, 8 entries",
-            0x05 => "Data TLB1: 4 MByte pages, 4-way set associative, 32 entries",
-            0x06 => "1st-level instruction cache: 8 KBytes, 4-way set associative, 32 byte line size",
-            0x08 => "1st-level instruction cache: 16 KBytes, 4-way set associative, 32 byte line size",
-            0x09 => "1st-level instruction cache: 32KBytes, 4-way set associative, 64 byte line size",
-            0x0A => "1st-level data cache: 8 KBytes, 2-way set associative, 32 byte line size",
-            0x0B => "Instruction TLB: 4 MByte pages, 4-way set associative, 4 entries",
-            0x0C => "1st-level data cache: 16 KBytes, 4-way set associative, 32 byte line size",
-            0x0D => "1st-level data cache: 16 KBytes, 4-way set associative, 64 byte line size",
-            0x0E => "1st-level data cache: 24 KBytes, 6-way set associative, 64 byte line size",
-            0x1D => "2nd-level cache: 128 KBytes, 2-way set associative, 64 byte line size",
-            0x21 => "2nd-level cache: 256 KBytes, 8-way set associative, 64 byte line size",
-            0x22 => "3rd-level cache: 512 KBytes, 4-way set associative, 64 byte line size, 2 lines per sector",
-            0x23 => "3rd-level cache: 1 MBytes, 8-way set associative, 64 byte line size, 2 lines per sector",
-            0x24 => "2nd-level cache: 1 MBytes, 16-way set associative, 64 byte line size",
-            0x25 => "3rd-level cache: 2 MBytes, 8-way set associative, 64 byte line size, 2 lines per sector",
-            0x29 => "3rd-level cache: 4 MBytes, 8-way set associative, 64 byte line size, 2 lines per sector",
-            0x2C => "1st-level data cache: 32 KBytes, 8-way set associative, 64 byte line size",
-            0x30 => "1st-level instruction cache: 32 KBytes, 8-way set associative, 64 byte line size",
-            0x40 => "No 2nd-level cache or, if processor contains a valid 2nd-level cache, no 3rd-level cache",
-            0x41 => "2nd-level cache: 128 KBytes, 4-way set associative, 32 byte line size",
-            0x42 => "2nd-level cache: 256 KBytes, 4-way set associative, 32 byte line size",
-            0x43 => "2nd-level cache: 512 KBytes, 4-way set associative, 32 byte line size",
-            0x44 => "2nd-level cache: 1 MByte, 4-way set associative, 32 byte line size",
-            0x45 => "2nd-level cache: 2 MByte, 4-way set associative, 32 byte line size",
-            0x46 => "3rd-level cache: 4 MByte, 4-way set associative, 64 byte line size",
-            0x47 => "3rd-level cache: 8 MByte, 8-way set associative, 64 byte line size",
-            0x48 => "2nd-level cache: 3MByte, 12-way set associative, 64 byte line size",
-            0x49 => "3rd-level cache: 4MB, 16-way set associative, 64-byte line size (Intel Xeon processor MP, Family 0FH, Model 06H); 2nd-level cache: 4 MByte, 16-way set ssociative, 64 byte line size",
-            0x4A => "3rd-level cache: 6MByte, 12-way set associative, 64 byte line size",
-            0x4B => "3rd-level cache: 8MByte, 16-way set associative, 64 byte line size",
-            0x4C => "3rd-level cache: 12MByte, 12-way set associative, 64 byte line size",
-            0x4D => "3rd-level cache: 16MByte, 16-way set associative, 64 byte line size",
-            0x4E => "2nd-level cache: 6MByte, 24-way set associative, 64 byte line size",
-            0x4F => "Instruction TLB: 4 KByte pages, 32 entries",
-            0x50 => "Instruction TLB: 4 KByte and 2-MByte or 4-MByte pages, 64 entries",
-            0x51 => "Instruction TLB: 4 KByte and 2-MByte or 4-MByte pages, 128 entries",
-            0x52 => "Instruction TLB: 4 KByte and 2-MByte or 4-MByte pages, 256 entries",
-            0x55 => "Instruction TLB: 2-MByte or 4-MByte pages, fully associative, 7 entries",
-            0x56 => "Data TLB0: 4 MByte pages, 4-way set associative, 16 entries",
-            0x57 => "Data TLB0: 4 KByte pages, 4-way associative, 16 entries",
-            0x59 => "Data TLB0: 4 KByte pages, fully associative, 16 entries",
-            0x5A => "Data TLB0: 2-MByte or 4 MByte pages, 4-way set associative, 32 entries",
-            0x5B => "Data TLB: 4 KByte and 4 MByte pages, 64 entries",
-            0x5C => "Data TLB: 4 KByte and 4 MByte pages,128 entries",
-            0x5D => "Data TLB: 4 KByte and 4 MByte pages,256 entries",
-            0x60 => "1st-level data cache: 16 KByte, 8-way set associative, 64 byte line size",
-            0x61 => "Instruction TLB: 4 KByte pages, fully associative, 48 entries",
-            0x63 => "Data TLB: 2 MByte or 4 MByte pages, 4-way set associative, 32 entries and a separate array with 1 GByte pages, 4-way set associative, 4 entries",
-            0x64 => "Data TLB: 4 KByte pages, 4-way set associative, 512 entries",
-            0x66 => "1st-level data cache: 8 KByte, 4-way set associative, 64 byte line size",
-            0x67 => "1st-level data cache: 16 KByte, 4-way set associative, 64 byte line size",
-            0x68 => "1st-level data cache: 32 KByte, 4-way set associative, 64 byte line size",
-            0x6A => "uTLB: 4 KByte pages, 8-way set associative, 64 entries",
-            0x6B => "DTLB: 4 KByte pages, 8-way set associative, 256 entries",
-            0x6C => "DTLB: 2M/4M pages, 8-way set associative, 128 entries",
-            0x6D => "DTLB: 1 GByte pages, fully associative, 16 entries",
-            0x70 => "Trace cache: 12 K-μop, 8-way set associative",
-            0x71 => "Trace cache: 16 K-μop, 8-way set associative",
-            0x72 => "Trace cache: 32 K-μop, 8-way set associative",
-            0x76 => "Instruction TLB: 2M/4M pages, fully associative, 8 entries",
-            0x78 => "2nd-level cache: 1 MByte, 4-way set associative, 64byte line size",
-            0x79 => "2nd-level cache: 128 KByte, 8-way set associative, 64 byte line size, 2 lines per sector",
-            0x7A => "2nd-level cache: 256 KByte, 8-way set associative, 64 byte line size, 2 lines per sector",
-            0x7B => "2nd-level cache: 512 KByte, 8-way set associative, 64 byte line size, 2 lines per sector",
-            0x7C => "2nd-level cache: 1 MByte, 8-way set associative, 64 byte line size, 2 lines per sector",
-            0x7D => "2nd-level cache: 2 MByte, 8-way set associative, 64byte line size",
-            0x7F => "2nd-level cache: 512 KByte, 2-way set associative, 64-byte line size",
-            0x80 => "2nd-level cache: 512 KByte, 8-way set associative, 64-byte line size",
-            0x82 => "2nd-level cache: 256 KByte, 8-way set associative, 32 byte line size",
-            0x83 => "2nd-level cache: 512 KByte, 8-way set associative, 32 byte line size",
-            0x84 => "2nd-level cache: 1 MByte, 8-way set associative, 32 byte line size",
-            0x85 => "2nd-level cache: 2 MByte, 8-way set associative, 32 byte line size",
-            0x86 => "2nd-level cache: 512 KByte, 4-way set associative, 64 byte line size",
-            0x87 => "2nd-level cache: 1 MByte, 8-way set associative, 64 byte line size",
-            0xA0 => "DTLB: 4k pages, fully associative, 32 entries",
-            0xB0 => "Instruction TLB: 4 KByte pages, 4-way set associative, 128 entries",
-            0xB1 => "Instruction TLB: 2M pages, 4-way, 8 entries or 4M pages, 4-way, 4 entries",
-            0xB2 => "Instruction TLB: 4KByte pages, 4-way set associative, 64 entries",
-            0xB3 => "Data TLB: 4 KByte pages, 4-way set associative, 128 entries",
-            0xB4 => "Data TLB1: 4 KByte pages, 4-way associative, 256 entries",
-            0xB5 => "Instruction TLB: 4KByte pages, 8-way set associative, 64 entries",
-            0xB6 => "Instruction TLB: 4KByte pages, 8-way set associative, 128 entries",
-            0xBA => "Data TLB1: 4 KByte pages, 4-way associative, 64 entries",
-            0xC0 => "Data TLB: 4 KByte and 4 MByte pages, 4-way associative, 8 entries",
-            0xC1 => "Shared 2nd-Level TLB: 4 KByte/2MByte pages, 8-way associative, 1024 entries",
-            0xC2 => "DTLB: 2 MByte/$MByte pages, 4-way associative, 16 entries",
-            0xC3 => "Shared 2nd-Level TLB: 4 KByte /2 MByte pages, 6-way associative, 1536 entries. Also 1GBbyte pages, 4-way, 16 entries.",
-            0xC4 => "DTLB: 2M/4M Byte pages, 4-way associative, 32 entries",
-            0xCA => "Shared 2nd-Level TLB: 4 KByte pages, 4-way associative, 512 entries",
-            0xD0 => "3rd-level cache: 512 KByte, 4-way set associative, 64 byte line size",
-            0xD1 => "3rd-level cache: 1 MByte, 4-way set associative, 64 byte line size",
-            0xD2 => "3rd-level cache: 2 MByte, 4-way set associative, 64 byte line size",
-            0xD6 => "3rd-level cache: 1 MByte, 8-way set associative, 64 byte line size",
-            0xD7 => "3rd-level cache: 2 MByte, 8-way set associative, 64 byte line size",
-            0xD8 => "3rd-level cache: 4 MByte, 8-way set associative, 64 byte line size",
-            0xDC => "3rd-level cache: 1.5 MByte, 12-way set associative, 64 byte line size",
-            0xDD => "3rd-level cache: 3 MByte, 12-way set associative, 64 byte line size",
-            0xDE => "3rd-level cache: 6 MByte, 12-way set associative, 64 byte line size",
-            0xE2 => "3rd-level cache: 2 MByte, 16-way set associative, 64 byte line size",
-            0xE3 => "3rd-level cache: 4 MByte, 16-way set associative, 64 byte line size",
-            0xE4 => "3rd-level cache: 8 MByte, 16-way set associative, 64 byte line size",
-            0xEA => "3rd-level cache: 12MByte, 24-way set associative, 64 byte line size",
-            0xEB => "3rd-level cache: 18MByte, 24-way set associative, 64 byte line size",
-            0xEC => "3rd-level cache: 24MByte, 24-way set associative, 64 byte line size",
-            0xF0 => "64-Byte prefetching",
-            0xF1 => "128-Byte prefetching",
-            0xFE => "CPUID leaf 2 does not report TLB descriptor information; use CPUID leaf 18H to query TLB and other address translation parameters.",
-            0xFF => "CPUID leaf 2 does not report cache descriptor information, use CPUID leaf 4 to query cache parameters",
-            _ => "Unknown cache type!"
+impl<R: CpuIdReader> CpuId<R> {
+    /// Like [`CpuId::get_cache_parameters`], but returns the reason
+    /// ([`CpuIdError`]) if the information isn't available.
+    pub fn try_get_cache_parameters(&self) -> Result<CacheParametersIter<R>, CpuIdError> {
+        self.get_cache_parameters().ok_or_else(|| {
+            if self.is_amd_compatible() {
+                self.unavailable_reason(EAX_CACHE_PARAMETERS_AMD, &[Feature::TopoExt])
+            } else {
+                self.unavailable_reason(EAX_CACHE_PARAMETERS, &[])
+            }
+        })
+    }
+
+    /// Why an accessor for `leaf`, which requires `features`, returned `None`.
+    fn unavailable_reason(&self, leaf: u32, features: &[Feature]) -> CpuIdError {
+        if let Some(&feature) = features.iter().find(|&&f| !self.has_feature(f)) {
+            CpuIdError::FeatureMissing { leaf, feature }
+        } else if !self.leaf_is_in_range(leaf) {
+            CpuIdError::LeafUnsupported { leaf }
+        } else if self.leaf_is_reserved_for_vendor(leaf)
+            || (leaf == EAX_L1_CACHE_INFO && !self.is_amd_compatible())
+        {
+            CpuIdError::VendorMismatch { leaf }
+        } else {
+            CpuIdError::InvalidData { leaf }
         }
     }
+
+    try_get_fn!(
+        try_get_vendor_info,
+        get_vendor_info,
+        VendorInfo,
+        EAX_VENDOR_INFO,
+        nonzero
+    );
+    try_get_fn!(
+        try_get_feature_info,
+        get_feature_info,
+        FeatureInfo,
+        EAX_FEATURE_INFO
+    );
+    try_get_fn!(
+        try_get_cache_info,
+        get_cache_info,
+        CacheInfoIter,
+        EAX_CACHE_INFO
+    );
+    try_get_fn!(
+        try_get_processor_serial,
+        get_processor_serial,
+        ProcessorSerial,
+        EAX_PROCESSOR_SERIAL
+    );
+    try_get_fn!(
+        try_get_monitor_mwait_info,
+        get_monitor_mwait_info,
+        MonitorMwaitInfo,
+        EAX_MONITOR_MWAIT_INFO
+    );
+    try_get_fn!(
+        try_get_thermal_power_info,
+        get_thermal_power_info,
+        ThermalPowerInfo,
+        EAX_THERMAL_POWER_INFO
+    );
+    try_get_fn!(
+        try_get_extended_feature_info,
+        get_extended_feature_info,
+        ExtendedFeatures,
+        EAX_STRUCTURED_EXTENDED_FEATURE_INFO
+    );
+    try_get_fn!(
+        try_get_direct_cache_access_info,
+        get_direct_cache_access_info,
+        DirectCacheAccessInfo,
+        EAX_DIRECT_CACHE_ACCESS_INFO,
+        Dca
+    );
+    try_get_fn!(
+        try_get_performance_monitoring_info,
+        get_performance_monitoring_info,
+        PerformanceMonitoringInfo,
+        EAX_PERFORMANCE_MONITOR_INFO,
+        nonzero
+    );
+    try_get_fn!(
+        try_get_extended_topology_info,
+        get_extended_topology_info,
+        ExtendedTopologyIter<R>,
+        EAX_EXTENDED_TOPOLOGY_INFO
+    );
+    try_get_fn!(
+        try_get_extended_state_info,
+        get_extended_state_info,
+        ExtendedStateInfo<R>,
+        EAX_EXTENDED_STATE_INFO
+    );
+    try_get_fn!(
+        try_get_rdt_monitoring_info,
+        get_rdt_monitoring_info,
+        RdtMonitoringInfo<R>,
+        EAX_RDT_MONITORING
+    );
+    try_get_fn!(
+        try_get_rdt_allocation_info,
+        get_rdt_allocation_info,
+        RdtAllocationInfo<R>,
+        EAX_RDT_ALLOCATION
+    );
+    try_get_fn!(try_get_sgx_info, get_sgx_info, SgxInfo<R>, EAX_SGX, Sgx);
+    try_get_fn!(
+        try_get_processor_trace_info,
+        get_processor_trace_info,
+        ProcessorTraceInfo,
+        EAX_TRACE_INFO
+    );
+    try_get_fn!(
+        try_get_tsc_info,
+        get_tsc_info,
+        TscInfo,
+        EAX_TIME_STAMP_COUNTER_INFO,
+        nonzero
+    );
+    try_get_fn!(
+        try_get_processor_frequency_info,
+        get_processor_frequency_info,
+        ProcessorFrequencyInfo,
+        EAX_FREQUENCY_INFO,
+        nonzero
+    );
+    try_get_fn!(
+        try_get_soc_vendor_info,
+        get_soc_vendor_info,
+        SoCVendorInfo<R>,
+        EAX_SOC_VENDOR_INFO
+    );
+    try_get_fn!(
+        try_get_deterministic_address_translation_info,
+        get_deterministic_address_translation_info,
+        DatIter<R>,
+        EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO
+    );
+    try_get_fn!(
+        try_get_hybrid_info,
+        get_hybrid_info,
+        HybridInfo,
+        EAX_HYBRID_INFO,
+        Hybrid
+    );
+    try_get_fn!(
+        try_get_pconfig_info,
+        get_pconfig_info,
+        PconfigIter<R>,
+        EAX_PCONFIG_INFO,
+        Pconfig
+    );
+    try_get_fn!(
+        try_get_lbr_info,
+        get_lbr_info,
+        LbrInfo,
+        EAX_LBR_INFO,
+        ArchLbr
+    );
+    try_get_fn!(
+        try_get_tmul_info,
+        get_tmul_info,
+        TmulInfo,
+        EAX_TMUL_INFO,
+        AmxTile
+    );
+    try_get_fn!(
+        try_get_hreset_info,
+        get_hreset_info,
+        HresetInfo,
+        EAX_HRESET_INFO,
+        Hreset
+    );
+    try_get_fn!(
+        try_get_arch_perfmon_ext_info,
+        get_arch_perfmon_ext_info,
+        ArchPerfmonExtInfo,
+        EAX_ARCH_PERFMON_EXT_INFO,
+        ArchPerfmonExt
+    );
+    try_get_fn!(
+        try_get_avx10_info,
+        get_avx10_info,
+        Avx10Info,
+        EAX_AVX10_INFO,
+        Avx10
+    );
+    try_get_fn!(
+        try_get_hypervisor_info,
+        get_hypervisor_info,
+        HypervisorInfo<R>,
+        EAX_HYPERVISOR_INFO,
+        Hypervisor
+    );
+    try_get_fn!(
+        try_get_extended_processor_and_feature_identifiers,
+        get_extended_processor_and_feature_identifiers,
+        ExtendedProcessorFeatureIdentifiers,
+        EAX_EXTENDED_PROCESSOR_AND_FEATURE_IDENTIFIERS
+    );
+    try_get_fn!(
+        try_get_processor_brand_string,
+        get_processor_brand_string,
+        ProcessorBrandString,
+        EAX_EXTENDED_BRAND_STRING,
+        nonzero
+    );
+    try_get_fn!(
+        try_get_l1_cache_and_tlb_info,
+        get_l1_cache_and_tlb_info,
+        L1CacheTlbInfo,
+        EAX_L1_CACHE_INFO
+    );
+    try_get_fn!(
+        try_get_l2_l3_cache_and_tlb_info,
+        get_l2_l3_cache_and_tlb_info,
+        L2And3CacheTlbInfo,
+        EAX_L2_L3_CACHE_INFO
+    );
+    try_get_fn!(
+        try_get_tlb_1g_page_info,
+        get_tlb_1g_page_info,
+        Tlb1gPageInfo,
+        EAX_TLB_1G_PAGE_INFO
+    );
+    try_get_fn!(
+        try_get_advanced_power_mgmt_info,
+        get_advanced_power_mgmt_info,
+        ApmInfo,
+        EAX_ADVANCED_POWER_MGMT_INFO
+    );
+    try_get_fn!(
+        try_get_processor_capacity_feature_info,
+        get_processor_capacity_feature_info,
+        ProcessorCapacityAndFeatureInfo,
+        EAX_PROCESSOR_CAPACITY_INFO,
+        nonzero
+    );
+    try_get_fn!(
+        try_get_svm_info,
+        get_svm_info,
+        SvmFeatures,
+        EAX_SVM_FEATURES,
+        Svm
+    );
+    try_get_fn!(
+        try_get_memory_encryption_info,
+        get_memory_encryption_info,
+        MemoryEncryptionInfo,
+        EAX_MEMORY_ENCRYPTION_INFO
+    );
+    try_get_fn!(
+        try_get_processor_topology_info,
+        get_processor_topology_info,
+        ProcessorTopologyInfo,
+        EAX_PROCESSOR_TOPOLOGY_INFO,
+        TopoExt
+    );
+    try_get_fn!(
+        try_get_performance_monitoring_debug_info,
+        get_performance_monitoring_debug_info,
+        PerformanceMonitoringDebugInfo,
+        EAX_PERFORMANCE_MONITORING_DEBUG_INFO
+    );
+    try_get_fn!(
+        try_get_extended_cpu_topology_info,
+        get_extended_cpu_topology_info,
+        ExtendedCpuTopologyIter<R>,
+        EAX_EXTENDED_CPU_TOPOLOGY
+    );
+    try_get_fn!(
+        try_get_transmeta_processor_info,
+        get_transmeta_processor_info,
+        TransmetaProcessorInfo,
+        EAX_TRANSMETA_PROCESSOR_INFO
+    );
+    try_get_fn!(
+        try_get_transmeta_cms_version,
+        get_transmeta_cms_version,
+        TransmetaCmsVersion,
+        EAX_TRANSMETA_CMS_VERSION
+    );
+    try_get_fn!(
+        try_get_transmeta_info_string,
+        get_transmeta_info_string,
+        TransmetaInfoString,
+        EAX_TRANSMETA_INFO_STRING + 3
+    );
+    try_get_fn!(
+        try_get_padlock_info,
+        get_padlock_info,
+        PadLockInfo,
+        EAX_CENTAUR_FEATURE_INFO
+    );
 }
 
-impl Debug for CacheInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.debug_struct("CacheInfo")
-            .field("typ", &self.typ)
-            .field("desc", &self.desc())
+impl<R: CpuIdReader> Debug for CpuId<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CpuId")
+            .field("vendor", &self.vendor)
+            // .field("supported_leafs", &(self.supported_leafs as *const u32))
+            // .field("supported_extended_leafs", &(self.supported_extended_leafs as *const u32))
+            .field("vendor_info", &self.get_vendor_info())
+            .field("feature_info", &self.get_feature_info())
+            .field("cache_info", &self.get_cache_info())
+            .field("processor_serial", &self.get_processor_serial())
+            .field("cache_parameters", &self.get_cache_parameters())
+            .field("monitor_mwait_info", &self.get_monitor_mwait_info())
+            .field("thermal_power_info", &self.get_thermal_power_info())
+            .field("extended_feature_info", &self.get_extended_feature_info())
+            .field(
+                "direct_cache_access_info",
+                &self.get_direct_cache_access_info(),
+            )
+            .field(
+                "performance_monitoring_info",
+                &self.get_performance_monitoring_info(),
+            )
+            .field("extended_topology_info", &self.get_extended_topology_info())
+            .field("extended_state_info", &self.get_extended_state_info())
+            .field("rdt_monitoring_info", &self.get_rdt_monitoring_info())
+            .field("rdt_allocation_info", &self.get_rdt_allocation_info())
+            .field("sgx_info", &self.get_sgx_info())
+            .field("processor_trace_info", &self.get_processor_trace_info())
+            .field("tsc_info", &self.get_tsc_info())
+            .field(
+                "processor_frequency_info",
+                &self.get_processor_frequency_info(),
+            )
+            .field(
+                "deterministic_address_translation_info",
+                &self.get_deterministic_address_translation_info(),
+            )
+            .field("soc_vendor_info", &self.get_soc_vendor_info())
+            .field("hypervisor_info", &self.get_hypervisor_info())
+            .field(
+                "extended_processor_and_feature_identifiers",
+                &self.get_extended_processor_and_feature_identifiers(),
+            )
+            .field("processor_brand_string", &self.get_processor_brand_string())
+            .field("memory_encryption_info", &self.get_memory_encryption_info())
+            .field(
+                "performance_monitoring_debug_info",
+                &self.get_performance_monitoring_debug_info(),
+            )
+            .field(
+                "extended_cpu_topology_info",
+                &self.get_extended_cpu_topology_info(),
+            )
+            .field(
+                "transmeta_processor_info",
+                &self.get_transmeta_processor_info(),
+            )
+            .field("transmeta_cms_version", &self.get_transmeta_cms_version())
+            .field("transmeta_info_string", &self.get_transmeta_info_string())
+            .field("padlock_info", &self.get_padlock_info())
             .finish()
     }
 }
 
-impl fmt::Display for CacheInfo {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let typ = match self.typ {
-            CacheInfoType::General => "N/A",
-            CacheInfoType::Cache => "Cache",
-            CacheInfoType::TLB => "TLB",
-            CacheInfoType::STLB => "STLB",
-            CacheInfoType::DTLB => "DTLB",
-            CacheInfoType::Prefetch => "Prefetcher",
-        };
+/// Vendor Info String (LEAF=0x0)
+///
+/// A string that can be for example "AuthenticAMD" or "GenuineIntel".
+///
+/// # Technical Background
+///
+/// The vendor info is a 12-byte (96 bit) long string stored in `ebx`, `edx` and
+/// `ecx` by the corresponding `cpuid` instruction.
+///
+/// # Platforms
+/// ✅ AMD ✅ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct VendorInfo {
+    ebx: u32,
+    edx: u32,
+    ecx: u32,
+}
 
-        write!(f, "{:x}:\t {}: {}", self.num, typ, self.desc())
+impl VendorInfo {
+    /// Decodes the registers of LEAF=0x00.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            ebx: data.ebx,
+            edx: data.edx,
+            ecx: data.ecx,
+        }
     }
-}
 
-/// This table is taken from Intel manual (Section CPUID instruction).
-pub const CACHE_INFO_TABLE: [CacheInfo; 108] = [
-    CacheInfo {
-        num: 0x00,
-        typ: CacheInfoType::General,
-    },
-    CacheInfo {
-        num: 0x01,
-        typ: CacheInfoType::TLB,
-    },
-    CacheInfo {
-        num: 0x02,
-        typ: CacheInfoType::TLB,
+    /// Creates the vendor info from the 12 bytes of the vendor identification
+    /// string (e.g., `b"GenuineIntel"`), see [`VendorInfo::as_bytes`].
+    pub fn from_bytes(bytes: &[u8; 12]) -> Self {
+        let register =
+            |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+        Self {
+            ebx: register(0),
+            edx: register(4),
+            ecx: register(8),
+        }
+    }
+
+    /// The raw vendor identification string in register order (EBX, EDX,
+    /// ECX), e.g., to write it back into the leaf 0 of a guest exactly.
+    ///
+    /// Unlike [`VendorInfo::as_str`] this also works for strings that aren't
+    /// valid UTF-8.
+    pub fn as_bytes(&self) -> &[u8; 12] {
+        const _: () = assert!(size_of::<VendorInfo>() == 12);
+        // Safety: VendorInfo is laid out with repr(C) and exactly 12 byte
+        // long without any padding, [u8; 12] has no alignment requirements.
+        unsafe { &*(self as *const VendorInfo as *const [u8; 12]) }
+    }
+
+    /// Return vendor identification as human readable string.
+    pub fn as_str(&self) -> &str {
+        str::from_utf8(self.as_bytes()).unwrap_or("InvalidVendorString")
+    }
+
+    #[deprecated(
+        since = "10.0.0",
+        note = "Use idiomatic function name `as_str` instead"
+    )]
+    pub fn as_string(&self) -> &str {
+        self.as_str()
+    }
+
+    /// Encode the vendor string back into the registers of leaf 0.
+    ///
+    /// EAX (the highest supported leaf) is not part of `VendorInfo` and is zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: 0,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
+}
+
+impl Debug for VendorInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("VendorInfo")
+            .field("brand_string", &self.as_str())
+            .finish()
+    }
+}
+
+impl fmt::Display for VendorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Iterates over cache information (LEAF=0x02).
+///
+/// This will just return an index into a static table of cache descriptions
+/// (see [CACHE_INFO_TABLE](crate::CACHE_INFO_TABLE)).
+///
+/// # Platforms
+/// ❌ AMD ✅ Intel
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CacheInfoIter {
+    current: u32,
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+    edx: u32,
+}
+
+impl Iterator for CacheInfoIter {
+    type Item = CacheInfo;
+
+    /// Iterate over all cache information.
+    fn next(&mut self) -> Option<CacheInfo> {
+        // Every byte of the 4 register values returned by cpuid
+        // can contain information about a cache (except the
+        // very first one).
+        if self.current >= 4 * 4 {
+            return None;
+        }
+        let reg_index = self.current % 4;
+        let byte_index = self.current / 4;
+
+        let reg = match reg_index {
+            0 => self.eax,
+            1 => self.ebx,
+            2 => self.ecx,
+            3 => self.edx,
+            _ => unreachable!(),
+        };
+
+        let byte = match byte_index {
+            0 => reg,
+            1 => reg >> 8,
+            2 => reg >> 16,
+            3 => reg >> 24,
+            _ => unreachable!(),
+        } as u8;
+
+        if byte == 0 {
+            self.current += 1;
+            return self.next();
+        }
+
+        for cache_info in CACHE_INFO_TABLE.iter() {
+            if cache_info.num == byte {
+                self.current += 1;
+                return Some(*cache_info);
+            }
+        }
+
+        None
+    }
+}
+
+impl Debug for CacheInfoIter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut debug = f.debug_list();
+        self.clone().for_each(|ref item| {
+            debug.entry(item);
+        });
+        debug.finish()
+    }
+}
+
+/// What type of cache are we dealing with?
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CacheInfoType {
+    General,
+    Cache,
+    TLB,
+    STLB,
+    DTLB,
+    Prefetch,
+}
+
+/// Describes any kind of cache (TLB, Data and Instruction caches plus prefetchers).
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CacheInfo {
+    /// Number as retrieved from cpuid
+    pub num: u8,
+    /// Cache type
+    pub typ: CacheInfoType,
+}
+
+impl CacheInfo {
+    /// Description of the cache (from Intel Manual)
+    pub fn desc(&self) -> &'static str {
+        match self.num {
+            0x00 => "Null descriptor, this byte contains no information",
+            0x01 => "Instruction TLB: 4 KByte pages, 4-way set associative, 32 entries",
+            0x02 => "Instruction TLB: 4 MByte pages, fully associative, 2 entries",
+            0x03 => "Data TLB: 4 KByte pages, 4-way set associative, 64 entries",
+            0x04 => "Data TLB: 4 MByte pages, 4-way set associative, 8 entries",
+            0x05 => "Data TLB1: 4 MByte pages, 4-way set associative, 32 entries",
+            0x06 => "1st-level instruction cache: 8 KBytes, 4-way set associative, 32 byte line size",
+            0x08 => "1st-level instruction cache: 16 KBytes, 4-way set associative, 32 byte line size",
+            0x09 => "1st-level instruction cache: 32KBytes, 4-way set associative, 64 byte line size",
+            0x0A => "1st-level data cache: 8 KBytes, 2-way set associative, 32 byte line size",
+            0x0B => "Instruction TLB: 4 MByte pages, 4-way set associative, 4 entries",
+            0x0C => "1st-level data cache: 16 KBytes, 4-way set associative, 32 byte line size",
+            0x0D => "1st-level data cache: 16 KBytes, 4-way set associative, 64 byte line size",
+            0x0E => "1st-level data cache: 24 KBytes, 6-way set associative, 64 byte line size",
+            0x1D => "2nd-level cache: 128 KBytes, 2-way set associative, 64 byte line size",
+            0x21 => "2nd-level cache: 256 KBytes, 8-way set associative, 64 byte line size",
+            0x22 => "3rd-level cache: 512 KBytes, 4-way set associative, 64 byte line size, 2 lines per sector",
+            0x23 => "3rd-level cache: 1 MBytes, 8-way set associative, 64 byte line size, 2 lines per sector",
+            0x24 => "2nd-level cache: 1 MBytes, 16-way set associative, 64 byte line size",
+            0x25 => "3rd-level cache: 2 MBytes, 8-way set associative, 64 byte line size, 2 lines per sector",
+            0x29 => "3rd-level cache: 4 MBytes, 8-way set associative, 64 byte line size, 2 lines per sector",
+            0x2C => "1st-level data cache: 32 KBytes, 8-way set associative, 64 byte line size",
+            0x30 => "1st-level instruction cache: 32 KBytes, 8-way set associative, 64 byte line size",
+            0x40 => "No 2nd-level cache or, if processor contains a valid 2nd-level cache, no 3rd-level cache",
+            0x41 => "2nd-level cache: 128 KBytes, 4-way set associative, 32 byte line size",
+            0x42 => "2nd-level cache: 256 KBytes, 4-way set associative, 32 byte line size",
+            0x43 => "2nd-level cache: 512 KBytes, 4-way set associative, 32 byte line size",
+            0x44 => "2nd-level cache: 1 MByte, 4-way set associative, 32 byte line size",
+            0x45 => "2nd-level cache: 2 MByte, 4-way set associative, 32 byte line size",
+            0x46 => "3rd-level cache: 4 MByte, 4-way set associative, 64 byte line size",
+            0x47 => "3rd-level cache: 8 MByte, 8-way set associative, 64 byte line size",
+            0x48 => "2nd-level cache: 3MByte, 12-way set associative, 64 byte line size",
+            0x49 => "3rd-level cache: 4MB, 16-way set associative, 64-byte line size (Intel Xeon processor MP, Family 0FH, Model 06H); 2nd-level cache: 4 MByte, 16-way set ssociative, 64 byte line size",
+            0x4A => "3rd-level cache: 6MByte, 12-way set associative, 64 byte line size",
+            0x4B => "3rd-level cache: 8MByte, 16-way set associative, 64 byte line size",
+            0x4C => "3rd-level cache: 12MByte, 12-way set associative, 64 byte line size",
+            0x4D => "3rd-level cache: 16MByte, 16-way set associative, 64 byte line size",
+            0x4E => "2nd-level cache: 6MByte, 24-way set associative, 64 byte line size",
+            0x4F => "Instruction TLB: 4 KByte pages, 32 entries",
+            0x50 => "Instruction TLB: 4 KByte and 2-MByte or 4-MByte pages, 64 entries",
+            0x51 => "Instruction TLB: 4 KByte and 2-MByte or 4-MByte pages, 128 entries",
+            0x52 => "Instruction TLB: 4 KByte and 2-MByte or 4-MByte pages, 256 entries",
+            0x55 => "Instruction TLB: 2-MByte or 4-MByte pages, fully associative, 7 entries",
+            0x56 => "Data TLB0: 4 MByte pages, 4-way set associative, 16 entries",
+            0x57 => "Data TLB0: 4 KByte pages, 4-way associative, 16 entries",
+            0x59 => "Data TLB0: 4 KByte pages, fully associative, 16 entries",
+            0x5A => "Data TLB0: 2-MByte or 4 MByte pages, 4-way set associative, 32 entries",
+            0x5B => "Data TLB: 4 KByte and 4 MByte pages, 64 entries",
+            0x5C => "Data TLB: 4 KByte and 4 MByte pages,128 entries",
+            0x5D => "Data TLB: 4 KByte and 4 MByte pages,256 entries",
+            0x60 => "1st-level data cache: 16 KByte, 8-way set associative, 64 byte line size",
+            0x61 => "Instruction TLB: 4 KByte pages, fully associative, 48 entries",
+            0x63 => "Data TLB: 2 MByte or 4 MByte pages, 4-way set associative, 32 entries and a separate array with 1 GByte pages, 4-way set associative, 4 entries",
+            0x64 => "Data TLB: 4 KByte pages, 4-way set associative, 512 entries",
+            0x66 => "1st-level data cache: 8 KByte, 4-way set associative, 64 byte line size",
+            0x67 => "1st-level data cache: 16 KByte, 4-way set associative, 64 byte line size",
+            0x68 => "1st-level data cache: 32 KByte, 4-way set associative, 64 byte line size",
+            0x6A => "uTLB: 4 KByte pages, 8-way set associative, 64 entries",
+            0x6B => "DTLB: 4 KByte pages, 8-way set associative, 256 entries",
+            0x6C => "DTLB: 2M/4M pages, 8-way set associative, 128 entries",
+            0x6D => "DTLB: 1 GByte pages, fully associative, 16 entries",
+            0x70 => "Trace cache: 12 K-μop, 8-way set associative",
+            0x71 => "Trace cache: 16 K-μop, 8-way set associative",
+            0x72 => "Trace cache: 32 K-μop, 8-way set associative",
+            0x76 => "Instruction TLB: 2M/4M pages, fully associative, 8 entries",
+            0x78 => "2nd-level cache: 1 MByte, 4-way set associative, 64byte line size",
+            0x79 => "2nd-level cache: 128 KByte, 8-way set associative, 64 byte line size, 2 lines per sector",
+            0x7A => "2nd-level cache: 256 KByte, 8-way set associative, 64 byte line size, 2 lines per sector",
+            0x7B => "2nd-level cache: 512 KByte, 8-way set associative, 64 byte line size, 2 lines per sector",
+            0x7C => "2nd-level cache: 1 MByte, 8-way set associative, 64 byte line size, 2 lines per sector",
+            0x7D => "2nd-level cache: 2 MByte, 8-way set associative, 64byte line size",
+            0x7F => "2nd-level cache: 512 KByte, 2-way set associative, 64-byte line size",
+            0x80 => "2nd-level cache: 512 KByte, 8-way set associative, 64-byte line size",
+            0x82 => "2nd-level cache: 256 KByte, 8-way set associative, 32 byte line size",
+            0x83 => "2nd-level cache: 512 KByte, 8-way set associative, 32 byte line size",
+            0x84 => "2nd-level cache: 1 MByte, 8-way set associative, 32 byte line size",
+            0x85 => "2nd-level cache: 2 MByte, 8-way set associative, 32 byte line size",
+            0x86 => "2nd-level cache: 512 KByte, 4-way set associative, 64 byte line size",
+            0x87 => "2nd-level cache: 1 MByte, 8-way set associative, 64 byte line size",
+            0xA0 => "DTLB: 4k pages, fully associative, 32 entries",
+            0xB0 => "Instruction TLB: 4 KByte pages, 4-way set associative, 128 entries",
+            0xB1 => "Instruction TLB: 2M pages, 4-way, 8 entries or 4M pages, 4-way, 4 entries",
+            0xB2 => "Instruction TLB: 4KByte pages, 4-way set associative, 64 entries",
+            0xB3 => "Data TLB: 4 KByte pages, 4-way set associative, 128 entries",
+            0xB4 => "Data TLB1: 4 KByte pages, 4-way associative, 256 entries",
+            0xB5 => "Instruction TLB: 4KByte pages, 8-way set associative, 64 entries",
+            0xB6 => "Instruction TLB: 4KByte pages, 8-way set associative, 128 entries",
+            0xBA => "Data TLB1: 4 KByte pages, 4-way associative, 64 entries",
+            0xC0 => "Data TLB: 4 KByte and 4 MByte pages, 4-way associative, 8 entries",
+            0xC1 => "Shared 2nd-Level TLB: 4 KByte/2MByte pages, 8-way associative, 1024 entries",
+            0xC2 => "DTLB: 2 MByte/$MByte pages, 4-way associative, 16 entries",
+            0xC3 => "Shared 2nd-Level TLB: 4 KByte /2 MByte pages, 6-way associative, 1536 entries. Also 1GBbyte pages, 4-way, 16 entries.",
+            0xC4 => "DTLB: 2M/4M Byte pages, 4-way associative, 32 entries",
+            0xCA => "Shared 2nd-Level TLB: 4 KByte pages, 4-way associative, 512 entries",
+            0xD0 => "3rd-level cache: 512 KByte, 4-way set associative, 64 byte line size",
+            0xD1 => "3rd-level cache: 1 MByte, 4-way set associative, 64 byte line size",
+            0xD2 => "3rd-level cache: 2 MByte, 4-way set associative, 64 byte line size",
+            0xD6 => "3rd-level cache: 1 MByte, 8-way set associative, 64 byte line size",
+            0xD7 => "3rd-level cache: 2 MByte, 8-way set associative, 64 byte line size",
+            0xD8 => "3rd-level cache: 4 MByte, 8-way set associative, 64 byte line size",
+            0xDC => "3rd-level cache: 1.5 MByte, 12-way set associative, 64 byte line size",
+            0xDD => "3rd-level cache: 3 MByte, 12-way set associative, 64 byte line size",
+            0xDE => "3rd-level cache: 6 MByte, 12-way set associative, 64 byte line size",
+            0xE2 => "3rd-level cache: 2 MByte, 16-way set associative, 64 byte line size",
+            0xE3 => "3rd-level cache: 4 MByte, 16-way set associative, 64 byte line size",
+            0xE4 => "3rd-level cache: 8 MByte, 16-way set associative, 64 byte line size",
+            0xEA => "3rd-level cache: 12MByte, 24-way set associative, 64 byte line size",
+            0xEB => "3rd-level cache: 18MByte, 24-way set associative, 64 byte line size",
+            0xEC => "3rd-level cache: 24MByte, 24-way set associative, 64 byte line size",
+            0xF0 => "64-Byte prefetching",
+            0xF1 => "128-Byte prefetching",
+            0xFE => "CPUID leaf 2 does not report TLB descriptor information; use CPUID leaf 18H to query TLB and other address translation parameters.",
+            0xFF => "CPUID leaf 2 does not report cache descriptor information, use CPUID leaf 4 to query cache parameters",
+            _ => "Unknown cache type!"
+        }
+    }
+}
+
+impl Debug for CacheInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CacheInfo")
+            .field("typ", &self.typ)
+            .field("desc", &self.desc())
+            .finish()
+    }
+}
+
+impl fmt::Display for CacheInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let typ = match self.typ {
+            CacheInfoType::General => "N/A",
+            CacheInfoType::Cache => "Cache",
+            CacheInfoType::TLB => "TLB",
+            CacheInfoType::STLB => "STLB",
+            CacheInfoType::DTLB => "DTLB",
+            CacheInfoType::Prefetch => "Prefetcher",
+        };
+
+        write!(f, "{:x}:\t {}: {}", self.num, typ, self.desc())
+    }
+}
+
+/// This table is taken from Intel manual (Section CPUID instruction).
+pub const CACHE_INFO_TABLE: [CacheInfo; 113] = [
+    CacheInfo {
+        num: 0x00,
+        typ: CacheInfoType::General,
+    },
+    CacheInfo {
+        num: 0x01,
+        typ: CacheInfoType::TLB,
+    },
+    CacheInfo {
+        num: 0x02,
+        typ: CacheInfoType::TLB,
     },
     CacheInfo {
         num: 0x03,
@@ -1269,6 +3425,10 @@ pub const CACHE_INFO_TABLE: [CacheInfo; 108] = [
         num: 0x0E,
         typ: CacheInfoType::Cache,
     },
+    CacheInfo {
+        num: 0x1D,
+        typ: CacheInfoType::Cache,
+    },
     CacheInfo {
         num: 0x21,
         typ: CacheInfoType::Cache,
@@ -1421,6 +3581,10 @@ pub const CACHE_INFO_TABLE: [CacheInfo; 108] = [
         num: 0x63,
         typ: CacheInfoType::TLB,
     },
+    CacheInfo {
+        num: 0x64,
+        typ: CacheInfoType::TLB,
+    },
     CacheInfo {
         num: 0x66,
         typ: CacheInfoType::Cache,
@@ -1521,6 +3685,10 @@ pub const CACHE_INFO_TABLE: [CacheInfo; 108] = [
         num: 0x87,
         typ: CacheInfoType::Cache,
     },
+    CacheInfo {
+        num: 0xA0,
+        typ: CacheInfoType::DTLB,
+    },
     CacheInfo {
         num: 0xB0,
         typ: CacheInfoType::TLB,
@@ -1565,6 +3733,14 @@ pub const CACHE_INFO_TABLE: [CacheInfo; 108] = [
         num: 0xC2,
         typ: CacheInfoType::DTLB,
     },
+    CacheInfo {
+        num: 0xC3,
+        typ: CacheInfoType::STLB,
+    },
+    CacheInfo {
+        num: 0xC4,
+        typ: CacheInfoType::DTLB,
+    },
     CacheInfo {
         num: 0xCA,
         typ: CacheInfoType::STLB,
@@ -1647,2998 +3823,6747 @@ pub const CACHE_INFO_TABLE: [CacheInfo; 108] = [
     },
 ];
 
-/// Processor Serial Number (LEAF=0x3).
-///
-/// # Deprecated
-///
-/// Processor serial number (PSN) is not supported in the Pentium 4 processor or
-/// later. On all models, use the PSN flag (returned using CPUID) to check for
-/// PSN support before accessing the feature.
-///
-/// # Platforms
-/// ❌ AMD ✅ Intel
-#[derive(PartialEq, Eq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct ProcessorSerial {
-    /// Lower bits
-    ecx: u32,
-    /// Middle bits
-    edx: u32,
-    /// Upper bits (come from leaf 0x1)
-    eax: u32,
-}
+/// Processor Serial Number (LEAF=0x3).
+///
+/// # Deprecated
+///
+/// Processor serial number (PSN) is not supported in the Pentium 4 processor or
+/// later. On all models, use the PSN flag (returned using CPUID) to check for
+/// PSN support before accessing the feature.
+///
+/// # Platforms
+/// ❌ AMD ✅ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessorSerial {
+    /// Lower bits
+    ecx: u32,
+    /// Middle bits
+    edx: u32,
+    /// Upper bits (come from leaf 0x1)
+    eax: u32,
+}
+
+impl ProcessorSerial {
+    /// Decodes the registers of LEAF=0x03, the upper bits are taken from EAX of LEAF=0x01 (`feature_info`).
+    pub fn from_registers(feature_info: CpuIdResult, data: CpuIdResult) -> Self {
+        Self {
+            ecx: data.ecx,
+            edx: data.edx,
+            eax: feature_info.eax,
+        }
+    }
+
+    /// Bits 00-31 of 96 bit processor serial number.
+    ///
+    /// (Available in Pentium III processor only; otherwise, the value in this register is reserved.)
+    pub fn serial_lower(&self) -> u32 {
+        self.ecx
+    }
+
+    /// Bits 32-63 of 96 bit processor serial number.
+    ///
+    /// (Available in Pentium III processor only; otherwise, the value in this register is reserved.)
+    pub fn serial_middle(&self) -> u32 {
+        self.edx
+    }
+
+    /// Bits 64-96 of 96 bit processor serial number.
+    pub fn serial_upper(&self) -> u32 {
+        self.eax
+    }
+
+    /// Combination of bits 00-31 and 32-63 of 96 bit processor serial number.
+    pub fn serial(&self) -> u64 {
+        (self.serial_lower() as u64) | (self.serial_middle() as u64) << 32
+    }
+
+    /// 96 bit processor serial number.
+    pub fn serial_all(&self) -> u128 {
+        (self.serial_lower() as u128)
+            | ((self.serial_middle() as u128) << 32)
+            | ((self.serial_upper() as u128) << 64)
+    }
+
+    /// Encode the serial number back into the registers of leaf 3.
+    ///
+    /// The upper 32 bits of the serial are reported in EAX of leaf 1 and are
+    /// therefore not part of the result.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: 0,
+            ebx: 0,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
+
+    set_bits_fn!(set_serial_lower, serial_lower, ecx, 0, 31, u32);
+    set_bits_fn!(set_serial_middle, serial_middle, edx, 0, 31, u32);
+    set_bits_fn!(set_serial_upper, serial_upper, eax, 0, 31, u32);
+}
+
+impl Debug for ProcessorSerial {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ProcessorSerial")
+            .field("serial_lower", &self.serial_lower())
+            .field("serial_middle", &self.serial_middle())
+            .finish()
+    }
+}
+
+/// Processor and Processor Feature Identifiers (LEAF=0x01).
+///
+/// # Platforms
+/// ✅ AMD ✅ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FeatureInfo {
+    vendor: Vendor,
+    eax: u32,
+    ebx: u32,
+    edx_ecx: FeatureInfoFlags,
+}
+
+impl FeatureInfo {
+    /// Decodes the registers of LEAF=0x01, the vendor decides how vendor specific fields are interpreted.
+    pub fn from_registers(vendor: &VendorInfo, data: CpuIdResult) -> Self {
+        Self {
+            vendor: Vendor::from_vendor_leaf(vendor.as_cpuid_result()),
+            eax: data.eax,
+            ebx: data.ebx,
+            edx_ecx: FeatureInfoFlags {
+                bits: ((data.edx as u64) << 32) | (data.ecx as u64),
+            },
+        }
+    }
+
+    /// Version Information: Extended Family
+    pub fn extended_family_id(&self) -> u8 {
+        get_bits(self.eax, 20, 27) as u8
+    }
+
+    /// Version Information: Extended Model
+    pub fn extended_model_id(&self) -> u8 {
+        get_bits(self.eax, 16, 19) as u8
+    }
+
+    /// Version Information: Family
+    pub fn base_family_id(&self) -> u8 {
+        get_bits(self.eax, 8, 11) as u8
+    }
+
+    /// Version Information: Model
+    pub fn base_model_id(&self) -> u8 {
+        get_bits(self.eax, 4, 7) as u8
+    }
+
+    pub fn family_id(&self) -> u8 {
+        let base_family_id = self.base_family_id();
+        let extended_family_id = self.extended_family_id();
+        let just_use_base = (matches!(self.vendor, Vendor::Amd | Vendor::Hygon)
+            && base_family_id < 0xf)
+            || (self.vendor == Vendor::Intel && base_family_id != 0xf);
+
+        if just_use_base {
+            base_family_id
+        } else {
+            base_family_id + extended_family_id
+        }
+    }
+
+    pub fn model_id(&self) -> u8 {
+        let base_family_id = self.base_family_id();
+        let base_model_id = self.base_model_id();
+        let extended_model_id = self.extended_model_id();
+        let just_use_base = (matches!(self.vendor, Vendor::Amd | Vendor::Hygon)
+            && base_family_id < 0xf)
+            || (self.vendor == Vendor::Intel && base_family_id != 0xf && base_family_id != 0x6);
+
+        if just_use_base {
+            base_model_id
+        } else {
+            (extended_model_id << 4) | base_model_id
+        }
+    }
+
+    /// Version Information: Stepping ID
+    pub fn stepping_id(&self) -> u8 {
+        get_bits(self.eax, 0, 3) as u8
+    }
+
+    /// Brand Index
+    pub fn brand_index(&self) -> u8 {
+        get_bits(self.ebx, 0, 7) as u8
+    }
+
+    /// Processor brand for the [`Self::brand_index`] of older Intel CPUs
+    /// (before the brand string of LEAF=0x8000_0002..=0x8000_0004).
+    ///
+    /// Decoded with the brand index table of the Intel SDM (Vol. 2A,
+    /// Table 3-14). Returns `None` if the brand index is zero (not supported),
+    /// reserved, or the CPU isn't from Intel.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn brand_index_string(&self) -> Option<&'static str> {
+        if self.vendor != Vendor::Intel {
+            return None;
+        }
+
+        let brand = match (self.brand_index(), self.eax) {
+            (0x01, _) => "Intel(R) Celeron(R) processor",
+            (0x02, _) => "Intel(R) Pentium(R) III processor",
+            (0x03, 0x6b1) => "Intel(R) Celeron(R) processor",
+            (0x03, _) => "Intel(R) Pentium(R) III Xeon(R) processor",
+            (0x04, _) => "Intel(R) Pentium(R) III processor",
+            (0x06, _) => "Mobile Intel(R) Pentium(R) III processor-M",
+            (0x07, _) => "Mobile Intel(R) Celeron(R) processor",
+            (0x08, _) => "Intel(R) Pentium(R) 4 processor",
+            (0x09, _) => "Intel(R) Pentium(R) 4 processor",
+            (0x0a, _) => "Intel(R) Celeron(R) processor",
+            (0x0b, 0xf13) => "Intel(R) Xeon(R) processor MP",
+            (0x0b, _) => "Intel(R) Xeon(R) processor",
+            (0x0c, _) => "Intel(R) Xeon(R) processor MP",
+            (0x0e, 0xf13) => "Intel(R) Xeon(R) processor",
+            (0x0e, _) => "Mobile Intel(R) Pentium(R) 4 processor-M",
+            (0x0f, _) => "Mobile Intel(R) Celeron(R) processor",
+            (0x11, _) => "Mobile Genuine Intel(R) processor",
+            (0x12, _) => "Intel(R) Celeron(R) M processor",
+            (0x13, _) => "Mobile Intel(R) Celeron(R) processor",
+            (0x14, _) => "Intel(R) Celeron(R) processor",
+            (0x15, _) => "Mobile Genuine Intel(R) processor",
+            (0x16, _) => "Intel(R) Pentium(R) M processor",
+            (0x17, _) => "Mobile Intel(R) Celeron(R) processor",
+            _ => return None,
+        };
+        Some(brand)
+    }
+
+    /// CLFLUSH line size (Value ∗ 8 = cache line size in bytes)
+    pub fn cflush_cache_line_size(&self) -> u8 {
+        get_bits(self.ebx, 8, 15) as u8
+    }
+
+    /// CLFLUSH line size in bytes, i.e., [`Self::cflush_cache_line_size`] ∗ 8.
+    ///
+    /// Only valid if [`Self::has_clflush`] is set.
+    pub fn cache_line_flush_size_bytes(&self) -> u16 {
+        self.cflush_cache_line_size() as u16 * 8
+    }
+
+    /// Initial APIC ID
+    pub fn initial_local_apic_id(&self) -> u8 {
+        get_bits(self.ebx, 24, 31) as u8
+    }
+
+    /// Maximum number of addressable IDs for logical processors in this physical package.
+    pub fn max_logical_processor_ids(&self) -> u8 {
+        get_bits(self.ebx, 16, 23) as u8
+    }
+
+    check_flag!(
+        doc = "Streaming SIMD Extensions 3 (SSE3). A value of 1 indicates the processor \
+               supports this technology.",
+        has_sse3,
+        edx_ecx,
+        FeatureInfoFlags::SSE3
+    );
+
+    check_flag!(
+        doc = "PCLMULQDQ. A value of 1 indicates the processor supports the PCLMULQDQ \
+               instruction",
+        has_pclmulqdq,
+        edx_ecx,
+        FeatureInfoFlags::PCLMULQDQ
+    );
+
+    check_flag!(
+        doc = "64-bit DS Area. A value of 1 indicates the processor supports DS area \
+               using 64-bit layout",
+        has_ds_area,
+        edx_ecx,
+        FeatureInfoFlags::DTES64
+    );
+
+    check_flag!(
+        doc = "MONITOR/MWAIT. A value of 1 indicates the processor supports this feature.",
+        has_monitor_mwait,
+        edx_ecx,
+        FeatureInfoFlags::MONITOR
+    );
+
+    check_flag!(
+        doc = "CPL Qualified Debug Store. A value of 1 indicates the processor supports \
+               the extensions to the  Debug Store feature to allow for branch message \
+               storage qualified by CPL.",
+        has_cpl,
+        edx_ecx,
+        FeatureInfoFlags::DSCPL
+    );
+
+    check_flag!(
+        doc = "Virtual Machine Extensions. A value of 1 indicates that the processor \
+               supports this technology.",
+        has_vmx,
+        edx_ecx,
+        FeatureInfoFlags::VMX
+    );
+
+    check_flag!(
+        doc = "Safer Mode Extensions. A value of 1 indicates that the processor supports \
+               this technology. See Chapter 5, Safer Mode Extensions Reference.",
+        has_smx,
+        edx_ecx,
+        FeatureInfoFlags::SMX
+    );
+
+    check_flag!(
+        doc = "Enhanced Intel SpeedStep® technology. A value of 1 indicates that the \
+               processor supports this technology.",
+        has_eist,
+        edx_ecx,
+        FeatureInfoFlags::EIST
+    );
+
+    check_flag!(
+        doc = "Thermal Monitor 2. A value of 1 indicates whether the processor supports \
+               this technology.",
+        has_tm2,
+        edx_ecx,
+        FeatureInfoFlags::TM2
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates the presence of the Supplemental Streaming SIMD \
+               Extensions 3 (SSSE3). A value of 0 indicates the instruction extensions \
+               are not present in the processor",
+        has_ssse3,
+        edx_ecx,
+        FeatureInfoFlags::SSSE3
+    );
+
+    check_flag!(
+        doc = "L1 Context ID. A value of 1 indicates the L1 data cache mode can be set \
+               to either adaptive mode or shared mode. A value of 0 indicates this \
+               feature is not supported. See definition of the IA32_MISC_ENABLE MSR Bit \
+               24 (L1 Data Cache Context Mode) for details.",
+        has_cnxtid,
+        edx_ecx,
+        FeatureInfoFlags::CNXTID
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates the processor supports FMA extensions using YMM \
+               state.",
+        has_fma,
+        edx_ecx,
+        FeatureInfoFlags::FMA
+    );
+
+    check_flag!(
+        doc = "CMPXCHG16B Available. A value of 1 indicates that the feature is \
+               available. See the CMPXCHG8B/CMPXCHG16B Compare and Exchange Bytes \
+               section. 14",
+        has_cmpxchg16b,
+        edx_ecx,
+        FeatureInfoFlags::CMPXCHG16B
+    );
+
+    check_flag!(
+        doc = "Perfmon and Debug Capability: A value of 1 indicates the processor \
+               supports the performance   and debug feature indication MSR \
+               IA32_PERF_CAPABILITIES.",
+        has_pdcm,
+        edx_ecx,
+        FeatureInfoFlags::PDCM
+    );
+
+    check_flag!(
+        doc = "Process-context identifiers. A value of 1 indicates that the processor \
+               supports PCIDs and the software may set CR4.PCIDE to 1.",
+        has_pcid,
+        edx_ecx,
+        FeatureInfoFlags::PCID
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates the processor supports the ability to prefetch \
+               data from a memory mapped device. See also \
+               [`CpuId::get_direct_cache_access_info`].",
+        has_dca,
+        edx_ecx,
+        FeatureInfoFlags::DCA
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates that the processor supports SSE4.1.",
+        has_sse41,
+        edx_ecx,
+        FeatureInfoFlags::SSE41
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates that the processor supports SSE4.2.",
+        has_sse42,
+        edx_ecx,
+        FeatureInfoFlags::SSE42
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates that the processor supports x2APIC feature.",
+        has_x2apic,
+        edx_ecx,
+        FeatureInfoFlags::X2APIC
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates that the processor supports MOVBE instruction.",
+        has_movbe,
+        edx_ecx,
+        FeatureInfoFlags::MOVBE
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates that the processor supports the POPCNT instruction.",
+        has_popcnt,
+        edx_ecx,
+        FeatureInfoFlags::POPCNT
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates that the processors local APIC timer supports \
+               one-shot operation using a TSC deadline value.",
+        has_tsc_deadline,
+        edx_ecx,
+        FeatureInfoFlags::TSC_DEADLINE
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates that the processor supports the AESNI instruction \
+               extensions.",
+        has_aesni,
+        edx_ecx,
+        FeatureInfoFlags::AESNI
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates that the processor supports the XSAVE/XRSTOR \
+               processor extended states feature, the XSETBV/XGETBV instructions, and \
+               XCR0.",
+        has_xsave,
+        edx_ecx,
+        FeatureInfoFlags::XSAVE
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates that the OS has enabled XSETBV/XGETBV instructions \
+               to access XCR0, and support for processor extended state management using \
+               XSAVE/XRSTOR.",
+        has_oxsave,
+        edx_ecx,
+        FeatureInfoFlags::OSXSAVE
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates the processor supports the AVX instruction \
+               extensions.",
+        has_avx,
+        edx_ecx,
+        FeatureInfoFlags::AVX
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates that processor supports 16-bit floating-point \
+               conversion instructions.",
+        has_f16c,
+        edx_ecx,
+        FeatureInfoFlags::F16C
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates that processor supports RDRAND instruction.",
+        has_rdrand,
+        edx_ecx,
+        FeatureInfoFlags::RDRAND
+    );
+
+    check_flag!(
+        doc = "A value of 1 indicates the indicates the presence of a hypervisor.",
+        has_hypervisor,
+        edx_ecx,
+        FeatureInfoFlags::HYPERVISOR
+    );
+
+    check_flag!(
+        doc = "Floating Point Unit On-Chip. The processor contains an x87 FPU.",
+        has_fpu,
+        edx_ecx,
+        FeatureInfoFlags::FPU
+    );
+
+    check_flag!(
+        doc = "Virtual 8086 Mode Enhancements. Virtual 8086 mode enhancements, including \
+               CR4.VME for controlling the feature, CR4.PVI for protected mode virtual \
+               interrupts, software interrupt indirection, expansion of the TSS with the \
+               software indirection bitmap, and EFLAGS.VIF and EFLAGS.VIP flags.",
+        has_vme,
+        edx_ecx,
+        FeatureInfoFlags::VME
+    );
+
+    check_flag!(
+        doc = "Debugging Extensions. Support for I/O breakpoints, including CR4.DE for \
+               controlling the feature, and optional trapping of accesses to DR4 and DR5.",
+        has_de,
+        edx_ecx,
+        FeatureInfoFlags::DE
+    );
+
+    check_flag!(
+        doc = "Page Size Extension. Large pages of size 4 MByte are supported, including \
+               CR4.PSE for controlling the feature, the defined dirty bit in PDE (Page \
+               Directory Entries), optional reserved bit trapping in CR3, PDEs, and PTEs.",
+        has_pse,
+        edx_ecx,
+        FeatureInfoFlags::PSE
+    );
+
+    check_flag!(
+        doc = "Time Stamp Counter. The RDTSC instruction is supported, including CR4.TSD \
+               for controlling privilege.",
+        has_tsc,
+        edx_ecx,
+        FeatureInfoFlags::TSC
+    );
+
+    check_flag!(
+        doc = "Model Specific Registers RDMSR and WRMSR Instructions. The RDMSR and \
+               WRMSR instructions are supported. Some of the MSRs are implementation \
+               dependent.",
+        has_msr,
+        edx_ecx,
+        FeatureInfoFlags::MSR
+    );
+
+    check_flag!(
+        doc = "Physical Address Extension. Physical addresses greater than 32 bits are \
+               supported: extended page table entry formats, an extra level in the page \
+               translation tables is defined, 2-MByte pages are supported instead of 4 \
+               Mbyte pages if PAE bit is 1.",
+        has_pae,
+        edx_ecx,
+        FeatureInfoFlags::PAE
+    );
+
+    check_flag!(
+        doc = "Machine Check Exception. Exception 18 is defined for Machine Checks, \
+               including CR4.MCE for controlling the feature. This feature does not \
+               define the model-specific implementations of machine-check error logging, \
+               reporting, and processor shutdowns. Machine Check exception handlers may \
+               have to depend on processor version to do model specific processing of \
+               the exception, or test for the presence of the Machine Check feature.",
+        has_mce,
+        edx_ecx,
+        FeatureInfoFlags::MCE
+    );
+
+    check_flag!(
+        doc = "CMPXCHG8B Instruction. The compare-and-exchange 8 bytes (64 bits) \
+               instruction is supported (implicitly locked and atomic).",
+        has_cmpxchg8b,
+        edx_ecx,
+        FeatureInfoFlags::CX8
+    );
+
+    check_flag!(
+        doc = "APIC On-Chip. The processor contains an Advanced Programmable Interrupt \
+               Controller (APIC), responding to memory mapped commands in the physical \
+               address range FFFE0000H to FFFE0FFFH (by default - some processors permit \
+               the APIC to be relocated).",
+        has_apic,
+        edx_ecx,
+        FeatureInfoFlags::APIC
+    );
+
+    check_flag!(
+        doc = "SYSENTER and SYSEXIT Instructions. The SYSENTER and SYSEXIT and \
+               associated MSRs are supported.",
+        has_sysenter_sysexit,
+        edx_ecx,
+        FeatureInfoFlags::SEP
+    );
+
+    check_flag!(
+        doc = "Memory Type Range Registers. MTRRs are supported. The MTRRcap MSR \
+               contains feature bits that describe what memory types are supported, how \
+               many variable MTRRs are supported, and whether fixed MTRRs are supported.",
+        has_mtrr,
+        edx_ecx,
+        FeatureInfoFlags::MTRR
+    );
+
+    check_flag!(
+        doc = "Page Global Bit. The global bit is supported in paging-structure entries \
+               that map a page, indicating TLB entries that are common to different \
+               processes and need not be flushed. The CR4.PGE bit controls this feature.",
+        has_pge,
+        edx_ecx,
+        FeatureInfoFlags::PGE
+    );
+
+    check_flag!(
+        doc = "Machine Check Architecture. A value of 1 indicates the Machine Check \
+               Architecture of reporting machine errors is supported. The MCG_CAP MSR \
+               contains feature bits describing how many banks of error reporting MSRs \
+               are supported.",
+        has_mca,
+        edx_ecx,
+        FeatureInfoFlags::MCA
+    );
+
+    check_flag!(
+        doc = "Conditional Move Instructions. The conditional move instruction CMOV is \
+               supported. In addition, if x87 FPU is present as indicated by the \
+               CPUID.FPU feature bit, then the FCOMI and FCMOV instructions are supported",
+        has_cmov,
+        edx_ecx,
+        FeatureInfoFlags::CMOV
+    );
+
+    check_flag!(
+        doc = "Page Attribute Table. Page Attribute Table is supported. This feature \
+               augments the Memory Type Range Registers (MTRRs), allowing an operating \
+               system to specify attributes of memory accessed through a linear address \
+               on a 4KB granularity.",
+        has_pat,
+        edx_ecx,
+        FeatureInfoFlags::PAT
+    );
+
+    check_flag!(
+        doc = "36-Bit Page Size Extension. 4-MByte pages addressing physical memory \
+               beyond 4 GBytes are supported with 32-bit paging. This feature indicates \
+               that upper bits of the physical address of a 4-MByte page are encoded in \
+               bits 20:13 of the page-directory entry. Such physical addresses are \
+               limited by MAXPHYADDR and may be up to 40 bits in size.",
+        has_pse36,
+        edx_ecx,
+        FeatureInfoFlags::PSE36
+    );
+
+    check_flag!(
+        doc = "Processor Serial Number. The processor supports the 96-bit processor \
+               identification number feature and the feature is enabled.",
+        has_psn,
+        edx_ecx,
+        FeatureInfoFlags::PSN
+    );
+
+    check_flag!(
+        doc = "CLFLUSH Instruction. CLFLUSH Instruction is supported.",
+        has_clflush,
+        edx_ecx,
+        FeatureInfoFlags::CLFSH
+    );
+
+    check_flag!(
+        doc = "Debug Store. The processor supports the ability to write debug \
+               information into a memory resident buffer. This feature is used by the \
+               branch trace store (BTS) and processor event-based sampling (PEBS) \
+               facilities (see Chapter 23, Introduction to Virtual-Machine Extensions, \
+               in the Intel® 64 and IA-32 Architectures Software Developers Manual, \
+               Volume 3C).",
+        has_ds,
+        edx_ecx,
+        FeatureInfoFlags::DS
+    );
+
+    check_flag!(
+        doc = "Thermal Monitor and Software Controlled Clock Facilities. The processor \
+               implements internal MSRs that allow processor temperature to be monitored \
+               and processor performance to be modulated in predefined duty cycles under \
+               software control.",
+        has_acpi,
+        edx_ecx,
+        FeatureInfoFlags::ACPI
+    );
+
+    check_flag!(
+        doc = "Intel MMX Technology. The processor supports the Intel MMX technology.",
+        has_mmx,
+        edx_ecx,
+        FeatureInfoFlags::MMX
+    );
+
+    check_flag!(
+        doc = "FXSAVE and FXRSTOR Instructions. The FXSAVE and FXRSTOR instructions are \
+               supported for fast save and restore of the floating point context. \
+               Presence of this bit also indicates that CR4.OSFXSR is available for an \
+               operating system to indicate that it supports the FXSAVE and FXRSTOR \
+               instructions.",
+        has_fxsave_fxstor,
+        edx_ecx,
+        FeatureInfoFlags::FXSR
+    );
+
+    check_flag!(
+        doc = "SSE. The processor supports the SSE extensions.",
+        has_sse,
+        edx_ecx,
+        FeatureInfoFlags::SSE
+    );
+
+    check_flag!(
+        doc = "SSE2. The processor supports the SSE2 extensions.",
+        has_sse2,
+        edx_ecx,
+        FeatureInfoFlags::SSE2
+    );
+
+    check_flag!(
+        doc = "Self Snoop. The processor supports the management of conflicting memory \
+               types by performing a snoop of its own cache structure for transactions \
+               issued to the bus.",
+        has_ss,
+        edx_ecx,
+        FeatureInfoFlags::SS
+    );
+
+    check_flag!(
+        doc = "Max APIC IDs reserved field is Valid. A value of 0 for HTT indicates \
+               there is only a single logical processor in the package and software \
+               should assume only a single APIC ID is reserved.  A value of 1 for HTT \
+               indicates the value in CPUID.1.EBX\\[23:16\\] (the Maximum number of \
+               addressable IDs for logical processors in this package) is valid for the \
+               package.",
+        has_htt,
+        edx_ecx,
+        FeatureInfoFlags::HTT
+    );
+
+    check_flag!(
+        doc = "Thermal Monitor. The processor implements the thermal monitor automatic \
+               thermal control circuitry (TCC).",
+        has_tm,
+        edx_ecx,
+        FeatureInfoFlags::TM
+    );
+
+    check_flag!(
+        doc = "Pending Break Enable. The processor supports the use of the FERR#/PBE# \
+               pin when the processor is in the stop-clock state (STPCLK# is asserted) \
+               to signal the processor that an interrupt is pending and that the \
+               processor should return to normal operation to handle the interrupt. Bit \
+               10 (PBE enable) in the IA32_MISC_ENABLE MSR enables this capability.",
+        has_pbe,
+        edx_ecx,
+        FeatureInfoFlags::PBE
+    );
+
+    /// Encode this struct back into the registers of leaf 1.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.edx_ecx.bits() as u32,
+            edx: (self.edx_ecx.bits() >> 32) as u32,
+        }
+    }
+
+    /// Bits set in ECX and EDX that this library doesn't interpret, i.e.,
+    /// reserved bits or flags it doesn't know about yet.
+    ///
+    /// EAX and EBX only contain values and always read as zero.
+    pub fn reserved_bits(&self) -> CpuIdResult {
+        let unknown = self.edx_ecx.bits() & !FeatureInfoFlags::all().bits();
+        CpuIdResult {
+            eax: 0,
+            ebx: 0,
+            ecx: unknown as u32,
+            edx: (unknown >> 32) as u32,
+        }
+    }
+
+    /// Set the family reported by [`Self::family_id`].
+    ///
+    /// Families above 0xF are encoded using the extended family field.
+    pub fn set_family_id(&mut self, family: u8) {
+        if family >= 0xF {
+            self.set_base_family_id(0xF);
+            self.set_extended_family_id(family - 0xF);
+        } else {
+            self.set_base_family_id(family);
+            self.set_extended_family_id(0);
+        }
+    }
+
+    /// Set the model reported by [`Self::model_id`].
+    ///
+    /// The upper nibble is only taken into account for family 0x6 and 0xF,
+    /// so set the family first.
+    pub fn set_model_id(&mut self, model: u8) {
+        self.set_base_model_id(model & 0xF);
+        self.set_extended_model_id(model >> 4);
+    }
+
+    set_bits_fn!(set_extended_family_id, extended_family_id, eax, 20, 27, u8);
+    set_bits_fn!(set_extended_model_id, extended_model_id, eax, 16, 19, u8);
+    set_bits_fn!(set_base_family_id, base_family_id, eax, 8, 11, u8);
+    set_bits_fn!(set_base_model_id, base_model_id, eax, 4, 7, u8);
+    set_bits_fn!(set_stepping_id, stepping_id, eax, 0, 3, u8);
+    set_bits_fn!(set_brand_index, brand_index, ebx, 0, 7, u8);
+    set_bits_fn!(
+        set_cflush_cache_line_size,
+        cflush_cache_line_size,
+        ebx,
+        8,
+        15,
+        u8
+    );
+    set_bits_fn!(
+        set_initial_local_apic_id,
+        initial_local_apic_id,
+        ebx,
+        24,
+        31,
+        u8
+    );
+    set_bits_fn!(
+        set_max_logical_processor_ids,
+        max_logical_processor_ids,
+        ebx,
+        16,
+        23,
+        u8
+    );
+    set_flag!(set_sse3, has_sse3, edx_ecx, FeatureInfoFlags::SSE3);
+    set_flag!(
+        set_pclmulqdq,
+        has_pclmulqdq,
+        edx_ecx,
+        FeatureInfoFlags::PCLMULQDQ
+    );
+    set_flag!(set_ds_area, has_ds_area, edx_ecx, FeatureInfoFlags::DTES64);
+    set_flag!(
+        set_monitor_mwait,
+        has_monitor_mwait,
+        edx_ecx,
+        FeatureInfoFlags::MONITOR
+    );
+    set_flag!(set_cpl, has_cpl, edx_ecx, FeatureInfoFlags::DSCPL);
+    set_flag!(set_vmx, has_vmx, edx_ecx, FeatureInfoFlags::VMX);
+    set_flag!(set_smx, has_smx, edx_ecx, FeatureInfoFlags::SMX);
+    set_flag!(set_eist, has_eist, edx_ecx, FeatureInfoFlags::EIST);
+    set_flag!(set_tm2, has_tm2, edx_ecx, FeatureInfoFlags::TM2);
+    set_flag!(set_ssse3, has_ssse3, edx_ecx, FeatureInfoFlags::SSSE3);
+    set_flag!(set_cnxtid, has_cnxtid, edx_ecx, FeatureInfoFlags::CNXTID);
+    set_flag!(set_fma, has_fma, edx_ecx, FeatureInfoFlags::FMA);
+    set_flag!(
+        set_cmpxchg16b,
+        has_cmpxchg16b,
+        edx_ecx,
+        FeatureInfoFlags::CMPXCHG16B
+    );
+    set_flag!(set_pdcm, has_pdcm, edx_ecx, FeatureInfoFlags::PDCM);
+    set_flag!(set_pcid, has_pcid, edx_ecx, FeatureInfoFlags::PCID);
+    set_flag!(set_dca, has_dca, edx_ecx, FeatureInfoFlags::DCA);
+    set_flag!(set_sse41, has_sse41, edx_ecx, FeatureInfoFlags::SSE41);
+    set_flag!(set_sse42, has_sse42, edx_ecx, FeatureInfoFlags::SSE42);
+    set_flag!(set_x2apic, has_x2apic, edx_ecx, FeatureInfoFlags::X2APIC);
+    set_flag!(set_movbe, has_movbe, edx_ecx, FeatureInfoFlags::MOVBE);
+    set_flag!(set_popcnt, has_popcnt, edx_ecx, FeatureInfoFlags::POPCNT);
+    set_flag!(
+        set_tsc_deadline,
+        has_tsc_deadline,
+        edx_ecx,
+        FeatureInfoFlags::TSC_DEADLINE
+    );
+    set_flag!(set_aesni, has_aesni, edx_ecx, FeatureInfoFlags::AESNI);
+    set_flag!(set_xsave, has_xsave, edx_ecx, FeatureInfoFlags::XSAVE);
+    set_flag!(set_oxsave, has_oxsave, edx_ecx, FeatureInfoFlags::OSXSAVE);
+    set_flag!(set_avx, has_avx, edx_ecx, FeatureInfoFlags::AVX);
+    set_flag!(set_f16c, has_f16c, edx_ecx, FeatureInfoFlags::F16C);
+    set_flag!(set_rdrand, has_rdrand, edx_ecx, FeatureInfoFlags::RDRAND);
+    set_flag!(
+        set_hypervisor,
+        has_hypervisor,
+        edx_ecx,
+        FeatureInfoFlags::HYPERVISOR
+    );
+    set_flag!(set_fpu, has_fpu, edx_ecx, FeatureInfoFlags::FPU);
+    set_flag!(set_vme, has_vme, edx_ecx, FeatureInfoFlags::VME);
+    set_flag!(set_de, has_de, edx_ecx, FeatureInfoFlags::DE);
+    set_flag!(set_pse, has_pse, edx_ecx, FeatureInfoFlags::PSE);
+    set_flag!(set_tsc, has_tsc, edx_ecx, FeatureInfoFlags::TSC);
+    set_flag!(set_msr, has_msr, edx_ecx, FeatureInfoFlags::MSR);
+    set_flag!(set_pae, has_pae, edx_ecx, FeatureInfoFlags::PAE);
+    set_flag!(set_mce, has_mce, edx_ecx, FeatureInfoFlags::MCE);
+    set_flag!(set_cmpxchg8b, has_cmpxchg8b, edx_ecx, FeatureInfoFlags::CX8);
+    set_flag!(set_apic, has_apic, edx_ecx, FeatureInfoFlags::APIC);
+    set_flag!(
+        set_sysenter_sysexit,
+        has_sysenter_sysexit,
+        edx_ecx,
+        FeatureInfoFlags::SEP
+    );
+    set_flag!(set_mtrr, has_mtrr, edx_ecx, FeatureInfoFlags::MTRR);
+    set_flag!(set_pge, has_pge, edx_ecx, FeatureInfoFlags::PGE);
+    set_flag!(set_mca, has_mca, edx_ecx, FeatureInfoFlags::MCA);
+    set_flag!(set_cmov, has_cmov, edx_ecx, FeatureInfoFlags::CMOV);
+    set_flag!(set_pat, has_pat, edx_ecx, FeatureInfoFlags::PAT);
+    set_flag!(set_pse36, has_pse36, edx_ecx, FeatureInfoFlags::PSE36);
+    set_flag!(set_psn, has_psn, edx_ecx, FeatureInfoFlags::PSN);
+    set_flag!(set_clflush, has_clflush, edx_ecx, FeatureInfoFlags::CLFSH);
+    set_flag!(set_ds, has_ds, edx_ecx, FeatureInfoFlags::DS);
+    set_flag!(set_acpi, has_acpi, edx_ecx, FeatureInfoFlags::ACPI);
+    set_flag!(set_mmx, has_mmx, edx_ecx, FeatureInfoFlags::MMX);
+    set_flag!(
+        set_fxsave_fxstor,
+        has_fxsave_fxstor,
+        edx_ecx,
+        FeatureInfoFlags::FXSR
+    );
+    set_flag!(set_sse, has_sse, edx_ecx, FeatureInfoFlags::SSE);
+    set_flag!(set_sse2, has_sse2, edx_ecx, FeatureInfoFlags::SSE2);
+    set_flag!(set_ss, has_ss, edx_ecx, FeatureInfoFlags::SS);
+    set_flag!(set_htt, has_htt, edx_ecx, FeatureInfoFlags::HTT);
+    set_flag!(set_tm, has_tm, edx_ecx, FeatureInfoFlags::TM);
+    set_flag!(set_pbe, has_pbe, edx_ecx, FeatureInfoFlags::PBE);
+}
+
+impl Debug for FeatureInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FeatureInfo")
+            .field("extended_family_id", &self.extended_family_id())
+            .field("extended_model_id", &self.extended_model_id())
+            .field("family_id", &self.family_id())
+            .field("model_id", &self.model_id())
+            .field("stepping_id", &self.stepping_id())
+            .field("brand_index", &self.brand_index())
+            .field("cflush_cache_line_size", &self.cflush_cache_line_size())
+            .field("initial_local_apic_id", &self.initial_local_apic_id())
+            .field(
+                "max_logical_processor_ids",
+                &self.max_logical_processor_ids(),
+            )
+            .field("edx_ecx", &self.edx_ecx)
+            .finish()
+    }
+}
+
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    struct FeatureInfoFlags: u64 {
+
+        // ECX flags
+
+        /// Streaming SIMD Extensions 3 (SSE3). A value of 1 indicates the processor supports this technology.
+        const SSE3 = 1 << 0;
+        /// PCLMULQDQ. A value of 1 indicates the processor supports the PCLMULQDQ instruction
+        const PCLMULQDQ = 1 << 1;
+        /// 64-bit DS Area. A value of 1 indicates the processor supports DS area using 64-bit layout
+        const DTES64 = 1 << 2;
+        /// MONITOR/MWAIT. A value of 1 indicates the processor supports this feature.
+        const MONITOR = 1 << 3;
+        /// CPL Qualified Debug Store. A value of 1 indicates the processor supports the extensions to the  Debug Store feature to allow for branch message storage qualified by CPL.
+        const DSCPL = 1 << 4;
+        /// Virtual Machine Extensions. A value of 1 indicates that the processor supports this technology.
+        const VMX = 1 << 5;
+        /// Safer Mode Extensions. A value of 1 indicates that the processor supports this technology. See Chapter 5, Safer Mode Extensions Reference.
+        const SMX = 1 << 6;
+        /// Enhanced Intel SpeedStep® technology. A value of 1 indicates that the processor supports this technology.
+        const EIST = 1 << 7;
+        /// Thermal Monitor 2. A value of 1 indicates whether the processor supports this technology.
+        const TM2 = 1 << 8;
+        /// A value of 1 indicates the presence of the Supplemental Streaming SIMD Extensions 3 (SSSE3). A value of 0 indicates the instruction extensions are not present in the processor
+        const SSSE3 = 1 << 9;
+        /// L1 Context ID. A value of 1 indicates the L1 data cache mode can be set to either adaptive mode or shared mode. A value of 0 indicates this feature is not supported. See definition of the IA32_MISC_ENABLE MSR Bit 24 (L1 Data Cache Context Mode) for details.
+        const CNXTID = 1 << 10;
+        /// A value of 1 indicates the processor supports FMA extensions using YMM state.
+        const FMA = 1 << 12;
+        /// CMPXCHG16B Available. A value of 1 indicates that the feature is available. See the CMPXCHG8B/CMPXCHG16B Compare and Exchange Bytes section. 14
+        const CMPXCHG16B = 1 << 13;
+        /// Perfmon and Debug Capability: A value of 1 indicates the processor supports the performance   and debug feature indication MSR IA32_PERF_CAPABILITIES.
+        const PDCM = 1 << 15;
+        /// Process-context identifiers. A value of 1 indicates that the processor supports PCIDs and the software may set CR4.PCIDE to 1.
+        const PCID = 1 << 17;
+        /// A value of 1 indicates the processor supports the ability to prefetch data from a memory mapped device.
+        const DCA = 1 << 18;
+        /// A value of 1 indicates that the processor supports SSE4.1.
+        const SSE41 = 1 << 19;
+        /// A value of 1 indicates that the processor supports SSE4.2.
+        const SSE42 = 1 << 20;
+        /// A value of 1 indicates that the processor supports x2APIC feature.
+        const X2APIC = 1 << 21;
+        /// A value of 1 indicates that the processor supports MOVBE instruction.
+        const MOVBE = 1 << 22;
+        /// A value of 1 indicates that the processor supports the POPCNT instruction.
+        const POPCNT = 1 << 23;
+        /// A value of 1 indicates that the processors local APIC timer supports one-shot operation using a TSC deadline value.
+        const TSC_DEADLINE = 1 << 24;
+        /// A value of 1 indicates that the processor supports the AESNI instruction extensions.
+        const AESNI = 1 << 25;
+        /// A value of 1 indicates that the processor supports the XSAVE/XRSTOR processor extended states feature, the XSETBV/XGETBV instructions, and XCR0.
+        const XSAVE = 1 << 26;
+        /// A value of 1 indicates that the OS has enabled XSETBV/XGETBV instructions to access XCR0, and support for processor extended state management using XSAVE/XRSTOR.
+        const OSXSAVE = 1 << 27;
+        /// A value of 1 indicates the processor supports the AVX instruction extensions.
+        const AVX = 1 << 28;
+        /// A value of 1 indicates that processor supports 16-bit floating-point conversion instructions.
+        const F16C = 1 << 29;
+        /// A value of 1 indicates that processor supports RDRAND instruction.
+        const RDRAND = 1 << 30;
+        /// A value of 1 indicates the indicates the presence of a hypervisor.
+        const HYPERVISOR = 1 << 31;
+
+
+        // EDX flags
+
+        /// Floating Point Unit On-Chip. The processor contains an x87 FPU.
+        const FPU = 1 << 32;
+        /// Virtual 8086 Mode Enhancements. Virtual 8086 mode enhancements, including CR4.VME for controlling the feature, CR4.PVI for protected mode virtual interrupts, software interrupt indirection, expansion of the TSS with the software indirection bitmap, and EFLAGS.VIF and EFLAGS.VIP flags.
+        const VME = 1 << (32 + 1);
+        /// Debugging Extensions. Support for I/O breakpoints, including CR4.DE for controlling the feature, and optional trapping of accesses to DR4 and DR5.
+        const DE = 1 << (32 + 2);
+        /// Page Size Extension. Large pages of size 4 MByte are supported, including CR4.PSE for controlling the feature, the defined dirty bit in PDE (Page Directory Entries), optional reserved bit trapping in CR3, PDEs, and PTEs.
+        const PSE = 1 << (32 + 3);
+        /// Time Stamp Counter. The RDTSC instruction is supported, including CR4.TSD for controlling privilege.
+        const TSC = 1 << (32 + 4);
+        /// Model Specific Registers RDMSR and WRMSR Instructions. The RDMSR and WRMSR instructions are supported. Some of the MSRs are implementation dependent.
+        const MSR = 1 << (32 + 5);
+        /// Physical Address Extension. Physical addresses greater than 32 bits are supported: extended page table entry formats, an extra level in the page translation tables is defined, 2-MByte pages are supported instead of 4 Mbyte pages if PAE bit is 1.
+        const PAE = 1 << (32 + 6);
+        /// Machine Check Exception. Exception 18 is defined for Machine Checks, including CR4.MCE for controlling the feature. This feature does not define the model-specific implementations of machine-check error logging, reporting, and processor shutdowns. Machine Check exception handlers may have to depend on processor version to do model specific processing of the exception, or test for the presence of the Machine Check feature.
+        const MCE = 1 << (32 + 7);
+        /// CMPXCHG8B Instruction. The compare-and-exchange 8 bytes (64 bits) instruction is supported (implicitly locked and atomic).
+        const CX8 = 1 << (32 + 8);
+        /// APIC On-Chip. The processor contains an Advanced Programmable Interrupt Controller (APIC), responding to memory mapped commands in the physical address range FFFE0000H to FFFE0FFFH (by default - some processors permit the APIC to be relocated).
+        const APIC = 1 << (32 + 9);
+        /// SYSENTER and SYSEXIT Instructions. The SYSENTER and SYSEXIT and associated MSRs are supported.
+        const SEP = 1 << (32 + 11);
+        /// Memory Type Range Registers. MTRRs are supported. The MTRRcap MSR contains feature bits that describe what memory types are supported, how many variable MTRRs are supported, and whether fixed MTRRs are supported.
+        const MTRR = 1 << (32 + 12);
+        /// Page Global Bit. The global bit is supported in paging-structure entries that map a page, indicating TLB entries that are common to different processes and need not be flushed. The CR4.PGE bit controls this feature.
+        const PGE = 1 << (32 + 13);
+        /// Machine Check Architecture. The Machine Check exArchitecture, which provides a compatible mechanism for error reporting in P6 family, Pentium 4, Intel Xeon processors, and future processors, is supported. The MCG_CAP MSR contains feature bits describing how many banks of error reporting MSRs are supported.
+        const MCA = 1 << (32 + 14);
+        /// Conditional Move Instructions. The conditional move instruction CMOV is supported. In addition, if x87 FPU is present as indicated by the CPUID.FPU feature bit, then the FCOMI and FCMOV instructions are supported
+        const CMOV = 1 << (32 + 15);
+        /// Page Attribute Table. Page Attribute Table is supported. This feature augments the Memory Type Range Registers (MTRRs), allowing an operating system to specify attributes of memory accessed through a linear address on a 4KB granularity.
+        const PAT = 1 << (32 + 16);
+        /// 36-Bit Page Size Extension. 4-MByte pages addressing physical memory beyond 4 GBytes are supported with 32-bit paging. This feature indicates that upper bits of the physical address of a 4-MByte page are encoded in bits 20:13 of the page-directory entry. Such physical addresses are limited by MAXPHYADDR and may be up to 40 bits in size.
+        const PSE36 = 1 << (32 + 17);
+        /// Processor Serial Number. The processor supports the 96-bit processor identification number feature and the feature is enabled.
+        const PSN = 1 << (32 + 18);
+        /// CLFLUSH Instruction. CLFLUSH Instruction is supported.
+        const CLFSH = 1 << (32 + 19);
+        /// Debug Store. The processor supports the ability to write debug information into a memory resident buffer. This feature is used by the branch trace store (BTS) and precise event-based sampling (PEBS) facilities (see Chapter 23, Introduction to Virtual-Machine Extensions, in the Intel® 64 and IA-32 Architectures Software Developers Manual, Volume 3C).
+        const DS = 1 << (32 + 21);
+        /// Thermal Monitor and Software Controlled Clock Facilities. The processor implements internal MSRs that allow processor temperature to be monitored and processor performance to be modulated in predefined duty cycles under software control.
+        const ACPI = 1 << (32 + 22);
+        /// Intel MMX Technology. The processor supports the Intel MMX technology.
+        const MMX = 1 << (32 + 23);
+        /// FXSAVE and FXRSTOR Instructions. The FXSAVE and FXRSTOR instructions are supported for fast save and restore of the floating point context. Presence of this bit also indicates that CR4.OSFXSR is available for an operating system to indicate that it supports the FXSAVE and FXRSTOR instructions.
+        const FXSR = 1 << (32 + 24);
+        /// SSE. The processor supports the SSE extensions.
+        const SSE = 1 << (32 + 25);
+        /// SSE2. The processor supports the SSE2 extensions.
+        const SSE2 = 1 << (32 + 26);
+        /// Self Snoop. The processor supports the management of conflicting memory types by performing a snoop of its own cache structure for transactions issued to the bus.
+        const SS = 1 << (32 + 27);
+        /// Max APIC IDs reserved field is Valid. A value of 0 for HTT indicates there is only a single logical processor in the package and software should assume only a single APIC ID is reserved.  A value of 1 for HTT indicates the value in CPUID.1.EBX[23:16] (the Maximum number of addressable IDs for logical processors in this package) is valid for the package.
+        const HTT = 1 << (32 + 28);
+        /// Thermal Monitor. The processor implements the thermal monitor automatic thermal control circuitry (TCC).
+        const TM = 1 << (32 + 29);
+        /// Pending Break Enable. The processor supports the use of the FERR#/PBE# pin when the processor is in the stop-clock state (STPCLK# is asserted) to signal the processor that an interrupt is pending and that the processor should return to normal operation to handle the interrupt. Bit 10 (PBE enable) in the IA32_MISC_ENABLE MSR enables this capability.
+        const PBE = 1 << (32 + 31);
+    }
+}
+
+/// Iterator over caches (LEAF=0x04 or LEAF=0x8000_001D).
+///
+/// Yields a [CacheParameter] for each cache.
+///
+/// # Platforms
+/// ✅ AMD ✅ Intel
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CacheParametersIter<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    subleafs: Subleafs,
+    current: u32,
+    end: u32,
+}
+
+impl<R: CpuIdReader> CacheParametersIter<R> {
+    fn new(read: R, leaf: u32) -> Self {
+        let subleafs = Subleafs::read(&read, leaf, 0, |res| {
+            !matches!(
+                CacheParameter::from_registers(res).cache_type(),
+                CacheType::Null | CacheType::Reserved
+            )
+        });
+        Self {
+            read,
+            current: 0,
+            end: subleafs.end,
+            subleafs,
+        }
+    }
+
+    /// The leaf the caches are read from: 0x04 (Intel) or 0x8000_001D (AMD).
+    pub fn leaf(&self) -> u32 {
+        self.subleafs.leaf
+    }
+}
+
+impl<R: CpuIdReader> Iterator for CacheParametersIter<R> {
+    type Item = CacheParameter;
+
+    /// Iterate over all cache info subleafs for this CPU.
+    ///
+    /// # Note
+    /// The subleafs are read when the iterator is created, advancing it
+    /// doesn't call cpuid.
+    fn next(&mut self) -> Option<CacheParameter> {
+        if self.current >= self.end {
+            return None;
+        }
+        let res = self.subleafs.get(&self.read, self.current);
+        self.current += 1;
+        Some(CacheParameter::from_registers(res))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.current) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<R: CpuIdReader> DoubleEndedIterator for CacheParametersIter<R> {
+    fn next_back(&mut self) -> Option<CacheParameter> {
+        if self.current >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(CacheParameter::from_registers(
+            self.subleafs.get(&self.read, self.end),
+        ))
+    }
+}
+
+impl<R: CpuIdReader> ExactSizeIterator for CacheParametersIter<R> {}
+
+impl<R: CpuIdReader> FusedIterator for CacheParametersIter<R> {}
+
+impl<R: CpuIdReader> Debug for CacheParametersIter<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut debug = f.debug_list();
+        self.clone().for_each(|ref item| {
+            debug.entry(item);
+        });
+        debug.finish()
+    }
+}
+
+/// Information about an individual cache in the hierarchy.
+///
+/// On AMD some fields are reserved and read as zero, e.g.,
+/// [`CacheParameter::max_cores_for_package`] is always 1.
+///
+/// # Platforms
+/// ✅ AMD ✅ Intel
+#[derive(Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CacheParameter {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+    edx: u32,
+}
+
+/// Info about a what a given cache caches (instructions, data, etc.)
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CacheType {
+    /// Null - No more caches
+    Null = 0,
+    /// Data cache
+    Data,
+    /// Instruction cache
+    Instruction,
+    /// Data and Instruction cache
+    Unified,
+    /// 4-31 = Reserved
+    Reserved,
+}
+
+impl fmt::Display for CacheType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let typ = match self {
+            CacheType::Null => "Null",
+            CacheType::Data => "Data",
+            CacheType::Instruction => "Instruction",
+            CacheType::Unified => "Unified",
+            CacheType::Reserved => "Reserved",
+        };
+
+        f.write_str(typ)
+    }
+}
+
+impl CacheParameter {
+    /// Decodes the registers of a subleaf of LEAF=0x04 (or LEAF=0x8000_001D).
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+            edx: data.edx,
+        }
+    }
+
+    /// Cache Type
+    pub fn cache_type(&self) -> CacheType {
+        let typ = get_bits(self.eax, 0, 4) as u8;
+        match typ {
+            0 => CacheType::Null,
+            1 => CacheType::Data,
+            2 => CacheType::Instruction,
+            3 => CacheType::Unified,
+            _ => CacheType::Reserved,
+        }
+    }
+
+    /// Cache Level (starts at 1)
+    pub fn level(&self) -> u8 {
+        get_bits(self.eax, 5, 7) as u8
+    }
+
+    /// Self Initializing cache level (does not need SW initialization).
+    pub fn is_self_initializing(&self) -> bool {
+        get_bits(self.eax, 8, 8) == 1
+    }
+
+    /// Fully Associative cache
+    pub fn is_fully_associative(&self) -> bool {
+        get_bits(self.eax, 9, 9) == 1
+    }
+
+    /// Maximum number of addressable IDs for logical processors sharing this cache
+    pub fn max_cores_for_cache(&self) -> usize {
+        (get_bits(self.eax, 14, 25) + 1) as usize
+    }
+
+    /// Maximum number of addressable IDs for processor cores in the physical package
+    pub fn max_cores_for_package(&self) -> usize {
+        (get_bits(self.eax, 26, 31) + 1) as usize
+    }
+
+    /// Number of bits to shift an APIC ID right to get the ID of the cache
+    /// instance it uses.
+    ///
+    /// Derived from [`CacheParameter::max_cores_for_cache`].
+    pub fn apic_id_shift(&self) -> u32 {
+        (self.max_cores_for_cache() as u32)
+            .next_power_of_two()
+            .trailing_zeros()
+    }
+
+    /// ID of the cache instance used by the logical processor with the given
+    /// APIC ID.
+    ///
+    /// Logical processors with the same cache ID share this cache.
+    pub fn cache_id(&self, apic_id: u32) -> u32 {
+        apic_id >> self.apic_id_shift()
+    }
+
+    /// System Coherency Line Size (Bits 11-00)
+    pub fn coherency_line_size(&self) -> usize {
+        (get_bits(self.ebx, 0, 11) + 1) as usize
+    }
+
+    /// Physical Line partitions (Bits 21-12)
+    pub fn physical_line_partitions(&self) -> usize {
+        (get_bits(self.ebx, 12, 21) + 1) as usize
+    }
+
+    /// Ways of associativity (Bits 31-22)
+    pub fn ways(&self) -> usize {
+        (get_bits(self.ebx, 22, 31) + 1) as usize
+    }
+
+    /// Associativity, combining [`Self::ways`] and
+    /// [`Self::is_fully_associative`].
+    pub fn associativity(&self) -> Associativity {
+        Associativity::for_ways(self.ways(), self.is_fully_associative())
+    }
+
+    /// Number of Sets (Bits 31-00)
+    pub fn sets(&self) -> usize {
+        (self.ecx + 1) as usize
+    }
+
+    /// Size of the cache in bytes (ways × partitions × line size × sets).
+    pub fn size(&self) -> usize {
+        self.ways() * self.physical_line_partitions() * self.coherency_line_size() * self.sets()
+    }
+
+    /// Summary of the cache, which displays as e.g.
+    /// `L2 Unified, 1 MiB, 16-way, 64 B lines, shared by 2 threads`.
+    pub fn description(&self) -> CacheDescription {
+        CacheDescription {
+            level: self.level(),
+            cache_type: self.cache_type(),
+            size: self.size() as u64,
+            associativity: self.associativity(),
+            line_size: self.coherency_line_size() as u16,
+            shared_by: Some(self.max_cores_for_cache()),
+        }
+    }
+
+    /// Write-Back Invalidate/Invalidate (Bit 0)
+    /// False: WBINVD/INVD from threads sharing this cache acts upon lower level caches for threads sharing this cache.
+    /// True: WBINVD/INVD is not guaranteed to act upon lower level caches of non-originating threads sharing this cache.
+    pub fn is_write_back_invalidate(&self) -> bool {
+        get_bits(self.edx, 0, 0) == 1
+    }
+
+    /// Cache Inclusiveness (Bit 1)
+    /// False: Cache is not inclusive of lower cache levels.
+    /// True: Cache is inclusive of lower cache levels.
+    pub fn is_inclusive(&self) -> bool {
+        get_bits(self.edx, 1, 1) == 1
+    }
+
+    /// Complex Cache Indexing (Bit 2)
+    /// False: Direct mapped cache.
+    /// True: A complex function is used to index the cache, potentially using all address bits.
+    pub fn has_complex_indexing(&self) -> bool {
+        get_bits(self.edx, 2, 2) == 1
+    }
+
+    /// Encode this cache back into the registers of its leaf 4 subleaf.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
+
+    /// Set the type reported by [`Self::cache_type`].
+    pub fn set_cache_type(&mut self, typ: CacheType) {
+        let typ = match typ {
+            CacheType::Null => 0,
+            CacheType::Data => 1,
+            CacheType::Instruction => 2,
+            CacheType::Unified => 3,
+            CacheType::Reserved => 4,
+        };
+        set_bits(&mut self.eax, 0, 4, typ);
+    }
+
+    set_bits_fn!(set_level, level, eax, 5, 7, u8);
+    set_bits_fn!(set_self_initializing, is_self_initializing, eax, 8, 8, bool);
+    set_bits_fn!(set_fully_associative, is_fully_associative, eax, 9, 9, bool);
+    set_count_fn!(
+        set_max_cores_for_cache,
+        max_cores_for_cache,
+        eax,
+        14,
+        25,
+        usize
+    );
+    set_count_fn!(
+        set_max_cores_for_package,
+        max_cores_for_package,
+        eax,
+        26,
+        31,
+        usize
+    );
+    set_count_fn!(
+        set_coherency_line_size,
+        coherency_line_size,
+        ebx,
+        0,
+        11,
+        usize
+    );
+    set_count_fn!(
+        set_physical_line_partitions,
+        physical_line_partitions,
+        ebx,
+        12,
+        21,
+        usize
+    );
+    set_count_fn!(set_ways, ways, ebx, 22, 31, usize);
+    set_bits_fn!(
+        set_write_back_invalidate,
+        is_write_back_invalidate,
+        edx,
+        0,
+        0,
+        bool
+    );
+    set_bits_fn!(set_inclusive, is_inclusive, edx, 1, 1, bool);
+    set_bits_fn!(set_complex_indexing, has_complex_indexing, edx, 2, 2, bool);
+    set_count_fn!(set_sets, sets, ecx, 0, 31, usize);
+}
+
+impl Debug for CacheParameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CacheParameter")
+            .field("cache_type", &self.cache_type())
+            .field("level", &self.level())
+            .field("is_self_initializing", &self.is_self_initializing())
+            .field("is_fully_associative", &self.is_fully_associative())
+            .field("max_cores_for_cache", &self.max_cores_for_cache())
+            .field("max_cores_for_package", &self.max_cores_for_package())
+            .field("coherency_line_size", &self.coherency_line_size())
+            .field("physical_line_partitions", &self.physical_line_partitions())
+            .field("ways", &self.ways())
+            .field("sets", &self.sets())
+            .field("is_write_back_invalidate", &self.is_write_back_invalidate())
+            .field("is_inclusive", &self.is_inclusive())
+            .field("has_complex_indexing", &self.has_complex_indexing())
+            .finish()
+    }
+}
+
+/// Summary of a cache, see [`CacheParameter::description`] and the
+/// `*_description` methods of [`L1CacheTlbInfo`] and [`L2And3CacheTlbInfo`].
+///
+/// Displays as e.g. `L2 Unified, 1 MiB, 16-way, 64 B lines, shared by 2 threads`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CacheDescription {
+    /// Cache level (starts at 1).
+    pub level: u8,
+    pub cache_type: CacheType,
+    /// Size in bytes.
+    pub size: u64,
+    pub associativity: Associativity,
+    /// Line size in bytes.
+    pub line_size: u16,
+    /// Maximum number of logical processors sharing the cache, if known.
+    pub shared_by: Option<usize>,
+}
+
+impl fmt::Display for CacheDescription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "L{} {}, {}, ",
+            self.level,
+            self.cache_type,
+            ByteSize(self.size)
+        )?;
+        match self.associativity {
+            Associativity::Disabled => f.write_str("disabled")?,
+            Associativity::Direct => f.write_str("direct mapped")?,
+            Associativity::NWay(n) => write!(f, "{}-way", n)?,
+            Associativity::Full => f.write_str("fully associative")?,
+            Associativity::Unknown => f.write_str("unknown associativity")?,
+        }
+        write!(f, ", {} B lines", self.line_size)?;
+        match self.shared_by {
+            Some(1) => f.write_str(", shared by 1 thread"),
+            Some(n) => write!(f, ", shared by {} threads", n),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Displays a size in bytes with the largest binary unit that represents it
+/// exactly (e.g., `32 KiB`).
+pub(crate) struct ByteSize(pub(crate) u64);
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+        let mut bytes = self.0;
+        let mut unit = 0;
+        while unit < UNITS.len() - 1 && bytes >= 1024 && bytes.trailing_zeros() >= 10 {
+            bytes >>= 10;
+            unit += 1;
+        }
+        write!(f, "{} {}", bytes, UNITS[unit])
+    }
+}
+
+/// Iterator over the caches of the current logical processor and which other
+/// logical processors share them (LEAF=0x04 or LEAF=0x8000_001D).
+///
+/// Yields a [CacheSharing] for each cache.
+///
+/// # Platforms
+/// ✅ AMD ✅ Intel
+#[derive(Clone)]
+pub struct CacheSharingIter<R: CpuIdReader = CpuIdReaderNative> {
+    caches: CacheParametersIter<R>,
+    apic_id: u32,
+}
+
+impl<R: CpuIdReader> Iterator for CacheSharingIter<R> {
+    type Item = CacheSharing;
+
+    fn next(&mut self) -> Option<CacheSharing> {
+        self.caches.next().map(|cache| CacheSharing {
+            cache,
+            apic_id: self.apic_id,
+        })
+    }
+}
+
+impl<R: CpuIdReader> Debug for CacheSharingIter<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut debug = f.debug_list();
+        self.clone().for_each(|ref item| {
+            debug.entry(item);
+        });
+        debug.finish()
+    }
+}
+
+/// Describes the set of logical processors that share a cache with a given
+/// logical processor.
+///
+/// # Platforms
+/// ✅ AMD ✅ Intel
+#[derive(Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CacheSharing {
+    cache: CacheParameter,
+    apic_id: u32,
+}
+
+impl CacheSharing {
+    /// Decodes the registers of a subleaf of LEAF=0x04 (or LEAF=0x8000_001D) for the logical processor with `apic_id`.
+    pub fn from_registers(data: CpuIdResult, apic_id: u32) -> Self {
+        Self {
+            cache: CacheParameter::from_registers(data),
+            apic_id,
+        }
+    }
+
+    /// The parameters of the shared cache.
+    pub fn cache(&self) -> &CacheParameter {
+        &self.cache
+    }
+
+    /// Cache Level (starts at 1)
+    pub fn level(&self) -> u8 {
+        self.cache.level()
+    }
+
+    /// Cache Type
+    pub fn cache_type(&self) -> CacheType {
+        self.cache.cache_type()
+    }
+
+    /// APIC ID of the logical processor this information was gathered for.
+    pub fn apic_id(&self) -> u32 {
+        self.apic_id
+    }
+
+    /// ID of the cache instance, unique among caches of the same level and
+    /// type in the system.
+    pub fn cache_id(&self) -> u32 {
+        self.cache.cache_id(self.apic_id)
+    }
+
+    /// Range of APIC IDs of all logical processors that can share this cache.
+    ///
+    /// # Note
+    /// The range covers all addressable IDs, not every ID in it needs to
+    /// belong to a logical processor that is present in the system.
+    pub fn apic_ids(&self) -> RangeInclusive<u32> {
+        let shift = self.cache.apic_id_shift();
+        let start = self.cache_id() << shift;
+        // Inclusive so the last cache of the 32-bit x2APIC ID space fits:
+        start..=start | ((1 << shift) - 1)
+    }
+
+    /// Does the logical processor with the given APIC ID share this cache?
+    pub fn is_shared_with(&self, apic_id: u32) -> bool {
+        self.cache.cache_id(apic_id) == self.cache_id()
+    }
+}
+
+impl Debug for CacheSharing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CacheSharing")
+            .field("level", &self.level())
+            .field("cache_type", &self.cache_type())
+            .field("cache_id", &self.cache_id())
+            .field("apic_ids", &self.apic_ids())
+            .finish()
+    }
+}
+
+/// Information about how monitor/mwait works on this CPU (LEAF=0x05).
+///
+/// # Platforms
+/// 🟡 AMD ✅ Intel
+#[derive(Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MonitorMwaitInfo {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+    edx: u32,
+}
+
+impl MonitorMwaitInfo {
+    /// Decodes the registers of LEAF=0x05.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+            edx: data.edx,
+        }
+    }
+
+    /// Smallest monitor-line size in bytes (default is processor's monitor granularity)
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn smallest_monitor_line(&self) -> u16 {
+        get_bits(self.eax, 0, 15) as u16
+    }
+
+    /// Largest monitor-line size in bytes (default is processor's monitor granularity
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn largest_monitor_line(&self) -> u16 {
+        get_bits(self.ebx, 0, 15) as u16
+    }
+
+    ///  Enumeration of Monitor-Mwait extensions (beyond EAX and EBX registers) supported
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn extensions_supported(&self) -> bool {
+        get_bits(self.ecx, 0, 0) == 1
+    }
+
+    ///  Supports treating interrupts as break-event for MWAIT, even when interrupts disabled
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn interrupts_as_break_event(&self) -> bool {
+        get_bits(self.ecx, 1, 1) == 1
+    }
+
+    /// Number of C0 sub C-states supported using MWAIT (Bits 03 - 00)
+    ///
+    /// # Platforms
+    /// ❌ AMD (undefined/reserved) ✅ Intel
+    pub fn supported_c0_states(&self) -> u16 {
+        get_bits(self.edx, 0, 3) as u16
+    }
+
+    /// Number of C1 sub C-states supported using MWAIT (Bits 07 - 04)
+    ///
+    /// # Platforms
+    /// ❌ AMD (undefined/reserved) ✅ Intel
+    pub fn supported_c1_states(&self) -> u16 {
+        get_bits(self.edx, 4, 7) as u16
+    }
+
+    /// Number of C2 sub C-states supported using MWAIT (Bits 11 - 08)
+    ///
+    /// # Platforms
+    /// ❌ AMD (undefined/reserved) ✅ Intel
+    pub fn supported_c2_states(&self) -> u16 {
+        get_bits(self.edx, 8, 11) as u16
+    }
+
+    /// Number of C3 sub C-states supported using MWAIT (Bits 15 - 12)
+    ///
+    /// # Platforms
+    /// ❌ AMD (undefined/reserved) ✅ Intel
+    pub fn supported_c3_states(&self) -> u16 {
+        get_bits(self.edx, 12, 15) as u16
+    }
+
+    /// Number of C4 sub C-states supported using MWAIT (Bits 19 - 16)
+    ///
+    /// # Platforms
+    /// ❌ AMD (undefined/reserved) ✅ Intel
+    pub fn supported_c4_states(&self) -> u16 {
+        get_bits(self.edx, 16, 19) as u16
+    }
+
+    /// Number of C5 sub C-states supported using MWAIT (Bits 23 - 20)
+    ///
+    /// # Platforms
+    /// ❌ AMD (undefined/reserved) ✅ Intel
+    pub fn supported_c5_states(&self) -> u16 {
+        get_bits(self.edx, 20, 23) as u16
+    }
+
+    /// Number of C6 sub C-states supported using MWAIT (Bits 27 - 24)
+    ///
+    /// # Platforms
+    /// ❌ AMD (undefined/reserved) ✅ Intel
+    pub fn supported_c6_states(&self) -> u16 {
+        get_bits(self.edx, 24, 27) as u16
+    }
+
+    /// Number of C7 sub C-states supported using MWAIT (Bits 31 - 28)
+    ///
+    /// # Platforms
+    /// ❌ AMD (undefined/reserved) ✅ Intel
+    pub fn supported_c7_states(&self) -> u16 {
+        get_bits(self.edx, 28, 31) as u16
+    }
+
+    /// Encode this struct back into the registers of leaf 5.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
+
+    set_bits_fn!(
+        set_smallest_monitor_line,
+        smallest_monitor_line,
+        eax,
+        0,
+        15,
+        u16
+    );
+    set_bits_fn!(
+        set_largest_monitor_line,
+        largest_monitor_line,
+        ebx,
+        0,
+        15,
+        u16
+    );
+    set_bits_fn!(
+        set_extensions_supported,
+        extensions_supported,
+        ecx,
+        0,
+        0,
+        bool
+    );
+    set_bits_fn!(
+        set_interrupts_as_break_event,
+        interrupts_as_break_event,
+        ecx,
+        1,
+        1,
+        bool
+    );
+    set_bits_fn!(set_supported_c0_states, supported_c0_states, edx, 0, 3, u16);
+    set_bits_fn!(set_supported_c1_states, supported_c1_states, edx, 4, 7, u16);
+    set_bits_fn!(
+        set_supported_c2_states,
+        supported_c2_states,
+        edx,
+        8,
+        11,
+        u16
+    );
+    set_bits_fn!(
+        set_supported_c3_states,
+        supported_c3_states,
+        edx,
+        12,
+        15,
+        u16
+    );
+    set_bits_fn!(
+        set_supported_c4_states,
+        supported_c4_states,
+        edx,
+        16,
+        19,
+        u16
+    );
+    set_bits_fn!(
+        set_supported_c5_states,
+        supported_c5_states,
+        edx,
+        20,
+        23,
+        u16
+    );
+    set_bits_fn!(
+        set_supported_c6_states,
+        supported_c6_states,
+        edx,
+        24,
+        27,
+        u16
+    );
+    set_bits_fn!(
+        set_supported_c7_states,
+        supported_c7_states,
+        edx,
+        28,
+        31,
+        u16
+    );
+}
+
+impl Debug for MonitorMwaitInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MonitorMwaitInfo")
+            .field("smallest_monitor_line", &self.smallest_monitor_line())
+            .field("largest_monitor_line", &self.largest_monitor_line())
+            .field("extensions_supported", &self.extensions_supported())
+            .field(
+                "interrupts_as_break_event",
+                &self.interrupts_as_break_event(),
+            )
+            .field("supported_c0_states", &self.supported_c0_states())
+            .field("supported_c1_states", &self.supported_c1_states())
+            .field("supported_c2_states", &self.supported_c2_states())
+            .field("supported_c3_states", &self.supported_c3_states())
+            .field("supported_c4_states", &self.supported_c4_states())
+            .field("supported_c5_states", &self.supported_c5_states())
+            .field("supported_c6_states", &self.supported_c6_states())
+            .field("supported_c7_states", &self.supported_c7_states())
+            .finish()
+    }
+}
+
+/// Query information about thermal and power management features of the CPU (LEAF=0x06).
+///
+/// # Platforms
+/// 🟡 AMD ✅ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThermalPowerInfo {
+    eax: ThermalPowerFeaturesEax,
+    ebx: u32,
+    ecx: ThermalPowerFeaturesEcx,
+    edx: u32,
+}
+
+impl ThermalPowerInfo {
+    /// Decodes the registers of LEAF=0x06.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: ThermalPowerFeaturesEax { bits: data.eax },
+            ebx: data.ebx,
+            ecx: ThermalPowerFeaturesEcx { bits: data.ecx },
+            edx: data.edx,
+        }
+    }
+
+    /// Number of Interrupt Thresholds in Digital Thermal Sensor
+    ///
+    /// # Platforms
+    /// ❌ AMD (undefined/reserved) ✅ Intel
+    pub fn dts_irq_threshold(&self) -> u8 {
+        get_bits(self.ebx, 0, 3) as u8
+    }
+
+    /// Digital temperature sensor is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_dts(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::DTS)
+    }
+
+    /// Intel Turbo Boost Technology Available (see description of
+    /// IA32_MISC_ENABLE\[38\]).
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_turbo_boost(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::TURBO_BOOST)
+    }
+
+    /// ARAT. APIC-Timer-always-running feature is supported if set.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_arat(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::ARAT)
+    }
+
+    /// PLN. Power limit notification controls are supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_pln(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::PLN)
+    }
+
+    /// ECMD. Clock modulation duty cycle extension is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_ecmd(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::ECMD)
+    }
+
+    /// PTM. Package thermal management is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_ptm(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::PTM)
+    }
+
+    /// HWP. HWP base registers (IA32_PM_ENABLE[bit 0], IA32_HWP_CAPABILITIES,
+    /// IA32_HWP_REQUEST, IA32_HWP_STATUS) are supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hwp(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::HWP)
+    }
+
+    /// HWP Notification. IA32_HWP_INTERRUPT MSR is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hwp_notification(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::HWP_NOTIFICATION)
+    }
+
+    /// HWP Activity Window. IA32_HWP_REQUEST[bits 41:32] is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hwp_activity_window(&self) -> bool {
+        self.eax
+            .contains(ThermalPowerFeaturesEax::HWP_ACTIVITY_WINDOW)
+    }
+
+    /// HWP Energy Performance Preference. IA32_HWP_REQUEST[bits 31:24] is
+    /// supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hwp_energy_performance_preference(&self) -> bool {
+        self.eax
+            .contains(ThermalPowerFeaturesEax::HWP_ENERGY_PERFORMANCE_PREFERENCE)
+    }
+
+    /// HWP Package Level Request. IA32_HWP_REQUEST_PKG MSR is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hwp_package_level_request(&self) -> bool {
+        self.eax
+            .contains(ThermalPowerFeaturesEax::HWP_PACKAGE_LEVEL_REQUEST)
+    }
+
+    /// HDC. HDC base registers IA32_PKG_HDC_CTL, IA32_PM_CTL1,
+    /// IA32_THREAD_STALL MSRs are supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hdc(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::HDC)
+    }
+
+    /// Intel® Turbo Boost Max Technology 3.0 available.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_turbo_boost3(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::TURBO_BOOST_3)
+    }
+
+    /// HWP Capabilities. Highest Performance change is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hwp_capabilities(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::HWP_CAPABILITIES)
+    }
+
+    /// HWP PECI override is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hwp_peci_override(&self) -> bool {
+        self.eax
+            .contains(ThermalPowerFeaturesEax::HWP_PECI_OVERRIDE)
+    }
+
+    /// Flexible HWP is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_flexible_hwp(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::FLEXIBLE_HWP)
+    }
+
+    /// Fast access mode for the IA32_HWP_REQUEST MSR is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hwp_fast_access_mode(&self) -> bool {
+        self.eax
+            .contains(ThermalPowerFeaturesEax::HWP_REQUEST_MSR_FAST_ACCESS)
+    }
+
+    /// Ignoring Idle Logical Processor HWP request is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_ignore_idle_processor_hwp_request(&self) -> bool {
+        self.eax
+            .contains(ThermalPowerFeaturesEax::IGNORE_IDLE_PROCESSOR_HWP_REQUEST)
+    }
+
+    /// HWP Control. IA32_HWP_CTL MSR is supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hwp_control(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::HWP_CONTROL)
+    }
+
+    /// Hardware Feedback Interface (HFI). IA32_HW_FEEDBACK_PTR and
+    /// IA32_HW_FEEDBACK_CONFIG MSRs are supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hw_feedback(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::HW_FEEDBACK)
+    }
+
+    /// Intel Thread Director. IA32_HW_FEEDBACK_THREAD_CONFIG and
+    /// IA32_THREAD_FEEDBACK_CHAR MSRs are supported if set.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_thread_director(&self) -> bool {
+        self.eax.contains(ThermalPowerFeaturesEax::THREAD_DIRECTOR)
+    }
+
+    /// Bitmap of the capabilities reported in the HFI table (EDX\[7:0\]).
+    ///
+    /// Bit 0 is the performance capability and bit 1 the energy efficiency
+    /// capability. Each set bit has a column in every row of the table.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn hw_feedback_capabilities(&self) -> u8 {
+        get_bits(self.edx, 0, 7) as u8
+    }
+
+    /// Does the HFI table report the performance capability?
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hw_feedback_performance(&self) -> bool {
+        is_bit_set!(self.edx, 0)
+    }
+
+    /// Does the HFI table report the energy efficiency capability?
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hw_feedback_energy_efficiency(&self) -> bool {
+        is_bit_set!(self.edx, 1)
+    }
+
+    /// Size of the HFI table in bytes (EDX\[11:8\] + 1 4 KiB pages).
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn hw_feedback_table_size(&self) -> usize {
+        (get_bits(self.edx, 8, 11) as usize + 1) * 4096
+    }
+
+    /// Row of the HFI table that holds the capabilities of the logical
+    /// processor we're running on (EDX\[31:16\]).
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn hw_feedback_row_index(&self) -> u16 {
+        get_bits(self.edx, 16, 31) as u16
+    }
+
+    /// Number of Intel Thread Director classes (ECX\[15:8\]), i.e., the
+    /// number of capability column groups in the HFI table.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn thread_director_classes(&self) -> u8 {
+        get_bits(self.ecx.bits(), 8, 15) as u8
+    }
+
+    /// Hardware Coordination Feedback Capability
+    ///
+    /// Presence of IA32_MPERF and IA32_APERF.
+    ///
+    /// The capability to provide a measure of delivered processor performance
+    /// (since last reset of the counters), as a percentage of expected
+    /// processor performance at frequency specified in CPUID Brand String Bits
+    /// 02 - 01
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_hw_coord_feedback(&self) -> bool {
+        self.ecx
+            .contains(ThermalPowerFeaturesEcx::HW_COORD_FEEDBACK)
+    }
+
+    /// The processor supports performance-energy bias preference if
+    /// CPUID.06H:ECX.SETBH[bit 3] is set and it also implies the presence of a
+    /// new architectural MSR called IA32_ENERGY_PERF_BIAS (1B0H)
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_energy_bias_pref(&self) -> bool {
+        self.ecx.contains(ThermalPowerFeaturesEcx::ENERGY_BIAS_PREF)
+    }
+
+    /// Encode this struct back into the registers of leaf 6.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax.bits(),
+            ebx: self.ebx,
+            ecx: self.ecx.bits(),
+            edx: self.edx,
+        }
+    }
+
+    /// Bits set in EAX and ECX that this library doesn't interpret, i.e.,
+    /// reserved bits or flags it doesn't know about yet.
+    ///
+    /// EBX and EDX only contain values and always read as zero.
+    pub fn reserved_bits(&self) -> CpuIdResult {
+        let known_eax = ThermalPowerFeaturesEax::all()
+            - ThermalPowerFeaturesEax::RESERVED_3
+            - ThermalPowerFeaturesEax::RESERVED_12;
+        // ECX[15:8] is the number of Thread Director classes.
+        let known_ecx = ThermalPowerFeaturesEcx::all().bits() | 0xff00;
+        CpuIdResult {
+            eax: self.eax.bits() & !known_eax.bits(),
+            ebx: 0,
+            ecx: self.ecx.bits() & !known_ecx,
+            edx: 0,
+        }
+    }
+
+    set_bits_fn!(set_dts_irq_threshold, dts_irq_threshold, ebx, 0, 3, u8);
+    set_flag!(set_dts, has_dts, eax, ThermalPowerFeaturesEax::DTS);
+    set_flag!(
+        set_turbo_boost,
+        has_turbo_boost,
+        eax,
+        ThermalPowerFeaturesEax::TURBO_BOOST
+    );
+    set_flag!(set_arat, has_arat, eax, ThermalPowerFeaturesEax::ARAT);
+    set_flag!(set_pln, has_pln, eax, ThermalPowerFeaturesEax::PLN);
+    set_flag!(set_ecmd, has_ecmd, eax, ThermalPowerFeaturesEax::ECMD);
+    set_flag!(set_ptm, has_ptm, eax, ThermalPowerFeaturesEax::PTM);
+    set_flag!(set_hwp, has_hwp, eax, ThermalPowerFeaturesEax::HWP);
+    set_flag!(
+        set_hwp_notification,
+        has_hwp_notification,
+        eax,
+        ThermalPowerFeaturesEax::HWP_NOTIFICATION
+    );
+    set_flag!(
+        set_hwp_activity_window,
+        has_hwp_activity_window,
+        eax,
+        ThermalPowerFeaturesEax::HWP_ACTIVITY_WINDOW
+    );
+    set_flag!(
+        set_hwp_energy_performance_preference,
+        has_hwp_energy_performance_preference,
+        eax,
+        ThermalPowerFeaturesEax::HWP_ENERGY_PERFORMANCE_PREFERENCE
+    );
+    set_flag!(
+        set_hwp_package_level_request,
+        has_hwp_package_level_request,
+        eax,
+        ThermalPowerFeaturesEax::HWP_PACKAGE_LEVEL_REQUEST
+    );
+    set_flag!(set_hdc, has_hdc, eax, ThermalPowerFeaturesEax::HDC);
+    set_flag!(
+        set_turbo_boost3,
+        has_turbo_boost3,
+        eax,
+        ThermalPowerFeaturesEax::TURBO_BOOST_3
+    );
+    set_flag!(
+        set_hwp_capabilities,
+        has_hwp_capabilities,
+        eax,
+        ThermalPowerFeaturesEax::HWP_CAPABILITIES
+    );
+    set_flag!(
+        set_hwp_peci_override,
+        has_hwp_peci_override,
+        eax,
+        ThermalPowerFeaturesEax::HWP_PECI_OVERRIDE
+    );
+    set_flag!(
+        set_flexible_hwp,
+        has_flexible_hwp,
+        eax,
+        ThermalPowerFeaturesEax::FLEXIBLE_HWP
+    );
+    set_flag!(
+        set_hwp_fast_access_mode,
+        has_hwp_fast_access_mode,
+        eax,
+        ThermalPowerFeaturesEax::HWP_REQUEST_MSR_FAST_ACCESS
+    );
+    set_flag!(
+        set_ignore_idle_processor_hwp_request,
+        has_ignore_idle_processor_hwp_request,
+        eax,
+        ThermalPowerFeaturesEax::IGNORE_IDLE_PROCESSOR_HWP_REQUEST
+    );
+    set_flag!(
+        set_hwp_control,
+        has_hwp_control,
+        eax,
+        ThermalPowerFeaturesEax::HWP_CONTROL
+    );
+    set_flag!(
+        set_hw_feedback,
+        has_hw_feedback,
+        eax,
+        ThermalPowerFeaturesEax::HW_FEEDBACK
+    );
+    set_flag!(
+        set_thread_director,
+        has_thread_director,
+        eax,
+        ThermalPowerFeaturesEax::THREAD_DIRECTOR
+    );
+    set_bits_fn!(
+        set_hw_feedback_capabilities,
+        hw_feedback_capabilities,
+        edx,
+        0,
+        7,
+        u8
+    );
+    set_bits_fn!(
+        set_hw_feedback_row_index,
+        hw_feedback_row_index,
+        edx,
+        16,
+        31,
+        u16
+    );
+
+    /// Set the value reported by [`Self::hw_feedback_table_size`], rounded up
+    /// to 4 KiB pages.
+    pub fn set_hw_feedback_table_size(&mut self, bytes: usize) {
+        let pages = bytes.div_ceil(4096).max(1) as u32;
+        set_bits(&mut self.edx, 8, 11, pages - 1);
+    }
+
+    /// Set the value reported by [`Self::thread_director_classes`].
+    pub fn set_thread_director_classes(&mut self, value: u8) {
+        let mut ecx = self.ecx.bits();
+        set_bits(&mut ecx, 8, 15, value as u32);
+        self.ecx = ThermalPowerFeaturesEcx { bits: ecx };
+    }
+    set_flag!(
+        set_hw_coord_feedback,
+        has_hw_coord_feedback,
+        ecx,
+        ThermalPowerFeaturesEcx::HW_COORD_FEEDBACK
+    );
+    set_flag!(
+        set_energy_bias_pref,
+        has_energy_bias_pref,
+        ecx,
+        ThermalPowerFeaturesEcx::ENERGY_BIAS_PREF
+    );
+}
+
+impl Debug for ThermalPowerInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ThermalPowerInfo")
+            .field("dts_irq_threshold", &self.dts_irq_threshold())
+            .field("has_dts", &self.has_dts())
+            .field("has_arat", &self.has_arat())
+            .field("has_pln", &self.has_pln())
+            .field("has_ecmd", &self.has_ecmd())
+            .field("has_ptm", &self.has_ptm())
+            .field("has_hwp", &self.has_hwp())
+            .field("has_hwp_notification", &self.has_hwp_notification())
+            .field("has_hwp_activity_window", &self.has_hwp_activity_window())
+            .field(
+                "has_hwp_energy_performance_preference",
+                &self.has_hwp_energy_performance_preference(),
+            )
+            .field(
+                "has_hwp_package_level_request",
+                &self.has_hwp_package_level_request(),
+            )
+            .field("has_hdc", &self.has_hdc())
+            .field("has_turbo_boost3", &self.has_turbo_boost3())
+            .field("has_hwp_capabilities", &self.has_hwp_capabilities())
+            .field("has_hwp_peci_override", &self.has_hwp_peci_override())
+            .field("has_flexible_hwp", &self.has_flexible_hwp())
+            .field("has_hwp_fast_access_mode", &self.has_hwp_fast_access_mode())
+            .field(
+                "has_ignore_idle_processor_hwp_request",
+                &self.has_ignore_idle_processor_hwp_request(),
+            )
+            .field("has_hwp_control", &self.has_hwp_control())
+            .field("has_hw_feedback", &self.has_hw_feedback())
+            .field("has_thread_director", &self.has_thread_director())
+            .field("hw_feedback_capabilities", &self.hw_feedback_capabilities())
+            .field("hw_feedback_table_size", &self.hw_feedback_table_size())
+            .field("hw_feedback_row_index", &self.hw_feedback_row_index())
+            .field("thread_director_classes", &self.thread_director_classes())
+            .field("has_hw_coord_feedback", &self.has_hw_coord_feedback())
+            .field("has_energy_bias_pref", &self.has_energy_bias_pref())
+            .finish()
+    }
+}
+
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    struct ThermalPowerFeaturesEax: u32 {
+        /// Digital temperature sensor is supported if set. (Bit 00)
+        const DTS = 1 << 0;
+        /// Intel Turbo Boost Technology Available (see description of IA32_MISC_ENABLE[38]). (Bit 01)
+        const TURBO_BOOST = 1 << 1;
+        /// ARAT. APIC-Timer-always-running feature is supported if set. (Bit 02)
+        const ARAT = 1 << 2;
+        /// Bit 3: Reserved.
+        const RESERVED_3 = 1 << 3;
+        /// PLN. Power limit notification controls are supported if set. (Bit 04)
+        const PLN = 1 << 4;
+        /// ECMD. Clock modulation duty cycle extension is supported if set. (Bit 05)
+        const ECMD = 1 << 5;
+        /// PTM. Package thermal management is supported if set. (Bit 06)
+        const PTM = 1 << 6;
+        /// Bit 07: HWP. HWP base registers (IA32_PM_ENABLE[bit 0], IA32_HWP_CAPABILITIES, IA32_HWP_REQUEST, IA32_HWP_STATUS) are supported if set.
+        const HWP = 1 << 7;
+        /// Bit 08: HWP_Notification. IA32_HWP_INTERRUPT MSR is supported if set.
+        const HWP_NOTIFICATION = 1 << 8;
+        /// Bit 09: HWP_Activity_Window. IA32_HWP_REQUEST[bits 41:32] is supported if set.
+        const HWP_ACTIVITY_WINDOW = 1 << 9;
+        /// Bit 10: HWP_Energy_Performance_Preference. IA32_HWP_REQUEST[bits 31:24] is supported if set.
+        const HWP_ENERGY_PERFORMANCE_PREFERENCE = 1 << 10;
+        /// Bit 11: HWP_Package_Level_Request. IA32_HWP_REQUEST_PKG MSR is supported if set.
+        const HWP_PACKAGE_LEVEL_REQUEST = 1 << 11;
+        /// Bit 12: Reserved.
+        const RESERVED_12 = 1 << 12;
+        /// Bit 13: HDC. HDC base registers IA32_PKG_HDC_CTL, IA32_PM_CTL1, IA32_THREAD_STALL MSRs are supported if set.
+        const HDC = 1 << 13;
+        /// Bit 14: Intel® Turbo Boost Max Technology 3.0 available.
+        const TURBO_BOOST_3 = 1 << 14;
+        /// Bit 15: HWP Capabilities. Highest Performance change is supported if set.
+        const HWP_CAPABILITIES = 1 << 15;
+        /// Bit 16: HWP PECI override is supported if set.
+        const HWP_PECI_OVERRIDE = 1 << 16;
+        /// Bit 17: Flexible HWP is supported if set.
+        const FLEXIBLE_HWP = 1 << 17;
+        /// Bit 18: Fast access mode for the IA32_HWP_REQUEST MSR is supported if set.
+        const HWP_REQUEST_MSR_FAST_ACCESS = 1 << 18;
+        /// Bit 19: HW_FEEDBACK. Hardware Feedback Interface is supported if set.
+        const HW_FEEDBACK = 1 << 19;
+        /// Bit 20: Ignoring Idle Logical Processor HWP request is supported if set.
+        const IGNORE_IDLE_PROCESSOR_HWP_REQUEST = 1 << 20;
+        /// Bit 22: HWP Control. IA32_HWP_CTL MSR is supported if set.
+        const HWP_CONTROL = 1 << 22;
+        /// Bit 23: Intel Thread Director is supported if set.
+        const THREAD_DIRECTOR = 1 << 23;
+    }
+}
+
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    struct ThermalPowerFeaturesEcx: u32 {
+        const HW_COORD_FEEDBACK = 1 << 0;
+
+        /// The processor supports performance-energy bias preference if CPUID.06H:ECX.SETBH[bit 3] is set and it also implies the presence of a new architectural MSR called IA32_ENERGY_PERF_BIAS (1B0H)
+        const ENERGY_BIAS_PREF = 1 << 3;
+    }
+}
+
+/// Structured Extended Feature Identifiers (LEAF=0x07).
+///
+/// # Platforms
+/// 🟡 AMD ✅ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtendedFeatures {
+    _eax: u32,
+    ebx: ExtendedFeaturesEbx,
+    ecx: ExtendedFeaturesEcx,
+    edx: u32,
+    // Registers of subleaf 1 (zero if subleaf 1 isn't supported):
+    eax1: u32,
+    ebx1: u32,
+    ecx1: u32,
+    edx1: u32,
+    // EDX of subleaf 2 (zero if subleaf 2 isn't supported):
+    edx2: u32,
+}
+
+impl ExtendedFeatures {
+    /// Decodes the registers of LEAF=0x07, `subleaf1` and `subleaf2` are all
+    /// zero if the CPU doesn't support the subleaf.
+    pub fn from_registers(data: CpuIdResult, subleaf1: CpuIdResult, subleaf2: CpuIdResult) -> Self {
+        Self {
+            _eax: data.eax,
+            ebx: ExtendedFeaturesEbx { bits: data.ebx },
+            ecx: ExtendedFeaturesEcx { bits: data.ecx },
+            edx: data.edx,
+            eax1: subleaf1.eax,
+            ebx1: subleaf1.ebx,
+            ecx1: subleaf1.ecx,
+            edx1: subleaf1.edx,
+            edx2: subleaf2.edx,
+        }
+    }
+
+    /// FSGSBASE. Supports RDFSBASE/RDGSBASE/WRFSBASE/WRGSBASE if 1.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_fsgsbase(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::FSGSBASE)
+    }
+
+    /// IA32_TSC_ADJUST MSR is supported if 1.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_tsc_adjust_msr(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::ADJUST_MSR)
+    }
+
+    /// BMI1
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_bmi1(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::BMI1)
+    }
+
+    /// HLE
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_hle(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::HLE)
+    }
+
+    /// AVX2
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_avx2(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::AVX2)
+    }
+
+    /// FDP_EXCPTN_ONLY. x87 FPU Data Pointer updated only on x87 exceptions if
+    /// 1.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_fdp(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::FDP)
+    }
+
+    /// SMEP. Supports Supervisor-Mode Execution Prevention if 1.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_smep(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::SMEP)
+    }
+
+    /// BMI2
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_bmi2(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::BMI2)
+    }
+
+    /// Supports Enhanced REP MOVSB/STOSB if 1.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_rep_movsb_stosb(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::REP_MOVSB_STOSB)
+    }
+
+    /// INVPCID. If 1, supports INVPCID instruction for system software that
+    /// manages process-context identifiers.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_invpcid(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::INVPCID)
+    }
+
+    /// RTM
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_rtm(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::RTM)
+    }
+
+    /// Supports Intel Resource Director Technology (RDT) Monitoring capability.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_rdtm(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::RDTM)
+    }
+
+    /// Deprecates FPU CS and FPU DS values if 1.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_fpu_cs_ds_deprecated(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::DEPRECATE_FPU_CS_DS)
+    }
+
+    /// MPX. Supports Intel Memory Protection Extensions if 1.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_mpx(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::MPX)
+    }
+
+    /// Supports Intel Resource Director Technology (RDT) Allocation capability.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_rdta(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::RDTA)
+    }
+
+    /// Supports RDSEED.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_rdseed(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::RDSEED)
+    }
+
+    /// Supports ADX.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_adx(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::ADX)
+    }
+
+    /// SMAP. Supports Supervisor-Mode Access Prevention (and the CLAC/STAC
+    /// instructions) if 1.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_smap(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::SMAP)
+    }
 
-impl ProcessorSerial {
-    /// Bits 00-31 of 96 bit processor serial number.
+    /// Supports CLFLUSHOPT.
     ///
-    /// (Available in Pentium III processor only; otherwise, the value in this register is reserved.)
-    pub fn serial_lower(&self) -> u32 {
-        self.ecx
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_clflushopt(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::CLFLUSHOPT)
     }
 
-    /// Bits 32-63 of 96 bit processor serial number.
+    /// Supports Intel Processor Trace.
     ///
-    /// (Available in Pentium III processor only; otherwise, the value in this register is reserved.)
-    pub fn serial_middle(&self) -> u32 {
-        self.edx
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_processor_trace(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::PROCESSOR_TRACE)
     }
 
-    /// Bits 64-96 of 96 bit processor serial number.
-    pub fn serial_upper(&self) -> u32 {
-        self.eax
+    /// Supports SHA Instructions.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_sha(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::SHA)
     }
 
-    /// Combination of bits 00-31 and 32-63 of 96 bit processor serial number.
-    pub fn serial(&self) -> u64 {
-        (self.serial_lower() as u64) | (self.serial_middle() as u64) << 32
+    /// Supports Intel® Software Guard Extensions (Intel® SGX Extensions).
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_sgx(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::SGX)
     }
 
-    /// 96 bit processor serial number.
-    pub fn serial_all(&self) -> u128 {
-        (self.serial_lower() as u128)
-            | ((self.serial_middle() as u128) << 32)
-            | ((self.serial_upper() as u128) << 64)
+    /// Supports AVX512F.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_avx512f(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::AVX512F)
     }
-}
 
-impl Debug for ProcessorSerial {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("ProcessorSerial")
-            .field("serial_lower", &self.serial_lower())
-            .field("serial_middle", &self.serial_middle())
-            .finish()
+    /// Supports AVX512DQ.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_avx512dq(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::AVX512DQ)
     }
-}
 
-/// Processor and Processor Feature Identifiers (LEAF=0x01).
-///
-/// # Platforms
-/// ✅ AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct FeatureInfo {
-    vendor: Vendor,
-    eax: u32,
-    ebx: u32,
-    edx_ecx: FeatureInfoFlags,
-}
+    /// AVX512_IFMA
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_avx512_ifma(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::AVX512_IFMA)
+    }
 
-impl FeatureInfo {
-    /// Version Information: Extended Family
-    pub fn extended_family_id(&self) -> u8 {
-        get_bits(self.eax, 20, 27) as u8
+    /// AVX512PF
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_avx512pf(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::AVX512PF)
     }
 
-    /// Version Information: Extended Model
-    pub fn extended_model_id(&self) -> u8 {
-        get_bits(self.eax, 16, 19) as u8
+    /// AVX512ER
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_avx512er(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::AVX512ER)
     }
 
-    /// Version Information: Family
-    pub fn base_family_id(&self) -> u8 {
-        get_bits(self.eax, 8, 11) as u8
+    /// AVX512CD
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_avx512cd(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::AVX512CD)
     }
 
-    /// Version Information: Model
-    pub fn base_model_id(&self) -> u8 {
-        get_bits(self.eax, 4, 7) as u8
+    /// AVX512BW
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_avx512bw(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::AVX512BW)
     }
 
-    pub fn family_id(&self) -> u8 {
-        let base_family_id = self.base_family_id();
-        let extended_family_id = self.extended_family_id();
-        let just_use_base = (self.vendor == Vendor::Amd && base_family_id < 0xf)
-            || (self.vendor == Vendor::Intel && base_family_id != 0xf);
+    /// AVX512VL
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_avx512vl(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::AVX512VL)
+    }
 
-        if just_use_base {
-            base_family_id
-        } else {
-            base_family_id + extended_family_id
-        }
+    /// CLWB
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_clwb(&self) -> bool {
+        self.ebx.contains(ExtendedFeaturesEbx::CLWB)
     }
 
-    pub fn model_id(&self) -> u8 {
-        let base_family_id = self.base_family_id();
-        let base_model_id = self.base_model_id();
-        let extended_model_id = self.extended_model_id();
-        let just_use_base = (self.vendor == Vendor::Amd && base_family_id < 0xf)
-            || (self.vendor == Vendor::Intel && base_family_id != 0xf && base_family_id != 0x6);
+    /// Has PREFETCHWT1 (Intel® Xeon Phi™ only).
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_prefetchwt1(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::PREFETCHWT1)
+    }
 
-        if just_use_base {
-            base_model_id
-        } else {
-            (extended_model_id << 4) | base_model_id
-        }
+    /// Supports user-mode instruction prevention if 1.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_umip(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::UMIP)
     }
 
-    /// Version Information: Stepping ID
-    pub fn stepping_id(&self) -> u8 {
-        get_bits(self.eax, 0, 3) as u8
+    /// Supports protection keys for user-mode pages.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_pku(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::PKU)
     }
 
-    /// Brand Index
-    pub fn brand_index(&self) -> u8 {
-        get_bits(self.ebx, 0, 7) as u8
+    /// OS has set CR4.PKE to enable protection keys (and the RDPKRU/WRPKRU
+    /// instructions.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_ospke(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::OSPKE)
+    }
+
+    /// Supports TPAUSE, UMONITOR and UMWAIT (user-level wait).
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_waitpkg(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::WAITPKG)
+    }
+
+    /// CET_SS. Supports CET shadow stack features, see [`CpuId::cet_support`].
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_cet_ss(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::CET_SS)
+    }
+
+    /// AVX512VNNI
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_avx512vnni(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::AVX512VNNI)
+    }
+
+    /// Supports Total Memory Encryption (TME).
+    ///
+    /// # Note
+    /// The number of key ID bits used for multi-key TME (MKTME) is configured
+    /// in the IA32_TME_ACTIVATE MSR and isn't reported by `cpuid`. Use
+    /// [`CpuId::get_pconfig_info`] to check whether MKTME keys can be
+    /// programmed.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_tme(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::TME_EN)
+    }
+
+    /// Supports 57-bit linear addresses and five-level paging.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_la57(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::LA57)
+    }
+
+    /// RDPID and IA32_TSC_AUX are available.
+    ///
+    /// # Bug
+    /// The Intel manual lists RDPID as bit 22 in the ECX register, but AMD
+    /// lists it as bit 22 in the ebx register. We assumed that the AMD manual
+    /// was wrong and query ecx, let's see what happens.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn has_rdpid(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::RDPID)
     }
 
-    /// CLFLUSH line size (Value ∗ 8 = cache line size in bytes)
-    pub fn cflush_cache_line_size(&self) -> u8 {
-        get_bits(self.ebx, 8, 15) as u8
+    /// Supports MOVDIRI (direct store of a doubleword or quadword).
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_movdiri(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::MOVDIRI)
     }
 
-    /// Initial APIC ID
-    pub fn initial_local_apic_id(&self) -> u8 {
-        get_bits(self.ebx, 24, 31) as u8
+    /// Supports MOVDIR64B (direct store of 64 bytes).
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_movdir64b(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::MOVDIR64B)
     }
 
-    /// Maximum number of addressable IDs for logical processors in this physical package.
-    pub fn max_logical_processor_ids(&self) -> u8 {
-        get_bits(self.ebx, 16, 23) as u8
+    /// Supports Enqueue Stores (ENQCMD and ENQCMDS).
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_enqcmd(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::ENQCMD)
     }
 
-    check_flag!(
-        doc = "Streaming SIMD Extensions 3 (SSE3). A value of 1 indicates the processor \
-               supports this technology.",
-        has_sse3,
-        edx_ecx,
-        FeatureInfoFlags::SSE3
-    );
-
-    check_flag!(
-        doc = "PCLMULQDQ. A value of 1 indicates the processor supports the PCLMULQDQ \
-               instruction",
-        has_pclmulqdq,
-        edx_ecx,
-        FeatureInfoFlags::PCLMULQDQ
-    );
-
-    check_flag!(
-        doc = "64-bit DS Area. A value of 1 indicates the processor supports DS area \
-               using 64-bit layout",
-        has_ds_area,
-        edx_ecx,
-        FeatureInfoFlags::DTES64
-    );
+    /// Supports SGX Launch Configuration.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn has_sgx_lc(&self) -> bool {
+        self.ecx.contains(ExtendedFeaturesEcx::SGX_LC)
+    }
 
-    check_flag!(
-        doc = "MONITOR/MWAIT. A value of 1 indicates the processor supports this feature.",
-        has_monitor_mwait,
-        edx_ecx,
-        FeatureInfoFlags::MONITOR
-    );
+    /// Supports user interrupts (UIRET, SENDUIPI, CLUI, STUI and TESTUI).
+    ///
+    /// The UINTR state is saved with XSAVES, see
+    /// [`ExtendedStateInfo::ia32_xss_supports_uintr`].
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_uintr(&self) -> bool {
+        is_bit_set!(self.edx, 5)
+    }
 
-    check_flag!(
-        doc = "CPL Qualified Debug Store. A value of 1 indicates the processor supports \
-               the extensions to the  Debug Store feature to allow for branch message \
-               storage qualified by CPL.",
-        has_cpl,
-        edx_ecx,
-        FeatureInfoFlags::DSCPL
-    );
+    /// RTM_ALWAYS_ABORT. XBEGIN is supported but every RTM transaction
+    /// aborts, see [`CpuId::transactional_memory_usable`].
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_rtm_always_abort(&self) -> bool {
+        is_bit_set!(self.edx, 11)
+    }
 
-    check_flag!(
-        doc = "Virtual Machine Extensions. A value of 1 indicates that the processor \
-               supports this technology.",
-        has_vmx,
-        edx_ecx,
-        FeatureInfoFlags::VMX
-    );
+    /// TSX_FORCE_ABORT. The TSX_FORCE_ABORT MSR is supported, which can force
+    /// all RTM transactions to abort (e.g., to free performance counter 3).
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_tsx_force_abort(&self) -> bool {
+        is_bit_set!(self.edx, 13)
+    }
 
-    check_flag!(
-        doc = "Safer Mode Extensions. A value of 1 indicates that the processor supports \
-               this technology. See Chapter 5, Safer Mode Extensions Reference.",
-        has_smx,
-        edx_ecx,
-        FeatureInfoFlags::SMX
-    );
+    /// Supports the SERIALIZE instruction.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_serialize(&self) -> bool {
+        is_bit_set!(self.edx, 14)
+    }
 
-    check_flag!(
-        doc = "Enhanced Intel SpeedStep® technology. A value of 1 indicates that the \
-               processor supports this technology.",
-        has_eist,
-        edx_ecx,
-        FeatureInfoFlags::EIST
-    );
+    /// Supports XSUSLDTRK and XRESLDTRK (TSX suspend load address tracking).
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_tsxldtrk(&self) -> bool {
+        is_bit_set!(self.edx, 16)
+    }
 
-    check_flag!(
-        doc = "Thermal Monitor 2. A value of 1 indicates whether the processor supports \
-               this technology.",
-        has_tm2,
-        edx_ecx,
-        FeatureInfoFlags::TM2
-    );
+    /// The processor is a hybrid part with different core types (e.g.,
+    /// performance and efficiency cores), see [`CpuId::get_hybrid_info`].
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_hybrid(&self) -> bool {
+        is_bit_set!(self.edx, 15)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates the presence of the Supplemental Streaming SIMD \
-               Extensions 3 (SSSE3). A value of 0 indicates the instruction extensions \
-               are not present in the processor",
-        has_ssse3,
-        edx_ecx,
-        FeatureInfoFlags::SSSE3
-    );
+    /// Supports the PCONFIG instruction, see [`CpuId::get_pconfig_info`].
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_pconfig(&self) -> bool {
+        is_bit_set!(self.edx, 18)
+    }
 
-    check_flag!(
-        doc = "L1 Context ID. A value of 1 indicates the L1 data cache mode can be set \
-               to either adaptive mode or shared mode. A value of 0 indicates this \
-               feature is not supported. See definition of the IA32_MISC_ENABLE MSR Bit \
-               24 (L1 Data Cache Context Mode) for details.",
-        has_cnxtid,
-        edx_ecx,
-        FeatureInfoFlags::CNXTID
-    );
+    /// Supports architectural last branch records (LBRs), see
+    /// [`CpuId::get_lbr_info`].
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_arch_lbr(&self) -> bool {
+        is_bit_set!(self.edx, 19)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates the processor supports FMA extensions using YMM \
-               state.",
-        has_fma,
-        edx_ecx,
-        FeatureInfoFlags::FMA
-    );
+    /// CET_IBT. Supports CET indirect branch tracking (ENDBR32/ENDBR64), see
+    /// [`CpuId::cet_support`].
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_cet_ibt(&self) -> bool {
+        is_bit_set!(self.edx, 20)
+    }
 
-    check_flag!(
-        doc = "CMPXCHG16B Available. A value of 1 indicates that the feature is \
-               available. See the CMPXCHG8B/CMPXCHG16B Compare and Exchange Bytes \
-               section. 14",
-        has_cmpxchg16b,
-        edx_ecx,
-        FeatureInfoFlags::CMPXCHG16B
-    );
+    /// AMX-BF16. Tile computational operations on BF16 numbers.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_amx_bf16(&self) -> bool {
+        is_bit_set!(self.edx, 22)
+    }
 
-    check_flag!(
-        doc = "Perfmon and Debug Capability: A value of 1 indicates the processor \
-               supports the performance   and debug feature indication MSR \
-               IA32_PERF_CAPABILITIES.",
-        has_pdcm,
-        edx_ecx,
-        FeatureInfoFlags::PDCM
-    );
+    /// AMX-TILE. Tile architecture (LDTILECFG, TILELOADD, ...), see
+    /// [`CpuId::get_tmul_info`] for the limits of the tile operations.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_amx_tile(&self) -> bool {
+        is_bit_set!(self.edx, 24)
+    }
 
-    check_flag!(
-        doc = "Process-context identifiers. A value of 1 indicates that the processor \
-               supports PCIDs and the software may set CR4.PCIDE to 1.",
-        has_pcid,
-        edx_ecx,
-        FeatureInfoFlags::PCID
-    );
+    /// AMX-INT8. Tile computational operations on 8-bit integers.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_amx_int8(&self) -> bool {
+        is_bit_set!(self.edx, 25)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates the processor supports the ability to prefetch \
-               data from a memory mapped device.",
-        has_dca,
-        edx_ecx,
-        FeatureInfoFlags::DCA
-    );
+    /// The value of MAWAU used by the BNDLDX and BNDSTX instructions in 64-bit mode.
+    ///
+    /// # Platforms
+    /// ❌ AMD (reserved) ✅ Intel
+    pub fn mawau_value(&self) -> u8 {
+        get_bits(self.ecx.bits(), 17, 21) as u8
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates that the processor supports SSE4.1.",
-        has_sse41,
-        edx_ecx,
-        FeatureInfoFlags::SSE41
-    );
+    /// RAO-INT. Remote atomic operations (AADD, AAND, AOR and AXOR).
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_rao_int(&self) -> bool {
+        is_bit_set!(self.eax1, 3)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates that the processor supports SSE4.2.",
-        has_sse42,
-        edx_ecx,
-        FeatureInfoFlags::SSE42
-    );
+    /// CMPCCXADD. Compare and add if condition is met.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_cmpccxadd(&self) -> bool {
+        is_bit_set!(self.eax1, 7)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates that the processor supports x2APIC feature.",
-        has_x2apic,
-        edx_ecx,
-        FeatureInfoFlags::X2APIC
-    );
+    /// WRMSRNS. Non-serializing write to model specific registers.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_wrmsrns(&self) -> bool {
+        is_bit_set!(self.eax1, 19)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates that the processor supports MOVBE instruction.",
-        has_movbe,
-        edx_ecx,
-        FeatureInfoFlags::MOVBE
-    );
+    /// HRESET. History reset instruction and the IA32_HRESET_ENABLE MSR, see
+    /// [`CpuId::get_hreset_info`].
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_hreset(&self) -> bool {
+        is_bit_set!(self.eax1, 22)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates that the processor supports the POPCNT instruction.",
-        has_popcnt,
-        edx_ecx,
-        FeatureInfoFlags::POPCNT
-    );
+    /// Architectural performance monitoring extended leaf, see
+    /// [`CpuId::get_arch_perfmon_ext_info`].
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_arch_perfmon_ext(&self) -> bool {
+        is_bit_set!(self.eax1, 8)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates that the processors local APIC timer supports \
-               one-shot operation using a TSC deadline value.",
-        has_tsc_deadline,
-        edx_ecx,
-        FeatureInfoFlags::TSC_DEADLINE
-    );
+    /// AMX-FP16. Tile computational operations on FP16 numbers.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_amx_fp16(&self) -> bool {
+        is_bit_set!(self.eax1, 21)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates that the processor supports the AESNI instruction \
-               extensions.",
-        has_aesni,
-        edx_ecx,
-        FeatureInfoFlags::AESNI
-    );
+    /// MSRLIST. RDMSRLIST and WRMSRLIST instructions and the IA32_BARRIER MSR.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_msrlist(&self) -> bool {
+        is_bit_set!(self.eax1, 27)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates that the processor supports the XSAVE/XRSTOR \
-               processor extended states feature, the XSETBV/XGETBV instructions, and \
-               XCR0.",
-        has_xsave,
-        edx_ecx,
-        FeatureInfoFlags::XSAVE
-    );
+    /// PPIN. IA32_PPIN and IA32_PPIN_CTL MSRs (protected processor inventory number).
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_ppin(&self) -> bool {
+        is_bit_set!(self.ebx1, 0)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates that the OS has enabled XSETBV/XGETBV instructions \
-               to access XCR0, and support for processor extended state management using \
-               XSAVE/XRSTOR.",
-        has_oxsave,
-        edx_ecx,
-        FeatureInfoFlags::OSXSAVE
-    );
+    /// PBNDKB. PBNDKB instruction and the IA32_TSE_CAPABILITY MSR.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_pbndkb(&self) -> bool {
+        is_bit_set!(self.ebx1, 1)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates the processor supports the AVX instruction \
-               extensions.",
-        has_avx,
-        edx_ecx,
-        FeatureInfoFlags::AVX
-    );
+    /// AVX-VNNI-INT8. VEX-encoded dot products of signed/unsigned bytes.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_avx_vnni_int8(&self) -> bool {
+        is_bit_set!(self.edx1, 4)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates that processor supports 16-bit floating-point \
-               conversion instructions.",
-        has_f16c,
-        edx_ecx,
-        FeatureInfoFlags::F16C
-    );
+    /// AVX-NE-CONVERT. VEX-encoded BF16/FP16 conversion instructions without exceptions.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_avx_ne_convert(&self) -> bool {
+        is_bit_set!(self.edx1, 5)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates that processor supports RDRAND instruction.",
-        has_rdrand,
-        edx_ecx,
-        FeatureInfoFlags::RDRAND
-    );
+    /// AMX-COMPLEX. Tile computational operations on complex FP16 numbers.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_amx_complex(&self) -> bool {
+        is_bit_set!(self.edx1, 8)
+    }
 
-    check_flag!(
-        doc = "A value of 1 indicates the indicates the presence of a hypervisor.",
-        has_hypervisor,
-        edx_ecx,
-        FeatureInfoFlags::HYPERVISOR
-    );
+    /// AVX-VNNI-INT16. VEX-encoded dot products of signed/unsigned words.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_avx_vnni_int16(&self) -> bool {
+        is_bit_set!(self.edx1, 10)
+    }
 
-    check_flag!(
-        doc = "Floating Point Unit On-Chip. The processor contains an x87 FPU.",
-        has_fpu,
-        edx_ecx,
-        FeatureInfoFlags::FPU
-    );
+    /// PREFETCHI. PREFETCHIT0 and PREFETCHIT1 instructions.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_prefetchi(&self) -> bool {
+        is_bit_set!(self.edx1, 14)
+    }
 
-    check_flag!(
-        doc = "Virtual 8086 Mode Enhancements. Virtual 8086 mode enhancements, including \
-               CR4.VME for controlling the feature, CR4.PVI for protected mode virtual \
-               interrupts, software interrupt indirection, expansion of the TSS with the \
-               software indirection bitmap, and EFLAGS.VIF and EFLAGS.VIP flags.",
-        has_vme,
-        edx_ecx,
-        FeatureInfoFlags::VME
-    );
+    /// CET_SSS. The processor doesn't modify supervisor shadow stack tokens
+    /// speculatively, so a supervisor shadow stack can be used.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_cet_sss(&self) -> bool {
+        is_bit_set!(self.edx1, 18)
+    }
 
-    check_flag!(
-        doc = "Debugging Extensions. Support for I/O breakpoints, including CR4.DE for \
-               controlling the feature, and optional trapping of accesses to DR4 and DR5.",
-        has_de,
-        edx_ecx,
-        FeatureInfoFlags::DE
-    );
+    /// AVX10 converged vector ISA, see [`CpuId::get_avx10_info`].
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_avx10(&self) -> bool {
+        is_bit_set!(self.edx1, 19)
+    }
 
-    check_flag!(
-        doc = "Page Size Extension. Large pages of size 4 MByte are supported, including \
-               CR4.PSE for controlling the feature, the defined dirty bit in PDE (Page \
-               Directory Entries), optional reserved bit trapping in CR3, PDEs, and PTEs.",
-        has_pse,
-        edx_ecx,
-        FeatureInfoFlags::PSE
-    );
+    /// APX_F. Advanced Performance Extensions Foundation (extended GPRs R16-R31 and
+    /// related instructions).
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_apx_f(&self) -> bool {
+        is_bit_set!(self.edx1, 21)
+    }
 
-    check_flag!(
-        doc = "Time Stamp Counter. The RDTSC instruction is supported, including CR4.TSD \
-               for controlling privilege.",
-        has_tsc,
-        edx_ecx,
-        FeatureInfoFlags::TSC
-    );
+    /// PSFD. Predictive Store Forwarding can be disabled with IA32_SPEC_CTRL[7].
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_psfd(&self) -> bool {
+        is_bit_set!(self.edx2, 0)
+    }
 
-    check_flag!(
-        doc = "Model Specific Registers RDMSR and WRMSR Instructions. The RDMSR and \
-               WRMSR instructions are supported. Some of the MSRs are implementation \
-               dependent.",
-        has_msr,
-        edx_ecx,
-        FeatureInfoFlags::MSR
-    );
+    /// IPRED_CTRL. Indirect branch predictor behavior can be restricted with
+    /// IA32_SPEC_CTRL[4:3] (IPRED_DIS_U and IPRED_DIS_S).
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_ipred_ctrl(&self) -> bool {
+        is_bit_set!(self.edx2, 1)
+    }
 
-    check_flag!(
-        doc = "Physical Address Extension. Physical addresses greater than 32 bits are \
-               supported: extended page table entry formats, an extra level in the page \
-               translation tables is defined, 2-MByte pages are supported instead of 4 \
-               Mbyte pages if PAE bit is 1.",
-        has_pae,
-        edx_ecx,
-        FeatureInfoFlags::PAE
-    );
+    /// RRSBA_CTRL. Alternate predictors for RET instructions (RRSBA) can be
+    /// disabled with IA32_SPEC_CTRL[6:5] (RRSBA_DIS_U and RRSBA_DIS_S).
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_rrsba_ctrl(&self) -> bool {
+        is_bit_set!(self.edx2, 2)
+    }
 
-    check_flag!(
-        doc = "Machine Check Exception. Exception 18 is defined for Machine Checks, \
-               including CR4.MCE for controlling the feature. This feature does not \
-               define the model-specific implementations of machine-check error logging, \
-               reporting, and processor shutdowns. Machine Check exception handlers may \
-               have to depend on processor version to do model specific processing of \
-               the exception, or test for the presence of the Machine Check feature.",
-        has_mce,
-        edx_ecx,
-        FeatureInfoFlags::MCE
-    );
+    /// DDPD_U. The data dependent prefetcher can be disabled with
+    /// IA32_SPEC_CTRL[8].
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_ddpd_u(&self) -> bool {
+        is_bit_set!(self.edx2, 3)
+    }
 
-    check_flag!(
-        doc = "CMPXCHG8B Instruction. The compare-and-exchange 8 bytes (64 bits) \
-               instruction is supported (implicitly locked and atomic).",
-        has_cmpxchg8b,
-        edx_ecx,
-        FeatureInfoFlags::CX8
-    );
+    /// BHI_CTRL. Branch History Injection can be mitigated by setting
+    /// BHI_DIS_S (IA32_SPEC_CTRL[10]).
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_bhi_ctrl(&self) -> bool {
+        is_bit_set!(self.edx2, 4)
+    }
 
-    check_flag!(
-        doc = "APIC On-Chip. The processor contains an Advanced Programmable Interrupt \
-               Controller (APIC), responding to memory mapped commands in the physical \
-               address range FFFE0000H to FFFE0FFFH (by default - some processors permit \
-               the APIC to be relocated).",
-        has_apic,
-        edx_ecx,
-        FeatureInfoFlags::APIC
-    );
+    /// MCDT_NO. The processor doesn't exhibit MXCSR Configuration Dependent
+    /// Timing (MCDT).
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_mcdt_no(&self) -> bool {
+        is_bit_set!(self.edx2, 5)
+    }
 
-    check_flag!(
-        doc = "SYSENTER and SYSEXIT Instructions. The SYSENTER and SYSEXIT and \
-               associated MSRs are supported.",
-        has_sysenter_sysexit,
-        edx_ecx,
-        FeatureInfoFlags::SEP
-    );
+    /// UC_LOCK_DISABLE. Locks on uncached memory can cause a fault
+    /// (MSR_MEMORY_CTRL[28]).
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_uc_lock_disable(&self) -> bool {
+        is_bit_set!(self.edx2, 6)
+    }
 
-    check_flag!(
-        doc = "Memory Type Range Registers. MTRRs are supported. The MTRRcap MSR \
-               contains feature bits that describe what memory types are supported, how \
-               many variable MTRRs are supported, and whether fixed MTRRs are supported.",
-        has_mtrr,
-        edx_ecx,
-        FeatureInfoFlags::MTRR
-    );
+    /// MONITOR_MITG_NO. MONITOR/UMONITOR aren't affected by performance or
+    /// power issues that need mitigation.
+    ///
+    /// # Platforms
+    /// ❌ AMD ✅ Intel
+    pub fn has_monitor_mitg_no(&self) -> bool {
+        is_bit_set!(self.edx2, 7)
+    }
 
-    check_flag!(
-        doc = "Page Global Bit. The global bit is supported in paging-structure entries \
-               that map a page, indicating TLB entries that are common to different \
-               processes and need not be flushed. The CR4.PGE bit controls this feature.",
-        has_pge,
-        edx_ecx,
-        FeatureInfoFlags::PGE
-    );
+    /// Encode this struct back into the registers of leaf 7 (subleaf 0).
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self._eax,
+            ebx: self.ebx.bits(),
+            ecx: self.ecx.bits(),
+            edx: self.edx,
+        }
+    }
 
-    check_flag!(
-        doc = "Machine Check Architecture. A value of 1 indicates the Machine Check \
-               Architecture of reporting machine errors is supported. The MCG_CAP MSR \
-               contains feature bits describing how many banks of error reporting MSRs \
-               are supported.",
-        has_mca,
-        edx_ecx,
-        FeatureInfoFlags::MCA
+    /// Encode this struct back into the registers of leaf 7, subleafs 0 to 2.
+    ///
+    /// Only EDX of subleaf 2 is kept, the other registers of subleaf 2 are
+    /// zero.
+    pub fn as_cpuid_results(&self) -> [CpuIdResult; 3] {
+        [
+            self.as_cpuid_result(),
+            CpuIdResult {
+                eax: self.eax1,
+                ebx: self.ebx1,
+                ecx: self.ecx1,
+                edx: self.edx1,
+            },
+            CpuIdResult {
+                eax: 0,
+                ebx: 0,
+                ecx: 0,
+                edx: self.edx2,
+            },
+        ]
+    }
+
+    /// Bits set in the flag registers of leaf 7, subleafs 0 to 2, that this
+    /// library doesn't interpret, i.e., reserved bits or flags it doesn't
+    /// know about yet.
+    ///
+    /// EAX of subleaf 0 (the maximum subleaf) always reads as zero.
+    pub fn reserved_bits(&self) -> [CpuIdResult; 3] {
+        // ECX[21:17] is the MAWAU value.
+        let known_ecx = ExtendedFeaturesEcx::all().bits() | 0x3e_0000;
+        [
+            CpuIdResult {
+                eax: 0,
+                ebx: self.ebx.bits() & !ExtendedFeaturesEbx::all().bits(),
+                ecx: self.ecx.bits() & !known_ecx,
+                edx: self.edx & !EXTENDED_FEATURES_EDX,
+            },
+            CpuIdResult {
+                eax: self.eax1 & !EXTENDED_FEATURES_EAX1,
+                ebx: self.ebx1 & !EXTENDED_FEATURES_EBX1,
+                ecx: self.ecx1,
+                edx: self.edx1 & !EXTENDED_FEATURES_EDX1,
+            },
+            CpuIdResult {
+                eax: 0,
+                ebx: 0,
+                ecx: 0,
+                edx: self.edx2 & !EXTENDED_FEATURES_EDX2,
+            },
+        ]
+    }
+
+    /// Set the value reported by [`Self::mawau_value`].
+    pub fn set_mawau_value(&mut self, value: u8) {
+        let mut bits = self.ecx.bits();
+        set_bits(&mut bits, 17, 21, value as u32);
+        self.ecx = ExtendedFeaturesEcx { bits };
+    }
+
+    set_flag!(
+        set_fsgsbase,
+        has_fsgsbase,
+        ebx,
+        ExtendedFeaturesEbx::FSGSBASE
     );
-
-    check_flag!(
-        doc = "Conditional Move Instructions. The conditional move instruction CMOV is \
-               supported. In addition, if x87 FPU is present as indicated by the \
-               CPUID.FPU feature bit, then the FCOMI and FCMOV instructions are supported",
-        has_cmov,
-        edx_ecx,
-        FeatureInfoFlags::CMOV
+    set_flag!(
+        set_tsc_adjust_msr,
+        has_tsc_adjust_msr,
+        ebx,
+        ExtendedFeaturesEbx::ADJUST_MSR
     );
-
-    check_flag!(
-        doc = "Page Attribute Table. Page Attribute Table is supported. This feature \
-               augments the Memory Type Range Registers (MTRRs), allowing an operating \
-               system to specify attributes of memory accessed through a linear address \
-               on a 4KB granularity.",
-        has_pat,
-        edx_ecx,
-        FeatureInfoFlags::PAT
+    set_flag!(set_bmi1, has_bmi1, ebx, ExtendedFeaturesEbx::BMI1);
+    set_flag!(set_hle, has_hle, ebx, ExtendedFeaturesEbx::HLE);
+    set_flag!(set_avx2, has_avx2, ebx, ExtendedFeaturesEbx::AVX2);
+    set_flag!(set_fdp, has_fdp, ebx, ExtendedFeaturesEbx::FDP);
+    set_flag!(set_smep, has_smep, ebx, ExtendedFeaturesEbx::SMEP);
+    set_flag!(set_bmi2, has_bmi2, ebx, ExtendedFeaturesEbx::BMI2);
+    set_flag!(
+        set_rep_movsb_stosb,
+        has_rep_movsb_stosb,
+        ebx,
+        ExtendedFeaturesEbx::REP_MOVSB_STOSB
     );
-
-    check_flag!(
-        doc = "36-Bit Page Size Extension. 4-MByte pages addressing physical memory \
-               beyond 4 GBytes are supported with 32-bit paging. This feature indicates \
-               that upper bits of the physical address of a 4-MByte page are encoded in \
-               bits 20:13 of the page-directory entry. Such physical addresses are \
-               limited by MAXPHYADDR and may be up to 40 bits in size.",
-        has_pse36,
-        edx_ecx,
-        FeatureInfoFlags::PSE36
+    set_flag!(set_invpcid, has_invpcid, ebx, ExtendedFeaturesEbx::INVPCID);
+    set_flag!(set_rtm, has_rtm, ebx, ExtendedFeaturesEbx::RTM);
+    set_flag!(set_rdtm, has_rdtm, ebx, ExtendedFeaturesEbx::RDTM);
+    set_flag!(
+        set_fpu_cs_ds_deprecated,
+        has_fpu_cs_ds_deprecated,
+        ebx,
+        ExtendedFeaturesEbx::DEPRECATE_FPU_CS_DS
     );
-
-    check_flag!(
-        doc = "Processor Serial Number. The processor supports the 96-bit processor \
-               identification number feature and the feature is enabled.",
-        has_psn,
-        edx_ecx,
-        FeatureInfoFlags::PSN
+    set_flag!(set_mpx, has_mpx, ebx, ExtendedFeaturesEbx::MPX);
+    set_flag!(set_rdta, has_rdta, ebx, ExtendedFeaturesEbx::RDTA);
+    set_flag!(set_rdseed, has_rdseed, ebx, ExtendedFeaturesEbx::RDSEED);
+    set_flag!(set_adx, has_adx, ebx, ExtendedFeaturesEbx::ADX);
+    set_flag!(set_smap, has_smap, ebx, ExtendedFeaturesEbx::SMAP);
+    set_flag!(
+        set_clflushopt,
+        has_clflushopt,
+        ebx,
+        ExtendedFeaturesEbx::CLFLUSHOPT
     );
-
-    check_flag!(
-        doc = "CLFLUSH Instruction. CLFLUSH Instruction is supported.",
-        has_clflush,
-        edx_ecx,
-        FeatureInfoFlags::CLFSH
+    set_flag!(
+        set_processor_trace,
+        has_processor_trace,
+        ebx,
+        ExtendedFeaturesEbx::PROCESSOR_TRACE
     );
-
-    check_flag!(
-        doc = "Debug Store. The processor supports the ability to write debug \
-               information into a memory resident buffer. This feature is used by the \
-               branch trace store (BTS) and processor event-based sampling (PEBS) \
-               facilities (see Chapter 23, Introduction to Virtual-Machine Extensions, \
-               in the Intel® 64 and IA-32 Architectures Software Developers Manual, \
-               Volume 3C).",
-        has_ds,
-        edx_ecx,
-        FeatureInfoFlags::DS
+    set_flag!(set_sha, has_sha, ebx, ExtendedFeaturesEbx::SHA);
+    set_flag!(set_sgx, has_sgx, ebx, ExtendedFeaturesEbx::SGX);
+    set_flag!(set_avx512f, has_avx512f, ebx, ExtendedFeaturesEbx::AVX512F);
+    set_flag!(
+        set_avx512dq,
+        has_avx512dq,
+        ebx,
+        ExtendedFeaturesEbx::AVX512DQ
     );
-
-    check_flag!(
-        doc = "Thermal Monitor and Software Controlled Clock Facilities. The processor \
-               implements internal MSRs that allow processor temperature to be monitored \
-               and processor performance to be modulated in predefined duty cycles under \
-               software control.",
-        has_acpi,
-        edx_ecx,
-        FeatureInfoFlags::ACPI
+    set_flag!(
+        set_avx512_ifma,
+        has_avx512_ifma,
+        ebx,
+        ExtendedFeaturesEbx::AVX512_IFMA
     );
-
-    check_flag!(
-        doc = "Intel MMX Technology. The processor supports the Intel MMX technology.",
-        has_mmx,
-        edx_ecx,
-        FeatureInfoFlags::MMX
+    set_flag!(
+        set_avx512pf,
+        has_avx512pf,
+        ebx,
+        ExtendedFeaturesEbx::AVX512PF
     );
-
-    check_flag!(
-        doc = "FXSAVE and FXRSTOR Instructions. The FXSAVE and FXRSTOR instructions are \
-               supported for fast save and restore of the floating point context. \
-               Presence of this bit also indicates that CR4.OSFXSR is available for an \
-               operating system to indicate that it supports the FXSAVE and FXRSTOR \
-               instructions.",
-        has_fxsave_fxstor,
-        edx_ecx,
-        FeatureInfoFlags::FXSR
+    set_flag!(
+        set_avx512er,
+        has_avx512er,
+        ebx,
+        ExtendedFeaturesEbx::AVX512ER
     );
-
-    check_flag!(
-        doc = "SSE. The processor supports the SSE extensions.",
-        has_sse,
-        edx_ecx,
-        FeatureInfoFlags::SSE
+    set_flag!(
+        set_avx512cd,
+        has_avx512cd,
+        ebx,
+        ExtendedFeaturesEbx::AVX512CD
     );
-
-    check_flag!(
-        doc = "SSE2. The processor supports the SSE2 extensions.",
-        has_sse2,
-        edx_ecx,
-        FeatureInfoFlags::SSE2
+    set_flag!(
+        set_avx512bw,
+        has_avx512bw,
+        ebx,
+        ExtendedFeaturesEbx::AVX512BW
     );
-
-    check_flag!(
-        doc = "Self Snoop. The processor supports the management of conflicting memory \
-               types by performing a snoop of its own cache structure for transactions \
-               issued to the bus.",
-        has_ss,
-        edx_ecx,
-        FeatureInfoFlags::SS
+    set_flag!(
+        set_avx512vl,
+        has_avx512vl,
+        ebx,
+        ExtendedFeaturesEbx::AVX512VL
     );
-
-    check_flag!(
-        doc = "Max APIC IDs reserved field is Valid. A value of 0 for HTT indicates \
-               there is only a single logical processor in the package and software \
-               should assume only a single APIC ID is reserved.  A value of 1 for HTT \
-               indicates the value in CPUID.1.EBX\\[23:16\\] (the Maximum number of \
-               addressable IDs for logical processors in this package) is valid for the \
-               package.",
-        has_htt,
-        edx_ecx,
-        FeatureInfoFlags::HTT
+    set_flag!(set_clwb, has_clwb, ebx, ExtendedFeaturesEbx::CLWB);
+    set_flag!(
+        set_prefetchwt1,
+        has_prefetchwt1,
+        ecx,
+        ExtendedFeaturesEcx::PREFETCHWT1
     );
-
-    check_flag!(
-        doc = "Thermal Monitor. The processor implements the thermal monitor automatic \
-               thermal control circuitry (TCC).",
-        has_tm,
-        edx_ecx,
-        FeatureInfoFlags::TM
+    set_flag!(set_umip, has_umip, ecx, ExtendedFeaturesEcx::UMIP);
+    set_flag!(set_pku, has_pku, ecx, ExtendedFeaturesEcx::PKU);
+    set_flag!(set_ospke, has_ospke, ecx, ExtendedFeaturesEcx::OSPKE);
+    set_flag!(set_waitpkg, has_waitpkg, ecx, ExtendedFeaturesEcx::WAITPKG);
+    set_flag!(set_cet_ss, has_cet_ss, ecx, ExtendedFeaturesEcx::CET_SS);
+    set_flag!(
+        set_avx512vnni,
+        has_avx512vnni,
+        ecx,
+        ExtendedFeaturesEcx::AVX512VNNI
     );
-
-    check_flag!(
-        doc = "Pending Break Enable. The processor supports the use of the FERR#/PBE# \
-               pin when the processor is in the stop-clock state (STPCLK# is asserted) \
-               to signal the processor that an interrupt is pending and that the \
-               processor should return to normal operation to handle the interrupt. Bit \
-               10 (PBE enable) in the IA32_MISC_ENABLE MSR enables this capability.",
-        has_pbe,
-        edx_ecx,
-        FeatureInfoFlags::PBE
+    set_flag!(set_tme, has_tme, ecx, ExtendedFeaturesEcx::TME_EN);
+    set_flag!(set_la57, has_la57, ecx, ExtendedFeaturesEcx::LA57);
+    set_flag!(set_rdpid, has_rdpid, ecx, ExtendedFeaturesEcx::RDPID);
+    set_flag!(set_movdiri, has_movdiri, ecx, ExtendedFeaturesEcx::MOVDIRI);
+    set_flag!(
+        set_movdir64b,
+        has_movdir64b,
+        ecx,
+        ExtendedFeaturesEcx::MOVDIR64B
     );
+    set_flag!(set_enqcmd, has_enqcmd, ecx, ExtendedFeaturesEcx::ENQCMD);
+    set_flag!(set_sgx_lc, has_sgx_lc, ecx, ExtendedFeaturesEcx::SGX_LC);
+    set_bit_fn!(set_uintr, has_uintr, edx, 5);
+    set_bit_fn!(set_rtm_always_abort, has_rtm_always_abort, edx, 11);
+    set_bit_fn!(set_tsx_force_abort, has_tsx_force_abort, edx, 13);
+    set_bit_fn!(set_serialize, has_serialize, edx, 14);
+    set_bit_fn!(set_hybrid, has_hybrid, edx, 15);
+    set_bit_fn!(set_tsxldtrk, has_tsxldtrk, edx, 16);
+    set_bit_fn!(set_pconfig, has_pconfig, edx, 18);
+    set_bit_fn!(set_arch_lbr, has_arch_lbr, edx, 19);
+    set_bit_fn!(set_cet_ibt, has_cet_ibt, edx, 20);
+    set_bit_fn!(set_amx_bf16, has_amx_bf16, edx, 22);
+    set_bit_fn!(set_amx_tile, has_amx_tile, edx, 24);
+    set_bit_fn!(set_amx_int8, has_amx_int8, edx, 25);
+    set_bit_fn!(set_rao_int, has_rao_int, eax1, 3);
+    set_bit_fn!(set_cmpccxadd, has_cmpccxadd, eax1, 7);
+    set_bit_fn!(set_arch_perfmon_ext, has_arch_perfmon_ext, eax1, 8);
+    set_bit_fn!(set_wrmsrns, has_wrmsrns, eax1, 19);
+    set_bit_fn!(set_amx_fp16, has_amx_fp16, eax1, 21);
+    set_bit_fn!(set_hreset, has_hreset, eax1, 22);
+    set_bit_fn!(set_msrlist, has_msrlist, eax1, 27);
+    set_bit_fn!(set_ppin, has_ppin, ebx1, 0);
+    set_bit_fn!(set_pbndkb, has_pbndkb, ebx1, 1);
+    set_bit_fn!(set_avx_vnni_int8, has_avx_vnni_int8, edx1, 4);
+    set_bit_fn!(set_avx_ne_convert, has_avx_ne_convert, edx1, 5);
+    set_bit_fn!(set_amx_complex, has_amx_complex, edx1, 8);
+    set_bit_fn!(set_avx_vnni_int16, has_avx_vnni_int16, edx1, 10);
+    set_bit_fn!(set_prefetchi, has_prefetchi, edx1, 14);
+    set_bit_fn!(set_cet_sss, has_cet_sss, edx1, 18);
+    set_bit_fn!(set_avx10, has_avx10, edx1, 19);
+    set_bit_fn!(set_apx_f, has_apx_f, edx1, 21);
+    set_bit_fn!(set_psfd, has_psfd, edx2, 0);
+    set_bit_fn!(set_ipred_ctrl, has_ipred_ctrl, edx2, 1);
+    set_bit_fn!(set_rrsba_ctrl, has_rrsba_ctrl, edx2, 2);
+    set_bit_fn!(set_ddpd_u, has_ddpd_u, edx2, 3);
+    set_bit_fn!(set_bhi_ctrl, has_bhi_ctrl, edx2, 4);
+    set_bit_fn!(set_mcdt_no, has_mcdt_no, edx2, 5);
+    set_bit_fn!(set_uc_lock_disable, has_uc_lock_disable, edx2, 6);
+    set_bit_fn!(set_monitor_mitg_no, has_monitor_mitg_no, edx2, 7);
 }
 
-impl Debug for FeatureInfo {
+impl Debug for ExtendedFeatures {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("FeatureInfo")
-            .field("extended_family_id", &self.extended_family_id())
-            .field("extended_model_id", &self.extended_model_id())
-            .field("family_id", &self.family_id())
-            .field("model_id", &self.model_id())
-            .field("stepping_id", &self.stepping_id())
-            .field("brand_index", &self.brand_index())
-            .field("cflush_cache_line_size", &self.cflush_cache_line_size())
-            .field("initial_local_apic_id", &self.initial_local_apic_id())
-            .field(
-                "max_logical_processor_ids",
-                &self.max_logical_processor_ids(),
-            )
-            .field("edx_ecx", &self.edx_ecx)
+        f.debug_struct("ExtendedFeatures")
+            .field("ebx", &self.ebx)
+            .field("ecx", &self.ecx)
+            .field("mawau_value", &self.mawau_value())
+            .field("eax1", &self.eax1)
+            .field("ebx1", &self.ebx1)
+            .field("edx1", &self.edx1)
+            .field("edx2", &self.edx2)
             .finish()
     }
 }
 
-bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-    struct FeatureInfoFlags: u64 {
-
-        // ECX flags
+/// Flags of leaf 7 EDX (subleaf 0) decoded by [`ExtendedFeatures`].
+const EXTENDED_FEATURES_EDX: u32 = (1 << 5)
+    | (1 << 11)
+    | (1 << 13)
+    | (1 << 14)
+    | (1 << 15)
+    | (1 << 16)
+    | (1 << 18)
+    | (1 << 19)
+    | (1 << 20)
+    | (1 << 22)
+    | (1 << 24)
+    | (1 << 25);
+/// Flags of leaf 7 EAX (subleaf 1) decoded by [`ExtendedFeatures`].
+const EXTENDED_FEATURES_EAX1: u32 =
+    (1 << 3) | (1 << 7) | (1 << 8) | (1 << 19) | (1 << 21) | (1 << 22) | (1 << 27);
+/// Flags of leaf 7 EBX (subleaf 1) decoded by [`ExtendedFeatures`].
+const EXTENDED_FEATURES_EBX1: u32 = (1 << 0) | (1 << 1);
+/// Flags of leaf 7 EDX (subleaf 1) decoded by [`ExtendedFeatures`].
+const EXTENDED_FEATURES_EDX1: u32 =
+    (1 << 4) | (1 << 5) | (1 << 8) | (1 << 10) | (1 << 14) | (1 << 18) | (1 << 19) | (1 << 21);
+/// Flags of leaf 7 EDX (subleaf 2) decoded by [`ExtendedFeatures`].
+const EXTENDED_FEATURES_EDX2: u32 = 0xff;
 
-        /// Streaming SIMD Extensions 3 (SSE3). A value of 1 indicates the processor supports this technology.
-        const SSE3 = 1 << 0;
-        /// PCLMULQDQ. A value of 1 indicates the processor supports the PCLMULQDQ instruction
-        const PCLMULQDQ = 1 << 1;
-        /// 64-bit DS Area. A value of 1 indicates the processor supports DS area using 64-bit layout
-        const DTES64 = 1 << 2;
-        /// MONITOR/MWAIT. A value of 1 indicates the processor supports this feature.
-        const MONITOR = 1 << 3;
-        /// CPL Qualified Debug Store. A value of 1 indicates the processor supports the extensions to the  Debug Store feature to allow for branch message storage qualified by CPL.
-        const DSCPL = 1 << 4;
-        /// Virtual Machine Extensions. A value of 1 indicates that the processor supports this technology.
-        const VMX = 1 << 5;
-        /// Safer Mode Extensions. A value of 1 indicates that the processor supports this technology. See Chapter 5, Safer Mode Extensions Reference.
-        const SMX = 1 << 6;
-        /// Enhanced Intel SpeedStep® technology. A value of 1 indicates that the processor supports this technology.
-        const EIST = 1 << 7;
-        /// Thermal Monitor 2. A value of 1 indicates whether the processor supports this technology.
-        const TM2 = 1 << 8;
-        /// A value of 1 indicates the presence of the Supplemental Streaming SIMD Extensions 3 (SSSE3). A value of 0 indicates the instruction extensions are not present in the processor
-        const SSSE3 = 1 << 9;
-        /// L1 Context ID. A value of 1 indicates the L1 data cache mode can be set to either adaptive mode or shared mode. A value of 0 indicates this feature is not supported. See definition of the IA32_MISC_ENABLE MSR Bit 24 (L1 Data Cache Context Mode) for details.
-        const CNXTID = 1 << 10;
-        /// A value of 1 indicates the processor supports FMA extensions using YMM state.
-        const FMA = 1 << 12;
-        /// CMPXCHG16B Available. A value of 1 indicates that the feature is available. See the CMPXCHG8B/CMPXCHG16B Compare and Exchange Bytes section. 14
-        const CMPXCHG16B = 1 << 13;
-        /// Perfmon and Debug Capability: A value of 1 indicates the processor supports the performance   and debug feature indication MSR IA32_PERF_CAPABILITIES.
-        const PDCM = 1 << 15;
-        /// Process-context identifiers. A value of 1 indicates that the processor supports PCIDs and the software may set CR4.PCIDE to 1.
-        const PCID = 1 << 17;
-        /// A value of 1 indicates the processor supports the ability to prefetch data from a memory mapped device.
-        const DCA = 1 << 18;
-        /// A value of 1 indicates that the processor supports SSE4.1.
-        const SSE41 = 1 << 19;
-        /// A value of 1 indicates that the processor supports SSE4.2.
-        const SSE42 = 1 << 20;
-        /// A value of 1 indicates that the processor supports x2APIC feature.
-        const X2APIC = 1 << 21;
-        /// A value of 1 indicates that the processor supports MOVBE instruction.
-        const MOVBE = 1 << 22;
-        /// A value of 1 indicates that the processor supports the POPCNT instruction.
-        const POPCNT = 1 << 23;
-        /// A value of 1 indicates that the processors local APIC timer supports one-shot operation using a TSC deadline value.
-        const TSC_DEADLINE = 1 << 24;
-        /// A value of 1 indicates that the processor supports the AESNI instruction extensions.
-        const AESNI = 1 << 25;
-        /// A value of 1 indicates that the processor supports the XSAVE/XRSTOR processor extended states feature, the XSETBV/XGETBV instructions, and XCR0.
-        const XSAVE = 1 << 26;
-        /// A value of 1 indicates that the OS has enabled XSETBV/XGETBV instructions to access XCR0, and support for processor extended state management using XSAVE/XRSTOR.
-        const OSXSAVE = 1 << 27;
-        /// A value of 1 indicates the processor supports the AVX instruction extensions.
-        const AVX = 1 << 28;
-        /// A value of 1 indicates that processor supports 16-bit floating-point conversion instructions.
-        const F16C = 1 << 29;
-        /// A value of 1 indicates that processor supports RDRAND instruction.
-        const RDRAND = 1 << 30;
-        /// A value of 1 indicates the indicates the presence of a hypervisor.
-        const HYPERVISOR = 1 << 31;
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    struct ExtendedFeaturesEbx: u32 {
+        /// FSGSBASE. Supports RDFSBASE/RDGSBASE/WRFSBASE/WRGSBASE if 1. (Bit 00)
+        const FSGSBASE = 1 << 0;
+        /// IA32_TSC_ADJUST MSR is supported if 1. (Bit 01)
+        const ADJUST_MSR = 1 << 1;
+        /// Bit 02: SGX. Supports Intel® Software Guard Extensions (Intel® SGX Extensions) if 1.
+        const SGX = 1 << 2;
+        /// BMI1 (Bit 03)
+        const BMI1 = 1 << 3;
+        /// HLE (Bit 04)
+        const HLE = 1 << 4;
+        /// AVX2 (Bit 05)
+        const AVX2 = 1 << 5;
+        /// FDP_EXCPTN_ONLY. x87 FPU Data Pointer updated only on x87 exceptions if 1.
+        const FDP = 1 << 6;
+        /// SMEP. Supports Supervisor-Mode Execution Prevention if 1. (Bit 07)
+        const SMEP = 1 << 7;
+        /// BMI2 (Bit 08)
+        const BMI2 = 1 << 8;
+        /// Supports Enhanced REP MOVSB/STOSB if 1. (Bit 09)
+        const REP_MOVSB_STOSB = 1 << 9;
+        /// INVPCID. If 1, supports INVPCID instruction for system software that manages process-context identifiers. (Bit 10)
+        const INVPCID = 1 << 10;
+        /// RTM (Bit 11)
+        const RTM = 1 << 11;
+        /// Supports Intel Resource Director Technology (RDT) Monitoring. (Bit 12)
+        const RDTM = 1 << 12;
+        /// Deprecates FPU CS and FPU DS values if 1. (Bit 13)
+        const DEPRECATE_FPU_CS_DS = 1 << 13;
+        /// Deprecates FPU CS and FPU DS values if 1. (Bit 14)
+        const MPX = 1 << 14;
+        /// Supports Intel Resource Director Technology (RDT) Allocation capability if 1.
+        const RDTA = 1 << 15;
+        /// Bit 16: AVX512F.
+        const AVX512F = 1 << 16;
+        /// Bit 17: AVX512DQ.
+        const AVX512DQ = 1 << 17;
+        /// Supports RDSEED.
+        const RDSEED = 1 << 18;
+        /// Supports ADX.
+        const ADX = 1 << 19;
+        /// SMAP. Supports Supervisor-Mode Access Prevention (and the CLAC/STAC instructions) if 1.
+        const SMAP = 1 << 20;
+        /// Bit 21: AVX512_IFMA.
+        const AVX512_IFMA = 1 << 21;
+        // Bit 22: Reserved.
+        /// Bit 23: CLFLUSHOPT
+        const CLFLUSHOPT = 1 << 23;
+        /// Bit 24: CLWB.
+        const CLWB = 1 << 24;
+        /// Bit 25: Intel Processor Trace
+        const PROCESSOR_TRACE = 1 << 25;
+        /// Bit 26: AVX512PF. (Intel® Xeon Phi™ only.)
+        const AVX512PF = 1 << 26;
+        /// Bit 27: AVX512ER. (Intel® Xeon Phi™ only.)
+        const AVX512ER = 1 << 27;
+        /// Bit 28: AVX512CD.
+        const AVX512CD = 1 << 28;
+        /// Bit 29: Intel SHA Extensions
+        const SHA = 1 << 29;
+        /// Bit 30: AVX512BW.
+        const AVX512BW = 1 << 30;
+        /// Bit 31: AVX512VL.
+        const AVX512VL = 1 << 31;
+    }
+}
 
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    struct ExtendedFeaturesEcx: u32 {
+        /// Bit 0: Prefetch WT1. (Intel® Xeon Phi™ only).
+        const PREFETCHWT1 = 1 << 0;
 
-        // EDX flags
+        // Bit 01: AVX512_VBMI
+        const AVX512VBMI = 1 << 1;
 
-        /// Floating Point Unit On-Chip. The processor contains an x87 FPU.
-        const FPU = 1 << 32;
-        /// Virtual 8086 Mode Enhancements. Virtual 8086 mode enhancements, including CR4.VME for controlling the feature, CR4.PVI for protected mode virtual interrupts, software interrupt indirection, expansion of the TSS with the software indirection bitmap, and EFLAGS.VIF and EFLAGS.VIP flags.
-        const VME = 1 << (32 + 1);
-        /// Debugging Extensions. Support for I/O breakpoints, including CR4.DE for controlling the feature, and optional trapping of accesses to DR4 and DR5.
-        const DE = 1 << (32 + 2);
-        /// Page Size Extension. Large pages of size 4 MByte are supported, including CR4.PSE for controlling the feature, the defined dirty bit in PDE (Page Directory Entries), optional reserved bit trapping in CR3, PDEs, and PTEs.
-        const PSE = 1 << (32 + 3);
-        /// Time Stamp Counter. The RDTSC instruction is supported, including CR4.TSD for controlling privilege.
-        const TSC = 1 << (32 + 4);
-        /// Model Specific Registers RDMSR and WRMSR Instructions. The RDMSR and WRMSR instructions are supported. Some of the MSRs are implementation dependent.
-        const MSR = 1 << (32 + 5);
-        /// Physical Address Extension. Physical addresses greater than 32 bits are supported: extended page table entry formats, an extra level in the page translation tables is defined, 2-MByte pages are supported instead of 4 Mbyte pages if PAE bit is 1.
-        const PAE = 1 << (32 + 6);
-        /// Machine Check Exception. Exception 18 is defined for Machine Checks, including CR4.MCE for controlling the feature. This feature does not define the model-specific implementations of machine-check error logging, reporting, and processor shutdowns. Machine Check exception handlers may have to depend on processor version to do model specific processing of the exception, or test for the presence of the Machine Check feature.
-        const MCE = 1 << (32 + 7);
-        /// CMPXCHG8B Instruction. The compare-and-exchange 8 bytes (64 bits) instruction is supported (implicitly locked and atomic).
-        const CX8 = 1 << (32 + 8);
-        /// APIC On-Chip. The processor contains an Advanced Programmable Interrupt Controller (APIC), responding to memory mapped commands in the physical address range FFFE0000H to FFFE0FFFH (by default - some processors permit the APIC to be relocated).
-        const APIC = 1 << (32 + 9);
-        /// SYSENTER and SYSEXIT Instructions. The SYSENTER and SYSEXIT and associated MSRs are supported.
-        const SEP = 1 << (32 + 11);
-        /// Memory Type Range Registers. MTRRs are supported. The MTRRcap MSR contains feature bits that describe what memory types are supported, how many variable MTRRs are supported, and whether fixed MTRRs are supported.
-        const MTRR = 1 << (32 + 12);
-        /// Page Global Bit. The global bit is supported in paging-structure entries that map a page, indicating TLB entries that are common to different processes and need not be flushed. The CR4.PGE bit controls this feature.
-        const PGE = 1 << (32 + 13);
-        /// Machine Check Architecture. The Machine Check exArchitecture, which provides a compatible mechanism for error reporting in P6 family, Pentium 4, Intel Xeon processors, and future processors, is supported. The MCG_CAP MSR contains feature bits describing how many banks of error reporting MSRs are supported.
-        const MCA = 1 << (32 + 14);
-        /// Conditional Move Instructions. The conditional move instruction CMOV is supported. In addition, if x87 FPU is present as indicated by the CPUID.FPU feature bit, then the FCOMI and FCMOV instructions are supported
-        const CMOV = 1 << (32 + 15);
-        /// Page Attribute Table. Page Attribute Table is supported. This feature augments the Memory Type Range Registers (MTRRs), allowing an operating system to specify attributes of memory accessed through a linear address on a 4KB granularity.
-        const PAT = 1 << (32 + 16);
-        /// 36-Bit Page Size Extension. 4-MByte pages addressing physical memory beyond 4 GBytes are supported with 32-bit paging. This feature indicates that upper bits of the physical address of a 4-MByte page are encoded in bits 20:13 of the page-directory entry. Such physical addresses are limited by MAXPHYADDR and may be up to 40 bits in size.
-        const PSE36 = 1 << (32 + 17);
-        /// Processor Serial Number. The processor supports the 96-bit processor identification number feature and the feature is enabled.
-        const PSN = 1 << (32 + 18);
-        /// CLFLUSH Instruction. CLFLUSH Instruction is supported.
-        const CLFSH = 1 << (32 + 19);
-        /// Debug Store. The processor supports the ability to write debug information into a memory resident buffer. This feature is used by the branch trace store (BTS) and precise event-based sampling (PEBS) facilities (see Chapter 23, Introduction to Virtual-Machine Extensions, in the Intel® 64 and IA-32 Architectures Software Developers Manual, Volume 3C).
-        const DS = 1 << (32 + 21);
-        /// Thermal Monitor and Software Controlled Clock Facilities. The processor implements internal MSRs that allow processor temperature to be monitored and processor performance to be modulated in predefined duty cycles under software control.
-        const ACPI = 1 << (32 + 22);
-        /// Intel MMX Technology. The processor supports the Intel MMX technology.
-        const MMX = 1 << (32 + 23);
-        /// FXSAVE and FXRSTOR Instructions. The FXSAVE and FXRSTOR instructions are supported for fast save and restore of the floating point context. Presence of this bit also indicates that CR4.OSFXSR is available for an operating system to indicate that it supports the FXSAVE and FXRSTOR instructions.
-        const FXSR = 1 << (32 + 24);
-        /// SSE. The processor supports the SSE extensions.
-        const SSE = 1 << (32 + 25);
-        /// SSE2. The processor supports the SSE2 extensions.
-        const SSE2 = 1 << (32 + 26);
-        /// Self Snoop. The processor supports the management of conflicting memory types by performing a snoop of its own cache structure for transactions issued to the bus.
-        const SS = 1 << (32 + 27);
-        /// Max APIC IDs reserved field is Valid. A value of 0 for HTT indicates there is only a single logical processor in the package and software should assume only a single APIC ID is reserved.  A value of 1 for HTT indicates the value in CPUID.1.EBX[23:16] (the Maximum number of addressable IDs for logical processors in this package) is valid for the package.
-        const HTT = 1 << (32 + 28);
-        /// Thermal Monitor. The processor implements the thermal monitor automatic thermal control circuitry (TCC).
-        const TM = 1 << (32 + 29);
-        /// Pending Break Enable. The processor supports the use of the FERR#/PBE# pin when the processor is in the stop-clock state (STPCLK# is asserted) to signal the processor that an interrupt is pending and that the processor should return to normal operation to handle the interrupt. Bit 10 (PBE enable) in the IA32_MISC_ENABLE MSR enables this capability.
-        const PBE = 1 << (32 + 31);
+        /// Bit 02: UMIP. Supports user-mode instruction prevention if 1.
+        const UMIP = 1 << 2;
+
+        /// Bit 03: PKU. Supports protection keys for user-mode pages if 1.
+        const PKU = 1 << 3;
+
+        /// Bit 04: OSPKE. If 1, OS has set CR4.PKE to enable protection keys (and the RDPKRU/WRPKRU instruc-tions).
+        const OSPKE = 1 << 4;
+
+        /// Bit 05: WAITPKG. Supports TPAUSE, UMONITOR and UMWAIT if 1.
+        const WAITPKG = 1 << 5;
+
+        /// Bit 07: CET_SS. Supports CET shadow stack features if 1.
+        const CET_SS = 1 << 7;
+
+        /// Bit 11: AVX512_VNNI
+        const AVX512VNNI = 1 << 11;
+
+        /// Bit 13: TME_EN. Supports Total Memory Encryption if 1.
+        const TME_EN = 1 << 13;
+
+        /// Bit 16: LA57. Supports 57-bit linear addresses and five-level paging if 1.
+        const LA57 = 1 << 16;
+
+        // Bits 21 - 17: The value of MAWAU used by the BNDLDX and BNDSTX instructions in 64-bit mode.
+
+
+        /// Bit 22: RDPID. RDPID and IA32_TSC_AUX are available if 1.
+        const RDPID = 1 << 22;
+
+        // Bits 26 - 23: Reserved.
+
+        /// Bit 27: MOVDIRI. Supports MOVDIRI if 1.
+        const MOVDIRI = 1 << 27;
+
+        /// Bit 28: MOVDIR64B. Supports MOVDIR64B if 1.
+        const MOVDIR64B = 1 << 28;
+
+        /// Bit 29: ENQCMD. Supports Enqueue Stores if 1.
+        const ENQCMD = 1 << 29;
+
+        /// Bit 30: SGX_LC. Supports SGX Launch Configuration if 1.
+        const SGX_LC = 1 << 30;
     }
 }
 
-/// Iterator over caches (LEAF=0x04).
-///
-/// Yields a [CacheParameter] for each cache.
+/// Direct cache access info (LEAF=0x09).
 ///
 /// # Platforms
-/// ❌ AMD ✅ Intel
-#[derive(Clone)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct CacheParametersIter {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
-    current: u32,
+/// ❌ AMD (reserved) ✅ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DirectCacheAccessInfo {
+    eax: u32,
 }
 
-impl Iterator for CacheParametersIter {
-    type Item = CacheParameter;
+impl DirectCacheAccessInfo {
+    /// Decodes the registers of LEAF=0x09.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self { eax: data.eax }
+    }
 
-    /// Iterate over all cache info subleafs for this CPU.
-    ///
-    /// # Note
-    /// cpuid is called every-time we advance the iterator to get information
-    /// about the next cache.
-    fn next(&mut self) -> Option<CacheParameter> {
-        let res = self.read.cpuid2(EAX_CACHE_PARAMETERS, self.current);
-        let cp = CacheParameter {
-            eax: res.eax,
-            ebx: res.ebx,
-            ecx: res.ecx,
-            edx: res.edx,
-        };
+    /// Value of bits \[31:0\] of IA32_PLATFORM_DCA_CAP MSR (address 1F8H)
+    pub fn get_dca_cap_value(&self) -> u32 {
+        self.eax
+    }
 
-        match cp.cache_type() {
-            CacheType::Null => None,
-            CacheType::Reserved => None,
-            _ => {
-                self.current += 1;
-                Some(cp)
-            }
+    check_bit_fn!(
+        doc = "Is DCA enabled by the BIOS (bit 0 of IA32_PLATFORM_DCA_CAP)?",
+        is_dca_enabled,
+        eax,
+        0
+    );
+
+    /// Encode this struct back into the registers of leaf 9.
+    ///
+    /// EBX, ECX and EDX are reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
         }
     }
+
+    set_bits_fn!(set_dca_cap_value, get_dca_cap_value, eax, 0, 31, u32);
+    set_bit_fn!(set_dca_enabled, is_dca_enabled, eax, 0);
 }
 
-impl Debug for CacheParametersIter {
+impl Debug for DirectCacheAccessInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        let mut debug = f.debug_list();
-        self.clone().for_each(|ref item| {
-            debug.entry(item);
-        });
-        debug.finish()
+        f.debug_struct("DirectCacheAccessInfo")
+            .field("dca_cap_value", &self.get_dca_cap_value())
+            .field("is_dca_enabled", &self.is_dca_enabled())
+            .finish()
     }
 }
 
-/// Information about an individual cache in the hierarchy.
+/// Info about performance monitoring -- how many counters etc. (LEAF=0x0A)
 ///
 /// # Platforms
 /// ❌ AMD ✅ Intel
-#[derive(Copy, Clone, Eq, PartialEq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct CacheParameter {
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PerformanceMonitoringInfo {
     eax: u32,
-    ebx: u32,
+    ebx: PerformanceMonitoringFeaturesEbx,
     ecx: u32,
     edx: u32,
 }
 
-/// Info about a what a given cache caches (instructions, data, etc.)
-#[derive(PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub enum CacheType {
-    /// Null - No more caches
-    Null = 0,
-    /// Data cache
-    Data,
-    /// Instruction cache
-    Instruction,
-    /// Data and Instruction cache
-    Unified,
-    /// 4-31 = Reserved
-    Reserved,
-}
+impl PerformanceMonitoringInfo {
+    /// Decodes the registers of LEAF=0x0A.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: PerformanceMonitoringFeaturesEbx { bits: data.ebx },
+            ecx: data.ecx,
+            edx: data.edx,
+        }
+    }
+
+    /// Version ID of architectural performance monitoring. (Bits 07 - 00)
+    pub fn version_id(&self) -> u8 {
+        get_bits(self.eax, 0, 7) as u8
+    }
+
+    /// Number of general-purpose performance monitoring counter per logical processor. (Bits 15- 08)
+    pub fn number_of_counters(&self) -> u8 {
+        get_bits(self.eax, 8, 15) as u8
+    }
+
+    /// Bit width of general-purpose, performance monitoring counter. (Bits 23 - 16)
+    pub fn counter_bit_width(&self) -> u8 {
+        get_bits(self.eax, 16, 23) as u8
+    }
+
+    /// Length of EBX bit vector to enumerate architectural performance monitoring events. (Bits 31 - 24)
+    pub fn ebx_length(&self) -> u8 {
+        get_bits(self.eax, 24, 31) as u8
+    }
+
+    /// Number of fixed-function performance counters (if Version ID > 1). (Bits 04 - 00)
+    pub fn fixed_function_counters(&self) -> u8 {
+        get_bits(self.edx, 0, 4) as u8
+    }
+
+    /// Bit width of fixed-function performance counters (if Version ID > 1). (Bits 12- 05)
+    pub fn fixed_function_counters_bit_width(&self) -> u8 {
+        get_bits(self.edx, 5, 12) as u8
+    }
+
+    check_bit_fn!(
+        doc = "AnyThread deprecation",
+        has_any_thread_deprecation,
+        edx,
+        15
+    );
+
+    check_flag!(
+        doc = "Core cycle event not available if 1.",
+        is_core_cyc_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::CORE_CYC_EV_UNAVAILABLE
+    );
+
+    check_flag!(
+        doc = "Instruction retired event not available if 1.",
+        is_inst_ret_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::INST_RET_EV_UNAVAILABLE
+    );
+
+    check_flag!(
+        doc = "Reference cycles event not available if 1.",
+        is_ref_cycle_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::REF_CYC_EV_UNAVAILABLE
+    );
+
+    check_flag!(
+        doc = "Last-level cache reference event not available if 1.",
+        is_cache_ref_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::CACHE_REF_EV_UNAVAILABLE
+    );
+
+    check_flag!(
+        doc = "Last-level cache misses event not available if 1.",
+        is_ll_cache_miss_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::LL_CACHE_MISS_EV_UNAVAILABLE
+    );
 
-impl fmt::Display for CacheType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let typ = match self {
-            CacheType::Null => "Null",
-            CacheType::Data => "Data",
-            CacheType::Instruction => "Instruction",
-            CacheType::Unified => "Unified",
-            CacheType::Reserved => "Reserved",
-        };
+    check_flag!(
+        doc = "Branch instruction retired event not available if 1.",
+        is_branch_inst_ret_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::BRANCH_INST_RET_EV_UNAVAILABLE
+    );
 
-        f.write_str(typ)
-    }
-}
+    check_flag!(
+        doc = "Branch mispredict retired event not available if 1.",
+        is_branch_midpred_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::BRANCH_MISPRED_EV_UNAVAILABLE
+    );
 
-impl CacheParameter {
-    /// Cache Type
-    pub fn cache_type(&self) -> CacheType {
-        let typ = get_bits(self.eax, 0, 4) as u8;
-        match typ {
-            0 => CacheType::Null,
-            1 => CacheType::Data,
-            2 => CacheType::Instruction,
-            3 => CacheType::Unified,
-            _ => CacheType::Reserved,
-        }
-    }
+    check_flag!(
+        doc = "Top-down slots event not available if 1.",
+        is_topdown_slots_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::TOPDOWN_SLOTS_EV_UNAVAILABLE
+    );
 
-    /// Cache Level (starts at 1)
-    pub fn level(&self) -> u8 {
-        get_bits(self.eax, 5, 7) as u8
+    /// Bitmap of the architectural events that are *not* available (EBX).
+    ///
+    /// Bit `i` corresponds to architectural event `i` (bit 0 is core cycles,
+    /// see the `is_*_ev_unavailable` functions). Only the first
+    /// [`Self::ebx_length`] bits are valid, the others are cleared.
+    pub fn unavailable_events(&self) -> u32 {
+        self.ebx.bits() & self.valid_events()
     }
 
-    /// Self Initializing cache level (does not need SW initialization).
-    pub fn is_self_initializing(&self) -> bool {
-        get_bits(self.eax, 8, 8) == 1
+    /// Bitmap of the architectural events that are available.
+    ///
+    /// This is the complement of [`Self::unavailable_events`] within the first
+    /// [`Self::ebx_length`] events.
+    pub fn available_events(&self) -> u32 {
+        !self.ebx.bits() & self.valid_events()
     }
 
-    /// Fully Associative cache
-    pub fn is_fully_associative(&self) -> bool {
-        get_bits(self.eax, 9, 9) == 1
+    /// Is architectural event `index` available?
+    ///
+    /// Events beyond [`Self::ebx_length`] are not enumerated and reported as
+    /// unavailable.
+    pub fn is_event_available(&self, index: u8) -> bool {
+        index < 32 && self.available_events() & (1 << index) != 0
+    }
+
+    /// Mask of the events enumerated by the EBX bit vector.
+    fn valid_events(&self) -> u32 {
+        match self.ebx_length() {
+            0 => 0,
+            len if len >= 32 => u32::MAX,
+            len => (1 << len) - 1,
+        }
     }
 
-    /// Maximum number of addressable IDs for logical processors sharing this cache
-    pub fn max_cores_for_cache(&self) -> usize {
-        (get_bits(self.eax, 14, 25) + 1) as usize
+    /// Supported fixed-function counters bitmap (ECX, if Version ID > 4).
+    ///
+    /// Use [`Self::fixed_counters`] for a bitmap that also includes the
+    /// contiguous counters reported by [`Self::fixed_function_counters`].
+    pub fn fixed_counters_bitmap(&self) -> u32 {
+        self.ecx
     }
 
-    /// Maximum number of addressable IDs for processor cores in the physical package
-    pub fn max_cores_for_package(&self) -> usize {
-        (get_bits(self.eax, 26, 31) + 1) as usize
+    /// Bitmap of all supported fixed-function counters.
+    ///
+    /// Fixed counter `i` is supported if bit `i` of
+    /// [`Self::fixed_counters_bitmap`] is set or `i` is less than
+    /// [`Self::fixed_function_counters`].
+    pub fn fixed_counters(&self) -> u32 {
+        let contiguous = match self.fixed_function_counters() {
+            n if n >= 32 => u32::MAX,
+            n => (1 << n) - 1,
+        };
+        self.ecx | contiguous
     }
 
-    /// System Coherency Line Size (Bits 11-00)
-    pub fn coherency_line_size(&self) -> usize {
-        (get_bits(self.ebx, 0, 11) + 1) as usize
+    /// Is fixed-function counter `index` supported?
+    pub fn is_fixed_counter_supported(&self, index: u8) -> bool {
+        index < 32 && self.fixed_counters() & (1 << index) != 0
     }
 
-    /// Physical Line partitions (Bits 21-12)
-    pub fn physical_line_partitions(&self) -> usize {
-        (get_bits(self.ebx, 12, 21) + 1) as usize
+    /// Encode this struct back into the registers of leaf 0xA.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx.bits(),
+            ecx: self.ecx,
+            edx: self.edx,
+        }
     }
 
-    /// Ways of associativity (Bits 31-22)
-    pub fn associativity(&self) -> usize {
-        (get_bits(self.ebx, 22, 31) + 1) as usize
-    }
+    set_bits_fn!(set_version_id, version_id, eax, 0, 7, u8);
+    set_bits_fn!(set_number_of_counters, number_of_counters, eax, 8, 15, u8);
+    set_bits_fn!(set_counter_bit_width, counter_bit_width, eax, 16, 23, u8);
+    set_bits_fn!(set_ebx_length, ebx_length, eax, 24, 31, u8);
+    set_bits_fn!(
+        set_fixed_function_counters,
+        fixed_function_counters,
+        edx,
+        0,
+        4,
+        u8
+    );
+    set_bits_fn!(
+        set_fixed_function_counters_bit_width,
+        fixed_function_counters_bit_width,
+        edx,
+        5,
+        12,
+        u8
+    );
+    set_bit_fn!(
+        set_any_thread_deprecation,
+        has_any_thread_deprecation,
+        edx,
+        15
+    );
+    set_flag!(
+        set_core_cyc_ev_unavailable,
+        is_core_cyc_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::CORE_CYC_EV_UNAVAILABLE
+    );
+    set_flag!(
+        set_inst_ret_ev_unavailable,
+        is_inst_ret_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::INST_RET_EV_UNAVAILABLE
+    );
+    set_flag!(
+        set_ref_cycle_ev_unavailable,
+        is_ref_cycle_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::REF_CYC_EV_UNAVAILABLE
+    );
+    set_flag!(
+        set_cache_ref_ev_unavailable,
+        is_cache_ref_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::CACHE_REF_EV_UNAVAILABLE
+    );
+    set_flag!(
+        set_ll_cache_miss_ev_unavailable,
+        is_ll_cache_miss_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::LL_CACHE_MISS_EV_UNAVAILABLE
+    );
+    set_flag!(
+        set_branch_inst_ret_ev_unavailable,
+        is_branch_inst_ret_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::BRANCH_INST_RET_EV_UNAVAILABLE
+    );
+    set_flag!(
+        set_branch_midpred_ev_unavailable,
+        is_branch_midpred_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::BRANCH_MISPRED_EV_UNAVAILABLE
+    );
+    set_flag!(
+        set_topdown_slots_ev_unavailable,
+        is_topdown_slots_ev_unavailable,
+        ebx,
+        PerformanceMonitoringFeaturesEbx::TOPDOWN_SLOTS_EV_UNAVAILABLE
+    );
+    set_bits_fn!(
+        set_fixed_counters_bitmap,
+        fixed_counters_bitmap,
+        ecx,
+        0,
+        31,
+        u32
+    );
+}
 
-    /// Number of Sets (Bits 31-00)
-    pub fn sets(&self) -> usize {
-        (self.ecx + 1) as usize
+impl Debug for PerformanceMonitoringInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PerformanceMonitoringInfo")
+            .field("version_id", &self.version_id())
+            .field("number_of_counters", &self.number_of_counters())
+            .field("counter_bit_width", &self.counter_bit_width())
+            .field("ebx_length", &self.ebx_length())
+            .field("fixed_function_counters", &self.fixed_function_counters())
+            .field(
+                "fixed_function_counters_bit_width",
+                &self.fixed_function_counters_bit_width(),
+            )
+            .field("unavailable_events", &self.unavailable_events())
+            .field("fixed_counters", &self.fixed_counters())
+            .finish()
     }
+}
 
-    /// Write-Back Invalidate/Invalidate (Bit 0)
-    /// False: WBINVD/INVD from threads sharing this cache acts upon lower level caches for threads sharing this cache.
-    /// True: WBINVD/INVD is not guaranteed to act upon lower level caches of non-originating threads sharing this cache.
-    pub fn is_write_back_invalidate(&self) -> bool {
-        get_bits(self.edx, 0, 0) == 1
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    struct PerformanceMonitoringFeaturesEbx: u32 {
+        /// Core cycle event not available if 1. (Bit 0)
+        const CORE_CYC_EV_UNAVAILABLE = 1 << 0;
+        /// Instruction retired event not available if 1. (Bit 01)
+        const INST_RET_EV_UNAVAILABLE = 1 << 1;
+        /// Reference cycles event not available if 1. (Bit 02)
+        const REF_CYC_EV_UNAVAILABLE = 1 << 2;
+        /// Last-level cache reference event not available if 1. (Bit 03)
+        const CACHE_REF_EV_UNAVAILABLE = 1 << 3;
+        /// Last-level cache misses event not available if 1. (Bit 04)
+        const LL_CACHE_MISS_EV_UNAVAILABLE = 1 << 4;
+        /// Branch instruction retired event not available if 1. (Bit 05)
+        const BRANCH_INST_RET_EV_UNAVAILABLE = 1 << 5;
+        /// Branch mispredict retired event not available if 1. (Bit 06)
+        const BRANCH_MISPRED_EV_UNAVAILABLE = 1 << 6;
+        /// Top-down slots event not available if 1. (Bit 07)
+        const TOPDOWN_SLOTS_EV_UNAVAILABLE = 1 << 7;
     }
+}
 
-    /// Cache Inclusiveness (Bit 1)
-    /// False: Cache is not inclusive of lower cache levels.
-    /// True: Cache is inclusive of lower cache levels.
-    pub fn is_inclusive(&self) -> bool {
-        get_bits(self.edx, 1, 1) == 1
-    }
+/// Information about topology (LEAF=0x0B).
+///
+/// Iterates over the system topology in order to retrieve more system
+/// information at each level of the topology: how many cores and what kind of
+/// cores
+///
+/// # Platforms
+/// ✅ AMD ✅ Intel
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtendedTopologyIter<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    subleafs: Subleafs,
+    level: u32,
+    end: u32,
+}
 
-    /// Complex Cache Indexing (Bit 2)
-    /// False: Direct mapped cache.
-    /// True: A complex function is used to index the cache, potentially using all address bits.
-    pub fn has_complex_indexing(&self) -> bool {
-        get_bits(self.edx, 2, 2) == 1
+impl<R: CpuIdReader> ExtendedTopologyIter<R> {
+    fn new(read: R, leaf: u32) -> Self {
+        let subleafs = Subleafs::read(&read, leaf, 0, |res| {
+            ExtendedTopologyLevel::from_registers(res).level_type() != TopologyType::Invalid
+        });
+        Self {
+            read,
+            level: 0,
+            end: subleafs.end,
+            subleafs,
+        }
     }
-}
 
-impl Debug for CacheParameter {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("CacheParameter")
-            .field("cache_type", &self.cache_type())
-            .field("level", &self.level())
-            .field("is_self_initializing", &self.is_self_initializing())
-            .field("is_fully_associative", &self.is_fully_associative())
-            .field("max_cores_for_cache", &self.max_cores_for_cache())
-            .field("max_cores_for_package", &self.max_cores_for_package())
-            .field("coherency_line_size", &self.coherency_line_size())
-            .field("physical_line_partitions", &self.physical_line_partitions())
-            .field("associativity", &self.associativity())
-            .field("sets", &self.sets())
-            .field("is_write_back_invalidate", &self.is_write_back_invalidate())
-            .field("is_inclusive", &self.is_inclusive())
-            .field("has_complex_indexing", &self.has_complex_indexing())
-            .finish()
+    /// The leaf the levels are read from: 0x1F (V2 extended topology) or 0x0B.
+    pub fn leaf(&self) -> u32 {
+        self.subleafs.leaf
     }
 }
 
-/// Information about how monitor/mwait works on this CPU (LEAF=0x05).
+/// Gives information about the current level in the topology.
 ///
-/// # Platforms
-/// 🟡 AMD ✅ Intel
-#[derive(Eq, PartialEq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct MonitorMwaitInfo {
+/// How many cores, what type etc.
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtendedTopologyLevel {
     eax: u32,
     ebx: u32,
     ecx: u32,
     edx: u32,
 }
 
-impl MonitorMwaitInfo {
-    /// Smallest monitor-line size in bytes (default is processor's monitor granularity)
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn smallest_monitor_line(&self) -> u16 {
-        get_bits(self.eax, 0, 15) as u16
+impl fmt::Debug for ExtendedTopologyLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExtendedTopologyLevel")
+            .field("processors", &self.processors())
+            .field("number", &self.level_number())
+            .field("type", &self.level_type())
+            .field("x2apic_id", &self.x2apic_id())
+            .field("next_apic_id", &self.shift_right_for_next_apic_id())
+            .finish()
     }
+}
 
-    /// Largest monitor-line size in bytes (default is processor's monitor granularity
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn largest_monitor_line(&self) -> u16 {
+impl ExtendedTopologyLevel {
+    /// Decodes the registers of a subleaf of LEAF=0x0B (or LEAF=0x1F).
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+            edx: data.edx,
+        }
+    }
+
+    /// Number of logical processors at this level type.
+    /// The number reflects configuration as shipped.
+    pub fn processors(&self) -> u16 {
         get_bits(self.ebx, 0, 15) as u16
     }
 
-    ///  Enumeration of Monitor-Mwait extensions (beyond EAX and EBX registers) supported
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn extensions_supported(&self) -> bool {
-        get_bits(self.ecx, 0, 0) == 1
+    /// Level number.
+    pub fn level_number(&self) -> u8 {
+        get_bits(self.ecx, 0, 7) as u8
     }
 
-    ///  Supports treating interrupts as break-event for MWAIT, even when interrupts disabled
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn interrupts_as_break_event(&self) -> bool {
-        get_bits(self.ecx, 1, 1) == 1
+    /// Level type.
+    pub fn level_type(&self) -> TopologyType {
+        TopologyType::from(get_bits(self.ecx, 8, 15) as u8)
+    }
+
+    /// x2APIC ID the current logical processor. (Bits 31-00)
+    pub fn x2apic_id(&self) -> u32 {
+        self.edx
     }
 
-    /// Number of C0 sub C-states supported using MWAIT (Bits 03 - 00)
-    ///
-    /// # Platforms
-    /// ❌ AMD (undefined/reserved) ✅ Intel
-    pub fn supported_c0_states(&self) -> u16 {
-        get_bits(self.edx, 0, 3) as u16
+    /// Number of bits to shift right on x2APIC ID to get a unique topology ID of the next level type. (Bits 04-00)
+    /// All logical processors with the same next level ID share current level.
+    pub fn shift_right_for_next_apic_id(&self) -> u32 {
+        get_bits(self.eax, 0, 4)
     }
 
-    /// Number of C1 sub C-states supported using MWAIT (Bits 07 - 04)
-    ///
-    /// # Platforms
-    /// ❌ AMD (undefined/reserved) ✅ Intel
-    pub fn supported_c1_states(&self) -> u16 {
-        get_bits(self.edx, 4, 7) as u16
+    /// Encode this level back into the registers of its leaf 0xB (or 0x1F) subleaf.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
     }
 
-    /// Number of C2 sub C-states supported using MWAIT (Bits 11 - 08)
-    ///
-    /// # Platforms
-    /// ❌ AMD (undefined/reserved) ✅ Intel
-    pub fn supported_c2_states(&self) -> u16 {
-        get_bits(self.edx, 8, 11) as u16
+    /// Set the type reported by [`Self::level_type`].
+    pub fn set_level_type(&mut self, typ: TopologyType) {
+        let typ = match typ {
+            TopologyType::Invalid => 0,
+            TopologyType::SMT => 1,
+            TopologyType::Core => 2,
+            TopologyType::Module => 3,
+            TopologyType::Tile => 4,
+            TopologyType::Die => 5,
+            TopologyType::DieGrp => 6,
+            TopologyType::Unknown(t) => t as u32,
+        };
+        set_bits(&mut self.ecx, 8, 15, typ);
     }
 
-    /// Number of C3 sub C-states supported using MWAIT (Bits 15 - 12)
-    ///
-    /// # Platforms
-    /// ❌ AMD (undefined/reserved) ✅ Intel
-    pub fn supported_c3_states(&self) -> u16 {
-        get_bits(self.edx, 12, 15) as u16
+    set_bits_fn!(set_processors, processors, ebx, 0, 15, u16);
+    set_bits_fn!(set_level_number, level_number, ecx, 0, 7, u8);
+    set_bits_fn!(set_x2apic_id, x2apic_id, edx, 0, 31, u32);
+    set_bits_fn!(
+        set_shift_right_for_next_apic_id,
+        shift_right_for_next_apic_id,
+        eax,
+        0,
+        4,
+        u32
+    );
+}
+
+/// What type of core we have at this level in the topology (real CPU or hyper-threaded).
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TopologyType {
+    Invalid,
+    /// Hyper-thread (Simultaneous multithreading)
+    SMT,
+    Core,
+    Module,
+    Tile,
+    Die,
+    /// Die group.
+    DieGrp,
+    /// A level type not (yet) known to this library.
+    Unknown(u8),
+}
+
+impl From<u8> for TopologyType {
+    fn from(value: u8) -> TopologyType {
+        match value {
+            0 => TopologyType::Invalid,
+            1 => TopologyType::SMT,
+            2 => TopologyType::Core,
+            3 => TopologyType::Module,
+            4 => TopologyType::Tile,
+            5 => TopologyType::Die,
+            6 => TopologyType::DieGrp,
+            x => TopologyType::Unknown(x),
+        }
     }
+}
 
-    /// Number of C4 sub C-states supported using MWAIT (Bits 19 - 16)
-    ///
-    /// # Platforms
-    /// ❌ AMD (undefined/reserved) ✅ Intel
-    pub fn supported_c4_states(&self) -> u16 {
-        get_bits(self.edx, 16, 19) as u16
+impl fmt::Display for TopologyType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data = match self {
+            TopologyType::Invalid => "Invalid",
+            TopologyType::SMT => "SMT",
+            TopologyType::Core => "Core",
+            TopologyType::Module => "Module",
+            TopologyType::Tile => "Tile",
+            TopologyType::Die => "Die",
+            TopologyType::DieGrp => "DieGrp",
+            TopologyType::Unknown(t) => {
+                return write!(f, "Unknown({})", t);
+            }
+        };
+
+        f.write_str(data)
     }
+}
 
-    /// Number of C5 sub C-states supported using MWAIT (Bits 23 - 20)
-    ///
-    /// # Platforms
-    /// ❌ AMD (undefined/reserved) ✅ Intel
-    pub fn supported_c5_states(&self) -> u16 {
-        get_bits(self.edx, 20, 23) as u16
+impl<R: CpuIdReader> Iterator for ExtendedTopologyIter<R> {
+    type Item = ExtendedTopologyLevel;
+
+    fn next(&mut self) -> Option<ExtendedTopologyLevel> {
+        if self.level >= self.end {
+            return None;
+        }
+        let res = self.subleafs.get(&self.read, self.level);
+        self.level += 1;
+        Some(ExtendedTopologyLevel::from_registers(res))
     }
 
-    /// Number of C6 sub C-states supported using MWAIT (Bits 27 - 24)
-    ///
-    /// # Platforms
-    /// ❌ AMD (undefined/reserved) ✅ Intel
-    pub fn supported_c6_states(&self) -> u16 {
-        get_bits(self.edx, 24, 27) as u16
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.level) as usize;
+        (len, Some(len))
     }
+}
 
-    /// Number of C7 sub C-states supported using MWAIT (Bits 31 - 28)
-    ///
-    /// # Platforms
-    /// ❌ AMD (undefined/reserved) ✅ Intel
-    pub fn supported_c7_states(&self) -> u16 {
-        get_bits(self.edx, 28, 31) as u16
+impl<R: CpuIdReader> DoubleEndedIterator for ExtendedTopologyIter<R> {
+    fn next_back(&mut self) -> Option<ExtendedTopologyLevel> {
+        if self.level >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(ExtendedTopologyLevel::from_registers(
+            self.subleafs.get(&self.read, self.end),
+        ))
     }
 }
 
-impl Debug for MonitorMwaitInfo {
+impl<R: CpuIdReader> ExactSizeIterator for ExtendedTopologyIter<R> {}
+
+impl<R: CpuIdReader> FusedIterator for ExtendedTopologyIter<R> {}
+
+impl<R: CpuIdReader> Debug for ExtendedTopologyIter<R> {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("MonitorMwaitInfo")
-            .field("smallest_monitor_line", &self.smallest_monitor_line())
-            .field("largest_monitor_line", &self.largest_monitor_line())
-            .field("extensions_supported", &self.extensions_supported())
-            .field(
-                "interrupts_as_break_event",
-                &self.interrupts_as_break_event(),
-            )
-            .field("supported_c0_states", &self.supported_c0_states())
-            .field("supported_c1_states", &self.supported_c1_states())
-            .field("supported_c2_states", &self.supported_c2_states())
-            .field("supported_c3_states", &self.supported_c3_states())
-            .field("supported_c4_states", &self.supported_c4_states())
-            .field("supported_c5_states", &self.supported_c5_states())
-            .field("supported_c6_states", &self.supported_c6_states())
-            .field("supported_c7_states", &self.supported_c7_states())
-            .finish()
+        let mut debug = f.debug_list();
+        self.clone().for_each(|ref item| {
+            debug.entry(item);
+        });
+        debug.finish()
     }
 }
 
-/// Query information about thermal and power management features of the CPU (LEAF=0x06).
+/// Extended CPU topology of AMD CPUs (LEAF=0x8000_0026).
+///
+/// Iterates over the levels of the topology, starting with the core level.
 ///
 /// # Platforms
-/// 🟡 AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct ThermalPowerInfo {
-    eax: ThermalPowerFeaturesEax,
-    ebx: u32,
-    ecx: ThermalPowerFeaturesEcx,
-    _edx: u32,
+/// ✅ AMD ❌ Intel
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtendedCpuTopologyIter<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    subleafs: Subleafs,
+    level: u32,
+    end: u32,
 }
 
-impl ThermalPowerInfo {
-    /// Number of Interrupt Thresholds in Digital Thermal Sensor
-    ///
-    /// # Platforms
-    /// ❌ AMD (undefined/reserved) ✅ Intel
-    pub fn dts_irq_threshold(&self) -> u8 {
-        get_bits(self.ebx, 0, 3) as u8
+impl<R: CpuIdReader> ExtendedCpuTopologyIter<R> {
+    fn new(read: R) -> Self {
+        let subleafs = Subleafs::read(&read, EAX_EXTENDED_CPU_TOPOLOGY, 0, |res| {
+            ExtendedCpuTopologyLevel::from_registers(res).level_type()
+                != ExtendedCpuTopologyType::Invalid
+        });
+        Self {
+            read,
+            level: 0,
+            end: subleafs.end,
+            subleafs,
+        }
     }
+}
 
-    /// Digital temperature sensor is supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_dts(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::DTS)
+impl<R: CpuIdReader> Iterator for ExtendedCpuTopologyIter<R> {
+    type Item = ExtendedCpuTopologyLevel;
+
+    fn next(&mut self) -> Option<ExtendedCpuTopologyLevel> {
+        if self.level >= self.end {
+            return None;
+        }
+        let res = self.subleafs.get(&self.read, self.level);
+        self.level += 1;
+        Some(ExtendedCpuTopologyLevel::from_registers(res))
     }
 
-    /// Intel Turbo Boost Technology Available (see description of
-    /// IA32_MISC_ENABLE\[38\]).
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_turbo_boost(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::TURBO_BOOST)
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.level) as usize;
+        (len, Some(len))
     }
+}
 
-    /// ARAT. APIC-Timer-always-running feature is supported if set.
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_arat(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::ARAT)
+impl<R: CpuIdReader> DoubleEndedIterator for ExtendedCpuTopologyIter<R> {
+    fn next_back(&mut self) -> Option<ExtendedCpuTopologyLevel> {
+        if self.level >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(ExtendedCpuTopologyLevel::from_registers(
+            self.subleafs.get(&self.read, self.end),
+        ))
     }
+}
 
-    /// PLN. Power limit notification controls are supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_pln(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::PLN)
+impl<R: CpuIdReader> ExactSizeIterator for ExtendedCpuTopologyIter<R> {}
+
+impl<R: CpuIdReader> FusedIterator for ExtendedCpuTopologyIter<R> {}
+
+impl<R: CpuIdReader> Debug for ExtendedCpuTopologyIter<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut debug = f.debug_list();
+        self.clone().for_each(|ref item| {
+            debug.entry(item);
+        });
+        debug.finish()
     }
+}
 
-    /// ECMD. Clock modulation duty cycle extension is supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_ecmd(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::ECMD)
+/// One level of the AMD extended CPU topology (LEAF=0x8000_0026).
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtendedCpuTopologyLevel {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+    edx: u32,
+}
+
+impl ExtendedCpuTopologyLevel {
+    /// Decodes the registers of a subleaf of LEAF=0x8000_0026.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+            edx: data.edx,
+        }
     }
 
-    /// PTM. Package thermal management is supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_ptm(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::PTM)
+    /// Number of bits to shift the extended APIC ID right to get a unique
+    /// topology ID of the next level. (Bits 04-00)
+    pub fn shift_right_for_next_apic_id(&self) -> u32 {
+        get_bits(self.eax, 0, 4)
     }
 
-    /// HWP. HWP base registers (IA32_PM_ENABLE[bit 0], IA32_HWP_CAPABILITIES,
-    /// IA32_HWP_REQUEST, IA32_HWP_STATUS) are supported if set.
+    check_bit_fn!(
+        doc = "Is [`Self::power_efficiency_ranking`] valid at this level?",
+        has_efficiency_ranking,
+        eax,
+        29
+    );
+
+    check_bit_fn!(
+        doc = "Are there different core types (see [`Self::core_type`]) at \
+               this level?",
+        has_heterogeneous_cores,
+        eax,
+        30
+    );
+
+    check_bit_fn!(
+        doc = "Do the instances of this level have a different number of \
+               logical processors?",
+        has_asymmetric_topology,
+        eax,
+        31
+    );
+
+    /// Number of logical processors at this level, as shipped. (Bits 15-00)
     ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_hwp(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::HWP)
+    /// For asymmetric topologies, this is the number of the instance we are
+    /// running on.
+    pub fn processors(&self) -> u16 {
+        get_bits(self.ebx, 0, 15) as u16
     }
 
-    /// HWP Notification. IA32_HWP_INTERRUPT MSR is supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_hwp_notification(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::HWP_NOTIFICATION)
+    /// Power efficiency ranking of the core we are running on (lower is more
+    /// power efficient), if [`Self::has_efficiency_ranking`]. (Bits 23-16)
+    pub fn power_efficiency_ranking(&self) -> u8 {
+        get_bits(self.ebx, 16, 23) as u8
     }
 
-    /// HWP Activity Window. IA32_HWP_REQUEST[bits 41:32] is supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_hwp_activity_window(&self) -> bool {
-        self.eax
-            .contains(ThermalPowerFeaturesEax::HWP_ACTIVITY_WINDOW)
+    /// Native model ID of the core we are running on, if
+    /// [`Self::has_heterogeneous_cores`]. (Bits 27-24)
+    pub fn native_model_id(&self) -> u8 {
+        get_bits(self.ebx, 24, 27) as u8
     }
 
-    /// HWP Energy Performance Preference. IA32_HWP_REQUEST[bits 31:24] is
-    /// supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_hwp_energy_performance_preference(&self) -> bool {
-        self.eax
-            .contains(ThermalPowerFeaturesEax::HWP_ENERGY_PERFORMANCE_PREFERENCE)
+    /// Type of the core we are running on, if
+    /// [`Self::has_heterogeneous_cores`]: 0 for performance and 1 for
+    /// efficiency (dense) cores. (Bits 31-28)
+    pub fn core_type(&self) -> u8 {
+        get_bits(self.ebx, 28, 31) as u8
     }
 
-    /// HWP Package Level Request. IA32_HWP_REQUEST_PKG MSR is supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_hwp_package_level_request(&self) -> bool {
-        self.eax
-            .contains(ThermalPowerFeaturesEax::HWP_PACKAGE_LEVEL_REQUEST)
+    /// Level number (the subleaf). (Bits 07-00)
+    pub fn level_number(&self) -> u8 {
+        get_bits(self.ecx, 0, 7) as u8
     }
 
-    /// HDC. HDC base registers IA32_PKG_HDC_CTL, IA32_PM_CTL1,
-    /// IA32_THREAD_STALL MSRs are supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_hdc(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::HDC)
+    /// Level type. (Bits 15-08)
+    pub fn level_type(&self) -> ExtendedCpuTopologyType {
+        ExtendedCpuTopologyType::from(get_bits(self.ecx, 8, 15) as u8)
     }
 
-    /// Intel® Turbo Boost Max Technology 3.0 available.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_turbo_boost3(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::TURBO_BOOST_3)
+    /// Extended APIC ID of the current logical processor. (Bits 31-00)
+    pub fn extended_apic_id(&self) -> u32 {
+        self.edx
     }
 
-    /// HWP Capabilities. Highest Performance change is supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_hwp_capabilities(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::HWP_CAPABILITIES)
+    /// Encode this level back into the registers of its leaf 0x8000_0026
+    /// subleaf.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
     }
 
-    /// HWP PECI override is supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_hwp_peci_override(&self) -> bool {
-        self.eax
-            .contains(ThermalPowerFeaturesEax::HWP_PECI_OVERRIDE)
+    /// Set the type reported by [`Self::level_type`].
+    pub fn set_level_type(&mut self, typ: ExtendedCpuTopologyType) {
+        set_bits(&mut self.ecx, 8, 15, u8::from(typ) as u32);
     }
 
-    /// Flexible HWP is supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_flexible_hwp(&self) -> bool {
-        self.eax.contains(ThermalPowerFeaturesEax::FLEXIBLE_HWP)
+    set_bits_fn!(
+        set_shift_right_for_next_apic_id,
+        shift_right_for_next_apic_id,
+        eax,
+        0,
+        4,
+        u32
+    );
+    set_bit_fn!(set_efficiency_ranking, has_efficiency_ranking, eax, 29);
+    set_bit_fn!(set_heterogeneous_cores, has_heterogeneous_cores, eax, 30);
+    set_bit_fn!(set_asymmetric_topology, has_asymmetric_topology, eax, 31);
+    set_bits_fn!(set_processors, processors, ebx, 0, 15, u16);
+    set_bits_fn!(
+        set_power_efficiency_ranking,
+        power_efficiency_ranking,
+        ebx,
+        16,
+        23,
+        u8
+    );
+    set_bits_fn!(set_native_model_id, native_model_id, ebx, 24, 27, u8);
+    set_bits_fn!(set_core_type, core_type, ebx, 28, 31, u8);
+    set_bits_fn!(set_level_number, level_number, ecx, 0, 7, u8);
+    set_bits_fn!(set_extended_apic_id, extended_apic_id, edx, 0, 31, u32);
+}
+
+impl Debug for ExtendedCpuTopologyLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ExtendedCpuTopologyLevel")
+            .field("processors", &self.processors())
+            .field("number", &self.level_number())
+            .field("type", &self.level_type())
+            .field("extended_apic_id", &self.extended_apic_id())
+            .field("next_apic_id", &self.shift_right_for_next_apic_id())
+            .field("has_efficiency_ranking", &self.has_efficiency_ranking())
+            .field("has_heterogeneous_cores", &self.has_heterogeneous_cores())
+            .field("has_asymmetric_topology", &self.has_asymmetric_topology())
+            .field("power_efficiency_ranking", &self.power_efficiency_ranking())
+            .field("native_model_id", &self.native_model_id())
+            .field("core_type", &self.core_type())
+            .finish()
     }
+}
 
-    /// Fast access mode for the IA32_HWP_REQUEST MSR is supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_hwp_fast_access_mode(&self) -> bool {
-        self.eax
-            .contains(ThermalPowerFeaturesEax::HWP_REQUEST_MSR_FAST_ACCESS)
+/// Level type of the AMD extended CPU topology (LEAF=0x8000_0026).
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExtendedCpuTopologyType {
+    Invalid,
+    Core,
+    /// Core complex (CCX), the cores that share an L3 cache.
+    Complex,
+    /// Core complex die (CCD).
+    Die,
+    Socket,
+    /// A level type not (yet) known to this library.
+    Unknown(u8),
+}
+
+impl From<u8> for ExtendedCpuTopologyType {
+    fn from(value: u8) -> ExtendedCpuTopologyType {
+        match value {
+            0 => ExtendedCpuTopologyType::Invalid,
+            1 => ExtendedCpuTopologyType::Core,
+            2 => ExtendedCpuTopologyType::Complex,
+            3 => ExtendedCpuTopologyType::Die,
+            4 => ExtendedCpuTopologyType::Socket,
+            x => ExtendedCpuTopologyType::Unknown(x),
+        }
     }
+}
 
-    /// Ignoring Idle Logical Processor HWP request is supported if set.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_ignore_idle_processor_hwp_request(&self) -> bool {
-        self.eax
-            .contains(ThermalPowerFeaturesEax::IGNORE_IDLE_PROCESSOR_HWP_REQUEST)
+impl From<ExtendedCpuTopologyType> for u8 {
+    fn from(value: ExtendedCpuTopologyType) -> u8 {
+        match value {
+            ExtendedCpuTopologyType::Invalid => 0,
+            ExtendedCpuTopologyType::Core => 1,
+            ExtendedCpuTopologyType::Complex => 2,
+            ExtendedCpuTopologyType::Die => 3,
+            ExtendedCpuTopologyType::Socket => 4,
+            ExtendedCpuTopologyType::Unknown(x) => x,
+        }
     }
+}
 
-    /// Hardware Coordination Feedback Capability
-    ///
-    /// Presence of IA32_MPERF and IA32_APERF.
-    ///
-    /// The capability to provide a measure of delivered processor performance
-    /// (since last reset of the counters), as a percentage of expected
-    /// processor performance at frequency specified in CPUID Brand String Bits
-    /// 02 - 01
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_hw_coord_feedback(&self) -> bool {
-        self.ecx
-            .contains(ThermalPowerFeaturesEcx::HW_COORD_FEEDBACK)
+impl fmt::Display for ExtendedCpuTopologyType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data = match self {
+            ExtendedCpuTopologyType::Invalid => "Invalid",
+            ExtendedCpuTopologyType::Core => "Core",
+            ExtendedCpuTopologyType::Complex => "Complex",
+            ExtendedCpuTopologyType::Die => "Die",
+            ExtendedCpuTopologyType::Socket => "Socket",
+            ExtendedCpuTopologyType::Unknown(t) => {
+                return write!(f, "Unknown({})", t);
+            }
+        };
+
+        f.write_str(data)
     }
+}
 
-    /// The processor supports performance-energy bias preference if
-    /// CPUID.06H:ECX.SETBH[bit 3] is set and it also implies the presence of a
-    /// new architectural MSR called IA32_ENERGY_PERF_BIAS (1B0H)
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_energy_bias_pref(&self) -> bool {
-        self.ecx.contains(ThermalPowerFeaturesEcx::ENERGY_BIAS_PREF)
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    struct ExtendedStateInfoXCR0Flags: u32 {
+        /// legacy x87 (Bit 00).
+        const LEGACY_X87 = 1 << 0;
+
+        /// 128-bit SSE (Bit 01).
+        const SSE128 = 1 << 1;
+
+        /// 256-bit AVX (Bit 02).
+        const AVX256 = 1 << 2;
+
+        /// MPX BNDREGS (Bit 03).
+        const MPX_BNDREGS = 1 << 3;
+
+        /// MPX BNDCSR (Bit 04).
+        const MPX_BNDCSR = 1 << 4;
+
+        /// AVX512 OPMASK (Bit 05).
+        const AVX512_OPMASK = 1 << 5;
+
+        /// AVX ZMM Hi256 (Bit 06).
+        const AVX512_ZMM_HI256 = 1 << 6;
+
+        /// AVX 512 ZMM Hi16 (Bit 07).
+        const AVX512_ZMM_HI16 = 1 << 7;
+
+        /// PKRU state (Bit 09).
+        const PKRU = 1 << 9;
+
+        /// IA32_XSS HDC State (Bit 13).
+        const IA32_XSS_HDC = 1 << 13;
     }
 }
 
-impl Debug for ThermalPowerInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("ThermalPowerInfo")
-            .field("dts_irq_threshold", &self.dts_irq_threshold())
-            .field("has_dts", &self.has_dts())
-            .field("has_arat", &self.has_arat())
-            .field("has_pln", &self.has_pln())
-            .field("has_ecmd", &self.has_ecmd())
-            .field("has_ptm", &self.has_ptm())
-            .field("has_hwp", &self.has_hwp())
-            .field("has_hwp_notification", &self.has_hwp_notification())
-            .field("has_hwp_activity_window", &self.has_hwp_activity_window())
-            .field(
-                "has_hwp_energy_performance_preference",
-                &self.has_hwp_energy_performance_preference(),
-            )
-            .field(
-                "has_hwp_package_level_request",
-                &self.has_hwp_package_level_request(),
-            )
-            .field("has_hdc", &self.has_hdc())
-            .field("has_turbo_boost3", &self.has_turbo_boost3())
-            .field("has_hwp_capabilities", &self.has_hwp_capabilities())
-            .field("has_hwp_peci_override", &self.has_hwp_peci_override())
-            .field("has_flexible_hwp", &self.has_flexible_hwp())
-            .field("has_hwp_fast_access_mode", &self.has_hwp_fast_access_mode())
-            .field(
-                "has_ignore_idle_processor_hwp_request",
-                &self.has_ignore_idle_processor_hwp_request(),
-            )
-            .field("has_hw_coord_feedback", &self.has_hw_coord_feedback())
-            .field("has_energy_bias_pref", &self.has_energy_bias_pref())
-            .finish()
+bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    struct ExtendedStateInfoXSSFlags: u32 {
+        /// IA32_XSS PT (Trace Packet) State (Bit 08).
+        const PT = 1 << 8;
+
+        /// IA32_XSS CET user state (Bit 11).
+        const CET_U = 1 << 11;
+
+        /// IA32_XSS CET supervisor state (Bit 12).
+        const CET_S = 1 << 12;
+
+        /// IA32_XSS HDC State (Bit 13).
+        const HDC = 1 << 13;
+
+        /// IA32_XSS UINTR State (Bit 14).
+        const UINTR = 1 << 14;
     }
 }
 
-bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-    struct ThermalPowerFeaturesEax: u32 {
-        /// Digital temperature sensor is supported if set. (Bit 00)
-        const DTS = 1 << 0;
-        /// Intel Turbo Boost Technology Available (see description of IA32_MISC_ENABLE[38]). (Bit 01)
-        const TURBO_BOOST = 1 << 1;
-        /// ARAT. APIC-Timer-always-running feature is supported if set. (Bit 02)
-        const ARAT = 1 << 2;
-        /// Bit 3: Reserved.
-        const RESERVED_3 = 1 << 3;
-        /// PLN. Power limit notification controls are supported if set. (Bit 04)
-        const PLN = 1 << 4;
-        /// ECMD. Clock modulation duty cycle extension is supported if set. (Bit 05)
-        const ECMD = 1 << 5;
-        /// PTM. Package thermal management is supported if set. (Bit 06)
-        const PTM = 1 << 6;
-        /// Bit 07: HWP. HWP base registers (IA32_PM_ENABLE[bit 0], IA32_HWP_CAPABILITIES, IA32_HWP_REQUEST, IA32_HWP_STATUS) are supported if set.
-        const HWP = 1 << 7;
-        /// Bit 08: HWP_Notification. IA32_HWP_INTERRUPT MSR is supported if set.
-        const HWP_NOTIFICATION = 1 << 8;
-        /// Bit 09: HWP_Activity_Window. IA32_HWP_REQUEST[bits 41:32] is supported if set.
-        const HWP_ACTIVITY_WINDOW = 1 << 9;
-        /// Bit 10: HWP_Energy_Performance_Preference. IA32_HWP_REQUEST[bits 31:24] is supported if set.
-        const HWP_ENERGY_PERFORMANCE_PREFERENCE = 1 << 10;
-        /// Bit 11: HWP_Package_Level_Request. IA32_HWP_REQUEST_PKG MSR is supported if set.
-        const HWP_PACKAGE_LEVEL_REQUEST = 1 << 11;
-        /// Bit 12: Reserved.
-        const RESERVED_12 = 1 << 12;
-        /// Bit 13: HDC. HDC base registers IA32_PKG_HDC_CTL, IA32_PM_CTL1, IA32_THREAD_STALL MSRs are supported if set.
-        const HDC = 1 << 13;
-        /// Bit 14: Intel® Turbo Boost Max Technology 3.0 available.
-        const TURBO_BOOST_3 = 1 << 14;
-        /// Bit 15: HWP Capabilities. Highest Performance change is supported if set.
-        const HWP_CAPABILITIES = 1 << 15;
-        /// Bit 16: HWP PECI override is supported if set.
-        const HWP_PECI_OVERRIDE = 1 << 16;
-        /// Bit 17: Flexible HWP is supported if set.
-        const FLEXIBLE_HWP = 1 << 17;
-        /// Bit 18: Fast access mode for the IA32_HWP_REQUEST MSR is supported if set.
-        const HWP_REQUEST_MSR_FAST_ACCESS = 1 << 18;
-        /// Bit 19: Reserved.
-        const RESERVED_19 = 1 << 19;
-        /// Bit 20: Ignoring Idle Logical Processor HWP request is supported if set.
-        const IGNORE_IDLE_PROCESSOR_HWP_REQUEST = 1 << 20;
-        // Bits 31 - 21: Reserved
-    }
-}
+/// Information for saving/restoring extended register state (LEAF=0x0D).
+///
+/// # Platforms
+/// ✅ AMD ✅ Intel
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtendedStateInfo<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    eax: ExtendedStateInfoXCR0Flags,
+    ebx: u32,
+    ecx: u32,
+    _edx: u32,
+    eax1: u32,
+    ebx1: u32,
+    ecx1: ExtendedStateInfoXSSFlags,
+    _edx1: u32,
+}
+
+eq_without_reader!(
+    ExtendedStateInfo,
+    eax,
+    ebx,
+    ecx,
+    _edx,
+    eax1,
+    ebx1,
+    ecx1,
+    _edx1
+);
+
+impl<R: CpuIdReader> ExtendedStateInfo<R> {
+    check_flag!(
+        doc = "Support for legacy x87 in XCR0.",
+        xcr0_supports_legacy_x87,
+        eax,
+        ExtendedStateInfoXCR0Flags::LEGACY_X87
+    );
+
+    check_flag!(
+        doc = "Support for SSE 128-bit in XCR0.",
+        xcr0_supports_sse_128,
+        eax,
+        ExtendedStateInfoXCR0Flags::SSE128
+    );
+
+    check_flag!(
+        doc = "Support for AVX 256-bit in XCR0.",
+        xcr0_supports_avx_256,
+        eax,
+        ExtendedStateInfoXCR0Flags::AVX256
+    );
+
+    check_flag!(
+        doc = "Support for MPX BNDREGS in XCR0.",
+        xcr0_supports_mpx_bndregs,
+        eax,
+        ExtendedStateInfoXCR0Flags::MPX_BNDREGS
+    );
+
+    check_flag!(
+        doc = "Support for MPX BNDCSR in XCR0.",
+        xcr0_supports_mpx_bndcsr,
+        eax,
+        ExtendedStateInfoXCR0Flags::MPX_BNDCSR
+    );
+
+    check_flag!(
+        doc = "Support for AVX512 OPMASK in XCR0.",
+        xcr0_supports_avx512_opmask,
+        eax,
+        ExtendedStateInfoXCR0Flags::AVX512_OPMASK
+    );
+
+    check_flag!(
+        doc = "Support for AVX512 ZMM Hi256 XCR0.",
+        xcr0_supports_avx512_zmm_hi256,
+        eax,
+        ExtendedStateInfoXCR0Flags::AVX512_ZMM_HI256
+    );
+
+    check_flag!(
+        doc = "Support for AVX512 ZMM Hi16 in XCR0.",
+        xcr0_supports_avx512_zmm_hi16,
+        eax,
+        ExtendedStateInfoXCR0Flags::AVX512_ZMM_HI16
+    );
+
+    check_flag!(
+        doc = "Support for PKRU in XCR0.",
+        xcr0_supports_pkru,
+        eax,
+        ExtendedStateInfoXCR0Flags::PKRU
+    );
+
+    check_flag!(
+        doc = "Support for PT in IA32_XSS.",
+        ia32_xss_supports_pt,
+        ecx1,
+        ExtendedStateInfoXSSFlags::PT
+    );
+
+    check_flag!(
+        doc = "Support for CET user state (IA32_U_CET and IA32_PL3_SSP) in IA32_XSS.",
+        ia32_xss_supports_cet_u,
+        ecx1,
+        ExtendedStateInfoXSSFlags::CET_U
+    );
 
-bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-    struct ThermalPowerFeaturesEcx: u32 {
-        const HW_COORD_FEEDBACK = 1 << 0;
+    check_flag!(
+        doc = "Support for CET supervisor state (IA32_PL0_SSP to IA32_PL2_SSP) in IA32_XSS.",
+        ia32_xss_supports_cet_s,
+        ecx1,
+        ExtendedStateInfoXSSFlags::CET_S
+    );
 
-        /// The processor supports performance-energy bias preference if CPUID.06H:ECX.SETBH[bit 3] is set and it also implies the presence of a new architectural MSR called IA32_ENERGY_PERF_BIAS (1B0H)
-        const ENERGY_BIAS_PREF = 1 << 3;
-    }
-}
+    check_flag!(
+        doc = "Support for HDC in IA32_XSS.",
+        ia32_xss_supports_hdc,
+        ecx1,
+        ExtendedStateInfoXSSFlags::HDC
+    );
 
-/// Structured Extended Feature Identifiers (LEAF=0x07).
-///
-/// # Platforms
-/// 🟡 AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct ExtendedFeatures {
-    _eax: u32,
-    ebx: ExtendedFeaturesEbx,
-    ecx: ExtendedFeaturesEcx,
-    _edx: u32,
-}
+    check_flag!(
+        doc = "Support for user interrupt state (UINTR) in IA32_XSS.",
+        ia32_xss_supports_uintr,
+        ecx1,
+        ExtendedStateInfoXSSFlags::UINTR
+    );
 
-impl ExtendedFeatures {
-    /// FSGSBASE. Supports RDFSBASE/RDGSBASE/WRFSBASE/WRGSBASE if 1.
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_fsgsbase(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::FSGSBASE)
+    /// Maximum size (bytes, from the beginning of the XSAVE/XRSTOR save area) required by
+    /// enabled features in XCR0. May be different than ECX if some features at the end of the XSAVE save area
+    /// are not enabled.
+    pub fn xsave_area_size_enabled_features(&self) -> u32 {
+        self.ebx
     }
 
-    /// IA32_TSC_ADJUST MSR is supported if 1.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_tsc_adjust_msr(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::ADJUST_MSR)
+    /// Maximum size (bytes, from the beginning of the XSAVE/XRSTOR save area) of the
+    /// XSAVE/XRSTOR save area required by all supported features in the processor,
+    /// i.e all the valid bit fields in XCR0.
+    pub fn xsave_area_size_supported_features(&self) -> u32 {
+        self.ecx
     }
 
-    /// BMI1
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_bmi1(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::BMI1)
+    /// CPU has xsaveopt feature.
+    pub fn has_xsaveopt(&self) -> bool {
+        self.eax1 & 0x1 > 0
     }
 
-    /// HLE
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_hle(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::HLE)
+    /// Supports XSAVEC and the compacted form of XRSTOR if set.
+    pub fn has_xsavec(&self) -> bool {
+        self.eax1 & 0b10 > 0
     }
 
-    /// AVX2
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_avx2(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::AVX2)
+    /// Supports XGETBV with ECX = 1 if set.
+    pub fn has_xgetbv(&self) -> bool {
+        self.eax1 & 0b100 > 0
     }
 
-    /// FDP_EXCPTN_ONLY. x87 FPU Data Pointer updated only on x87 exceptions if
-    /// 1.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_fdp(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::FDP)
+    /// Supports XSAVES/XRSTORS and IA32_XSS if set.
+    pub fn has_xsaves_xrstors(&self) -> bool {
+        self.eax1 & 0b1000 > 0
     }
 
-    /// SMEP. Supports Supervisor-Mode Execution Prevention if 1.
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_smep(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::SMEP)
+    /// The size in bytes of the XSAVE area containing all states enabled by XCRO | IA32_XSS.
+    pub fn xsave_size(&self) -> u32 {
+        self.ebx1
     }
 
-    /// BMI2
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_bmi2(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::BMI2)
+    /// Iterator over extended state enumeration levels >= 2.
+    pub fn iter(&self) -> ExtendedStateIter<R> {
+        ExtendedStateIter {
+            read: self.read.clone(),
+            level: 1,
+            supported_xcr0: self.eax.bits(),
+            supported_xss: self.ecx1.bits(),
+        }
     }
 
-    /// Supports Enhanced REP MOVSB/STOSB if 1.
+    /// Encode this struct back into the registers of leaf 0xD, subleafs 0 and 1.
     ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_rep_movsb_stosb(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::REP_MOVSB_STOSB)
+    /// The per-component subleafs are available through [`ExtendedState::as_cpuid_result`].
+    pub fn as_cpuid_results(&self) -> [CpuIdResult; 2] {
+        [
+            CpuIdResult {
+                eax: self.eax.bits(),
+                ebx: self.ebx,
+                ecx: self.ecx,
+                edx: self._edx,
+            },
+            CpuIdResult {
+                eax: self.eax1,
+                ebx: self.ebx1,
+                ecx: self.ecx1.bits(),
+                edx: self._edx1,
+            },
+        ]
+    }
+
+    set_flag!(
+        set_xcr0_supports_legacy_x87,
+        xcr0_supports_legacy_x87,
+        eax,
+        ExtendedStateInfoXCR0Flags::LEGACY_X87
+    );
+    set_flag!(
+        set_xcr0_supports_sse_128,
+        xcr0_supports_sse_128,
+        eax,
+        ExtendedStateInfoXCR0Flags::SSE128
+    );
+    set_flag!(
+        set_xcr0_supports_avx_256,
+        xcr0_supports_avx_256,
+        eax,
+        ExtendedStateInfoXCR0Flags::AVX256
+    );
+    set_flag!(
+        set_xcr0_supports_mpx_bndregs,
+        xcr0_supports_mpx_bndregs,
+        eax,
+        ExtendedStateInfoXCR0Flags::MPX_BNDREGS
+    );
+    set_flag!(
+        set_xcr0_supports_mpx_bndcsr,
+        xcr0_supports_mpx_bndcsr,
+        eax,
+        ExtendedStateInfoXCR0Flags::MPX_BNDCSR
+    );
+    set_flag!(
+        set_xcr0_supports_avx512_opmask,
+        xcr0_supports_avx512_opmask,
+        eax,
+        ExtendedStateInfoXCR0Flags::AVX512_OPMASK
+    );
+    set_flag!(
+        set_xcr0_supports_avx512_zmm_hi256,
+        xcr0_supports_avx512_zmm_hi256,
+        eax,
+        ExtendedStateInfoXCR0Flags::AVX512_ZMM_HI256
+    );
+    set_flag!(
+        set_xcr0_supports_avx512_zmm_hi16,
+        xcr0_supports_avx512_zmm_hi16,
+        eax,
+        ExtendedStateInfoXCR0Flags::AVX512_ZMM_HI16
+    );
+    set_flag!(
+        set_xcr0_supports_pkru,
+        xcr0_supports_pkru,
+        eax,
+        ExtendedStateInfoXCR0Flags::PKRU
+    );
+    set_flag!(
+        set_ia32_xss_supports_pt,
+        ia32_xss_supports_pt,
+        ecx1,
+        ExtendedStateInfoXSSFlags::PT
+    );
+    set_flag!(
+        set_ia32_xss_supports_cet_u,
+        ia32_xss_supports_cet_u,
+        ecx1,
+        ExtendedStateInfoXSSFlags::CET_U
+    );
+    set_flag!(
+        set_ia32_xss_supports_cet_s,
+        ia32_xss_supports_cet_s,
+        ecx1,
+        ExtendedStateInfoXSSFlags::CET_S
+    );
+    set_flag!(
+        set_ia32_xss_supports_hdc,
+        ia32_xss_supports_hdc,
+        ecx1,
+        ExtendedStateInfoXSSFlags::HDC
+    );
+    set_flag!(
+        set_ia32_xss_supports_uintr,
+        ia32_xss_supports_uintr,
+        ecx1,
+        ExtendedStateInfoXSSFlags::UINTR
+    );
+    set_bits_fn!(
+        set_xsave_area_size_enabled_features,
+        xsave_area_size_enabled_features,
+        ebx,
+        0,
+        31,
+        u32
+    );
+    set_bits_fn!(
+        set_xsave_area_size_supported_features,
+        xsave_area_size_supported_features,
+        ecx,
+        0,
+        31,
+        u32
+    );
+    set_bits_fn!(set_xsave_size, xsave_size, ebx1, 0, 31, u32);
+    set_bit_fn!(set_xsaveopt, has_xsaveopt, eax1, 0);
+    set_bit_fn!(set_xsavec, has_xsavec, eax1, 1);
+    set_bit_fn!(set_xgetbv, has_xgetbv, eax1, 2);
+    set_bit_fn!(set_xsaves_xrstors, has_xsaves_xrstors, eax1, 3);
+}
+
+impl<R: CpuIdReader> Debug for ExtendedStateInfo<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ExtendedStateInfo")
+            .field("eax", &self.eax)
+            .field("ecx1", &self.ecx1)
+            .field(
+                "xsave_area_size_enabled_features",
+                &self.xsave_area_size_enabled_features(),
+            )
+            .field(
+                "xsave_area_size_supported_features",
+                &self.xsave_area_size_supported_features(),
+            )
+            .field("has_xsaveopt", &self.has_xsaveopt())
+            .field("has_xsavec", &self.has_xsavec())
+            .field("has_xgetbv", &self.has_xgetbv())
+            .field("has_xsaves_xrstors", &self.has_xsaves_xrstors())
+            .field("xsave_size", &self.xsave_size())
+            .field("extended_state_iter", &self.iter())
+            .finish()
     }
+}
+
+/// Yields [ExtendedState] structs.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtendedStateIter<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    level: u32,
+    supported_xcr0: u32,
+    supported_xss: u32,
+}
+
+/// When CPUID executes with EAX set to 0DH and ECX = n (n > 1, and is a valid
+/// sub-leaf index), the processor returns information about the size and offset
+/// of each processor extended state save area within the XSAVE/XRSTOR area.
+///
+/// The iterator goes over the valid sub-leaves and obtain size and offset
+/// information for each processor extended state save area:
+impl<R: CpuIdReader> Iterator for ExtendedStateIter<R> {
+    type Item = ExtendedState;
 
-    /// INVPCID. If 1, supports INVPCID instruction for system software that
-    /// manages process-context identifiers.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_invpcid(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::INVPCID)
-    }
+    fn next(&mut self) -> Option<ExtendedState> {
+        self.level += 1;
+        if self.level > 31 {
+            return None;
+        }
 
-    /// RTM
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_rtm(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::RTM)
-    }
+        let bit = 1 << self.level;
+        if (self.supported_xcr0 & bit > 0) || (self.supported_xss & bit > 0) {
+            let res = self.read.cpuid2(EAX_EXTENDED_STATE_INFO, self.level);
+            return Some(ExtendedState {
+                subleaf: self.level,
+                eax: res.eax,
+                ebx: res.ebx,
+                ecx: res.ecx,
+            });
+        }
 
-    /// Supports Intel Resource Director Technology (RDT) Monitoring capability.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_rdtm(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::RDTM)
+        self.next()
     }
+}
 
-    /// Deprecates FPU CS and FPU DS values if 1.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_fpu_cs_ds_deprecated(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::DEPRECATE_FPU_CS_DS)
+impl<R: CpuIdReader> Debug for ExtendedStateIter<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_list();
+        self.clone().for_each(|ref item| {
+            debug.entry(item);
+        });
+        debug.finish()
     }
+}
 
-    /// MPX. Supports Intel Memory Protection Extensions if 1.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_mpx(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::MPX)
-    }
+/// What kidn of extended register state this is.
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(u32)]
+pub enum ExtendedRegisterType {
+    Avx,
+    MpxBndregs,
+    MpxBndcsr,
+    Avx512Opmask,
+    Avx512ZmmHi256,
+    Avx512ZmmHi16,
+    Pt,
+    Pkru,
+    CetU,
+    CetS,
+    Hdc,
+    Uintr,
+    Unknown(u32),
+}
 
-    /// Supports Intel Resource Director Technology (RDT) Allocation capability.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_rdta(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::RDTA)
+impl From<u32> for ExtendedRegisterType {
+    fn from(value: u32) -> ExtendedRegisterType {
+        match value {
+            0x2 => ExtendedRegisterType::Avx,
+            0x3 => ExtendedRegisterType::MpxBndregs,
+            0x4 => ExtendedRegisterType::MpxBndcsr,
+            0x5 => ExtendedRegisterType::Avx512Opmask,
+            0x6 => ExtendedRegisterType::Avx512ZmmHi256,
+            0x7 => ExtendedRegisterType::Avx512ZmmHi16,
+            0x8 => ExtendedRegisterType::Pt,
+            0x9 => ExtendedRegisterType::Pkru,
+            0xb => ExtendedRegisterType::CetU,
+            0xc => ExtendedRegisterType::CetS,
+            0xd => ExtendedRegisterType::Hdc,
+            0xe => ExtendedRegisterType::Uintr,
+            x => ExtendedRegisterType::Unknown(x),
+        }
     }
+}
 
-    /// Supports RDSEED.
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_rdseed(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::RDSEED)
-    }
+impl fmt::Display for ExtendedRegisterType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data = match self {
+            ExtendedRegisterType::Avx => "AVX/YMM",
+            ExtendedRegisterType::MpxBndregs => "MPX BNDREGS",
+            ExtendedRegisterType::MpxBndcsr => "MPX BNDCSR",
+            ExtendedRegisterType::Avx512Opmask => "AVX-512 opmask",
+            ExtendedRegisterType::Avx512ZmmHi256 => "AVX-512 ZMM_Hi256",
+            ExtendedRegisterType::Avx512ZmmHi16 => "AVX-512 Hi16_ZMM",
+            ExtendedRegisterType::Pkru => "PKRU",
+            ExtendedRegisterType::Pt => "PT",
+            ExtendedRegisterType::CetU => "CET_U",
+            ExtendedRegisterType::CetS => "CET_S",
+            ExtendedRegisterType::Hdc => "HDC",
+            ExtendedRegisterType::Uintr => "UINTR",
+            ExtendedRegisterType::Unknown(t) => {
+                return write!(f, "Unknown({})", t);
+            }
+        };
 
-    /// Supports ADX.
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_adx(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::ADX)
+        f.write_str(data)
     }
+}
 
-    /// SMAP. Supports Supervisor-Mode Access Prevention (and the CLAC/STAC
-    /// instructions) if 1.
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_smap(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::SMAP)
-    }
+/// Where the extended register state is stored.
+#[derive(PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExtendedRegisterStateLocation {
+    Xcr0,
+    Ia32Xss,
+}
 
-    /// Supports CLFLUSHOPT.
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_clflushopt(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::CLFLUSHOPT)
-    }
+impl fmt::Display for ExtendedRegisterStateLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data = match self {
+            ExtendedRegisterStateLocation::Xcr0 => "XCR0 (user state)",
+            ExtendedRegisterStateLocation::Ia32Xss => "IA32_XSS (supervisor state)",
+        };
 
-    /// Supports Intel Processor Trace.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_processor_trace(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::PROCESSOR_TRACE)
+        f.write_str(data)
     }
+}
 
-    /// Supports SHA Instructions.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_sha(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::SHA)
-    }
+/// ExtendedState subleaf structure for things that need to be restored.
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExtendedState {
+    pub subleaf: u32,
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+}
 
-    /// Supports Intel® Software Guard Extensions (Intel® SGX Extensions).
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_sgx(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::SGX)
+impl ExtendedState {
+    /// Decodes the registers of `subleaf` (>= 2) of LEAF=0x0D.
+    pub fn from_registers(subleaf: u32, data: CpuIdResult) -> Self {
+        Self {
+            subleaf,
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+        }
     }
 
-    /// Supports AVX512F.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_avx512f(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::AVX512F)
+    /// Returns which register this specific extended subleaf contains information for.
+    pub fn register(&self) -> ExtendedRegisterType {
+        self.subleaf.into()
     }
 
-    /// Supports AVX512DQ.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_avx512dq(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::AVX512DQ)
+    /// The size in bytes (from the offset specified in EBX) of the save area
+    /// for an extended state feature associated with a valid sub-leaf index, n.
+    /// This field reports 0 if the sub-leaf index, n, is invalid.
+    pub fn size(&self) -> u32 {
+        self.eax
     }
 
-    /// AVX512_IFMA
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_avx512_ifma(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::AVX512_IFMA)
+    /// The offset in bytes of this extended state components save area
+    /// from the beginning of the XSAVE/XRSTOR area.
+    pub fn offset(&self) -> u32 {
+        self.ebx
     }
 
-    /// AVX512PF
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_avx512pf(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::AVX512PF)
+    pub fn location(&self) -> ExtendedRegisterStateLocation {
+        if self.is_in_xcr0() {
+            ExtendedRegisterStateLocation::Xcr0
+        } else {
+            ExtendedRegisterStateLocation::Ia32Xss
+        }
     }
 
-    /// AVX512ER
+    /// True if the bit n (corresponding to the sub-leaf index)
+    /// is supported in the IA32_XSS MSR;
     ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_avx512er(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::AVX512ER)
+    /// # Deprecation note
+    /// This will likely be removed in the future. Use `location()` instead.
+    pub fn is_in_ia32_xss(&self) -> bool {
+        self.ecx & 0b1 > 0
     }
 
-    /// AVX512CD
+    /// True if bit n is supported in XCR0.
     ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_avx512cd(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::AVX512CD)
+    /// # Deprecation note
+    /// This will likely be removed in the future. Use `location()` instead.
+    pub fn is_in_xcr0(&self) -> bool {
+        self.ecx & 0b1 == 0
     }
 
-    /// AVX512BW
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_avx512bw(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::AVX512BW)
+    /// Returns true when the compacted format of an XSAVE area is used,
+    /// this extended state component located on the next 64-byte
+    /// boundary following the preceding state component
+    /// (otherwise, it is located immediately following the preceding state component).
+    pub fn is_compacted_format(&self) -> bool {
+        self.ecx & 0b10 > 0
     }
 
-    /// AVX512VL
+    /// Encode this component back into the registers of its leaf 0xD subleaf.
     ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_avx512vl(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::AVX512VL)
+    /// EDX is reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: 0,
+        }
     }
 
-    /// CLWB
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_clwb(&self) -> bool {
-        self.ebx.contains(ExtendedFeaturesEbx::CLWB)
+    set_bits_fn!(set_size, size, eax, 0, 31, u32);
+    set_bits_fn!(set_offset, offset, ebx, 0, 31, u32);
+    set_bit_fn!(set_in_ia32_xss, is_in_ia32_xss, ecx, 0);
+    set_bit_fn!(set_compacted_format, is_compacted_format, ecx, 1);
+}
+
+impl Debug for ExtendedState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtendedState")
+            .field("size", &self.size())
+            .field("offset", &self.offset())
+            .field("is_in_ia32_xss", &self.is_in_ia32_xss())
+            .field("is_in_xcr0", &self.is_in_xcr0())
+            .field("is_compacted_format", &self.is_compacted_format())
+            .finish()
     }
+}
 
-    /// Has PREFETCHWT1 (Intel® Xeon Phi™ only).
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_prefetchwt1(&self) -> bool {
-        self.ecx.contains(ExtendedFeaturesEcx::PREFETCHWT1)
+/// Intel Resource Director Technology RDT (LEAF=0x0F).
+///
+/// Monitoring Enumeration Sub-leaf (EAX = 0FH, ECX = 0 and ECX = 1)
+/// # Platforms
+/// ❌ AMD ✅ Intel
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RdtMonitoringInfo<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    ebx: u32,
+    edx: u32,
+}
+
+eq_without_reader!(RdtMonitoringInfo, ebx, edx);
+
+impl<R: CpuIdReader> RdtMonitoringInfo<R> {
+    /// Maximum range (zero-based) of RMID within this physical processor of all types.
+    pub fn rmid_range(&self) -> u32 {
+        self.ebx
     }
 
-    /// Supports user-mode instruction prevention if 1.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_umip(&self) -> bool {
-        self.ecx.contains(ExtendedFeaturesEcx::UMIP)
+    check_bit_fn!(
+        doc = "Supports L3 Cache Intel RDT Monitoring.",
+        has_l3_monitoring,
+        edx,
+        1
+    );
+
+    /// L3 Cache Monitoring.
+    pub fn l3_monitoring(&self) -> Option<L3MonitoringInfo> {
+        if self.has_l3_monitoring() {
+            let res = self.read.cpuid2(EAX_RDT_MONITORING, 1);
+            Some(L3MonitoringInfo {
+                ebx: res.ebx,
+                ecx: res.ecx,
+                edx: res.edx,
+            })
+        } else {
+            None
+        }
     }
 
-    /// Supports protection keys for user-mode pages.
+    /// Encode this struct back into the registers of leaf 0xF (subleaf 0).
     ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_pku(&self) -> bool {
-        self.ecx.contains(ExtendedFeaturesEcx::PKU)
+    /// EAX and ECX are reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: 0,
+            ebx: self.ebx,
+            ecx: 0,
+            edx: self.edx,
+        }
     }
 
-    /// OS has set CR4.PKE to enable protection keys (and the RDPKRU/WRPKRU
-    /// instructions.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_ospke(&self) -> bool {
-        self.ecx.contains(ExtendedFeaturesEcx::OSPKE)
+    set_bits_fn!(set_rmid_range, rmid_range, ebx, 0, 31, u32);
+    set_bit_fn!(set_l3_monitoring, has_l3_monitoring, edx, 1);
+}
+
+impl<R: CpuIdReader> Debug for RdtMonitoringInfo<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RdtMonitoringInfo")
+            .field("rmid_range", &self.rmid_range())
+            .field("l3_monitoring", &self.l3_monitoring())
+            .finish()
     }
+}
 
-    /// AVX512VNNI
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_avx512vnni(&self) -> bool {
-        self.ecx.contains(ExtendedFeaturesEcx::AVX512VNNI)
+/// Information about L3 cache monitoring.
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct L3MonitoringInfo {
+    ebx: u32,
+    ecx: u32,
+    edx: u32,
+}
+
+impl L3MonitoringInfo {
+    /// Decodes the registers of LEAF=0x0F, subleaf 1.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            ebx: data.ebx,
+            ecx: data.ecx,
+            edx: data.edx,
+        }
     }
 
-    /// RDPID and IA32_TSC_AUX are available.
-    ///
-    /// # Bug
-    /// The Intel manual lists RDPID as bit 22 in the ECX register, but AMD
-    /// lists it as bit 22 in the ebx register. We assumed that the AMD manual
-    /// was wrong and query ecx, let's see what happens.
-    ///
-    /// # Platforms
-    /// ✅ AMD ✅ Intel
-    pub fn has_rdpid(&self) -> bool {
-        self.ecx.contains(ExtendedFeaturesEcx::RDPID)
+    /// Conversion factor from reported IA32_QM_CTR value to occupancy metric (bytes).
+    pub fn conversion_factor(&self) -> u32 {
+        self.ebx
     }
 
-    /// Supports SGX Launch Configuration.
-    ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn has_sgx_lc(&self) -> bool {
-        self.ecx.contains(ExtendedFeaturesEcx::SGX_LC)
+    /// Maximum range (zero-based) of RMID of L3.
+    pub fn maximum_rmid_range(&self) -> u32 {
+        self.ecx
     }
 
-    /// The value of MAWAU used by the BNDLDX and BNDSTX instructions in 64-bit mode.
+    check_bit_fn!(
+        doc = "Supports occupancy monitoring.",
+        has_occupancy_monitoring,
+        edx,
+        0
+    );
+
+    check_bit_fn!(
+        doc = "Supports total bandwidth monitoring.",
+        has_total_bandwidth_monitoring,
+        edx,
+        1
+    );
+
+    check_bit_fn!(
+        doc = "Supports local bandwidth monitoring.",
+        has_local_bandwidth_monitoring,
+        edx,
+        2
+    );
+
+    /// Encode this struct back into the registers of leaf 0xF (subleaf 1).
     ///
-    /// # Platforms
-    /// ❌ AMD (reserved) ✅ Intel
-    pub fn mawau_value(&self) -> u8 {
-        get_bits(self.ecx.bits(), 17, 21) as u8
+    /// EAX is reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: 0,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
     }
+
+    set_bits_fn!(set_conversion_factor, conversion_factor, ebx, 0, 31, u32);
+    set_bits_fn!(set_maximum_rmid_range, maximum_rmid_range, ecx, 0, 31, u32);
+    set_bit_fn!(set_occupancy_monitoring, has_occupancy_monitoring, edx, 0);
+    set_bit_fn!(
+        set_total_bandwidth_monitoring,
+        has_total_bandwidth_monitoring,
+        edx,
+        1
+    );
+    set_bit_fn!(
+        set_local_bandwidth_monitoring,
+        has_local_bandwidth_monitoring,
+        edx,
+        2
+    );
 }
 
-impl Debug for ExtendedFeatures {
+impl Debug for L3MonitoringInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("ExtendedFeatures")
-            .field("ebx", &self.ebx)
-            .field("ecx", &self.ecx)
-            .field("mawau_value", &self.mawau_value())
+        f.debug_struct("L3MonitoringInfo")
+            .field("conversion_factor", &self.conversion_factor())
+            .field("maximum_rmid_range", &self.maximum_rmid_range())
             .finish()
     }
 }
 
-bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-    struct ExtendedFeaturesEbx: u32 {
-        /// FSGSBASE. Supports RDFSBASE/RDGSBASE/WRFSBASE/WRGSBASE if 1. (Bit 00)
-        const FSGSBASE = 1 << 0;
-        /// IA32_TSC_ADJUST MSR is supported if 1. (Bit 01)
-        const ADJUST_MSR = 1 << 1;
-        /// Bit 02: SGX. Supports Intel® Software Guard Extensions (Intel® SGX Extensions) if 1.
-        const SGX = 1 << 2;
-        /// BMI1 (Bit 03)
-        const BMI1 = 1 << 3;
-        /// HLE (Bit 04)
-        const HLE = 1 << 4;
-        /// AVX2 (Bit 05)
-        const AVX2 = 1 << 5;
-        /// FDP_EXCPTN_ONLY. x87 FPU Data Pointer updated only on x87 exceptions if 1.
-        const FDP = 1 << 6;
-        /// SMEP. Supports Supervisor-Mode Execution Prevention if 1. (Bit 07)
-        const SMEP = 1 << 7;
-        /// BMI2 (Bit 08)
-        const BMI2 = 1 << 8;
-        /// Supports Enhanced REP MOVSB/STOSB if 1. (Bit 09)
-        const REP_MOVSB_STOSB = 1 << 9;
-        /// INVPCID. If 1, supports INVPCID instruction for system software that manages process-context identifiers. (Bit 10)
-        const INVPCID = 1 << 10;
-        /// RTM (Bit 11)
-        const RTM = 1 << 11;
-        /// Supports Intel Resource Director Technology (RDT) Monitoring. (Bit 12)
-        const RDTM = 1 << 12;
-        /// Deprecates FPU CS and FPU DS values if 1. (Bit 13)
-        const DEPRECATE_FPU_CS_DS = 1 << 13;
-        /// Deprecates FPU CS and FPU DS values if 1. (Bit 14)
-        const MPX = 1 << 14;
-        /// Supports Intel Resource Director Technology (RDT) Allocation capability if 1.
-        const RDTA = 1 << 15;
-        /// Bit 16: AVX512F.
-        const AVX512F = 1 << 16;
-        /// Bit 17: AVX512DQ.
-        const AVX512DQ = 1 << 17;
-        /// Supports RDSEED.
-        const RDSEED = 1 << 18;
-        /// Supports ADX.
-        const ADX = 1 << 19;
-        /// SMAP. Supports Supervisor-Mode Access Prevention (and the CLAC/STAC instructions) if 1.
-        const SMAP = 1 << 20;
-        /// Bit 21: AVX512_IFMA.
-        const AVX512_IFMA = 1 << 21;
-        // Bit 22: Reserved.
-        /// Bit 23: CLFLUSHOPT
-        const CLFLUSHOPT = 1 << 23;
-        /// Bit 24: CLWB.
-        const CLWB = 1 << 24;
-        /// Bit 25: Intel Processor Trace
-        const PROCESSOR_TRACE = 1 << 25;
-        /// Bit 26: AVX512PF. (Intel® Xeon Phi™ only.)
-        const AVX512PF = 1 << 26;
-        /// Bit 27: AVX512ER. (Intel® Xeon Phi™ only.)
-        const AVX512ER = 1 << 27;
-        /// Bit 28: AVX512CD.
-        const AVX512CD = 1 << 28;
-        /// Bit 29: Intel SHA Extensions
-        const SHA = 1 << 29;
-        /// Bit 30: AVX512BW.
-        const AVX512BW = 1 << 30;
-        /// Bit 31: AVX512VL.
-        const AVX512VL = 1 << 31;
-    }
+/// Quality of service enforcement information (LEAF=0x10).
+///
+/// # Platforms
+/// ❌ AMD ✅ Intel
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RdtAllocationInfo<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    ebx: u32,
 }
 
-bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-    struct ExtendedFeaturesEcx: u32 {
-        /// Bit 0: Prefetch WT1. (Intel® Xeon Phi™ only).
-        const PREFETCHWT1 = 1 << 0;
-
-        // Bit 01: AVX512_VBMI
-        const AVX512VBMI = 1 << 1;
+eq_without_reader!(RdtAllocationInfo, ebx);
 
-        /// Bit 02: UMIP. Supports user-mode instruction prevention if 1.
-        const UMIP = 1 << 2;
+impl<R: CpuIdReader> RdtAllocationInfo<R> {
+    check_bit_fn!(doc = "Supports L3 Cache Allocation.", has_l3_cat, ebx, 1);
 
-        /// Bit 03: PKU. Supports protection keys for user-mode pages if 1.
-        const PKU = 1 << 3;
+    check_bit_fn!(doc = "Supports L2 Cache Allocation.", has_l2_cat, ebx, 2);
 
-        /// Bit 04: OSPKE. If 1, OS has set CR4.PKE to enable protection keys (and the RDPKRU/WRPKRU instruc-tions).
-        const OSPKE = 1 << 4;
+    check_bit_fn!(
+        doc = "Supports Memory Bandwidth Allocation.",
+        has_memory_bandwidth_allocation,
+        ebx,
+        3
+    );
 
-        /// Bit 11: AVX512_VNNI
-        const AVX512VNNI = 1 << 11;
+    /// L3 Cache Allocation Information.
+    pub fn l3_cat(&self) -> Option<L3CatInfo> {
+        if self.has_l3_cat() {
+            let res = self.read.cpuid2(EAX_RDT_ALLOCATION, 1);
+            Some(L3CatInfo {
+                eax: res.eax,
+                ebx: res.ebx,
+                ecx: res.ecx,
+                edx: res.edx,
+            })
+        } else {
+            None
+        }
+    }
 
-        // Bits 16 - 5: Reserved.
-        // Bits 21 - 17: The value of MAWAU used by the BNDLDX and BNDSTX instructions in 64-bit mode.
+    /// L2 Cache Allocation Information.
+    pub fn l2_cat(&self) -> Option<L2CatInfo> {
+        if self.has_l2_cat() {
+            let res = self.read.cpuid2(EAX_RDT_ALLOCATION, 2);
+            Some(L2CatInfo {
+                eax: res.eax,
+                ebx: res.ebx,
+                edx: res.edx,
+            })
+        } else {
+            None
+        }
+    }
 
+    /// Memory Bandwidth Allocation Information.
+    pub fn memory_bandwidth_allocation(&self) -> Option<MemBwAllocationInfo> {
+        if self.has_memory_bandwidth_allocation() {
+            let res = self.read.cpuid2(EAX_RDT_ALLOCATION, 3);
+            Some(MemBwAllocationInfo {
+                eax: res.eax,
+                ecx: res.ecx,
+                edx: res.edx,
+            })
+        } else {
+            None
+        }
+    }
 
-        /// Bit 22: RDPID. RDPID and IA32_TSC_AUX are available if 1.
-        const RDPID = 1 << 22;
+    /// Encode this struct back into the registers of leaf 0x10 (subleaf 0).
+    ///
+    /// EAX, ECX and EDX are reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: 0,
+            ebx: self.ebx,
+            ecx: 0,
+            edx: 0,
+        }
+    }
 
-        // Bits 29 - 23: Reserved.
+    set_bit_fn!(set_l3_cat, has_l3_cat, ebx, 1);
+    set_bit_fn!(set_l2_cat, has_l2_cat, ebx, 2);
+    set_bit_fn!(
+        set_memory_bandwidth_allocation,
+        has_memory_bandwidth_allocation,
+        ebx,
+        3
+    );
+}
 
-        /// Bit 30: SGX_LC. Supports SGX Launch Configuration if 1.
-        const SGX_LC = 1 << 30;
+impl<R: CpuIdReader> Debug for RdtAllocationInfo<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RdtAllocationInfo")
+            .field("l3_cat", &self.l3_cat())
+            .field("l2_cat", &self.l2_cat())
+            .field(
+                "memory_bandwidth_allocation",
+                &self.memory_bandwidth_allocation(),
+            )
+            .finish()
     }
 }
 
-/// Direct cache access info (LEAF=0x09).
-///
-/// # Platforms
-/// ❌ AMD (reserved) ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct DirectCacheAccessInfo {
+/// L3 Cache Allocation Technology Enumeration Sub-leaf (LEAF=0x10, SUBLEAF=1).
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct L3CatInfo {
     eax: u32,
+    ebx: u32,
+    ecx: u32,
+    edx: u32,
 }
 
-impl DirectCacheAccessInfo {
-    /// Value of bits \[31:0\] of IA32_PLATFORM_DCA_CAP MSR (address 1F8H)
-    pub fn get_dca_cap_value(&self) -> u32 {
-        self.eax
+impl L3CatInfo {
+    /// Decodes the registers of LEAF=0x10, subleaf 1.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+            edx: data.edx,
+        }
+    }
+
+    /// Length of the capacity bit mask.
+    pub fn capacity_mask_length(&self) -> u8 {
+        (get_bits(self.eax, 0, 4) + 1) as u8
+    }
+
+    /// Bit-granular map of isolation/contention of allocation units.
+    pub fn isolation_bitmap(&self) -> u32 {
+        self.ebx
+    }
+
+    /// Highest COS number supported for this Leaf.
+    pub fn highest_cos(&self) -> u16 {
+        get_bits(self.edx, 0, 15) as u16
+    }
+
+    check_bit_fn!(
+        doc = "Is Code and Data Prioritization Technology supported?",
+        has_code_data_prioritization,
+        ecx,
+        2
+    );
+
+    /// Encode this struct back into the registers of leaf 0x10 (subleaf 1).
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
     }
+
+    set_count_fn!(
+        set_capacity_mask_length,
+        capacity_mask_length,
+        eax,
+        0,
+        4,
+        u8
+    );
+    set_bits_fn!(set_isolation_bitmap, isolation_bitmap, ebx, 0, 31, u32);
+    set_bits_fn!(set_highest_cos, highest_cos, edx, 0, 15, u16);
+    set_bit_fn!(
+        set_code_data_prioritization,
+        has_code_data_prioritization,
+        ecx,
+        2
+    );
 }
 
-impl Debug for DirectCacheAccessInfo {
+impl Debug for L3CatInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("DirectCacheAccessInfo")
-            .field("dca_cap_value", &self.get_dca_cap_value())
+        f.debug_struct("L3CatInfo")
+            .field("capacity_mask_length", &self.capacity_mask_length())
+            .field("isolation_bitmap", &self.isolation_bitmap())
+            .field("highest_cos", &self.highest_cos())
             .finish()
     }
 }
 
-/// Info about performance monitoring -- how many counters etc. (LEAF=0x0A)
-///
-/// # Platforms
-/// ❌ AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct PerformanceMonitoringInfo {
+/// L2 Cache Allocation Technology Enumeration Sub-leaf (LEAF=0x10, SUBLEAF=2).
+#[derive(Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct L2CatInfo {
     eax: u32,
-    ebx: PerformanceMonitoringFeaturesEbx,
-    _ecx: u32,
+    ebx: u32,
     edx: u32,
 }
 
-impl PerformanceMonitoringInfo {
-    /// Version ID of architectural performance monitoring. (Bits 07 - 00)
-    pub fn version_id(&self) -> u8 {
-        get_bits(self.eax, 0, 7) as u8
-    }
-
-    /// Number of general-purpose performance monitoring counter per logical processor. (Bits 15- 08)
-    pub fn number_of_counters(&self) -> u8 {
-        get_bits(self.eax, 8, 15) as u8
+impl L2CatInfo {
+    /// Decodes the registers of LEAF=0x10, subleaf 2.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            edx: data.edx,
+        }
     }
 
-    /// Bit width of general-purpose, performance monitoring counter. (Bits 23 - 16)
-    pub fn counter_bit_width(&self) -> u8 {
-        get_bits(self.eax, 16, 23) as u8
+    /// Length of the capacity bit mask.
+    pub fn capacity_mask_length(&self) -> u8 {
+        (get_bits(self.eax, 0, 4) + 1) as u8
     }
 
-    /// Length of EBX bit vector to enumerate architectural performance monitoring events. (Bits 31 - 24)
-    pub fn ebx_length(&self) -> u8 {
-        get_bits(self.eax, 24, 31) as u8
+    /// Bit-granular map of isolation/contention of allocation units.
+    pub fn isolation_bitmap(&self) -> u32 {
+        self.ebx
     }
 
-    /// Number of fixed-function performance counters (if Version ID > 1). (Bits 04 - 00)
-    pub fn fixed_function_counters(&self) -> u8 {
-        get_bits(self.edx, 0, 4) as u8
+    /// Highest COS number supported for this Leaf.
+    pub fn highest_cos(&self) -> u16 {
+        get_bits(self.edx, 0, 15) as u16
     }
 
-    /// Bit width of fixed-function performance counters (if Version ID > 1). (Bits 12- 05)
-    pub fn fixed_function_counters_bit_width(&self) -> u8 {
-        get_bits(self.edx, 5, 12) as u8
+    /// Encode this struct back into the registers of leaf 0x10 (subleaf 2).
+    ///
+    /// ECX is reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: 0,
+            edx: self.edx,
+        }
     }
 
-    check_bit_fn!(
-        doc = "AnyThread deprecation",
-        has_any_thread_deprecation,
-        edx,
-        15
+    set_count_fn!(
+        set_capacity_mask_length,
+        capacity_mask_length,
+        eax,
+        0,
+        4,
+        u8
     );
+    set_bits_fn!(set_isolation_bitmap, isolation_bitmap, ebx, 0, 31, u32);
+    set_bits_fn!(set_highest_cos, highest_cos, edx, 0, 15, u16);
+}
 
-    check_flag!(
-        doc = "Core cycle event not available if 1.",
-        is_core_cyc_ev_unavailable,
-        ebx,
-        PerformanceMonitoringFeaturesEbx::CORE_CYC_EV_UNAVAILABLE
-    );
+impl Debug for L2CatInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("L2CatInfo")
+            .field("capacity_mask_length", &self.capacity_mask_length())
+            .field("isolation_bitmap", &self.isolation_bitmap())
+            .field("highest_cos", &self.highest_cos())
+            .finish()
+    }
+}
 
-    check_flag!(
-        doc = "Instruction retired event not available if 1.",
-        is_inst_ret_ev_unavailable,
-        ebx,
-        PerformanceMonitoringFeaturesEbx::INST_RET_EV_UNAVAILABLE
-    );
+/// Memory Bandwidth Allocation Enumeration Sub-leaf (LEAF=0x10, SUBLEAF=3).
+#[derive(Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemBwAllocationInfo {
+    eax: u32,
+    ecx: u32,
+    edx: u32,
+}
 
-    check_flag!(
-        doc = "Reference cycles event not available if 1.",
-        is_ref_cycle_ev_unavailable,
-        ebx,
-        PerformanceMonitoringFeaturesEbx::REF_CYC_EV_UNAVAILABLE
-    );
+impl MemBwAllocationInfo {
+    /// Decodes the registers of LEAF=0x10, subleaf 3.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ecx: data.ecx,
+            edx: data.edx,
+        }
+    }
 
-    check_flag!(
-        doc = "Last-level cache reference event not available if 1.",
-        is_cache_ref_ev_unavailable,
-        ebx,
-        PerformanceMonitoringFeaturesEbx::CACHE_REF_EV_UNAVAILABLE
-    );
+    /// Reports the maximum MBA throttling value supported for the corresponding ResID.
+    pub fn max_hba_throttling(&self) -> u16 {
+        (get_bits(self.eax, 0, 11) + 1) as u16
+    }
 
-    check_flag!(
-        doc = "Last-level cache misses event not available if 1.",
-        is_ll_cache_miss_ev_unavailable,
-        ebx,
-        PerformanceMonitoringFeaturesEbx::LL_CACHE_MISS_EV_UNAVAILABLE
+    /// Highest COS number supported for this Leaf.
+    pub fn highest_cos(&self) -> u16 {
+        get_bits(self.edx, 0, 15) as u16
+    }
+
+    check_bit_fn!(
+        doc = "Reports whether the response of the delay values is linear.",
+        has_linear_response_delay,
+        ecx,
+        2
     );
 
-    check_flag!(
-        doc = "Branch instruction retired event not available if 1.",
-        is_branch_inst_ret_ev_unavailable,
-        ebx,
-        PerformanceMonitoringFeaturesEbx::BRANCH_INST_RET_EV_UNAVAILABLE
-    );
+    /// Encode this struct back into the registers of leaf 0x10 (subleaf 3).
+    ///
+    /// EBX is reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: 0,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
 
-    check_flag!(
-        doc = "Branch mispredict retired event not available if 1.",
-        is_branch_midpred_ev_unavailable,
-        ebx,
-        PerformanceMonitoringFeaturesEbx::BRANCH_MISPRED_EV_UNAVAILABLE
-    );
+    set_count_fn!(set_max_hba_throttling, max_hba_throttling, eax, 0, 11, u16);
+    set_bits_fn!(set_highest_cos, highest_cos, edx, 0, 15, u16);
+    set_bit_fn!(set_linear_response_delay, has_linear_response_delay, ecx, 2);
 }
 
-impl Debug for PerformanceMonitoringInfo {
+impl Debug for MemBwAllocationInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("PerformanceMonitoringInfo")
-            .field("version_id", &self.version_id())
-            .field("number_of_counters", &self.number_of_counters())
-            .field("counter_bit_width", &self.counter_bit_width())
-            .field("ebx_length", &self.ebx_length())
-            .field("fixed_function_counters", &self.fixed_function_counters())
+        f.debug_struct("MemBwAllocationInfo")
+            .field("max_hba_throttling", &self.max_hba_throttling())
+            .field("highest_cos", &self.highest_cos())
             .field(
-                "fixed_function_counters_bit_width",
-                &self.fixed_function_counters_bit_width(),
+                "has_linear_response_delay",
+                &self.has_linear_response_delay(),
             )
             .finish()
     }
 }
 
-bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-    struct PerformanceMonitoringFeaturesEbx: u32 {
-        /// Core cycle event not available if 1. (Bit 0)
-        const CORE_CYC_EV_UNAVAILABLE = 1 << 0;
-        /// Instruction retired event not available if 1. (Bit 01)
-        const INST_RET_EV_UNAVAILABLE = 1 << 1;
-        /// Reference cycles event not available if 1. (Bit 02)
-        const REF_CYC_EV_UNAVAILABLE = 1 << 2;
-        /// Last-level cache reference event not available if 1. (Bit 03)
-        const CACHE_REF_EV_UNAVAILABLE = 1 << 3;
-        /// Last-level cache misses event not available if 1. (Bit 04)
-        const LL_CACHE_MISS_EV_UNAVAILABLE = 1 << 4;
-        /// Branch instruction retired event not available if 1. (Bit 05)
-        const BRANCH_INST_RET_EV_UNAVAILABLE = 1 << 5;
-        /// Branch mispredict retired event not available if 1. (Bit 06)
-        const BRANCH_MISPRED_EV_UNAVAILABLE = 1 << 6;
-    }
-}
-
-/// Information about topology (LEAF=0x0B).
+/// Intel SGX Capability Enumeration Leaf (LEAF=0x12).
 ///
-/// Iterates over the system topology in order to retrieve more system
-/// information at each level of the topology: how many cores and what kind of
-/// cores
+/// Two sub-leafs: (EAX = 12H, ECX = 0 and ECX = 1)
 ///
 /// # Platforms
-/// ✅ AMD ✅ Intel
-#[derive(Clone)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct ExtendedTopologyIter {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
-    level: u32,
-}
-
-/// Gives information about the current level in the topology.
-///
-/// How many cores, what type etc.
-#[derive(PartialEq, Eq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct ExtendedTopologyLevel {
+/// ❌ AMD ✅ Intel
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SgxInfo<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
     eax: u32,
     ebx: u32,
-    ecx: u32,
+    _ecx: u32,
     edx: u32,
+    eax1: u32,
+    ebx1: u32,
+    ecx1: u32,
+    edx1: u32,
 }
 
-impl fmt::Debug for ExtendedTopologyLevel {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("ExtendedTopologyLevel")
-            .field("processors", &self.processors())
-            .field("number", &self.level_number())
-            .field("type", &self.level_type())
-            .field("x2apic_id", &self.x2apic_id())
-            .field("next_apic_id", &self.shift_right_for_next_apic_id())
-            .finish()
+eq_without_reader!(SgxInfo, eax, ebx, _ecx, edx, eax1, ebx1, ecx1, edx1);
+
+impl<R: CpuIdReader> SgxInfo<R> {
+    check_bit_fn!(doc = "Has SGX1 support.", has_sgx1, eax, 0);
+    check_bit_fn!(doc = "Has SGX2 support.", has_sgx2, eax, 1);
+
+    check_bit_fn!(
+        doc = "Supports ENCLV instruction leaves EINCVIRTCHILD, EDECVIRTCHILD, and ESETCONTEXT.",
+        has_enclv_leaves_einvirtchild_edecvirtchild_esetcontext,
+        eax,
+        5
+    );
+
+    check_bit_fn!(
+        doc = "Supports ENCLS instruction leaves ETRACKC, ERDINFO, ELDBC, and ELDUC.",
+        has_encls_leaves_etrackc_erdinfo_eldbc_elduc,
+        eax,
+        6
+    );
+
+    /// Bit vector of supported extended SGX features.
+    pub fn miscselect(&self) -> u32 {
+        self.ebx
     }
-}
 
-impl ExtendedTopologyLevel {
-    /// Number of logical processors at this level type.
-    /// The number reflects configuration as shipped.
-    pub fn processors(&self) -> u16 {
-        get_bits(self.ebx, 0, 15) as u16
+    ///  The maximum supported enclave size in non-64-bit mode is 2^retval.
+    pub fn max_enclave_size_non_64bit(&self) -> u8 {
+        get_bits(self.edx, 0, 7) as u8
     }
 
-    /// Level number.
-    pub fn level_number(&self) -> u8 {
-        get_bits(self.ecx, 0, 7) as u8
+    ///  The maximum supported enclave size in 64-bit mode is 2^retval.
+    pub fn max_enclave_size_64bit(&self) -> u8 {
+        get_bits(self.edx, 8, 15) as u8
     }
 
-    // Level type.
-    pub fn level_type(&self) -> TopologyType {
-        match get_bits(self.ecx, 8, 15) {
-            0 => TopologyType::Invalid,
-            1 => TopologyType::SMT,
-            2 => TopologyType::Core,
-            _ => unreachable!(),
-        }
+    /// The maximum supported enclave size in non-64-bit mode in bytes.
+    ///
+    /// Saturates at `u64::MAX`.
+    pub fn max_enclave_bytes_non_64bit(&self) -> u64 {
+        1u64.checked_shl(self.max_enclave_size_non_64bit() as u32)
+            .unwrap_or(u64::MAX)
     }
 
-    /// x2APIC ID the current logical processor. (Bits 31-00)
-    pub fn x2apic_id(&self) -> u32 {
-        self.edx
+    /// The maximum supported enclave size in 64-bit mode in bytes.
+    ///
+    /// Saturates at `u64::MAX`.
+    pub fn max_enclave_bytes_64bit(&self) -> u64 {
+        1u64.checked_shl(self.max_enclave_size_64bit() as u32)
+            .unwrap_or(u64::MAX)
     }
 
-    /// Number of bits to shift right on x2APIC ID to get a unique topology ID of the next level type. (Bits 04-00)
-    /// All logical processors with the same next level ID share current level.
-    pub fn shift_right_for_next_apic_id(&self) -> u32 {
-        get_bits(self.eax, 0, 4)
+    /// Reports the valid bits of SECS.ATTRIBUTES\[127:0\] that software can set with ECREATE.
+    pub fn secs_attributes(&self) -> (u64, u64) {
+        let lower = self.eax1 as u64 | (self.ebx1 as u64) << 32;
+        let upper = self.ecx1 as u64 | (self.edx1 as u64) << 32;
+        (lower, upper)
     }
-}
 
-/// What type of core we have at this level in the topology (real CPU or hyper-threaded).
-#[derive(PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub enum TopologyType {
-    Invalid = 0,
-    /// Hyper-thread (Simultaneous multithreading)
-    SMT = 1,
-    Core = 2,
-}
+    check_bit_fn!(
+        doc = "Enclaves can be created with SECS.ATTRIBUTES.DEBUG set.",
+        has_attribute_debug,
+        eax1,
+        1
+    );
+    check_bit_fn!(
+        doc = "Enclaves can be created with SECS.ATTRIBUTES.MODE64BIT set.",
+        has_attribute_mode64bit,
+        eax1,
+        2
+    );
+    check_bit_fn!(
+        doc = "Enclaves can be created with SECS.ATTRIBUTES.PROVISIONKEY set.",
+        has_attribute_provisionkey,
+        eax1,
+        4
+    );
+    check_bit_fn!(
+        doc = "Enclaves can be created with SECS.ATTRIBUTES.EINITTOKEN_KEY set.",
+        has_attribute_einittoken_key,
+        eax1,
+        5
+    );
+    check_bit_fn!(
+        doc = "Enclaves can be created with SECS.ATTRIBUTES.CET set.",
+        has_attribute_cet,
+        eax1,
+        6
+    );
+    check_bit_fn!(
+        doc = "Enclaves can be created with SECS.ATTRIBUTES.KSS (Key Separation and Sharing) set.",
+        has_attribute_kss,
+        eax1,
+        7
+    );
+    check_bit_fn!(
+        doc = "Enclaves can be created with SECS.ATTRIBUTES.AEXNOTIFY set.",
+        has_attribute_aexnotify,
+        eax1,
+        10
+    );
 
-impl fmt::Display for TopologyType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let data = match self {
-            TopologyType::Invalid => "Invalid",
-            TopologyType::SMT => "SMT",
-            TopologyType::Core => "Core",
-        };
+    /// Total size of the Enclave Page Cache, summed over all EPC sections.
+    pub fn epc_size(&self) -> u64 {
+        self.iter()
+            .map(|section| match section {
+                SgxSectionInfo::Epc(epc) => epc.size(),
+            })
+            .sum()
+    }
+    /// Iterator over SGX sub-leafs.
+    pub fn iter(&self) -> SgxSectionIter<R> {
+        SgxSectionIter::new(self.read.clone())
+    }
 
-        f.write_str(data)
+    /// Encode this struct back into the registers of leaf 0x12, subleafs 0 and 1.
+    ///
+    /// The EPC sections are available through [`EpcSection::as_cpuid_result`].
+    pub fn as_cpuid_results(&self) -> [CpuIdResult; 2] {
+        [
+            CpuIdResult {
+                eax: self.eax,
+                ebx: self.ebx,
+                ecx: self._ecx,
+                edx: self.edx,
+            },
+            CpuIdResult {
+                eax: self.eax1,
+                ebx: self.ebx1,
+                ecx: self.ecx1,
+                edx: self.edx1,
+            },
+        ]
+    }
+
+    set_bit_fn!(set_sgx1, has_sgx1, eax, 0);
+    set_bit_fn!(set_sgx2, has_sgx2, eax, 1);
+    set_bit_fn!(
+        set_enclv_leaves_einvirtchild_edecvirtchild_esetcontext,
+        has_enclv_leaves_einvirtchild_edecvirtchild_esetcontext,
+        eax,
+        5
+    );
+    set_bit_fn!(
+        set_encls_leaves_etrackc_erdinfo_eldbc_elduc,
+        has_encls_leaves_etrackc_erdinfo_eldbc_elduc,
+        eax,
+        6
+    );
+    set_bits_fn!(set_miscselect, miscselect, ebx, 0, 31, u32);
+    set_bits_fn!(
+        set_max_enclave_size_non_64bit,
+        max_enclave_size_non_64bit,
+        edx,
+        0,
+        7,
+        u8
+    );
+    set_bits_fn!(
+        set_max_enclave_size_64bit,
+        max_enclave_size_64bit,
+        edx,
+        8,
+        15,
+        u8
+    );
+}
+
+impl<R: CpuIdReader> Debug for SgxInfo<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SgxInfo")
+            .field("has_sgx1", &self.has_sgx1())
+            .field("has_sgx2", &self.has_sgx2())
+            .field("miscselect", &self.miscselect())
+            .field(
+                "max_enclave_size_non_64bit",
+                &self.max_enclave_size_non_64bit(),
+            )
+            .field("max_enclave_size_64bit", &self.max_enclave_size_64bit())
+            .field(
+                "has_encls_leaves_etrackc_erdinfo_eldbc_elduc",
+                &self.has_encls_leaves_etrackc_erdinfo_eldbc_elduc(),
+            )
+            .field(
+                "has_enclv_leaves_einvirtchild_edecvirtchild_esetcontext",
+                &self.has_enclv_leaves_einvirtchild_edecvirtchild_esetcontext(),
+            )
+            .field("sgx_section_iter", &self.iter())
+            .finish()
     }
 }
 
-impl Iterator for ExtendedTopologyIter {
-    type Item = ExtendedTopologyLevel;
+/// Iterator over the SGX sub-leafs (ECX >= 2).
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SgxSectionIter<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    subleafs: Subleafs,
+    current: u32,
+    end: u32,
+}
 
-    fn next(&mut self) -> Option<ExtendedTopologyLevel> {
-        let res = self.read.cpuid2(EAX_EXTENDED_TOPOLOGY_INFO, self.level);
-        self.level += 1;
+impl<R: CpuIdReader> SgxSectionIter<R> {
+    fn new(read: R) -> Self {
+        let subleafs = Subleafs::read(&read, EAX_SGX, 2, |res| get_bits(res.eax, 0, 3) == 0b0001);
+        Self {
+            read,
+            current: 2,
+            end: subleafs.end,
+            subleafs,
+        }
+    }
 
-        let et = ExtendedTopologyLevel {
+    fn section(&self, subleaf: u32) -> SgxSectionInfo {
+        let res = self.subleafs.get(&self.read, subleaf);
+        SgxSectionInfo::Epc(EpcSection {
             eax: res.eax,
             ebx: res.ebx,
             ecx: res.ecx,
             edx: res.edx,
-        };
+        })
+    }
+}
+
+impl<R: CpuIdReader> Iterator for SgxSectionIter<R> {
+    type Item = SgxSectionInfo;
 
-        match et.level_type() {
-            TopologyType::Invalid => None,
-            _ => Some(et),
+    fn next(&mut self) -> Option<SgxSectionInfo> {
+        if self.current >= self.end {
+            return None;
         }
+        self.current += 1;
+        Some(self.section(self.current - 1))
     }
-}
 
-impl Debug for ExtendedTopologyIter {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        let mut debug = f.debug_list();
-        self.clone().for_each(|ref item| {
-            debug.entry(item);
-        });
-        debug.finish()
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.current) as usize;
+        (len, Some(len))
     }
 }
 
-bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-    struct ExtendedStateInfoXCR0Flags: u32 {
-        /// legacy x87 (Bit 00).
-        const LEGACY_X87 = 1 << 0;
-
-        /// 128-bit SSE (Bit 01).
-        const SSE128 = 1 << 1;
-
-        /// 256-bit AVX (Bit 02).
-        const AVX256 = 1 << 2;
-
-        /// MPX BNDREGS (Bit 03).
-        const MPX_BNDREGS = 1 << 3;
+impl<R: CpuIdReader> DoubleEndedIterator for SgxSectionIter<R> {
+    fn next_back(&mut self) -> Option<SgxSectionInfo> {
+        if self.current >= self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(self.section(self.end))
+    }
+}
 
-        /// MPX BNDCSR (Bit 04).
-        const MPX_BNDCSR = 1 << 4;
+impl<R: CpuIdReader> ExactSizeIterator for SgxSectionIter<R> {}
 
-        /// AVX512 OPMASK (Bit 05).
-        const AVX512_OPMASK = 1 << 5;
+impl<R: CpuIdReader> FusedIterator for SgxSectionIter<R> {}
 
-        /// AVX ZMM Hi256 (Bit 06).
-        const AVX512_ZMM_HI256 = 1 << 6;
+impl<R: CpuIdReader> Debug for SgxSectionIter<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut debug = f.debug_list();
+        self.clone().for_each(|ref item| {
+            debug.entry(item);
+        });
+        debug.finish()
+    }
+}
 
-        /// AVX 512 ZMM Hi16 (Bit 07).
-        const AVX512_ZMM_HI16 = 1 << 7;
+/// Intel SGX EPC Enumeration Leaf
+///
+/// Sub-leaves 2 or higher.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SgxSectionInfo {
+    // This would be nice: https://github.com/rust-lang/rfcs/pull/1450
+    Epc(EpcSection),
+}
 
-        /// PKRU state (Bit 09).
-        const PKRU = 1 << 9;
+/// EBX:EAX and EDX:ECX provide information on the Enclave Page Cache (EPC) section
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EpcSection {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+    edx: u32,
+}
 
-        /// IA32_XSS HDC State (Bit 13).
-        const IA32_XSS_HDC = 1 << 13;
+impl EpcSection {
+    /// Decodes the registers of an EPC section subleaf (>= 2) of LEAF=0x12.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+            edx: data.edx,
+        }
     }
-}
 
-bitflags! {
-    #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-    struct ExtendedStateInfoXSSFlags: u32 {
-        /// IA32_XSS PT (Trace Packet) State (Bit 08).
-        const PT = 1 << 8;
+    /// The physical address of the base of the EPC section
+    pub fn physical_base(&self) -> u64 {
+        let lower = (get_bits(self.eax, 12, 31) << 12) as u64;
+        let upper = (get_bits(self.ebx, 0, 19) as u64) << 32;
+        lower | upper
+    }
 
-        /// IA32_XSS HDC State (Bit 13).
-        const HDC = 1 << 13;
+    /// Size of the corresponding EPC section within the Processor Reserved Memory.
+    pub fn size(&self) -> u64 {
+        let lower = (get_bits(self.ecx, 12, 31) << 12) as u64;
+        let upper = (get_bits(self.edx, 0, 19) as u64) << 32;
+        lower | upper
+    }
+
+    /// Encode this section back into the registers of its leaf 0x12 subleaf.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
     }
 }
 
-/// Information for saving/restoring extended register state (LEAF=0x0D).
+/// Intel Processor Trace Information (LEAF=0x14).
 ///
 /// # Platforms
-/// ✅ AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct ExtendedStateInfo {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
-    eax: ExtendedStateInfoXCR0Flags,
+/// ❌ AMD ✅ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessorTraceInfo {
+    _eax: u32,
     ebx: u32,
     ecx: u32,
     _edx: u32,
-    eax1: u32,
-    ebx1: u32,
-    ecx1: ExtendedStateInfoXSSFlags,
-    _edx1: u32,
+    leaf1: Option<CpuIdResult>,
 }
 
-impl ExtendedStateInfo {
-    check_flag!(
-        doc = "Support for legacy x87 in XCR0.",
-        xcr0_supports_legacy_x87,
-        eax,
-        ExtendedStateInfoXCR0Flags::LEGACY_X87
-    );
+impl ProcessorTraceInfo {
+    /// Decodes the registers of LEAF=0x14, `subleaf1` is `None` if the CPU doesn't support subleaf 1.
+    pub fn from_registers(data: CpuIdResult, subleaf1: Option<CpuIdResult>) -> Self {
+        Self {
+            _eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+            _edx: data.edx,
+            leaf1: subleaf1,
+        }
+    }
 
-    check_flag!(
-        doc = "Support for SSE 128-bit in XCR0.",
-        xcr0_supports_sse_128,
-        eax,
-        ExtendedStateInfoXCR0Flags::SSE128
+    // EBX features
+    check_bit_fn!(
+        doc = "If true, Indicates that IA32_RTIT_CTL.CR3Filter can be set to 1, and \
+               that IA32_RTIT_CR3_MATCH MSR can be accessed.",
+        has_rtit_cr3_match,
+        ebx,
+        0
     );
-
-    check_flag!(
-        doc = "Support for AVX 256-bit in XCR0.",
-        xcr0_supports_avx_256,
-        eax,
-        ExtendedStateInfoXCR0Flags::AVX256
+    check_bit_fn!(
+        doc = "If true, Indicates support of Configurable PSB and Cycle-Accurate Mode.",
+        has_configurable_psb_and_cycle_accurate_mode,
+        ebx,
+        1
     );
-
-    check_flag!(
-        doc = "Support for MPX BNDREGS in XCR0.",
-        xcr0_supports_mpx_bndregs,
-        eax,
-        ExtendedStateInfoXCR0Flags::MPX_BNDREGS
+    check_bit_fn!(
+        doc = "If true, Indicates support of IP Filtering, TraceStop filtering, and \
+               preservation of Intel PT MSRs across warm reset.",
+        has_ip_tracestop_filtering,
+        ebx,
+        2
     );
-
-    check_flag!(
-        doc = "Support for MPX BNDCSR in XCR0.",
-        xcr0_supports_mpx_bndcsr,
-        eax,
-        ExtendedStateInfoXCR0Flags::MPX_BNDCSR
+    check_bit_fn!(
+        doc = "If true, Indicates support of MTC timing packet and suppression of \
+               COFI-based packets.",
+        has_mtc_timing_packet_coefi_suppression,
+        ebx,
+        3
     );
 
-    check_flag!(
-        doc = "Support for AVX512 OPMASK in XCR0.",
-        xcr0_supports_avx512_opmask,
-        eax,
-        ExtendedStateInfoXCR0Flags::AVX512_OPMASK
+    check_bit_fn!(
+        doc = "Indicates support of PTWRITE. Writes can set IA32_RTIT_CTL\\[12\\] (PTWEn \
+               and IA32_RTIT_CTL\\[5\\] (FUPonPTW), and PTWRITE can generate packets",
+        has_ptwrite,
+        ebx,
+        4
     );
 
-    check_flag!(
-        doc = "Support for AVX512 ZMM Hi256 XCR0.",
-        xcr0_supports_avx512_zmm_hi256,
-        eax,
-        ExtendedStateInfoXCR0Flags::AVX512_ZMM_HI256
+    check_bit_fn!(
+        doc = "Support of Power Event Trace. Writes can set IA32_RTIT_CTL\\[4\\] (PwrEvtEn) \
+               enabling Power Event Trace packet generation.",
+        has_power_event_trace,
+        ebx,
+        5
     );
 
-    check_flag!(
-        doc = "Support for AVX512 ZMM Hi16 in XCR0.",
-        xcr0_supports_avx512_zmm_hi16,
-        eax,
-        ExtendedStateInfoXCR0Flags::AVX512_ZMM_HI16
+    // ECX features
+    check_bit_fn!(
+        doc = "If true, Tracing can be enabled with IA32_RTIT_CTL.ToPA = 1, hence \
+               utilizing the ToPA output scheme; IA32_RTIT_OUTPUT_BASE and \
+               IA32_RTIT_OUTPUT_MASK_PTRS MSRs can be accessed.",
+        has_topa,
+        ecx,
+        0
     );
-
-    check_flag!(
-        doc = "Support for PKRU in XCR0.",
-        xcr0_supports_pkru,
-        eax,
-        ExtendedStateInfoXCR0Flags::PKRU
+    check_bit_fn!(
+        doc = "If true, ToPA tables can hold any number of output entries, up to the \
+               maximum allowed by the MaskOrTableOffset field of \
+               IA32_RTIT_OUTPUT_MASK_PTRS.",
+        has_topa_maximum_entries,
+        ecx,
+        1
     );
-
-    check_flag!(
-        doc = "Support for PT in IA32_XSS.",
-        ia32_xss_supports_pt,
-        ecx1,
-        ExtendedStateInfoXSSFlags::PT
+    check_bit_fn!(
+        doc = "If true, Indicates support of Single-Range Output scheme.",
+        has_single_range_output_scheme,
+        ecx,
+        2
     );
-
-    check_flag!(
-        doc = "Support for HDC in IA32_XSS.",
-        ia32_xss_supports_hdc,
-        ecx1,
-        ExtendedStateInfoXSSFlags::HDC
+    check_bit_fn!(
+        doc = "If true, Indicates support of output to Trace Transport subsystem.",
+        has_trace_transport_subsystem,
+        ecx,
+        3
+    );
+    check_bit_fn!(
+        doc = "If true, Generated packets which contain IP payloads have LIP values, \
+               which include the CS base component.",
+        has_lip_with_cs_base,
+        ecx,
+        31
     );
 
-    /// Maximum size (bytes, from the beginning of the XSAVE/XRSTOR save area) required by
-    /// enabled features in XCR0. May be different than ECX if some features at the end of the XSAVE save area
-    /// are not enabled.
-    pub fn xsave_area_size_enabled_features(&self) -> u32 {
-        self.ebx
-    }
-
-    /// Maximum size (bytes, from the beginning of the XSAVE/XRSTOR save area) of the
-    /// XSAVE/XRSTOR save area required by all supported features in the processor,
-    /// i.e all the valid bit fields in XCR0.
-    pub fn xsave_area_size_supported_features(&self) -> u32 {
-        self.ecx
-    }
-
-    /// CPU has xsaveopt feature.
-    pub fn has_xsaveopt(&self) -> bool {
-        self.eax1 & 0x1 > 0
-    }
-
-    /// Supports XSAVEC and the compacted form of XRSTOR if set.
-    pub fn has_xsavec(&self) -> bool {
-        self.eax1 & 0b10 > 0
-    }
-
-    /// Supports XGETBV with ECX = 1 if set.
-    pub fn has_xgetbv(&self) -> bool {
-        self.eax1 & 0b100 > 0
+    /// Number of configurable Address Ranges for filtering (Bits 2:0).
+    pub fn configurable_address_ranges(&self) -> u8 {
+        self.leaf1.map_or(0, |res| get_bits(res.eax, 0, 2) as u8)
     }
 
-    /// Supports XSAVES/XRSTORS and IA32_XSS if set.
-    pub fn has_xsaves_xrstors(&self) -> bool {
-        self.eax1 & 0b1000 > 0
+    /// Bitmap of supported MTC period encodings (Bit 31:16).
+    pub fn supported_mtc_period_encodings(&self) -> u16 {
+        self.leaf1.map_or(0, |res| get_bits(res.eax, 16, 31) as u16)
     }
 
-    /// The size in bytes of the XSAVE area containing all states enabled by XCRO | IA32_XSS.
-    pub fn xsave_size(&self) -> u32 {
-        self.ebx1
+    /// Bitmap of supported Cycle Threshold value encodings (Bits 15-0).
+    pub fn supported_cycle_threshold_value_encodings(&self) -> u16 {
+        self.leaf1.map_or(0, |res| get_bits(res.ebx, 0, 15) as u16)
     }
 
-    /// Iterator over extended state enumeration levels >= 2.
-    pub fn iter(&self) -> ExtendedStateIter {
-        ExtendedStateIter {
-            read: self.read,
-            level: 1,
-            supported_xcr0: self.eax.bits(),
-            supported_xss: self.ecx1.bits(),
-        }
+    /// Bitmap of supported Configurable PSB frequency encodings (Bit 31:16)
+    pub fn supported_psb_frequency_encodings(&self) -> u16 {
+        self.leaf1.map_or(0, |res| get_bits(res.ebx, 16, 31) as u16)
     }
+
+    /// Encode this struct back into the registers of leaf 0x14.
+    ///
+    /// Returns subleaf 0 and, if the CPU reported one, subleaf 1.
+    pub fn as_cpuid_results(&self) -> (CpuIdResult, Option<CpuIdResult>) {
+        (
+            CpuIdResult {
+                eax: self._eax,
+                ebx: self.ebx,
+                ecx: self.ecx,
+                edx: self._edx,
+            },
+            self.leaf1,
+        )
+    }
+
+    set_bit_fn!(set_rtit_cr3_match, has_rtit_cr3_match, ebx, 0);
+    set_bit_fn!(
+        set_configurable_psb_and_cycle_accurate_mode,
+        has_configurable_psb_and_cycle_accurate_mode,
+        ebx,
+        1
+    );
+    set_bit_fn!(
+        set_ip_tracestop_filtering,
+        has_ip_tracestop_filtering,
+        ebx,
+        2
+    );
+    set_bit_fn!(
+        set_mtc_timing_packet_coefi_suppression,
+        has_mtc_timing_packet_coefi_suppression,
+        ebx,
+        3
+    );
+    set_bit_fn!(set_ptwrite, has_ptwrite, ebx, 4);
+    set_bit_fn!(set_power_event_trace, has_power_event_trace, ebx, 5);
+    set_bit_fn!(set_topa, has_topa, ecx, 0);
+    set_bit_fn!(set_topa_maximum_entries, has_topa_maximum_entries, ecx, 1);
+    set_bit_fn!(
+        set_single_range_output_scheme,
+        has_single_range_output_scheme,
+        ecx,
+        2
+    );
+    set_bit_fn!(
+        set_trace_transport_subsystem,
+        has_trace_transport_subsystem,
+        ecx,
+        3
+    );
+    set_bit_fn!(set_lip_with_cs_base, has_lip_with_cs_base, ecx, 31);
 }
 
-impl Debug for ExtendedStateInfo {
+impl Debug for ProcessorTraceInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("ExtendedStateInfo")
-            .field("eax", &self.eax)
-            .field("ecx1", &self.ecx1)
+        f.debug_struct("ProcessorTraceInfo")
             .field(
-                "xsave_area_size_enabled_features",
-                &self.xsave_area_size_enabled_features(),
+                "configurable_address_ranges",
+                &self.configurable_address_ranges(),
             )
             .field(
-                "xsave_area_size_supported_features",
-                &self.xsave_area_size_supported_features(),
+                "supported_mtc_period_encodings",
+                &self.supported_mtc_period_encodings(),
+            )
+            .field(
+                "supported_cycle_threshold_value_encodings",
+                &self.supported_cycle_threshold_value_encodings(),
+            )
+            .field(
+                "supported_psb_frequency_encodings",
+                &self.supported_psb_frequency_encodings(),
             )
-            .field("has_xsaveopt", &self.has_xsaveopt())
-            .field("has_xsavec", &self.has_xsavec())
-            .field("has_xgetbv", &self.has_xgetbv())
-            .field("has_xsaves_xrstors", &self.has_xsaves_xrstors())
-            .field("xsave_size", &self.xsave_size())
-            .field("extended_state_iter", &self.iter())
             .finish()
     }
 }
 
-/// Yields [ExtendedState] structs.
-#[derive(Clone)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct ExtendedStateIter {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
-    level: u32,
-    supported_xcr0: u32,
-    supported_xss: u32,
-}
-
-/// When CPUID executes with EAX set to 0DH and ECX = n (n > 1, and is a valid
-/// sub-leaf index), the processor returns information about the size and offset
-/// of each processor extended state save area within the XSAVE/XRSTOR area.
+/// Time Stamp Counter/Core Crystal Clock Information (LEAF=0x15).
 ///
-/// The iterator goes over the valid sub-leaves and obtain size and offset
-/// information for each processor extended state save area:
-impl Iterator for ExtendedStateIter {
-    type Item = ExtendedState;
-
-    fn next(&mut self) -> Option<ExtendedState> {
-        self.level += 1;
-        if self.level > 31 {
-            return None;
-        }
-
-        let bit = 1 << self.level;
-        if (self.supported_xcr0 & bit > 0) || (self.supported_xss & bit > 0) {
-            let res = self.read.cpuid2(EAX_EXTENDED_STATE_INFO, self.level);
-            return Some(ExtendedState {
-                subleaf: self.level,
-                eax: res.eax,
-                ebx: res.ebx,
-                ecx: res.ecx,
-            });
-        }
-
-        self.next()
-    }
+/// # Platforms
+/// ❌ AMD ✅ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TscInfo {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
 }
 
-impl Debug for ExtendedStateIter {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut debug = f.debug_list();
-        self.clone().for_each(|ref item| {
-            debug.entry(item);
-        });
-        debug.finish()
+impl fmt::Debug for TscInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TscInfo")
+            .field("denominator", &self.denominator())
+            .field("numerator", &self.numerator())
+            .field("nominal_frequency", &self.nominal_frequency())
+            .field("tsc_frequency", &self.tsc_frequency())
+            .finish()
     }
 }
 
-/// What kidn of extended register state this is.
-#[derive(PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-#[repr(u32)]
-pub enum ExtendedRegisterType {
-    Avx,
-    MpxBndregs,
-    MpxBndcsr,
-    Avx512Opmask,
-    Avx512ZmmHi256,
-    Avx512ZmmHi16,
-    Pt,
-    Pkru,
-    Hdc,
-    Unknown(u32),
-}
-
-impl From<u32> for ExtendedRegisterType {
-    fn from(value: u32) -> ExtendedRegisterType {
-        match value {
-            0x2 => ExtendedRegisterType::Avx,
-            0x3 => ExtendedRegisterType::MpxBndregs,
-            0x4 => ExtendedRegisterType::MpxBndcsr,
-            0x5 => ExtendedRegisterType::Avx512Opmask,
-            0x6 => ExtendedRegisterType::Avx512ZmmHi256,
-            0x7 => ExtendedRegisterType::Avx512ZmmHi16,
-            0x8 => ExtendedRegisterType::Pt,
-            0x9 => ExtendedRegisterType::Pkru,
-            0xd => ExtendedRegisterType::Hdc,
-            x => ExtendedRegisterType::Unknown(x),
+impl TscInfo {
+    /// Decodes the registers of LEAF=0x15.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
         }
     }
-}
 
-impl fmt::Display for ExtendedRegisterType {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let data = match self {
-            ExtendedRegisterType::Avx => "AVX/YMM",
-            ExtendedRegisterType::MpxBndregs => "MPX BNDREGS",
-            ExtendedRegisterType::MpxBndcsr => "MPX BNDCSR",
-            ExtendedRegisterType::Avx512Opmask => "AVX-512 opmask",
-            ExtendedRegisterType::Avx512ZmmHi256 => "AVX-512 ZMM_Hi256",
-            ExtendedRegisterType::Avx512ZmmHi16 => "AVX-512 Hi16_ZMM",
-            ExtendedRegisterType::Pkru => "PKRU",
-            ExtendedRegisterType::Pt => "PT",
-            ExtendedRegisterType::Hdc => "HDC",
-            ExtendedRegisterType::Unknown(t) => {
-                return write!(f, "Unknown({})", t);
-            }
-        };
+    /// An unsigned integer which is the denominator of the TSC/”core crystal clock” ratio.
+    pub fn denominator(&self) -> u32 {
+        self.eax
+    }
 
-        f.write_str(data)
+    /// An unsigned integer which is the numerator of the TSC/”core crystal clock” ratio.
+    ///
+    /// If this is 0, the TSC/”core crystal clock” ratio is not enumerated.
+    pub fn numerator(&self) -> u32 {
+        self.ebx
     }
-}
 
-/// Where the extended register state is stored.
-#[derive(PartialEq, Eq, Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub enum ExtendedRegisterStateLocation {
-    Xcr0,
-    Ia32Xss,
-}
+    /// An unsigned integer which is the nominal frequency of the core crystal clock in Hz.
+    ///
+    /// If this is 0, the nominal core crystal clock frequency is not enumerated.
+    pub fn nominal_frequency(&self) -> u32 {
+        self.ecx
+    }
 
-impl fmt::Display for ExtendedRegisterStateLocation {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let data = match self {
-            ExtendedRegisterStateLocation::Xcr0 => "XCR0 (user state)",
-            ExtendedRegisterStateLocation::Ia32Xss => "IA32_XSS (supervisor state)",
-        };
+    /// “TSC frequency” = “core crystal clock frequency” * EBX/EAX.
+    pub fn tsc_frequency(&self) -> Option<u64> {
+        // In some case TscInfo is a valid leaf, but the values reported are still 0
+        // we should avoid a division by zero in case denominator ends up being 0.
+        if self.nominal_frequency() == 0 || self.numerator() == 0 || self.denominator() == 0 {
+            return None;
+        }
 
-        f.write_str(data)
+        Some(self.nominal_frequency() as u64 * self.numerator() as u64 / self.denominator() as u64)
+    }
+
+    /// Encode this struct back into the registers of leaf 0x15.
+    ///
+    /// EDX is reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: 0,
+        }
     }
+
+    set_bits_fn!(set_denominator, denominator, eax, 0, 31, u32);
+    set_bits_fn!(set_numerator, numerator, ebx, 0, 31, u32);
+    set_bits_fn!(set_nominal_frequency, nominal_frequency, ecx, 0, 31, u32);
 }
 
-/// ExtendedState subleaf structure for things that need to be restored.
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct ExtendedState {
-    pub subleaf: u32,
+/// Processor Frequency Information (LEAF=0x16).
+///
+/// # Platforms
+/// ❌ AMD ✅ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProcessorFrequencyInfo {
     eax: u32,
     ebx: u32,
     ecx: u32,
 }
 
-impl ExtendedState {
-    /// Returns which register this specific extended subleaf contains information for.
-    pub fn register(&self) -> ExtendedRegisterType {
-        self.subleaf.into()
-    }
-
-    /// The size in bytes (from the offset specified in EBX) of the save area
-    /// for an extended state feature associated with a valid sub-leaf index, n.
-    /// This field reports 0 if the sub-leaf index, n, is invalid.
-    pub fn size(&self) -> u32 {
-        self.eax
-    }
-
-    /// The offset in bytes of this extended state components save area
-    /// from the beginning of the XSAVE/XRSTOR area.
-    pub fn offset(&self) -> u32 {
-        self.ebx
+impl ProcessorFrequencyInfo {
+    /// Decodes the registers of LEAF=0x16.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+        }
     }
 
-    pub fn location(&self) -> ExtendedRegisterStateLocation {
-        if self.is_in_xcr0() {
-            ExtendedRegisterStateLocation::Xcr0
-        } else {
-            ExtendedRegisterStateLocation::Ia32Xss
-        }
+    /// Processor Base Frequency (in MHz).
+    pub fn processor_base_frequency(&self) -> u16 {
+        get_bits(self.eax, 0, 15) as u16
     }
 
-    /// True if the bit n (corresponding to the sub-leaf index)
-    /// is supported in the IA32_XSS MSR;
-    ///
-    /// # Deprecation note
-    /// This will likely be removed in the future. Use `location()` instead.
-    pub fn is_in_ia32_xss(&self) -> bool {
-        self.ecx & 0b1 > 0
+    /// Maximum Frequency (in MHz).
+    pub fn processor_max_frequency(&self) -> u16 {
+        get_bits(self.ebx, 0, 15) as u16
     }
 
-    /// True if bit n is supported in XCR0.
-    ///
-    /// # Deprecation note
-    /// This will likely be removed in the future. Use `location()` instead.
-    pub fn is_in_xcr0(&self) -> bool {
-        self.ecx & 0b1 == 0
+    /// Bus (Reference) Frequency (in MHz).
+    pub fn bus_frequency(&self) -> u16 {
+        get_bits(self.ecx, 0, 15) as u16
     }
 
-    /// Returns true when the compacted format of an XSAVE area is used,
-    /// this extended state component located on the next 64-byte
-    /// boundary following the preceding state component
-    /// (otherwise, it is located immediately following the preceding state component).
-    pub fn is_compacted_format(&self) -> bool {
-        self.ecx & 0b10 > 0
+    /// Encode this struct back into the registers of leaf 0x16.
+    ///
+    /// EDX is reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: 0,
+        }
     }
+
+    set_bits_fn!(
+        set_processor_base_frequency,
+        processor_base_frequency,
+        eax,
+        0,
+        15,
+        u16
+    );
+    set_bits_fn!(
+        set_processor_max_frequency,
+        processor_max_frequency,
+        ebx,
+        0,
+        15,
+        u16
+    );
+    set_bits_fn!(set_bus_frequency, bus_frequency, ecx, 0, 15, u16);
 }
 
-impl Debug for ExtendedState {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.debug_struct("ExtendedState")
-            .field("size", &self.size())
-            .field("offset", &self.offset())
-            .field("is_in_ia32_xss", &self.is_in_ia32_xss())
-            .field("is_in_xcr0", &self.is_in_xcr0())
-            .field("is_compacted_format", &self.is_compacted_format())
+impl fmt::Debug for ProcessorFrequencyInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProcessorFrequencyInfo")
+            .field("processor_base_frequency", &self.processor_base_frequency())
+            .field("processor_max_frequency", &self.processor_max_frequency())
+            .field("bus_frequency", &self.bus_frequency())
             .finish()
     }
 }
 
-/// Intel Resource Director Technology RDT (LEAF=0x0F).
+/// Deterministic Address Translation Structure Iterator (LEAF=0x18).
 ///
-/// Monitoring Enumeration Sub-leaf (EAX = 0FH, ECX = 0 and ECX = 1)
 /// # Platforms
 /// ❌ AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct RdtMonitoringInfo {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
-    ebx: u32,
-    edx: u32,
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DatIter<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    current: u32,
+    count: u32,
 }
 
-impl RdtMonitoringInfo {
-    /// Maximum range (zero-based) of RMID within this physical processor of all types.
-    pub fn rmid_range(&self) -> u32 {
-        self.ebx
-    }
+impl<R: CpuIdReader> Iterator for DatIter<R> {
+    type Item = DatInfo;
 
-    check_bit_fn!(
-        doc = "Supports L3 Cache Intel RDT Monitoring.",
-        has_l3_monitoring,
-        edx,
-        1
-    );
+    /// Iterate over each sub-leaf with an address translation structure.
+    fn next(&mut self) -> Option<DatInfo> {
+        loop {
+            // Sub-leaf index n is invalid if n exceeds the value that sub-leaf 0 returns in EAX
+            if self.current > self.count {
+                return None;
+            }
 
-    /// L3 Cache Monitoring.
-    pub fn l3_monitoring(&self) -> Option<L3MonitoringInfo> {
-        if self.has_l3_monitoring() {
-            let res = self.read.cpuid2(EAX_RDT_MONITORING, 1);
-            Some(L3MonitoringInfo {
+            let res = self
+                .read
+                .cpuid2(EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO, self.current);
+            self.current += 1;
+
+            // A sub-leaf index is also invalid if EDX[4:0] returns 0.
+            if get_bits(res.edx, 0, 4) == 0 {
+                // Valid sub-leaves do not need to be contiguous or in any particular order.
+                // A valid sub-leaf may be in a higher input ECX value than an invalid sub-leaf
+                // or than a valid sub-leaf of a higher or lower-level struc-ture
+                continue;
+            }
+
+            return Some(DatInfo {
+                _eax: res.eax,
                 ebx: res.ebx,
                 ecx: res.ecx,
                 edx: res.edx,
-            })
-        } else {
-            None
+            });
         }
     }
 }
 
-impl Debug for RdtMonitoringInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("RdtMonitoringInfo")
-            .field("rmid_range", &self.rmid_range())
-            .field("l3_monitoring", &self.l3_monitoring())
-            .finish()
+impl<R: CpuIdReader> Debug for DatIter<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_list();
+        self.clone().for_each(|ref item| {
+            debug.entry(item);
+        });
+        debug.finish()
     }
 }
 
-/// Information about L3 cache monitoring.
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct L3MonitoringInfo {
+/// Deterministic Address Translation Structure
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DatInfo {
+    _eax: u32,
     ebx: u32,
     ecx: u32,
     edx: u32,
 }
 
-impl L3MonitoringInfo {
-    /// Conversion factor from reported IA32_QM_CTR value to occupancy metric (bytes).
-    pub fn conversion_factor(&self) -> u32 {
-        self.ebx
-    }
-
-    /// Maximum range (zero-based) of RMID of L3.
-    pub fn maximum_rmid_range(&self) -> u32 {
-        self.ecx
+impl DatInfo {
+    /// Decodes the registers of a subleaf of LEAF=0x18.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            _eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+            edx: data.edx,
+        }
     }
 
     check_bit_fn!(
-        doc = "Supports occupancy monitoring.",
-        has_occupancy_monitoring,
-        edx,
+        doc = "4K page size entries supported by this structure",
+        has_4k_entries,
+        ebx,
         0
     );
 
     check_bit_fn!(
-        doc = "Supports total bandwidth monitoring.",
-        has_total_bandwidth_monitoring,
-        edx,
+        doc = "2MB page size entries supported by this structure",
+        has_2mb_entries,
+        ebx,
         1
     );
 
     check_bit_fn!(
-        doc = "Supports local bandwidth monitoring.",
-        has_local_bandwidth_monitoring,
-        edx,
+        doc = "4MB page size entries supported by this structure",
+        has_4mb_entries,
+        ebx,
         2
     );
+
+    check_bit_fn!(
+        doc = "1GB page size entries supported by this structure",
+        has_1gb_entries,
+        ebx,
+        3
+    );
+
+    check_bit_fn!(
+        doc = "Fully associative structure",
+        is_fully_associative,
+        edx,
+        8
+    );
+
+    /// Partitioning (0: Soft partitioning between the logical processors sharing this structure).
+    pub fn partitioning(&self) -> u8 {
+        get_bits(self.ebx, 8, 10) as u8
+    }
+
+    /// Ways of associativity.
+    pub fn ways(&self) -> u16 {
+        get_bits(self.ebx, 16, 31) as u16
+    }
+
+    /// Associativity, combining [`Self::ways`] and
+    /// [`Self::is_fully_associative`].
+    pub fn associativity(&self) -> Associativity {
+        Associativity::for_ways(self.ways() as usize, self.is_fully_associative())
+    }
+
+    /// Number of Sets.
+    pub fn sets(&self) -> u32 {
+        self.ecx
+    }
+
+    /// Translation cache type field.
+    pub fn cache_type(&self) -> DatType {
+        match get_bits(self.edx, 0, 4) as u8 {
+            0b00001 => DatType::DataTLB,
+            0b00010 => DatType::InstructionTLB,
+            0b00011 => DatType::UnifiedTLB,
+            0b00000 => DatType::Null, // should never be returned as this indicates invalid struct!
+            _ => DatType::Unknown,
+        }
+    }
+
+    /// Translation cache level (starts at 1)
+    pub fn cache_level(&self) -> u8 {
+        get_bits(self.edx, 5, 7) as u8
+    }
+
+    /// Maximum number of addressable IDs for logical processors sharing this translation cache
+    pub fn max_addressable_ids(&self) -> u16 {
+        // Add one to the return value to get the result:
+        (get_bits(self.edx, 14, 25) + 1) as u16
+    }
+
+    /// Encode this struct back into the registers of its leaf 0x18 subleaf.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self._eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
+
+    set_bit_fn!(set_4k_entries, has_4k_entries, ebx, 0);
+    set_bit_fn!(set_2mb_entries, has_2mb_entries, ebx, 1);
+    set_bit_fn!(set_4mb_entries, has_4mb_entries, ebx, 2);
+    set_bit_fn!(set_1gb_entries, has_1gb_entries, ebx, 3);
+    set_bit_fn!(set_fully_associative, is_fully_associative, edx, 8);
+    set_bits_fn!(set_partitioning, partitioning, ebx, 8, 10, u8);
+    set_bits_fn!(set_ways, ways, ebx, 16, 31, u16);
+    set_bits_fn!(set_sets, sets, ecx, 0, 31, u32);
+    set_bits_fn!(set_cache_level, cache_level, edx, 5, 7, u8);
+    set_count_fn!(
+        set_max_addressable_ids,
+        max_addressable_ids,
+        edx,
+        14,
+        25,
+        u16
+    );
 }
 
-impl Debug for L3MonitoringInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("L3MonitoringInfo")
-            .field("conversion_factor", &self.conversion_factor())
-            .field("maximum_rmid_range", &self.maximum_rmid_range())
+impl Debug for DatInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatInfo")
+            .field("has_4k_entries", &self.has_4k_entries())
+            .field("has_2mb_entries", &self.has_2mb_entries())
+            .field("has_4mb_entries", &self.has_4mb_entries())
+            .field("has_1gb_entries", &self.has_1gb_entries())
+            .field("is_fully_associative", &self.is_fully_associative())
             .finish()
     }
 }
 
-/// Quality of service enforcement information (LEAF=0x10).
+/// Deterministic Address Translation cache type (EDX bits 04 -- 00)
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DatType {
+    /// Null (indicates this sub-leaf is not valid).
+    Null = 0b00000,
+    DataTLB = 0b00001,
+    InstructionTLB = 0b00010,
+    /// Some unified TLBs will allow a single TLB entry to satisfy data read/write
+    /// and instruction fetches. Others will require separate entries (e.g., one
+    /// loaded on data read/write and another loaded on an instruction fetch) .
+    /// Please see the Intel® 64 and IA-32 Architectures Optimization Reference Manual
+    /// for details of a particular product.
+    UnifiedTLB = 0b00011,
+    Unknown,
+}
+
+impl fmt::Display for DatType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let t = match self {
+            DatType::Null => "invalid (0)",
+            DatType::DataTLB => "Data TLB",
+            DatType::InstructionTLB => "Instruction TLB",
+            DatType::UnifiedTLB => "Unified TLB",
+            DatType::Unknown => "Unknown",
+        };
+        f.write_str(t)
+    }
+}
+
+/// SoC vendor specific information (LEAF=0x17).
 ///
 /// # Platforms
 /// ❌ AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct RdtAllocationInfo {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SoCVendorInfo<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    /// MaxSOCID_Index
+    eax: u32,
     ebx: u32,
+    ecx: u32,
+    edx: u32,
 }
 
-impl RdtAllocationInfo {
-    check_bit_fn!(doc = "Supports L3 Cache Allocation.", has_l3_cat, ebx, 1);
-
-    check_bit_fn!(doc = "Supports L2 Cache Allocation.", has_l2_cat, ebx, 2);
+eq_without_reader!(SoCVendorInfo, eax, ebx, ecx, edx);
 
-    check_bit_fn!(
-        doc = "Supports Memory Bandwidth Allocation.",
-        has_memory_bandwidth_allocation,
-        ebx,
-        3
-    );
+impl<R: CpuIdReader> SoCVendorInfo<R> {
+    pub fn get_soc_vendor_id(&self) -> u16 {
+        get_bits(self.ebx, 0, 15) as u16
+    }
 
-    /// L3 Cache Allocation Information.
-    pub fn l3_cat(&self) -> Option<L3CatInfo> {
-        if self.has_l3_cat() {
-            let res = self.read.cpuid2(EAX_RDT_ALLOCATION, 1);
-            Some(L3CatInfo {
-                eax: res.eax,
-                ebx: res.ebx,
-                ecx: res.ecx,
-                edx: res.edx,
-            })
+    pub fn get_project_id(&self) -> u32 {
+        self.ecx
+    }
+
+    pub fn get_stepping_id(&self) -> u32 {
+        self.edx
+    }
+
+    pub fn get_vendor_brand(&self) -> Option<SoCVendorBrand> {
+        // Leaf 17H is valid if MaxSOCID_Index >= 3.
+        if self.eax >= 3 {
+            let r1 = self.read.cpuid2(EAX_SOC_VENDOR_INFO, 1);
+            let r2 = self.read.cpuid2(EAX_SOC_VENDOR_INFO, 2);
+            let r3 = self.read.cpuid2(EAX_SOC_VENDOR_INFO, 3);
+            Some(SoCVendorBrand { data: [r1, r2, r3] })
         } else {
             None
         }
     }
 
-    /// L2 Cache Allocation Information.
-    pub fn l2_cat(&self) -> Option<L2CatInfo> {
-        if self.has_l2_cat() {
-            let res = self.read.cpuid2(EAX_RDT_ALLOCATION, 2);
-            Some(L2CatInfo {
-                eax: res.eax,
-                ebx: res.ebx,
-                edx: res.edx,
+    pub fn get_vendor_attributes(&self) -> Option<SoCVendorAttributesIter<R>> {
+        if self.eax > 3 {
+            Some(SoCVendorAttributesIter {
+                read: self.read.clone(),
+                count: self.eax,
+                current: 3,
             })
         } else {
             None
         }
     }
 
-    /// Memory Bandwidth Allocation Information.
-    pub fn memory_bandwidth_allocation(&self) -> Option<MemBwAllocationInfo> {
-        if self.has_memory_bandwidth_allocation() {
-            let res = self.read.cpuid2(EAX_RDT_ALLOCATION, 3);
-            Some(MemBwAllocationInfo {
-                eax: res.eax,
-                ecx: res.ecx,
-                edx: res.edx,
-            })
-        } else {
-            None
+    /// Encode this struct back into the registers of leaf 0x17 (subleaf 0).
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
         }
     }
+
+    set_bits_fn!(set_soc_vendor_id, get_soc_vendor_id, ebx, 0, 15, u16);
+    set_bits_fn!(set_project_id, get_project_id, ecx, 0, 31, u32);
+    set_bits_fn!(set_stepping_id, get_stepping_id, edx, 0, 31, u32);
 }
 
-impl Debug for RdtAllocationInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("RdtAllocationInfo")
-            .field("l3_cat", &self.l3_cat())
-            .field("l2_cat", &self.l2_cat())
-            .field(
-                "memory_bandwidth_allocation",
-                &self.memory_bandwidth_allocation(),
-            )
+impl<R: CpuIdReader> fmt::Debug for SoCVendorInfo<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SoCVendorInfo")
+            .field("soc_vendor_id", &self.get_soc_vendor_id())
+            .field("project_id", &self.get_project_id())
+            .field("stepping_id", &self.get_stepping_id())
+            .field("vendor_brand", &self.get_vendor_brand())
+            .field("vendor_attributes", &self.get_vendor_attributes())
             .finish()
     }
 }
 
-/// L3 Cache Allocation Technology Enumeration Sub-leaf (LEAF=0x10, SUBLEAF=1).
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct L3CatInfo {
-    eax: u32,
-    ebx: u32,
-    ecx: u32,
-    edx: u32,
+/// Iterator for SoC vendor attributes.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SoCVendorAttributesIter<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    count: u32,
+    current: u32,
 }
 
-impl L3CatInfo {
-    /// Length of the capacity bit mask.
-    pub fn capacity_mask_length(&self) -> u8 {
-        (get_bits(self.eax, 0, 4) + 1) as u8
-    }
+impl<R: CpuIdReader> Iterator for SoCVendorAttributesIter<R> {
+    type Item = CpuIdResult;
 
-    /// Bit-granular map of isolation/contention of allocation units.
-    pub fn isolation_bitmap(&self) -> u32 {
-        self.ebx
+    /// Iterate over all SoC vendor specific attributes.
+    fn next(&mut self) -> Option<CpuIdResult> {
+        if self.current >= self.count {
+            return None;
+        }
+        self.current += 1;
+        Some(self.read.cpuid2(EAX_SOC_VENDOR_INFO, self.current))
     }
 
-    /// Highest COS number supported for this Leaf.
-    pub fn highest_cos(&self) -> u16 {
-        get_bits(self.edx, 0, 15) as u16
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.count.saturating_sub(self.current) as usize;
+        (len, Some(len))
     }
-
-    check_bit_fn!(
-        doc = "Is Code and Data Prioritization Technology supported?",
-        has_code_data_prioritization,
-        ecx,
-        2
-    );
 }
 
-impl Debug for L3CatInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("L3CatInfo")
-            .field("capacity_mask_length", &self.capacity_mask_length())
-            .field("isolation_bitmap", &self.isolation_bitmap())
-            .field("highest_cos", &self.highest_cos())
-            .finish()
+impl<R: CpuIdReader> DoubleEndedIterator for SoCVendorAttributesIter<R> {
+    fn next_back(&mut self) -> Option<CpuIdResult> {
+        if self.current >= self.count {
+            return None;
+        }
+        self.count -= 1;
+        Some(self.read.cpuid2(EAX_SOC_VENDOR_INFO, self.count + 1))
     }
 }
 
-/// L2 Cache Allocation Technology Enumeration Sub-leaf (LEAF=0x10, SUBLEAF=2).
-#[derive(Eq, PartialEq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct L2CatInfo {
-    eax: u32,
-    ebx: u32,
-    edx: u32,
-}
-
-impl L2CatInfo {
-    /// Length of the capacity bit mask.
-    pub fn capacity_mask_length(&self) -> u8 {
-        (get_bits(self.eax, 0, 4) + 1) as u8
-    }
-
-    /// Bit-granular map of isolation/contention of allocation units.
-    pub fn isolation_bitmap(&self) -> u32 {
-        self.ebx
-    }
+impl<R: CpuIdReader> ExactSizeIterator for SoCVendorAttributesIter<R> {}
 
-    /// Highest COS number supported for this Leaf.
-    pub fn highest_cos(&self) -> u16 {
-        get_bits(self.edx, 0, 15) as u16
-    }
-}
+impl<R: CpuIdReader> FusedIterator for SoCVendorAttributesIter<R> {}
 
-impl Debug for L2CatInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("L2CatInfo")
-            .field("capacity_mask_length", &self.capacity_mask_length())
-            .field("isolation_bitmap", &self.isolation_bitmap())
-            .field("highest_cos", &self.highest_cos())
+impl<R: CpuIdReader> Debug for SoCVendorAttributesIter<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SoCVendorAttributesIter")
+            .field("count", &self.count)
+            .field("current", &self.current)
             .finish()
     }
 }
 
-/// Memory Bandwidth Allocation Enumeration Sub-leaf (LEAF=0x10, SUBLEAF=3).
-#[derive(Eq, PartialEq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct MemBwAllocationInfo {
-    eax: u32,
-    ecx: u32,
-    edx: u32,
+/// A vendor brand string as queried from the cpuid leaf.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[repr(C)]
+pub struct SoCVendorBrand {
+    data: [CpuIdResult; 3],
 }
 
-impl MemBwAllocationInfo {
-    /// Reports the maximum MBA throttling value supported for the corresponding ResID.
-    pub fn max_hba_throttling(&self) -> u16 {
-        (get_bits(self.eax, 0, 11) + 1) as u16
+impl SoCVendorBrand {
+    /// Decodes the registers of subleafs 1 to 3 of LEAF=0x17.
+    pub fn from_registers(data: [CpuIdResult; 3]) -> Self {
+        Self { data }
     }
 
-    /// Highest COS number supported for this Leaf.
-    pub fn highest_cos(&self) -> u16 {
-        get_bits(self.edx, 0, 15) as u16
+    /// Return the SocVendorBrand as a string.
+    pub fn as_str(&self) -> &str {
+        let brand_string_start = self as *const SoCVendorBrand as *const u8;
+        let slice = unsafe {
+            // Safety: SoCVendorBrand is laid out with repr(C).
+            slice::from_raw_parts(brand_string_start, size_of::<SoCVendorBrand>())
+        };
+        str::from_utf8(slice).unwrap_or("InvalidSoCVendorString")
     }
 
-    check_bit_fn!(
-        doc = "Reports whether the response of the delay values is linear.",
-        has_linear_response_delay,
-        ecx,
-        2
-    );
+    #[deprecated(
+        since = "10.0.0",
+        note = "Use idiomatic function name `as_str` instead"
+    )]
+    pub fn as_string(&self) -> &str {
+        self.as_str()
+    }
+
+    /// Encode the brand string back into the registers of leaf 0x17, subleafs 1 to 3.
+    pub fn as_cpuid_results(&self) -> [CpuIdResult; 3] {
+        self.data
+    }
 }
 
-impl Debug for MemBwAllocationInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("MemBwAllocationInfo")
-            .field("max_hba_throttling", &self.max_hba_throttling())
-            .field("highest_cos", &self.highest_cos())
-            .field(
-                "has_linear_response_delay",
-                &self.has_linear_response_delay(),
-            )
-            .finish()
+impl fmt::Display for SoCVendorBrand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
     }
 }
 
-/// Intel SGX Capability Enumeration Leaf (LEAF=0x12).
-///
-/// Two sub-leafs: (EAX = 12H, ECX = 0 and ECX = 1)
+/// Native Model ID Enumeration of hybrid processors (LEAF=0x1A).
 ///
 /// # Platforms
 /// ❌ AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct SgxInfo {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HybridInfo {
     eax: u32,
-    ebx: u32,
-    _ecx: u32,
-    edx: u32,
-    eax1: u32,
-    ebx1: u32,
-    ecx1: u32,
-    edx1: u32,
 }
 
-impl SgxInfo {
-    check_bit_fn!(doc = "Has SGX1 support.", has_sgx1, eax, 0);
-    check_bit_fn!(doc = "Has SGX2 support.", has_sgx2, eax, 1);
-
-    check_bit_fn!(
-        doc = "Supports ENCLV instruction leaves EINCVIRTCHILD, EDECVIRTCHILD, and ESETCONTEXT.",
-        has_enclv_leaves_einvirtchild_edecvirtchild_esetcontext,
-        eax,
-        5
-    );
-
-    check_bit_fn!(
-        doc = "Supports ENCLS instruction leaves ETRACKC, ERDINFO, ELDBC, and ELDUC.",
-        has_encls_leaves_etrackc_erdinfo_eldbc_elduc,
-        eax,
-        6
-    );
-
-    /// Bit vector of supported extended SGX features.
-    pub fn miscselect(&self) -> u32 {
-        self.ebx
+impl HybridInfo {
+    /// Decodes the registers of LEAF=0x1A.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self { eax: data.eax }
     }
 
-    ///  The maximum supported enclave size in non-64-bit mode is 2^retval.
-    pub fn max_enclave_size_non_64bit(&self) -> u8 {
-        get_bits(self.edx, 0, 7) as u8
+    /// Type of the core we're running on.
+    pub fn core_type(&self) -> CoreType {
+        CoreType::from(get_bits(self.eax, 24, 31) as u8)
     }
 
-    ///  The maximum supported enclave size in 64-bit mode is 2^retval.
-    pub fn max_enclave_size_64bit(&self) -> u8 {
-        get_bits(self.edx, 8, 15) as u8
+    /// Native model ID of the core we're running on.
+    ///
+    /// Identifies the microarchitecture of the core type, together with
+    /// [`HybridInfo::core_type`].
+    pub fn native_model_id(&self) -> u32 {
+        get_bits(self.eax, 0, 23)
     }
 
-    /// Reports the valid bits of SECS.ATTRIBUTES\[127:0\] that software can set with ECREATE.
-    pub fn secs_attributes(&self) -> (u64, u64) {
-        let lower = self.eax1 as u64 | (self.ebx1 as u64) << 32;
-        let upper = self.ecx1 as u64 | (self.edx1 as u64) << 32;
-        (lower, upper)
-    }
-    /// Iterator over SGX sub-leafs.
-    pub fn iter(&self) -> SgxSectionIter {
-        SgxSectionIter {
-            read: self.read,
-            current: 2,
+    /// Encode this struct back into the registers of leaf 0x1A.
+    ///
+    /// EBX, ECX and EDX are reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
         }
     }
+
+    /// Set the value reported by [`Self::core_type`].
+    pub fn set_core_type(&mut self, core_type: CoreType) {
+        set_bits(&mut self.eax, 24, 31, u8::from(core_type) as u32);
+    }
+
+    set_bits_fn!(set_native_model_id, native_model_id, eax, 0, 23, u32);
 }
 
-impl Debug for SgxInfo {
+impl Debug for HybridInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("SgxInfo")
-            .field("has_sgx1", &self.has_sgx1())
-            .field("has_sgx2", &self.has_sgx2())
-            .field("miscselect", &self.miscselect())
-            .field(
-                "max_enclave_size_non_64bit",
-                &self.max_enclave_size_non_64bit(),
-            )
-            .field("max_enclave_size_64bit", &self.max_enclave_size_64bit())
-            .field(
-                "has_encls_leaves_etrackc_erdinfo_eldbc_elduc",
-                &self.has_encls_leaves_etrackc_erdinfo_eldbc_elduc(),
-            )
-            .field(
-                "has_enclv_leaves_einvirtchild_edecvirtchild_esetcontext",
-                &self.has_enclv_leaves_einvirtchild_edecvirtchild_esetcontext(),
-            )
-            .field("sgx_section_iter", &self.iter())
+        f.debug_struct("HybridInfo")
+            .field("core_type", &self.core_type())
+            .field("native_model_id", &self.native_model_id())
             .finish()
     }
 }
 
-/// Iterator over the SGX sub-leafs (ECX >= 2).
+/// Core type of a hybrid processor (LEAF=0x1A).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CoreType {
+    /// Efficiency core (Intel Atom).
+    Atom,
+    /// Performance core (Intel Core).
+    Core,
+    /// A core type not (yet) known to this library.
+    Unknown(u8),
+}
+
+impl From<u8> for CoreType {
+    fn from(value: u8) -> CoreType {
+        match value {
+            0x20 => CoreType::Atom,
+            0x40 => CoreType::Core,
+            x => CoreType::Unknown(x),
+        }
+    }
+}
+
+impl From<CoreType> for u8 {
+    fn from(value: CoreType) -> u8 {
+        match value {
+            CoreType::Atom => 0x20,
+            CoreType::Core => 0x40,
+            CoreType::Unknown(x) => x,
+        }
+    }
+}
+
+impl fmt::Display for CoreType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CoreType::Atom => f.write_str("Efficiency core (Atom)"),
+            CoreType::Core => f.write_str("Performance core (Core)"),
+            CoreType::Unknown(t) => write!(f, "Unknown({:#x})", t),
+        }
+    }
+}
+
+/// Iterator over the PCONFIG targets (LEAF=0x1B).
+///
+/// Each sub-leaf of type "target identifier" reports up to three targets in
+/// EBX, ECX and EDX. The enumeration ends at the first sub-leaf with an
+/// invalid type.
+///
+/// # Platforms
+/// ❌ AMD ✅ Intel
 #[derive(Clone)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct SgxSectionIter {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PconfigIter<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
+    /// Next register to look at, as `subleaf * 3 + register`.
     current: u32,
 }
 
-impl Iterator for SgxSectionIter {
-    type Item = SgxSectionInfo;
+impl<R: CpuIdReader> Iterator for PconfigIter<R> {
+    type Item = PconfigTarget;
 
-    fn next(&mut self) -> Option<SgxSectionInfo> {
-        let res = self.read.cpuid2(EAX_SGX, self.current);
-        self.current += 1;
-        match get_bits(res.eax, 0, 3) {
-            0b0001 => Some(SgxSectionInfo::Epc(EpcSection {
-                eax: res.eax,
-                ebx: res.ebx,
-                ecx: res.ecx,
-                edx: res.edx,
-            })),
-            _ => None,
+    fn next(&mut self) -> Option<PconfigTarget> {
+        loop {
+            let subleaf = self.current / 3;
+            // Protects against readers that never report an invalid sub-leaf.
+            if subleaf >= 32 {
+                return None;
+            }
+
+            let res = self.read.cpuid2(EAX_PCONFIG_INFO, subleaf);
+            // Sub-leaf type 1 is "target identifier", everything else is invalid.
+            if get_bits(res.eax, 0, 11) != 1 {
+                return None;
+            }
+
+            let id = match self.current % 3 {
+                0 => res.ebx,
+                1 => res.ecx,
+                _ => res.edx,
+            };
+            self.current += 1;
+
+            // Unused target slots are zero.
+            if id != 0 {
+                return Some(PconfigTarget::from(id));
+            }
         }
     }
 }
 
-impl Debug for SgxSectionIter {
-    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+impl<R: CpuIdReader> Debug for PconfigIter<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let mut debug = f.debug_list();
         self.clone().for_each(|ref item| {
             debug.entry(item);
@@ -4647,575 +10572,727 @@ impl Debug for SgxSectionIter {
     }
 }
 
-/// Intel SGX EPC Enumeration Leaf
-///
-/// Sub-leaves 2 or higher.
-#[derive(Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub enum SgxSectionInfo {
-    // This would be nice: https://github.com/rust-lang/rfcs/pull/1450
-    Epc(EpcSection),
+/// A target of the PCONFIG instruction (LEAF=0x1B).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PconfigTarget {
+    /// Multi-key Total Memory Encryption (MKTME) key programming.
+    Mktme,
+    /// Total Storage Encryption (TSE) key programming.
+    TseKeyProgram,
+    /// Total Storage Encryption (TSE) wrapped key programming.
+    TseKeyProgramWrapped,
+    /// Unknown target identifier.
+    Unknown(u32),
 }
 
-/// EBX:EAX and EDX:ECX provide information on the Enclave Page Cache (EPC) section
-#[derive(Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct EpcSection {
-    eax: u32,
-    ebx: u32,
-    ecx: u32,
-    edx: u32,
+impl From<u32> for PconfigTarget {
+    fn from(value: u32) -> PconfigTarget {
+        match value {
+            1 => PconfigTarget::Mktme,
+            2 => PconfigTarget::TseKeyProgram,
+            3 => PconfigTarget::TseKeyProgramWrapped,
+            x => PconfigTarget::Unknown(x),
+        }
+    }
 }
 
-impl EpcSection {
-    /// The physical address of the base of the EPC section
-    pub fn physical_base(&self) -> u64 {
-        let lower = (get_bits(self.eax, 12, 31) << 12) as u64;
-        let upper = (get_bits(self.ebx, 0, 19) as u64) << 32;
-        lower | upper
-    }
+impl fmt::Display for PconfigTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data = match self {
+            PconfigTarget::Mktme => "MKTME",
+            PconfigTarget::TseKeyProgram => "TSE key programming",
+            PconfigTarget::TseKeyProgramWrapped => "TSE wrapped key programming",
+            PconfigTarget::Unknown(t) => {
+                return write!(f, "Unknown({})", t);
+            }
+        };
 
-    /// Size of the corresponding EPC section within the Processor Reserved Memory.
-    pub fn size(&self) -> u64 {
-        let lower = (get_bits(self.ecx, 12, 31) << 12) as u64;
-        let upper = (get_bits(self.edx, 0, 19) as u64) << 32;
-        lower | upper
+        f.write_str(data)
     }
 }
 
-/// Intel Processor Trace Information (LEAF=0x14).
+/// Architectural Last Branch Record Enumeration (LEAF=0x1C).
 ///
 /// # Platforms
 /// ❌ AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct ProcessorTraceInfo {
-    _eax: u32,
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LbrInfo {
+    eax: u32,
     ebx: u32,
     ecx: u32,
-    _edx: u32,
-    leaf1: Option<CpuIdResult>,
 }
 
-impl ProcessorTraceInfo {
-    // EBX features
-    check_bit_fn!(
-        doc = "If true, Indicates that IA32_RTIT_CTL.CR3Filter can be set to 1, and \
-               that IA32_RTIT_CR3_MATCH MSR can be accessed.",
-        has_rtit_cr3_match,
-        ebx,
-        0
-    );
+impl LbrInfo {
+    /// Decodes the registers of LEAF=0x1C.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+        }
+    }
+
+    /// Bitmap of supported values for IA32_LBR_DEPTH.DEPTH.
+    ///
+    /// If bit n is set, a depth of 8 * (n + 1) is supported.
+    pub fn depth_bitmap(&self) -> u8 {
+        get_bits(self.eax, 0, 7) as u8
+    }
+
+    /// Is `depth` a supported value for IA32_LBR_DEPTH.DEPTH?
+    pub fn supports_depth(&self, depth: u8) -> bool {
+        depth != 0 && depth.is_multiple_of(8) && depth <= 64 && is_bit_set!(self.eax, depth / 8 - 1)
+    }
+
+    /// The largest supported LBR depth (number of LBR entries), or `None` if
+    /// no depth is enumerated.
+    pub fn max_depth(&self) -> Option<u8> {
+        match self.depth_bitmap() {
+            0 => None,
+            bitmap => Some((8 - bitmap.leading_zeros() as u8) * 8),
+        }
+    }
+
     check_bit_fn!(
-        doc = "If true, Indicates support of Configurable PSB and Cycle-Accurate Mode.",
-        has_configurable_psb_and_cycle_accurate_mode,
-        ebx,
-        1
+        doc = "Deep C-state reset: LBRs may be cleared on MWAIT requests to \
+               C-states deeper than C1.",
+        has_deep_cstate_reset,
+        eax,
+        30
     );
     check_bit_fn!(
-        doc = "If true, Indicates support of IP Filtering, TraceStop filtering, and \
-               preservation of Intel PT MSRs across warm reset.",
-        has_ip_tracestop_filtering,
-        ebx,
-        2
+        doc = "LBR IP values contain linear instruction pointers (LIP) if set, \
+               effective IPs otherwise.",
+        has_lip_values,
+        eax,
+        31
     );
     check_bit_fn!(
-        doc = "If true, Indicates support of MTC timing packet and suppression of \
-               COFI-based packets.",
-        has_mtc_timing_packet_coefi_suppression,
+        doc = "CPL filtering (IA32_LBR_CTL.OS and IA32_LBR_CTL.USR) is supported.",
+        has_cpl_filtering,
         ebx,
-        3
+        0
     );
-
     check_bit_fn!(
-        doc = "Indicates support of PTWRITE. Writes can set IA32_RTIT_CTL\\[12\\] (PTWEn \
-               and IA32_RTIT_CTL\\[5\\] (FUPonPTW), and PTWRITE can generate packets",
-        has_ptwrite,
+        doc = "Branch filtering (IA32_LBR_CTL branch type bits) is supported.",
+        has_branch_filtering,
         ebx,
-        4
+        1
     );
-
     check_bit_fn!(
-        doc = "Support of Power Event Trace. Writes can set IA32_RTIT_CTL\\[4\\] (PwrEvtEn) \
-               enabling Power Event Trace packet generation.",
-        has_power_event_trace,
+        doc = "Call-stack mode (IA32_LBR_CTL.CALL_STACK) is supported.",
+        has_call_stack_mode,
         ebx,
-        5
+        2
     );
-
-    // ECX features
     check_bit_fn!(
-        doc = "If true, Tracing can be enabled with IA32_RTIT_CTL.ToPA = 1, hence \
-               utilizing the ToPA output scheme; IA32_RTIT_OUTPUT_BASE and \
-               IA32_RTIT_OUTPUT_MASK_PTRS MSRs can be accessed.",
-        has_topa,
+        doc = "IA32_LBR_x_INFO.MISPRED holds whether a branch was mispredicted.",
+        has_mispredict_bit,
         ecx,
         0
     );
     check_bit_fn!(
-        doc = "If true, ToPA tables can hold any number of output entries, up to the \
-               maximum allowed by the MaskOrTableOffset field of \
-               IA32_RTIT_OUTPUT_MASK_PTRS.",
-        has_topa_maximum_entries,
+        doc = "Timed LBRs: IA32_LBR_x_INFO.CYC_CNT holds the elapsed core clocks.",
+        has_timed_lbrs,
         ecx,
         1
     );
     check_bit_fn!(
-        doc = "If true, Indicates support of Single-Range Output scheme.",
-        has_single_range_output_scheme,
+        doc = "IA32_LBR_x_INFO.BR_TYPE holds the type of the branch.",
+        has_branch_type_field,
         ecx,
         2
     );
-    check_bit_fn!(
-        doc = "If true, Indicates support of output to Trace Transport subsystem.",
-        has_trace_transport_subsystem,
-        ecx,
-        3
-    );
-    check_bit_fn!(
-        doc = "If true, Generated packets which contain IP payloads have LIP values, \
-               which include the CS base component.",
-        has_lip_with_cs_base,
-        ecx,
-        31
-    );
-
-    /// Number of configurable Address Ranges for filtering (Bits 2:0).
-    pub fn configurable_address_ranges(&self) -> u8 {
-        self.leaf1.map_or(0, |res| get_bits(res.eax, 0, 2) as u8)
-    }
 
-    /// Bitmap of supported MTC period encodings (Bit 31:16).
-    pub fn supported_mtc_period_encodings(&self) -> u16 {
-        self.leaf1.map_or(0, |res| get_bits(res.eax, 16, 31) as u16)
+    /// Bitmap of general-purpose counters that support event logging, i.e.,
+    /// whose occurrences can be recorded in IA32_LBR_x_INFO.
+    ///
+    /// Bit n corresponds to IA32_PMCn.
+    pub fn event_logging_bitmap(&self) -> u8 {
+        get_bits(self.ecx, 16, 19) as u8
     }
 
-    /// Bitmap of supported Cycle Threshold value encodings (Bits 15-0).
-    pub fn supported_cycle_threshold_value_encodings(&self) -> u16 {
-        self.leaf1.map_or(0, |res| get_bits(res.ebx, 0, 15) as u16)
+    /// Encode this struct back into the registers of leaf 0x1C, subleaf 0.
+    ///
+    /// EDX is reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: 0,
+        }
     }
 
-    /// Bitmap of supported Configurable PSB frequency encodings (Bit 31:16)
-    pub fn supported_psb_frequency_encodings(&self) -> u16 {
-        self.leaf1.map_or(0, |res| get_bits(res.ebx, 16, 31) as u16)
-    }
+    set_bits_fn!(set_depth_bitmap, depth_bitmap, eax, 0, 7, u8);
+    set_bit_fn!(set_deep_cstate_reset, has_deep_cstate_reset, eax, 30);
+    set_bit_fn!(set_lip_values, has_lip_values, eax, 31);
+    set_bit_fn!(set_cpl_filtering, has_cpl_filtering, ebx, 0);
+    set_bit_fn!(set_branch_filtering, has_branch_filtering, ebx, 1);
+    set_bit_fn!(set_call_stack_mode, has_call_stack_mode, ebx, 2);
+    set_bit_fn!(set_mispredict_bit, has_mispredict_bit, ecx, 0);
+    set_bit_fn!(set_timed_lbrs, has_timed_lbrs, ecx, 1);
+    set_bit_fn!(set_branch_type_field, has_branch_type_field, ecx, 2);
+    set_bits_fn!(
+        set_event_logging_bitmap,
+        event_logging_bitmap,
+        ecx,
+        16,
+        19,
+        u8
+    );
 }
 
-impl Debug for ProcessorTraceInfo {
+impl Debug for LbrInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
-        f.debug_struct("ProcessorTraceInfo")
-            .field(
-                "configurable_address_ranges",
-                &self.configurable_address_ranges(),
-            )
-            .field(
-                "supported_mtc_period_encodings",
-                &self.supported_mtc_period_encodings(),
-            )
-            .field(
-                "supported_cycle_threshold_value_encodings",
-                &self.supported_cycle_threshold_value_encodings(),
-            )
-            .field(
-                "supported_psb_frequency_encodings",
-                &self.supported_psb_frequency_encodings(),
-            )
+        f.debug_struct("LbrInfo")
+            .field("depth_bitmap", &self.depth_bitmap())
+            .field("max_depth", &self.max_depth())
+            .field("has_deep_cstate_reset", &self.has_deep_cstate_reset())
+            .field("has_lip_values", &self.has_lip_values())
+            .field("has_cpl_filtering", &self.has_cpl_filtering())
+            .field("has_branch_filtering", &self.has_branch_filtering())
+            .field("has_call_stack_mode", &self.has_call_stack_mode())
+            .field("has_mispredict_bit", &self.has_mispredict_bit())
+            .field("has_timed_lbrs", &self.has_timed_lbrs())
+            .field("has_branch_type_field", &self.has_branch_type_field())
+            .field("event_logging_bitmap", &self.event_logging_bitmap())
             .finish()
     }
 }
 
-/// Time Stamp Counter/Core Crystal Clock Information (LEAF=0x15).
+/// AMX TMUL Information (LEAF=0x1E).
+///
+/// Subleaf 0 reports the limits of the tile matrix multiply (TMUL) unit,
+/// subleaf 1 (if supported) the AMX features.
 ///
 /// # Platforms
 /// ❌ AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct TscInfo {
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TmulInfo {
     eax: u32,
     ebx: u32,
-    ecx: u32,
+    // EAX of subleaf 1 (zero if subleaf 1 isn't supported):
+    eax1: u32,
 }
 
-impl fmt::Debug for TscInfo {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("TscInfo")
-            .field("denominator", &self.denominator())
-            .field("numerator", &self.numerator())
-            .field("nominal_frequency", &self.nominal_frequency())
-            .field("tsc_frequency", &self.tsc_frequency())
-            .finish()
+impl TmulInfo {
+    /// Decodes the registers of LEAF=0x1E, `subleaf1` is `None` if the CPU
+    /// doesn't support subleaf 1.
+    pub fn from_registers(data: CpuIdResult, subleaf1: Option<CpuIdResult>) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            eax1: subleaf1.map_or(0, |res| res.eax),
+        }
     }
-}
 
-impl TscInfo {
-    /// An unsigned integer which is the denominator of the TSC/”core crystal clock” ratio.
-    pub fn denominator(&self) -> u32 {
+    /// Maximum supported subleaf of leaf 0x1E.
+    pub fn max_subleaf(&self) -> u32 {
         self.eax
     }
 
-    /// An unsigned integer which is the numerator of the TSC/”core crystal clock” ratio.
-    ///
-    /// If this is 0, the TSC/”core crystal clock” ratio is not enumerated.
-    pub fn numerator(&self) -> u32 {
-        self.ebx
+    /// Maximum number of rows or columns (K) of the tiles TMUL operates on
+    /// (`tmul_maxk`).
+    pub fn max_k(&self) -> u8 {
+        get_bits(self.ebx, 0, 7) as u8
     }
 
-    /// An unsigned integer which is the nominal frequency of the core crystal clock in Hz.
-    ///
-    /// If this is 0, the nominal core crystal clock frequency is not enumerated.
-    pub fn nominal_frequency(&self) -> u32 {
-        self.ecx
+    /// Maximum number of bytes per row of the tiles TMUL operates on (N,
+    /// `tmul_maxn`).
+    pub fn max_n(&self) -> u16 {
+        get_bits(self.ebx, 8, 23) as u16
     }
 
-    /// “TSC frequency” = “core crystal clock frequency” * EBX/EAX.
-    pub fn tsc_frequency(&self) -> Option<u64> {
-        // In some case TscInfo is a valid leaf, but the values reported are still 0
-        // we should avoid a division by zero in case denominator ends up being 0.
-        if self.nominal_frequency() == 0 || self.numerator() == 0 || self.denominator() == 0 {
-            return None;
+    check_bit_fn!(
+        doc = "AMX-INT8 is supported (subleaf 1).",
+        has_amx_int8,
+        eax1,
+        0
+    );
+    check_bit_fn!(
+        doc = "AMX-BF16 is supported (subleaf 1).",
+        has_amx_bf16,
+        eax1,
+        1
+    );
+    check_bit_fn!(
+        doc = "AMX-COMPLEX is supported (subleaf 1).",
+        has_amx_complex,
+        eax1,
+        2
+    );
+    check_bit_fn!(
+        doc = "AMX-FP16 is supported (subleaf 1).",
+        has_amx_fp16,
+        eax1,
+        3
+    );
+    check_bit_fn!(
+        doc = "AMX-FP8 is supported (subleaf 1).",
+        has_amx_fp8,
+        eax1,
+        4
+    );
+    check_bit_fn!(
+        doc = "AMX-TRANSPOSE is supported (subleaf 1).",
+        has_amx_transpose,
+        eax1,
+        5
+    );
+    check_bit_fn!(
+        doc = "AMX-TF32 is supported (subleaf 1).",
+        has_amx_tf32,
+        eax1,
+        6
+    );
+    check_bit_fn!(
+        doc = "AMX-AVX512 is supported (subleaf 1).",
+        has_amx_avx512,
+        eax1,
+        7
+    );
+    check_bit_fn!(
+        doc = "AMX-MOVRS is supported (subleaf 1).",
+        has_amx_movrs,
+        eax1,
+        8
+    );
+
+    /// Encode this struct back into the registers of leaf 0x1E, subleaf 0.
+    ///
+    /// ECX and EDX are reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: 0,
+            edx: 0,
         }
+    }
 
-        Some(self.nominal_frequency() as u64 * self.numerator() as u64 / self.denominator() as u64)
+    /// Encode the AMX features back into the registers of leaf 0x1E, subleaf
+    /// 1, or `None` if subleaf 1 isn't supported.
+    pub fn subleaf1_as_cpuid_result(&self) -> Option<CpuIdResult> {
+        (self.eax >= 1).then_some(CpuIdResult {
+            eax: self.eax1,
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
+        })
     }
+
+    set_bits_fn!(set_max_subleaf, max_subleaf, eax, 0, 31, u32);
+    set_bits_fn!(set_max_k, max_k, ebx, 0, 7, u8);
+    set_bits_fn!(set_max_n, max_n, ebx, 8, 23, u16);
+    set_bit_fn!(set_amx_int8, has_amx_int8, eax1, 0);
+    set_bit_fn!(set_amx_bf16, has_amx_bf16, eax1, 1);
+    set_bit_fn!(set_amx_complex, has_amx_complex, eax1, 2);
+    set_bit_fn!(set_amx_fp16, has_amx_fp16, eax1, 3);
+    set_bit_fn!(set_amx_fp8, has_amx_fp8, eax1, 4);
+    set_bit_fn!(set_amx_transpose, has_amx_transpose, eax1, 5);
+    set_bit_fn!(set_amx_tf32, has_amx_tf32, eax1, 6);
+    set_bit_fn!(set_amx_avx512, has_amx_avx512, eax1, 7);
+    set_bit_fn!(set_amx_movrs, has_amx_movrs, eax1, 8);
 }
 
-/// Processor Frequency Information (LEAF=0x16).
+impl Debug for TmulInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TmulInfo")
+            .field("max_k", &self.max_k())
+            .field("max_n", &self.max_n())
+            .field("has_amx_int8", &self.has_amx_int8())
+            .field("has_amx_bf16", &self.has_amx_bf16())
+            .field("has_amx_complex", &self.has_amx_complex())
+            .field("has_amx_fp16", &self.has_amx_fp16())
+            .field("has_amx_fp8", &self.has_amx_fp8())
+            .field("has_amx_transpose", &self.has_amx_transpose())
+            .field("has_amx_tf32", &self.has_amx_tf32())
+            .field("has_amx_avx512", &self.has_amx_avx512())
+            .field("has_amx_movrs", &self.has_amx_movrs())
+            .finish()
+    }
+}
+
+/// Processor History Reset (LEAF=0x20).
 ///
 /// # Platforms
 /// ❌ AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct ProcessorFrequencyInfo {
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HresetInfo {
     eax: u32,
     ebx: u32,
-    ecx: u32,
 }
 
-impl ProcessorFrequencyInfo {
-    /// Processor Base Frequency (in MHz).
-    pub fn processor_base_frequency(&self) -> u16 {
-        get_bits(self.eax, 0, 15) as u16
+impl HresetInfo {
+    /// Decodes the registers of LEAF=0x20.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+        }
     }
 
-    /// Maximum Frequency (in MHz).
-    pub fn processor_max_frequency(&self) -> u16 {
-        get_bits(self.ebx, 0, 15) as u16
+    /// Maximum supported subleaf of leaf 0x20.
+    pub fn max_subleaf(&self) -> u32 {
+        self.eax
     }
 
-    /// Bus (Reference) Frequency (in MHz).
-    pub fn bus_frequency(&self) -> u16 {
-        get_bits(self.ecx, 0, 15) as u16
+    /// Bitmap of history reset capabilities that can be enabled in the
+    /// IA32_HRESET_ENABLE MSR.
+    ///
+    /// HRESET only resets the components whose bit is set in both this bitmap
+    /// and IA32_HRESET_ENABLE.
+    pub fn capabilities(&self) -> u32 {
+        self.ebx
+    }
+
+    check_bit_fn!(
+        doc = "HRESET can reset the Intel Thread Director history.",
+        has_thread_director_reset,
+        ebx,
+        0
+    );
+
+    /// Encode this struct back into the registers of leaf 0x20, subleaf 0.
+    ///
+    /// ECX and EDX are reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: 0,
+            edx: 0,
+        }
     }
+
+    set_bits_fn!(set_max_subleaf, max_subleaf, eax, 0, 31, u32);
+    set_bits_fn!(set_capabilities, capabilities, ebx, 0, 31, u32);
+    set_bit_fn!(set_thread_director_reset, has_thread_director_reset, ebx, 0);
 }
 
-impl fmt::Debug for ProcessorFrequencyInfo {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("ProcessorFrequencyInfo")
-            .field("processor_base_frequency", &self.processor_base_frequency())
-            .field("processor_max_frequency", &self.processor_max_frequency())
-            .field("bus_frequency", &self.bus_frequency())
+impl Debug for HresetInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HresetInfo")
+            .field("capabilities", &self.capabilities())
+            .field(
+                "has_thread_director_reset",
+                &self.has_thread_director_reset(),
+            )
             .finish()
     }
 }
 
-/// Deterministic Address Translation Structure Iterator (LEAF=0x18).
+/// Architectural Performance Monitoring Extended Leaf (LEAF=0x23).
+///
+/// Newer CPUs enumerate their counters as bitmaps here, which supersedes the
+/// counter counts and the (inverted) event bit vector of
+/// [`PerformanceMonitoringInfo`] (LEAF=0x0A).
 ///
 /// # Platforms
 /// ❌ AMD ✅ Intel
-#[derive(Clone)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct DatIter {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
-    current: u32,
-    count: u32,
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ArchPerfmonExtInfo {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+    leaf1: Option<CpuIdResult>,
+    leaf3: Option<CpuIdResult>,
 }
 
-impl Iterator for DatIter {
-    type Item = DatInfo;
-
-    /// Iterate over each sub-leaf with an address translation structure.
-    fn next(&mut self) -> Option<DatInfo> {
-        loop {
-            // Sub-leaf index n is invalid if n exceeds the value that sub-leaf 0 returns in EAX
-            if self.current > self.count {
-                return None;
-            }
-
-            let res = self
-                .read
-                .cpuid2(EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO, self.current);
-            self.current += 1;
-
-            // A sub-leaf index is also invalid if EDX[4:0] returns 0.
-            if get_bits(res.edx, 0, 4) == 0 {
-                // Valid sub-leaves do not need to be contiguous or in any particular order.
-                // A valid sub-leaf may be in a higher input ECX value than an invalid sub-leaf
-                // or than a valid sub-leaf of a higher or lower-level struc-ture
-                continue;
-            }
-
-            return Some(DatInfo {
-                _eax: res.eax,
-                ebx: res.ebx,
-                ecx: res.ecx,
-                edx: res.edx,
-            });
+impl ArchPerfmonExtInfo {
+    /// Decodes the registers of LEAF=0x23, subleafs the CPU doesn't report are `None`.
+    pub fn from_registers(
+        data: CpuIdResult,
+        subleaf1: Option<CpuIdResult>,
+        subleaf3: Option<CpuIdResult>,
+    ) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+            leaf1: subleaf1,
+            leaf3: subleaf3,
         }
     }
-}
 
-impl Debug for DatIter {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut debug = f.debug_list();
-        self.clone().for_each(|ref item| {
-            debug.entry(item);
-        });
-        debug.finish()
+    /// Bitmap of valid subleafs of leaf 0x23.
+    pub fn valid_subleafs(&self) -> u32 {
+        self.eax
     }
-}
-
-/// Deterministic Address Translation Structure
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct DatInfo {
-    _eax: u32,
-    ebx: u32,
-    ecx: u32,
-    edx: u32,
-}
 
-impl DatInfo {
     check_bit_fn!(
-        doc = "4K page size entries supported by this structure",
-        has_4k_entries,
+        doc = "UnitMask2 field in IA32_PERFEVTSELx MSRs is supported.",
+        has_unit_mask2,
         ebx,
         0
     );
-
     check_bit_fn!(
-        doc = "2MB page size entries supported by this structure",
-        has_2mb_entries,
+        doc = "EQ bit in IA32_PERFEVTSELx MSRs is supported.",
+        has_eq_bit,
         ebx,
         1
     );
 
-    check_bit_fn!(
-        doc = "4MB page size entries supported by this structure",
-        has_4mb_entries,
-        ebx,
-        2
-    );
+    /// Number of top-down microarchitecture analysis (TMA) slots per cycle.
+    pub fn topdown_slots(&self) -> u8 {
+        get_bits(self.ecx, 0, 7) as u8
+    }
 
-    check_bit_fn!(
-        doc = "1GB page size entries supported by this structure",
-        has_1gb_entries,
-        ebx,
-        3
-    );
+    /// Bitmap of general-purpose counters, bit n corresponds to IA32_PMCn
+    /// (Subleaf 1, EAX).
+    pub fn general_counter_bitmap(&self) -> u32 {
+        self.leaf1.map_or(0, |res| res.eax)
+    }
 
-    check_bit_fn!(
-        doc = "Fully associative structure",
-        is_fully_associative,
-        edx,
-        8
-    );
+    /// Bitmap of fixed-function counters, bit n corresponds to
+    /// IA32_FIXED_CTRn (Subleaf 1, EBX).
+    pub fn fixed_counter_bitmap(&self) -> u32 {
+        self.leaf1.map_or(0, |res| res.ebx)
+    }
 
-    /// Partitioning (0: Soft partitioning between the logical processors sharing this structure).
-    pub fn partitioning(&self) -> u8 {
-        get_bits(self.ebx, 8, 10) as u8
+    /// Number of general-purpose counters.
+    pub fn number_of_counters(&self) -> u8 {
+        self.general_counter_bitmap().count_ones() as u8
     }
 
-    /// Ways of associativity.
-    pub fn ways(&self) -> u16 {
-        get_bits(self.ebx, 16, 31) as u16
+    /// Number of fixed-function counters.
+    pub fn fixed_function_counters(&self) -> u8 {
+        self.fixed_counter_bitmap().count_ones() as u8
     }
 
-    /// Number of Sets.
-    pub fn sets(&self) -> u32 {
-        self.ecx
+    /// Bitmap of supported architectural performance monitoring events
+    /// (Subleaf 3, EAX).
+    ///
+    /// Unlike EBX of leaf 0x0A, a set bit means the event is supported.
+    pub fn event_bitmap(&self) -> u32 {
+        self.leaf3.map_or(0, |res| res.eax)
     }
 
-    /// Translation cache type field.
-    pub fn cache_type(&self) -> DatType {
-        match get_bits(self.edx, 0, 4) as u8 {
-            0b00001 => DatType::DataTLB,
-            0b00010 => DatType::InstructionTLB,
-            0b00011 => DatType::UnifiedTLB,
-            0b00000 => DatType::Null, // should never be returned as this indicates invalid struct!
-            _ => DatType::Unknown,
-        }
+    /// Core cycles event is supported.
+    pub fn has_core_cycles_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 0)
     }
 
-    /// Translation cache level (starts at 1)
-    pub fn cache_level(&self) -> u8 {
-        get_bits(self.edx, 5, 7) as u8
+    /// Instructions retired event is supported.
+    pub fn has_instructions_retired_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 1)
     }
 
-    /// Maximum number of addressable IDs for logical processors sharing this translation cache
-    pub fn max_addressable_ids(&self) -> u16 {
-        // Add one to the return value to get the result:
-        (get_bits(self.edx, 14, 25) + 1) as u16
+    /// Reference cycles event is supported.
+    pub fn has_ref_cycles_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 2)
     }
-}
 
-impl Debug for DatInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.debug_struct("DatInfo")
-            .field("has_4k_entries", &self.has_4k_entries())
-            .field("has_2mb_entries", &self.has_2mb_entries())
-            .field("has_4mb_entries", &self.has_4mb_entries())
-            .field("has_1gb_entries", &self.has_1gb_entries())
-            .field("is_fully_associative", &self.is_fully_associative())
-            .finish()
+    /// Last-level cache references event is supported.
+    pub fn has_llc_references_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 3)
     }
-}
 
-/// Deterministic Address Translation cache type (EDX bits 04 -- 00)
-#[derive(Eq, PartialEq, Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub enum DatType {
-    /// Null (indicates this sub-leaf is not valid).
-    Null = 0b00000,
-    DataTLB = 0b00001,
-    InstructionTLB = 0b00010,
-    /// Some unified TLBs will allow a single TLB entry to satisfy data read/write
-    /// and instruction fetches. Others will require separate entries (e.g., one
-    /// loaded on data read/write and another loaded on an instruction fetch) .
-    /// Please see the Intel® 64 and IA-32 Architectures Optimization Reference Manual
-    /// for details of a particular product.
-    UnifiedTLB = 0b00011,
-    Unknown,
-}
+    /// Last-level cache misses event is supported.
+    pub fn has_llc_misses_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 4)
+    }
 
-impl fmt::Display for DatType {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let t = match self {
-            DatType::Null => "invalid (0)",
-            DatType::DataTLB => "Data TLB",
-            DatType::InstructionTLB => "Instruction TLB",
-            DatType::UnifiedTLB => "Unified TLB",
-            DatType::Unknown => "Unknown",
-        };
-        f.write_str(t)
+    /// Branch instructions retired event is supported.
+    pub fn has_branch_instructions_retired_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 5)
     }
-}
 
-/// SoC vendor specific information (LEAF=0x17).
-///
-/// # Platforms
-/// ❌ AMD ✅ Intel
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct SoCVendorInfo {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
-    /// MaxSOCID_Index
-    eax: u32,
-    ebx: u32,
-    ecx: u32,
-    edx: u32,
-}
+    /// Branch mispredicts retired event is supported.
+    pub fn has_branch_mispredicts_retired_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 6)
+    }
 
-impl SoCVendorInfo {
-    pub fn get_soc_vendor_id(&self) -> u16 {
-        get_bits(self.ebx, 0, 15) as u16
+    /// Top-down slots event is supported.
+    pub fn has_topdown_slots_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 7)
     }
 
-    pub fn get_project_id(&self) -> u32 {
-        self.ecx
+    /// Top-down backend bound event is supported.
+    pub fn has_topdown_backend_bound_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 8)
     }
 
-    pub fn get_stepping_id(&self) -> u32 {
-        self.edx
+    /// Top-down bad speculation event is supported.
+    pub fn has_topdown_bad_speculation_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 9)
     }
 
-    pub fn get_vendor_brand(&self) -> Option<SoCVendorBrand> {
-        // Leaf 17H is valid if MaxSOCID_Index >= 3.
-        if self.eax >= 3 {
-            let r1 = cpuid!(EAX_SOC_VENDOR_INFO, 1);
-            let r2 = cpuid!(EAX_SOC_VENDOR_INFO, 2);
-            let r3 = cpuid!(EAX_SOC_VENDOR_INFO, 3);
-            Some(SoCVendorBrand { data: [r1, r2, r3] })
-        } else {
-            None
-        }
+    /// Top-down frontend bound event is supported.
+    pub fn has_topdown_frontend_bound_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 10)
     }
 
-    pub fn get_vendor_attributes(&self) -> Option<SoCVendorAttributesIter> {
-        if self.eax > 3 {
-            Some(SoCVendorAttributesIter {
-                read: self.read,
-                count: self.eax,
-                current: 3,
-            })
-        } else {
-            None
-        }
+    /// Top-down retiring event is supported.
+    pub fn has_topdown_retiring_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 11)
     }
-}
 
-impl fmt::Debug for SoCVendorInfo {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("SoCVendorInfo")
-            .field("soc_vendor_id", &self.get_soc_vendor_id())
-            .field("project_id", &self.get_project_id())
-            .field("stepping_id", &self.get_stepping_id())
-            .field("vendor_brand", &self.get_vendor_brand())
-            .field("vendor_attributes", &self.get_vendor_attributes())
+    /// LBR inserts event is supported.
+    pub fn has_lbr_inserts_event(&self) -> bool {
+        is_bit_set!(self.event_bitmap(), 12)
+    }
+
+    /// Encode this struct back into the registers of leaf 0x23.
+    ///
+    /// Returns subleaf 0 and, if they are valid, subleafs 1 and 3.
+    pub fn as_cpuid_results(&self) -> (CpuIdResult, Option<CpuIdResult>, Option<CpuIdResult>) {
+        (
+            CpuIdResult {
+                eax: self.eax,
+                ebx: self.ebx,
+                ecx: self.ecx,
+                edx: 0,
+            },
+            self.leaf1,
+            self.leaf3,
+        )
+    }
+}
+
+impl Debug for ArchPerfmonExtInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ArchPerfmonExtInfo")
+            .field("valid_subleafs", &self.valid_subleafs())
+            .field("has_unit_mask2", &self.has_unit_mask2())
+            .field("has_eq_bit", &self.has_eq_bit())
+            .field("topdown_slots", &self.topdown_slots())
+            .field("general_counter_bitmap", &self.general_counter_bitmap())
+            .field("fixed_counter_bitmap", &self.fixed_counter_bitmap())
+            .field("has_core_cycles_event", &self.has_core_cycles_event())
+            .field(
+                "has_instructions_retired_event",
+                &self.has_instructions_retired_event(),
+            )
+            .field("has_ref_cycles_event", &self.has_ref_cycles_event())
+            .field("has_llc_references_event", &self.has_llc_references_event())
+            .field("has_llc_misses_event", &self.has_llc_misses_event())
+            .field(
+                "has_branch_instructions_retired_event",
+                &self.has_branch_instructions_retired_event(),
+            )
+            .field(
+                "has_branch_mispredicts_retired_event",
+                &self.has_branch_mispredicts_retired_event(),
+            )
+            .field("has_topdown_slots_event", &self.has_topdown_slots_event())
+            .field(
+                "has_topdown_backend_bound_event",
+                &self.has_topdown_backend_bound_event(),
+            )
+            .field(
+                "has_topdown_bad_speculation_event",
+                &self.has_topdown_bad_speculation_event(),
+            )
+            .field(
+                "has_topdown_frontend_bound_event",
+                &self.has_topdown_frontend_bound_event(),
+            )
+            .field(
+                "has_topdown_retiring_event",
+                &self.has_topdown_retiring_event(),
+            )
+            .field("has_lbr_inserts_event", &self.has_lbr_inserts_event())
             .finish()
     }
 }
 
-/// Iterator for SoC vendor attributes.
-#[derive(Debug)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct SoCVendorAttributesIter {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
-    count: u32,
-    current: u32,
+/// Intel AVX10 Converged Vector ISA Enumeration (LEAF=0x24).
+///
+/// # Platforms
+/// ❌ AMD ✅ Intel
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Avx10Info {
+    eax: u32,
+    ebx: u32,
 }
 
-impl Iterator for SoCVendorAttributesIter {
-    type Item = CpuIdResult;
-
-    /// Iterate over all SoC vendor specific attributes.
-    fn next(&mut self) -> Option<CpuIdResult> {
-        if self.current > self.count {
-            return None;
+impl Avx10Info {
+    /// Decodes the registers of LEAF=0x24.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
         }
-        self.count += 1;
-        Some(self.read.cpuid2(EAX_SOC_VENDOR_INFO, self.count))
     }
-}
 
-/// A vendor brand string as queried from the cpuid leaf.
-#[derive(Debug, PartialEq, Eq)]
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-#[repr(C)]
-pub struct SoCVendorBrand {
-    data: [CpuIdResult; 3],
-}
+    /// Maximum supported subleaf of leaf 0x24.
+    pub fn max_subleaf(&self) -> u32 {
+        self.eax
+    }
 
-impl SoCVendorBrand {
-    /// Return the SocVendorBrand as a string.
-    pub fn as_str(&self) -> &str {
-        let brand_string_start = self as *const SoCVendorBrand as *const u8;
-        let slice = unsafe {
-            // Safety: SoCVendorBrand is laid out with repr(C).
-            slice::from_raw_parts(brand_string_start, size_of::<SoCVendorBrand>())
-        };
-        str::from_utf8(slice).unwrap_or("InvalidSoCVendorString")
+    /// AVX10 converged vector ISA version (e.g., 1 for AVX10.1).
+    pub fn version(&self) -> u8 {
+        get_bits(self.ebx, 0, 7) as u8
     }
 
-    #[deprecated(
-        since = "10.0.0",
-        note = "Use idiomatic function name `as_str` instead"
-    )]
-    pub fn as_string(&self) -> &str {
-        self.as_str()
+    check_bit_fn!(
+        doc = "AVX10 instructions with 128-bit vector length are supported.",
+        has_128bit_vectors,
+        ebx,
+        16
+    );
+    check_bit_fn!(
+        doc = "AVX10 instructions with 256-bit vector length are supported.",
+        has_256bit_vectors,
+        ebx,
+        17
+    );
+    check_bit_fn!(
+        doc = "AVX10 instructions with 512-bit vector length are supported.",
+        has_512bit_vectors,
+        ebx,
+        18
+    );
+
+    /// Largest supported vector length in bits (128, 256 or 512), or `None`
+    /// if no vector length is enumerated.
+    pub fn max_vector_length(&self) -> Option<u16> {
+        if self.has_512bit_vectors() {
+            Some(512)
+        } else if self.has_256bit_vectors() {
+            Some(256)
+        } else if self.has_128bit_vectors() {
+            Some(128)
+        } else {
+            None
+        }
+    }
+
+    /// Encode this struct back into the registers of leaf 0x24, subleaf 0.
+    ///
+    /// ECX and EDX are reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: 0,
+            edx: 0,
+        }
     }
+
+    set_bits_fn!(set_max_subleaf, max_subleaf, eax, 0, 31, u32);
+    set_bits_fn!(set_version, version, ebx, 0, 7, u8);
+    set_bit_fn!(set_128bit_vectors, has_128bit_vectors, ebx, 16);
+    set_bit_fn!(set_256bit_vectors, has_256bit_vectors, ebx, 17);
+    set_bit_fn!(set_512bit_vectors, has_512bit_vectors, ebx, 18);
 }
 
-impl fmt::Display for SoCVendorBrand {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.as_str())
+impl Debug for Avx10Info {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Avx10Info")
+            .field("version", &self.version())
+            .field("has_128bit_vectors", &self.has_128bit_vectors())
+            .field("has_256bit_vectors", &self.has_256bit_vectors())
+            .field("has_512bit_vectors", &self.has_512bit_vectors())
+            .finish()
     }
 }
 
@@ -5223,16 +11300,27 @@ impl fmt::Display for SoCVendorBrand {
 ///
 /// More information about this semi-official leaf can be found here
 /// <https://lwn.net/Articles/301888/>
-#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
-pub struct HypervisorInfo {
-    #[cfg_attr(feature = "serialize", serde(skip))]
-    read: CpuIdReader,
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HypervisorInfo<R: CpuIdReader = CpuIdReaderNative> {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    read: R,
     res: CpuIdResult,
+    /// First leaf of the block, 0x4000_0000 + N * 0x100.
+    #[cfg_attr(feature = "serde", serde(default = "hypervisor_base"))]
+    base: u32,
 }
 
-impl fmt::Debug for HypervisorInfo {
+#[cfg(feature = "serde")]
+fn hypervisor_base() -> u32 {
+    EAX_HYPERVISOR_INFO
+}
+
+eq_without_reader!(HypervisorInfo, res, base);
+
+impl<R: CpuIdReader> fmt::Debug for HypervisorInfo<R> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("HypervisorInfo")
+            .field("base", &self.base)
             .field("identify", &self.identify())
             .field("tsc_frequency", &self.tsc_frequency())
             .field("apic_frequency", &self.apic_frequency())
@@ -5256,7 +11344,18 @@ pub enum Hypervisor {
     Unknown(u32, u32, u32),
 }
 
-impl HypervisorInfo {
+impl<R: CpuIdReader> HypervisorInfo<R> {
+    /// The first leaf of the interface's block, e.g., 0x4000_0000 or
+    /// 0x4000_0100 (see [`CpuId::get_hypervisor_interfaces`]).
+    pub fn base(&self) -> u32 {
+        self.base
+    }
+
+    /// The leaf in this block that corresponds to `leaf` of the first block.
+    fn leaf(&self, leaf: u32) -> u32 {
+        self.base + (leaf - EAX_HYPERVISOR_INFO)
+    }
+
     /// Returns the identity of the [`Hypervisor`].
     ///
     /// ## Technical Background
@@ -5297,28 +11396,463 @@ impl HypervisorInfo {
         }
     }
 
-    /// TSC frequency in kHz.
-    pub fn tsc_frequency(&self) -> Option<u32> {
-        // vm aware tsc frequency retrieval:
-        // # EAX: (Virtual) TSC frequency in kHz.
-        if self.res.eax >= 0x40000010 {
-            let virt_tinfo = self.read.cpuid2(0x40000010, 0);
-            Some(virt_tinfo.eax)
+    /// The raw 12-byte hypervisor signature in register order (EBX, ECX,
+    /// EDX), e.g., `b"KVMKVMKVM\0\0\0"`.
+    ///
+    /// Unlike the vendor string of leaf 0, the signature is stored in EBX,
+    /// ECX and EDX (in this order).
+    pub fn signature(&self) -> [u8; 12] {
+        let mut bytes = [0; 12];
+        bytes[0..4].copy_from_slice(&self.res.ebx.to_le_bytes());
+        bytes[4..8].copy_from_slice(&self.res.ecx.to_le_bytes());
+        bytes[8..12].copy_from_slice(&self.res.edx.to_le_bytes());
+        bytes
+    }
+
+    /// How the hypervisor reports the (virtual) TSC and local APIC timer
+    /// frequency.
+    ///
+    /// VMware and KVM/QEMU report them in the timing leaf (LEAF=0x4000_0010).
+    /// Hyper-V (and hypervisors implementing its interface) doesn't report
+    /// them in CPUID, but tells whether they can be read from its synthetic
+    /// MSRs or whether a constant-rate reference counter is available
+    /// (LEAF=0x4000_0003).
+    pub fn clock_source(&self) -> Option<HypervisorClockSource> {
+        if self.has_hyperv_interface() {
+            if self.res.eax < self.leaf(EAX_HYPERV_FEATURES) {
+                return None;
+            }
+            let features = self.read.cpuid1(self.leaf(EAX_HYPERV_FEATURES));
+            // AccessFrequencyRegs and the "frequency MSRs available" flag:
+            if is_bit_set!(features.eax, 11) && is_bit_set!(features.edx, 8) {
+                Some(HypervisorClockSource::HyperVFrequencyMsrs)
+            } else if is_bit_set!(features.eax, 9) || is_bit_set!(features.eax, 1) {
+                // AccessPartitionReferenceTsc or AccessPartitionReferenceCounter
+                Some(HypervisorClockSource::HyperVReferenceCounter)
+            } else {
+                None
+            }
+        } else if self.res.eax >= self.leaf(EAX_HYPERVISOR_TIMING_INFO) {
+            let res = self.read.cpuid1(self.leaf(EAX_HYPERVISOR_TIMING_INFO));
+            (res.eax != 0 || res.ebx != 0).then_some(HypervisorClockSource::TimingLeaf {
+                tsc_khz: res.eax,
+                apic_khz: res.ebx,
+            })
         } else {
             None
         }
     }
 
+    /// (Virtual) TSC frequency in kHz.
+    ///
+    /// Only available if the hypervisor reports it in the timing leaf, see
+    /// [`HypervisorInfo::clock_source`] for other hypervisors.
+    pub fn tsc_frequency(&self) -> Option<u32> {
+        match self.clock_source()? {
+            HypervisorClockSource::TimingLeaf { tsc_khz, .. } => (tsc_khz != 0).then_some(tsc_khz),
+            _ => None,
+        }
+    }
+
     /// (Virtual) Bus (local apic timer) frequency in kHz.
+    ///
+    /// Only available if the hypervisor reports it in the timing leaf, see
+    /// [`HypervisorInfo::clock_source`] for other hypervisors.
     pub fn apic_frequency(&self) -> Option<u32> {
-        // # EBX: (Virtual) Bus (local apic timer) frequency in kHz.
-        if self.res.eax >= 0x40000010 {
-            let virt_tinfo = self.read.cpuid2(0x40000010, 0);
-            Some(virt_tinfo.ebx)
+        match self.clock_source()? {
+            HypervisorClockSource::TimingLeaf { apic_khz, .. } => {
+                (apic_khz != 0).then_some(apic_khz)
+            }
+            _ => None,
+        }
+    }
+
+    /// Is the Hyper-V hypervisor interface (`Hv#1` in LEAF=0x4000_0001)
+    /// implemented?
+    ///
+    /// Besides Hyper-V this is also reported by other hypervisors that
+    /// provide Hyper-V enlightenments (e.g., KVM or QEMU).
+    pub fn has_hyperv_interface(&self) -> bool {
+        // "Hv#1"
+        self.res.eax >= self.leaf(EAX_HYPERV_INTERFACE)
+            && self.read.cpuid1(self.leaf(EAX_HYPERV_INTERFACE)).eax == 0x31237648
+    }
+
+    /// Nested virtualization features of the Hyper-V interface
+    /// (LEAF=0x4000_000A).
+    ///
+    /// Only returned if the Hyper-V interface is present, see
+    /// [`HypervisorInfo::has_hyperv_interface`].
+    pub fn hyperv_nested_features(&self) -> Option<HyperVNestedFeatures> {
+        if self.has_hyperv_interface() && self.res.eax >= self.leaf(EAX_HYPERV_NESTED_FEATURES) {
+            let res = self.read.cpuid1(self.leaf(EAX_HYPERV_NESTED_FEATURES));
+            Some(HyperVNestedFeatures {
+                eax: res.eax,
+                ebx: res.ebx,
+            })
         } else {
             None
         }
     }
+
+    /// Encode this struct back into the registers of the first leaf of the
+    /// block (e.g., 0x4000_0000).
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        self.res
+    }
+}
+
+/// Iterator over the hypervisor interfaces (LEAF=0x4000_0000 + N * 0x100).
+///
+/// Yields a [`HypervisorInfo`] for each block of leafs that is present, see
+/// [`CpuId::get_hypervisor_interfaces`].
+#[derive(Clone)]
+pub struct HypervisorInterfacesIter<R: CpuIdReader = CpuIdReaderNative> {
+    read: R,
+    /// The next block to probe.
+    base: u32,
+}
+
+impl<R: CpuIdReader> Iterator for HypervisorInterfacesIter<R> {
+    type Item = HypervisorInfo<R>;
+
+    fn next(&mut self) -> Option<HypervisorInfo<R>> {
+        while self.base <= EAX_HYPERVISOR_LAST_BLOCK {
+            let base = self.base;
+            self.base += HYPERVISOR_BLOCK_SIZE;
+
+            let res = self.read.cpuid1(base);
+            if hypervisor_block_max_leaf(base, &res).is_some() {
+                return Some(HypervisorInfo {
+                    read: self.read.clone(),
+                    res,
+                    base,
+                });
+            }
+        }
+        None
+    }
+}
+
+impl<R: CpuIdReader> FusedIterator for HypervisorInterfacesIter<R> {}
+
+/// The highest leaf of the block of hypervisor leafs at `base`, given the
+/// first leaf of the block, or `None` if the block isn't present.
+pub(crate) fn hypervisor_block_max_leaf(base: u32, res: &CpuIdResult) -> Option<u32> {
+    // Old KVM versions report 0 instead of the highest leaf, which is the
+    // second one of the block ("KVMKVMKVM\0\0\0").
+    let kvm_signature = (0x4b4d564b, 0x564b4d56, 0x0000004d);
+    if res.eax == 0 && (res.ebx, res.ecx, res.edx) == kvm_signature {
+        return Some(base + 1);
+    }
+    (base..base + HYPERVISOR_BLOCK_SIZE)
+        .contains(&res.eax)
+        .then_some(res.eax)
+}
+
+impl<R: CpuIdReader> Debug for HypervisorInterfacesIter<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut debug = f.debug_list();
+        self.clone().for_each(|ref item| {
+            debug.entry(item);
+        });
+        debug.finish()
+    }
+}
+
+/// How a hypervisor reports the (virtual) TSC and local APIC timer frequency,
+/// see [`HypervisorInfo::clock_source`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HypervisorClockSource {
+    /// Frequencies from the timing leaf (LEAF=0x4000_0010), used by VMware
+    /// and KVM/QEMU.
+    TimingLeaf {
+        /// TSC frequency in kHz.
+        tsc_khz: u32,
+        /// Local APIC timer (bus) frequency in kHz.
+        apic_khz: u32,
+    },
+    /// Hyper-V: the frequencies can be read from the synthetic MSRs
+    /// `HV_X64_MSR_TSC_FREQUENCY` (0x4000_0022) and
+    /// `HV_X64_MSR_APIC_FREQUENCY` (0x4000_0023).
+    HyperVFrequencyMsrs,
+    /// Hyper-V: the frequencies aren't available, but the partition reference
+    /// counter (or reference TSC page) provides time at a constant rate of
+    /// 10 MHz.
+    HyperVReferenceCounter,
+}
+
+/// Hyper-V nested hypervisor feature identification (LEAF=0x4000_000A).
+///
+/// Used by hypervisors running on top of Hyper-V (e.g., KVM) to configure
+/// enlightenments. See the Hyper-V Hypervisor Top Level Functional
+/// Specification for details.
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HyperVNestedFeatures {
+    eax: u32,
+    ebx: u32,
+}
+
+impl HyperVNestedFeatures {
+    /// Decodes the registers of LEAF=0x4000_000A.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+        }
+    }
+
+    /// Lowest supported enlightened VMCS version.
+    pub fn enlightened_vmcs_version_low(&self) -> u8 {
+        get_bits(self.eax, 0, 7) as u8
+    }
+
+    /// Highest supported enlightened VMCS version.
+    pub fn enlightened_vmcs_version_high(&self) -> u8 {
+        get_bits(self.eax, 8, 15) as u8
+    }
+
+    check_bit_fn!(
+        doc = "Direct virtual flush hypercalls (HvCallFlushVirtualAddressSpace{Ex}, HvCallFlushVirtualAddressList{Ex}) are supported.",
+        has_direct_virtual_flush,
+        eax,
+        17
+    );
+    check_bit_fn!(
+        doc = "HvCallFlushGuestPhysicalAddressSpace and HvCallFlushGuestPhysicalAddressList are supported.",
+        has_guest_mapping_flush,
+        eax,
+        18
+    );
+    check_bit_fn!(
+        doc = "An enlightened MSR bitmap can be used.",
+        has_enlightened_msr_bitmap,
+        eax,
+        19
+    );
+    check_bit_fn!(
+        doc = "Virtualization exceptions can be combined in the page fault exception class.",
+        has_combined_virtualization_exceptions,
+        eax,
+        20
+    );
+    check_bit_fn!(
+        doc = "The enlightened TLB is supported (AMD only).",
+        has_enlightened_tlb,
+        eax,
+        22
+    );
+    check_bit_fn!(
+        doc = "The enlightened VMCS supports the GUEST_IA32_PERF_GLOBAL_CTRL and HOST_IA32_PERF_GLOBAL_CTRL fields.",
+        has_evmcs_perf_global_ctrl,
+        ebx,
+        0
+    );
+
+    /// Encode this struct back into the registers of leaf 0x4000_000A.
+    ///
+    /// ECX and EDX are reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: 0,
+            edx: 0,
+        }
+    }
+
+    set_bits_fn!(
+        set_enlightened_vmcs_version_low,
+        enlightened_vmcs_version_low,
+        eax,
+        0,
+        7,
+        u8
+    );
+    set_bits_fn!(
+        set_enlightened_vmcs_version_high,
+        enlightened_vmcs_version_high,
+        eax,
+        8,
+        15,
+        u8
+    );
+    set_bit_fn!(set_direct_virtual_flush, has_direct_virtual_flush, eax, 17);
+    set_bit_fn!(set_guest_mapping_flush, has_guest_mapping_flush, eax, 18);
+    set_bit_fn!(
+        set_enlightened_msr_bitmap,
+        has_enlightened_msr_bitmap,
+        eax,
+        19
+    );
+    set_bit_fn!(
+        set_combined_virtualization_exceptions,
+        has_combined_virtualization_exceptions,
+        eax,
+        20
+    );
+    set_bit_fn!(set_enlightened_tlb, has_enlightened_tlb, eax, 22);
+    set_bit_fn!(
+        set_evmcs_perf_global_ctrl,
+        has_evmcs_perf_global_ctrl,
+        ebx,
+        0
+    );
+}
+
+impl Debug for HyperVNestedFeatures {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("HyperVNestedFeatures")
+            .field(
+                "enlightened_vmcs_version_low",
+                &self.enlightened_vmcs_version_low(),
+            )
+            .field(
+                "enlightened_vmcs_version_high",
+                &self.enlightened_vmcs_version_high(),
+            )
+            .field("has_direct_virtual_flush", &self.has_direct_virtual_flush())
+            .field("has_guest_mapping_flush", &self.has_guest_mapping_flush())
+            .field(
+                "has_enlightened_msr_bitmap",
+                &self.has_enlightened_msr_bitmap(),
+            )
+            .field(
+                "has_combined_virtualization_exceptions",
+                &self.has_combined_virtualization_exceptions(),
+            )
+            .field("has_enlightened_tlb", &self.has_enlightened_tlb())
+            .field(
+                "has_evmcs_perf_global_ctrl",
+                &self.has_evmcs_perf_global_ctrl(),
+            )
+            .finish()
+    }
+}
+
+/// Availability of the PadLock security engines (LEAF=0xC000_0001).
+///
+/// An engine is only usable if it is both present and enabled.
+///
+/// # Platforms
+/// ❌ AMD ❌ Intel ✅ Centaur ✅ Zhaoxin
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PadLockInfo {
+    edx: u32,
+}
+
+impl PadLockInfo {
+    /// Decodes the registers of LEAF=0xC000_0001.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self { edx: data.edx }
+    }
+
+    check_bit_fn!(
+        doc = "Is the random number generator (XSTORE) present?",
+        has_rng,
+        edx,
+        2
+    );
+
+    check_bit_fn!(
+        doc = "Is the random number generator enabled?",
+        is_rng_enabled,
+        edx,
+        3
+    );
+
+    check_bit_fn!(
+        doc = "Is the Advanced Cryptography Engine (XCRYPT, AES) present?",
+        has_ace,
+        edx,
+        6
+    );
+
+    check_bit_fn!(
+        doc = "Is the Advanced Cryptography Engine enabled?",
+        is_ace_enabled,
+        edx,
+        7
+    );
+
+    check_bit_fn!(
+        doc = "Is the Advanced Cryptography Engine 2 (additional AES modes) present?",
+        has_ace2,
+        edx,
+        8
+    );
+
+    check_bit_fn!(
+        doc = "Is the Advanced Cryptography Engine 2 enabled?",
+        is_ace2_enabled,
+        edx,
+        9
+    );
+
+    check_bit_fn!(
+        doc = "Is the Hash Engine (SHA-1, SHA-256) present?",
+        has_phe,
+        edx,
+        10
+    );
+
+    check_bit_fn!(doc = "Is the Hash Engine enabled?", is_phe_enabled, edx, 11);
+
+    check_bit_fn!(
+        doc = "Is the Montgomery Multiplier (RSA) present?",
+        has_pmm,
+        edx,
+        12
+    );
+
+    check_bit_fn!(
+        doc = "Is the Montgomery Multiplier enabled?",
+        is_pmm_enabled,
+        edx,
+        13
+    );
+
+    /// Encode this struct back into the registers of leaf 0xC000_0001.
+    ///
+    /// Only EDX is decoded, the other registers are zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: 0,
+            ebx: 0,
+            ecx: 0,
+            edx: self.edx,
+        }
+    }
+
+    set_bit_fn!(set_rng, has_rng, edx, 2);
+    set_bit_fn!(set_rng_enabled, is_rng_enabled, edx, 3);
+    set_bit_fn!(set_ace, has_ace, edx, 6);
+    set_bit_fn!(set_ace_enabled, is_ace_enabled, edx, 7);
+    set_bit_fn!(set_ace2, has_ace2, edx, 8);
+    set_bit_fn!(set_ace2_enabled, is_ace2_enabled, edx, 9);
+    set_bit_fn!(set_phe, has_phe, edx, 10);
+    set_bit_fn!(set_phe_enabled, is_phe_enabled, edx, 11);
+    set_bit_fn!(set_pmm, has_pmm, edx, 12);
+    set_bit_fn!(set_pmm_enabled, is_pmm_enabled, edx, 13);
+}
+
+impl Debug for PadLockInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PadLockInfo")
+            .field("has_rng", &self.has_rng())
+            .field("is_rng_enabled", &self.is_rng_enabled())
+            .field("has_ace", &self.has_ace())
+            .field("is_ace_enabled", &self.is_ace_enabled())
+            .field("has_ace2", &self.has_ace2())
+            .field("is_ace2_enabled", &self.is_ace2_enabled())
+            .field("has_phe", &self.has_phe())
+            .field("is_phe_enabled", &self.is_phe_enabled())
+            .field("has_pmm", &self.has_pmm())
+            .field("is_pmm_enabled", &self.is_pmm_enabled())
+            .finish()
+    }
 }
 
 #[cfg(doctest)]
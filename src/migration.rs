@@ -0,0 +1,169 @@
+//! Compute a CPUID table that is supported by a group of hosts, so a virtual
+//! machine using it can be live-migrated between all of them.
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{
+    get_bits, set_bits, CpuIdResult, CpuIdSnapshot, EAX_ADVANCED_POWER_MGMT_INFO,
+    EAX_CACHE_PARAMETERS, EAX_CACHE_PARAMETERS_AMD, EAX_EXTENDED_FUNCTION_INFO,
+    EAX_EXTENDED_PROCESSOR_AND_FEATURE_IDENTIFIERS, EAX_EXTENDED_STATE_INFO,
+    EAX_EXTENDED_TOPOLOGY_INFO, EAX_EXTENDED_TOPOLOGY_INFO_V2, EAX_FEATURE_INFO, EAX_L1_CACHE_INFO,
+    EAX_L2_L3_CACHE_INFO, EAX_MEMORY_ENCRYPTION_INFO, EAX_PROCESSOR_CAPACITY_INFO,
+    EAX_STRUCTURED_EXTENDED_FEATURE_INFO, EAX_SVM_FEATURES, EAX_THERMAL_POWER_INFO, EAX_TRACE_INFO,
+    EAX_VENDOR_INFO,
+};
+
+/// Register fields that are combined by taking the minimum over all hosts.
+///
+/// Each entry is `(register, from bit, to bit)` where registers are numbered
+/// EAX = 0, EBX = 1, ECX = 2 and EDX = 3.
+type MinFields = &'static [(usize, u32, u32)];
+
+/// How the registers of a leaf are combined.
+struct Rule {
+    /// Bitmask of registers that contain feature flags and are combined with
+    /// a bitwise AND.
+    and: u8,
+    /// Fields that are combined by taking the minimum.
+    min: MinFields,
+}
+
+const EAX: u8 = 1 << 0;
+const EBX: u8 = 1 << 1;
+const ECX: u8 = 1 << 2;
+const EDX: u8 = 1 << 3;
+
+fn rule(leaf: u32, subleaf: u32) -> Rule {
+    let (and, min): (u8, MinFields) = match (leaf, subleaf) {
+        // Maximum leaf:
+        (EAX_VENDOR_INFO, _) | (EAX_EXTENDED_FUNCTION_INFO, _) => (0, &[(0, 0, 31)]),
+        (EAX_FEATURE_INFO, _) => (ECX | EDX, &[]),
+        // Line size, partitions, ways, sets and the number of sharing cores:
+        (EAX_CACHE_PARAMETERS, _) | (EAX_CACHE_PARAMETERS_AMD, _) => (
+            0,
+            &[
+                (0, 14, 25),
+                (0, 26, 31),
+                (1, 0, 11),
+                (1, 12, 21),
+                (1, 22, 31),
+                (2, 0, 31),
+            ],
+        ),
+        (EAX_THERMAL_POWER_INFO, _) => (EAX | ECX, &[]),
+        // Subleaf 0 reports the maximum subleaf in EAX:
+        (EAX_STRUCTURED_EXTENDED_FEATURE_INFO, 0) => (EBX | ECX | EDX, &[(0, 0, 31)]),
+        (EAX_STRUCTURED_EXTENDED_FEATURE_INFO, _) => (EAX | EBX | ECX | EDX, &[]),
+        // Number of logical processors at this level:
+        (EAX_EXTENDED_TOPOLOGY_INFO, _) | (EAX_EXTENDED_TOPOLOGY_INFO_V2, _) => (0, &[(1, 0, 15)]),
+        // Supported XCR0 bits (XSAVE area sizes are taken from the first host):
+        (EAX_EXTENDED_STATE_INFO, 0) => (EAX | EDX, &[]),
+        // XSAVE features and supported IA32_XSS bits:
+        (EAX_EXTENDED_STATE_INFO, 1) => (EAX | ECX | EDX, &[]),
+        (EAX_TRACE_INFO, 0) => (EBX | ECX, &[(0, 0, 31)]),
+        (EAX_EXTENDED_PROCESSOR_AND_FEATURE_IDENTIFIERS, _) => (ECX | EDX, &[]),
+        // L1 data and instruction cache sizes:
+        (EAX_L1_CACHE_INFO, _) => (0, &[(2, 24, 31), (3, 24, 31)]),
+        // L2 and L3 cache sizes:
+        (EAX_L2_L3_CACHE_INFO, _) => (0, &[(2, 16, 31), (3, 18, 31)]),
+        (EAX_ADVANCED_POWER_MGMT_INFO, _) => (EBX | EDX, &[]),
+        // Physical, linear and guest physical address sizes:
+        (EAX_PROCESSOR_CAPACITY_INFO, _) => (EBX, &[(0, 0, 7), (0, 8, 15), (0, 16, 23)]),
+        // Number of ASIDs:
+        (EAX_SVM_FEATURES, _) => (EDX, &[(1, 0, 31)]),
+        // Number of encrypted guests (the minimum SEV ASID is handled
+        // separately):
+        (EAX_MEMORY_ENCRYPTION_INFO, _) => (EAX, &[(2, 0, 31)]),
+        _ => (0, &[]),
+    };
+    Rule { and, min }
+}
+
+fn registers(res: &CpuIdResult) -> [u32; 4] {
+    [res.eax, res.ebx, res.ecx, res.edx]
+}
+
+impl CpuIdSnapshot {
+    /// Computes the CPUID table a virtual machine can use on all hosts with
+    /// the given `snapshots`.
+    ///
+    /// - Leafs that are missing on any host are removed.
+    /// - Feature flags are only kept if they're set on all hosts.
+    /// - Cache sizes, topology counts, address sizes and maximum leaf values
+    ///   are set to the minimum across all hosts.
+    /// - Identification (vendor, family, model, brand string) and all other
+    ///   leafs are taken from the first snapshot.
+    ///
+    /// Returns `None` if `snapshots` is empty.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{CpuIdBuilder, CpuIdSnapshot, Feature};
+    ///
+    /// let host_a = CpuIdSnapshot::from_host();
+    /// let mut builder = CpuIdBuilder::with_cpuid_reader(&host_a);
+    /// builder.set_feature(Feature::Sse2, false);
+    /// let host_b = CpuIdSnapshot::from(builder.build());
+    ///
+    /// let guest = CpuIdSnapshot::intersection([&host_a, &host_b]).unwrap();
+    /// assert!(!guest.cpuid().has_feature(Feature::Sse2));
+    /// ```
+    pub fn intersection<'a, I>(snapshots: I) -> Option<CpuIdSnapshot>
+    where
+        I: IntoIterator<Item = &'a CpuIdSnapshot>,
+    {
+        let snapshots: Vec<&CpuIdSnapshot> = snapshots.into_iter().collect();
+        let (first, others) = snapshots.split_first()?;
+
+        let mut leafs = BTreeMap::new();
+        'leafs: for (&(leaf, subleaf), res) in first.leafs() {
+            let rule = rule(leaf, subleaf);
+            let mut regs = registers(res);
+            for other in others {
+                let other = match other.get(leaf, subleaf) {
+                    Some(res) => registers(&res),
+                    None => continue 'leafs,
+                };
+                for idx in 0..regs.len() {
+                    if rule.and & (1 << idx) != 0 {
+                        regs[idx] &= other[idx];
+                    }
+                }
+                for &(idx, from, to) in rule.min {
+                    let value = get_bits(other[idx], from, to);
+                    if value < get_bits(regs[idx], from, to) {
+                        set_bits(&mut regs[idx], from, to, value);
+                    }
+                }
+            }
+            let [eax, ebx, ecx, edx] = regs;
+            leafs.insert((leaf, subleaf), CpuIdResult { eax, ebx, ecx, edx });
+        }
+
+        // Keep the largest minimum SEV ASID, so the range of ASIDs used for
+        // SEV-ES guests is valid on all hosts.
+        if let Some(res) = leafs.get_mut(&(EAX_MEMORY_ENCRYPTION_INFO, 0)) {
+            res.edx = snapshots
+                .iter()
+                .filter_map(|s| s.get(EAX_MEMORY_ENCRYPTION_INFO, 0))
+                .map(|res| res.edx)
+                .max()
+                .unwrap_or(res.edx);
+        }
+
+        // Remove XSAVE state components that aren't supported everywhere.
+        let xcr0 = leafs
+            .get(&(EAX_EXTENDED_STATE_INFO, 0))
+            .map_or(0, |res| (res.edx as u64) << 32 | res.eax as u64);
+        let xss = leafs
+            .get(&(EAX_EXTENDED_STATE_INFO, 1))
+            .map_or(0, |res| (res.edx as u64) << 32 | res.ecx as u64);
+        leafs.retain(|&(leaf, subleaf), _| {
+            leaf != EAX_EXTENDED_STATE_INFO
+                || subleaf < 2
+                || (subleaf < 64 && (xcr0 | xss) & (1 << subleaf) != 0)
+        });
+
+        Some(CpuIdSnapshot::from(leafs))
+    }
+}
@@ -0,0 +1,761 @@
+//! Named CPU models as defined by QEMU (and used by libvirt), e.g., to decide
+//! which model a guest can use so it stays migratable between hosts.
+use crate::{CpuId, CpuIdReader, Feature};
+
+use Feature::*;
+
+/// A named CPU model (e.g., `Skylake-Server`) and the feature flags it
+/// requires from the host.
+///
+/// The definitions follow the latest version of each model in QEMU (i.e.,
+/// without TSX and MPX). Only flags known to this library (see [`Feature`])
+/// are checked.
+///
+/// # Example
+/// ```
+/// use raw_cpuid::{CpuId, CpuModel};
+///
+/// let cpuid = CpuId::new();
+/// let westmere = CpuModel::from_name("Westmere").unwrap();
+/// if cpuid.supports_model(westmere) {
+///     println!("Can run Westmere guests");
+/// }
+/// if let Some(model) = cpuid.newest_supported_model() {
+///     println!("Newest model: {}", model.name);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuModel {
+    /// Name of the model as used by `qemu -cpu` and libvirt.
+    pub name: &'static str,
+    /// Vendor identification string of the model, e.g., `GenuineIntel`.
+    pub vendor: &'static str,
+    /// The closest `-march=` name of GCC and Clang, e.g., `skylake-avx512`.
+    pub march: &'static str,
+    /// Required features, grouped by the model that introduced them.
+    features: &'static [&'static [Feature]],
+}
+
+impl CpuModel {
+    /// All known models, ordered from oldest to newest for each vendor.
+    pub const ALL: &'static [CpuModel] = &[
+        CpuModel::intel("Conroe", "core2", CONROE),
+        CpuModel::intel("Penryn", "core2", PENRYN),
+        CpuModel::intel("Nehalem", "nehalem", NEHALEM),
+        CpuModel::intel("Westmere", "westmere", WESTMERE),
+        CpuModel::intel("SandyBridge", "sandybridge", SANDY_BRIDGE),
+        CpuModel::intel("IvyBridge", "ivybridge", IVY_BRIDGE),
+        CpuModel::intel("Haswell", "haswell", HASWELL),
+        CpuModel::intel("Broadwell", "broadwell", BROADWELL),
+        CpuModel::intel("Skylake-Client", "skylake", SKYLAKE_CLIENT),
+        CpuModel::intel("Skylake-Server", "skylake-avx512", SKYLAKE_SERVER),
+        CpuModel::intel("Cascadelake-Server", "cascadelake", CASCADELAKE_SERVER),
+        CpuModel::intel("Cooperlake", "cooperlake", COOPERLAKE),
+        CpuModel::intel("Icelake-Server", "icelake-server", ICELAKE_SERVER),
+        CpuModel::intel("SapphireRapids", "sapphirerapids", SAPPHIRE_RAPIDS),
+        CpuModel::amd("Opteron_G1", "k8-sse3", OPTERON_G1),
+        CpuModel::amd("Opteron_G2", "k8-sse3", OPTERON_G2),
+        CpuModel::amd("Opteron_G3", "amdfam10", OPTERON_G3),
+        CpuModel::amd("Opteron_G4", "bdver1", OPTERON_G4),
+        CpuModel::amd("Opteron_G5", "bdver2", OPTERON_G5),
+        CpuModel::amd("EPYC", "znver1", EPYC),
+        CpuModel::amd("EPYC-Rome", "znver2", EPYC_ROME),
+        CpuModel::amd("EPYC-Milan", "znver3", EPYC_MILAN),
+        CpuModel::amd("EPYC-Genoa", "znver4", EPYC_GENOA),
+    ];
+
+    const fn intel(
+        name: &'static str,
+        march: &'static str,
+        features: &'static [&'static [Feature]],
+    ) -> Self {
+        CpuModel {
+            name,
+            vendor: "GenuineIntel",
+            march,
+            features,
+        }
+    }
+
+    const fn amd(
+        name: &'static str,
+        march: &'static str,
+        features: &'static [&'static [Feature]],
+    ) -> Self {
+        CpuModel {
+            name,
+            vendor: "AuthenticAMD",
+            march,
+            features,
+        }
+    }
+
+    /// Look up a model by its name (case-sensitive, e.g., `EPYC-Rome`).
+    pub fn from_name(name: &str) -> Option<&'static CpuModel> {
+        CpuModel::ALL.iter().find(|model| model.name == name)
+    }
+
+    /// All features required by this model.
+    pub fn features(&self) -> impl Iterator<Item = Feature> + '_ {
+        self.features.iter().flat_map(|group| group.iter().copied())
+    }
+
+    /// Is `feature` part of this model?
+    pub fn has_feature(&self, feature: Feature) -> bool {
+        self.features().any(|f| f == feature)
+    }
+
+    /// Features required by this model that are not supported by `cpuid`.
+    pub fn missing_features<'a, R: CpuIdReader>(
+        &'a self,
+        cpuid: &'a CpuId<R>,
+    ) -> impl Iterator<Item = Feature> + 'a {
+        self.features().filter(move |f| !cpuid.has_feature(*f))
+    }
+}
+
+impl<R: CpuIdReader> CpuId<R> {
+    /// Can this CPU back a guest with the given `model`, i.e., does it support
+    /// all features of the model?
+    ///
+    /// The vendor is not taken into account.
+    pub fn supports_model(&self, model: &CpuModel) -> bool {
+        model.missing_features(self).next().is_none()
+    }
+
+    /// The newest model of this CPU's vendor that [`CpuId::supports_model`].
+    pub fn newest_supported_model(&self) -> Option<&'static CpuModel> {
+        let vendor = self.get_vendor_info()?;
+        CpuModel::ALL
+            .iter()
+            .rev()
+            .filter(|model| model.vendor == vendor.as_str())
+            .find(|model| self.supports_model(model))
+    }
+
+    /// The closest `-march=` name of GCC and Clang for this CPU, e.g.,
+    /// `skylake-avx512` or `znver3`.
+    ///
+    /// This is the newest target of the CPU's vendor whose instruction set
+    /// extensions are all supported. Unlike the [`CpuModel`]s, the targets
+    /// don't require system features such as SVM, VME or MONITOR, which
+    /// hypervisors often hide. CPUs of other vendors (or older than all
+    /// targets) get the x86-64 microarchitecture level they support
+    /// (`x86-64-v4` to `x86-64`), `None` if they don't support 64-bit mode.
+    ///
+    /// Unlike `-march=native`, the name only depends on the features of the
+    /// CPU, so builds on machines with the same name are reproducible.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// if let Some(march) = CpuId::new().march() {
+    ///     println!("CFLAGS=-march={}", march);
+    /// }
+    /// ```
+    pub fn march(&self) -> Option<&'static str> {
+        let vendor = self.get_vendor_info();
+        let has_all = |groups: &[&[Feature]]| {
+            groups
+                .iter()
+                .flat_map(|group| group.iter())
+                .all(|f| self.has_feature(*f))
+        };
+        MARCHES
+            .iter()
+            .rev()
+            .filter(|(march_vendor, _, _)| {
+                vendor.as_ref().map(|v| v.as_str()) == Some(*march_vendor)
+            })
+            .find(|(_, _, groups)| has_all(groups))
+            .map(|(_, name, _)| *name)
+            .or_else(|| self.x86_64_level())
+    }
+
+    /// The highest x86-64 microarchitecture level of the psABI the CPU
+    /// supports: `x86-64-v4`, `x86-64-v3`, `x86-64-v2` or `x86-64`.
+    ///
+    /// Returns `None` if the CPU doesn't support 64-bit mode.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// if let Some(level) = CpuId::new().x86_64_level() {
+    ///     println!("RUSTFLAGS=-C target-cpu={}", level);
+    /// }
+    /// ```
+    pub fn x86_64_level(&self) -> Option<&'static str> {
+        let has_all = |features: &[Feature]| features.iter().all(|f| self.has_feature(*f));
+        X86_64_LEVELS
+            .iter()
+            .find(|(_, features)| has_all(features))
+            .map(|(name, _)| *name)
+    }
+}
+
+/// The x86-64 microarchitecture levels of the psABI, newest first, with the
+/// features each level requires (including those of the lower levels).
+const X86_64_LEVELS: &[(&str, &[Feature])] = &[
+    (
+        "x86-64-v4",
+        &[
+            Lm, Cmov, Cx8, Fpu, Fxsr, Mmx, Sse, Sse2, Cmpxchg16b, LahfLm, Popcnt, Sse3, Sse41,
+            Sse42, Ssse3, Avx, Avx2, Bmi1, Bmi2, F16c, Fma, Lzcnt, Movbe, Xsave, Avx512f, Avx512bw,
+            Avx512cd, Avx512dq, Avx512vl,
+        ],
+    ),
+    (
+        "x86-64-v3",
+        &[
+            Lm, Cmov, Cx8, Fpu, Fxsr, Mmx, Sse, Sse2, Cmpxchg16b, LahfLm, Popcnt, Sse3, Sse41,
+            Sse42, Ssse3, Avx, Avx2, Bmi1, Bmi2, F16c, Fma, Lzcnt, Movbe, Xsave,
+        ],
+    ),
+    (
+        "x86-64-v2",
+        &[
+            Lm, Cmov, Cx8, Fpu, Fxsr, Mmx, Sse, Sse2, Cmpxchg16b, LahfLm, Popcnt, Sse3, Sse41,
+            Sse42, Ssse3,
+        ],
+    ),
+    ("x86-64", &[Lm, Cmov, Cx8, Fpu, Fxsr, Mmx, Sse, Sse2]),
+];
+
+/// `-march=` targets of GCC and Clang as `(vendor, name, features)`, ordered
+/// from oldest to newest for each vendor.
+const MARCHES: &[(&str, &str, &[&[Feature]])] = &[
+    ("GenuineIntel", "core2", CORE2_ISA),
+    ("GenuineIntel", "nehalem", NEHALEM_ISA),
+    ("GenuineIntel", "westmere", WESTMERE_ISA),
+    ("GenuineIntel", "sandybridge", SANDY_BRIDGE_ISA),
+    ("GenuineIntel", "ivybridge", IVY_BRIDGE_ISA),
+    ("GenuineIntel", "haswell", HASWELL_ISA),
+    ("GenuineIntel", "broadwell", BROADWELL_ISA),
+    ("GenuineIntel", "skylake", SKYLAKE_ISA),
+    ("GenuineIntel", "skylake-avx512", SKYLAKE_AVX512_ISA),
+    ("GenuineIntel", "cascadelake", CASCADELAKE_ISA),
+    ("GenuineIntel", "cooperlake", COOPERLAKE_ISA),
+    ("GenuineIntel", "icelake-server", ICELAKE_SERVER_ISA),
+    ("GenuineIntel", "sapphirerapids", SAPPHIRE_RAPIDS_ISA),
+    ("AuthenticAMD", "k8", K8_ISA),
+    ("AuthenticAMD", "k8-sse3", K8_SSE3_ISA),
+    ("AuthenticAMD", "amdfam10", AMDFAM10_ISA),
+    ("AuthenticAMD", "bdver1", BDVER1_ISA),
+    ("AuthenticAMD", "bdver2", BDVER2_ISA),
+    ("AuthenticAMD", "znver1", ZNVER1_ISA),
+    ("AuthenticAMD", "znver2", ZNVER2_ISA),
+    ("AuthenticAMD", "znver3", ZNVER3_ISA),
+    ("AuthenticAMD", "znver4", ZNVER4_ISA),
+];
+
+// Instruction set extensions added by each `-march=` target. Unlike the
+// model features below, they leave out system features (e.g., SVM, VME,
+// MONITOR or ARAT) that don't affect the generated code and are often hidden
+// by hypervisors. Ice Lake doesn't have Cooper Lake's BF16, and Zen dropped
+// FMA4, XOP and TBM.
+
+const BASELINE_ISA_FEATURES: &[Feature] = &[Fpu, Cx8, Cmov, Mmx, Fxsr, Sse, Sse2, Lm];
+const CORE2_ISA_FEATURES: &[Feature] = &[Sse3, Ssse3, LahfLm];
+const NEHALEM_ISA_FEATURES: &[Feature] = &[Sse41, Sse42, Popcnt, Cmpxchg16b];
+const WESTMERE_ISA_FEATURES: &[Feature] = &[Aesni, Pclmulqdq];
+const SANDY_BRIDGE_ISA_FEATURES: &[Feature] = &[Avx, Xsave, Xsaveopt];
+const IVY_BRIDGE_ISA_FEATURES: &[Feature] = &[F16c, Rdrand, Fsgsbase];
+const HASWELL_ISA_FEATURES: &[Feature] = &[Fma, Movbe, Bmi1, Bmi2, Avx2, Lzcnt];
+const BROADWELL_ISA_FEATURES: &[Feature] = &[Rdseed, Adx, Prefetchw];
+const SKYLAKE_ISA_FEATURES: &[Feature] = &[Clflushopt, Xsavec, Xsaves];
+const SKYLAKE_AVX512_ISA_FEATURES: &[Feature] =
+    &[Avx512f, Avx512dq, Avx512bw, Avx512cd, Avx512vl, Clwb, Pku];
+const CASCADELAKE_ISA_FEATURES: &[Feature] = &[Avx512vnni];
+const COOPERLAKE_ISA_FEATURES: &[Feature] = &[Avx512bf16];
+const ICELAKE_SERVER_ISA_FEATURES: &[Feature] = &[
+    Avx512vbmi,
+    Avx512vbmi2,
+    Avx512ifma,
+    Avx512bitalg,
+    Avx512vpopcntdq,
+    Gfni,
+    Vaes,
+    Vpclmulqdq,
+    Sha,
+    Rdpid,
+    Wbnoinvd,
+];
+const SAPPHIRE_RAPIDS_ISA_FEATURES: &[Feature] = &[
+    AvxVnni, Avx512fp16, AmxBf16, AmxTile, AmxInt8, Serialize, Cldemote, Movdiri, Movdir64b,
+];
+const K8_SSE3_ISA_FEATURES: &[Feature] = &[Sse3];
+const AMDFAM10_ISA_FEATURES: &[Feature] = &[Sse4a, Popcnt, Lzcnt, Cmpxchg16b, LahfLm];
+const BDVER1_ISA_FEATURES: &[Feature] = &[
+    Ssse3, Sse41, Sse42, Aesni, Pclmulqdq, Avx, Xsave, Fma4, Xop, Prefetchw,
+];
+const BDVER2_ISA_FEATURES: &[Feature] = &[F16c, Fma, Tbm];
+const ZNVER1_ISA_FEATURES: &[Feature] = &[
+    Ssse3, Sse41, Sse42, Aesni, Pclmulqdq, Avx, Avx2, F16c, Fma, Bmi1, Bmi2, Movbe, Xsave,
+    Xsaveopt, Xsavec, Fsgsbase, Rdrand, Rdseed, Adx, Sha, Clflushopt, Clzero, Prefetchw,
+];
+const ZNVER2_ISA_FEATURES: &[Feature] = &[Clwb, Rdpid, Wbnoinvd];
+const ZNVER3_ISA_FEATURES: &[Feature] = &[Vaes, Vpclmulqdq, Pku];
+const ZNVER4_ISA_FEATURES: &[Feature] = &[
+    Avx512f,
+    Avx512dq,
+    Avx512ifma,
+    Avx512cd,
+    Avx512bw,
+    Avx512vl,
+    Avx512vbmi,
+    Avx512vbmi2,
+    Avx512vnni,
+    Avx512bitalg,
+    Avx512vpopcntdq,
+    Gfni,
+    Avx512bf16,
+];
+
+const CORE2_ISA: &[&[Feature]] = &[BASELINE_ISA_FEATURES, CORE2_ISA_FEATURES];
+const NEHALEM_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+];
+const WESTMERE_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+    WESTMERE_ISA_FEATURES,
+];
+const SANDY_BRIDGE_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+    WESTMERE_ISA_FEATURES,
+    SANDY_BRIDGE_ISA_FEATURES,
+];
+const IVY_BRIDGE_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+    WESTMERE_ISA_FEATURES,
+    SANDY_BRIDGE_ISA_FEATURES,
+    IVY_BRIDGE_ISA_FEATURES,
+];
+const HASWELL_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+    WESTMERE_ISA_FEATURES,
+    SANDY_BRIDGE_ISA_FEATURES,
+    IVY_BRIDGE_ISA_FEATURES,
+    HASWELL_ISA_FEATURES,
+];
+const BROADWELL_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+    WESTMERE_ISA_FEATURES,
+    SANDY_BRIDGE_ISA_FEATURES,
+    IVY_BRIDGE_ISA_FEATURES,
+    HASWELL_ISA_FEATURES,
+    BROADWELL_ISA_FEATURES,
+];
+const SKYLAKE_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+    WESTMERE_ISA_FEATURES,
+    SANDY_BRIDGE_ISA_FEATURES,
+    IVY_BRIDGE_ISA_FEATURES,
+    HASWELL_ISA_FEATURES,
+    BROADWELL_ISA_FEATURES,
+    SKYLAKE_ISA_FEATURES,
+];
+const SKYLAKE_AVX512_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+    WESTMERE_ISA_FEATURES,
+    SANDY_BRIDGE_ISA_FEATURES,
+    IVY_BRIDGE_ISA_FEATURES,
+    HASWELL_ISA_FEATURES,
+    BROADWELL_ISA_FEATURES,
+    SKYLAKE_ISA_FEATURES,
+    SKYLAKE_AVX512_ISA_FEATURES,
+];
+const CASCADELAKE_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+    WESTMERE_ISA_FEATURES,
+    SANDY_BRIDGE_ISA_FEATURES,
+    IVY_BRIDGE_ISA_FEATURES,
+    HASWELL_ISA_FEATURES,
+    BROADWELL_ISA_FEATURES,
+    SKYLAKE_ISA_FEATURES,
+    SKYLAKE_AVX512_ISA_FEATURES,
+    CASCADELAKE_ISA_FEATURES,
+];
+const COOPERLAKE_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+    WESTMERE_ISA_FEATURES,
+    SANDY_BRIDGE_ISA_FEATURES,
+    IVY_BRIDGE_ISA_FEATURES,
+    HASWELL_ISA_FEATURES,
+    BROADWELL_ISA_FEATURES,
+    SKYLAKE_ISA_FEATURES,
+    SKYLAKE_AVX512_ISA_FEATURES,
+    CASCADELAKE_ISA_FEATURES,
+    COOPERLAKE_ISA_FEATURES,
+];
+const ICELAKE_SERVER_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+    WESTMERE_ISA_FEATURES,
+    SANDY_BRIDGE_ISA_FEATURES,
+    IVY_BRIDGE_ISA_FEATURES,
+    HASWELL_ISA_FEATURES,
+    BROADWELL_ISA_FEATURES,
+    SKYLAKE_ISA_FEATURES,
+    SKYLAKE_AVX512_ISA_FEATURES,
+    CASCADELAKE_ISA_FEATURES,
+    ICELAKE_SERVER_ISA_FEATURES,
+];
+const SAPPHIRE_RAPIDS_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    CORE2_ISA_FEATURES,
+    NEHALEM_ISA_FEATURES,
+    WESTMERE_ISA_FEATURES,
+    SANDY_BRIDGE_ISA_FEATURES,
+    IVY_BRIDGE_ISA_FEATURES,
+    HASWELL_ISA_FEATURES,
+    BROADWELL_ISA_FEATURES,
+    SKYLAKE_ISA_FEATURES,
+    SKYLAKE_AVX512_ISA_FEATURES,
+    CASCADELAKE_ISA_FEATURES,
+    COOPERLAKE_ISA_FEATURES,
+    ICELAKE_SERVER_ISA_FEATURES,
+    SAPPHIRE_RAPIDS_ISA_FEATURES,
+];
+const K8_ISA: &[&[Feature]] = &[BASELINE_ISA_FEATURES];
+const K8_SSE3_ISA: &[&[Feature]] = &[BASELINE_ISA_FEATURES, K8_SSE3_ISA_FEATURES];
+const AMDFAM10_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    K8_SSE3_ISA_FEATURES,
+    AMDFAM10_ISA_FEATURES,
+];
+const BDVER1_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    K8_SSE3_ISA_FEATURES,
+    AMDFAM10_ISA_FEATURES,
+    BDVER1_ISA_FEATURES,
+];
+const BDVER2_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    K8_SSE3_ISA_FEATURES,
+    AMDFAM10_ISA_FEATURES,
+    BDVER1_ISA_FEATURES,
+    BDVER2_ISA_FEATURES,
+];
+const ZNVER1_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    K8_SSE3_ISA_FEATURES,
+    AMDFAM10_ISA_FEATURES,
+    ZNVER1_ISA_FEATURES,
+];
+const ZNVER2_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    K8_SSE3_ISA_FEATURES,
+    AMDFAM10_ISA_FEATURES,
+    ZNVER1_ISA_FEATURES,
+    ZNVER2_ISA_FEATURES,
+];
+const ZNVER3_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    K8_SSE3_ISA_FEATURES,
+    AMDFAM10_ISA_FEATURES,
+    ZNVER1_ISA_FEATURES,
+    ZNVER2_ISA_FEATURES,
+    ZNVER3_ISA_FEATURES,
+];
+const ZNVER4_ISA: &[&[Feature]] = &[
+    BASELINE_ISA_FEATURES,
+    K8_SSE3_ISA_FEATURES,
+    AMDFAM10_ISA_FEATURES,
+    ZNVER1_ISA_FEATURES,
+    ZNVER2_ISA_FEATURES,
+    ZNVER3_ISA_FEATURES,
+    ZNVER4_ISA_FEATURES,
+];
+
+// Features added by each generation. A model lists the generations it
+// includes, which isn't always a strict superset of its predecessor (e.g.,
+// EPYC dropped FMA4, XOP and TBM).
+
+const BASELINE_FEATURES: &[Feature] = &[
+    Fpu, De, Pse, Tsc, Msr, Pae, Mce, Cx8, Apic, Sep, Mtrr, Pge, Mca, Cmov, Pat, Pse36, Clflush,
+    Mmx, Fxsr, Sse, Sse2, Sse3, Lm, Nx, Syscall,
+];
+
+const CONROE_FEATURES: &[Feature] = &[Vme, Ssse3, LahfLm];
+const PENRYN_FEATURES: &[Feature] = &[Sse41, Cmpxchg16b];
+const NEHALEM_FEATURES: &[Feature] = &[Sse42, Popcnt];
+const WESTMERE_FEATURES: &[Feature] = &[Aesni, Pclmulqdq, Arat];
+const SANDY_BRIDGE_FEATURES: &[Feature] = &[Avx, Xsave, TscDeadline, X2apic, Rdtscp, Xsaveopt];
+const IVY_BRIDGE_FEATURES: &[Feature] = &[F16c, Rdrand, Fsgsbase, Smep, Erms];
+const HASWELL_FEATURES: &[Feature] = &[Fma, Movbe, Pcid, Bmi1, Avx2, Bmi2, Invpcid, Lzcnt];
+const BROADWELL_FEATURES: &[Feature] = &[Rdseed, Adx, Smap, Prefetchw];
+const SKYLAKE_CLIENT_FEATURES: &[Feature] = &[Clflushopt, Xsavec, Xgetbv1, Xsaves];
+const SKYLAKE_SERVER_FEATURES: &[Feature] = &[
+    Pdpe1gb, Clwb, Avx512f, Avx512dq, Avx512bw, Avx512cd, Avx512vl, Pku,
+];
+const CASCADELAKE_SERVER_FEATURES: &[Feature] = &[Avx512vnni];
+const COOPERLAKE_FEATURES: &[Feature] = &[Avx512bf16];
+const ICELAKE_SERVER_FEATURES: &[Feature] = &[
+    Avx512vbmi,
+    Avx512vbmi2,
+    Avx512ifma,
+    Avx512bitalg,
+    Avx512vpopcntdq,
+    Gfni,
+    Vaes,
+    Vpclmulqdq,
+    Sha,
+    Rdpid,
+    Wbnoinvd,
+];
+const SAPPHIRE_RAPIDS_FEATURES: &[Feature] = &[
+    AvxVnni, Avx512fp16, AmxBf16, AmxTile, AmxInt8, Serialize, Cldemote, Movdiri, Movdir64b,
+];
+
+const OPTERON_G1_FEATURES: &[Feature] = &[Vme];
+const OPTERON_G2_FEATURES: &[Feature] = &[Cmpxchg16b, Rdtscp, Svm, LahfLm];
+const OPTERON_G3_FEATURES: &[Feature] = &[Popcnt, MisalignSse, Sse4a, Lzcnt];
+const OPTERON_G4_FEATURES: &[Feature] = &[
+    Avx, Xsave, Aesni, Sse42, Sse41, Ssse3, Pclmulqdq, Pdpe1gb, Fma4, Xop, Prefetchw,
+];
+const OPTERON_G5_FEATURES: &[Feature] = &[F16c, Fma, Tbm];
+const EPYC_FEATURES: &[Feature] = &[
+    Monitor, Ssse3, Fma, Sse41, Sse42, Movbe, Aesni, Pclmulqdq, Xsave, Avx, F16c, Rdrand, Pdpe1gb,
+    FxsrOpt, MmxExt, Osvw, Prefetchw, Cr8Legacy, TopoExt, Fsgsbase, Bmi1, Avx2, Smep, Bmi2, Rdseed,
+    Adx, Smap, Clflushopt, Sha, Clzero, XsaveErPtr, Xsaveopt, Xsavec, Xgetbv1, Arat,
+];
+const EPYC_ROME_FEATURES: &[Feature] = &[Clwb, Umip, Rdpid, Wbnoinvd, PerfCtrCore, Xsaves];
+const EPYC_MILAN_FEATURES: &[Feature] = &[Pcid, Erms, Invpcid, Pku, Vaes, Vpclmulqdq];
+const EPYC_GENOA_FEATURES: &[Feature] = &[
+    Avx512f,
+    Avx512dq,
+    Avx512ifma,
+    Avx512cd,
+    Avx512bw,
+    Avx512vl,
+    Avx512vbmi,
+    Avx512vbmi2,
+    Avx512vnni,
+    Avx512bitalg,
+    Avx512vpopcntdq,
+    Gfni,
+    Avx512bf16,
+];
+
+const CONROE: &[&[Feature]] = &[BASELINE_FEATURES, CONROE_FEATURES];
+const PENRYN: &[&[Feature]] = &[BASELINE_FEATURES, CONROE_FEATURES, PENRYN_FEATURES];
+const NEHALEM: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+];
+const WESTMERE: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+    WESTMERE_FEATURES,
+];
+const SANDY_BRIDGE: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+    WESTMERE_FEATURES,
+    SANDY_BRIDGE_FEATURES,
+];
+const IVY_BRIDGE: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+    WESTMERE_FEATURES,
+    SANDY_BRIDGE_FEATURES,
+    IVY_BRIDGE_FEATURES,
+];
+const HASWELL: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+    WESTMERE_FEATURES,
+    SANDY_BRIDGE_FEATURES,
+    IVY_BRIDGE_FEATURES,
+    HASWELL_FEATURES,
+];
+const BROADWELL: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+    WESTMERE_FEATURES,
+    SANDY_BRIDGE_FEATURES,
+    IVY_BRIDGE_FEATURES,
+    HASWELL_FEATURES,
+    BROADWELL_FEATURES,
+];
+const SKYLAKE_CLIENT: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+    WESTMERE_FEATURES,
+    SANDY_BRIDGE_FEATURES,
+    IVY_BRIDGE_FEATURES,
+    HASWELL_FEATURES,
+    BROADWELL_FEATURES,
+    SKYLAKE_CLIENT_FEATURES,
+];
+const SKYLAKE_SERVER: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+    WESTMERE_FEATURES,
+    SANDY_BRIDGE_FEATURES,
+    IVY_BRIDGE_FEATURES,
+    HASWELL_FEATURES,
+    BROADWELL_FEATURES,
+    SKYLAKE_CLIENT_FEATURES,
+    SKYLAKE_SERVER_FEATURES,
+];
+const CASCADELAKE_SERVER: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+    WESTMERE_FEATURES,
+    SANDY_BRIDGE_FEATURES,
+    IVY_BRIDGE_FEATURES,
+    HASWELL_FEATURES,
+    BROADWELL_FEATURES,
+    SKYLAKE_CLIENT_FEATURES,
+    SKYLAKE_SERVER_FEATURES,
+    CASCADELAKE_SERVER_FEATURES,
+];
+const COOPERLAKE: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+    WESTMERE_FEATURES,
+    SANDY_BRIDGE_FEATURES,
+    IVY_BRIDGE_FEATURES,
+    HASWELL_FEATURES,
+    BROADWELL_FEATURES,
+    SKYLAKE_CLIENT_FEATURES,
+    SKYLAKE_SERVER_FEATURES,
+    CASCADELAKE_SERVER_FEATURES,
+    COOPERLAKE_FEATURES,
+];
+const ICELAKE_SERVER: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+    WESTMERE_FEATURES,
+    SANDY_BRIDGE_FEATURES,
+    IVY_BRIDGE_FEATURES,
+    HASWELL_FEATURES,
+    BROADWELL_FEATURES,
+    SKYLAKE_CLIENT_FEATURES,
+    SKYLAKE_SERVER_FEATURES,
+    CASCADELAKE_SERVER_FEATURES,
+    ICELAKE_SERVER_FEATURES,
+];
+const SAPPHIRE_RAPIDS: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    CONROE_FEATURES,
+    PENRYN_FEATURES,
+    NEHALEM_FEATURES,
+    WESTMERE_FEATURES,
+    SANDY_BRIDGE_FEATURES,
+    IVY_BRIDGE_FEATURES,
+    HASWELL_FEATURES,
+    BROADWELL_FEATURES,
+    SKYLAKE_CLIENT_FEATURES,
+    SKYLAKE_SERVER_FEATURES,
+    CASCADELAKE_SERVER_FEATURES,
+    COOPERLAKE_FEATURES,
+    ICELAKE_SERVER_FEATURES,
+    SAPPHIRE_RAPIDS_FEATURES,
+];
+
+const OPTERON_G1: &[&[Feature]] = &[BASELINE_FEATURES, OPTERON_G1_FEATURES];
+const OPTERON_G2: &[&[Feature]] = &[BASELINE_FEATURES, OPTERON_G1_FEATURES, OPTERON_G2_FEATURES];
+const OPTERON_G3: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    OPTERON_G1_FEATURES,
+    OPTERON_G2_FEATURES,
+    OPTERON_G3_FEATURES,
+    // QEMU drops MONITOR again starting with Opteron_G4.
+    &[Monitor],
+];
+const OPTERON_G4: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    OPTERON_G1_FEATURES,
+    OPTERON_G2_FEATURES,
+    OPTERON_G3_FEATURES,
+    OPTERON_G4_FEATURES,
+];
+const OPTERON_G5: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    OPTERON_G1_FEATURES,
+    OPTERON_G2_FEATURES,
+    OPTERON_G3_FEATURES,
+    OPTERON_G4_FEATURES,
+    OPTERON_G5_FEATURES,
+];
+const EPYC: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    OPTERON_G1_FEATURES,
+    OPTERON_G2_FEATURES,
+    OPTERON_G3_FEATURES,
+    EPYC_FEATURES,
+];
+const EPYC_ROME: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    OPTERON_G1_FEATURES,
+    OPTERON_G2_FEATURES,
+    OPTERON_G3_FEATURES,
+    EPYC_FEATURES,
+    EPYC_ROME_FEATURES,
+];
+const EPYC_MILAN: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    OPTERON_G1_FEATURES,
+    OPTERON_G2_FEATURES,
+    OPTERON_G3_FEATURES,
+    EPYC_FEATURES,
+    EPYC_ROME_FEATURES,
+    EPYC_MILAN_FEATURES,
+];
+const EPYC_GENOA: &[&[Feature]] = &[
+    BASELINE_FEATURES,
+    OPTERON_G1_FEATURES,
+    OPTERON_G2_FEATURES,
+    OPTERON_G3_FEATURES,
+    EPYC_FEATURES,
+    EPYC_ROME_FEATURES,
+    EPYC_MILAN_FEATURES,
+    EPYC_GENOA_FEATURES,
+];
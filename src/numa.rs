@@ -0,0 +1,114 @@
+//! Map APIC IDs to NUMA nodes using the node IDs of AMD's processor topology
+//! leaf (LEAF=0x8000_001E).
+//!
+//! This lets allocators and thread pools do NUMA-aware placement without
+//! reading sysfs (or on systems without it). The node IDs are those of the
+//! CPU's internal fabric; with memory interleaving enabled they may not match
+//! the nodes the OS reports.
+use alloc::vec::Vec;
+
+use crate::CpuIdSnapshot;
+
+/// The logical processors of one NUMA node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct NumaNode {
+    /// Node ID reported by the CPU.
+    pub node_id: u8,
+    /// Extended APIC IDs of the logical processors in the node, ordered like
+    /// [`NumaNode::cores`].
+    pub apic_ids: Vec<u32>,
+    /// The cores (as passed to [`NumaTopology::from_snapshots`]) in the node.
+    pub cores: Vec<usize>,
+}
+
+/// The logical processors grouped by NUMA node.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "std")]
+/// # {
+/// use raw_cpuid::NumaTopology;
+///
+/// let numa = NumaTopology::from_all_cores().unwrap();
+/// for node in numa.nodes.iter() {
+///     println!("node {}: cores {:?}", node.node_id, node.cores);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct NumaTopology {
+    /// The nodes, ordered by node ID.
+    pub nodes: Vec<NumaNode>,
+    /// Number of nodes per processor (package).
+    pub nodes_per_processor: u8,
+}
+
+impl NumaTopology {
+    /// Groups the `(core, snapshot)` pairs by node ID.
+    ///
+    /// Cores whose snapshot doesn't report a node
+    /// ([`crate::CpuId::get_processor_topology_info`] returns `None`) are
+    /// left out, so the topology is empty on Intel CPUs.
+    pub fn from_snapshots(snapshots: &[(usize, CpuIdSnapshot)]) -> Self {
+        let mut topology = NumaTopology::default();
+        for (core, snapshot) in snapshots {
+            let Some(info) = snapshot.cpuid().get_processor_topology_info() else {
+                continue;
+            };
+            topology.nodes_per_processor =
+                topology.nodes_per_processor.max(info.nodes_per_processor());
+
+            let node_id = info.node_id();
+            let idx = match topology.nodes.iter().position(|n| n.node_id == node_id) {
+                Some(idx) => idx,
+                None => {
+                    topology.nodes.push(NumaNode {
+                        node_id,
+                        apic_ids: Vec::new(),
+                        cores: Vec::new(),
+                    });
+                    topology.nodes.len() - 1
+                }
+            };
+            topology.nodes[idx].apic_ids.push(info.extended_apic_id());
+            topology.nodes[idx].cores.push(*core);
+        }
+        topology.nodes.sort_by_key(|n| n.node_id);
+        topology
+    }
+
+    /// Reads the node of every core (see [`CpuIdSnapshot::from_all_cores`]).
+    #[cfg(feature = "std")]
+    pub fn from_all_cores() -> std::io::Result<Self> {
+        let snapshots = CpuIdSnapshot::from_all_cores()?;
+        Ok(Self::from_snapshots(&snapshots))
+    }
+
+    /// Did any core report a node?
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The node with the given ID.
+    pub fn node(&self, node_id: u8) -> Option<&NumaNode> {
+        self.nodes.iter().find(|n| n.node_id == node_id)
+    }
+
+    /// The node of the logical processor with the given extended APIC ID.
+    pub fn node_of_apic_id(&self, apic_id: u32) -> Option<u8> {
+        self.nodes
+            .iter()
+            .find(|n| n.apic_ids.contains(&apic_id))
+            .map(|n| n.node_id)
+    }
+
+    /// The node of the given core.
+    pub fn node_of_core(&self, core: usize) -> Option<u8> {
+        self.nodes
+            .iter()
+            .find(|n| n.cores.contains(&core))
+            .map(|n| n.node_id)
+    }
+}
@@ -0,0 +1,232 @@
+//! Feature flag names as used by QEMU's `-cpu` option.
+#[cfg(any(test, feature = "alloc"))]
+use alloc::string::String;
+
+use crate::Feature;
+#[cfg(any(test, feature = "alloc"))]
+use crate::{CpuIdDiff, FeatureChange};
+
+impl Feature {
+    /// Name of the flag as a QEMU CPU property, e.g., `sse4.1` or `avx512-vpopcntdq`.
+    ///
+    /// Returns `None` for flags QEMU can't configure (e.g., `osxsave`, which
+    /// reflects the state of the guest OS).
+    pub fn qemu_name(&self) -> Option<&'static str> {
+        use Feature::*;
+
+        let name = match self {
+            Sse3 => "pni",
+            Pclmulqdq => "pclmulqdq",
+            Dtes64 => "dtes64",
+            Monitor => "monitor",
+            DsCpl => "ds-cpl",
+            Vmx => "vmx",
+            Smx => "smx",
+            Eist => "est",
+            Tm2 => "tm2",
+            Ssse3 => "ssse3",
+            CnxtId => "cid",
+            Fma => "fma",
+            Cmpxchg16b => "cx16",
+            Pdcm => "pdcm",
+            Pcid => "pcid",
+            Dca => "dca",
+            Sse41 => "sse4.1",
+            Sse42 => "sse4.2",
+            X2apic => "x2apic",
+            Movbe => "movbe",
+            Popcnt => "popcnt",
+            TscDeadline => "tsc-deadline",
+            Aesni => "aes",
+            Xsave => "xsave",
+            Avx => "avx",
+            F16c => "f16c",
+            Rdrand => "rdrand",
+            Hypervisor => "hypervisor",
+            Fpu => "fpu",
+            Vme => "vme",
+            De => "de",
+            Pse => "pse",
+            Tsc => "tsc",
+            Msr => "msr",
+            Pae => "pae",
+            Mce => "mce",
+            Cx8 => "cx8",
+            Apic => "apic",
+            Sep => "sep",
+            Mtrr => "mtrr",
+            Pge => "pge",
+            Mca => "mca",
+            Cmov => "cmov",
+            Pat => "pat",
+            Pse36 => "pse36",
+            Psn => "pn",
+            Clflush => "clflush",
+            Ds => "ds",
+            Acpi => "acpi",
+            Mmx => "mmx",
+            Fxsr => "fxsr",
+            Sse => "sse",
+            Sse2 => "sse2",
+            Ss => "ss",
+            Htt => "ht",
+            Tm => "tm",
+            Pbe => "pbe",
+            Arat => "arat",
+            Fsgsbase => "fsgsbase",
+            TscAdjust => "tsc-adjust",
+            Sgx => "sgx",
+            Bmi1 => "bmi1",
+            Hle => "hle",
+            Avx2 => "avx2",
+            FdpExcptnOnly => "fdp-excptn-only",
+            Smep => "smep",
+            Bmi2 => "bmi2",
+            Erms => "erms",
+            Invpcid => "invpcid",
+            Rtm => "rtm",
+            ZeroFcsFds => "zero-fcs-fds",
+            Mpx => "mpx",
+            Avx512f => "avx512f",
+            Avx512dq => "avx512dq",
+            Rdseed => "rdseed",
+            Adx => "adx",
+            Smap => "smap",
+            Avx512ifma => "avx512ifma",
+            Clflushopt => "clflushopt",
+            Clwb => "clwb",
+            IntelPt => "intel-pt",
+            Avx512pf => "avx512pf",
+            Avx512er => "avx512er",
+            Avx512cd => "avx512cd",
+            Sha => "sha-ni",
+            Avx512bw => "avx512bw",
+            Avx512vl => "avx512vl",
+            Prefetchwt1 => "prefetchwt1",
+            Avx512vbmi => "avx512vbmi",
+            Umip => "umip",
+            Pku => "pku",
+            Waitpkg => "waitpkg",
+            Avx512vbmi2 => "avx512vbmi2",
+            Gfni => "gfni",
+            Vaes => "vaes",
+            Vpclmulqdq => "vpclmulqdq",
+            Avx512vnni => "avx512vnni",
+            Avx512bitalg => "avx512bitalg",
+            Avx512vpopcntdq => "avx512-vpopcntdq",
+            La57 => "la57",
+            Rdpid => "rdpid",
+            Cldemote => "cldemote",
+            Movdiri => "movdiri",
+            Movdir64b => "movdir64b",
+            Enqcmd => "enqcmd",
+            SgxLc => "sgxlc",
+            Pks => "pks",
+            Avx5124vnniw => "avx512-4vnniw",
+            Avx5124fmaps => "avx512-4fmaps",
+            Avx512vp2intersect => "avx512-vp2intersect",
+            Serialize => "serialize",
+            Tsxldtrk => "tsx-ldtrk",
+            ArchLbr => "arch-lbr",
+            AmxBf16 => "amx-bf16",
+            Avx512fp16 => "avx512-fp16",
+            AmxTile => "amx-tile",
+            AmxInt8 => "amx-int8",
+            AvxVnni => "avx-vnni",
+            Avx512bf16 => "avx512-bf16",
+            Cmpccxadd => "cmpccxadd",
+            Wrmsrns => "wrmsrns",
+            AmxFp16 => "amx-fp16",
+            AvxVnniInt8 => "avx-vnni-int8",
+            AvxNeConvert => "avx-ne-convert",
+            AmxComplex => "amx-complex",
+            AvxVnniInt16 => "avx-vnni-int16",
+            Prefetchi => "prefetchiti",
+            Avx10 => "avx10",
+            ApxF => "apx",
+            Xsaveopt => "xsaveopt",
+            Xsavec => "xsavec",
+            Xgetbv1 => "xgetbv1",
+            Xsaves => "xsaves",
+            LahfLm => "lahf-lm",
+            CmpLegacy => "cmp-legacy",
+            Svm => "svm",
+            ExtApic => "extapic",
+            Cr8Legacy => "cr8legacy",
+            Lzcnt => "abm",
+            Sse4a => "sse4a",
+            MisalignSse => "misalignsse",
+            Prefetchw => "3dnowprefetch",
+            Osvw => "osvw",
+            Ibs => "ibs",
+            Xop => "xop",
+            Skinit => "skinit",
+            Wdt => "wdt",
+            Lwp => "lwp",
+            Fma4 => "fma4",
+            Tce => "tce",
+            NodeIdMsr => "nodeid-msr",
+            Tbm => "tbm",
+            TopoExt => "topoext",
+            PerfCtrCore => "perfctr-core",
+            PerfCtrNb => "perfctr-nb",
+            Syscall => "syscall",
+            Nx => "nx",
+            MmxExt => "mmxext",
+            FxsrOpt => "fxsr-opt",
+            Pdpe1gb => "pdpe1gb",
+            Rdtscp => "rdtscp",
+            Lm => "lm",
+            ThreeDNowExt => "3dnowext",
+            ThreeDNow => "3dnow",
+            InvariantTsc => "invtsc",
+            Clzero => "clzero",
+            XsaveErPtr => "xsaveerptr",
+            Wbnoinvd => "wbnoinvd",
+            _ => return None,
+        };
+        Some(name)
+    }
+
+    /// Look up a feature by its [`Feature::qemu_name`].
+    pub fn from_qemu_name(name: &str) -> Option<Feature> {
+        Feature::ALL
+            .iter()
+            .copied()
+            .find(|f| f.qemu_name() == Some(name))
+    }
+}
+
+#[cfg(any(test, feature = "alloc"))]
+impl CpuIdDiff {
+    /// Formats the feature changes as QEMU `-cpu` option starting with
+    /// `model`, e.g., `host,-avx512f,+hypervisor`.
+    ///
+    /// Features without a [`Feature::qemu_name`] are left out.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{CpuIdBuilder, CpuIdSnapshot, Feature};
+    ///
+    /// let host = CpuIdSnapshot::from_host();
+    /// let mut builder = CpuIdBuilder::with_cpuid_reader(&host);
+    /// builder.set_feature(Feature::Sse2, false);
+    /// let guest = CpuIdSnapshot::from(builder.build());
+    ///
+    /// assert_eq!(host.diff(&guest).qemu_cpu_option("host"), "host,-sse2");
+    /// ```
+    pub fn qemu_cpu_option(&self, model: &str) -> String {
+        let mut option = String::from(model);
+        for change in &self.features {
+            if let Some(name) = change.feature().qemu_name() {
+                option.push(',');
+                option.push(match change {
+                    FeatureChange::Added(_) => '+',
+                    FeatureChange::Removed(_) => '-',
+                });
+                option.push_str(name);
+            }
+        }
+        option
+    }
+}
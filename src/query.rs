@@ -0,0 +1,347 @@
+//! Decode any `(leaf, subleaf)` with a single call, e.g., to walk over all
+//! leafs of a dump and still get the typed view of the ones this library
+//! knows.
+use core::fmt::{self, Debug, Formatter};
+
+use crate::{
+    get_bits, ApmInfo, ArchPerfmonExtInfo, Avx10Info, CacheInfoIter, CacheParameter, CpuId,
+    CpuIdReader, CpuIdReaderNative, CpuIdResult, DatInfo, DirectCacheAccessInfo, EpcSection,
+    ExtendedCpuTopologyLevel, ExtendedFeatures, ExtendedProcessorFeatureIdentifiers, ExtendedState,
+    ExtendedStateInfo, ExtendedTopologyLevel, FeatureInfo, HresetInfo, HybridInfo, HypervisorInfo,
+    L1CacheTlbInfo, L2And3CacheTlbInfo, LbrInfo, MemoryEncryptionInfo, MonitorMwaitInfo,
+    PadLockInfo, PconfigIter, PerformanceMonitoringDebugInfo, PerformanceMonitoringInfo,
+    ProcessorBrandString, ProcessorCapacityAndFeatureInfo, ProcessorFrequencyInfo, ProcessorSerial,
+    ProcessorTopologyInfo, ProcessorTraceInfo, RdtAllocationInfo, RdtMonitoringInfo, SgxInfo,
+    SoCVendorInfo, SvmFeatures, ThermalPowerInfo, Tlb1gPageInfo, TmulInfo, TransmetaCmsVersion,
+    TransmetaInfoString, TransmetaProcessorInfo, TscInfo, VendorInfo, EAX_ADVANCED_POWER_MGMT_INFO,
+    EAX_ARCH_PERFMON_EXT_INFO, EAX_AVX10_INFO, EAX_CACHE_INFO, EAX_CACHE_PARAMETERS,
+    EAX_CACHE_PARAMETERS_AMD, EAX_CENTAUR_FEATURE_INFO, EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO,
+    EAX_DIRECT_CACHE_ACCESS_INFO, EAX_EXTENDED_BRAND_STRING, EAX_EXTENDED_CPU_TOPOLOGY,
+    EAX_EXTENDED_PROCESSOR_AND_FEATURE_IDENTIFIERS, EAX_EXTENDED_STATE_INFO,
+    EAX_EXTENDED_TOPOLOGY_INFO, EAX_EXTENDED_TOPOLOGY_INFO_V2, EAX_FEATURE_INFO,
+    EAX_FREQUENCY_INFO, EAX_HRESET_INFO, EAX_HYBRID_INFO, EAX_HYPERVISOR_INFO, EAX_L1_CACHE_INFO,
+    EAX_L2_L3_CACHE_INFO, EAX_LBR_INFO, EAX_MEMORY_ENCRYPTION_INFO, EAX_MONITOR_MWAIT_INFO,
+    EAX_PCONFIG_INFO, EAX_PERFORMANCE_MONITORING_DEBUG_INFO, EAX_PERFORMANCE_MONITOR_INFO,
+    EAX_PROCESSOR_CAPACITY_INFO, EAX_PROCESSOR_SERIAL, EAX_PROCESSOR_TOPOLOGY_INFO,
+    EAX_RDT_ALLOCATION, EAX_RDT_MONITORING, EAX_SGX, EAX_SOC_VENDOR_INFO,
+    EAX_STRUCTURED_EXTENDED_FEATURE_INFO, EAX_SVM_FEATURES, EAX_THERMAL_POWER_INFO,
+    EAX_TIME_STAMP_COUNTER_INFO, EAX_TLB_1G_PAGE_INFO, EAX_TMUL_INFO, EAX_TRACE_INFO,
+    EAX_TRANSMETA_CMS_VERSION, EAX_TRANSMETA_INFO_STRING, EAX_TRANSMETA_PROCESSOR_INFO,
+    EAX_VENDOR_INFO,
+};
+
+/// The `(leaf, subleaf)` to decode with [`CpuId::query`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LeafRequest {
+    pub leaf: u32,
+    pub subleaf: u32,
+}
+
+impl LeafRequest {
+    /// Requests subleaf 0 of `leaf`.
+    pub fn new(leaf: u32) -> Self {
+        Self { leaf, subleaf: 0 }
+    }
+
+    /// Requests `subleaf` of `leaf`.
+    pub fn with_subleaf(leaf: u32, subleaf: u32) -> Self {
+        Self { leaf, subleaf }
+    }
+}
+
+impl From<u32> for LeafRequest {
+    fn from(leaf: u32) -> Self {
+        Self::new(leaf)
+    }
+}
+
+impl From<(u32, u32)> for LeafRequest {
+    fn from((leaf, subleaf): (u32, u32)) -> Self {
+        Self::with_subleaf(leaf, subleaf)
+    }
+}
+
+macro_rules! leaf_data {
+    ($($(#[$doc:meta])* $variant:ident($t:ty),)*) => {
+        /// A decoded `(leaf, subleaf)`, see [`CpuId::query`].
+        ///
+        /// Each variant wraps the type returned by the corresponding
+        /// accessor of [`CpuId`]. Leafs (or subleafs) this library doesn't
+        /// decode are returned as [`LeafData::Raw`].
+        #[non_exhaustive]
+        pub enum LeafData<R: CpuIdReader = CpuIdReaderNative> {
+            $($(#[$doc])* $variant($t),)*
+            /// Registers of a leaf that isn't decoded.
+            Raw(CpuIdResult),
+        }
+
+        impl<R: CpuIdReader> Debug for LeafData<R> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(LeafData::$variant(data) => {
+                        f.debug_tuple(stringify!($variant)).field(data).finish()
+                    })*
+                    LeafData::Raw(res) => f.debug_tuple("Raw").field(res).finish(),
+                }
+            }
+        }
+    };
+}
+
+leaf_data! {
+    /// LEAF=0x00.
+    VendorInfo(VendorInfo),
+    /// LEAF=0x01.
+    FeatureInfo(FeatureInfo),
+    /// LEAF=0x02.
+    CacheInfo(CacheInfoIter),
+    /// LEAF=0x03.
+    ProcessorSerial(ProcessorSerial),
+    /// A subleaf of LEAF=0x04 or LEAF=0x8000_001D.
+    CacheParameter(CacheParameter),
+    /// LEAF=0x05.
+    MonitorMwaitInfo(MonitorMwaitInfo),
+    /// LEAF=0x06.
+    ThermalPowerInfo(ThermalPowerInfo),
+    /// LEAF=0x07.
+    ExtendedFeatures(ExtendedFeatures),
+    /// LEAF=0x09.
+    DirectCacheAccessInfo(DirectCacheAccessInfo),
+    /// LEAF=0x0A.
+    PerformanceMonitoringInfo(PerformanceMonitoringInfo),
+    /// A subleaf of LEAF=0x0B or LEAF=0x1F.
+    ExtendedTopologyLevel(ExtendedTopologyLevel),
+    /// LEAF=0x0D, subleaf 0.
+    ExtendedStateInfo(ExtendedStateInfo<R>),
+    /// A state component subleaf (>= 2) of LEAF=0x0D.
+    ExtendedState(ExtendedState),
+    /// LEAF=0x0F.
+    RdtMonitoringInfo(RdtMonitoringInfo<R>),
+    /// LEAF=0x10.
+    RdtAllocationInfo(RdtAllocationInfo<R>),
+    /// LEAF=0x12, subleaf 0.
+    SgxInfo(SgxInfo<R>),
+    /// An EPC section subleaf (>= 2) of LEAF=0x12.
+    EpcSection(EpcSection),
+    /// LEAF=0x14.
+    ProcessorTraceInfo(ProcessorTraceInfo),
+    /// LEAF=0x15.
+    TscInfo(TscInfo),
+    /// LEAF=0x16.
+    ProcessorFrequencyInfo(ProcessorFrequencyInfo),
+    /// LEAF=0x17.
+    SoCVendorInfo(SoCVendorInfo<R>),
+    /// A subleaf of LEAF=0x18.
+    DatInfo(DatInfo),
+    /// LEAF=0x1A.
+    HybridInfo(HybridInfo),
+    /// LEAF=0x1B.
+    PconfigInfo(PconfigIter<R>),
+    /// LEAF=0x1C.
+    LbrInfo(LbrInfo),
+    /// LEAF=0x1E.
+    TmulInfo(TmulInfo),
+    /// LEAF=0x20.
+    HresetInfo(HresetInfo),
+    /// LEAF=0x23.
+    ArchPerfmonExtInfo(ArchPerfmonExtInfo),
+    /// LEAF=0x24.
+    Avx10Info(Avx10Info),
+    /// LEAF=0x4000_0000.
+    HypervisorInfo(HypervisorInfo<R>),
+    /// LEAF=0x8000_0001.
+    ExtendedProcessorFeatureIdentifiers(ExtendedProcessorFeatureIdentifiers),
+    /// LEAF=0x8000_0002 (the brand string spans up to LEAF=0x8000_0004).
+    ProcessorBrandString(ProcessorBrandString),
+    /// LEAF=0x8000_0005.
+    L1CacheTlbInfo(L1CacheTlbInfo),
+    /// LEAF=0x8000_0006.
+    L2And3CacheTlbInfo(L2And3CacheTlbInfo),
+    /// LEAF=0x8000_0007.
+    ApmInfo(ApmInfo),
+    /// LEAF=0x8000_0008.
+    ProcessorCapacityAndFeatureInfo(ProcessorCapacityAndFeatureInfo),
+    /// LEAF=0x8000_000A.
+    SvmFeatures(SvmFeatures),
+    /// LEAF=0x8000_0019.
+    Tlb1gPageInfo(Tlb1gPageInfo),
+    /// LEAF=0x8000_001E.
+    ProcessorTopologyInfo(ProcessorTopologyInfo),
+    /// LEAF=0x8000_001F.
+    MemoryEncryptionInfo(MemoryEncryptionInfo),
+    /// LEAF=0x8000_0022.
+    PerformanceMonitoringDebugInfo(PerformanceMonitoringDebugInfo),
+    /// A subleaf of LEAF=0x8000_0026.
+    ExtendedCpuTopologyLevel(ExtendedCpuTopologyLevel),
+    /// LEAF=0x8086_0001.
+    TransmetaProcessorInfo(TransmetaProcessorInfo),
+    /// LEAF=0x8086_0002.
+    TransmetaCmsVersion(TransmetaCmsVersion),
+    /// LEAF=0x8086_0003 (the information string spans up to LEAF=0x8086_0006).
+    TransmetaInfoString(TransmetaInfoString),
+    /// LEAF=0xC000_0001.
+    PadLockInfo(PadLockInfo),
+}
+
+impl<R: CpuIdReader> LeafData<R> {
+    /// Is this a leaf that isn't decoded ([`LeafData::Raw`])?
+    pub fn is_raw(&self) -> bool {
+        matches!(self, LeafData::Raw(_))
+    }
+}
+
+impl<R: CpuIdReader> CpuId<R> {
+    /// Decodes any `(leaf, subleaf)` with the accessor this library has for
+    /// it (e.g., [`CpuId::get_feature_info`] for LEAF=0x01).
+    ///
+    /// Leafs that have one entry per subleaf (caches, topology levels, state
+    /// components, EPC sections and address translation structures) are
+    /// decoded per subleaf if the subleaf is valid (see
+    /// [`CpuId::supports_leaf`]). The other accessors decode a leaf as a
+    /// whole and are returned for subleaf 0 (or the first of the leafs they
+    /// span), their other subleafs are returned as [`LeafData::Raw`].
+    ///
+    /// Everything that isn't decoded (unknown leafs, leafs the accessor
+    /// rejects, e.g., because of the vendor, or unsupported leafs) is
+    /// returned as the raw registers.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{CpuId, LeafData, LeafRequest};
+    ///
+    /// let cpuid = CpuId::new();
+    /// match cpuid.query(LeafRequest::new(0x1)) {
+    ///     LeafData::FeatureInfo(info) => println!("Family: {}", info.family_id()),
+    ///     other => println!("{:?}", other),
+    /// }
+    /// ```
+    pub fn query(&self, request: LeafRequest) -> LeafData<R> {
+        let LeafRequest { leaf, subleaf } = request;
+        self.decode(leaf, subleaf)
+            .unwrap_or_else(|| LeafData::Raw(self.read.cpuid2(leaf, subleaf)))
+    }
+
+    fn decode(&self, leaf: u32, subleaf: u32) -> Option<LeafData<R>> {
+        let res = || self.read.cpuid2(leaf, subleaf);
+
+        // Leafs with one entry per subleaf:
+        match leaf {
+            EAX_CACHE_PARAMETERS | EAX_CACHE_PARAMETERS_AMD => {
+                return (self.get_cache_parameters()?.leaf() == leaf
+                    && self.supports_leaf(leaf, subleaf))
+                .then(|| LeafData::CacheParameter(CacheParameter::from_registers(res())));
+            }
+            EAX_EXTENDED_TOPOLOGY_INFO | EAX_EXTENDED_TOPOLOGY_INFO_V2 => {
+                return self.supports_leaf(leaf, subleaf).then(|| {
+                    LeafData::ExtendedTopologyLevel(ExtendedTopologyLevel::from_registers(res()))
+                });
+            }
+            EAX_EXTENDED_CPU_TOPOLOGY => {
+                return (self.get_extended_cpu_topology_info().is_some()
+                    && self.supports_leaf(leaf, subleaf))
+                .then(|| {
+                    LeafData::ExtendedCpuTopologyLevel(ExtendedCpuTopologyLevel::from_registers(
+                        res(),
+                    ))
+                });
+            }
+            EAX_DETERMINISTIC_ADDRESS_TRANSLATION_INFO => {
+                return (self.get_deterministic_address_translation_info().is_some()
+                    && self.supports_leaf(leaf, subleaf))
+                .then(|| LeafData::DatInfo(DatInfo::from_registers(res())));
+            }
+            EAX_EXTENDED_STATE_INFO if subleaf >= 2 => {
+                return (self.get_extended_state_info().is_some()
+                    && self.supports_leaf(leaf, subleaf))
+                .then(|| LeafData::ExtendedState(ExtendedState::from_registers(subleaf, res())));
+            }
+            EAX_SGX if subleaf >= 2 => {
+                return (self.get_sgx_info().is_some() && get_bits(res().eax, 0, 3) == 0b0001)
+                    .then(|| LeafData::EpcSection(EpcSection::from_registers(res())));
+            }
+            _ => {}
+        }
+
+        // Accessors that decode the whole leaf:
+        if subleaf != 0 {
+            return None;
+        }
+        let data = match leaf {
+            EAX_VENDOR_INFO => LeafData::VendorInfo(self.get_vendor_info()?),
+            EAX_FEATURE_INFO => LeafData::FeatureInfo(self.get_feature_info()?),
+            EAX_CACHE_INFO => LeafData::CacheInfo(self.get_cache_info()?),
+            EAX_PROCESSOR_SERIAL => LeafData::ProcessorSerial(self.get_processor_serial()?),
+            EAX_MONITOR_MWAIT_INFO => LeafData::MonitorMwaitInfo(self.get_monitor_mwait_info()?),
+            EAX_THERMAL_POWER_INFO => LeafData::ThermalPowerInfo(self.get_thermal_power_info()?),
+            EAX_STRUCTURED_EXTENDED_FEATURE_INFO => {
+                LeafData::ExtendedFeatures(self.get_extended_feature_info()?)
+            }
+            EAX_DIRECT_CACHE_ACCESS_INFO => {
+                LeafData::DirectCacheAccessInfo(self.get_direct_cache_access_info()?)
+            }
+            EAX_PERFORMANCE_MONITOR_INFO => {
+                LeafData::PerformanceMonitoringInfo(self.get_performance_monitoring_info()?)
+            }
+            EAX_EXTENDED_STATE_INFO => LeafData::ExtendedStateInfo(self.get_extended_state_info()?),
+            EAX_RDT_MONITORING => LeafData::RdtMonitoringInfo(self.get_rdt_monitoring_info()?),
+            EAX_RDT_ALLOCATION => LeafData::RdtAllocationInfo(self.get_rdt_allocation_info()?),
+            EAX_SGX => LeafData::SgxInfo(self.get_sgx_info()?),
+            EAX_TRACE_INFO => LeafData::ProcessorTraceInfo(self.get_processor_trace_info()?),
+            EAX_TIME_STAMP_COUNTER_INFO => LeafData::TscInfo(self.get_tsc_info()?),
+            EAX_FREQUENCY_INFO => {
+                LeafData::ProcessorFrequencyInfo(self.get_processor_frequency_info()?)
+            }
+            EAX_SOC_VENDOR_INFO => LeafData::SoCVendorInfo(self.get_soc_vendor_info()?),
+            EAX_HYBRID_INFO => LeafData::HybridInfo(self.get_hybrid_info()?),
+            EAX_PCONFIG_INFO => LeafData::PconfigInfo(self.get_pconfig_info()?),
+            EAX_LBR_INFO => LeafData::LbrInfo(self.get_lbr_info()?),
+            EAX_TMUL_INFO => LeafData::TmulInfo(self.get_tmul_info()?),
+            EAX_HRESET_INFO => LeafData::HresetInfo(self.get_hreset_info()?),
+            EAX_ARCH_PERFMON_EXT_INFO => {
+                LeafData::ArchPerfmonExtInfo(self.get_arch_perfmon_ext_info()?)
+            }
+            EAX_AVX10_INFO => LeafData::Avx10Info(self.get_avx10_info()?),
+            EAX_HYPERVISOR_INFO => LeafData::HypervisorInfo(self.get_hypervisor_info()?),
+            EAX_EXTENDED_PROCESSOR_AND_FEATURE_IDENTIFIERS => {
+                LeafData::ExtendedProcessorFeatureIdentifiers(
+                    self.get_extended_processor_and_feature_identifiers()?,
+                )
+            }
+            EAX_EXTENDED_BRAND_STRING => {
+                LeafData::ProcessorBrandString(self.get_processor_brand_string()?)
+            }
+            EAX_L1_CACHE_INFO => LeafData::L1CacheTlbInfo(self.get_l1_cache_and_tlb_info()?),
+            EAX_L2_L3_CACHE_INFO => {
+                LeafData::L2And3CacheTlbInfo(self.get_l2_l3_cache_and_tlb_info()?)
+            }
+            EAX_ADVANCED_POWER_MGMT_INFO => LeafData::ApmInfo(self.get_advanced_power_mgmt_info()?),
+            EAX_PROCESSOR_CAPACITY_INFO => LeafData::ProcessorCapacityAndFeatureInfo(
+                self.get_processor_capacity_feature_info()?,
+            ),
+            EAX_SVM_FEATURES => LeafData::SvmFeatures(self.get_svm_info()?),
+            EAX_TLB_1G_PAGE_INFO => LeafData::Tlb1gPageInfo(self.get_tlb_1g_page_info()?),
+            EAX_PROCESSOR_TOPOLOGY_INFO => {
+                LeafData::ProcessorTopologyInfo(self.get_processor_topology_info()?)
+            }
+            EAX_MEMORY_ENCRYPTION_INFO => {
+                LeafData::MemoryEncryptionInfo(self.get_memory_encryption_info()?)
+            }
+            EAX_PERFORMANCE_MONITORING_DEBUG_INFO => LeafData::PerformanceMonitoringDebugInfo(
+                self.get_performance_monitoring_debug_info()?,
+            ),
+            EAX_TRANSMETA_PROCESSOR_INFO => {
+                LeafData::TransmetaProcessorInfo(self.get_transmeta_processor_info()?)
+            }
+            EAX_TRANSMETA_CMS_VERSION => {
+                LeafData::TransmetaCmsVersion(self.get_transmeta_cms_version()?)
+            }
+            EAX_TRANSMETA_INFO_STRING => {
+                LeafData::TransmetaInfoString(self.get_transmeta_info_string()?)
+            }
+            EAX_CENTAUR_FEATURE_INFO => LeafData::PadLockInfo(self.get_padlock_info()?),
+            _ => return None,
+        };
+        Some(data)
+    }
+}
@@ -0,0 +1,590 @@
+//! A human readable report of everything this library can decode, similar to
+//! the output of `lscpu` (e.g., to attach to bug reports).
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::diff::format_size;
+use crate::{
+    Associativity, CacheInfoType, CacheType, CpuId, CpuIdReader, CpuIdSnapshot, DatType, Feature,
+    Register,
+};
+
+/// A single `name: value` line of a [`ReportSection`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReportEntry {
+    pub name: String,
+    pub value: String,
+}
+
+/// A titled group of entries (e.g., all caches).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReportSection {
+    pub title: String,
+    pub entries: Vec<ReportEntry>,
+}
+
+impl ReportSection {
+    fn new(title: &str) -> Self {
+        ReportSection {
+            title: title.to_string(),
+            entries: Vec::new(),
+        }
+    }
+
+    fn push<N: ToString, V: ToString>(&mut self, name: N, value: V) {
+        self.entries.push(ReportEntry {
+            name: name.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    /// Returns the value of the entry called `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.value.as_str())
+    }
+}
+
+/// Everything known about a CPU, organized in sections: processor, frequency,
+/// topology, caches, TLBs, address sizes and features (grouped by leaf).
+///
+/// Sections or entries that a CPU doesn't report are left out. The `Display`
+/// implementation prints the report as aligned plain text.
+///
+/// # Example
+/// ```
+/// use raw_cpuid::{CpuId, Report};
+///
+/// let report = Report::new(&CpuId::new());
+/// println!("{}", report);
+/// assert!(report.section("Processor").and_then(|s| s.get("Vendor")).is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Report {
+    pub sections: Vec<ReportSection>,
+}
+
+impl Report {
+    /// Decodes all information reported by `cpuid`.
+    pub fn new<R: CpuIdReader>(cpuid: &CpuId<R>) -> Self {
+        let sections = [
+            processor(cpuid),
+            frequency(cpuid),
+            topology(cpuid),
+            caches(cpuid),
+            tlbs(cpuid),
+            address_sizes(cpuid),
+            features(cpuid),
+        ];
+        Report {
+            sections: sections
+                .into_iter()
+                .filter(|section| !section.entries.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Returns the section with the given `title`.
+    pub fn section(&self, title: &str) -> Option<&ReportSection> {
+        self.sections.iter().find(|section| section.title == title)
+    }
+
+    /// Renders the report as Markdown with one table per section.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{CpuId, Report};
+    ///
+    /// let markdown = Report::new(&CpuId::new()).to_markdown();
+    /// assert!(markdown.starts_with("## Processor\n\n| Name | Value |\n"));
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for (idx, section) in self.sections.iter().enumerate() {
+            if idx > 0 {
+                out.push('\n');
+            }
+            out.push_str(&format!("## {}\n\n", section.title));
+            out.push_str("| Name | Value |\n");
+            out.push_str("| --- | --- |\n");
+            for entry in &section.entries {
+                out.push_str(&format!(
+                    "| {} | {} |\n",
+                    escape_markdown(&entry.name),
+                    escape_markdown(&entry.value)
+                ));
+            }
+        }
+        out
+    }
+
+    /// Renders the report as an HTML fragment with one `<table>` per section.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{CpuId, Report};
+    ///
+    /// let html = Report::new(&CpuId::new()).to_html();
+    /// assert!(html.starts_with("<h2>Processor</h2>\n<table>\n"));
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        for section in &self.sections {
+            out.push_str(&format!(
+                "<h2>{}</h2>\n<table>\n",
+                escape_html(&section.title)
+            ));
+            for entry in &section.entries {
+                out.push_str(&format!(
+                    "  <tr><th>{}</th><td>{}</td></tr>\n",
+                    escape_html(&entry.name),
+                    escape_html(&entry.value)
+                ));
+            }
+            out.push_str("</table>\n");
+        }
+        out
+    }
+}
+
+/// Escapes characters that would end a Markdown table cell.
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (idx, section) in self.sections.iter().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "{}:", section.title)?;
+            let width = section
+                .entries
+                .iter()
+                .map(|entry| entry.name.chars().count() + 1)
+                .max()
+                .unwrap_or(0);
+            for entry in &section.entries {
+                let name = format!("{}:", entry.name);
+                writeln!(f, "  {:width$} {}", name, entry.value, width = width)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Exports a CSV table with one row per core and one column per attribute:
+/// the core id, APIC ID, vendor, family, model, stepping, topology counts
+/// and a `0`/`1` column for every [`Feature`] (named by [`Feature::name`]).
+///
+/// `cores` are `(core id, snapshot)` pairs, as returned by
+/// [`CpuIdSnapshot::from_all_cores`].
+///
+/// # Example
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use raw_cpuid::{feature_matrix_csv, CpuIdSnapshot};
+///
+/// let cores = CpuIdSnapshot::from_all_cores().expect("Can't determine cores");
+/// let csv = feature_matrix_csv(&cores);
+/// assert!(csv.starts_with("core,apic_id,vendor,family,model,stepping,"));
+/// assert_eq!(csv.lines().count(), cores.len() + 1);
+/// # }
+/// ```
+pub fn feature_matrix_csv<'a, I>(cores: I) -> String
+where
+    I: IntoIterator<Item = &'a (usize, CpuIdSnapshot)>,
+{
+    let mut out = String::from(
+        "core,apic_id,vendor,family,model,stepping,logical_cpus,cores,threads_per_core",
+    );
+    for feature in Feature::ALL {
+        out.push(',');
+        out.push_str(feature.name());
+    }
+    out.push('\n');
+
+    let opt = |value: Option<usize>| value.map(|v| v.to_string()).unwrap_or_default();
+    for (core_id, snapshot) in cores {
+        let cpuid = snapshot.cpuid();
+        let finfo = cpuid.get_feature_info();
+        let fields = [
+            core_id.to_string(),
+            opt(cpuid.x2apic_id().map(|id| id as usize)),
+            escape_csv(
+                cpuid
+                    .get_vendor_info()
+                    .as_ref()
+                    .map_or("", |vendor| vendor.as_str()),
+            ),
+            opt(finfo.as_ref().map(|f| f.family_id() as usize)),
+            opt(finfo.as_ref().map(|f| f.model_id() as usize)),
+            opt(finfo.as_ref().map(|f| f.stepping_id() as usize)),
+            opt(cpuid.num_logical_cpus()),
+            opt(cpuid.num_cores()),
+            opt(cpuid.threads_per_core()),
+        ];
+        out.push_str(&fields.join(","));
+        for feature in Feature::ALL {
+            out.push_str(if cpuid.has_feature(*feature) {
+                ",1"
+            } else {
+                ",0"
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn processor<R: CpuIdReader>(cpuid: &CpuId<R>) -> ReportSection {
+    let mut section = ReportSection::new("Processor");
+    if let Some(vendor) = cpuid.get_vendor_info() {
+        section.push("Vendor", vendor.as_str());
+    }
+    if let Some(brand) = cpuid.get_processor_brand_string() {
+        section.push("Model name", brand.as_str().trim());
+    }
+    if let Some(finfo) = cpuid.get_feature_info() {
+        section.push("Family", format!("{:#x}", finfo.family_id()));
+        section.push("Model", format!("{:#x}", finfo.model_id()));
+        section.push("Stepping", format!("{:#x}", finfo.stepping_id()));
+    }
+    if let Some(hv) = cpuid.get_hypervisor_info() {
+        section.push("Hypervisor", format!("{:?}", hv.identify()));
+    }
+    section
+}
+
+fn frequency<R: CpuIdReader>(cpuid: &CpuId<R>) -> ReportSection {
+    let mut section = ReportSection::new("Frequency");
+    if let Some(freq) = cpuid.get_processor_frequency_info() {
+        let mhz = |mhz: u16| (mhz > 0).then(|| format!("{} MHz", mhz));
+        if let Some(base) = mhz(freq.processor_base_frequency()) {
+            section.push("Base frequency", base);
+        }
+        if let Some(max) = mhz(freq.processor_max_frequency()) {
+            section.push("Max frequency", max);
+        }
+        if let Some(bus) = mhz(freq.bus_frequency()) {
+            section.push("Bus frequency", bus);
+        }
+    }
+    if let Some(tsc) = cpuid.get_tsc_info() {
+        if let Some(hz) = tsc.tsc_frequency() {
+            section.push("TSC frequency", format!("{} MHz", hz / 1_000_000));
+        }
+        if tsc.nominal_frequency() > 0 {
+            section.push(
+                "Crystal frequency",
+                format!("{} Hz", tsc.nominal_frequency()),
+            );
+        }
+    }
+    section
+}
+
+fn topology<R: CpuIdReader>(cpuid: &CpuId<R>) -> ReportSection {
+    let mut section = ReportSection::new("Topology");
+    if let Some(n) = cpuid.num_logical_cpus() {
+        section.push("Logical CPUs per package", n);
+    }
+    if let Some(n) = cpuid.num_cores() {
+        section.push("Cores per package", n);
+    }
+    if let Some(n) = cpuid.threads_per_core() {
+        section.push("Threads per core", n);
+    }
+    if let Some(id) = cpuid.x2apic_id() {
+        section.push("APIC ID", id);
+    }
+    if let Some(levels) = cpuid.get_extended_topology_info() {
+        for level in levels {
+            section.push(
+                format!("{} level", level.level_type()),
+                format!(
+                    "{} logical processors, APIC ID shift {}",
+                    level.processors(),
+                    level.shift_right_for_next_apic_id()
+                ),
+            );
+        }
+    }
+    section
+}
+
+fn associativity(assoc: Associativity) -> String {
+    match assoc {
+        Associativity::Direct => "direct mapped".to_string(),
+        Associativity::Full => "fully associative".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn caches<R: CpuIdReader>(cpuid: &CpuId<R>) -> ReportSection {
+    let mut section = ReportSection::new("Caches");
+    if let Some(caches) = cpuid.get_cache_parameters() {
+        for cache in caches {
+            let name = match cache.cache_type() {
+                CacheType::Data => format!("L{}d", cache.level()),
+                CacheType::Instruction => format!("L{}i", cache.level()),
+                _ => format!("L{}", cache.level()),
+            };
+            section.push(
+                name,
+                format!(
+                    "{}, {}, {} B lines, shared by {} threads",
+                    format_size(cache.size() as u64),
+                    associativity(cache.associativity()),
+                    cache.coherency_line_size(),
+                    cache.max_cores_for_cache()
+                ),
+            );
+        }
+    }
+    if !section.entries.is_empty() {
+        return section;
+    }
+
+    if let Some(l1) = cpuid.get_l1_cache_and_tlb_info() {
+        section.push(
+            "L1d",
+            format!(
+                "{}, {}, {} B lines",
+                format_size(l1.dcache_size() as u64 * 1024),
+                associativity(l1.dcache_associativity()),
+                l1.dcache_line_size()
+            ),
+        );
+        section.push(
+            "L1i",
+            format!(
+                "{}, {}, {} B lines",
+                format_size(l1.icache_size() as u64 * 1024),
+                associativity(l1.icache_associativity()),
+                l1.icache_line_size()
+            ),
+        );
+    }
+    if let Some(l2_l3) = cpuid.get_l2_l3_cache_and_tlb_info() {
+        if l2_l3.l2cache_size() > 0 {
+            section.push(
+                "L2",
+                format!(
+                    "{}, {}, {} B lines",
+                    format_size(l2_l3.l2cache_size() as u64 * 1024),
+                    associativity(l2_l3.l2cache_associativity()),
+                    l2_l3.l2cache_line_size()
+                ),
+            );
+        }
+        if l2_l3.l3cache_size() > 0 {
+            section.push(
+                "L3",
+                format!(
+                    "{}, {}, {} B lines",
+                    format_size(l2_l3.l3cache_size() as u64 * 512 * 1024),
+                    associativity(l2_l3.l3cache_associativity()),
+                    l2_l3.l3cache_line_size()
+                ),
+            );
+        }
+    }
+    section
+}
+
+fn tlbs<R: CpuIdReader>(cpuid: &CpuId<R>) -> ReportSection {
+    let mut section = ReportSection::new("TLBs");
+    if let Some(dat) = cpuid.get_deterministic_address_translation_info() {
+        for tlb in dat {
+            if tlb.cache_type() == DatType::Null {
+                continue;
+            }
+            let pages: Vec<&str> = [
+                (tlb.has_4k_entries(), "4K"),
+                (tlb.has_2mb_entries(), "2M"),
+                (tlb.has_4mb_entries(), "4M"),
+                (tlb.has_1gb_entries(), "1G"),
+            ]
+            .iter()
+            .filter(|(supported, _)| *supported)
+            .map(|&(_, size)| size)
+            .collect();
+            section.push(
+                format!(
+                    "L{} {} ({})",
+                    tlb.cache_level(),
+                    tlb.cache_type(),
+                    pages.join("/")
+                ),
+                format!(
+                    "{} entries, {}",
+                    tlb.ways() as u32 * tlb.sets(),
+                    associativity(tlb.associativity())
+                ),
+            );
+        }
+    }
+    if let Some(descriptors) = cpuid.get_cache_info() {
+        for desc in descriptors {
+            if matches!(
+                desc.typ,
+                CacheInfoType::TLB | CacheInfoType::DTLB | CacheInfoType::STLB
+            ) {
+                section.push(format!("Descriptor {:#04x}", desc.num), desc.desc());
+            }
+        }
+    }
+    if let Some(l1) = cpuid.get_l1_cache_and_tlb_info() {
+        section.push(
+            "L1 Data TLB (4K)",
+            format!(
+                "{} entries, {}",
+                l1.dtlb_4k_size(),
+                associativity(l1.dtlb_4k_associativity())
+            ),
+        );
+        section.push(
+            "L1 Instruction TLB (4K)",
+            format!(
+                "{} entries, {}",
+                l1.itlb_4k_size(),
+                associativity(l1.itlb_4k_associativity())
+            ),
+        );
+        section.push(
+            "L1 Data TLB (2M/4M)",
+            format!(
+                "{} entries, {}",
+                l1.dtlb_2m_4m_size(),
+                associativity(l1.dtlb_2m_4m_associativity())
+            ),
+        );
+        section.push(
+            "L1 Instruction TLB (2M/4M)",
+            format!(
+                "{} entries, {}",
+                l1.itlb_2m_4m_size(),
+                associativity(l1.itlb_2m_4m_associativity())
+            ),
+        );
+    }
+    if let Some(l2) = cpuid.get_l2_l3_cache_and_tlb_info() {
+        if l2.dtlb_4k_size() > 0 {
+            section.push(
+                "L2 Data TLB (4K)",
+                format!(
+                    "{} entries, {}",
+                    l2.dtlb_4k_size(),
+                    associativity(l2.dtlb_4k_associativity())
+                ),
+            );
+        }
+        if l2.itlb_4k_size() > 0 {
+            section.push(
+                "L2 Instruction TLB (4K)",
+                format!(
+                    "{} entries, {}",
+                    l2.itlb_4k_size(),
+                    associativity(l2.itlb_4k_associativity())
+                ),
+            );
+        }
+        if l2.dtlb_2m_4m_size() > 0 {
+            section.push(
+                "L2 Data TLB (2M/4M)",
+                format!(
+                    "{} entries, {}",
+                    l2.dtlb_2m_4m_size(),
+                    associativity(l2.dtlb_2m_4m_associativity())
+                ),
+            );
+        }
+        if l2.itlb_2m_4m_size() > 0 {
+            section.push(
+                "L2 Instruction TLB (2M/4M)",
+                format!(
+                    "{} entries, {}",
+                    l2.itlb_2m_4m_size(),
+                    associativity(l2.itlb_2m_4m_associativity())
+                ),
+            );
+        }
+    }
+    section
+}
+
+fn address_sizes<R: CpuIdReader>(cpuid: &CpuId<R>) -> ReportSection {
+    let mut section = ReportSection::new("Address sizes");
+    if let Some(capacity) = cpuid.get_processor_capacity_feature_info() {
+        section.push(
+            "Physical",
+            format!("{} bits", capacity.physical_address_bits()),
+        );
+        section.push("Linear", format!("{} bits", capacity.linear_address_bits()));
+    }
+    section
+}
+
+fn features<R: CpuIdReader>(cpuid: &CpuId<R>) -> ReportSection {
+    let mut section = ReportSection::new("Features");
+    let mut group: Option<(u32, u32, Register)> = None;
+    let mut names: Vec<&str> = Vec::new();
+    let flush = |section: &mut ReportSection, group, names: &mut Vec<&str>| {
+        if let Some((leaf, subleaf, register)) = group {
+            if !names.is_empty() {
+                section.push(
+                    format!("Leaf {:#x}.{} {}", leaf, subleaf, register),
+                    names.join(" "),
+                );
+            }
+        }
+        names.clear();
+    };
+
+    for feature in Feature::ALL {
+        let loc = feature.location();
+        let key = Some((loc.leaf, loc.subleaf, loc.register));
+        if key != group {
+            flush(&mut section, group, &mut names);
+            group = key;
+        }
+        if cpuid.has_feature(*feature) {
+            names.push(feature.name());
+        }
+    }
+    flush(&mut section, group, &mut names);
+    section
+}
@@ -0,0 +1,125 @@
+//! Feature flag names as used by rustc's `-C target-feature` option and
+//! `cfg(target_feature = "...")`.
+#[cfg(any(test, feature = "alloc"))]
+use alloc::string::String;
+
+use crate::{CpuFeatures, Feature};
+
+impl Feature {
+    /// Name of the flag as a rustc target feature, e.g., `sse4.1` or
+    /// `avx512vpopcntdq`.
+    ///
+    /// The same name is used in `-C target-feature=+name` and in
+    /// `cfg(target_feature = "name")`. Returns `None` for flags rustc doesn't
+    /// know (e.g., `vmx`).
+    pub fn rustc_name(&self) -> Option<&'static str> {
+        use Feature::*;
+
+        let name = match self {
+            Sse3 => "sse3",
+            Pclmulqdq => "pclmulqdq",
+            Ssse3 => "ssse3",
+            Fma => "fma",
+            Cmpxchg16b => "cmpxchg16b",
+            Sse41 => "sse4.1",
+            Sse42 => "sse4.2",
+            Movbe => "movbe",
+            Popcnt => "popcnt",
+            Aesni => "aes",
+            Xsave => "xsave",
+            Avx => "avx",
+            F16c => "f16c",
+            Rdrand => "rdrand",
+            Fxsr => "fxsr",
+            Sse => "sse",
+            Sse2 => "sse2",
+            Bmi1 => "bmi1",
+            Avx2 => "avx2",
+            Bmi2 => "bmi2",
+            Erms => "ermsb",
+            Rtm => "rtm",
+            Avx512f => "avx512f",
+            Avx512dq => "avx512dq",
+            Rdseed => "rdseed",
+            Adx => "adx",
+            Avx512ifma => "avx512ifma",
+            Avx512cd => "avx512cd",
+            Sha => "sha",
+            Avx512bw => "avx512bw",
+            Avx512vl => "avx512vl",
+            Avx512vbmi => "avx512vbmi",
+            Avx512vbmi2 => "avx512vbmi2",
+            Gfni => "gfni",
+            Vaes => "vaes",
+            Vpclmulqdq => "vpclmulqdq",
+            Avx512vnni => "avx512vnni",
+            Avx512bitalg => "avx512bitalg",
+            Avx512vpopcntdq => "avx512vpopcntdq",
+            Kl => "kl",
+            Avx512vp2intersect => "avx512vp2intersect",
+            Avx512fp16 => "avx512fp16",
+            AvxVnni => "avxvnni",
+            Avx512bf16 => "avx512bf16",
+            AvxVnniInt8 => "avxvnniint8",
+            AvxNeConvert => "avxneconvert",
+            AvxVnniInt16 => "avxvnniint16",
+            Xsaveopt => "xsaveopt",
+            Xsavec => "xsavec",
+            Xsaves => "xsaves",
+            LahfLm => "lahfsahf",
+            Lzcnt => "lzcnt",
+            Sse4a => "sse4a",
+            Prefetchw => "prfchw",
+            Tbm => "tbm",
+            _ => return None,
+        };
+        Some(name)
+    }
+
+    /// Look up a feature by its [`Feature::rustc_name`].
+    pub fn from_rustc_name(name: &str) -> Option<Feature> {
+        Feature::ALL
+            .iter()
+            .copied()
+            .find(|f| f.rustc_name() == Some(name))
+    }
+}
+
+impl CpuFeatures {
+    /// The [`Feature::rustc_name`]s of the features in the set, e.g., for
+    /// `cfg(target_feature = "...")`.
+    pub fn rustc_names(&self) -> impl Iterator<Item = &'static str> {
+        self.iter().filter_map(|f| f.rustc_name())
+    }
+
+    /// Formats the features as value of rustc's `-C target-feature` option,
+    /// e.g., `+sse2,+avx2,+fma`.
+    ///
+    /// Features without a [`Feature::rustc_name`] are left out.
+    ///
+    /// # Note
+    /// CPUID reports what the CPU supports, not what the OS enabled (e.g.,
+    /// the AVX state in XCR0). Binaries for the local machine should only be
+    /// built with features that are also detected by the standard library,
+    /// see `CpuId::std_detection_mismatches`.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// let features = CpuId::new().features();
+    /// println!("RUSTFLAGS=-C target-feature={}", features.rustc_target_features());
+    /// ```
+    #[cfg(any(test, feature = "alloc"))]
+    pub fn rustc_target_features(&self) -> String {
+        let mut option = String::new();
+        for name in self.rustc_names() {
+            if !option.is_empty() {
+                option.push(',');
+            }
+            option.push('+');
+            option.push_str(name);
+        }
+        option
+    }
+}
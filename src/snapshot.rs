@@ -0,0 +1,439 @@
+//! Capture the complete CPUID space of a CPU so it can be stored, compared or
+//! replayed later on (e.g., to reproduce a bug report from another machine).
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+#[cfg(all(feature = "std", target_os = "linux"))]
+use alloc::format;
+#[cfg(not(feature = "std"))]
+use core::cell::UnsafeCell;
+use core::ptr;
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::AtomicU8;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+use crate::builder::for_each_leaf;
+use crate::native_cpuid::MockReader;
+use crate::{native_cpuid, CpuId, CpuIdReader, CpuIdReaderNative, CpuIdResult};
+
+/// All leafs and subleafs reported by a CPU.
+///
+/// A snapshot implements [`CpuIdReader`], so it can be queried like the CPU
+/// it was taken from by using [`CpuIdSnapshot::cpuid`] (or
+/// [`CpuId::with_cpuid_reader`]). Leafs that are not part of the snapshot read
+/// as zero.
+///
+/// # Example
+/// ```
+/// use raw_cpuid::CpuIdSnapshot;
+///
+/// let snapshot = CpuIdSnapshot::from_host();
+/// let cpuid = snapshot.cpuid();
+/// assert_eq!(
+///     cpuid.get_vendor_info().map(|vi| vi.as_str().to_owned()),
+///     raw_cpuid::CpuId::new().get_vendor_info().map(|vi| vi.as_str().to_owned())
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuIdSnapshot {
+    #[cfg_attr(feature = "serde", serde(with = "leafs_as_seq"))]
+    leafs: BTreeMap<(u32, u32), CpuIdResult>,
+}
+
+impl CpuIdSnapshot {
+    /// Takes a snapshot of the CPU we're running on.
+    pub fn from_host() -> Self {
+        Self::with_cpuid_reader(CpuIdReaderNative)
+    }
+
+    /// Takes a snapshot of all leafs reported by `read`.
+    pub fn with_cpuid_reader<R: CpuIdReader>(read: R) -> Self {
+        let mut leafs = BTreeMap::new();
+        for_each_leaf(&read, |leaf, subleaf, res| {
+            leafs.insert((leaf, subleaf), res);
+        });
+        Self { leafs }
+    }
+
+    /// Returns a [`CpuId`] that reads from this snapshot.
+    pub fn cpuid(&self) -> CpuId<&Self> {
+        CpuId::with_cpuid_reader(self)
+    }
+
+    /// Answers all requests of [`CpuIdReaderNative`] (and thus
+    /// [`CpuId::new`]) from this snapshot instead of executing `cpuid`, e.g.,
+    /// to run tests on CI hosts that aren't x86 (see
+    /// [`native_cpuid::set_mock_reader`]).
+    ///
+    /// The snapshot replaces the one installed before (if any) for all
+    /// threads until the returned guard is dropped, which restores the
+    /// previous snapshot or mock reader and frees this one. Guards should be
+    /// dropped in the reverse order of installation. Use
+    /// [`CpuIdSnapshot::install_as_thread_native`] to keep tests that run in
+    /// parallel apart.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{native_cpuid, CpuId, CpuIdResult, CpuIdSnapshot};
+    /// use std::collections::BTreeMap;
+    ///
+    /// // A CPU that only reports its vendor (leaf 0 EBX, EDX, ECX).
+    /// let cpu = |vendor: &[u8; 12]| {
+    ///     let reg = |i: usize| u32::from_le_bytes(vendor[i..i + 4].try_into().unwrap());
+    ///     let leaf_0 = CpuIdResult { eax: 0, ebx: reg(0), ecx: reg(8), edx: reg(4) };
+    ///     CpuIdSnapshot::from(BTreeMap::from([((0, 0), leaf_0)]))
+    /// };
+    ///
+    /// let amd = cpu(b"AuthenticAMD").install_as_native();
+    /// assert_eq!(CpuId::new().get_vendor_info().unwrap().as_str(), "AuthenticAMD");
+    ///
+    /// let intel = cpu(b"GenuineIntel").install_as_native();
+    /// assert_eq!(CpuId::new().get_vendor_info().unwrap().as_str(), "GenuineIntel");
+    ///
+    /// drop(intel);
+    /// assert_eq!(CpuId::new().get_vendor_info().unwrap().as_str(), "AuthenticAMD");
+    /// drop(amd);
+    /// assert!(native_cpuid::mock_reader().is_none());
+    /// ```
+    pub fn install_as_native(self) -> NativeMockGuard {
+        let previous_reader = native_cpuid::mock_reader();
+        let installed = Box::into_raw(Box::new(self));
+        let previous = NATIVE_MOCK.swap(installed, Ordering::SeqCst);
+        native_cpuid::set_mock_reader(Some(read_native_mock));
+        NativeMockGuard {
+            scope: MockScope::Process {
+                installed,
+                previous,
+            },
+            previous_reader,
+        }
+    }
+
+    /// Like [`CpuIdSnapshot::install_as_native`], but only answers the
+    /// requests of the current thread.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::{CpuId, CpuIdResult, CpuIdSnapshot};
+    /// use std::collections::BTreeMap;
+    ///
+    /// // "RawCpuIdMock" in leaf 0 EBX, EDX, ECX:
+    /// let leaf_0 = CpuIdResult { eax: 0, ebx: 0x43776152, ecx: 0x6b636f4d, edx: 0x64497570 };
+    /// let snapshot = CpuIdSnapshot::from(BTreeMap::from([((0, 0), leaf_0)]));
+    ///
+    /// let _mock = snapshot.install_as_thread_native();
+    /// assert_eq!(CpuId::new().get_vendor_info().unwrap().as_str(), "RawCpuIdMock");
+    ///
+    /// let other = std::thread::spawn(|| CpuId::new().get_vendor_info().map(|vi| vi.as_str().to_owned()));
+    /// assert_ne!(other.join().unwrap().as_deref(), Some("RawCpuIdMock"));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn install_as_thread_native(self) -> NativeMockGuard {
+        let previous = THREAD_NATIVE_MOCK.with(|mock| mock.replace(Some(self)));
+        let previous_reader = native_cpuid::thread_mock_reader();
+        native_cpuid::set_thread_mock_reader(Some(read_thread_native_mock));
+        NativeMockGuard {
+            scope: MockScope::Thread { previous },
+            previous_reader,
+        }
+    }
+
+    /// Returns the value of a leaf, or `None` if it's not part of the
+    /// snapshot.
+    pub fn get(&self, leaf: u32, subleaf: u32) -> Option<CpuIdResult> {
+        self.leafs.get(&(leaf, subleaf)).copied()
+    }
+
+    /// All `(leaf, subleaf)` entries of the snapshot in ascending order.
+    pub fn leafs(&self) -> &BTreeMap<(u32, u32), CpuIdResult> {
+        &self.leafs
+    }
+
+    /// Number of entries in the snapshot.
+    pub fn len(&self) -> usize {
+        self.leafs.len()
+    }
+
+    /// Returns `true` if the snapshot doesn't contain any leafs.
+    pub fn is_empty(&self) -> bool {
+        self.leafs.is_empty()
+    }
+}
+
+#[cfg(all(feature = "std", target_os = "linux"))]
+impl CpuIdSnapshot {
+    /// Takes a snapshot of logical CPU `cpu` by reading `/dev/cpu/<cpu>/cpuid`.
+    ///
+    /// Unlike [`CpuIdSnapshot::from_host`] this doesn't require running on
+    /// `cpu`. The `cpuid` kernel module has to be loaded and reading the device
+    /// usually requires root privileges.
+    pub fn from_dev_cpu(cpu: usize) -> std::io::Result<Self> {
+        Self::from_cpuid_device(format!("/dev/cpu/{}/cpuid", cpu))
+    }
+
+    /// Takes a snapshot by reading the Linux cpuid device at `path`.
+    ///
+    /// The device returns the registers of leaf `offset & 0xffff_ffff` and
+    /// subleaf `offset >> 32` when reading 16 bytes at `offset`.
+    pub fn from_cpuid_device<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        use core::cell::RefCell;
+        use std::os::unix::fs::FileExt;
+
+        let file = std::fs::File::open(path)?;
+        let error = RefCell::new(None);
+        let read = |eax: u32, ecx: u32| {
+            let mut buf = [0u8; 16];
+            let offset = (ecx as u64) << 32 | eax as u64;
+            if let Err(e) = file.read_exact_at(&mut buf, offset) {
+                error.borrow_mut().get_or_insert(e);
+            }
+            let reg = |i: usize| u32::from_le_bytes([buf[i], buf[i + 1], buf[i + 2], buf[i + 3]]);
+            CpuIdResult {
+                eax: reg(0),
+                ebx: reg(4),
+                ecx: reg(8),
+                edx: reg(12),
+            }
+        };
+
+        let snapshot = Self::with_cpuid_reader(read);
+        match error.into_inner() {
+            Some(e) => Err(e),
+            None => Ok(snapshot),
+        }
+    }
+}
+
+/// A [`CpuId`] for a process-wide snapshot of the CPU, which is taken the
+/// first time this is called.
+///
+/// Libraries can use this to query features without executing `cpuid` again
+/// and again. The snapshot is taken on whatever core the first caller runs
+/// on, so per-core values like the APIC ID are those of that core.
+///
+/// # Example
+/// ```
+/// let has_sse2 = raw_cpuid::global()
+///     .get_feature_info()
+///     .is_some_and(|finfo| finfo.has_sse2());
+/// # assert!(cfg!(not(target_arch = "x86_64")) || has_sse2);
+/// ```
+pub fn global() -> &'static CpuId<CpuIdSnapshot> {
+    #[cfg(feature = "std")]
+    {
+        static GLOBAL: std::sync::OnceLock<CpuId<CpuIdSnapshot>> = std::sync::OnceLock::new();
+        GLOBAL.get_or_init(|| CpuId::with_cpuid_reader(CpuIdSnapshot::from_host()))
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        static GLOBAL: GlobalCpuId = GlobalCpuId::new();
+        GLOBAL.get(CpuIdSnapshot::from_host)
+    }
+}
+
+/// Initializes a [`CpuId`] for a snapshot once without `std` (which uses a
+/// `OnceLock` instead).
+#[cfg(not(feature = "std"))]
+pub(crate) struct GlobalCpuId {
+    state: AtomicU8,
+    cpuid: UnsafeCell<Option<CpuId<CpuIdSnapshot>>>,
+}
+
+// Safety: `cpuid` is only written once, before `state` becomes `READY`.
+#[cfg(not(feature = "std"))]
+unsafe impl Sync for GlobalCpuId {}
+
+#[cfg(not(feature = "std"))]
+impl GlobalCpuId {
+    const UNINIT: u8 = 0;
+    const RUNNING: u8 = 1;
+    const READY: u8 = 2;
+
+    pub(crate) const fn new() -> Self {
+        GlobalCpuId {
+            state: AtomicU8::new(Self::UNINIT),
+            cpuid: UnsafeCell::new(None),
+        }
+    }
+
+    pub(crate) fn get(&'static self, init: fn() -> CpuIdSnapshot) -> &'static CpuId<CpuIdSnapshot> {
+        loop {
+            match self.state.compare_exchange(
+                Self::UNINIT,
+                Self::RUNNING,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    // Let the next caller retry if taking the snapshot panics
+                    // (e.g., in a mock reader) instead of spinning forever.
+                    let reset = ResetOnUnwind(&self.state);
+                    let cpuid = CpuId::with_cpuid_reader(init());
+                    // Safety: Only the thread that moved the state from
+                    // `UNINIT` to `RUNNING` writes the value.
+                    unsafe { *self.cpuid.get() = Some(cpuid) };
+                    core::mem::forget(reset);
+                    self.state.store(Self::READY, Ordering::Release);
+                }
+                Err(Self::READY) => break,
+                Err(_) => core::hint::spin_loop(),
+            }
+        }
+
+        // Safety: The value is initialized and never written again once the
+        // state is `READY`.
+        unsafe { (*self.cpuid.get()).as_ref() }.expect("initialized CpuId")
+    }
+}
+
+/// Moves the state of a [`GlobalCpuId`] back to `UNINIT` when dropped.
+#[cfg(not(feature = "std"))]
+struct ResetOnUnwind<'a>(&'a AtomicU8);
+
+#[cfg(not(feature = "std"))]
+impl Drop for ResetOnUnwind<'_> {
+    fn drop(&mut self) {
+        self.0.store(GlobalCpuId::UNINIT, Ordering::Release);
+    }
+}
+
+/// Uninstalls a snapshot installed with [`CpuIdSnapshot::install_as_native`]
+/// or [`CpuIdSnapshot::install_as_thread_native`] when dropped.
+#[must_use = "the snapshot is uninstalled when the guard is dropped"]
+#[derive(Debug)]
+pub struct NativeMockGuard {
+    scope: MockScope,
+    previous_reader: Option<MockReader>,
+}
+
+// The raw pointers keep the guard on the thread that installed it, which
+// `MockScope::Thread` relies on.
+#[derive(Debug)]
+enum MockScope {
+    Process {
+        installed: *mut CpuIdSnapshot,
+        previous: *mut CpuIdSnapshot,
+    },
+    #[cfg(feature = "std")]
+    Thread { previous: Option<CpuIdSnapshot> },
+}
+
+impl Drop for NativeMockGuard {
+    fn drop(&mut self) {
+        match &mut self.scope {
+            MockScope::Process {
+                installed,
+                previous,
+            } => {
+                if NATIVE_MOCK
+                    .compare_exchange(*installed, *previous, Ordering::SeqCst, Ordering::SeqCst)
+                    .is_err()
+                {
+                    // A guard installed later is still alive and restores our
+                    // snapshot when dropped, so it has to stay valid.
+                    return;
+                }
+                if previous.is_null() {
+                    native_cpuid::set_mock_reader(self.previous_reader);
+                }
+                while NATIVE_MOCK_READERS.load(Ordering::SeqCst) != 0 {
+                    core::hint::spin_loop();
+                }
+                // Safety: The snapshot is no longer published and no reader
+                // is left that loaded it.
+                drop(unsafe { Box::from_raw(*installed) });
+            }
+            #[cfg(feature = "std")]
+            MockScope::Thread { previous } => {
+                THREAD_NATIVE_MOCK.with(|mock| mock.replace(previous.take()));
+                native_cpuid::set_thread_mock_reader(self.previous_reader);
+            }
+        }
+    }
+}
+
+static NATIVE_MOCK: AtomicPtr<CpuIdSnapshot> = AtomicPtr::new(ptr::null_mut());
+/// Number of `read_native_mock` calls that may still use a loaded snapshot.
+static NATIVE_MOCK_READERS: AtomicUsize = AtomicUsize::new(0);
+
+fn read_native_mock(eax: u32, ecx: u32) -> CpuIdResult {
+    NATIVE_MOCK_READERS.fetch_add(1, Ordering::SeqCst);
+    let snapshot = NATIVE_MOCK.load(Ordering::SeqCst);
+    // Safety: A guard only frees its snapshot after unpublishing it and
+    // waiting until no reader is left, and we registered before loading it.
+    // The snapshot can already be gone if the guard was dropped after this
+    // reader was looked up.
+    let res = unsafe { snapshot.as_ref() }
+        .map_or_else(|| native_cpuid::execute(eax, ecx), |s| s.cpuid2(eax, ecx));
+    NATIVE_MOCK_READERS.fetch_sub(1, Ordering::SeqCst);
+    res
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static THREAD_NATIVE_MOCK: core::cell::RefCell<Option<CpuIdSnapshot>> = const { core::cell::RefCell::new(None) };
+}
+
+#[cfg(feature = "std")]
+fn read_thread_native_mock(eax: u32, ecx: u32) -> CpuIdResult {
+    THREAD_NATIVE_MOCK.with(|mock| match &*mock.borrow() {
+        Some(snapshot) => snapshot.cpuid2(eax, ecx),
+        None => native_cpuid::execute(eax, ecx),
+    })
+}
+
+impl From<BTreeMap<(u32, u32), CpuIdResult>> for CpuIdSnapshot {
+    fn from(leafs: BTreeMap<(u32, u32), CpuIdResult>) -> Self {
+        Self { leafs }
+    }
+}
+
+impl CpuIdReader for CpuIdSnapshot {
+    fn cpuid2(&self, eax: u32, ecx: u32) -> CpuIdResult {
+        self.get(eax, ecx).unwrap_or(CpuIdResult {
+            eax: 0,
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
+        })
+    }
+}
+
+impl CpuIdReader for &CpuIdSnapshot {
+    fn cpuid2(&self, eax: u32, ecx: u32) -> CpuIdResult {
+        (*self).cpuid2(eax, ecx)
+    }
+}
+
+/// Serializes the leafs as a list of `(leaf, subleaf, result)` tuples since
+/// formats like JSON only allow strings as map keys.
+#[cfg(feature = "serde")]
+mod leafs_as_seq {
+    use alloc::collections::BTreeMap;
+    use alloc::vec::Vec;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::CpuIdResult;
+
+    pub fn serialize<S: Serializer>(
+        leafs: &BTreeMap<(u32, u32), CpuIdResult>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            leafs
+                .iter()
+                .map(|(&(leaf, subleaf), res)| (leaf, subleaf, *res)),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<(u32, u32), CpuIdResult>, D::Error> {
+        let entries: Vec<(u32, u32, CpuIdResult)> = Vec::deserialize(deserializer)?;
+        Ok(entries
+            .into_iter()
+            .map(|(leaf, subleaf, res)| ((leaf, subleaf), res))
+            .collect())
+    }
+}
@@ -0,0 +1,102 @@
+//! A one-line description of the CPU for logging, e.g., at application
+//! startup.
+use core::fmt::{self, Display, Formatter};
+
+use crate::{CpuId, CpuIdReader, Frequency, ProcessorBrandString, VendorInfo};
+
+/// The most important facts about the CPU, see [`CpuId::summary`].
+///
+/// The `Display` implementation writes them on one line, e.g.,
+/// `AMD Ryzen 5 3600X 6-Core Processor, family 0x17 model 0x71 stepping 0,
+/// 6c/12t, znver2, x86-64-v3`. Parts the CPU doesn't report are left out.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Summary {
+    /// Vendor identification string (LEAF=0x00).
+    pub vendor: Option<VendorInfo>,
+    /// Processor brand string (LEAF=0x8000_0002..=0x8000_0004).
+    pub brand: Option<ProcessorBrandString>,
+    /// `(family, model, stepping)`, see [`crate::FeatureInfo::family_id`].
+    pub signature: Option<(u8, u8, u8)>,
+    /// Cores per package, see [`CpuId::num_cores`].
+    pub cores: Option<usize>,
+    /// Logical processors per package, see [`CpuId::num_logical_cpus`].
+    pub threads: Option<usize>,
+    /// Base frequency, see [`CpuId::frequency`].
+    pub base_frequency: Option<Frequency>,
+    /// Closest `-march=` name, see [`CpuId::march`].
+    pub march: Option<&'static str>,
+    /// The x86-64 microarchitecture level, see [`CpuId::x86_64_level`].
+    pub x86_64_level: Option<&'static str>,
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut sep = "";
+        let mut part = |f: &mut Formatter<'_>, args: fmt::Arguments<'_>| {
+            let res = write!(f, "{}{}", sep, args);
+            sep = ", ";
+            res
+        };
+
+        match (&self.brand, &self.vendor) {
+            (Some(brand), _) if !brand.brand_bytes().is_empty() => {
+                part(f, format_args!("{}", brand))?
+            }
+            (_, Some(vendor)) if vendor.as_bytes().iter().any(|&b| b != 0) => {
+                part(f, format_args!("{}", vendor))?
+            }
+            _ => part(f, format_args!("unknown CPU"))?,
+        }
+        if let Some((family, model, stepping)) = self.signature {
+            part(
+                f,
+                format_args!(
+                    "family {:#x} model {:#x} stepping {}",
+                    family, model, stepping
+                ),
+            )?;
+        }
+        if let (Some(cores), Some(threads)) = (self.cores, self.threads) {
+            part(f, format_args!("{}c/{}t", cores, threads))?;
+        }
+        if let Some(base) = self.base_frequency {
+            part(f, format_args!("{} MHz", base.hz / 1_000_000))?;
+        }
+        // The march is the level itself for CPUs without a known model.
+        if let Some(march) = self.march.filter(|&march| Some(march) != self.x86_64_level) {
+            part(f, format_args!("{}", march))?;
+        }
+        if let Some(level) = self.x86_64_level {
+            part(f, format_args!("{}", level))?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: CpuIdReader> CpuId<R> {
+    /// Collects the vendor, brand, family/model/stepping, core and thread
+    /// counts, base frequency and microarchitecture of the CPU.
+    ///
+    /// # Example
+    /// ```
+    /// use raw_cpuid::CpuId;
+    ///
+    /// // e.g., "Intel(R) Xeon(R) Gold 6252 CPU @ 2.10GHz, family 0x6 model 0x55
+    /// // stepping 7, 24c/48t, 2100 MHz, cascadelake, x86-64-v4"
+    /// println!("CPU: {}", CpuId::new().summary());
+    /// ```
+    pub fn summary(&self) -> Summary {
+        Summary {
+            vendor: self.get_vendor_info(),
+            brand: self.get_processor_brand_string(),
+            signature: self
+                .get_feature_info()
+                .map(|info| (info.family_id(), info.model_id(), info.stepping_id())),
+            cores: self.num_cores(),
+            threads: self.num_logical_cpus(),
+            base_frequency: self.frequency().base,
+            march: self.march(),
+            x86_64_level: self.x86_64_level(),
+        }
+    }
+}
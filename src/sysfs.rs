@@ -0,0 +1,216 @@
+//! Cross-check the decoded cache and topology information against what Linux
+//! reports in sysfs (`/sys/devices/system/cpu/cpuN/{cache,topology}`).
+//!
+//! Linux derives these files from the same CPUID leafs, so a mismatch usually
+//! means one of the two decodes a leaf differently (e.g., on unusual
+//! hardware).
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use std::io;
+use std::path::Path;
+
+use crate::{CacheType, CpuId, CpuIdReader};
+
+/// Default location of the per-CPU sysfs directories.
+pub const SYSFS_CPU_ROOT: &str = "/sys/devices/system/cpu";
+
+/// A sysfs file whose value differs from the one derived from CPUID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SysfsMismatch {
+    /// Logical CPU the file belongs to.
+    pub cpu: usize,
+    /// The file relative to the CPU's directory, e.g., `cache/index0/size`.
+    pub file: String,
+    /// Value read from sysfs.
+    pub sysfs: String,
+    /// Value derived from CPUID, or `None` if CPUID doesn't report it (e.g.,
+    /// a cache that is missing).
+    pub cpuid: Option<String>,
+}
+
+impl fmt::Display for SysfsMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cpu{} {}: sysfs={}, cpuid={}",
+            self.cpu,
+            self.file,
+            self.sysfs,
+            self.cpuid.as_deref().unwrap_or("-")
+        )
+    }
+}
+
+/// Mismatches between sysfs and CPUID for all checked CPUs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SysfsReport {
+    /// All mismatches, ordered by CPU.
+    pub mismatches: Vec<SysfsMismatch>,
+}
+
+impl SysfsReport {
+    /// Checks every online CPU against [`SYSFS_CPU_ROOT`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use raw_cpuid::SysfsReport;
+    ///
+    /// let report = SysfsReport::from_all_cores().unwrap();
+    /// if !report.is_consistent() {
+    ///     print!("{}", report);
+    /// }
+    /// ```
+    #[cfg(feature = "linux")]
+    pub fn from_all_cores() -> io::Result<Self> {
+        let mut report = SysfsReport::default();
+        let mut result = Ok(());
+        CpuId::for_each_core(|cpu, cpuid| {
+            if result.is_ok() {
+                result = report.check_cpu(cpu, cpuid, Path::new(SYSFS_CPU_ROOT));
+            }
+        })?;
+        result.map(|_| report)
+    }
+
+    /// Compares `cpuid`, which must read from logical CPU `cpu`, with the
+    /// sysfs files of that CPU below `root` (usually [`SYSFS_CPU_ROOT`]) and
+    /// adds all mismatches to the report.
+    ///
+    /// Checked are the level, type, size, line size, number of ways and sets
+    /// of every cache, and the package, die and core ID of the CPU. Files
+    /// that don't exist are skipped.
+    pub fn check_cpu<R: CpuIdReader>(
+        &mut self,
+        cpu: usize,
+        cpuid: &CpuId<R>,
+        root: &Path,
+    ) -> io::Result<()> {
+        let dir = root.join(format!("cpu{}", cpu));
+        self.check_caches(cpu, cpuid, &dir)?;
+        self.check_topology(cpu, cpuid, &dir)
+    }
+
+    /// Did sysfs and CPUID agree on everything?
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    fn check_caches<R: CpuIdReader>(
+        &mut self,
+        cpu: usize,
+        cpuid: &CpuId<R>,
+        dir: &Path,
+    ) -> io::Result<()> {
+        let caches: Vec<_> = cpuid
+            .get_cache_parameters()
+            .map(|iter| iter.collect())
+            .unwrap_or_default();
+
+        for index in 0.. {
+            let file = |name: &str| format!("cache/index{}/{}", index, name);
+            let Some(level) = read(dir, &file("level"))? else {
+                break;
+            };
+            let typ = read(dir, &file("type"))?.unwrap_or_default();
+
+            let cache = caches.iter().find(|cache| {
+                cache.level().to_string() == level && cache_type_name(cache.cache_type()) == typ
+            });
+            let Some(cache) = cache else {
+                self.mismatches.push(SysfsMismatch {
+                    cpu,
+                    file: format!("cache/index{}", index),
+                    sysfs: format!("L{} {}", level, typ),
+                    cpuid: None,
+                });
+                continue;
+            };
+
+            let expected = [
+                ("size", format!("{}K", cache.size() / 1024)),
+                (
+                    "coherency_line_size",
+                    cache.coherency_line_size().to_string(),
+                ),
+                ("ways_of_associativity", cache.ways().to_string()),
+                ("number_of_sets", cache.sets().to_string()),
+            ];
+            for (name, value) in expected {
+                self.compare(cpu, dir, &file(name), value)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_topology<R: CpuIdReader>(
+        &mut self,
+        cpu: usize,
+        cpuid: &CpuId<R>,
+        dir: &Path,
+    ) -> io::Result<()> {
+        let Some(id) = cpuid.apic_id() else {
+            return Ok(());
+        };
+        let layout = id.layout();
+        // Linux numbers cores within the package, not within the die.
+        let core_bits = layout.package_shift.saturating_sub(layout.smt_shift);
+        let core_id = id.id().checked_shr(layout.smt_shift).unwrap_or(0)
+            & 1u32.checked_shl(core_bits).map_or(u32::MAX, |n| n - 1);
+
+        let expected = [
+            ("topology/physical_package_id", id.package_id()),
+            ("topology/die_id", id.die_id()),
+            ("topology/core_id", core_id),
+        ];
+        for (file, value) in expected {
+            self.compare(cpu, dir, file, value.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    fn compare(&mut self, cpu: usize, dir: &Path, file: &str, cpuid: String) -> io::Result<()> {
+        if let Some(sysfs) = read(dir, file)? {
+            if sysfs != cpuid {
+                self.mismatches.push(SysfsMismatch {
+                    cpu,
+                    file: file.to_string(),
+                    sysfs,
+                    cpuid: Some(cpuid),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SysfsReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for mismatch in self.mismatches.iter() {
+            writeln!(f, "{}", mismatch)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads a sysfs file, `None` if it doesn't exist.
+fn read(dir: &Path, file: &str) -> io::Result<Option<String>> {
+    match std::fs::read_to_string(dir.join(file)) {
+        Ok(value) => Ok(Some(value.trim().to_string())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Name of a cache type as used by Linux.
+fn cache_type_name(typ: CacheType) -> &'static str {
+    match typ {
+        CacheType::Data => "Data",
+        CacheType::Instruction => "Instruction",
+        CacheType::Unified => "Unified",
+        _ => "Unknown",
+    }
+}
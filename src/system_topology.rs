@@ -0,0 +1,249 @@
+//! Build the package → die → core → thread tree of the whole system from the
+//! APIC IDs of all logical processors.
+use alloc::vec::Vec;
+
+use crate::{ApicId, ApicIdLayout, CpuIdSnapshot};
+
+/// A logical processor (SMT thread).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TopologyThread {
+    /// ID of the thread within its core.
+    pub id: u32,
+    /// The full (x2)APIC ID.
+    pub apic_id: u32,
+    /// The logical CPU as numbered by the OS, if known.
+    pub cpu: Option<usize>,
+}
+
+/// A core and its threads, ordered by ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TopologyCore {
+    /// ID of the core within its die.
+    pub id: u32,
+    /// The threads of the core.
+    pub threads: Vec<TopologyThread>,
+}
+
+/// A die and its cores, ordered by ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TopologyDie {
+    /// ID of the die within its package (0 if the CPU doesn't enumerate dies).
+    pub id: u32,
+    /// The cores of the die.
+    pub cores: Vec<TopologyCore>,
+}
+
+/// A physical package (socket) and its dies, ordered by ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TopologyPackage {
+    /// ID of the package.
+    pub id: u32,
+    /// The dies of the package.
+    pub dies: Vec<TopologyDie>,
+}
+
+/// The topology of the whole system: all packages, dies, cores and threads.
+///
+/// Built by decomposing the APIC IDs of all logical processors with the
+/// [`ApicIdLayout`] of the CPU.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(feature = "std")]
+/// # {
+/// use raw_cpuid::SystemTopology;
+///
+/// let topology = SystemTopology::from_all_cores().unwrap();
+/// println!(
+///     "{} packages, {} dies, {} cores, {} threads",
+///     topology.num_packages(),
+///     topology.num_dies(),
+///     topology.num_cores(),
+///     topology.num_threads()
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct SystemTopology {
+    layout: ApicIdLayout,
+    /// The packages, ordered by ID.
+    pub packages: Vec<TopologyPackage>,
+}
+
+impl SystemTopology {
+    /// Creates an empty topology, add logical processors with
+    /// [`SystemTopology::add`].
+    pub fn new(layout: ApicIdLayout) -> Self {
+        Self {
+            layout,
+            packages: Vec::new(),
+        }
+    }
+
+    /// Builds the topology from the (x2)APIC IDs of all logical processors.
+    pub fn from_apic_ids<I: IntoIterator<Item = u32>>(layout: ApicIdLayout, apic_ids: I) -> Self {
+        let mut topology = Self::new(layout);
+        for apic_id in apic_ids {
+            topology.add(apic_id, None);
+        }
+        topology
+    }
+
+    /// Builds the topology from `(cpu, snapshot)` pairs, one per logical
+    /// processor.
+    ///
+    /// The layout is taken from the first snapshot that reports one. Returns
+    /// `None` if no snapshot reports a layout.
+    pub fn from_snapshots(snapshots: &[(usize, CpuIdSnapshot)]) -> Option<Self> {
+        let mut topology: Option<Self> = None;
+        for (cpu, snapshot) in snapshots {
+            let cpuid = snapshot.cpuid();
+            let Some(apic_id) = cpuid.x2apic_id() else {
+                continue;
+            };
+            if topology.is_none() {
+                topology = cpuid.apic_id_layout().map(Self::new);
+            }
+            if let Some(topology) = topology.as_mut() {
+                topology.add(apic_id, Some(*cpu));
+            }
+        }
+        topology
+    }
+
+    /// Reads the APIC ID of every logical processor (see
+    /// [`crate::CpuId::for_each_core`]) and builds the topology.
+    ///
+    /// Fails with [`std::io::ErrorKind::Unsupported`] if the CPU doesn't
+    /// report an [`ApicIdLayout`].
+    #[cfg(feature = "std")]
+    pub fn from_all_cores() -> std::io::Result<Self> {
+        let mut layout = None;
+        let mut apic_ids = Vec::new();
+        crate::CpuId::for_each_core(|cpu, cpuid| {
+            if layout.is_none() {
+                layout = cpuid.apic_id_layout();
+            }
+            if let Some(apic_id) = cpuid.x2apic_id() {
+                apic_ids.push((cpu, apic_id));
+            }
+        })?;
+
+        let layout = layout.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "no topology information available",
+            )
+        })?;
+        let mut topology = Self::new(layout);
+        for (cpu, apic_id) in apic_ids {
+            topology.add(apic_id, Some(cpu));
+        }
+        Ok(topology)
+    }
+
+    /// Adds a logical processor, `cpu` is its number in the OS (if known).
+    ///
+    /// Adding the same APIC ID twice has no effect.
+    pub fn add(&mut self, apic_id: u32, cpu: Option<usize>) -> &mut Self {
+        let id = ApicId::new(apic_id, self.layout);
+
+        let package = find_or_insert(
+            &mut self.packages,
+            id.package_id(),
+            |p| p.id,
+            |id| TopologyPackage {
+                id,
+                dies: Vec::new(),
+            },
+        );
+        let die = find_or_insert(
+            &mut package.dies,
+            id.die_id(),
+            |d| d.id,
+            |id| TopologyDie {
+                id,
+                cores: Vec::new(),
+            },
+        );
+        let core = find_or_insert(
+            &mut die.cores,
+            id.core_id(),
+            |c| c.id,
+            |id| TopologyCore {
+                id,
+                threads: Vec::new(),
+            },
+        );
+        find_or_insert(
+            &mut core.threads,
+            id.smt_id(),
+            |t| t.id,
+            |id| TopologyThread { id, apic_id, cpu },
+        );
+
+        self
+    }
+
+    /// The layout used to decompose the APIC IDs.
+    pub fn layout(&self) -> ApicIdLayout {
+        self.layout
+    }
+
+    /// Number of packages (sockets).
+    pub fn num_packages(&self) -> usize {
+        self.packages.len()
+    }
+
+    /// Number of dies in all packages.
+    pub fn num_dies(&self) -> usize {
+        self.packages.iter().map(|p| p.dies.len()).sum()
+    }
+
+    /// Number of cores in all packages.
+    pub fn num_cores(&self) -> usize {
+        self.dies().map(|d| d.cores.len()).sum()
+    }
+
+    /// Number of logical processors in all packages.
+    pub fn num_threads(&self) -> usize {
+        self.cores().map(|c| c.threads.len()).sum()
+    }
+
+    /// All dies, ordered by package and die ID.
+    pub fn dies(&self) -> impl Iterator<Item = &TopologyDie> {
+        self.packages.iter().flat_map(|p| p.dies.iter())
+    }
+
+    /// All cores, ordered by package, die and core ID.
+    pub fn cores(&self) -> impl Iterator<Item = &TopologyCore> {
+        self.dies().flat_map(|d| d.cores.iter())
+    }
+
+    /// All logical processors, ordered by APIC ID.
+    pub fn threads(&self) -> impl Iterator<Item = &TopologyThread> {
+        self.cores().flat_map(|c| c.threads.iter())
+    }
+}
+
+/// Returns the entry with the given ID, inserting it (in order) if missing.
+fn find_or_insert<T>(
+    entries: &mut Vec<T>,
+    id: u32,
+    key: impl Fn(&T) -> u32,
+    new: impl FnOnce(u32) -> T,
+) -> &mut T {
+    let idx = match entries.binary_search_by_key(&id, key) {
+        Ok(idx) => idx,
+        Err(idx) => {
+            entries.insert(idx, new(id));
+            idx
+        }
+    };
+    &mut entries[idx]
+}
@@ -0,0 +1,241 @@
+//! Real CPUID dumps to test feature detection against, e.g., in downstream
+//! crates (requires the `test-util` feature).
+//!
+//! The dumps are stored as captured, so leafs the CPU didn't report (e.g., the
+//! last subleaf of an enumeration) read as zero. Only the CPUs listed in
+//! [`NAMES`] are included: dumps of an old Pentium, Skylake, Ice Lake, Zen 1,
+//! Zen 3, Zen 4 and hybrid Alder Lake CPUs will be added once they have been
+//! captured on real hardware. Until then [`FakeCpu`](crate::FakeCpu) presets
+//! (e.g., `FakeCpu::skylake`) fabricate some of these CPUs.
+//!
+//! # Example
+//! ```
+//! use raw_cpuid::testdata;
+//!
+//! for &name in testdata::NAMES {
+//!     let snapshot = testdata::load(name).unwrap();
+//!     let cpuid = snapshot.cpuid();
+//!     assert!(cpuid.get_vendor_info().is_some(), "{}", name);
+//! }
+//! ```
+use alloc::collections::BTreeMap;
+
+use crate::{CpuIdResult, CpuIdSnapshot};
+
+/// Names of the CPUs with a dump in this module.
+pub const NAMES: &[&str] = &["xeon-gold-6252", "ryzen-5-3600x", "kvm-sapphire-rapids"];
+
+/// Loads the dump of the CPU `name` (see [`NAMES`]).
+///
+/// Leafs that are not part of the dump read as zero.
+pub fn load(name: &str) -> Option<CpuIdSnapshot> {
+    let leafs = match name {
+        "xeon-gold-6252" => XEON_GOLD_6252,
+        "ryzen-5-3600x" => RYZEN_5_3600X,
+        "kvm-sapphire-rapids" => KVM_SAPPHIRE_RAPIDS,
+        _ => return None,
+    };
+
+    let leafs: BTreeMap<_, _> = leafs
+        .iter()
+        .map(|&(leaf, subleaf, [eax, ebx, ecx, edx])| {
+            ((leaf, subleaf), CpuIdResult { eax, ebx, ecx, edx })
+        })
+        .collect();
+    Some(CpuIdSnapshot::from(leafs))
+}
+
+/// Intel Xeon Gold 6252 (Cascade Lake).
+#[rustfmt::skip]
+const XEON_GOLD_6252: &[(u32, u32, [u32; 4])] = &[
+    (0x0000_0000, 0, [0x00000016, 0x756e6547, 0x6c65746e, 0x49656e69]),
+    (0x0000_0001, 0, [0x00050657, 0xc7400800, 0x7ffefbff, 0xbfebfbff]),
+    (0x0000_0002, 0, [0x76036301, 0x00f0b5ff, 0x00000000, 0x00c30000]),
+    (0x0000_0003, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0004, 0, [0x7c004121, 0x01c0003f, 0x0000003f, 0x00000000]),
+    (0x0000_0004, 1, [0x7c004122, 0x01c0003f, 0x0000003f, 0x00000000]),
+    (0x0000_0004, 2, [0x7c004143, 0x03c0003f, 0x000003ff, 0x00000000]),
+    (0x0000_0004, 3, [0x7c0fc163, 0x0280003f, 0x0000cfff, 0x00000005]),
+    (0x0000_0005, 0, [0x00000040, 0x00000040, 0x00000003, 0x00002020]),
+    (0x0000_0006, 0, [0x00000077, 0x00000002, 0x00000009, 0x00000000]),
+    (0x0000_0007, 0, [0x00000000, 0xd39ff7eb, 0x00000818, 0xbc000400]),
+    (0x0000_0008, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0009, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_000a, 0, [0x07300404, 0x00000000, 0x00000000, 0x00000603]),
+    (0x0000_000b, 0, [0x00000001, 0x00000002, 0x00000100, 0x000000c7]),
+    (0x0000_000b, 1, [0x00000006, 0x00000030, 0x00000201, 0x000000c7]),
+    (0x0000_000c, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_000d, 0, [0x000002ff, 0x00000a88, 0x00000a88, 0x00000000]),
+    (0x0000_000d, 1, [0x0000000f, 0x00000a08, 0x00000100, 0x00000000]),
+    (0x0000_000d, 2, [0x00000100, 0x00000240, 0x00000000, 0x00000000]),
+    (0x0000_000d, 3, [0x00000040, 0x000003c0, 0x00000000, 0x00000000]),
+    (0x0000_000d, 4, [0x00000040, 0x00000400, 0x00000000, 0x00000000]),
+    (0x0000_000d, 5, [0x00000040, 0x00000440, 0x00000000, 0x00000000]),
+    (0x0000_000d, 6, [0x00000200, 0x00000480, 0x00000000, 0x00000000]),
+    (0x0000_000d, 7, [0x00000400, 0x00000680, 0x00000000, 0x00000000]),
+    (0x0000_000d, 8, [0x00000080, 0x00000000, 0x00000001, 0x00000000]),
+    (0x0000_000d, 9, [0x00000008, 0x00000a80, 0x00000000, 0x00000000]),
+    (0x0000_000e, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_000f, 0, [0x00000000, 0x000000cf, 0x00000000, 0x00000002]),
+    (0x0000_000f, 1, [0x00000000, 0x0001a000, 0x000000cf, 0x00000007]),
+    (0x0000_0010, 0, [0x00000000, 0x0000000a, 0x00000000, 0x00000000]),
+    (0x0000_0010, 1, [0x0000000a, 0x00000600, 0x00000004, 0x0000000f]),
+    (0x0000_0010, 3, [0x00000059, 0x00000000, 0x00000004, 0x00000007]),
+    (0x0000_0011, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0012, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0013, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0014, 0, [0x00000001, 0x0000000f, 0x00000007, 0x00000000]),
+    (0x0000_0014, 1, [0x02490002, 0x003f3fff, 0x00000000, 0x00000000]),
+    (0x0000_0015, 0, [0x00000002, 0x000000a8, 0x00000000, 0x00000000]),
+    (0x0000_0016, 0, [0x00000834, 0x00000e74, 0x00000064, 0x00000000]),
+    (0x2000_0000, 0, [0x00000834, 0x00000e74, 0x00000064, 0x00000000]),
+    (0x8000_0000, 0, [0x80000008, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0001, 0, [0x00000000, 0x00000000, 0x00000121, 0x2c100800]),
+    (0x8000_0002, 0, [0x65746e49, 0x2952286c, 0x6f655820, 0x2952286e]),
+    (0x8000_0003, 0, [0x6c6f4720, 0x32362064, 0x43203235, 0x40205550]),
+    (0x8000_0004, 0, [0x312e3220, 0x7a484730, 0x00000000, 0x00000000]),
+    (0x8000_0005, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0006, 0, [0x00000000, 0x00000000, 0x01006040, 0x00000000]),
+    (0x8000_0007, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000100]),
+    (0x8000_0008, 0, [0x0000302e, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8086_0000, 0, [0x00000834, 0x00000e74, 0x00000064, 0x00000000]),
+    (0xc000_0000, 0, [0x00000834, 0x00000e74, 0x00000064, 0x00000000]),
+];
+
+/// AMD Ryzen 5 3600X (Zen 2, Matisse).
+#[rustfmt::skip]
+const RYZEN_5_3600X: &[(u32, u32, [u32; 4])] = &[
+    (0x0000_0000, 0, [0x00000010, 0x68747541, 0x444d4163, 0x69746e65]),
+    (0x0000_0001, 0, [0x00870f10, 0x000c0800, 0x7ed8320b, 0x178bfbff]),
+    (0x0000_0002, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0003, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0005, 0, [0x00000040, 0x00000040, 0x00000003, 0x00000011]),
+    (0x0000_0006, 0, [0x00000004, 0x00000000, 0x00000001, 0x00000000]),
+    (0x0000_0007, 0, [0x00000000, 0x219c91a9, 0x00400004, 0x00000000]),
+    (0x0000_0008, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0009, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_000a, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_000b, 0, [0x00000001, 0x00000002, 0x00000100, 0x00000000]),
+    (0x0000_000b, 1, [0x00000007, 0x0000000c, 0x00000201, 0x00000000]),
+    (0x0000_000c, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_000d, 0, [0x00000207, 0x00000340, 0x00000380, 0x00000000]),
+    (0x0000_000d, 1, [0x0000000f, 0x00000340, 0x00000000, 0x00000000]),
+    (0x0000_000d, 2, [0x00000100, 0x00000240, 0x00000000, 0x00000000]),
+    (0x0000_000d, 9, [0x00000040, 0x00000340, 0x00000000, 0x00000000]),
+    (0x0000_000e, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_000f, 0, [0x00000000, 0x000000ff, 0x00000000, 0x00000002]),
+    (0x0000_000f, 1, [0x00000000, 0x00000040, 0x000000ff, 0x00000007]),
+    (0x0000_0010, 0, [0x00000000, 0x00000002, 0x00000000, 0x00000000]),
+    (0x0000_0010, 1, [0x0000000f, 0x00000000, 0x00000004, 0x0000000f]),
+    (0x2000_0000, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0000, 0, [0x80000020, 0x68747541, 0x444d4163, 0x69746e65]),
+    (0x8000_0001, 0, [0x00870f10, 0x20000000, 0x75c237ff, 0x2fd3fbff]),
+    (0x8000_0002, 0, [0x20444d41, 0x657a7952, 0x2035206e, 0x30303633]),
+    (0x8000_0003, 0, [0x2d362058, 0x65726f43, 0x6f725020, 0x73736563]),
+    (0x8000_0004, 0, [0x2020726f, 0x20202020, 0x20202020, 0x00202020]),
+    (0x8000_0005, 0, [0xff40ff40, 0xff40ff40, 0x20080140, 0x20080140]),
+    (0x8000_0006, 0, [0x48006400, 0x68006400, 0x02006140, 0x01009140]),
+    (0x8000_0007, 0, [0x00000000, 0x0000001b, 0x00000000, 0x00006799]),
+    (0x8000_0008, 0, [0x00003030, 0x010eb757, 0x0000700b, 0x00010000]),
+    (0x8000_0009, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_000a, 0, [0x00000001, 0x00008000, 0x00000000, 0x0013bcff]),
+    (0x8000_000b, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_000c, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_000d, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_000e, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_000f, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0010, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0011, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0012, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0013, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0014, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0015, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0016, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0017, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0018, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0019, 0, [0xf040f040, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_001a, 0, [0x00000006, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_001b, 0, [0x000003ff, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_001c, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_001d, 0, [0x00004121, 0x01c0003f, 0x0000003f, 0x00000000]),
+    (0x8000_001d, 1, [0x00004122, 0x01c0003f, 0x0000003f, 0x00000000]),
+    (0x8000_001d, 2, [0x00004143, 0x01c0003f, 0x000003ff, 0x00000002]),
+    (0x8000_001d, 3, [0x00014163, 0x03c0003f, 0x00003fff, 0x00000001]),
+    (0x8000_001e, 0, [0x00000000, 0x00000100, 0x00000000, 0x00000000]),
+    (0x8000_001f, 0, [0x0001000f, 0x0000016f, 0x000001fd, 0x00000001]),
+    (0x8000_0020, 0, [0x00000000, 0x00000002, 0x00000000, 0x00000000]),
+    (0x8000_0020, 1, [0x0000000b, 0x00000000, 0x00000000, 0x0000000f]),
+    (0x8086_0000, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0xc000_0000, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+];
+
+/// A single-vCPU KVM guest (Firecracker) on a Sapphire Rapids Xeon.
+#[rustfmt::skip]
+const KVM_SAPPHIRE_RAPIDS: &[(u32, u32, [u32; 4])] = &[
+    (0x0000_0000, 0, [0x00000020, 0x756e6547, 0x6c65746e, 0x49656e69]),
+    (0x0000_0001, 0, [0x000806f8, 0x00010800, 0xfffa3203, 0x0f8bfbff]),
+    (0x0000_0002, 0, [0x00feff01, 0x000000f0, 0x00000000, 0x00000000]),
+    (0x0000_0003, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0004, 0, [0x00000121, 0x02c0003f, 0x0000003f, 0x00000000]),
+    (0x0000_0004, 1, [0x00000122, 0x01c0003f, 0x0000003f, 0x00000000]),
+    (0x0000_0004, 2, [0x00000143, 0x03c0003f, 0x000007ff, 0x00000000]),
+    (0x0000_0004, 3, [0x00000163, 0x0380003f, 0x0001bfff, 0x00000004]),
+    (0x0000_0004, 4, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0005, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0006, 0, [0x00000004, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0007, 0, [0x00000002, 0xf1bf27eb, 0x1b415fde, 0xbfd14410]),
+    (0x0000_0007, 1, [0x00001c30, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0007, 2, [0x00000000, 0x00000000, 0x00000000, 0x00000017]),
+    (0x0000_0008, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0009, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_000a, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_000b, 0, [0x00000000, 0x00000001, 0x00000100, 0x00000000]),
+    (0x0000_000b, 1, [0x00000005, 0x00000001, 0x00000201, 0x00000000]),
+    (0x0000_000b, 2, [0x00000000, 0x00000000, 0x00000002, 0x00000000]),
+    (0x0000_000c, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_000d, 0, [0x000602e7, 0x00002b00, 0x00002b00, 0x00000000]),
+    (0x0000_000d, 1, [0x0000001f, 0x00002a00, 0x00001800, 0x00000000]),
+    (0x0000_000d, 2, [0x00000100, 0x00000240, 0x00000000, 0x00000000]),
+    (0x0000_000d, 5, [0x00000040, 0x00000440, 0x00000000, 0x00000000]),
+    (0x0000_000d, 6, [0x00000200, 0x00000480, 0x00000000, 0x00000000]),
+    (0x0000_000d, 7, [0x00000400, 0x00000680, 0x00000000, 0x00000000]),
+    (0x0000_000d, 9, [0x00000008, 0x00000a80, 0x00000000, 0x00000000]),
+    (0x0000_000d, 11, [0x00000010, 0x00000000, 0x00000001, 0x00000000]),
+    (0x0000_000d, 12, [0x00000018, 0x00000000, 0x00000001, 0x00000000]),
+    (0x0000_000d, 17, [0x00000040, 0x00000ac0, 0x00000002, 0x00000000]),
+    (0x0000_000d, 18, [0x00002000, 0x00000b00, 0x00000006, 0x00000000]),
+    (0x0000_000e, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_000f, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0010, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0011, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0012, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0012, 1, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0012, 2, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0013, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0014, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0015, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0016, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0017, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0018, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_0019, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_001a, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_001b, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_001c, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_001d, 0, [0x00000001, 0x00000000, 0x00000000, 0x00000000]),
+    (0x0000_001e, 0, [0x00000000, 0x00004010, 0x00000000, 0x00000000]),
+    (0x0000_001f, 0, [0x00000000, 0x00000001, 0x00000100, 0x00000000]),
+    (0x0000_001f, 1, [0x00000005, 0x00000001, 0x00000201, 0x00000000]),
+    (0x0000_001f, 2, [0x00000000, 0x00000000, 0x00000002, 0x00000000]),
+    (0x0000_0020, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x4000_0000, 0, [0x40000001, 0x4b4d564b, 0x564b4d56, 0x0000004d]),
+    (0x4000_0001, 0, [0x01007efb, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0000, 0, [0x80000008, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0001, 0, [0x00000000, 0x00000000, 0x00000121, 0x2c100800]),
+    (0x8000_0002, 0, [0x65746e49, 0x2952286c, 0x6f655820, 0x2952286e]),
+    (0x8000_0003, 0, [0x6f725020, 0x73736563, 0x0000726f, 0x00000000]),
+    (0x8000_0004, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0005, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000000]),
+    (0x8000_0006, 0, [0x00000000, 0x00000000, 0x08007040, 0x00000000]),
+    (0x8000_0007, 0, [0x00000000, 0x00000000, 0x00000000, 0x00000100]),
+    (0x8000_0008, 0, [0x002e392e, 0x0100d200, 0x00000000, 0x00000000]),
+];
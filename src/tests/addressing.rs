@@ -0,0 +1,41 @@
+//! Address sizes and paging features.
+use super::synthetic::{intel, res};
+use crate::{CpuId, CpuIdResult};
+
+#[test]
+fn address_bits_fallbacks() {
+    // Without leaf 0x8000_0008 (the reported values are checked with the
+    // dumps):
+    static PAE: &[((u32, u32), CpuIdResult)] = &[((0x1, 0), res(0, 0, 0, 1 << 6))];
+    let cpuid = CpuId::with_cpuid_fn(intel(PAE));
+    assert_eq!(cpuid.physical_address_bits(), 36);
+    assert_eq!(cpuid.guest_physical_address_bits(), 36);
+    assert_eq!(cpuid.linear_address_bits(), 32);
+
+    static NONE: &[((u32, u32), CpuIdResult)] = &[((0x1, 0), res(0, 0, 0, 0))];
+    let cpuid = CpuId::with_cpuid_fn(intel(NONE));
+    assert_eq!(cpuid.physical_address_bits(), 32);
+}
+
+#[test]
+fn five_level_paging() {
+    // LA57 without leaf 0x8000_0008:
+    static LA57: &[((u32, u32), CpuIdResult)] = &[((0x7, 0), res(0, 0, 1 << 16, 0))];
+    let cpuid = CpuId::with_cpuid_fn(intel(LA57));
+    assert!(cpuid.supports_5_level_paging());
+    assert_eq!(cpuid.max_linear_address_bits(), 57);
+
+    // No leaf 7 at all:
+    static NONE: &[((u32, u32), CpuIdResult)] = &[((0x1, 0), res(0, 0, 0, 0))];
+    let cpuid = CpuId::with_cpuid_fn(intel(NONE));
+    assert!(!cpuid.supports_5_level_paging());
+    assert_eq!(cpuid.max_linear_address_bits(), 32);
+}
+
+#[test]
+fn extended_feature_predicates_without_leaf() {
+    // Without leaf 0x8000_0001:
+    static NONE: &[((u32, u32), CpuIdResult)] = &[((0x1, 0), res(0, 0, 0, 0))];
+    let cpuid = CpuId::with_cpuid_fn(intel(NONE));
+    assert!(!cpuid.has_nx() && !cpuid.has_64bit_mode());
+}
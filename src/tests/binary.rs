@@ -0,0 +1,71 @@
+use crate::{testdata, CpuIdSnapshot, SnapshotDecodeError, SNAPSHOT_MAGIC};
+
+#[test]
+fn round_trip() {
+    for &name in testdata::NAMES {
+        let snapshot = testdata::load(name).unwrap();
+        let bytes = snapshot.to_bytes();
+        assert_eq!(&bytes[..4], &SNAPSHOT_MAGIC);
+        assert_eq!(bytes.len(), 12 + 24 * snapshot.len());
+        assert_eq!(CpuIdSnapshot::from_bytes(&bytes), Ok(snapshot));
+    }
+
+    let empty = CpuIdSnapshot::default().to_bytes();
+    assert_eq!(empty, b"RCID\x01\x00\x00\x00\x00\x00\x00\x00");
+    assert_eq!(
+        CpuIdSnapshot::from_bytes(&empty),
+        Ok(CpuIdSnapshot::default())
+    );
+}
+
+#[test]
+fn invalid() {
+    let bytes = testdata::load("ryzen-5-3600x").unwrap().to_bytes();
+
+    assert_eq!(
+        CpuIdSnapshot::from_bytes(&bytes[..8]),
+        Err(SnapshotDecodeError::Truncated)
+    );
+    assert_eq!(
+        CpuIdSnapshot::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(SnapshotDecodeError::Truncated)
+    );
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        CpuIdSnapshot::from_bytes(&trailing),
+        Err(SnapshotDecodeError::TrailingBytes)
+    );
+
+    let mut magic = bytes.clone();
+    magic[0] = b'X';
+    assert_eq!(
+        CpuIdSnapshot::from_bytes(&magic),
+        Err(SnapshotDecodeError::InvalidMagic)
+    );
+
+    let mut version = bytes;
+    version[4] = 2;
+    assert_eq!(
+        CpuIdSnapshot::from_bytes(&version),
+        Err(SnapshotDecodeError::UnsupportedVersion(2))
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn read_write() {
+    let snapshot = testdata::load("xeon-gold-6252").unwrap();
+    let mut buffer = std::vec::Vec::new();
+    snapshot.write_to(&mut buffer).unwrap();
+    buffer.extend_from_slice(b"more data");
+
+    let mut reader = &buffer[..];
+    assert_eq!(CpuIdSnapshot::read_from(&mut reader).unwrap(), snapshot);
+    assert_eq!(reader, b"more data");
+
+    let truncated = &buffer[..100];
+    let err = CpuIdSnapshot::read_from(truncated).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
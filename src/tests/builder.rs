@@ -0,0 +1,192 @@
+use crate::{ConsistencyReport, CpuIdBuilder, CpuIdResult, CpuIdSnapshot, Feature};
+
+/// A tiny CPU with a few leafs and subleafs.
+fn cpuid_reader(eax: u32, ecx: u32) -> CpuIdResult {
+    let (eax, ebx, ecx, edx) = match (eax, ecx) {
+        // "GenuineIntel", max leaf 0xb
+        (0x0, _) => (0xb, 0x756e6547, 0x6c65746e, 0x49656e69),
+        (0x1, _) => (0x000306a9, 0x02100800, 0x7fbae3ff, 0xbfebfbff),
+        // L1d cache, followed by Null
+        (0x4, 0) => (0x1c004121, 0x01c0003f, 0x0000003f, 0x0),
+        (0x7, 0) => (0x0, 0x00000281, 0x0, 0x0),
+        // SMT and core level, followed by Invalid
+        (0xb, 0) => (0x1, 0x2, 0x100, 0x3),
+        (0xb, 1) => (0x4, 0x4, 0x201, 0x3),
+        (0xb, 2) => (0x0, 0x0, 0x2, 0x3),
+        (0x8000_0000, _) => (0x8000_0001, 0x0, 0x0, 0x0),
+        (0x8000_0001, _) => (0x0, 0x0, 0x1, 0x28100800),
+        _ => (0x0, 0x0, 0x0, 0x0),
+    };
+    CpuIdResult { eax, ebx, ecx, edx }
+}
+
+#[test]
+fn reads_all_leafs() {
+    let builder = CpuIdBuilder::with_cpuid_reader(cpuid_reader);
+    let table = builder.build();
+
+    let keys: std::vec::Vec<(u32, u32)> = table.keys().copied().collect();
+    assert_eq!(
+        keys,
+        [
+            (0x0, 0),
+            (0x1, 0),
+            (0x2, 0),
+            (0x3, 0),
+            (0x4, 0),
+            (0x4, 1),
+            (0x5, 0),
+            (0x6, 0),
+            (0x7, 0),
+            (0x8, 0),
+            (0x9, 0),
+            (0xa, 0),
+            (0xb, 0),
+            (0xb, 1),
+            (0xb, 2),
+            (0x8000_0000, 0),
+            (0x8000_0001, 0),
+        ]
+    );
+    assert_eq!(table[&(0x1, 0)], cpuid_reader(0x1, 0));
+}
+
+#[test]
+fn set_and_clear_features() {
+    let mut builder = CpuIdBuilder::with_cpuid_reader(cpuid_reader);
+    assert!(builder.has_feature(Feature::Sse3));
+    assert!(!builder.has_feature(Feature::Hypervisor));
+    assert!(builder.has_feature(Feature::Lm));
+
+    builder
+        .set_feature(Feature::Sse3, false)
+        .set_feature(Feature::Lm, false)
+        .set_feature(Feature::Avx2, true);
+    assert!(!builder.has_feature(Feature::Sse3));
+    assert!(!builder.has_feature(Feature::Lm));
+    assert!(builder.has_feature(Feature::Avx2));
+
+    let table = builder.build();
+    assert_eq!(table[&(0x1, 0)].ecx, 0x7fbae3fe);
+    assert_eq!(table[&(0x7, 0)].ebx, 0x000002a1);
+    assert_eq!(table[&(0x8000_0001, 0)].edx, 0x08100800);
+}
+
+#[test]
+fn set_fields() {
+    let mut builder = CpuIdBuilder::with_cpuid_reader(cpuid_reader);
+    builder
+        .set_vendor(b"AuthenticAMD")
+        .set_brand_string("AMD EPYC 7742 64-Core Processor")
+        .set_family_model_stepping(0x17, 0x31, 0x0)
+        .set_apic_id(0x11)
+        .set_address_sizes(48, 57)
+        .set_hypervisor(b"KVMKVMKVM\0\0\0");
+    let table = builder.build();
+
+    assert_eq!(
+        table[&(0x0, 0)],
+        CpuIdResult {
+            eax: 0xb,
+            ebx: 0x68747541,
+            ecx: 0x444d4163,
+            edx: 0x69746e65
+        }
+    );
+    assert_eq!(table[&(0x1, 0)].eax, 0x00830f10);
+    assert_eq!(table[&(0x1, 0)].ebx, 0x11100800);
+    assert_eq!(table[&(0x1, 0)].ecx, 0xffbae3ff);
+    assert_eq!(table[&(0xb, 1)].edx, 0x11);
+    assert_eq!(
+        table[&(0x4000_0000, 0)],
+        CpuIdResult {
+            eax: 0x4000_0000,
+            ebx: 0x4b4d564b,
+            ecx: 0x564b4d56,
+            edx: 0x0000004d
+        }
+    );
+    assert_eq!(
+        table[&(0x8000_0002, 0)],
+        CpuIdResult {
+            eax: 0x20444d41,
+            ebx: 0x43595045,
+            ecx: 0x34373720,
+            edx: 0x34362032
+        }
+    );
+    assert_eq!(table[&(0x8000_0004, 0)].edx, 0x0);
+    assert_eq!(table[&(0x8000_0008, 0)].eax, 0x3930);
+    // Maximum extended leaf was raised to include the new leafs:
+    assert_eq!(table[&(0x8000_0000, 0)].eax, 0x8000_0008);
+}
+
+#[test]
+fn blank_template() {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"GenuineIntel")
+        .set_feature(Feature::Sse2, true)
+        .set(
+            0x7,
+            1,
+            CpuIdResult {
+                eax: 0x10,
+                ebx: 0x0,
+                ecx: 0x0,
+                edx: 0x0,
+            },
+        );
+    let table = builder.build();
+
+    assert_eq!(table.len(), 4);
+    assert_eq!(table[&(0x0, 0)].eax, 0x7);
+    assert_eq!(table[&(0x1, 0)].edx, 1 << 26);
+    // Maximum subleaf of leaf 7 is adjusted:
+    assert_eq!(table[&(0x7, 0)].eax, 0x1);
+}
+
+#[test]
+fn feature_names() {
+    for feature in Feature::ALL {
+        assert_eq!(Feature::from_name(feature.name()), Some(*feature));
+    }
+    assert_eq!(Feature::from_name("avx512f"), Some(Feature::Avx512f));
+    assert_eq!(Feature::from_name("not-a-feature"), None);
+}
+
+#[test]
+fn core_consistency() {
+    let core = |apic_id: u32, avx2: bool| {
+        let mut builder = CpuIdBuilder::with_cpuid_reader(cpuid_reader);
+        builder
+            .set_apic_id(apic_id)
+            .set_feature(Feature::Avx2, avx2);
+        CpuIdSnapshot::from(builder.build())
+    };
+
+    let report =
+        ConsistencyReport::from_snapshots(&[(0, core(0, true)), (1, core(1, true))]).unwrap();
+    assert!(report.is_consistent());
+    assert_eq!(format!("{}", report), "");
+
+    let report = ConsistencyReport::from_snapshots(&[
+        (0, core(0, true)),
+        (1, core(1, true)),
+        (2, core(2, false)),
+    ])
+    .unwrap();
+    assert!(!report.is_consistent());
+    assert_eq!(report.reference_core, 0);
+    assert_eq!(report.differences.len(), 1);
+    assert_eq!(report.differences[0].0, 2);
+    assert_eq!(
+        report.differences[0]
+            .1
+            .removed_features()
+            .collect::<std::vec::Vec<_>>(),
+        [Feature::Avx2]
+    );
+
+    assert!(ConsistencyReport::from_snapshots(&[]).is_none());
+}
@@ -0,0 +1,86 @@
+//! Cache and TLB information none of the CPU dumps cover.
+use alloc::vec::Vec;
+
+use super::synthetic::{amd, intel, res};
+use crate::{CacheSharing, CpuId, CpuIdResult};
+
+#[test]
+fn tlb_hierarchy_from_leaf_0x18() {
+    use crate::{Associativity, DatType, PageSize};
+
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        // Leaf 2 is ignored if leaf 0x18 reports TLBs:
+        ((0x2, 0), res(0x0000_6301, 0, 0, 0)),
+        // L1 data TLB for 4K, 2M and 4M pages, 4-way, 64 entries:
+        ((0x18, 0), res(2, (4 << 16) | 0b111, 16, (1 << 5) | 1)),
+        // L2 unified TLB for 4K and 2M pages, 6-way, 1536 entries:
+        ((0x18, 1), res(0, (6 << 16) | 0b11, 256, (2 << 5) | 3)),
+        // L1 instruction TLB for 1G pages, fully associative, 4 entries:
+        (
+            (0x18, 2),
+            res(0, (4 << 16) | 0b1000, 1, (1 << 8) | (1 << 5) | 2),
+        ),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let tlbs = cpuid.tlb_hierarchy();
+    assert_eq!(tlbs.tlbs.len(), 6);
+
+    let k4: Vec<_> = tlbs.page_size(PageSize::Size4K).collect();
+    assert_eq!(k4.len(), 2);
+    assert_eq!(k4[0].typ, DatType::DataTLB);
+    assert_eq!(k4[0].entries, 64);
+    assert_eq!(k4[1].level, 2);
+    assert_eq!(k4[1].typ, DatType::UnifiedTLB);
+    assert_eq!(k4[1].entries, 1536);
+    assert_eq!(k4[1].associativity, Associativity::NWay(6));
+
+    let g1 = tlbs
+        .get(PageSize::Size1G, 1, DatType::InstructionTLB)
+        .unwrap();
+    assert_eq!(g1.entries, 4);
+    assert_eq!(g1.associativity, Associativity::Full);
+    assert!(tlbs.get(PageSize::Size4M, 2, DatType::UnifiedTLB).is_none());
+}
+
+#[test]
+fn cache_line_size_fallbacks() {
+    // Leaf 4 wins over CLFLUSH: L1 data cache with 128 byte lines.
+    static PARAMETERS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 8 << 8, 0, 1 << 19)),
+        ((0x4, 0), res((1 << 5) | 1, 127, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(PARAMETERS));
+    assert_eq!(cpuid.cache_line_size(), Some(128));
+
+    // Without leaf 4: the CLFLUSH line size.
+    static CLFLUSH: &[((u32, u32), CpuIdResult)] = &[((0x1, 0), res(0, 8 << 8, 0, 1 << 19))];
+    let cpuid = CpuId::with_cpuid_fn(intel(CLFLUSH));
+    assert_eq!(cpuid.cache_line_size(), Some(64));
+
+    // Without CLFLUSH: the AMD L1 cache leaf.
+    static AMD_L1: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 0, 0)),
+        ((0x8000_0000, 0), res(0x8000_0005, 0, 0, 0)),
+        ((0x8000_0005, 0), res(0, 0, 0x2008_0140, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(amd(AMD_L1));
+    assert_eq!(cpuid.cache_line_size(), Some(0x40));
+
+    static NONE: &[((u32, u32), CpuIdResult)] = &[((0x1, 0), res(0, 0, 0, 0))];
+    let cpuid = CpuId::with_cpuid_fn(intel(NONE));
+    assert_eq!(cpuid.cache_line_size(), None);
+}
+
+#[test]
+fn cache_sharing_at_top_of_x2apic_id_space() {
+    // L3 shared by 64 logical processors:
+    let l3 = res(0x3 | (3 << 5) | (63 << 14), 0, 0, 0);
+    let sharing = CacheSharing::from_registers(l3, u32::MAX);
+    assert_eq!(sharing.cache_id(), u32::MAX >> 6);
+    assert_eq!(sharing.apic_ids(), 0xffff_ffc0..=u32::MAX);
+    assert!(sharing.is_shared_with(0xffff_ffc0));
+    assert!(!sharing.is_shared_with(0xffff_ffbf));
+
+    let sharing = CacheSharing::from_registers(l3, 0);
+    assert_eq!(sharing.apic_ids(), 0..=63);
+}
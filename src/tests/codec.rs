@@ -0,0 +1,31 @@
+use crate::testdata;
+
+#[cfg(feature = "json")]
+#[test]
+fn json() {
+    let snapshot = testdata::load("xeon-gold-6252").unwrap();
+    let json = snapshot.to_json().unwrap();
+    assert!(json.starts_with("{\n  \"leafs\": ["));
+    assert_eq!(crate::CpuIdSnapshot::from_json(&json).unwrap(), snapshot);
+    assert!(crate::CpuIdSnapshot::from_json("{}").is_err());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn yaml() {
+    let snapshot = testdata::load("xeon-gold-6252").unwrap();
+    let yaml = snapshot.to_yaml().unwrap();
+    assert!(yaml.starts_with("leafs:\n"));
+    assert_eq!(crate::CpuIdSnapshot::from_yaml(&yaml).unwrap(), snapshot);
+    assert!(crate::CpuIdSnapshot::from_yaml("leafs: 1").is_err());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml() {
+    let snapshot = testdata::load("ryzen-5-3600x").unwrap();
+    let toml = snapshot.to_toml().unwrap();
+    assert!(toml.starts_with("leafs = ["));
+    assert_eq!(crate::CpuIdSnapshot::from_toml(&toml).unwrap(), snapshot);
+    assert!(crate::CpuIdSnapshot::from_toml("leafs = 1").is_err());
+}
@@ -0,0 +1,23 @@
+use crate::{CpuIdReader, CpuIdReaderNative, CpuIdSnapshot};
+
+#[test]
+fn snapshot_from_dev_cpu() {
+    let snapshot = match CpuIdSnapshot::from_dev_cpu(0) {
+        Ok(snapshot) => snapshot,
+        // No cpuid device (module not loaded, not running as root or in a
+        // container):
+        Err(_) => return,
+    };
+
+    // The vendor and maximum leaf are the same on all cores:
+    assert_eq!(snapshot.get(0x0, 0), Some(CpuIdReaderNative.cpuid1(0x0)));
+    assert_eq!(
+        snapshot.get(0x8000_0000, 0),
+        Some(CpuIdReaderNative.cpuid1(0x8000_0000))
+    );
+}
+
+#[test]
+fn snapshot_from_missing_device() {
+    assert!(CpuIdSnapshot::from_cpuid_device("/dev/cpu/does-not-exist/cpuid").is_err());
+}
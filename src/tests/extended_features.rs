@@ -0,0 +1,340 @@
+//! Feature bits of leaf 7 and the leafs they enable, for features none of the
+//! CPU dumps report.
+use alloc::vec::Vec;
+
+use super::synthetic::{intel, res};
+use crate::{CpuId, CpuIdResult, PconfigTarget};
+
+#[test]
+fn avx10() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(1, 0, 0, 0)),
+        ((0x7, 1), res(0, 0, 0, 1 << 19)),
+        ((0x24, 0), res(0, 0x0007_0001, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let avx10 = cpuid.get_avx10_info().expect("AVX10 is supported");
+    assert_eq!(avx10.version(), 1);
+    assert!(avx10.has_128bit_vectors());
+    assert!(avx10.has_256bit_vectors());
+    assert!(avx10.has_512bit_vectors());
+    assert_eq!(avx10.max_vector_length(), Some(512));
+    assert_eq!(avx10.as_cpuid_result(), LEAFS[2].1);
+
+    // Leaf 0x24 is only valid if AVX10 is enumerated in leaf 7:
+    static WITHOUT_AVX10: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(1, 0, 0, 0)),
+        ((0x24, 0), res(0, 0x0007_0001, 0, 0)),
+    ];
+    assert!(CpuId::with_cpuid_fn(intel(WITHOUT_AVX10))
+        .get_avx10_info()
+        .is_none());
+}
+
+#[test]
+fn leaf7_subleaf1() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(1, 0, 0, 0)),
+        (
+            (0x7, 1),
+            res(
+                1 << 21,
+                1 << 0,
+                0,
+                (1 << 4) | (1 << 8) | (1 << 14) | (1 << 21),
+            ),
+        ),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let features = cpuid.get_extended_feature_info().unwrap();
+    assert!(features.has_amx_fp16());
+    assert!(features.has_ppin());
+    assert!(!features.has_pbndkb());
+    assert!(features.has_avx_vnni_int8());
+    assert!(!features.has_avx_ne_convert());
+    assert!(features.has_amx_complex());
+    assert!(!features.has_avx_vnni_int16());
+    assert!(features.has_prefetchi());
+    assert!(!features.has_avx10());
+    assert!(features.has_apx_f());
+    assert_eq!(
+        features.as_cpuid_results(),
+        [LEAFS[0].1, LEAFS[1].1, res(0, 0, 0, 0)]
+    );
+    assert!(cpuid.has_feature(crate::Feature::ApxF));
+
+    // Subleaf 1 is ignored if subleaf 0 reports it as unsupported:
+    static NO_SUBLEAF1: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(0, 0, 0, 0)),
+        ((0x7, 1), res(0, 0, 0, 1 << 21)),
+    ];
+    let features = CpuId::with_cpuid_fn(intel(NO_SUBLEAF1))
+        .get_extended_feature_info()
+        .unwrap();
+    assert!(!features.has_apx_f());
+}
+
+#[test]
+fn atomics_and_msr_instructions() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(1, 0, 0, 0)),
+        ((0x7, 1), res((1 << 3) | (1 << 7) | (1 << 27), 0, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let features = cpuid.get_extended_feature_info().unwrap();
+    assert!(features.has_rao_int());
+    assert!(features.has_cmpccxadd());
+    assert!(!features.has_wrmsrns());
+    assert!(features.has_msrlist());
+    assert!(cpuid.has_feature(crate::Feature::Cmpccxadd));
+    assert!(!cpuid.has_feature(crate::Feature::Wrmsrns));
+}
+
+#[test]
+fn direct_stores_and_serialize() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[(
+        (0x7, 0),
+        res(0, 0, (1 << 5) | (1 << 27) | (1 << 29), 1 << 14),
+    )];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let features = cpuid.get_extended_feature_info().unwrap();
+    assert!(features.has_waitpkg());
+    assert!(features.has_movdiri());
+    assert!(!features.has_movdir64b());
+    assert!(features.has_enqcmd());
+    assert!(features.has_serialize());
+    assert!(!features.has_tsxldtrk());
+    assert_eq!(features.as_cpuid_result(), LEAFS[0].1);
+    assert!(cpuid.has_feature(crate::Feature::Serialize));
+}
+
+#[test]
+fn uintr() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(0, 0, 0, 1 << 5)),
+        ((0xd, 0), res(0b11, 0, 0, 0)),
+        ((0xd, 1), res(0b1000, 0, 1 << 14, 0)),
+        ((0xd, 14), res(48, 0, 0b1, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    assert!(cpuid.get_extended_feature_info().unwrap().has_uintr());
+    assert!(cpuid.has_feature(crate::Feature::Uintr));
+
+    let state = cpuid.get_extended_state_info().unwrap();
+    assert!(state.ia32_xss_supports_uintr());
+    let uintr = state
+        .iter()
+        .find(|s| s.register() == crate::ExtendedRegisterType::Uintr)
+        .expect("UINTR state component");
+    assert_eq!(uintr.size(), 48);
+    assert!(uintr.is_in_ia32_xss());
+}
+
+#[test]
+fn pconfig() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(0, 0, 1 << 13, 1 << 18)),
+        ((0x1b, 0), res(1, 1, 0, 3)),
+        ((0x1b, 1), res(1, 7, 0, 0)),
+        ((0x1b, 2), res(0, 2, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    assert!(cpuid.get_extended_feature_info().unwrap().has_tme());
+    let targets: Vec<_> = cpuid.get_pconfig_info().unwrap().collect();
+    assert_eq!(
+        targets,
+        [
+            PconfigTarget::Mktme,
+            PconfigTarget::TseKeyProgramWrapped,
+            PconfigTarget::Unknown(7)
+        ]
+    );
+
+    // The builder captures sub-leafs up to (and including) the invalid one:
+    let table = crate::CpuIdBuilder::with_cpuid_reader(intel(LEAFS)).build();
+    assert!(table.contains_key(&(0x1b, 2)));
+    assert!(!table.contains_key(&(0x1b, 3)));
+
+    // Leaf 0x1B is only valid if PCONFIG is enumerated in leaf 7:
+    static WITHOUT_PCONFIG: &[((u32, u32), CpuIdResult)] =
+        &[((0x7, 0), res(0, 0, 0, 0)), ((0x1b, 0), res(1, 1, 0, 0))];
+    assert!(CpuId::with_cpuid_fn(intel(WITHOUT_PCONFIG))
+        .get_pconfig_info()
+        .is_none());
+}
+
+#[test]
+fn arch_lbr() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(0, 0, 0, 1 << 19)),
+        (
+            (0x1c, 0),
+            res((1 << 31) | (1 << 30) | 0x88, 0b111, 0x000f_0007, 0),
+        ),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let lbr = cpuid.get_lbr_info().expect("Arch LBR is supported");
+    assert_eq!(lbr.depth_bitmap(), 0x88);
+    assert!(lbr.supports_depth(32));
+    assert!(lbr.supports_depth(64));
+    assert!(!lbr.supports_depth(8));
+    assert!(!lbr.supports_depth(33));
+    assert_eq!(lbr.max_depth(), Some(64));
+    assert!(lbr.has_deep_cstate_reset());
+    assert!(lbr.has_lip_values());
+    assert!(lbr.has_cpl_filtering());
+    assert!(lbr.has_branch_filtering());
+    assert!(lbr.has_call_stack_mode());
+    assert!(lbr.has_mispredict_bit());
+    assert!(lbr.has_timed_lbrs());
+    assert!(lbr.has_branch_type_field());
+    assert_eq!(lbr.event_logging_bitmap(), 0xf);
+    assert_eq!(lbr.as_cpuid_result(), LEAFS[1].1);
+}
+
+#[test]
+fn hreset() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(1, 0, 0, 0)),
+        ((0x7, 1), res(1 << 22, 0, 0, 0)),
+        ((0x20, 0), res(0, 1, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    assert!(cpuid.has_feature(crate::Feature::Hreset));
+    let hreset = cpuid.get_hreset_info().expect("HRESET is supported");
+    assert_eq!(hreset.max_subleaf(), 0);
+    assert_eq!(hreset.capabilities(), 1);
+    assert!(hreset.has_thread_director_reset());
+    assert_eq!(hreset.as_cpuid_result(), LEAFS[2].1);
+}
+
+#[test]
+fn tmul() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(0, 0, 0, (1 << 22) | (1 << 24) | (1 << 25))),
+        ((0x1e, 0), res(1, 0x4010, 0, 0)),
+        ((0x1e, 1), res(0b1_0000_1011, 0, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    assert!(cpuid.has_feature(crate::Feature::AmxTile));
+    let tmul = cpuid.get_tmul_info().expect("AMX is supported");
+    assert_eq!(tmul.max_subleaf(), 1);
+    assert_eq!(tmul.max_k(), 16);
+    assert_eq!(tmul.max_n(), 64);
+    assert!(tmul.has_amx_int8());
+    assert!(tmul.has_amx_bf16());
+    assert!(!tmul.has_amx_complex());
+    assert!(tmul.has_amx_fp16());
+    assert!(tmul.has_amx_movrs());
+    assert_eq!(tmul.as_cpuid_result(), LEAFS[1].1);
+    assert_eq!(tmul.subleaf1_as_cpuid_result(), Some(LEAFS[2].1));
+
+    static NO_AMX: &[((u32, u32), CpuIdResult)] = &[((0x1e, 0), res(0, 0x4010, 0, 0))];
+    assert!(CpuId::with_cpuid_fn(intel(NO_AMX))
+        .get_tmul_info()
+        .is_none());
+}
+
+#[test]
+fn tsx_always_abort() {
+    // RTM and HLE are enumerated, but RTM_ALWAYS_ABORT and TSX_FORCE_ABORT
+    // are set (e.g., after a microcode update disabled TSX):
+    static ABORTS: &[((u32, u32), CpuIdResult)] = &[(
+        (0x7, 0),
+        res(0, (1 << 4) | (1 << 11), 0, (1 << 11) | (1 << 13)),
+    )];
+    let cpuid = CpuId::with_cpuid_fn(intel(ABORTS));
+    let features = cpuid.get_extended_feature_info().unwrap();
+    assert!(features.has_hle());
+    assert!(features.has_rtm());
+    assert!(features.has_rtm_always_abort());
+    assert!(features.has_tsx_force_abort());
+    assert_eq!(features.reserved_bits()[0], res(0, 0, 0, 0));
+    assert!(cpuid.has_feature(crate::Feature::RtmAlwaysAbort));
+    assert!(!cpuid.transactional_memory_usable());
+
+    static USABLE: &[((u32, u32), CpuIdResult)] =
+        &[((0x7, 0), res(0, (1 << 4) | (1 << 11), 0, 1 << 13))];
+    let cpuid = CpuId::with_cpuid_fn(intel(USABLE));
+    assert!(cpuid.transactional_memory_usable());
+
+    // HLE alone doesn't elide locks.
+    static HLE: &[((u32, u32), CpuIdResult)] = &[((0x7, 0), res(0, 1 << 4, 0, 0))];
+    let cpuid = CpuId::with_cpuid_fn(intel(HLE));
+    assert!(!cpuid.transactional_memory_usable());
+}
+
+#[test]
+fn extended_features_subleaf2() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(2, 0, 0, 0)),
+        (
+            (0x7, 2),
+            res(
+                0,
+                0,
+                0,
+                (1 << 0) | (1 << 2) | (1 << 4) | (1 << 5) | (1 << 9),
+            ),
+        ),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let mut features = cpuid.get_extended_feature_info().unwrap();
+    assert!(features.has_psfd());
+    assert!(!features.has_ipred_ctrl());
+    assert!(features.has_rrsba_ctrl());
+    assert!(!features.has_ddpd_u());
+    assert!(features.has_bhi_ctrl());
+    assert!(features.has_mcdt_no());
+    assert!(!features.has_uc_lock_disable());
+    assert!(!features.has_monitor_mitg_no());
+    assert_eq!(features.reserved_bits()[2], res(0, 0, 0, 1 << 9));
+    assert!(cpuid.has_feature(crate::Feature::BhiCtrl));
+    assert!(!cpuid.has_feature(crate::Feature::IpredCtrl));
+
+    features.set_ipred_ctrl(true);
+    features.set_bhi_ctrl(false);
+    assert_eq!(
+        features.as_cpuid_results()[2],
+        res(
+            0,
+            0,
+            0,
+            (1 << 0) | (1 << 1) | (1 << 2) | (1 << 5) | (1 << 9)
+        )
+    );
+
+    // Subleaf 2 is ignored if subleaf 0 reports it as unsupported:
+    static NO_SUBLEAF2: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(1, 0, 0, 0)),
+        ((0x7, 2), res(0, 0, 0, 1 << 4)),
+    ];
+    let features = CpuId::with_cpuid_fn(intel(NO_SUBLEAF2))
+        .get_extended_feature_info()
+        .unwrap();
+    assert!(!features.has_bhi_ctrl());
+}
+
+#[test]
+fn reserved_bits() {
+    // Reserved bits: leaf 1 ECX[16] and EDX[10], leaf 6 EAX[3], leaf 7 EDX[1]
+    // and leaf 7 subleaf 1 ECX[0].
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        (
+            (0x1, 0),
+            res(0, 0, (1 << 16) | (1 << 0), (1 << 10) | (1 << 0)),
+        ),
+        ((0x6, 0), res((1 << 3) | (1 << 7), 0, 4 << 8, 0)),
+        ((0x7, 0), res(1, 1 << 0, 0, (1 << 1) | (1 << 5))),
+        ((0x7, 1), res(1 << 3, 0, 1 << 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+
+    let reserved = cpuid.get_feature_info().unwrap().reserved_bits();
+    assert_eq!(reserved, res(0, 0, 1 << 16, 1 << 10));
+    let reserved = cpuid.get_thermal_power_info().unwrap().reserved_bits();
+    assert_eq!(reserved, res(1 << 3, 0, 0, 0));
+    let [reserved, reserved1, _] = cpuid.get_extended_feature_info().unwrap().reserved_bits();
+    assert_eq!(reserved, res(0, 0, 0, 1 << 1));
+    assert_eq!(reserved1, res(0, 0, 1 << 0, 0));
+}
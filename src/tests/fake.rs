@@ -0,0 +1,55 @@
+use crate::{testdata, FakeCpu, Feature};
+
+#[test]
+fn presets() {
+    let cpuid = FakeCpu::skylake().cpuid();
+    assert_eq!(cpuid.get_vendor_info().unwrap().as_str(), "GenuineIntel");
+    let fi = cpuid.get_feature_info().unwrap();
+    assert_eq!(fi.family_id(), 0x6);
+    assert_eq!(fi.model_id(), 0x5e);
+    assert!(cpuid.has_feature(Feature::Avx2));
+    assert!(!cpuid.has_feature(Feature::Avx512f));
+
+    let cpuid = FakeCpu::epyc_rome().cpuid();
+    assert_eq!(cpuid.get_vendor_info().unwrap().as_str(), "AuthenticAMD");
+    assert_eq!(cpuid.get_feature_info().unwrap().family_id(), 0x17);
+    assert!(cpuid.has_feature(Feature::Avx2));
+}
+
+#[test]
+fn with_without() {
+    let cpu = FakeCpu::skylake_server().without(Feature::Avx512f);
+    assert!(!cpu.has_feature(Feature::Avx512f));
+    assert!(cpu.has_feature(Feature::Avx512vl));
+    assert!(!cpu.cpuid().has_feature(Feature::Avx512f));
+
+    let cpu = FakeCpu::new(b"GenuineIntel").with(Feature::Sse2);
+    assert!(cpu.cpuid().has_feature(Feature::Sse2));
+    assert!(!cpu.cpuid().has_feature(Feature::Avx2));
+
+    let cpu = FakeCpu::with_cpuid_reader(testdata::load("ryzen-5-3600x").unwrap())
+        .without(Feature::Sha)
+        .modify(|builder| {
+            builder.set_brand_string("Fake Ryzen");
+        });
+    let cpuid = cpu.cpuid();
+    assert!(!cpuid.has_feature(Feature::Sha));
+    assert!(cpuid.has_feature(Feature::Avx2));
+    assert_eq!(
+        cpuid.get_processor_brand_string().unwrap().as_str(),
+        "Fake Ryzen"
+    );
+}
+
+#[test]
+fn variations() {
+    let features = [Feature::Avx2, Feature::Bmi2];
+    let cpus: std::vec::Vec<_> = FakeCpu::skylake().variations(&features).collect();
+    assert_eq!(cpus.len(), 4);
+    for (mask, cpu) in cpus.iter().enumerate() {
+        let cpuid = cpu.cpuid();
+        assert_eq!(cpuid.has_feature(Feature::Avx2), mask & 1 != 0);
+        assert_eq!(cpuid.has_feature(Feature::Bmi2), mask & 2 != 0);
+        assert!(cpuid.has_feature(Feature::Sse2));
+    }
+}
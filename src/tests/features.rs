@@ -0,0 +1,178 @@
+use std::string::ToString;
+use std::vec::Vec;
+
+use crate::{testdata, CpuFeatures, CpuIdBuilder, CpuIdSnapshot, Feature, LinuxFeatureBit};
+
+#[test]
+fn set_operations() {
+    let mut a = CpuFeatures::new();
+    assert!(a.is_empty());
+    a.insert(Feature::Sse2);
+    a.insert(Feature::Avx2);
+    a.insert(Feature::InvlpgbNested);
+    assert_eq!(a.len(), 3);
+    assert!(a.contains(Feature::InvlpgbNested));
+    assert!(!a.contains(Feature::Avx512f));
+
+    let b: CpuFeatures = [Feature::Sse2, Feature::Avx512f].into_iter().collect();
+    assert_eq!(a & b, CpuFeatures::from(Feature::Sse2));
+    assert_eq!((a | b).len(), 4);
+    assert_eq!(
+        (a - b).iter().collect::<alloc::vec::Vec<_>>(),
+        [Feature::Avx2, Feature::InvlpgbNested]
+    );
+    assert!((a & b).is_subset(&a));
+    assert!(!a.is_subset(&b));
+
+    a.remove(Feature::Avx2);
+    a.set(Feature::InvlpgbNested, false);
+    assert_eq!(a, CpuFeatures::from(Feature::Sse2));
+
+    let all = CpuFeatures::all();
+    assert_eq!(all.len(), Feature::ALL.len());
+    assert!(all.iter().eq(Feature::ALL.iter().copied()));
+}
+
+#[test]
+fn from_cpuid() {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_feature(Feature::Sse2, true)
+        .set_feature(Feature::Avx2, true)
+        .set_feature(Feature::Lzcnt, true);
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let features = snapshot.cpuid().features();
+
+    assert!(features.iter().all(|f| snapshot.cpuid().has_feature(f)));
+    assert!(features.contains(Feature::Sse2));
+    assert!(features.contains(Feature::Avx2));
+    assert!(features.contains(Feature::Lzcnt));
+    assert!(!features.contains(Feature::Avx512f));
+}
+
+#[test]
+fn table_in_bit_order() {
+    for pair in Feature::ALL.windows(2) {
+        let (a, b) = (pair[0].location(), pair[1].location());
+        if (a.leaf, a.subleaf, a.register) == (b.leaf, b.subleaf, b.register) {
+            assert!(a.bit < b.bit, "{:?} before {:?}", pair[0], pair[1]);
+        }
+    }
+}
+
+#[test]
+fn xsave_and_avx512_extensions() {
+    let xeon = testdata::load("xeon-gold-6252").unwrap();
+    let features = xeon.cpuid().features();
+    for f in [
+        Feature::Arat,
+        Feature::Xsaveopt,
+        Feature::Xsavec,
+        Feature::Xgetbv1,
+        Feature::Xsaves,
+    ] {
+        assert!(features.contains(f), "{:?}", f);
+    }
+    // Cascade Lake predates BF16, AVX-VNNI and FP16.
+    for f in [
+        Feature::Avx512bf16,
+        Feature::AvxVnni,
+        Feature::Avx512fp16,
+        Feature::Avx512vp2intersect,
+        Feature::Cldemote,
+    ] {
+        assert!(!features.contains(f), "{:?}", f);
+    }
+
+    assert_eq!(
+        Feature::Avx512bf16.linux_bit(),
+        Some(LinuxFeatureBit { word: 12, bit: 5 })
+    );
+    assert_eq!(
+        Feature::Xsaves.linux_bit(),
+        Some(LinuxFeatureBit { word: 10, bit: 3 })
+    );
+    assert_eq!(Feature::from_name("avx512_fp16"), Some(Feature::Avx512fp16));
+    assert_eq!(Feature::Avx512bf16.rustc_name(), Some("avx512bf16"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn flag_names() {
+    use serde::de::value::{Error, SeqDeserializer};
+
+    let names = ["sse2", "avx2"];
+    let features =
+        crate::flag_names::deserialize(SeqDeserializer::<_, Error>::new(names.into_iter()))
+            .unwrap();
+    assert_eq!(
+        features,
+        [Feature::Sse2, Feature::Avx2]
+            .into_iter()
+            .collect::<CpuFeatures>()
+    );
+
+    let unknown = ["sse2", "no-such-flag"];
+    assert!(
+        crate::flag_names::deserialize(SeqDeserializer::<_, Error>::new(unknown.into_iter()))
+            .is_err()
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn serialized_as_names() {
+    let features: CpuFeatures = [Feature::Avx2, Feature::Sse2].into_iter().collect();
+    let json = serde_json::to_string(&features).unwrap();
+    assert_eq!(json, r#"["sse2","avx2"]"#);
+    assert_eq!(
+        serde_json::from_str::<CpuFeatures>(&json).unwrap(),
+        features
+    );
+}
+
+#[test]
+fn linux_bits() {
+    let bit = |word, bit| LinuxFeatureBit { word, bit };
+
+    assert_eq!(Feature::Sse3.linux_bit(), Some(bit(4, 0)));
+    assert_eq!(Feature::Lm.linux_bit(), Some(bit(1, 29)));
+    assert_eq!(Feature::Svm.linux_bit(), Some(bit(6, 2)));
+    assert_eq!(Feature::Avx512f.linux_bit().map(|b| b.index()), Some(304));
+    // Scattered into Linux-defined words:
+    assert_eq!(Feature::Ppin.linux_bit(), None);
+    assert_eq!(Feature::InvariantTsc.linux_bit(), None);
+
+    assert_eq!(
+        Feature::from_linux_bit(LinuxFeatureBit::from_index(304)),
+        Some(Feature::Avx512f)
+    );
+    // X86_FEATURE_CONSTANT_TSC is Linux-defined.
+    assert_eq!(Feature::from_linux_bit(bit(3, 8)), None);
+    assert_eq!(bit(9, 16).to_string(), "9*32+16");
+
+    for &feature in Feature::ALL {
+        if let Some(bit) = feature.linux_bit() {
+            assert_eq!(Feature::from_linux_bit(bit), Some(feature));
+        }
+    }
+}
+
+#[test]
+fn rustc_target_features() {
+    assert_eq!(Feature::Sse41.rustc_name(), Some("sse4.1"));
+    assert_eq!(Feature::Aesni.rustc_name(), Some("aes"));
+    assert_eq!(Feature::Vmx.rustc_name(), None);
+    assert_eq!(Feature::from_rustc_name("ermsb"), Some(Feature::Erms));
+    assert_eq!(Feature::from_rustc_name("sse4_1"), None);
+
+    let features: CpuFeatures = [Feature::Sse2, Feature::Avx2, Feature::Fma, Feature::Vmx]
+        .into_iter()
+        .collect();
+    assert_eq!(features.rustc_target_features(), "+fma,+sse2,+avx2");
+    assert_eq!(
+        features.rustc_names().collect::<Vec<_>>(),
+        ["fma", "sse2", "avx2"]
+    );
+    assert_eq!(CpuFeatures::new().rustc_target_features(), "");
+}
@@ -0,0 +1,100 @@
+use core::ffi::{c_char, CStr};
+use core::ptr;
+
+use crate::ffi::*;
+use crate::{CpuId, Feature};
+
+fn as_str(buf: &[c_char]) -> &str {
+    unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str().unwrap()
+}
+
+#[test]
+fn strings() {
+    let cpuid = CpuId::new();
+    let vendor = cpuid.get_vendor_info().unwrap();
+
+    let mut buf = [0x7f as c_char; 64];
+    let len = unsafe { raw_cpuid_vendor(buf.as_mut_ptr(), buf.len()) };
+    assert_eq!(len, vendor.as_str().len());
+    assert_eq!(as_str(&buf), vendor.as_str());
+
+    // Truncated, but still NUL-terminated.
+    let mut buf = [0x7f as c_char; 5];
+    let len = unsafe { raw_cpuid_vendor(buf.as_mut_ptr(), buf.len()) };
+    assert_eq!(len, vendor.as_str().len());
+    assert_eq!(as_str(&buf), &vendor.as_str()[..4]);
+
+    assert_eq!(unsafe { raw_cpuid_vendor(ptr::null_mut(), 0) }, len);
+
+    let mut buf = [0 as c_char; 64];
+    let len = unsafe { raw_cpuid_brand_string(buf.as_mut_ptr(), buf.len()) };
+    let brand = cpuid.get_processor_brand_string();
+    assert_eq!(len, brand.as_ref().map_or(0, |bs| bs.as_str().len()));
+}
+
+#[test]
+fn features() {
+    let cpuid = CpuId::new();
+    let has = |name: &[u8]| unsafe { raw_cpuid_has_feature(name.as_ptr() as *const c_char) };
+    assert_eq!(has(b"sse2\0"), cpuid.has_feature(Feature::Sse2));
+    assert_eq!(
+        has(b"avx2\0"),
+        cpuid
+            .get_extended_feature_info()
+            .is_some_and(|ef| ef.has_avx2())
+    );
+    assert!(!has(b"no such feature\0"));
+    assert!(unsafe { !raw_cpuid_has_feature(ptr::null()) });
+
+    assert_eq!(raw_cpuid_feature_count(), Feature::ALL.len());
+    let mut buf = [0 as c_char; 64];
+    let len = unsafe { raw_cpuid_feature_name(0, buf.as_mut_ptr(), buf.len()) };
+    assert_eq!(as_str(&buf), Feature::ALL[0].name());
+    assert_eq!(len, Feature::ALL[0].name().len());
+    assert_eq!(
+        unsafe { raw_cpuid_feature_name(Feature::ALL.len(), buf.as_mut_ptr(), buf.len()) },
+        0
+    );
+
+    let len = unsafe { raw_cpuid_features(ptr::null_mut(), 0) };
+    let mut buf = std::vec![0x7f as c_char; len + 1];
+    assert_eq!(
+        unsafe { raw_cpuid_features(buf.as_mut_ptr(), buf.len()) },
+        len
+    );
+    let names: std::vec::Vec<&str> = as_str(&buf).split(' ').collect();
+    let expected: std::vec::Vec<&str> = Feature::ALL
+        .iter()
+        .filter(|f| cpuid.has_feature(**f))
+        .map(|f| f.name())
+        .collect();
+    assert_eq!(names, expected);
+
+    // A buffer that is too small is NUL-terminated.
+    let mut buf = [0x7f as c_char; 4];
+    assert_eq!(
+        unsafe { raw_cpuid_features(buf.as_mut_ptr(), buf.len()) },
+        len
+    );
+    assert_eq!(buf[3], 0);
+}
+
+#[test]
+fn caches_and_topology() {
+    let cpuid = CpuId::new();
+    let caches = raw_cpuid_cache_sizes();
+    assert_eq!(
+        [caches.l1d, caches.l1i, caches.l2, caches.l3],
+        cpuid.cache_sizes().map(|size| size.unwrap_or(0))
+    );
+
+    let topology = raw_cpuid_topology();
+    assert_eq!(
+        topology.logical_cpus as usize,
+        cpuid.num_logical_cpus().unwrap_or(0)
+    );
+    assert_eq!(
+        topology.threads_per_core as usize,
+        cpuid.threads_per_core().unwrap_or(0)
+    );
+}
@@ -0,0 +1,208 @@
+use crate::{
+    CpuIdBuilder, CpuIdResult, CpuIdSnapshot, EffectiveFrequencyInterface, Feature, Frequency,
+    FrequencySource, TscCapabilities,
+};
+
+fn builder() -> CpuIdBuilder {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"GenuineIntel")
+        .set_brand_string("Intel(R) Core(TM) i7-4790 CPU @ 3.60GHz");
+    builder
+}
+
+fn res(eax: u32, ebx: u32, ecx: u32, edx: u32) -> CpuIdResult {
+    CpuIdResult { eax, ebx, ecx, edx }
+}
+
+#[test]
+fn cpuid_leafs() {
+    let mut builder = builder();
+    builder
+        .set(0x15, 0, res(2, 188, 24_000_000, 0))
+        .set(0x16, 0, res(2100, 3900, 100, 0));
+    let freq = CpuIdSnapshot::from(builder.build()).cpuid().frequency();
+
+    assert_eq!(
+        freq.base,
+        Some(Frequency {
+            hz: 2_100_000_000,
+            source: FrequencySource::FrequencyLeaf
+        })
+    );
+    assert_eq!(
+        freq.max,
+        Some(Frequency {
+            hz: 3_900_000_000,
+            source: FrequencySource::FrequencyLeaf
+        })
+    );
+    assert_eq!(
+        freq.tsc,
+        Some(Frequency {
+            hz: 2_256_000_000,
+            source: FrequencySource::TscLeaf
+        })
+    );
+}
+
+#[test]
+fn hypervisor() {
+    let mut builder = builder();
+    builder
+        .set_hypervisor(b"KVMKVMKVM\0\0\0")
+        .update(0x4000_0000, 0, |res| res.eax = 0x4000_0010)
+        .set(0x4000_0010, 0, res(2_893_000, 1_000_000, 0, 0));
+    let freq = CpuIdSnapshot::from(builder.build()).cpuid().frequency();
+
+    assert_eq!(
+        freq.tsc,
+        Some(Frequency {
+            hz: 2_893_000_000,
+            source: FrequencySource::Hypervisor
+        })
+    );
+    assert_eq!(
+        freq.base,
+        Some(Frequency {
+            hz: 3_600_000_000,
+            source: FrequencySource::BrandString
+        })
+    );
+    assert_eq!(freq.max, None);
+}
+
+#[test]
+fn brand_string() {
+    let freq = CpuIdSnapshot::from(builder().build()).cpuid().frequency();
+    let brand = Some(Frequency {
+        hz: 3_600_000_000,
+        source: FrequencySource::BrandString,
+    });
+    assert_eq!(freq.base, brand);
+    assert_eq!(freq.tsc, brand);
+    assert_eq!(freq.max, None);
+
+    let mut builder = CpuIdBuilder::new();
+    builder.set_brand_string("AMD Ryzen 5 3600X 6-Core Processor");
+    let freq = CpuIdSnapshot::from(builder.build()).cpuid().frequency();
+    assert_eq!(freq.base, None);
+    assert_eq!(freq.tsc, None);
+}
+
+#[test]
+fn parse_brand_string() {
+    let frequency = |brand: &str| {
+        let mut builder = CpuIdBuilder::new();
+        builder.set_brand_string(brand);
+        CpuIdSnapshot::from(builder.build())
+            .cpuid()
+            .get_processor_brand_string()
+            .unwrap()
+            .frequency()
+    };
+
+    assert_eq!(
+        frequency("Intel(R) Core(TM) i7-4790 CPU @ 3.60GHz"),
+        Some(3_600_000_000)
+    );
+    assert_eq!(
+        frequency("Intel(R) Core(TM) i5-3337U CPU @ 1.80GHz"),
+        Some(1_800_000_000)
+    );
+    assert_eq!(
+        frequency("Intel(R) Pentium(R) 4 CPU 3.00GHz"),
+        Some(3_000_000_000)
+    );
+    assert_eq!(
+        frequency("Intel(R) Pentium(R) III CPU 800MHz"),
+        Some(800_000_000)
+    );
+    assert_eq!(
+        frequency("Genuine Intel(R) CPU @ 2.133ghz"),
+        Some(2_133_000_000)
+    );
+    assert_eq!(
+        frequency("Intel(R) Xeon(R) CPU E5-2620 v3 @ 2.40 GHz"),
+        Some(2_400_000_000)
+    );
+    assert_eq!(
+        frequency("VIA Esther processor 1500MHz  "),
+        Some(1_500_000_000)
+    );
+    assert_eq!(frequency("AMD EPYC 7B12 64-Core Processor"), None);
+    assert_eq!(frequency("Intel(R) Core(TM) i7-4.2GHz"), None);
+    assert_eq!(frequency("CPU @ GHz"), None);
+    assert_eq!(frequency("Weird CPU @ 1.2.3GHz"), None);
+    assert_eq!(frequency("Weird CPU @ GHz"), None);
+}
+
+#[test]
+fn tsc_capabilities() {
+    let mut builder = builder();
+    builder
+        .set_feature(Feature::Tsc, true)
+        .set_feature(Feature::TscDeadline, true)
+        .set_feature(Feature::Rdtscp, true)
+        .set_feature(Feature::InvariantTsc, true)
+        .set(0x15, 0, res(2, 188, 24_000_000, 0));
+    let tsc = CpuIdSnapshot::from(builder.build()).cpuid().tsc_info();
+
+    assert_eq!(
+        tsc,
+        TscCapabilities {
+            present: true,
+            rdtscp: true,
+            invariant: true,
+            deadline_timer: true,
+            crystal_ratio: Some((188, 2)),
+            crystal_hz: Some(24_000_000),
+            frequency: Some(Frequency {
+                hz: 2_256_000_000,
+                source: FrequencySource::TscLeaf
+            }),
+        }
+    );
+
+    let tsc = CpuIdSnapshot::from(CpuIdBuilder::new().build())
+        .cpuid()
+        .tsc_info();
+    assert!(!tsc.present);
+    assert!(!tsc.invariant);
+    assert_eq!(tsc.crystal_ratio, None);
+    assert_eq!(tsc.frequency, None);
+}
+
+#[test]
+fn boost_and_effective_frequency() {
+    let mut builder = builder();
+    builder.set(0x6, 0, res(1 << 1, 0, 1 << 0, 0));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+    assert!(cpuid.has_performance_boost());
+    assert_eq!(
+        cpuid.effective_frequency_interface(),
+        EffectiveFrequencyInterface {
+            mperf_aperf: true,
+            read_only: false,
+            rdpru: false,
+        }
+    );
+
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"AuthenticAMD")
+        .set(0x8000_0007, 0, res(0, 0, 0, (1 << 9) | (1 << 10)))
+        .set(0x8000_0008, 0, res(0x3030, 1 << 4, 0, 1 << 16));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+    assert!(cpuid.has_performance_boost());
+    let eff = cpuid.effective_frequency_interface();
+    assert!(!eff.mperf_aperf && eff.read_only && eff.rdpru);
+    assert!(eff.is_available());
+
+    let snapshot = CpuIdSnapshot::from(CpuIdBuilder::new().build());
+    let cpuid = snapshot.cpuid();
+    assert!(!cpuid.has_performance_boost());
+    assert!(!cpuid.effective_frequency_interface().is_available());
+}
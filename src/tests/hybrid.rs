@@ -0,0 +1,102 @@
+use crate::{
+    AsymmetricFeature, CoreType, CpuIdBuilder, CpuIdResult, CpuIdSnapshot, Feature, HybridIsaCheck,
+    HybridSummary,
+};
+
+fn core(core_type: u32, avx512: bool) -> CpuIdSnapshot {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"GenuineIntel")
+        .set_feature(Feature::Sse2, true)
+        .set_feature(Feature::Avx2, true)
+        .set_feature(Feature::Avx512f, avx512)
+        .set_feature(Feature::Hybrid, true)
+        .set(
+            0x1a,
+            0,
+            CpuIdResult {
+                eax: (core_type << 24) | 1,
+                ebx: 0,
+                ecx: 0,
+                edx: 0,
+            },
+        );
+    CpuIdSnapshot::from(builder.build())
+}
+
+#[test]
+fn hybrid_info() {
+    let snapshot = core(0x40, true);
+    let info = snapshot.cpuid().get_hybrid_info().unwrap();
+    assert_eq!(info.core_type(), CoreType::Core);
+    assert_eq!(info.native_model_id(), 1);
+    assert_eq!(info.as_cpuid_result().eax, 0x4000_0001);
+
+    let snapshot = core(0x20, false);
+    let info = snapshot.cpuid().get_hybrid_info().unwrap();
+    assert_eq!(info.core_type(), CoreType::Atom);
+
+    let mut builder = CpuIdBuilder::with_cpuid_reader(&snapshot);
+    builder.set_feature(Feature::Hybrid, false);
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert!(snapshot.cpuid().get_hybrid_info().is_none());
+}
+
+#[test]
+fn summary() {
+    let summary = HybridSummary::from_snapshots(&[
+        (0, core(0x40, true)),
+        (1, core(0x40, true)),
+        (2, core(0x20, false)),
+    ]);
+    assert!(summary.is_hybrid());
+
+    let p_cores = summary.core_type(CoreType::Core).unwrap();
+    assert_eq!(p_cores.cores, [0, 1]);
+    assert!(p_cores.features.contains(&Feature::Avx512f));
+
+    let e_cores = summary.core_type(CoreType::Atom).unwrap();
+    assert_eq!(e_cores.cores, [2]);
+    assert!(e_cores.features.contains(&Feature::Avx2));
+    assert!(!e_cores.features.contains(&Feature::Avx512f));
+
+    assert!(summary.common.contains(&Feature::Sse2));
+    assert!(summary.common.contains(&Feature::Avx2));
+    assert!(!summary.common.contains(&Feature::Avx512f));
+    assert_eq!(summary.asymmetric(), [Feature::Avx512f]);
+
+    let symmetric = HybridSummary::from_snapshots(&[(0, core(0x40, true))]);
+    assert!(!symmetric.is_hybrid());
+    assert!(symmetric.asymmetric().is_empty());
+}
+
+#[test]
+fn isa_check() {
+    let check = HybridIsaCheck::from_snapshots(&[
+        (0, core(0x40, true)),
+        (1, core(0x40, true)),
+        (2, core(0x20, false)),
+    ]);
+    assert!(!check.is_consistent());
+    assert!(check.is_safe(Feature::Avx2));
+    assert!(!check.is_safe(Feature::Avx512f));
+    assert_eq!(
+        check.asymmetric,
+        [AsymmetricFeature {
+            feature: Feature::Avx512f,
+            present: vec![0, 1],
+            missing: vec![2],
+        }]
+    );
+
+    // Cores of the same type that differ are found as well.
+    let check = HybridIsaCheck::from_snapshots(&[(0, core(0x40, true)), (1, core(0x40, false))]);
+    assert_eq!(check.asymmetric.len(), 1);
+    assert_eq!(check.asymmetric[0].missing, [1]);
+    let summary = HybridSummary::from_snapshots(&[(0, core(0x40, true)), (1, core(0x40, false))]);
+    assert!(summary.asymmetric().is_empty());
+
+    let check = HybridIsaCheck::from_snapshots(&[(0, core(0x40, true))]);
+    assert!(check.is_consistent());
+    assert!(check.is_safe(Feature::Avx512f));
+}
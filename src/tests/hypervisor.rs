@@ -0,0 +1,232 @@
+//! Hypervisor interfaces in the 0x4000_0000 range.
+use alloc::vec::Vec;
+
+use super::synthetic::{intel, res};
+use crate::{CpuId, CpuIdResult, CpuIdSnapshot, HypervisorClockSource};
+
+#[test]
+fn hyperv_nested_features() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 31, 0)),
+        // "Microsoft Hv"
+        (
+            (0x4000_0000, 0),
+            res(0x4000_000a, 0x7263_694d, 0x666f_736f, 0x7648_2074),
+        ),
+        // "Hv#1"
+        ((0x4000_0001, 0), res(0x3123_7648, 0, 0, 0)),
+        ((0x4000_000a, 0), res(0x000e_0101, 1, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let hv = cpuid.get_hypervisor_info().expect("Hypervisor is present");
+    assert!(hv.has_hyperv_interface());
+
+    let nested = hv
+        .hyperv_nested_features()
+        .expect("Leaf 0x4000_000A is supported");
+    assert_eq!(nested.enlightened_vmcs_version_low(), 1);
+    assert_eq!(nested.enlightened_vmcs_version_high(), 1);
+    assert!(nested.has_direct_virtual_flush());
+    assert!(nested.has_guest_mapping_flush());
+    assert!(nested.has_enlightened_msr_bitmap());
+    assert!(!nested.has_combined_virtualization_exceptions());
+    assert!(!nested.has_enlightened_tlb());
+    assert!(nested.has_evmcs_perf_global_ctrl());
+    assert_eq!(nested.as_cpuid_result(), LEAFS[3].1);
+
+    // Leaf 0x4000_000A is only valid with the Hyper-V interface:
+    static NO_INTERFACE: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 31, 0)),
+        (
+            (0x4000_0000, 0),
+            res(0x4000_000a, 0x7263_694d, 0x666f_736f, 0x7648_2074),
+        ),
+        ((0x4000_000a, 0), res(0x000e_0101, 1, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(NO_INTERFACE));
+    let hv = cpuid.get_hypervisor_info().expect("Hypervisor is present");
+    assert!(!hv.has_hyperv_interface());
+    assert!(hv.hyperv_nested_features().is_none());
+}
+
+#[test]
+fn hypervisor_interfaces() {
+    use crate::{Hypervisor, HypervisorClockSource};
+
+    // KVM with Hyper-V enlightenments:
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 31, 0)),
+        // "Microsoft Hv"
+        (
+            (0x4000_0000, 0),
+            res(0x4000_000a, 0x7263_694d, 0x666f_736f, 0x7648_2074),
+        ),
+        // "Hv#1"
+        ((0x4000_0001, 0), res(0x3123_7648, 0, 0, 0)),
+        // "KVMKVMKVM\0\0\0"
+        (
+            (0x4000_0100, 0),
+            res(0x4000_0110, 0x4b4d_564b, 0x564b_4d56, 0x0000_004d),
+        ),
+        ((0x4000_0110, 0), res(2_900_000, 1_000_000, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let hv = cpuid.get_hypervisor_info().expect("Hypervisor is present");
+    assert_eq!(hv.identify(), Hypervisor::HyperV);
+
+    let interfaces: Vec<_> = cpuid
+        .get_hypervisor_interfaces()
+        .expect("Hypervisor is present")
+        .collect();
+    assert_eq!(interfaces.len(), 2);
+    assert_eq!(interfaces[0], hv);
+    assert_eq!(interfaces[0].base(), 0x4000_0000);
+    assert!(interfaces[0].has_hyperv_interface());
+
+    let kvm = &interfaces[1];
+    assert_eq!(kvm.base(), 0x4000_0100);
+    assert_eq!(kvm.identify(), Hypervisor::KVM);
+    assert!(!kvm.has_hyperv_interface());
+    // The timing leaf of the block:
+    assert_eq!(
+        kvm.clock_source(),
+        Some(HypervisorClockSource::TimingLeaf {
+            tsc_khz: 2_900_000,
+            apic_khz: 1_000_000
+        })
+    );
+
+    // Snapshots contain all blocks:
+    let snapshot = CpuIdSnapshot::with_cpuid_reader(intel(LEAFS));
+    assert!(snapshot.get(0x4000_0100, 0).is_some());
+    assert!(snapshot.get(0x4000_0110, 0).is_some());
+    assert!(snapshot.get(0x4000_0200, 0).is_none());
+    let from_snapshot: Vec<_> = snapshot
+        .cpuid()
+        .get_hypervisor_interfaces()
+        .unwrap()
+        .map(|hv| hv.identify())
+        .collect();
+    assert_eq!(from_snapshot, [Hypervisor::HyperV, Hypervisor::KVM]);
+
+    static NO_HYPERVISOR: &[((u32, u32), CpuIdResult)] = &[((0x1, 0), res(0, 0, 0, 0))];
+    assert!(CpuId::with_cpuid_fn(intel(NO_HYPERVISOR))
+        .get_hypervisor_interfaces()
+        .is_none());
+}
+
+#[test]
+fn hypervisor_presence() {
+    use crate::Hypervisor;
+
+    let first_blocks = |leafs| {
+        let cpuid = CpuId::with_cpuid_fn(intel(leafs));
+        let first = cpuid.get_hypervisor_info();
+        let all: Vec<_> = cpuid.get_hypervisor_interfaces().unwrap().collect();
+        assert_eq!(first.as_ref(), all.first());
+        all.iter().map(|hv| hv.identify()).collect::<Vec<_>>()
+    };
+
+    // Old KVM reports EAX=0, also when it's the second block next to
+    // Hyper-V:
+    static OLD_KVM: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 31, 0)),
+        (
+            (0x4000_0000, 0),
+            res(0, 0x4b4d_564b, 0x564b_4d56, 0x0000_004d),
+        ),
+    ];
+    assert_eq!(first_blocks(OLD_KVM), [Hypervisor::KVM]);
+    // EAX=0 means the highest leaf is 0x4000_0001:
+    let cpuid = CpuId::with_cpuid_fn(intel(OLD_KVM));
+    assert_eq!(cpuid.max_hypervisor_leaf(), Some(0x4000_0001));
+    assert!(cpuid.read_leaf(0x4000_0001, 0).is_ok());
+    let snapshot = CpuIdSnapshot::with_cpuid_reader(intel(OLD_KVM));
+    assert!(snapshot.get(0x4000_0000, 0).is_some());
+    assert!(snapshot.get(0x4000_0001, 0).is_some());
+    assert!(snapshot.get(0x4000_0002, 0).is_none());
+    static HYPERV_AND_OLD_KVM: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 31, 0)),
+        (
+            (0x4000_0000, 0),
+            res(0x4000_000a, 0x7263_694d, 0x666f_736f, 0x7648_2074),
+        ),
+        (
+            (0x4000_0100, 0),
+            res(0, 0x4b4d_564b, 0x564b_4d56, 0x0000_004d),
+        ),
+    ];
+    assert_eq!(
+        first_blocks(HYPERV_AND_OLD_KVM),
+        [Hypervisor::HyperV, Hypervisor::KVM]
+    );
+
+    // A highest leaf outside the block (e.g., the values of the highest basic
+    // leaf) isn't a hypervisor interface:
+    static OUT_OF_RANGE: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 31, 0)),
+        ((0x4000_0000, 0), res(0x16, 0x1, 0x2, 0x3)),
+    ];
+    assert!(first_blocks(OUT_OF_RANGE).is_empty());
+}
+
+#[test]
+fn hypervisor_clock_source() {
+    static KVM: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 31, 0)),
+        // "KVMKVMKVM\0\0\0"
+        (
+            (0x4000_0000, 0),
+            res(0x4000_0010, 0x4b4d_564b, 0x564b_4d56, 0x4d),
+        ),
+        ((0x4000_0010, 0), res(2_893_000, 1_000_000, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(KVM));
+    let hv = cpuid.get_hypervisor_info().expect("Hypervisor is present");
+    assert_eq!(
+        hv.clock_source(),
+        Some(HypervisorClockSource::TimingLeaf {
+            tsc_khz: 2_893_000,
+            apic_khz: 1_000_000
+        })
+    );
+    assert_eq!(hv.tsc_frequency(), Some(2_893_000));
+    assert_eq!(hv.apic_frequency(), Some(1_000_000));
+
+    static HYPERV: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 31, 0)),
+        // "Microsoft Hv", "Hv#1"
+        (
+            (0x4000_0000, 0),
+            res(0x4000_0010, 0x7263_694d, 0x666f_736f, 0x7648_2074),
+        ),
+        ((0x4000_0001, 0), res(0x3123_7648, 0, 0, 0)),
+        ((0x4000_0003, 0), res((1 << 11) | (1 << 9), 0, 0, 1 << 8)),
+        // Not a timing leaf on Hyper-V:
+        ((0x4000_0010, 0), res(0xdead, 0xbeef, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(HYPERV));
+    let hv = cpuid.get_hypervisor_info().expect("Hypervisor is present");
+    assert_eq!(
+        hv.clock_source(),
+        Some(HypervisorClockSource::HyperVFrequencyMsrs)
+    );
+    assert_eq!(hv.tsc_frequency(), None);
+    assert_eq!(hv.apic_frequency(), None);
+
+    static HYPERV_REFERENCE: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 31, 0)),
+        (
+            (0x4000_0000, 0),
+            res(0x4000_0003, 0x7263_694d, 0x666f_736f, 0x7648_2074),
+        ),
+        ((0x4000_0001, 0), res(0x3123_7648, 0, 0, 0)),
+        ((0x4000_0003, 0), res(1 << 9, 0, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(HYPERV_REFERENCE));
+    let hv = cpuid.get_hypervisor_info().expect("Hypervisor is present");
+    assert_eq!(
+        hv.clock_source(),
+        Some(HypervisorClockSource::HyperVReferenceCounter)
+    );
+}
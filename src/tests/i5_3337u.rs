@@ -96,7 +96,7 @@ fn cache_parameters() {
                 assert!(cache.max_cores_for_package() == 8);
                 assert!(cache.coherency_line_size() == 64);
                 assert!(cache.physical_line_partitions() == 1);
-                assert!(cache.associativity() == 8);
+                assert_eq!(cache.associativity(), Associativity::NWay(8));
                 assert!(!cache.is_write_back_invalidate());
                 assert!(!cache.is_inclusive());
                 assert!(!cache.has_complex_indexing());
@@ -111,7 +111,7 @@ fn cache_parameters() {
                 assert!(cache.max_cores_for_package() == 8);
                 assert!(cache.coherency_line_size() == 64);
                 assert!(cache.physical_line_partitions() == 1);
-                assert!(cache.associativity() == 8);
+                assert_eq!(cache.associativity(), Associativity::NWay(8));
                 assert!(!cache.is_write_back_invalidate());
                 assert!(!cache.is_inclusive());
                 assert!(!cache.has_complex_indexing());
@@ -126,7 +126,7 @@ fn cache_parameters() {
                 assert!(cache.max_cores_for_package() == 8);
                 assert!(cache.coherency_line_size() == 64);
                 assert!(cache.physical_line_partitions() == 1);
-                assert!(cache.associativity() == 8);
+                assert_eq!(cache.associativity(), Associativity::NWay(8));
                 assert!(!cache.is_write_back_invalidate());
                 assert!(!cache.is_inclusive());
                 assert!(!cache.has_complex_indexing());
@@ -141,7 +141,7 @@ fn cache_parameters() {
                 assert!(cache.max_cores_for_package() == 8);
                 assert!(cache.coherency_line_size() == 64);
                 assert!(cache.physical_line_partitions() == 1);
-                assert!(cache.associativity() == 12);
+                assert_eq!(cache.associativity(), Associativity::NWay(12));
                 assert!(!cache.is_write_back_invalidate());
                 assert!(cache.is_inclusive());
                 assert!(cache.has_complex_indexing());
@@ -180,7 +180,7 @@ fn thermal_power_features() {
         eax: ThermalPowerFeaturesEax { bits: 119 },
         ebx: 2,
         ecx: ThermalPowerFeaturesEcx { bits: 9 },
-        _edx: 0,
+        edx: 0,
     };
 
     assert!(tpfeatures.eax.contains(ThermalPowerFeaturesEax::DTS));
@@ -214,7 +214,7 @@ fn thermal_power_features() {
             | ThermalPowerFeaturesEax::HDC,
         ebx: 2,
         ecx: ThermalPowerFeaturesEcx::HW_COORD_FEEDBACK | ThermalPowerFeaturesEcx::ENERGY_BIAS_PREF,
-        _edx: 0,
+        edx: 0,
     };
 
     assert!(tpfeatures.has_dts());
@@ -241,7 +241,12 @@ fn extended_features() {
         _eax: 0,
         ebx: ExtendedFeaturesEbx { bits: 641 },
         ecx: ExtendedFeaturesEcx { bits: 0 },
-        _edx: 0,
+        edx: 0,
+        eax1: 0,
+        ebx1: 0,
+        ecx1: 0,
+        edx1: 0,
+        edx2: 0,
     };
     assert!(tpfeatures._eax == 0);
     assert!(tpfeatures.has_fsgsbase());
@@ -275,7 +280,12 @@ fn extended_features() {
             | ExtendedFeaturesEbx::CLFLUSHOPT
             | ExtendedFeaturesEbx::PROCESSOR_TRACE,
         ecx: ExtendedFeaturesEcx { bits: 0 },
-        _edx: 201326592,
+        edx: 201326592,
+        eax1: 0,
+        ebx1: 0,
+        ecx1: 0,
+        edx1: 0,
+        edx2: 0,
     };
 
     assert!(tpfeatures2.has_fsgsbase());
@@ -306,7 +316,7 @@ fn performance_monitoring_info() {
     let pm = PerformanceMonitoringInfo {
         eax: 120587267,
         ebx: PerformanceMonitoringFeaturesEbx { bits: 0 },
-        _ecx: 0,
+        ecx: 0,
         edx: 1539,
     };
 
@@ -372,7 +382,7 @@ fn extended_topology_info() {
 #[test]
 fn extended_state_info() {
     let es = ExtendedStateInfo {
-        read: Default::default(),
+        read: CpuIdReaderNative,
         eax: ExtendedStateInfoXCR0Flags { bits: 7 },
         ebx: 832,
         ecx: 832,
@@ -399,7 +409,7 @@ fn extended_state_info3() {
     });*/
 
     let esi = ExtendedStateInfo {
-        read: Default::default(),
+        read: CpuIdReaderNative,
         eax: ExtendedStateInfoXCR0Flags::LEGACY_X87
             | ExtendedStateInfoXCR0Flags::SSE128
             | ExtendedStateInfoXCR0Flags::AVX256
@@ -558,7 +568,7 @@ fn extended_state_info3() {
 #[test]
 fn extended_state_info2() {
     let es = ExtendedStateInfo {
-        read: Default::default(),
+        read: CpuIdReaderNative,
         eax: ExtendedStateInfoXCR0Flags { bits: 31 },
         ebx: 1088,
         ecx: 1088,
@@ -633,7 +643,7 @@ fn extended_state_info2() {
 #[test]
 fn quality_of_service_info() {
     let qos = RdtMonitoringInfo {
-        read: Default::default(),
+        read: CpuIdReaderNative,
         ebx: 832,
         edx: 0,
     };
@@ -666,7 +676,7 @@ fn extended_processor_feature_identifiers() {
 
 #[test]
 fn processor_brand_string() {
-    let pbs = crate::extended::ProcessorBrandString::new([
+    let pbs = crate::extended::ProcessorBrandString::from_registers([
         CpuIdResult {
             eax: 538976288,
             ebx: 1226842144,
@@ -694,7 +704,7 @@ fn processor_brand_string() {
 #[test]
 fn sgx_test() {
     let sgx = SgxInfo {
-        read: Default::default(),
+        read: CpuIdReaderNative,
         eax: 1,
         ebx: 0,
         _ecx: 0,
@@ -711,4 +721,69 @@ fn sgx_test() {
     assert!(!sgx.has_sgx2());
     assert!(sgx.miscselect() == 0x0);
     assert!(sgx.secs_attributes() == (0x0000000000000036, 0x000000000000001f));
+
+    assert_eq!(sgx.max_enclave_bytes_64bit(), 1 << 0x24);
+    assert_eq!(sgx.max_enclave_bytes_non_64bit(), 1 << 0x1f);
+    assert!(sgx.has_attribute_debug());
+    assert!(sgx.has_attribute_mode64bit());
+    assert!(sgx.has_attribute_provisionkey());
+    assert!(sgx.has_attribute_einittoken_key());
+    assert!(!sgx.has_attribute_kss());
+}
+
+#[test]
+fn sgx_launch_control_and_epc() {
+    fn reader(eax: u32, ecx: u32) -> CpuIdResult {
+        let (eax, ebx, ecx, edx) = match (eax, ecx) {
+            (0x0, _) => (0x12, 0x756e6547, 0x6c65746e, 0x49656e69),
+            // SGX and SGX_LC:
+            (0x7, 0) => (0, 1 << 2, 1 << 30, 0),
+            (0x12, 0) => (0x1, 0, 0, 0x241f),
+            (0x12, 1) => (0x36, 0, 0x1f, 0),
+            // Two EPC sections of 93.5 MiB and 64 MiB:
+            (0x12, 2) => (0x7020_0001, 0, 0x05d8_0001, 0),
+            (0x12, 3) => (0x8000_0001, 0x1, 0x0400_0001, 0),
+            _ => (0, 0, 0, 0),
+        };
+        CpuIdResult { eax, ebx, ecx, edx }
+    }
+
+    let cpuid = CpuId::with_cpuid_fn(reader);
+    assert!(cpuid.has_sgx_flexible_launch_control());
+    let sgx = cpuid.get_sgx_info().expect("Leaf is supported");
+    assert_eq!(sgx.iter().count(), 2);
+    assert_eq!(sgx.epc_size(), 0x05d8_0000 + 0x0400_0000);
+
+    let without_lc = CpuId::with_cpuid_fn(|eax, ecx| match (eax, ecx) {
+        (0x7, 0) => CpuIdResult {
+            eax: 0,
+            ebx: 1 << 2,
+            ecx: 0,
+            edx: 0,
+        },
+        _ => reader(eax, ecx),
+    });
+    assert!(!without_lc.has_sgx_flexible_launch_control());
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn arch_cpuid_result() {
+    let arch = core::arch::x86_64::CpuidResult {
+        eax: 198313,
+        ebx: 34605056,
+        ecx: 2109399999,
+        edx: 3219913727,
+    };
+    let res: CpuIdResult = arch.into();
+    assert_eq!(
+        res,
+        CpuIdResult {
+            eax: 198313,
+            ebx: 34605056,
+            ecx: 2109399999,
+            edx: 3219913727,
+        }
+    );
+    assert_eq!(core::arch::x86_64::CpuidResult::from(res), arch);
 }
@@ -0,0 +1,61 @@
+use crate::{CpuIdBuilder, CpuIdSnapshot, KvmCpuidEntry2, KVM_CPUID_FLAG_SIGNIFCANT_INDEX};
+
+#[test]
+fn kvm_entries_round_trip() {
+    let snapshot = CpuIdSnapshot::from(CpuIdBuilder::from_host().build());
+    let entries = snapshot.to_kvm_entries();
+    assert_eq!(entries.len(), snapshot.len());
+    assert_eq!(CpuIdSnapshot::from_kvm_entries(&entries), snapshot);
+
+    for entry in entries.iter().filter(|e| e.function == 0x4) {
+        assert_eq!(entry.flags, KVM_CPUID_FLAG_SIGNIFCANT_INDEX);
+    }
+    for entry in entries.iter().filter(|e| e.function == 0x1) {
+        assert_eq!(entry.flags, 0);
+    }
+}
+
+#[test]
+fn kvm_entries_ignore_insignificant_index() {
+    let entries = [KvmCpuidEntry2 {
+        function: 0x1,
+        index: 3,
+        eax: 0x906ea,
+        ..Default::default()
+    }];
+    let snapshot = CpuIdSnapshot::from_kvm_entries(&entries);
+    assert_eq!(snapshot.get(0x1, 0).map(|r| r.eax), Some(0x906ea));
+    assert_eq!(snapshot.get(0x1, 3), None);
+}
+
+#[cfg(all(feature = "kvm", target_arch = "x86_64"))]
+#[test]
+fn kvm_bindings_cpuid() {
+    use crate::{testdata, CpuIdResult, KvmCpuIdError};
+    use alloc::collections::BTreeMap;
+    use kvm_bindings::KVM_MAX_CPUID_ENTRIES;
+
+    let snapshot = testdata::load("xeon-gold-6252").unwrap();
+    let cpuid = kvm_bindings::CpuId::try_from(&snapshot).unwrap();
+    assert_eq!(cpuid.as_slice().len(), snapshot.len());
+    let leaf_7 = cpuid.as_slice().iter().find(|e| e.function == 0x7).unwrap();
+    assert_eq!(leaf_7.flags, KVM_CPUID_FLAG_SIGNIFCANT_INDEX);
+    assert_eq!(CpuIdSnapshot::from(&cpuid), snapshot);
+
+    let zero = CpuIdResult {
+        eax: 0,
+        ebx: 0,
+        ecx: 0,
+        edx: 0,
+    };
+    let leafs: BTreeMap<_, _> = (0..=KVM_MAX_CPUID_ENTRIES as u32)
+        .map(|subleaf| ((0xd, subleaf), zero))
+        .collect();
+    assert_eq!(
+        kvm_bindings::CpuId::try_from(&CpuIdSnapshot::from(leafs)),
+        Err(KvmCpuIdError::TooManyEntries {
+            entries: KVM_MAX_CPUID_ENTRIES + 1,
+            max: KVM_MAX_CPUID_ENTRIES
+        })
+    );
+}
@@ -0,0 +1,159 @@
+//! Tests against the dump of a KVM guest on a Sapphire Rapids host (see
+//! [`testdata`](crate::testdata)).
+use alloc::vec::Vec;
+
+use crate::{
+    testdata, CetSupport, CpuIdError, CpuIdSnapshot, ExtendedRegisterType, Feature, Hypervisor,
+    TopologyType,
+};
+
+fn snapshot() -> CpuIdSnapshot {
+    testdata::load("kvm-sapphire-rapids").unwrap()
+}
+
+#[test]
+fn kvm_guest() {
+    let snapshot = snapshot();
+    let cpuid = snapshot.cpuid();
+    assert_eq!(
+        cpuid.get_hypervisor_info().map(|hv| hv.identify()),
+        Some(Hypervisor::KVM)
+    );
+    assert!(cpuid.has_feature(Feature::Hypervisor));
+    // The host's model and target are still recognized in the guest:
+    assert_eq!(
+        cpuid.newest_supported_model().map(|m| m.name),
+        Some("SapphireRapids")
+    );
+    assert_eq!(cpuid.march(), Some("sapphirerapids"));
+    // The terminating subleafs are part of this capture:
+    assert_eq!(snapshot.get(0x4, 4).map(|res| res.eax), Some(0));
+}
+
+#[test]
+fn max_leafs() {
+    let snapshot = snapshot();
+    let cpuid = snapshot.cpuid();
+    assert_eq!(cpuid.max_basic_leaf(), 0x20);
+    assert_eq!(cpuid.max_hypervisor_leaf(), Some(0x4000_0001));
+    assert_eq!(cpuid.max_extended_leaf(), Some(0x8000_0008));
+
+    assert!(cpuid.read_leaf(0x4000_0001, 0).is_ok());
+    assert_eq!(
+        cpuid.read_leaf(0x4000_0002, 0),
+        Err(CpuIdError::LeafUnsupported { leaf: 0x4000_0002 })
+    );
+    assert!(cpuid.read_leaf(0x8000_0008, 0).is_ok());
+    assert!(cpuid.read_leaf(0x8000_0009, 0).is_err());
+    assert!(cpuid.read_leaf(0x21, 0).is_err());
+}
+
+#[test]
+fn hypervisor_signature() {
+    let snapshot = snapshot();
+    let hv = snapshot.cpuid().get_hypervisor_info().unwrap();
+    assert_eq!(&hv.signature(), b"KVMKVMKVM\0\0\0");
+    assert!(!snapshot.cpuid().is_tdx_guest());
+}
+
+#[test]
+fn supported_subleafs() {
+    let snapshot = snapshot();
+    let cpuid = snapshot.cpuid();
+    assert!(cpuid.supports_leaf(0x4, 3));
+    assert!(!cpuid.supports_leaf(0x4, 4));
+    assert!(cpuid.supports_leaf(0x7, 2));
+    assert!(!cpuid.supports_leaf(0x7, 3));
+    assert!(cpuid.supports_leaf(0xb, 1));
+    assert!(!cpuid.supports_leaf(0xb, 2));
+    assert!(cpuid.supports_leaf(0x1f, 1));
+    assert!(!cpuid.supports_leaf(0x1f, 2));
+    // XCR0 components (AVX-512 and AMX among them), CET in IA32_XSS:
+    assert!(cpuid.supports_leaf(0xd, 1));
+    assert!(cpuid.supports_leaf(0xd, 2));
+    assert!(!cpuid.supports_leaf(0xd, 3));
+    assert!(cpuid.supports_leaf(0xd, 7));
+    assert!(!cpuid.supports_leaf(0xd, 8));
+    assert!(cpuid.supports_leaf(0xd, 11));
+    assert!(cpuid.supports_leaf(0xd, 12));
+    assert!(cpuid.supports_leaf(0xd, 18));
+    assert!(!cpuid.supports_leaf(0xd, 19));
+    assert!(!cpuid.supports_leaf(0x1e, 1));
+    // Beyond the maximum leafs:
+    assert!(!cpuid.supports_leaf(0x21, 0));
+    assert!(!cpuid.supports_leaf(0x8000_0009, 0));
+}
+
+#[test]
+fn address_bits() {
+    let snapshot = snapshot();
+    let cpuid = snapshot.cpuid();
+    assert!(cpuid.supports_5_level_paging());
+    assert_eq!(cpuid.physical_address_bits(), 46);
+    assert_eq!(cpuid.linear_address_bits(), 57);
+    // KVM reports the guest physical address bits on Intel as well:
+    assert_eq!(cpuid.guest_physical_address_bits(), 46);
+}
+
+#[test]
+fn extended_features_subleaf2() {
+    let snapshot = snapshot();
+    let cpuid = snapshot.cpuid();
+    let features = cpuid.get_extended_feature_info().unwrap();
+    assert!(features.has_psfd());
+    assert!(features.has_ipred_ctrl());
+    assert!(features.has_rrsba_ctrl());
+    assert!(!features.has_ddpd_u());
+    assert!(features.has_bhi_ctrl());
+    assert!(!features.has_mcdt_no());
+    assert!(cpuid.has_feature(Feature::BhiCtrl));
+}
+
+#[test]
+fn cet() {
+    let snapshot = snapshot();
+    let cpuid = snapshot.cpuid();
+    assert_eq!(
+        cpuid.cet_support(),
+        CetSupport {
+            shadow_stack: true,
+            indirect_branch_tracking: true,
+            supervisor_shadow_stack: false,
+            xsave_user_state: true,
+            xsave_supervisor_state: true,
+        }
+    );
+    let registers: Vec<_> = cpuid
+        .get_extended_state_info()
+        .unwrap()
+        .iter()
+        .map(|s| s.register())
+        .collect();
+    assert!(registers.contains(&ExtendedRegisterType::CetU));
+    assert!(registers.contains(&ExtendedRegisterType::CetS));
+}
+
+#[test]
+fn tmul() {
+    let snapshot = snapshot();
+    let cpuid = snapshot.cpuid();
+    assert!(cpuid.has_feature(Feature::AmxTile));
+    let tmul = cpuid.get_tmul_info().unwrap();
+    assert_eq!(tmul.max_subleaf(), 0);
+    assert_eq!(tmul.max_k(), 16);
+    assert_eq!(tmul.max_n(), 64);
+    // Sapphire Rapids only reports subleaf 0.
+    assert!(!tmul.has_amx_int8());
+    assert_eq!(tmul.subleaf1_as_cpuid_result(), None);
+}
+
+#[test]
+fn extended_topology_v2() {
+    let snapshot = snapshot();
+    let cpuid = snapshot.cpuid();
+    let levels = cpuid.get_extended_topology_info().unwrap();
+    assert_eq!(levels.leaf(), 0x1F);
+    let types: Vec<_> = levels.map(|level| level.level_type()).collect();
+    assert_eq!(types, [TopologyType::SMT, TopologyType::Core]);
+    assert_eq!(cpuid.num_logical_cpus(), Some(1));
+}
@@ -0,0 +1,242 @@
+//! Maximum leafs, leaf availability and how they are cached.
+use super::synthetic::{intel, res};
+use crate::{CpuId, CpuIdBuilder, CpuIdError, CpuIdResult, CpuIdSnapshot, Feature};
+
+#[test]
+fn refresh() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[((0x7, 0), res(0, 0, 0, 1 << 14))];
+
+    // The maximum basic leaf changes, e.g., after a microcode update.
+    let max_leaf = Rc::new(Cell::new(0x6));
+    let read = {
+        let max_leaf = max_leaf.clone();
+        let intel = intel(LEAFS);
+        move |eax, ecx| match eax {
+            0 => res(max_leaf.get(), 0x756e6547, 0x6c65746e, 0x49656e69),
+            _ => intel(eax, ecx),
+        }
+    };
+    let mut cpuid = CpuId::with_cpuid_fn(read);
+    assert!(cpuid.get_extended_feature_info().is_none());
+
+    max_leaf.set(0x7);
+    assert!(cpuid.get_extended_feature_info().is_none());
+    cpuid.refresh();
+    assert_eq!(cpuid.max_basic_leaf(), 0x7);
+    assert!(cpuid
+        .get_extended_feature_info()
+        .is_some_and(|info| info.has_serialize()));
+}
+
+#[test]
+fn cached_limits() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 31, 0)),
+        ((0x4000_0000, 0), res(0x4000_0001, 0, 0, 0)),
+        ((0x4000_0001, 0), res(0x3123_7648, 0, 0, 0)),
+    ];
+    // Counts the reads of the leafs that gate other leafs:
+    let reads = Rc::new(Cell::new(0));
+    let read = {
+        let reads = reads.clone();
+        let intel = intel(LEAFS);
+        move |eax, ecx| {
+            if matches!(eax, 0x0 | 0x8000_0000 | 0x4000_0000) {
+                reads.set(reads.get() + 1);
+            }
+            intel(eax, ecx)
+        }
+    };
+    let mut cpuid = CpuId::with_cpuid_fn(read);
+    assert_eq!(reads.get(), 3);
+
+    for _ in 0..4 {
+        assert_eq!(cpuid.max_hypervisor_leaf(), Some(0x4000_0001));
+        assert!(cpuid.read_leaf(0x4000_0001, 0).is_ok());
+        assert!(cpuid.read_leaf(0x4000_0002, 0).is_err());
+        assert!(cpuid.get_extended_feature_info().is_none());
+    }
+    assert_eq!(reads.get(), 3);
+
+    cpuid.refresh();
+    assert_eq!(reads.get(), 6);
+
+    // The Centaur and Transmeta limits of unknown vendors are cached too, in
+    // case the vendor is made permissive later on:
+    let reads = Rc::new(Cell::new(0));
+    let read = {
+        let reads = reads.clone();
+        move |eax, _ecx| {
+            let vendor = |name: &[u8; 4]| u32::from_le_bytes(*name);
+            match eax {
+                0x0 => res(0x1, vendor(b"Fake"), vendor(b"orXX"), vendor(b"Vend")),
+                0xc000_0000 => {
+                    reads.set(reads.get() + 1);
+                    res(0xc000_0001, 0, 0, 0)
+                }
+                0x8086_0000 => {
+                    reads.set(reads.get() + 1);
+                    res(0x8086_0002, 0, 0, 0)
+                }
+                _ => res(0, 0, 0, 0),
+            }
+        }
+    };
+    let mut cpuid = CpuId::with_cpuid_fn(read);
+    assert_eq!(reads.get(), 2);
+    assert_eq!(cpuid.max_centaur_leaf(), None);
+    cpuid.set_permissive_vendor(true);
+    for _ in 0..4 {
+        assert_eq!(cpuid.max_centaur_leaf(), Some(0xc000_0001));
+        assert_eq!(cpuid.max_transmeta_leaf(), Some(0x8086_0002));
+        assert!(cpuid.read_leaf(0xc000_0001, 0).is_ok());
+        assert!(cpuid.read_leaf(0x8086_0003, 0).is_err());
+    }
+    assert_eq!(reads.get(), 2);
+}
+
+#[test]
+fn unsupported_leafs() {
+    // Old CPUs without extended leafs return the highest basic leaf for
+    // 0x8000_0000:
+    static GARBAGE: &[((u32, u32), CpuIdResult)] = &[
+        ((0x2, 0), res(0x0302_0101, 0, 0, 0x0c04_0843)),
+        ((0x8000_0000, 0), res(0x0302_0101, 0, 0, 0x0c04_0843)),
+        ((0x8000_0001, 0), res(0x0302_0101, 0, 0, 0x0c04_0843)),
+        ((0x4000_0000, 0), res(0x0302_0101, 0, 0, 0x0c04_0843)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(GARBAGE));
+    assert!(cpuid
+        .get_extended_processor_and_feature_identifiers()
+        .is_none());
+    assert!(cpuid.get_processor_brand_string().is_none());
+    assert_eq!(
+        cpuid.read_leaf(0x8000_0001, 0),
+        Err(CpuIdError::LeafUnsupported { leaf: 0x8000_0001 })
+    );
+    assert_eq!(
+        cpuid.read_leaf(0x4000_0000, 0),
+        Err(CpuIdError::LeafUnsupported { leaf: 0x4000_0000 })
+    );
+    assert_eq!(
+        cpuid.read_leaf(0x3, 0),
+        Err(CpuIdError::LeafUnsupported { leaf: 0x3 })
+    );
+    assert_eq!(cpuid.read_leaf(0x2, 0), Ok(GARBAGE[0].1));
+}
+
+#[test]
+fn max_leafs() {
+    // No hypervisor flag and garbage in 0x8000_0000:
+    static BARE_METAL: &[((u32, u32), CpuIdResult)] = &[
+        ((0x2, 0), res(0x0302_0101, 0, 0, 0)),
+        ((0x4000_0000, 0), res(0x0302_0101, 0, 0, 0)),
+        ((0x8000_0000, 0), res(0x0302_0101, 0, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(BARE_METAL));
+    assert_eq!(cpuid.max_basic_leaf(), 0x2);
+    assert_eq!(cpuid.max_hypervisor_leaf(), None);
+    assert_eq!(cpuid.max_extended_leaf(), None);
+}
+
+#[test]
+fn supported_subleafs() {
+    // Enumerations that none of the dumps report:
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x12, 2), res(0x1, 0, 0, 0)),
+        ((0x1b, 0), res(1, 0, 0, 0)),
+        ((0x23, 0), res(0b1010, 0, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+
+    assert!(cpuid.supports_leaf(0x12, 1));
+    assert!(cpuid.supports_leaf(0x12, 2));
+    assert!(!cpuid.supports_leaf(0x12, 3));
+    assert!(!cpuid.supports_leaf(0x1b, 1));
+    assert!(cpuid.supports_leaf(0x23, 1));
+    assert!(!cpuid.supports_leaf(0x23, 2));
+}
+
+#[test]
+fn try_get_reasons() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(0, 0, 0, 0)),
+        ((0x8000_0000, 0), res(0x8000_0008, 0, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    assert!(cpuid.try_get_extended_feature_info().is_ok());
+    assert_eq!(
+        cpuid.try_get_tsc_info().unwrap_err(),
+        CpuIdError::LeafUnsupported { leaf: 0x15 }
+    );
+    assert_eq!(
+        cpuid.try_get_processor_capacity_feature_info().unwrap_err(),
+        CpuIdError::InvalidData { leaf: 0x8000_0008 }
+    );
+    assert_eq!(
+        cpuid.try_get_sgx_info().unwrap_err(),
+        CpuIdError::FeatureMissing {
+            leaf: 0x12,
+            feature: Feature::Sgx
+        }
+    );
+    assert_eq!(
+        cpuid.try_get_l1_cache_and_tlb_info().unwrap_err(),
+        CpuIdError::VendorMismatch { leaf: 0x8000_0005 }
+    );
+
+    // A hypervisor that doesn't report a maximum leaf:
+    static HYPERVISOR: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 31, 0)),
+        ((0x4000_0000, 0), res(0, 0, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(HYPERVISOR));
+    assert_eq!(
+        cpuid.try_get_hypervisor_info().unwrap_err(),
+        CpuIdError::LeafUnsupported { leaf: 0x4000_0000 }
+    );
+}
+
+#[test]
+fn permissive_vendor() {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"NewVendorX86")
+        .set_feature(Feature::Sse4a, true)
+        .set(
+            0x8000_0005,
+            0,
+            res(0xff40_ff18, 0xff40_ff30, 0x2004_0140, 0x2004_0140),
+        );
+    let snapshot = CpuIdSnapshot::from(builder.build());
+
+    let mut cpuid = snapshot.cpuid();
+    assert!(!cpuid.is_permissive_vendor());
+    assert!(cpuid.get_l1_cache_and_tlb_info().is_none());
+    assert!(!cpuid
+        .get_extended_processor_and_feature_identifiers()
+        .unwrap()
+        .has_sse4a());
+
+    cpuid.set_permissive_vendor(true);
+    let l1 = cpuid.get_l1_cache_and_tlb_info().unwrap();
+    assert_eq!(l1.dcache_size(), 32);
+    assert!(cpuid
+        .get_extended_processor_and_feature_identifiers()
+        .unwrap()
+        .has_sse4a());
+
+    // Intel doesn't define these leafs, permissive or not:
+    let mut builder = CpuIdBuilder::with_cpuid_reader(&snapshot);
+    builder.set_vendor(b"GenuineIntel");
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let mut cpuid = snapshot.cpuid();
+    cpuid.set_permissive_vendor(true);
+    assert!(cpuid.get_l1_cache_and_tlb_info().is_none());
+}
@@ -0,0 +1,70 @@
+use crate::{CpuIdBuilder, CpuIdResult, CpuIdSnapshot, Feature};
+
+fn host(l3_sets: u32, physical_bits: u8) -> CpuIdBuilder {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"GenuineIntel")
+        .set_family_model_stepping(6, 0x55, 4)
+        .set_address_sizes(physical_bits, 48)
+        .set_feature(Feature::Sse2, true)
+        .set_feature(Feature::Avx2, true)
+        .set(
+            0x4,
+            0,
+            CpuIdResult {
+                eax: 0x3c07c163,
+                ebx: 0x03c0003f,
+                ecx: l3_sets - 1,
+                edx: 0x4,
+            },
+        )
+        .set(
+            0x4,
+            1,
+            CpuIdResult {
+                eax: 0,
+                ebx: 0,
+                ecx: 0,
+                edx: 0,
+            },
+        );
+    builder
+}
+
+#[test]
+fn intersection() {
+    let a = CpuIdSnapshot::from(host(0x8000, 46).build());
+    let mut b = host(0x6000, 43);
+    b.set_feature(Feature::Avx2, false)
+        .set_feature(Feature::Avx512f, true)
+        .set(
+            0x8000_0007,
+            0,
+            CpuIdResult {
+                eax: 0,
+                ebx: 0,
+                ecx: 0,
+                edx: 1 << 8,
+            },
+        );
+    let b = CpuIdSnapshot::from(b.build());
+
+    let common = CpuIdSnapshot::intersection([&a, &b]).unwrap();
+    let cpuid = common.cpuid();
+    assert!(cpuid.has_feature(Feature::Sse2));
+    assert!(!cpuid.has_feature(Feature::Avx2));
+    assert!(!cpuid.has_feature(Feature::Avx512f));
+    assert_eq!(common.get(0x8000_0007, 0), None);
+    assert_eq!(
+        cpuid
+            .get_processor_capacity_feature_info()
+            .map(|c| (c.physical_address_bits(), c.linear_address_bits())),
+        Some((43, 48))
+    );
+    let l3 = cpuid.get_cache_parameters().unwrap().next().unwrap();
+    assert_eq!(l3.sets(), 0x6000);
+    assert_eq!(cpuid.get_vendor_info().unwrap().as_str(), "GenuineIntel");
+
+    assert_eq!(CpuIdSnapshot::intersection([&a]), Some(a.clone()));
+    assert_eq!(CpuIdSnapshot::intersection(None), None);
+}
@@ -1,3 +1,38 @@
+mod addressing;
+mod binary;
+mod builder;
+mod caches;
+#[cfg(any(feature = "json", feature = "yaml", feature = "toml"))]
+mod codec;
+#[cfg(all(feature = "std", target_os = "linux"))]
+mod dev_cpu;
+mod extended_features;
+mod fake;
+mod features;
+mod ffi;
+mod frequency;
+mod hybrid;
+mod hypervisor;
 mod i5_3337u;
+mod kvm;
+mod kvm_sapphire_rapids;
+mod limits;
+mod migration;
+mod models;
+mod native;
+mod numa;
+mod power_perf;
+mod query;
+mod registers;
+mod report;
 mod ryzen_matisse;
+mod security;
+mod summary;
+mod synthetic;
+#[cfg(target_os = "linux")]
+mod sysfs;
+mod testdata;
+mod topology;
+mod vendors;
+mod windows;
 mod xeon_gold_6252;
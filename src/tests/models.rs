@@ -0,0 +1,154 @@
+use crate::{CpuIdBuilder, CpuIdSnapshot, CpuModel, Feature};
+
+fn snapshot_of(model: &CpuModel) -> CpuIdSnapshot {
+    let mut builder = CpuIdBuilder::new();
+    builder.set_vendor(model.vendor.as_bytes().try_into().unwrap());
+    for feature in model.features() {
+        builder.set_feature(feature, true);
+    }
+    CpuIdSnapshot::from(builder.build())
+}
+
+#[test]
+fn model_names() {
+    for model in CpuModel::ALL {
+        assert_eq!(CpuModel::from_name(model.name), Some(model));
+    }
+    assert!(CpuModel::from_name("skylake-server").is_none());
+}
+
+#[test]
+fn newest_supported_model() {
+    for model in CpuModel::ALL {
+        let snapshot = snapshot_of(model);
+        let cpuid = snapshot.cpuid();
+        assert!(cpuid.supports_model(model), "{}", model.name);
+        assert_eq!(cpuid.newest_supported_model(), Some(model));
+    }
+
+    let skylake = CpuModel::from_name("Skylake-Server").unwrap();
+    let mut builder = CpuIdBuilder::with_cpuid_reader(snapshot_of(skylake));
+    builder.set_feature(Feature::Avx512f, false);
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+    assert!(!cpuid.supports_model(skylake));
+    assert_eq!(
+        skylake
+            .missing_features(&cpuid)
+            .collect::<std::vec::Vec<_>>(),
+        [Feature::Avx512f]
+    );
+    assert_eq!(
+        cpuid.newest_supported_model().map(|m| m.name),
+        Some("Skylake-Client")
+    );
+}
+
+#[test]
+fn newer_extensions_are_required() {
+    for (model, feature, fallback) in [
+        ("Cooperlake", Feature::Avx512bf16, "Cascadelake-Server"),
+        ("SapphireRapids", Feature::Avx512fp16, "Icelake-Server"),
+        ("SapphireRapids", Feature::AvxVnni, "Icelake-Server"),
+        ("EPYC-Genoa", Feature::Avx512bf16, "EPYC-Milan"),
+        ("Skylake-Client", Feature::Xsaves, "Broadwell"),
+    ] {
+        let model = CpuModel::from_name(model).unwrap();
+        let mut builder = CpuIdBuilder::with_cpuid_reader(snapshot_of(model));
+        builder.set_feature(feature, false);
+        let snapshot = CpuIdSnapshot::from(builder.build());
+        assert_eq!(
+            snapshot.cpuid().newest_supported_model().map(|m| m.name),
+            Some(fallback),
+            "{} without {:?}",
+            model.name,
+            feature
+        );
+    }
+}
+
+#[test]
+fn epyc_is_not_a_superset_of_opteron_g5() {
+    let epyc = snapshot_of(CpuModel::from_name("EPYC").unwrap());
+    let g5 = CpuModel::from_name("Opteron_G5").unwrap();
+    assert!(!epyc.cpuid().supports_model(g5));
+}
+
+#[test]
+fn qemu_names() {
+    assert_eq!(Feature::Sse41.qemu_name(), Some("sse4.1"));
+    assert_eq!(
+        Feature::Avx512vpopcntdq.qemu_name(),
+        Some("avx512-vpopcntdq")
+    );
+    assert_eq!(Feature::Osxsave.qemu_name(), None);
+    assert_eq!(
+        Feature::from_qemu_name("tsc-deadline"),
+        Some(Feature::TscDeadline)
+    );
+    assert_eq!(Feature::from_qemu_name("sse4_1"), None);
+
+    for model in CpuModel::ALL {
+        assert!(model.features().all(|f| f.qemu_name().is_some()));
+    }
+}
+
+#[test]
+fn qemu_cpu_option() {
+    let model = CpuModel::from_name("Haswell").unwrap();
+    let host = snapshot_of(model);
+    let mut builder = CpuIdBuilder::with_cpuid_reader(&host);
+    builder
+        .set_feature(Feature::Avx2, false)
+        .set_feature(Feature::Hypervisor, true)
+        .set_feature(Feature::Osxsave, true);
+    let guest = CpuIdSnapshot::from(builder.build());
+    assert_eq!(
+        host.diff(&guest).qemu_cpu_option("Haswell"),
+        "Haswell,+hypervisor,-avx2"
+    );
+}
+
+#[test]
+fn march() {
+    for model in CpuModel::ALL {
+        assert_eq!(snapshot_of(model).cpuid().march(), Some(model.march));
+    }
+    let skylake = CpuModel::from_name("Skylake-Server").unwrap();
+    assert_eq!(skylake.march, "skylake-avx512");
+
+    for (name, march) in [
+        ("xeon-gold-6252", "cascadelake"),
+        ("ryzen-5-3600x", "znver2"),
+    ] {
+        let snapshot = crate::testdata::load(name).unwrap();
+        assert_eq!(snapshot.cpuid().march(), Some(march));
+    }
+
+    // Hypervisors hide system features, which don't change the target.
+    for (name, hidden, march) in [
+        ("ryzen-5-3600x", Feature::Svm, "znver2"),
+        ("xeon-gold-6252", Feature::Vme, "cascadelake"),
+    ] {
+        let mut builder = CpuIdBuilder::with_cpuid_reader(crate::testdata::load(name).unwrap());
+        builder.set_feature(hidden, false);
+        let snapshot = CpuIdSnapshot::from(builder.build());
+        assert!(snapshot.cpuid().newest_supported_model().map(|m| m.march) != Some(march));
+        assert_eq!(snapshot.cpuid().march(), Some(march));
+    }
+
+    // Other vendors get the x86-64 level.
+    let haswell = CpuModel::from_name("Haswell").unwrap();
+    let mut builder = CpuIdBuilder::with_cpuid_reader(snapshot_of(haswell));
+    builder.set_vendor(b"HygonGenuine");
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert_eq!(snapshot.cpuid().march(), Some("x86-64-v3"));
+
+    builder.set_feature(Feature::Movbe, false);
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert_eq!(snapshot.cpuid().march(), Some("x86-64-v2"));
+    assert_eq!(snapshot.cpuid().x86_64_level(), Some("x86-64-v2"));
+
+    let snapshot = CpuIdSnapshot::from(CpuIdBuilder::new().build());
+    assert_eq!(snapshot.cpuid().march(), None);
+}
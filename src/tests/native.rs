@@ -0,0 +1,94 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::native_cpuid::{invocation_count, set_invocation_hook};
+#[cfg(not(feature = "std"))]
+use crate::snapshot::GlobalCpuId;
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+use crate::{std_detected_features, Feature};
+use crate::{CpuId, CpuIdResult};
+
+static LEAF_0_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+fn count_leaf_0(leaf: u32, _subleaf: u32, _res: CpuIdResult) {
+    if leaf == 0 {
+        LEAF_0_CALLS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn invocation_hook() {
+    let before = invocation_count();
+    set_invocation_hook(Some(count_leaf_0));
+    let cpuid = CpuId::new();
+    set_invocation_hook(None);
+
+    // `CpuId::new` reads the vendor and maximum leaf from leaf 0:
+    assert!(cpuid.get_vendor_info().is_some());
+    assert!(invocation_count() > before);
+    assert!(LEAF_0_CALLS.load(Ordering::Relaxed) >= 1);
+}
+
+#[test]
+fn global_snapshot() {
+    let global = crate::global();
+    assert_eq!(global.get_vendor_info(), CpuId::new().get_vendor_info());
+    // The `CpuId` is created once:
+    assert!(core::ptr::eq(global, crate::global()));
+}
+
+#[cfg(not(feature = "std"))]
+#[test]
+fn global_retries_after_panic() {
+    static CPUID: GlobalCpuId = GlobalCpuId::new();
+
+    let result = std::panic::catch_unwind(|| CPUID.get(|| panic!("no cpuid")));
+    assert!(result.is_err());
+    // The next caller takes the snapshot instead of waiting forever.
+    let cpuid = CPUID.get(|| crate::testdata::load("ryzen-5-3600x").unwrap());
+    assert_eq!(cpuid.get_vendor_info().unwrap().as_str(), "AuthenticAMD");
+    assert!(core::ptr::eq(cpuid, CPUID.get(|| unreachable!())));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn thread_native_mock() {
+    use crate::native_cpuid::thread_mock_reader;
+    use crate::testdata;
+    use std::string::String;
+
+    let vendor = || {
+        CpuId::new()
+            .get_vendor_info()
+            .map(|vi| String::from(vi.as_str()))
+    };
+    let host = vendor();
+
+    let ryzen = testdata::load("ryzen-5-3600x")
+        .unwrap()
+        .install_as_thread_native();
+    assert_eq!(vendor().as_deref(), Some("AuthenticAMD"));
+    let xeon = testdata::load("xeon-gold-6252")
+        .unwrap()
+        .install_as_thread_native();
+    assert_eq!(vendor().as_deref(), Some("GenuineIntel"));
+    assert_eq!(std::thread::spawn(vendor).join().unwrap(), host);
+
+    drop(xeon);
+    assert_eq!(vendor().as_deref(), Some("AuthenticAMD"));
+    drop(ryzen);
+    assert!(thread_mock_reader().is_none());
+    assert_eq!(vendor(), host);
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[test]
+fn std_detection() {
+    let detected = std_detected_features();
+    assert!(detected.contains(&(Feature::Sse, true)));
+
+    // The standard library never detects more than CPUID reports, it only
+    // masks features the OS doesn't support.
+    for mismatch in CpuId::new().std_detection_mismatches() {
+        assert!(mismatch.cpuid && !mismatch.std, "{}", mismatch);
+    }
+}
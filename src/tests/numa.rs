@@ -0,0 +1,76 @@
+use crate::{
+    CpuIdBuilder, CpuIdResult, CpuIdSnapshot, Feature, NumaTopology, ProcessorTopologyInfo,
+};
+
+fn core(apic_id: u32, node_id: u8) -> CpuIdSnapshot {
+    let mut info = ProcessorTopologyInfo::from_registers(CpuIdResult {
+        eax: 0,
+        ebx: 0,
+        ecx: 0,
+        edx: 0,
+    });
+    info.set_extended_apic_id(apic_id);
+    info.set_core_id((apic_id / 2) as u8);
+    info.set_threads_per_core(2);
+    info.set_node_id(node_id);
+    info.set_nodes_per_processor(2);
+
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"AuthenticAMD")
+        .set_feature(Feature::TopoExt, true)
+        .set(0x8000_001e, 0, info.as_cpuid_result());
+    CpuIdSnapshot::from(builder.build())
+}
+
+#[test]
+fn processor_topology_info() {
+    let snapshot = core(0x13, 1);
+    let info = snapshot.cpuid().get_processor_topology_info().unwrap();
+    assert_eq!(info.extended_apic_id(), 0x13);
+    assert_eq!(info.core_id(), 9);
+    assert_eq!(info.threads_per_core(), 2);
+    assert_eq!(info.node_id(), 1);
+    assert_eq!(info.nodes_per_processor(), 2);
+    assert_eq!(
+        info.as_cpuid_result(),
+        CpuIdResult {
+            eax: 0x13,
+            ebx: 0x109,
+            ecx: 0x101,
+            edx: 0
+        }
+    );
+
+    // Without topology extensions the leaf isn't decoded.
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"AuthenticAMD")
+        .set(0x8000_001e, 0, info.as_cpuid_result());
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert!(snapshot.cpuid().get_processor_topology_info().is_none());
+}
+
+#[test]
+fn nodes() {
+    let snapshots = [
+        (0, core(0, 0)),
+        (1, core(1, 0)),
+        (2, core(8, 1)),
+        (3, core(9, 1)),
+        (4, core(2, 0)),
+    ];
+    let numa = NumaTopology::from_snapshots(&snapshots);
+    assert_eq!(numa.nodes.len(), 2);
+    assert_eq!(numa.nodes_per_processor, 2);
+    assert_eq!(numa.nodes[0].node_id, 0);
+    assert_eq!(numa.nodes[0].cores, [0, 1, 4]);
+    assert_eq!(numa.nodes[0].apic_ids, [0, 1, 2]);
+    assert_eq!(numa.node(1).unwrap().cores, [2, 3]);
+    assert_eq!(numa.node_of_apic_id(9), Some(1));
+    assert_eq!(numa.node_of_apic_id(3), None);
+    assert_eq!(numa.node_of_core(4), Some(0));
+
+    let intel = crate::testdata::load("xeon-gold-6252").unwrap();
+    assert!(NumaTopology::from_snapshots(&[(0, intel)]).is_empty());
+}
@@ -0,0 +1,178 @@
+//! Power management and performance monitoring leafs.
+use super::synthetic::{intel, res};
+use crate::{CpuId, CpuIdBuilder, CpuIdError, CpuIdResult, CpuIdSnapshot, Feature};
+
+#[test]
+fn direct_cache_access() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x1, 0), res(0, 0, 1 << 18, 0)),
+        ((0x9, 0), res(0x1, 0, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let dca = cpuid.get_direct_cache_access_info().unwrap();
+    assert_eq!(dca.get_dca_cap_value(), 0x1);
+    assert!(dca.is_dca_enabled());
+
+    // Leaf 9 is only valid if DCA is enumerated in leaf 1:
+    static WITHOUT_DCA: &[((u32, u32), CpuIdResult)] = &[((0x9, 0), res(0x1, 0, 0, 0))];
+    let cpuid = CpuId::with_cpuid_fn(intel(WITHOUT_DCA));
+    assert_eq!(
+        cpuid.try_get_direct_cache_access_info().unwrap_err(),
+        CpuIdError::FeatureMissing {
+            leaf: 0x9,
+            feature: Feature::Dca
+        }
+    );
+}
+
+#[test]
+fn hwp_capabilities() {
+    type Predicate = fn(&crate::ThermalPowerInfo) -> bool;
+    let predicates: [(u32, Predicate); 9] = [
+        (7, |i| i.has_hwp()),
+        (8, |i| i.has_hwp_notification()),
+        (9, |i| i.has_hwp_activity_window()),
+        (10, |i| i.has_hwp_energy_performance_preference()),
+        (11, |i| i.has_hwp_package_level_request()),
+        (16, |i| i.has_hwp_peci_override()),
+        (17, |i| i.has_flexible_hwp()),
+        (18, |i| i.has_hwp_fast_access_mode()),
+        (22, |i| i.has_hwp_control()),
+    ];
+
+    // Each capability is reported by its own bit:
+    for (bit, _) in predicates {
+        let mut builder = CpuIdBuilder::new();
+        builder
+            .set_vendor(b"GenuineIntel")
+            .set(0x6, 0, res(1 << bit, 0, 0, 0));
+        let snapshot = CpuIdSnapshot::from(builder.build());
+        let info = snapshot.cpuid().get_thermal_power_info().unwrap();
+        for (other, predicate) in predicates {
+            assert_eq!(predicate(&info), other == bit, "bit {} vs {}", bit, other);
+        }
+    }
+}
+
+#[test]
+fn hardware_feedback_interface() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        // HFI and Thread Director, 4 classes, a two page table with
+        // performance and energy efficiency columns, row 5:
+        ((0x6, 0), res((1 << 19) | (1 << 23), 0, 0x0409, 0x0005_0103)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let info = cpuid.get_thermal_power_info().unwrap();
+    assert!(info.has_hw_feedback());
+    assert!(info.has_thread_director());
+    assert_eq!(info.hw_feedback_capabilities(), 0x3);
+    assert!(info.has_hw_feedback_performance());
+    assert!(info.has_hw_feedback_energy_efficiency());
+    assert_eq!(info.hw_feedback_table_size(), 8192);
+    assert_eq!(info.hw_feedback_row_index(), 5);
+    assert_eq!(info.thread_director_classes(), 4);
+    assert!(info.has_hw_coord_feedback() && info.has_energy_bias_pref());
+
+    let mut copy = cpuid.get_thermal_power_info().unwrap();
+    copy.set_thread_director_classes(0);
+    copy.set_hw_feedback_table_size(4096 + 1);
+    copy.set_thread_director_classes(4);
+    assert_eq!(copy, info);
+}
+
+#[test]
+fn performance_monitoring_bitmaps() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        // Version 5, 8 events with reference cycles and top-down slots missing
+        // (bit 9 is beyond the vector), fixed counters 0-3 and 5:
+        ((0xa, 0), res(0x0830_0805, 0x284, 0b10_0000, 0x8604)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let pm = cpuid.get_performance_monitoring_info().unwrap();
+    assert_eq!(pm.ebx_length(), 8);
+    assert_eq!(pm.unavailable_events(), 0x84);
+    assert_eq!(pm.available_events(), 0x7b);
+    assert!(pm.is_ref_cycle_ev_unavailable());
+    assert!(pm.is_topdown_slots_ev_unavailable());
+    assert!(pm.is_event_available(0));
+    assert!(!pm.is_event_available(2));
+    assert!(!pm.is_event_available(8));
+    assert!(!pm.is_event_available(40));
+
+    assert_eq!(pm.fixed_function_counters(), 4);
+    assert_eq!(pm.fixed_counters_bitmap(), 0b10_0000);
+    assert_eq!(pm.fixed_counters(), 0b10_1111);
+    assert!(pm.is_fixed_counter_supported(3));
+    assert!(!pm.is_fixed_counter_supported(4));
+    assert!(pm.is_fixed_counter_supported(5));
+}
+
+#[test]
+fn arch_perfmon_ext() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(1, 0, 0, 0)),
+        ((0x7, 1), res(1 << 8, 0, 0, 0)),
+        ((0x23, 0), res(0b1010, 0b11, 8, 0)),
+        ((0x23, 1), res(0xff, 0b1_0111, 0, 0)),
+        ((0x23, 2), res(0xdead, 0, 0, 0)),
+        ((0x23, 3), res(0x1f83, 0, 0, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let pmu = cpuid
+        .get_arch_perfmon_ext_info()
+        .expect("Leaf 0x23 is supported");
+    assert!(pmu.has_unit_mask2());
+    assert!(pmu.has_eq_bit());
+    assert_eq!(pmu.topdown_slots(), 8);
+    assert_eq!(pmu.general_counter_bitmap(), 0xff);
+    assert_eq!(pmu.number_of_counters(), 8);
+    assert_eq!(pmu.fixed_counter_bitmap(), 0b1_0111);
+    assert_eq!(pmu.fixed_function_counters(), 4);
+    assert!(pmu.has_core_cycles_event());
+    assert!(pmu.has_instructions_retired_event());
+    assert!(!pmu.has_ref_cycles_event());
+    assert!(pmu.has_topdown_slots_event());
+    assert!(pmu.has_lbr_inserts_event());
+    assert_eq!(
+        pmu.as_cpuid_results(),
+        (LEAFS[2].1, Some(LEAFS[3].1), Some(LEAFS[5].1))
+    );
+
+    // Only the subleafs in the bitmap are captured:
+    let table = crate::CpuIdBuilder::with_cpuid_reader(intel(LEAFS)).build();
+    assert!(table.contains_key(&(0x23, 1)));
+    assert!(!table.contains_key(&(0x23, 2)));
+    assert!(table.contains_key(&(0x23, 3)));
+}
+
+#[test]
+fn amd_performance_monitoring_debug() {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"AuthenticAMD")
+        .set_feature(Feature::PerfCtrCore, true)
+        // PerfMonV2 and LbrV2, 6 core, 16 DF and 8 UMC counters, 16 LBRs:
+        .set(0x8000_0022, 0, res(0x3, 0x0008_4106, 0xff, 0));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+
+    let info = cpuid.get_performance_monitoring_debug_info().unwrap();
+    assert!(info.has_perfmon_v2() && info.has_lbr_stack());
+    assert!(!info.has_lbr_and_pmc_freeze());
+    assert_eq!(info.num_core_counters(), 6);
+    assert_eq!(info.lbr_stack_size(), 16);
+    assert_eq!(info.num_northbridge_counters(), 16);
+    assert_eq!(info.num_umc_counters(), 8);
+    assert_eq!(info.active_umc_mask(), 0xff);
+    assert_eq!(cpuid.amd_core_performance_counters(), Some(6));
+
+    // Without PerfMonV2, the count depends on the counter extensions:
+    let mut builder = CpuIdBuilder::with_cpuid_reader(&snapshot);
+    builder.update(0x8000_0022, 0, |res| res.eax = 0);
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert_eq!(snapshot.cpuid().amd_core_performance_counters(), Some(6));
+    let mut builder = CpuIdBuilder::with_cpuid_reader(&snapshot);
+    builder.set_feature(Feature::PerfCtrCore, false);
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert_eq!(snapshot.cpuid().amd_core_performance_counters(), Some(4));
+}
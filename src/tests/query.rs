@@ -0,0 +1,93 @@
+use crate::{testdata, CacheType, CpuIdResult, LeafData, LeafRequest, TopologyType};
+
+#[test]
+fn query_xeon() {
+    let snapshot = testdata::load("xeon-gold-6252").unwrap();
+    let cpuid = snapshot.cpuid();
+
+    match cpuid.query(LeafRequest::new(0x0)) {
+        LeafData::VendorInfo(vendor) => assert_eq!(vendor.as_str(), "GenuineIntel"),
+        other => panic!("unexpected {:?}", other),
+    }
+    match cpuid.query((0x4, 3).into()) {
+        LeafData::CacheParameter(cache) => {
+            assert_eq!(cache.level(), 3);
+            assert_eq!(cache.cache_type(), CacheType::Unified);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    match cpuid.query((0xb, 1).into()) {
+        LeafData::ExtendedTopologyLevel(level) => {
+            assert_eq!(level.level_type(), TopologyType::Core)
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(matches!(
+        cpuid.query(0x8000_0002.into()),
+        LeafData::ProcessorBrandString(_)
+    ));
+
+    // The terminating subleaf of LEAF=0x04 (not part of the dump, so it reads
+    // as zero) isn't a cache:
+    let terminator = CpuIdResult {
+        eax: 0,
+        ebx: 0,
+        ecx: 0,
+        edx: 0,
+    };
+    assert!(matches!(cpuid.query((0x4, 4).into()), LeafData::Raw(res) if res == terminator));
+    // Subleaf 1 of LEAF=0x07 is part of ExtendedFeatures:
+    assert!(cpuid.query((0x7, 1).into()).is_raw());
+    // AMD leafs are rejected on Intel:
+    assert!(cpuid.query(0x8000_001e.into()).is_raw());
+    assert!(cpuid.query((0x8000_001d, 0).into()).is_raw());
+
+    // Every leaf of the dump can be queried:
+    let decoded = snapshot
+        .leafs()
+        .keys()
+        .filter(|&&(leaf, subleaf)| {
+            !cpuid
+                .query(LeafRequest::with_subleaf(leaf, subleaf))
+                .is_raw()
+        })
+        .count();
+    assert!(decoded > 20);
+}
+
+#[test]
+fn query_ryzen() {
+    let snapshot = testdata::load("ryzen-5-3600x").unwrap();
+    let cpuid = snapshot.cpuid();
+
+    match cpuid.query((0x8000_001d, 0).into()) {
+        LeafData::CacheParameter(cache) => assert_eq!(cache.cache_type(), CacheType::Data),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(matches!(
+        cpuid.query(0x8000_001e.into()),
+        LeafData::ProcessorTopologyInfo(_)
+    ));
+    assert!(matches!(
+        cpuid.query(0x8000_0008.into()),
+        LeafData::ProcessorCapacityAndFeatureInfo(_)
+    ));
+    // LEAF=0x04 is reserved on AMD:
+    assert!(cpuid.query((0x4, 0).into()).is_raw());
+}
+
+#[test]
+fn query_unknown_leaf() {
+    let snapshot = testdata::load("xeon-gold-6252").unwrap();
+    let cpuid = snapshot.cpuid();
+    let data = cpuid.query(LeafRequest::new(0x8000_0080));
+    assert!(matches!(
+        data,
+        LeafData::Raw(CpuIdResult {
+            eax: 0,
+            ebx: 0,
+            ecx: 0,
+            edx: 0
+        })
+    ));
+}
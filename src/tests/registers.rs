@@ -0,0 +1,37 @@
+//! Raw register values and their formatting.
+use super::synthetic::res;
+use crate::VendorInfo;
+
+#[test]
+fn cpuid_result_formatting() {
+    let r = res(0xd, 0x756e_6547, 0x6c65_746e, 0x4965_6e69);
+    assert_eq!(
+        alloc::format!("{:x}", r),
+        "eax=0000000d ebx=756e6547 ecx=6c65746e edx=49656e69"
+    );
+    assert_eq!(
+        alloc::format!("{:#X}", r),
+        "eax=0x0000000D ebx=0x756E6547 ecx=0x6C65746E edx=0x49656E69"
+    );
+    let r = res(0b101, 0, 1 << 31, u32::MAX);
+    assert_eq!(
+        alloc::format!("{:b}", r),
+        "eax=00000000000000000000000000000101 \
+         ebx=00000000000000000000000000000000 \
+         ecx=10000000000000000000000000000000 \
+         edx=11111111111111111111111111111111"
+    );
+    assert!(alloc::format!("{:#b}", r).starts_with("eax=0b00000000000000000000000000000101 "));
+}
+
+#[test]
+fn raw_signatures() {
+    let vendor = VendorInfo::from_registers(res(0xd, 0x756e_6547, 0x6c65_746e, 0x4965_6e69));
+    assert_eq!(vendor.as_bytes(), b"GenuineIntel");
+    assert_eq!(VendorInfo::from_bytes(b"GenuineIntel"), vendor);
+
+    // Not valid UTF-8, the bytes are still available:
+    let vendor = VendorInfo::from_bytes(b"Bad\xffVendor\0\0");
+    assert_eq!(vendor.as_str(), "InvalidVendorString");
+    assert_eq!(vendor.as_bytes(), b"Bad\xffVendor\0\0");
+}
@@ -0,0 +1,121 @@
+use crate::{feature_matrix_csv, CpuIdBuilder, CpuIdResult, CpuIdSnapshot, Feature, Report};
+use alloc::vec::Vec;
+
+fn snapshot() -> CpuIdSnapshot {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"GenuineIntel")
+        .set_brand_string("Intel(R) Xeon(R) Gold 6252 CPU @ 2.10GHz")
+        .set_family_model_stepping(6, 0x55, 7)
+        .set_address_sizes(46, 48)
+        .set_feature(Feature::Sse2, true)
+        .set_feature(Feature::Avx2, true)
+        .set(
+            0x4,
+            0,
+            CpuIdResult {
+                eax: 0x3c07c163,
+                ebx: 0x03c0003f,
+                ecx: 0xbfff,
+                edx: 0x4,
+            },
+        )
+        .set(
+            0x4,
+            1,
+            CpuIdResult {
+                eax: 0,
+                ebx: 0,
+                ecx: 0,
+                edx: 0,
+            },
+        );
+    CpuIdSnapshot::from(builder.build())
+}
+
+#[test]
+fn sections() {
+    let report = Report::new(&snapshot().cpuid());
+
+    let processor = report.section("Processor").unwrap();
+    assert_eq!(processor.get("Vendor"), Some("GenuineIntel"));
+    assert_eq!(
+        processor.get("Model name"),
+        Some("Intel(R) Xeon(R) Gold 6252 CPU @ 2.10GHz")
+    );
+    assert_eq!(processor.get("Model"), Some("0x55"));
+
+    let caches = report.section("Caches").unwrap();
+    assert_eq!(
+        caches.get("L3"),
+        Some("48 MiB, 16-way, 64 B lines, shared by 32 threads")
+    );
+
+    let sizes = report.section("Address sizes").unwrap();
+    assert_eq!(sizes.get("Physical"), Some("46 bits"));
+
+    let features = report.section("Features").unwrap();
+    assert_eq!(features.get("Leaf 0x1.0 EDX"), Some("sse2"));
+    assert_eq!(features.get("Leaf 0x7.0 EBX"), Some("avx2"));
+    assert_eq!(features.get("Leaf 0x1.0 ECX"), None);
+
+    assert!(report.section("Frequency").is_none());
+}
+
+#[test]
+fn display() {
+    let report = Report::new(&snapshot().cpuid());
+    let text = format!("{}", report);
+    assert!(text.starts_with("Processor:\n  Vendor:     GenuineIntel\n"));
+    assert!(text.contains("\nAddress sizes:\n  Physical: 46 bits\n  Linear:   48 bits\n"));
+}
+
+#[test]
+fn markdown() {
+    let report = Report::new(&snapshot().cpuid());
+    let markdown = report.to_markdown();
+    assert!(markdown.starts_with(
+        "## Processor\n\n| Name | Value |\n| --- | --- |\n| Vendor | GenuineIntel |\n"
+    ));
+    assert!(markdown.contains(
+        "\n\n## Address sizes\n\n| Name | Value |\n| --- | --- |\n| Physical | 46 bits |\n"
+    ));
+}
+
+#[test]
+fn html() {
+    let mut builder = CpuIdBuilder::with_cpuid_reader(snapshot());
+    builder.set_brand_string("<Fast & Cheap>");
+    let report = Report::new(&CpuIdSnapshot::from(builder.build()).cpuid());
+    let html = report.to_html();
+    assert!(html.starts_with(
+        "<h2>Processor</h2>\n<table>\n  <tr><th>Vendor</th><td>GenuineIntel</td></tr>\n"
+    ));
+    assert!(html.contains("<tr><th>Model name</th><td>&lt;Fast &amp; Cheap&gt;</td></tr>"));
+    assert!(html.ends_with("</table>\n"));
+}
+
+#[test]
+fn feature_matrix() {
+    let mut builder = CpuIdBuilder::with_cpuid_reader(snapshot());
+    builder
+        .set_vendor(b"Some,\"Things")
+        .set_feature(Feature::Avx2, false);
+    let cores = [(0, snapshot()), (1, CpuIdSnapshot::from(builder.build()))];
+
+    let csv = feature_matrix_csv(&cores);
+    let rows: Vec<Vec<&str>> = csv.lines().map(|l| l.split(',').collect()).collect();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0][..3], ["core", "apic_id", "vendor"]);
+    assert_eq!(rows[0].len(), 9 + Feature::ALL.len());
+    assert_eq!(rows[1].len(), rows[0].len());
+
+    let avx2 = rows[0].iter().position(|&name| name == "avx2").unwrap();
+    assert_eq!(rows[1][avx2], "1");
+    assert_eq!(rows[1][..6], ["0", "0", "GenuineIntel", "6", "85", "7"]);
+    assert!(csv
+        .lines()
+        .nth(2)
+        .unwrap()
+        .starts_with("1,0,\"Some,\"\"Things\",6,"));
+}
@@ -1,4 +1,6 @@
-use crate::{Associativity, CpuId, CpuIdResult, TopologyType};
+use crate::{Associativity, CacheType, CpuId, CpuIdResult, TopologyType};
+use alloc::string::ToString;
+use alloc::vec::Vec;
 use phf::phf_map;
 
 /// Raw dump of ryzen mantisse cpuid values.
@@ -680,6 +682,28 @@ fn cpuid_reader(eax: u32, ecx: u32) -> CpuIdResult {
     CPUID_VALUE_MAP[&key]
 }
 
+/// `cpuid_reader` plus the invalid subleafs that terminate the enumeration
+/// leafs, which aren't part of the dump.
+fn reader_with_terminators(eax: u32, ecx: u32) -> CpuIdResult {
+    match (eax, ecx) {
+        // Invalid level, ECX holds the level number and EDX the x2APIC ID:
+        (0xb, 2) => CpuIdResult {
+            eax: 0x00000000,
+            ebx: 0x00000000,
+            ecx: 0x00000002,
+            edx: 0x00000000,
+        },
+        // Null cache type:
+        (0x8000_001d, 4) => CpuIdResult {
+            eax: 0x00000000,
+            ebx: 0x00000000,
+            ecx: 0x00000000,
+            edx: 0x00000000,
+        },
+        _ => cpuid_reader(eax, ecx),
+    }
+}
+
 /// Check that vendor is AuthenticAMD.
 #[test]
 fn vendor_check() {
@@ -701,6 +725,7 @@ fn version_info() {
     assert_eq!(f.extended_model_id(), 0x7);
     assert_eq!(f.brand_index(), 0x0);
     assert_eq!(f.cflush_cache_line_size(), 0x8);
+    assert_eq!(f.cache_line_flush_size_bytes(), 64);
     assert_eq!(f.max_logical_processor_ids(), 0xc);
 
     assert!(f.has_fpu());
@@ -772,6 +797,44 @@ fn cache_info() {
     assert!(cpuid.get_cache_info().is_none(), "Not supported by AMD");
 }
 
+#[test]
+fn cache_parameters() {
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
+    let caches = cpuid.get_cache_parameters().expect("Leaf is supported");
+    assert_eq!(caches.leaf(), 0x8000_001D);
+
+    let caches: Vec<_> = caches.collect();
+    assert_eq!(caches.len(), 4);
+    assert_eq!(caches[0].cache_type(), CacheType::Data);
+    assert_eq!(caches[0].size(), 32 * 1024);
+    assert_eq!(caches[1].cache_type(), CacheType::Instruction);
+    assert_eq!(caches[2].level(), 2);
+    assert_eq!(caches[2].size(), 512 * 1024);
+    assert!(caches[2].is_inclusive());
+    assert_eq!(caches[3].level(), 3);
+    assert_eq!(caches[3].size(), 16 * 1024 * 1024);
+    assert_eq!(caches[3].max_cores_for_cache(), 6);
+    assert!(caches[3].is_write_back_invalidate());
+}
+
+#[test]
+fn cache_sharing() {
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
+    let caches: Vec<_> = cpuid
+        .get_cache_sharing_info()
+        .expect("Leaf is supported")
+        .collect();
+    assert_eq!(caches.len(), 4);
+    assert_eq!(caches[0].apic_id(), 0);
+    assert_eq!(caches[0].apic_ids(), 0..=1);
+    assert_eq!(caches[2].apic_ids(), 0..=1);
+    // Six threads share a CCX L3, the ID space is rounded up to eight:
+    assert_eq!(caches[3].cache().apic_id_shift(), 3);
+    assert_eq!(caches[3].apic_ids(), 0..=7);
+    assert!(caches[3].is_shared_with(5));
+    assert!(!caches[3].is_shared_with(8));
+}
+
 #[test]
 fn processor_serial() {
     let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
@@ -888,7 +951,7 @@ fn perfmon_info() {
 
 #[test]
 fn extended_topology_info() {
-    let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
     let mut e = cpuid
         .get_extended_topology_info()
         .expect("Leaf is supported");
@@ -908,6 +971,41 @@ fn extended_topology_info() {
     assert_eq!(t.shift_right_for_next_apic_id(), 0x7);
 }
 
+#[test]
+fn x2apic_id() {
+    let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
+    assert_eq!(cpuid.x2apic_id(), Some(0));
+}
+
+#[test]
+fn topology_counts() {
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
+    assert_eq!(cpuid.num_logical_cpus(), Some(12));
+    assert_eq!(cpuid.num_cores(), Some(6));
+    assert_eq!(cpuid.threads_per_core(), Some(2));
+}
+
+#[test]
+fn topology_counts_without_leaf_b() {
+    fn reader_without_leaf_b(eax: u32, ecx: u32) -> CpuIdResult {
+        match eax {
+            0xb => CpuIdResult {
+                eax: 0,
+                ebx: 0,
+                ecx: 0,
+                edx: 0,
+            },
+            _ => cpuid_reader(eax, ecx),
+        }
+    }
+
+    // Falls back to Fn8000_0008 and Fn8000_001E:
+    let cpuid = CpuId::with_cpuid_fn(reader_without_leaf_b);
+    assert_eq!(cpuid.num_logical_cpus(), Some(12));
+    assert_eq!(cpuid.num_cores(), Some(6));
+    assert_eq!(cpuid.threads_per_core(), Some(2));
+}
+
 #[test]
 fn extended_state_info() {
     let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
@@ -1006,6 +1104,8 @@ fn extended_processor_and_feature_identifiers() {
     assert!(e.has_wdt());
     assert!(!e.has_lwp());
     assert!(!e.has_fma4());
+    assert!(e.has_tce());
+    assert!(!e.has_node_id_msr());
     assert!(!e.has_tbm());
     assert!(e.has_topology_extensions());
     assert!(e.has_perf_cntr_extensions());
@@ -1043,21 +1143,15 @@ fn l1_tlb_cache() {
         .get_l1_cache_and_tlb_info()
         .expect("Leaf is supported");
 
-    assert_eq!(
-        e.dtlb_2m_4m_associativity(),
-        Associativity::FullyAssociative
-    );
+    assert_eq!(e.dtlb_2m_4m_associativity(), Associativity::Full);
     assert_eq!(e.dtlb_2m_4m_size(), 64);
 
-    assert_eq!(
-        e.itlb_2m_4m_associativity(),
-        Associativity::FullyAssociative
-    );
+    assert_eq!(e.itlb_2m_4m_associativity(), Associativity::Full);
     assert_eq!(e.itlb_2m_4m_size(), 64);
 
-    assert_eq!(e.dtlb_4k_associativity(), Associativity::FullyAssociative);
+    assert_eq!(e.dtlb_4k_associativity(), Associativity::Full);
     assert_eq!(e.dtlb_4k_size(), 64);
-    assert_eq!(e.itlb_4k_associativity(), Associativity::FullyAssociative);
+    assert_eq!(e.itlb_4k_associativity(), Associativity::Full);
     assert_eq!(e.itlb_4k_size(), 64);
 
     assert_eq!(e.dcache_line_size(), 64);
@@ -1069,6 +1163,12 @@ fn l1_tlb_cache() {
     assert_eq!(e.icache_lines_per_tag(), 1);
     assert_eq!(e.icache_associativity(), Associativity::NWay(8));
     assert_eq!(e.icache_size(), 32);
+
+    assert_eq!(
+        e.dcache_description().to_string(),
+        "L1 Data, 32 KiB, 8-way, 64 B lines"
+    );
+    assert_eq!(e.icache_description().size, 32 * 1024);
 }
 
 #[test]
@@ -1098,7 +1198,18 @@ fn l2_l3_tlb_cache() {
     assert_eq!(e.l3cache_line_size(), 64);
     assert_eq!(e.l3cache_lines_per_tag(), 1);
     assert_eq!(e.l3cache_associativity(), Associativity::Unknown);
+    assert_eq!(e.l2cache_associativity().ways(), Some(8));
+    assert_eq!(e.l3cache_associativity().ways(), None);
     assert_eq!(e.l3cache_size(), 64);
+
+    assert_eq!(
+        e.l2cache_description().to_string(),
+        "L2 Unified, 512 KiB, 8-way, 64 B lines"
+    );
+    assert_eq!(
+        e.l3cache_description().to_string(),
+        "L3 Unified, 32 MiB, unknown associativity, 64 B lines"
+    );
 }
 
 #[test]
@@ -1113,6 +1224,9 @@ fn apm() {
     assert!(e.has_mca_overflow_recovery());
     assert!(e.has_succor());
     assert!(!e.has_hwa());
+    assert!(e.has_scalable_mca());
+    assert!(e.has_pfeh());
+    assert_eq!(e.reserved_bits().ebx, 0);
 
     assert!(e.has_ts());
     assert!(!e.has_freq_id_ctrl());
@@ -1159,6 +1273,8 @@ fn processor_capcity_features() {
     assert_eq!(e.apic_id_size(), 7);
     assert_eq!(e.perf_tsc_size(), 40);
     assert_eq!(e.max_rdpru_id(), 0x1);
+    assert!(e.has_rdpru_mperf() && e.has_rdpru_aperf());
+    assert!(!e.is_rdpru_register_supported(2));
 }
 
 #[test]
@@ -1173,6 +1289,17 @@ fn secure_encryption() {
     assert!(e.has_page_flush_msr());
     assert!(e.has_sev_es());
     assert!(!e.has_sev_snp());
+
+    assert!(cpuid.can_run_nested_paging_guests());
+    assert!(cpuid.supports_avic());
+    assert!(cpuid.supports_sev_guests());
+    // All 509 ASIDs are reserved for SEV guests without ES.
+    assert!(!cpuid.supports_sev_es_guests());
+    assert!(!cpuid.supports_sev_snp_guests());
+    assert_eq!(e.sev_asid_range(), Some(1..=509));
+    assert_eq!(e.sev_es_asid_range(), None);
+    assert!(e.fits_sev_guests(509, 0));
+    assert!(!e.fits_sev_guests(0, 1));
     assert!(!e.has_vmpl());
     assert!(!e.has_hw_enforced_cache_coh());
     assert!(!e.has_64bit_mode());
@@ -1226,3 +1353,100 @@ fn remaining_unsupported_leafs() {
     assert!(cpuid.get_deterministic_address_translation_info().is_none());
     assert!(cpuid.get_soc_vendor_info().is_none());
 }
+
+#[test]
+fn re_encode_extended_leafs() {
+    let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
+
+    let l1 = cpuid
+        .get_l1_cache_and_tlb_info()
+        .expect("Leaf is supported");
+    assert_eq!(l1.as_cpuid_result(), cpuid_reader(0x8000_0005, 0));
+
+    let l2 = cpuid
+        .get_l2_l3_cache_and_tlb_info()
+        .expect("Leaf is supported");
+    assert_eq!(l2.as_cpuid_result(), cpuid_reader(0x8000_0006, 0));
+
+    let apm = cpuid
+        .get_advanced_power_mgmt_info()
+        .expect("Leaf is supported");
+    assert_eq!(apm.as_cpuid_result(), cpuid_reader(0x8000_0007, 0));
+
+    let mut svm = cpuid.get_svm_info().expect("Leaf is supported");
+    assert_eq!(svm.as_cpuid_result(), cpuid_reader(0x8000_000a, 0));
+    svm.set_nested_paging(false);
+    assert!(!svm.has_nested_paging());
+    assert_eq!(
+        svm.as_cpuid_result().edx,
+        cpuid_reader(0x8000_000a, 0).edx & !1
+    );
+
+    let mut mem = cpuid
+        .get_memory_encryption_info()
+        .expect("Leaf is supported");
+    assert_eq!(mem.as_cpuid_result(), cpuid_reader(0x8000_001f, 0));
+    mem.set_sev(false);
+    mem.set_c_bit_position(51);
+    assert!(!mem.has_sev());
+    assert_eq!(mem.c_bit_position(), 51);
+    assert_eq!(mem.num_vmpl(), 0);
+
+    // Zen 3 and later report the SEV-SNP fields.
+    mem.set_sev_snp(true);
+    mem.set_vmpl(true);
+    mem.set_num_vmpl(4);
+    mem.set_secure_tsc(true);
+    mem.set_vmsa_reg_protection(true);
+    assert_eq!(mem.num_vmpl(), 4);
+    assert_eq!(mem.c_bit_position(), 51);
+    assert!(mem.has_secure_tsc() && mem.has_vmsa_reg_protection());
+    assert_eq!(mem.reserved_bits().eax, 0);
+    assert_eq!(
+        mem.as_cpuid_result().eax & !cpuid_reader(0x8000_001f, 0).eax,
+        (1 << 4) | (1 << 5) | (1 << 8) | (1 << 24)
+    );
+}
+
+#[test]
+fn processor_topology_info() {
+    let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
+    let info = cpuid.get_processor_topology_info().unwrap();
+    assert_eq!(info.extended_apic_id(), 0);
+    assert_eq!(info.core_id(), 0);
+    assert_eq!(info.threads_per_core(), 2);
+    assert_eq!(info.node_id(), 0);
+    assert_eq!(info.nodes_per_processor(), 1);
+}
+
+#[test]
+fn tlb_hierarchy() {
+    use crate::{DatType, PageSize};
+
+    let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
+    let g = cpuid.get_tlb_1g_page_info().unwrap();
+    assert_eq!(g.l1_dtlb_1g_size(), 64);
+    assert_eq!(g.l1_itlb_1g_associativity(), Associativity::Full);
+    assert_eq!(g.l2_dtlb_1g_size(), 0);
+    assert_eq!(g.l2_dtlb_1g_associativity(), Associativity::Disabled);
+
+    let tlbs = cpuid.tlb_hierarchy();
+    assert_eq!(tlbs.tlbs.len(), 14);
+    let l2 = tlbs.get(PageSize::Size2M, 2, DatType::DataTLB).unwrap();
+    assert_eq!(l2.entries, 2048);
+    assert_eq!(l2.associativity, Associativity::NWay(4));
+    // 4-MB pages take two 2-MB entries:
+    let l2 = tlbs.get(PageSize::Size4M, 2, DatType::DataTLB).unwrap();
+    assert_eq!(l2.entries, 1024);
+    assert!(tlbs.get(PageSize::Size1G, 2, DatType::DataTLB).is_none());
+    assert_eq!(
+        tlbs.to_string().lines().next(),
+        Some("4K L1 Data TLB: 64 entries, Fully associative")
+    );
+}
+
+#[test]
+fn cache_line_size() {
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
+    assert_eq!(cpuid.cache_line_size(), Some(64));
+}
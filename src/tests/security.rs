@@ -0,0 +1,139 @@
+//! CET, SEV and speculation controls, for configurations none of the CPU dumps
+//! report.
+use alloc::vec::Vec;
+
+use super::synthetic::{amd, intel, res};
+use crate::{CpuId, CpuIdBuilder, CpuIdResult, CpuIdSnapshot, Feature};
+
+#[test]
+fn cet() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x7, 0), res(1, 0, 1 << 7, 1 << 20)),
+        ((0x7, 1), res(0, 0, 0, 1 << 18)),
+        ((0xd, 0), res(0b11, 0, 0, 0)),
+        ((0xd, 1), res(0b1000, 0, (1 << 11) | (1 << 12), 0)),
+        ((0xd, 11), res(16, 0, 0b1, 0)),
+        ((0xd, 12), res(24, 0, 0b1, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let features = cpuid.get_extended_feature_info().unwrap();
+    assert!(features.has_cet_ss());
+    assert!(features.has_cet_ibt());
+    assert!(features.has_cet_sss());
+    assert_eq!(features.reserved_bits(), [res(0, 0, 0, 0); 3]);
+    assert!(cpuid.has_feature(crate::Feature::CetSs));
+    assert!(cpuid.has_feature(crate::Feature::CetIbt));
+
+    let cet = cpuid.cet_support();
+    assert_eq!(
+        cet,
+        crate::CetSupport {
+            shadow_stack: true,
+            indirect_branch_tracking: true,
+            supervisor_shadow_stack: true,
+            xsave_user_state: true,
+            xsave_supervisor_state: true,
+        }
+    );
+    assert!(cet.has_user_shadow_stack());
+    assert!(cet.has_user_ibt());
+
+    let state = cpuid.get_extended_state_info().unwrap();
+    let registers: Vec<_> = state.iter().map(|s| s.register()).collect();
+    assert_eq!(
+        registers,
+        [
+            crate::ExtendedRegisterType::CetU,
+            crate::ExtendedRegisterType::CetS
+        ]
+    );
+
+    // Shadow stacks without XSAVES support for the CET state can't be used.
+    static NO_XSS: &[((u32, u32), CpuIdResult)] = &[((0x7, 0), res(0, 0, 1 << 7, 0))];
+    let cet = CpuId::with_cpuid_fn(intel(NO_XSS)).cet_support();
+    assert!(cet.is_supported());
+    assert!(!cet.has_user_shadow_stack());
+    assert!(!cet.has_user_ibt());
+}
+
+#[test]
+fn sev_guests() {
+    // ASIDs 1..=99 for SEV-ES/SEV-SNP guests, 100..=509 for SEV guests.
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x8000_0000, 0), res(0x8000_001f, 0, 0, 0)),
+        ((0x8000_0001, 0), res(0, 0, 1 << 2, 0)),
+        ((0x8000_000a, 0), res(1, 0x8000, 0, 1 << 13)),
+        ((0x8000_001f, 0), res(0b1_1011, 0x16f, 509, 100)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(amd(LEAFS));
+    assert!(!cpuid.can_run_nested_paging_guests());
+    assert!(cpuid.supports_avic());
+    assert!(cpuid.supports_sev_guests());
+    assert!(cpuid.supports_sev_es_guests());
+    assert!(cpuid.supports_sev_snp_guests());
+
+    let mem = cpuid.get_memory_encryption_info().unwrap();
+    assert_eq!(mem.min_sev_asid(), 100);
+    assert_eq!(mem.max_sev_asid(), 509);
+    assert_eq!(mem.c_bit_position(), 47);
+    assert_eq!(mem.sev_asid_range(), Some(100..=509));
+    assert_eq!(mem.sev_es_asid_range(), Some(1..=99));
+    assert!(mem.fits_sev_guests(410, 99));
+    assert!(!mem.fits_sev_guests(411, 0));
+    assert!(!mem.fits_sev_guests(0, 100));
+
+    // Without SVM, no guests can be launched at all.
+    static NO_SVM: &[((u32, u32), CpuIdResult)] = &[
+        ((0x8000_0000, 0), res(0x8000_001f, 0, 0, 0)),
+        ((0x8000_001f, 0), res(0b1_1011, 0x16f, 509, 100)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(amd(NO_SVM));
+    assert!(!cpuid.supports_avic());
+    assert!(!cpuid.supports_sev_guests());
+    assert!(!cpuid.supports_sev_es_guests());
+}
+
+#[test]
+fn amd_speculation_controls() {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"AuthenticAMD")
+        // CLZERO, IBPB, INT_WBINVD, IBRS, STIBP, STIBP always on, SSBD, PSFD and
+        // BTC_NO:
+        .set(
+            0x8000_0008,
+            0,
+            res(
+                0x3030,
+                (1 << 0) | (0x2f << 12) | (1 << 24) | (0x3 << 28),
+                0,
+                0,
+            ),
+        );
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+
+    let info = cpuid.get_processor_capacity_feature_info().unwrap();
+    assert!(info.has_cl_zero());
+    assert!(info.has_ibpb() && info.has_ibrs() && info.has_stibp());
+    assert!(!info.has_ibrs_always_on() && info.has_stibp_always_on());
+    assert!(info.has_ssbd() && !info.has_virt_ssbd() && !info.has_ssb_no());
+    assert!(info.has_psfd() && info.has_btc_no() && !info.has_ibpb_ret());
+    assert!(info.has_int_wbinvd());
+    assert!(cpuid.has_feature(Feature::AmdIbpb));
+    assert!(!cpuid.has_feature(Feature::AmdIbpbRet));
+
+    let mut copy = cpuid.get_processor_capacity_feature_info().unwrap();
+    copy.set_ssb_no(true);
+    assert!(copy.has_ssb_no() && copy.has_ssbd());
+}
+
+#[test]
+fn tdx_guest() {
+    static LEAFS: &[((u32, u32), CpuIdResult)] =
+        &[((0x21, 0), res(0, 0x6574_6e49, 0x2020_2020, 0x5844_546c))];
+    assert!(CpuId::with_cpuid_fn(intel(LEAFS)).is_tdx_guest());
+
+    static NO_TDX: &[((u32, u32), CpuIdResult)] = &[((0x21, 0), res(0, 0, 0, 0))];
+    assert!(!CpuId::with_cpuid_fn(intel(NO_TDX)).is_tdx_guest());
+}
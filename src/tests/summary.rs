@@ -0,0 +1,37 @@
+use alloc::format;
+
+use crate::{testdata, CpuIdBuilder, CpuIdSnapshot};
+
+#[test]
+fn summary() {
+    let summary = testdata::load("xeon-gold-6252").unwrap().cpuid().summary();
+    assert_eq!(summary.signature, Some((6, 0x55, 7)));
+    assert_eq!((summary.cores, summary.threads), (Some(24), Some(48)));
+    assert_eq!(
+        format!("{}", summary),
+        "Intel(R) Xeon(R) Gold 6252 CPU @ 2.10GHz, family 0x6 model 0x55 stepping 7, \
+         24c/48t, 2100 MHz, cascadelake, x86-64-v4"
+    );
+
+    // No base frequency on AMD:
+    let summary = testdata::load("ryzen-5-3600x").unwrap().cpuid().summary();
+    assert!(summary.base_frequency.is_none());
+    assert_eq!(
+        format!("{}", summary),
+        "AMD Ryzen 5 3600X 6-Core Processor, family 0x17 model 0x71 stepping 0, \
+         6c/12t, znver2, x86-64-v3"
+    );
+}
+
+#[test]
+fn summary_without_brand() {
+    let mut builder = CpuIdBuilder::new();
+    builder.set_vendor(b"HygonGenuine");
+    let summary = CpuIdSnapshot::from(builder.build()).cpuid().summary();
+    assert_eq!(format!("{}", summary), "HygonGenuine");
+
+    let summary = CpuIdSnapshot::from(CpuIdBuilder::new().build())
+        .cpuid()
+        .summary();
+    assert_eq!(format!("{}", summary), "unknown CPU");
+}
@@ -0,0 +1,51 @@
+//! Readers for CPUs made up from a few register values, for leafs that none
+//! of the CPU dumps in this directory report.
+use crate::CpuIdResult;
+
+/// Returns a reader for an Intel CPU with the given `(leaf, subleaf)` values.
+///
+/// The maximum basic leaf is derived from the largest leaf in `leafs`.
+pub(super) fn intel(
+    leafs: &'static [((u32, u32), CpuIdResult)],
+) -> impl Fn(u32, u32) -> CpuIdResult + Clone {
+    move |eax, ecx| {
+        if eax == 0 {
+            let max = leafs
+                .iter()
+                .map(|&((leaf, _), _)| leaf)
+                .filter(|&leaf| leaf < 0x4000_0000)
+                .max()
+                .unwrap_or(0);
+            return CpuIdResult {
+                eax: max,
+                ebx: 0x756e6547,
+                ecx: 0x6c65746e,
+                edx: 0x49656e69,
+            };
+        }
+        leafs.iter().find(|&&(key, _)| key == (eax, ecx)).map_or(
+            CpuIdResult {
+                eax: 0,
+                ebx: 0,
+                ecx: 0,
+                edx: 0,
+            },
+            |&(_, res)| res,
+        )
+    }
+}
+
+/// Like [`intel`], but for an AMD CPU.
+pub(super) fn amd(
+    leafs: &'static [((u32, u32), CpuIdResult)],
+) -> impl Fn(u32, u32) -> CpuIdResult + Clone {
+    let read = intel(leafs);
+    move |eax, ecx| match eax {
+        0 => res(read(0, 0).eax, 0x6874_7541, 0x444d_4163, 0x6974_6e65),
+        _ => read(eax, ecx),
+    }
+}
+
+pub(super) const fn res(eax: u32, ebx: u32, ecx: u32, edx: u32) -> CpuIdResult {
+    CpuIdResult { eax, ebx, ecx, edx }
+}
@@ -0,0 +1,83 @@
+use std::fs;
+use std::path::Path;
+use std::string::ToString;
+
+use crate::{testdata, SysfsMismatch, SysfsReport};
+
+fn write_files(dir: &Path, files: &[(&str, &str)]) {
+    for (file, value) in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, format!("{}\n", value)).unwrap();
+    }
+}
+
+#[test]
+fn check_cpu() {
+    let root = std::env::temp_dir().join(format!("raw-cpuid-sysfs-{}", std::process::id()));
+    // APIC ID 0xc7 of the Xeon Gold 6252 dump is core 3 of package 3.
+    write_files(
+        &root.join("cpu5"),
+        &[
+            ("cache/index0/level", "1"),
+            ("cache/index0/type", "Data"),
+            ("cache/index0/size", "32K"),
+            ("cache/index0/coherency_line_size", "64"),
+            ("cache/index0/ways_of_associativity", "8"),
+            ("cache/index0/number_of_sets", "64"),
+            ("cache/index1/level", "2"),
+            ("cache/index1/type", "Unified"),
+            ("cache/index1/size", "2048K"),
+            ("cache/index2/level", "3"),
+            ("cache/index2/type", "Unified"),
+            ("cache/index2/size", "36608K"),
+            ("cache/index2/ways_of_associativity", "11"),
+            ("cache/index3/level", "4"),
+            ("cache/index3/type", "Unified"),
+            ("topology/physical_package_id", "3"),
+            ("topology/die_id", "0"),
+            ("topology/core_id", "4"),
+        ],
+    );
+
+    let snapshot = testdata::load("xeon-gold-6252").unwrap();
+    let mut report = SysfsReport::default();
+    report.check_cpu(5, &snapshot.cpuid(), &root).unwrap();
+    // Nothing to compare for a CPU without a sysfs directory.
+    report.check_cpu(6, &snapshot.cpuid(), &root).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+
+    let mismatch = |file: &str, sysfs: &str, cpuid: Option<&str>| SysfsMismatch {
+        cpu: 5,
+        file: file.into(),
+        sysfs: sysfs.into(),
+        cpuid: cpuid.map(Into::into),
+    };
+    assert_eq!(
+        report.mismatches,
+        [
+            mismatch("cache/index1/size", "2048K", Some("1024K")),
+            mismatch("cache/index3", "L4 Unified", None),
+            mismatch("topology/core_id", "4", Some("3")),
+        ]
+    );
+    assert!(!report.is_consistent());
+    assert_eq!(
+        report.mismatches[0].to_string(),
+        "cpu5 cache/index1/size: sysfs=2048K, cpuid=1024K"
+    );
+}
+
+#[test]
+fn host() {
+    let mut report = SysfsReport::default();
+    if report
+        .check_cpu(0, &crate::CpuId::new(), Path::new(crate::SYSFS_CPU_ROOT))
+        .is_err()
+    {
+        return;
+    }
+    for mismatch in report.mismatches.iter() {
+        std::println!("{}", mismatch);
+    }
+}
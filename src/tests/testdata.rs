@@ -0,0 +1,16 @@
+use crate::testdata;
+
+#[test]
+fn load() {
+    let brands = [
+        "Intel(R) Xeon(R) Gold 6252 CPU @ 2.10GHz",
+        "AMD Ryzen 5 3600X 6-Core Processor",
+        "Intel(R) Xeon(R) Processor",
+    ];
+    for (&name, brand) in testdata::NAMES.iter().zip(brands) {
+        let snapshot = testdata::load(name).unwrap();
+        let cpuid = snapshot.cpuid();
+        assert_eq!(cpuid.get_processor_brand_string().unwrap().as_str(), brand);
+    }
+    assert!(testdata::load("pentium").is_none());
+}
@@ -0,0 +1,289 @@
+use alloc::vec::Vec;
+
+use super::synthetic::{self, res};
+use crate::{
+    ApicId, ApicIdLayout, CpuId, CpuIdBuilder, CpuIdResult, CpuIdSnapshot, ExtendedCpuTopologyType,
+    Feature, SystemTopology, TopologyType,
+};
+
+fn intel() -> CpuIdBuilder {
+    let mut builder = CpuIdBuilder::new();
+    builder.set_vendor(b"GenuineIntel");
+    builder
+}
+
+#[test]
+fn extended_topology_leaf() {
+    let mut builder = intel();
+    builder
+        // SMT: shift 1, core: shift 6, x2APIC ID 0x65:
+        .set(0xb, 0, res(1, 2, 0x100, 0x65))
+        .set(0xb, 1, res(6, 48, 0x201, 0x65))
+        .set(0xb, 2, res(0, 0, 2, 0x65));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+
+    let layout = cpuid.apic_id_layout().unwrap();
+    assert_eq!(
+        layout,
+        ApicIdLayout {
+            smt_shift: 1,
+            die_shift: 6,
+            package_shift: 6,
+        }
+    );
+
+    let id = cpuid.apic_id().unwrap();
+    assert_eq!(id.id(), 0x65);
+    assert_eq!(id.package_id(), 1);
+    assert_eq!(id.die_id(), 0);
+    assert_eq!(id.core_id(), 0x12);
+    assert_eq!(id.smt_id(), 1);
+}
+
+#[test]
+fn v2_extended_topology_leaf_with_dies() {
+    let mut builder = intel();
+    builder
+        // SMT: shift 1, core: shift 4, die: shift 5, x2APIC ID 0x67 (package 3,
+        // die 0, core 3, SMT 1):
+        .set(0x1f, 0, res(1, 2, 0x100, 0x67))
+        .set(0x1f, 1, res(4, 16, 0x201, 0x67))
+        .set(0x1f, 2, res(5, 32, 0x502, 0x67))
+        .set(0x1f, 3, res(0, 0, 3, 0x67));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+
+    let id = cpuid.apic_id().unwrap();
+    assert_eq!(
+        id.layout(),
+        ApicIdLayout {
+            smt_shift: 1,
+            die_shift: 4,
+            package_shift: 5,
+        }
+    );
+    assert_eq!(id.package_id(), 3);
+    assert_eq!(id.die_id(), 0);
+    assert_eq!(id.core_id(), 3);
+    assert_eq!(id.smt_id(), 1);
+
+    // Package 2, die 1, core 2, SMT 0:
+    let other = ApicId::new(0b101_0100, id.layout());
+    assert_eq!(other.package_id(), 2);
+    assert_eq!(other.die_id(), 1);
+    assert_eq!(other.core_id(), 2);
+    assert_eq!(other.smt_id(), 0);
+}
+
+#[test]
+fn legacy_xapic() {
+    let mut builder = intel();
+    builder
+        .set_feature(Feature::Htt, true)
+        // 16 logical processors per package, initial APIC ID 0x13:
+        .update(0x1, 0, |res| res.ebx = 0x1310_0000)
+        // 8 cores per package:
+        .set(0x4, 0, res((7 << 26) | 0x121, 0, 0, 0))
+        .set(0x4, 1, res(0, 0, 0, 0));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+
+    let id = cpuid.apic_id().unwrap();
+    assert_eq!(
+        id.layout(),
+        ApicIdLayout {
+            smt_shift: 1,
+            die_shift: 4,
+            package_shift: 4,
+        }
+    );
+    assert_eq!(id.package_id(), 1);
+    assert_eq!(id.core_id(), 1);
+    assert_eq!(id.smt_id(), 1);
+
+    let xapic = ApicId::from_xapic(0xff, id.layout());
+    assert_eq!(xapic.package_id(), 0xf);
+    assert_eq!(xapic.core_id(), 7);
+}
+
+#[test]
+fn smt_supported_vs_enabled() {
+    let mut builder = intel();
+    builder
+        .set_feature(Feature::Htt, true)
+        // SMT level with one thread per core (SMT disabled in the firmware):
+        .set(0xb, 0, res(0, 1, 0x100, 0))
+        .set(0xb, 1, res(4, 8, 0x201, 0))
+        .set(0xb, 2, res(0, 0, 2, 0));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert!(snapshot.cpuid().is_smt_supported());
+    assert_eq!(snapshot.cpuid().is_smt_enabled(), Some(false));
+
+    let mut builder = CpuIdBuilder::with_cpuid_reader(&snapshot);
+    builder.set(0xb, 0, res(1, 2, 0x100, 0));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert_eq!(snapshot.cpuid().is_smt_enabled(), Some(true));
+
+    // Legacy leafs only report the maximum number of addressable IDs:
+    let mut builder = intel();
+    builder.set_feature(Feature::Htt, true);
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert!(snapshot.cpuid().is_smt_supported());
+    assert_eq!(snapshot.cpuid().is_smt_enabled(), None);
+}
+
+#[test]
+fn amd_smt_enabled() {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"AuthenticAMD")
+        .set_feature(Feature::TopoExt, true)
+        .set(0x8000_001e, 0, res(0, 0x100, 0, 0));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert_eq!(snapshot.cpuid().is_smt_enabled(), Some(true));
+}
+
+#[test]
+fn system_topology() {
+    let layout = ApicIdLayout {
+        smt_shift: 1,
+        die_shift: 3,
+        package_shift: 4,
+    };
+    // 2 packages, 2 dies per package, 4 cores per die and 2 threads per core,
+    // in reverse order and with one duplicate:
+    let topology = SystemTopology::from_apic_ids(layout, (0..32).rev().chain([5]));
+    assert_eq!(topology.layout(), layout);
+    assert_eq!(topology.num_packages(), 2);
+    assert_eq!(topology.num_dies(), 4);
+    assert_eq!(topology.num_cores(), 16);
+    assert_eq!(topology.num_threads(), 32);
+    assert!(topology.threads().map(|t| t.apic_id).eq(0..32));
+
+    let package = &topology.packages[1];
+    assert_eq!(package.id, 1);
+    assert_eq!(package.dies[1].id, 1);
+    let core = &package.dies[1].cores[2];
+    assert_eq!(core.id, 2);
+    assert_eq!(core.threads.len(), 2);
+    assert_eq!(core.threads[1].id, 1);
+    // Package 1, die 1, core 2, SMT 1:
+    assert_eq!(core.threads[1].apic_id, 0b11101);
+    assert_eq!(core.threads[1].cpu, None);
+}
+
+#[test]
+fn system_topology_from_snapshots() {
+    let snapshot = |x2apic_id: u32| {
+        let mut builder = intel();
+        builder
+            .set(0x1f, 0, res(1, 2, 0x100, x2apic_id))
+            .set(0x1f, 1, res(4, 16, 0x201, x2apic_id))
+            .set(0x1f, 2, res(5, 32, 0x502, x2apic_id))
+            .set(0x1f, 3, res(0, 0, 3, x2apic_id));
+        CpuIdSnapshot::from(builder.build())
+    };
+    let snapshots = [(0, snapshot(0)), (1, snapshot(0x10)), (2, snapshot(1))];
+
+    let topology = SystemTopology::from_snapshots(&snapshots).unwrap();
+    assert_eq!(topology.num_packages(), 1);
+    assert_eq!(topology.num_dies(), 2);
+    assert_eq!(topology.num_cores(), 2);
+    assert_eq!(topology.num_threads(), 3);
+    let cpus: std::vec::Vec<_> = topology.threads().map(|t| t.cpu).collect();
+    assert_eq!(cpus, [Some(0), Some(2), Some(1)]);
+
+    assert!(SystemTopology::from_snapshots(&[(0, CpuIdSnapshot::from(intel().build()))]).is_none());
+}
+
+#[test]
+fn extended_topology_v2_fallback() {
+    // Leaf 0x1F with a die level, and leaf 0xB without:
+    static V2: &[((u32, u32), CpuIdResult)] = &[
+        ((0xB, 0), res(1, 2, 0x100, 7)),
+        ((0xB, 1), res(4, 16, 0x201, 7)),
+        ((0x1F, 0), res(1, 2, 0x100, 7)),
+        ((0x1F, 1), res(4, 16, 0x201, 7)),
+        ((0x1F, 2), res(5, 32, 0x502, 7)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(synthetic::intel(V2));
+    let levels = cpuid.get_extended_topology_info().unwrap();
+    assert_eq!(levels.leaf(), 0x1F);
+    let types: Vec<_> = levels.map(|level| level.level_type()).collect();
+    assert_eq!(
+        types,
+        [TopologyType::SMT, TopologyType::Core, TopologyType::Die]
+    );
+    assert_eq!(cpuid.num_logical_cpus(), Some(32));
+
+    // Leaf 0x1F is in range but not implemented:
+    static V1: &[((u32, u32), CpuIdResult)] = &[
+        ((0xB, 0), res(1, 2, 0x100, 7)),
+        ((0xB, 1), res(4, 16, 0x201, 7)),
+        ((0x1F, 0), res(0, 0, 0, 7)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(synthetic::intel(V1));
+    let levels = cpuid.get_extended_topology_info().unwrap();
+    assert_eq!(levels.leaf(), 0xB);
+    assert_eq!(levels.count(), 2);
+    assert_eq!(cpuid.x2apic_id(), Some(7));
+}
+
+#[test]
+fn x2apic_id_fallbacks() {
+    // AMD without leaf 0xB, but with the extended APIC ID in 0x8000_001E:
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"AuthenticAMD")
+        .set(0x1, 0, res(0, 0x23 << 24, 0, 0))
+        .set(0x8000_001E, 0, res(0x123, 0, 0, 0))
+        .set_feature(Feature::TopoExt, true);
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert_eq!(snapshot.cpuid().x2apic_id(), Some(0x123));
+
+    // Without topology extensions only the 8-bit value of leaf 1 is left:
+    builder.set_feature(Feature::TopoExt, false);
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert_eq!(snapshot.cpuid().x2apic_id(), Some(0x23));
+}
+
+#[test]
+fn amd_extended_cpu_topology() {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"AuthenticAMD")
+        // Core: 2 threads, heterogeneous, efficiency core with ranking 4:
+        .set(0x8000_0026, 0, res((3 << 29) | 1, 0x1504_0002, 0x100, 0x23))
+        // Complex: 8 cores, asymmetric:
+        .set(0x8000_0026, 1, res((1 << 31) | 4, 16, 0x201, 0x23))
+        .set(0x8000_0026, 2, res(7, 32, 0x302, 0x23))
+        .set(0x8000_0026, 3, res(0, 32, 0x403, 0x23))
+        .set(0x8000_0026, 4, res(0, 0, 0x4, 0x23));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+
+    let levels: Vec<_> = cpuid.get_extended_cpu_topology_info().unwrap().collect();
+    assert_eq!(
+        levels.iter().map(|l| l.level_type()).collect::<Vec<_>>(),
+        [
+            ExtendedCpuTopologyType::Core,
+            ExtendedCpuTopologyType::Complex,
+            ExtendedCpuTopologyType::Die,
+            ExtendedCpuTopologyType::Socket
+        ]
+    );
+    let core = &levels[0];
+    assert_eq!(core.processors(), 2);
+    assert_eq!(core.shift_right_for_next_apic_id(), 1);
+    assert!(core.has_efficiency_ranking() && core.has_heterogeneous_cores());
+    assert!(!core.has_asymmetric_topology());
+    assert_eq!(core.power_efficiency_ranking(), 4);
+    assert_eq!(core.native_model_id(), 5);
+    assert_eq!(core.core_type(), 1);
+    assert_eq!(core.extended_apic_id(), 0x23);
+    assert!(levels[1].has_asymmetric_topology());
+    assert_eq!(levels[3].level_number(), 3);
+    assert!(cpuid.supports_leaf(0x8000_0026, 3));
+    assert!(!cpuid.supports_leaf(0x8000_0026, 4));
+}
@@ -0,0 +1,202 @@
+//! Vendor specific leafs and brand strings of CPUs without a dump.
+use alloc::vec::Vec;
+
+use super::synthetic::{intel, res};
+use crate::{
+    CpuId, CpuIdBuilder, CpuIdError, CpuIdResult, CpuIdSnapshot, Feature, ProcessorBrandString,
+};
+
+#[test]
+fn hygon_is_amd_compatible() {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"HygonGenuine")
+        // Family 0x18, model 0x0, stepping 1 (Dhyana):
+        .update(0x1, 0, |res| res.eax = 0x0090_0f01)
+        .set_feature(Feature::Svm, true)
+        .set(0x2, 0, res(0x1, 0, 0, 0))
+        .set(
+            0x8000_0005,
+            0,
+            res(0xff40_ff18, 0xff40_ff30, 0x2004_0140, 0x2004_0140),
+        );
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+
+    let finfo = cpuid.get_feature_info().unwrap();
+    assert_eq!(finfo.family_id(), 0x18);
+    assert_eq!(finfo.model_id(), 0x0);
+    assert!(cpuid
+        .get_extended_processor_and_feature_identifiers()
+        .unwrap()
+        .has_svm());
+    assert_eq!(cpuid.get_l1_cache_and_tlb_info().unwrap().dcache_size(), 32);
+    // Reserved like on AMD:
+    assert!(cpuid.get_cache_info().is_none());
+}
+
+#[test]
+fn padlock() {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"  Shanghai  ")
+        // RNG and ACE present and enabled, PHE present but disabled:
+        .set(0xc000_0001, 0, res(0, 0, 0, 0x4cc));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+
+    assert_eq!(cpuid.max_centaur_leaf(), Some(0xc000_0001));
+    let padlock = cpuid.get_padlock_info().unwrap();
+    assert!(padlock.has_rng() && padlock.is_rng_enabled());
+    assert!(padlock.has_ace() && padlock.is_ace_enabled());
+    assert!(padlock.has_phe() && !padlock.is_phe_enabled());
+    assert!(!padlock.has_ace2() && !padlock.has_pmm());
+    assert_eq!(padlock.as_cpuid_result().edx, 0x4cc);
+    assert!(cpuid.has_feature(Feature::PadLockAceEnabled));
+    assert!(!cpuid.has_feature(Feature::PadLockPheEnabled));
+
+    // Other vendors don't implement the Centaur leafs:
+    let mut builder = CpuIdBuilder::with_cpuid_reader(&snapshot);
+    builder.set_vendor(b"GenuineIntel");
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert_eq!(snapshot.cpuid().max_centaur_leaf(), None);
+    assert_eq!(
+        snapshot.cpuid().try_get_padlock_info().unwrap_err(),
+        CpuIdError::LeafUnsupported { leaf: 0xc000_0001 }
+    );
+}
+
+#[test]
+fn transmeta() {
+    let mut builder = CpuIdBuilder::new();
+    builder
+        .set_vendor(b"GenuineTMx86")
+        // Efficeon, revision 2.0.0.0 (see leaf 0x8086_0002), 1 GHz, LongRun:
+        .set(0x8086_0001, 0, res(0xf2a, 0x0200_0000, 1000, 0b1010))
+        // CMS 6.0.6-3-15:
+        .set(0x8086_0002, 0, res(0x0302_0100, 0x0600_0603, 15, 0))
+        .set(
+            0x8086_0003,
+            0,
+            res(0x3230_3032, 0x3032_3630, 0x3a34_3020, 0x202c_3331),
+        )
+        .set(0x8086_0004, 0, res(0x3236_3031, 0x7a48_4d20, 0, 0))
+        .set(0x8086_0005, 0, res(0, 0, 0, 0))
+        .set(0x8086_0006, 0, res(0, 0, 0, 0));
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    let cpuid = snapshot.cpuid();
+
+    assert_eq!(cpuid.max_transmeta_leaf(), Some(0x8086_0006));
+    let info = cpuid.get_transmeta_processor_info().unwrap();
+    assert_eq!(info.signature(), 0xf2a);
+    assert_eq!(info.hardware_revision(), 0x0200_0000);
+    assert_eq!(info.frequency_mhz(), 1000);
+    assert!(info.has_longrun() && info.has_lrti() && !info.has_recovery());
+    assert!(cpuid.has_feature(Feature::LongRun));
+
+    let cms = cpuid.get_transmeta_cms_version().unwrap();
+    assert_eq!(cms.processor_revision(), 0x0302_0100);
+    assert_eq!(cms.cms_version(), (6, 0, 6, 3));
+    assert_eq!(alloc::format!("{}", cms), "6.0.6-3-15");
+
+    let info = cpuid.get_transmeta_info_string().unwrap();
+    assert_eq!(info.as_str(), "20020620 04:13, 1062 MHz");
+
+    // The Transmeta leafs are not part of the extended range:
+    assert_eq!(cpuid.max_extended_leaf(), None);
+    let mut builder = CpuIdBuilder::with_cpuid_reader(&snapshot);
+    builder.set_vendor(b"AuthenticAMD");
+    let snapshot = CpuIdSnapshot::from(builder.build());
+    assert!(snapshot.cpuid().get_transmeta_processor_info().is_none());
+}
+
+#[test]
+fn brand_index() {
+    // Pentium III Xeon (0x686), which becomes a Celeron with signature 0x6b1:
+    static XEON: &[((u32, u32), CpuIdResult)] = &[((0x1, 0), res(0x686, 0x03, 0, 0))];
+    let cpuid = CpuId::with_cpuid_fn(intel(XEON));
+    let finfo = cpuid.get_feature_info().unwrap();
+    assert_eq!(
+        finfo.brand_index_string(),
+        Some("Intel(R) Pentium(R) III Xeon(R) processor")
+    );
+    static CELERON: &[((u32, u32), CpuIdResult)] = &[((0x1, 0), res(0x6b1, 0x03, 0, 0))];
+    let cpuid = CpuId::with_cpuid_fn(intel(CELERON));
+    let finfo = cpuid.get_feature_info().unwrap();
+    assert_eq!(
+        finfo.brand_index_string(),
+        Some("Intel(R) Celeron(R) processor")
+    );
+
+    // Not supported and reserved:
+    static NONE: &[((u32, u32), CpuIdResult)] = &[((0x1, 0), res(0x906ea, 0, 0, 0))];
+    let cpuid = CpuId::with_cpuid_fn(intel(NONE));
+    assert_eq!(cpuid.get_feature_info().unwrap().brand_index_string(), None);
+    static RESERVED: &[((u32, u32), CpuIdResult)] = &[((0x1, 0), res(0x695, 0x18, 0, 0))];
+    let cpuid = CpuId::with_cpuid_fn(intel(RESERVED));
+    assert_eq!(cpuid.get_feature_info().unwrap().brand_index_string(), None);
+}
+
+#[test]
+fn brand_string_padding() {
+    // "  Intel(R) Pentium(R) 4 CPU 1.50GHz" right-justified with NUL bytes
+    // and spaces in front:
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x8000_0000, 0), res(0x8000_0004, 0, 0, 0)),
+        (
+            (0x8000_0002, 0),
+            res(0, 0x2020_0000, 0x6574_6e49, 0x2952_286c),
+        ),
+        (
+            (0x8000_0003, 0),
+            res(0x6e65_5020, 0x6d75_6974, 0x2029_5228, 0x5043_2034),
+        ),
+        ((0x8000_0004, 0), res(0x2e31_2055, 0x4847_3035, 0x7a, 0)),
+    ];
+    let cpuid = CpuId::with_cpuid_fn(intel(LEAFS));
+    let brand = cpuid.get_processor_brand_string().expect("Brand string");
+    assert_eq!(brand.as_str(), "Intel(R) Pentium(R) 4 CPU 1.50GHz");
+    assert_eq!(alloc::format!("{}", brand), brand.as_str());
+    assert_eq!(brand.brand_bytes(), b"Intel(R) Pentium(R) 4 CPU 1.50GHz");
+    assert_eq!(&brand.as_bytes()[..6], b"\0\0\0\0\0\0");
+}
+
+#[test]
+fn brand_string_invalid_utf8() {
+    let mut bytes = [0u8; 48];
+    bytes[..14].copy_from_slice(b" Sample\xff\xfeCPU  ");
+    let reg = |i: usize| u32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+    let leaf = |i: usize| res(reg(i), reg(i + 4), reg(i + 8), reg(i + 12));
+    let brand = ProcessorBrandString::from_registers([leaf(0), leaf(16), leaf(32)]);
+
+    assert_eq!(brand.as_bytes(), &bytes);
+    assert_eq!(brand.brand_bytes(), b"Sample\xff\xfeCPU");
+    assert_eq!(brand.as_str(), "Invalid Processor Brand String");
+    assert_eq!(brand.to_string_lossy(), "Sample\u{fffd}\u{fffd}CPU");
+    assert_eq!(alloc::format!("{}", brand), brand.to_string_lossy());
+}
+
+#[test]
+fn soc_vendor_attributes() {
+    // Subleafs 1-3 hold the brand string, the attributes follow up to the
+    // maximum subleaf in EAX.
+    static LEAFS: &[((u32, u32), CpuIdResult)] = &[
+        ((0x17, 0), res(5, 0, 0, 0)),
+        ((0x17, 4), res(4, 4, 4, 4)),
+        ((0x17, 5), res(5, 5, 5, 5)),
+        ((0x17, 6), res(6, 6, 6, 6)),
+    ];
+    let soc = CpuId::with_cpuid_fn(intel(LEAFS))
+        .get_soc_vendor_info()
+        .unwrap();
+    let attributes = soc.get_vendor_attributes().unwrap();
+    assert_eq!(attributes.len(), 2);
+    assert_eq!(
+        attributes.clone().collect::<Vec<_>>(),
+        [res(4, 4, 4, 4), res(5, 5, 5, 5)]
+    );
+    assert_eq!(
+        attributes.rev().collect::<Vec<_>>(),
+        [res(5, 5, 5, 5), res(4, 4, 4, 4)]
+    );
+}
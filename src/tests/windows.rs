@@ -0,0 +1,127 @@
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+use crate::{
+    testdata, ApicId, ApicIdLayout, Associativity, CacheType, GroupAffinity, LogicalProcessor,
+    WindowsMismatch, WindowsTopology,
+};
+
+/// Appends a `SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX` entry with the given
+/// payload and group masks at `masks_offset`.
+fn push_entry(buffer: &mut Vec<u8>, relationship: u32, payload: &[u8], masks: &[GroupAffinity]) {
+    let mut entry = Vec::new();
+    entry.extend_from_slice(&relationship.to_le_bytes());
+    entry.extend_from_slice(&0u32.to_le_bytes());
+    entry.extend_from_slice(payload);
+    for mask in masks {
+        entry.extend_from_slice(&mask.mask.to_le_bytes()[..size_of::<usize>()]);
+        entry.extend_from_slice(&mask.group.to_le_bytes());
+        entry.extend_from_slice(&[0; 6]);
+    }
+    let size = entry.len() as u32;
+    entry[4..8].copy_from_slice(&size.to_le_bytes());
+    buffer.extend_from_slice(&entry);
+}
+
+fn processor(flags: u8, masks: &[GroupAffinity]) -> [u8; 24] {
+    let mut payload = [0; 24];
+    payload[0] = flags;
+    payload[22..24].copy_from_slice(&(masks.len() as u16).to_le_bytes());
+    payload
+}
+
+fn cache(level: u8, ways: u8, size: u32, typ: u32, masks: &[GroupAffinity]) -> [u8; 32] {
+    let mut payload = [0; 32];
+    payload[0] = level;
+    payload[1] = ways;
+    payload[2..4].copy_from_slice(&64u16.to_le_bytes());
+    payload[4..8].copy_from_slice(&size.to_le_bytes());
+    payload[8..12].copy_from_slice(&typ.to_le_bytes());
+    payload[30..32].copy_from_slice(&(masks.len() as u16).to_le_bytes());
+    payload
+}
+
+fn group0(mask: u64) -> GroupAffinity {
+    GroupAffinity { group: 0, mask }
+}
+
+#[test]
+fn parse_and_compare() {
+    let core0 = [group0(0b0011)];
+    let core1 = [group0(0b1100)];
+    let package = [group0(0b1111)];
+    let mut buffer = Vec::new();
+    push_entry(&mut buffer, 0, &processor(1, &core0), &core0);
+    push_entry(&mut buffer, 0, &processor(1, &core1), &core1);
+    push_entry(&mut buffer, 3, &processor(0, &package), &package);
+    push_entry(&mut buffer, 2, &cache(1, 8, 32 * 1024, 2, &core0), &core0);
+    push_entry(
+        &mut buffer,
+        2,
+        &cache(2, 16, 2048 * 1024, 0, &core0),
+        &core0,
+    );
+    push_entry(
+        &mut buffer,
+        2,
+        &cache(3, 11, 36608 * 1024, 0, &package),
+        &package,
+    );
+    push_entry(
+        &mut buffer,
+        2,
+        &cache(4, 16, 1 << 30, 0, &package),
+        &package,
+    );
+    // NUMA node, skipped:
+    push_entry(&mut buffer, 1, &[0; 32], &[]);
+
+    let mut topology = WindowsTopology::parse(&buffer);
+    assert_eq!(topology.cores.len(), 2);
+    assert!(topology.cores[0].smt);
+    assert_eq!(topology.cores[1].affinity, core1);
+    assert_eq!(topology.packages, [package]);
+    assert_eq!(topology.caches.len(), 4);
+    assert_eq!(topology.caches[0].cache_type, CacheType::Data);
+    assert_eq!(topology.caches[0].associativity, Associativity::NWay(8));
+    assert_eq!(topology.caches[2].size, 36608 * 1024);
+    assert_eq!(core1[0].numbers().collect::<Vec<_>>(), [2, 3]);
+
+    // Processor 3 claims to be on another core than processor 2.
+    let layout = ApicIdLayout {
+        smt_shift: 1,
+        die_shift: 6,
+        package_shift: 6,
+    };
+    topology.processors = [0, 1, 2, 8]
+        .iter()
+        .enumerate()
+        .map(|(number, &id)| LogicalProcessor {
+            group: 0,
+            number: number as u8,
+            apic_id: ApicId::new(id, layout),
+        })
+        .collect();
+    assert_eq!(topology.processor(0, 3).unwrap().apic_id.core_id(), 4);
+
+    let snapshot = testdata::load("xeon-gold-6252").unwrap();
+    assert_eq!(
+        topology.mismatches(&snapshot.cpuid()),
+        [
+            WindowsMismatch::SplitCore(core1.to_vec()),
+            WindowsMismatch::CacheProperties {
+                level: 2,
+                cache_type: CacheType::Unified
+            },
+            WindowsMismatch::MissingCache {
+                level: 4,
+                cache_type: CacheType::Unified
+            },
+        ]
+    );
+
+    // A truncated buffer stops parsing.
+    let truncated = WindowsTopology::parse(&buffer[..buffer.len() - 1]);
+    assert_eq!(truncated.caches.len(), 4);
+    assert!(WindowsTopology::parse(&[]).cores.is_empty());
+}
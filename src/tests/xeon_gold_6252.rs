@@ -1,4 +1,9 @@
-use crate::{CpuId, CpuIdResult};
+use super::synthetic::res;
+use crate::{
+    testdata, CacheParameter, CpuId, CpuIdBuilder, CpuIdResult, CpuIdSnapshot, ExtendedFeatures,
+    ExtendedProcessorFeatureIdentifiers, ExtendedTopologyLevel, FeatureInfo,
+    ProcessorCapacityAndFeatureInfo, ThermalPowerInfo, TopologyType, VendorInfo,
+};
 use phf::phf_map;
 
 /// Raw dump of a cascade lake cpuid values.
@@ -596,6 +601,28 @@ fn cpuid_reader(eax: u32, ecx: u32) -> CpuIdResult {
     CPUID_VALUE_MAP[&key]
 }
 
+/// `cpuid_reader` plus the invalid subleafs that terminate the enumeration
+/// leafs, which aren't part of the dump.
+fn reader_with_terminators(eax: u32, ecx: u32) -> CpuIdResult {
+    match (eax, ecx) {
+        // Null cache type:
+        (0x4, 4) => CpuIdResult {
+            eax: 0x00000000,
+            ebx: 0x00000000,
+            ecx: 0x00000000,
+            edx: 0x00000000,
+        },
+        // Invalid level, ECX holds the level number and EDX the x2APIC ID:
+        (0xb, 2) => CpuIdResult {
+            eax: 0x00000000,
+            ebx: 0x00000000,
+            ecx: 0x00000002,
+            edx: 0x000000c7,
+        },
+        _ => cpuid_reader(eax, ecx),
+    }
+}
+
 /// Check that vendor is AuthenticAMD.
 #[test]
 fn vendor_check() {
@@ -621,6 +648,7 @@ fn version_info() {
     assert_eq!(f.max_logical_processor_ids(), 64);
     assert_eq!(f.initial_local_apic_id(), 199); // different from recorded output
     assert_eq!(f.cflush_cache_line_size(), 0x8);
+    assert_eq!(f.cache_line_flush_size_bytes(), 64);
     assert_eq!(f.brand_index(), 0x0);
 
     assert!(f.has_fpu());
@@ -690,6 +718,7 @@ fn version_info() {
 fn cache_info() {
     let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
     let ci = cpuid.get_cache_info().expect("Leaf is supported");
+    assert_eq!(ci.clone().count(), 7);
 
     for (idx, cache) in ci.enumerate() {
         match idx {
@@ -698,13 +727,71 @@ fn cache_info() {
             2 => assert_eq!(cache.num, 0xb5),
             3 => assert_eq!(cache.num, 0x03),
             4 => assert_eq!(cache.num, 0xf0),
-            5 => assert_eq!(cache.num, 0x76),
-            6 => assert_eq!(cache.num, 0xc3),
+            5 => assert_eq!(cache.num, 0xc3),
+            6 => assert_eq!(cache.num, 0x76),
             _ => unreachable!(),
         }
     }
 }
 
+#[test]
+fn cache_sharing() {
+    use crate::CacheType;
+
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
+    let mut caches = cpuid.get_cache_sharing_info().expect("Leaf is supported");
+
+    let l1d = caches.next().expect("Have L1d");
+    assert_eq!(l1d.level(), 1);
+    assert_eq!(l1d.cache_type(), CacheType::Data);
+    assert_eq!(l1d.apic_id(), 199);
+    assert_eq!(l1d.cache().apic_id_shift(), 1);
+    assert_eq!(l1d.cache_id(), 99);
+    assert_eq!(l1d.apic_ids(), 198..=199);
+    assert!(l1d.is_shared_with(198));
+    assert!(!l1d.is_shared_with(200));
+
+    let l1i = caches.next().expect("Have L1i");
+    assert_eq!(l1i.cache_type(), CacheType::Instruction);
+    assert_eq!(l1i.apic_ids(), 198..=199);
+
+    let l2 = caches.next().expect("Have L2");
+    assert_eq!(l2.level(), 2);
+    assert_eq!(l2.apic_ids(), 198..=199);
+
+    let l3 = caches.next().expect("Have L3");
+    assert_eq!(l3.level(), 3);
+    assert_eq!(l3.cache_type(), CacheType::Unified);
+    assert_eq!(l3.cache().apic_id_shift(), 6);
+    assert_eq!(l3.cache_id(), 3);
+    assert_eq!(l3.apic_ids(), 192..=255);
+    assert!(l3.is_shared_with(220));
+    assert!(!l3.is_shared_with(150));
+
+    assert!(caches.next().is_none());
+}
+
+#[test]
+fn cache_descriptions() {
+    use alloc::string::ToString;
+
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
+    let descriptions: alloc::vec::Vec<_> = cpuid
+        .get_cache_parameters()
+        .expect("Leaf is supported")
+        .map(|cache| cache.description().to_string())
+        .collect();
+    assert_eq!(
+        descriptions,
+        [
+            "L1 Data, 32 KiB, 8-way, 64 B lines, shared by 2 threads",
+            "L1 Instruction, 32 KiB, 8-way, 64 B lines, shared by 2 threads",
+            "L2 Unified, 1 MiB, 16-way, 64 B lines, shared by 2 threads",
+            "L3 Unified, 36608 KiB, 11-way, 64 B lines, shared by 64 threads",
+        ]
+    );
+}
+
 #[test]
 fn processor_serial() {
     let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
@@ -805,7 +892,7 @@ fn extended_features() {
     assert!(!e.has_umip());
     assert!(e.has_pku());
     assert!(e.has_ospke());
-    assert!(!e.has_avx512vnni());
+    assert!(e.has_avx512vnni());
     assert!(!e.has_rdpid());
     assert!(!e.has_sgx_lc());
     assert_eq!(e.mawau_value(), 0x0);
@@ -848,7 +935,7 @@ fn perfmon_info() {
 fn extended_topology_info() {
     use crate::TopologyType;
 
-    let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
     let mut e = cpuid
         .get_extended_topology_info()
         .expect("Leaf is supported");
@@ -868,6 +955,70 @@ fn extended_topology_info() {
     assert_eq!(t.x2apic_id(), 199); // different from doc, unpinned execution
 }
 
+#[test]
+fn extended_topology_info_other_level_types() {
+    use crate::TopologyType;
+
+    fn reader_with_more_levels(eax: u32, ecx: u32) -> CpuIdResult {
+        match (eax, ecx) {
+            // Die level:
+            (0xb, 2) => CpuIdResult {
+                eax: 0x00000007,
+                ebx: 0x00000060,
+                ecx: 0x00000502,
+                edx: 0x000000c7,
+            },
+            // A level type we don't know about yet:
+            (0xb, 3) => CpuIdResult {
+                eax: 0x00000008,
+                ebx: 0x000000c0,
+                ecx: 0x00000903,
+                edx: 0x000000c7,
+            },
+            (0xb, 4) => CpuIdResult {
+                eax: 0x00000000,
+                ebx: 0x00000000,
+                ecx: 0x00000004,
+                edx: 0x000000c7,
+            },
+            _ => cpuid_reader(eax, ecx),
+        }
+    }
+
+    let cpuid = CpuId::with_cpuid_fn(reader_with_more_levels);
+    let levels: std::vec::Vec<TopologyType> = cpuid
+        .get_extended_topology_info()
+        .expect("Leaf is supported")
+        .map(|level| level.level_type())
+        .collect();
+
+    assert_eq!(
+        levels,
+        [
+            TopologyType::SMT,
+            TopologyType::Core,
+            TopologyType::Die,
+            TopologyType::Unknown(9)
+        ]
+    );
+    assert_eq!(format!("{}", TopologyType::Unknown(9)), "Unknown(9)");
+    assert_eq!(cpuid.num_logical_cpus(), Some(192));
+}
+
+#[test]
+fn x2apic_id() {
+    let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
+    assert_eq!(cpuid.x2apic_id(), Some(199));
+}
+
+#[test]
+fn topology_counts() {
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
+    assert_eq!(cpuid.num_logical_cpus(), Some(48));
+    assert_eq!(cpuid.num_cores(), Some(24));
+    assert_eq!(cpuid.threads_per_core(), Some(2));
+}
+
 #[test]
 fn extended_state_info() {
     let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
@@ -1065,6 +1216,8 @@ fn extended_processor_and_feature_identifiers() {
     assert!(!e.has_wdt());
     assert!(!e.has_lwp());
     assert!(!e.has_fma4());
+    assert!(!e.has_tce());
+    assert!(!e.has_node_id_msr());
     assert!(!e.has_tbm());
     assert!(!e.has_topology_extensions());
     assert!(!e.has_perf_cntr_extensions());
@@ -1143,6 +1296,8 @@ fn apm() {
     assert!(!e.has_mca_overflow_recovery());
     assert!(!e.has_succor());
     assert!(!e.has_hwa());
+    assert!(!e.has_scalable_mca());
+    assert!(!e.has_pfeh());
     // ...
     assert_eq!(e.cpu_pwr_sample_time_ratio(), 0x0);
 
@@ -1170,6 +1325,8 @@ fn processor_capcity_features() {
     assert_eq!(e.physical_address_bits(), 46);
     assert_eq!(e.linear_address_bits(), 48);
     assert_eq!(e.guest_physical_address_bits(), 0);
+    assert!(!cpuid.supports_5_level_paging());
+    assert_eq!(cpuid.max_linear_address_bits(), 48);
 
     assert!(!e.has_cl_zero());
     assert!(!e.has_inst_ret_cntr_msr());
@@ -1189,6 +1346,7 @@ fn processor_capcity_features() {
     assert_eq!(e.apic_id_size(), 0);
     assert_eq!(e.perf_tsc_size(), 40); // Not sure why this is set, it's reserved :(
     assert_eq!(e.max_rdpru_id(), 0);
+    assert!(!e.has_rdpru_mperf());
 }
 
 #[test]
@@ -1198,3 +1356,434 @@ fn remaining_unsupported_leafs() {
     assert!(cpuid.get_deterministic_address_translation_info().is_none());
     assert!(cpuid.get_soc_vendor_info().is_none());
 }
+
+#[test]
+fn re_encode_leafs() {
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
+
+    let f = cpuid.get_feature_info().expect("Leaf is supported");
+    assert_eq!(f.as_cpuid_result(), reader_with_terminators(0x1, 0));
+
+    for (subleaf, cache) in cpuid.get_cache_parameters().unwrap().enumerate() {
+        assert_eq!(
+            cache.as_cpuid_result(),
+            reader_with_terminators(0x4, subleaf as u32)
+        );
+    }
+
+    let m = cpuid.get_monitor_mwait_info().expect("Leaf is supported");
+    assert_eq!(m.as_cpuid_result(), reader_with_terminators(0x5, 0));
+
+    let t = cpuid.get_thermal_power_info().expect("Leaf is supported");
+    assert_eq!(t.as_cpuid_result(), reader_with_terminators(0x6, 0));
+
+    let e = cpuid
+        .get_extended_feature_info()
+        .expect("Leaf is supported");
+    assert_eq!(e.as_cpuid_result(), reader_with_terminators(0x7, 0));
+
+    let p = cpuid
+        .get_performance_monitoring_info()
+        .expect("Leaf is supported");
+    assert_eq!(p.as_cpuid_result(), reader_with_terminators(0xa, 0));
+
+    for (subleaf, level) in cpuid.get_extended_topology_info().unwrap().enumerate() {
+        assert_eq!(
+            level.as_cpuid_result(),
+            reader_with_terminators(0xb, subleaf as u32)
+        );
+    }
+
+    let s = cpuid.get_extended_state_info().expect("Leaf is supported");
+    assert_eq!(
+        s.as_cpuid_results(),
+        [
+            reader_with_terminators(0xd, 0),
+            reader_with_terminators(0xd, 1)
+        ]
+    );
+
+    let e = cpuid
+        .get_extended_processor_and_feature_identifiers()
+        .expect("Leaf is supported");
+    assert_eq!(e.as_cpuid_result(), reader_with_terminators(0x8000_0001, 0));
+
+    let b = cpuid
+        .get_processor_brand_string()
+        .expect("Leaf is supported");
+    assert_eq!(
+        b.as_cpuid_results(),
+        [
+            reader_with_terminators(0x8000_0002, 0),
+            reader_with_terminators(0x8000_0003, 0),
+            reader_with_terminators(0x8000_0004, 0)
+        ]
+    );
+
+    let c = cpuid
+        .get_processor_capacity_feature_info()
+        .expect("Leaf is supported");
+    assert_eq!(c.as_cpuid_result(), reader_with_terminators(0x8000_0008, 0));
+}
+
+#[test]
+fn setters() {
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
+
+    let mut f = cpuid.get_feature_info().expect("Leaf is supported");
+    f.set_family_id(0x19);
+    f.set_model_id(0x21);
+    f.set_stepping_id(0x2);
+    f.set_vmx(false);
+    f.set_hypervisor(true);
+    f.set_initial_local_apic_id(3);
+    assert_eq!(f.family_id(), 0x19);
+    assert_eq!(f.model_id(), 0x21);
+    assert_eq!(f.stepping_id(), 0x2);
+    assert!(!f.has_vmx());
+    assert!(f.has_hypervisor());
+    assert_eq!(f.initial_local_apic_id(), 3);
+
+    let raw = f.as_cpuid_result();
+    let orig = reader_with_terminators(0x1, 0);
+    assert_eq!(raw.eax, 0x00a20f12);
+    assert_eq!(raw.ebx, (orig.ebx & 0x00ff_ffff) | 3 << 24);
+    assert_eq!(raw.ecx, (orig.ecx & !(1 << 5)) | 1 << 31);
+    assert_eq!(raw.edx, orig.edx);
+
+    let mut e = cpuid
+        .get_extended_feature_info()
+        .expect("Leaf is supported");
+    e.set_avx512f(false);
+    e.set_mawau_value(0x1f);
+    assert!(!e.has_avx512f());
+    assert_eq!(e.mawau_value(), 0x1f);
+    assert_eq!(
+        e.as_cpuid_result().ebx,
+        reader_with_terminators(0x7, 0).ebx & !(1 << 16)
+    );
+
+    let mut l1 = cpuid.get_cache_parameters().unwrap().next().unwrap();
+    l1.set_sets(128);
+    l1.set_ways(16);
+    l1.set_cache_type(crate::CacheType::Unified);
+    assert_eq!(l1.sets(), 128);
+    assert_eq!(l1.ways(), 16);
+    assert_eq!(l1.cache_type(), crate::CacheType::Unified);
+    assert_eq!(l1.as_cpuid_result().ecx, 127);
+
+    let mut c = cpuid
+        .get_processor_capacity_feature_info()
+        .expect("Leaf is supported");
+    c.set_physical_address_bits(52);
+    c.set_wbnoinvd(true);
+    assert_eq!(c.physical_address_bits(), 52);
+    assert!(c.has_wbnoinvd());
+    assert_eq!(c.as_cpuid_result().eax, 0x3034);
+}
+
+#[test]
+fn snapshot() {
+    // The dump doesn't contain every subleaf, missing ones read as zero:
+    let sparse_reader = |eax: u32, ecx: u32| {
+        let key = (eax as u64) << u32::BITS | ecx as u64;
+        CPUID_VALUE_MAP.get(&key).copied().unwrap_or(CpuIdResult {
+            eax: 0,
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
+        })
+    };
+    let snapshot = crate::CpuIdSnapshot::with_cpuid_reader(sparse_reader);
+    assert!(snapshot.diff(&snapshot).is_empty());
+    for (&(leaf, subleaf), res) in snapshot.leafs() {
+        assert_eq!(*res, sparse_reader(leaf, subleaf));
+    }
+    assert_eq!(snapshot.get(0x4, 3), Some(cpuid_reader(0x4, 3)));
+    assert_eq!(snapshot.get(0xb, 2), Some(sparse_reader(0xb, 2)));
+    assert_eq!(snapshot.get(0xd, 9), Some(cpuid_reader(0xd, 9)));
+    assert_eq!(
+        snapshot.get(0x8000_0008, 0),
+        Some(cpuid_reader(0x8000_0008, 0))
+    );
+    assert_eq!(snapshot.get(0x8000_0009, 0), None);
+
+    let cpuid = snapshot.cpuid();
+    let f = cpuid.get_feature_info().expect("Leaf is supported");
+    assert_eq!(f.model_id(), 85);
+    assert_eq!(cpuid.num_logical_cpus(), Some(48));
+    assert_eq!(
+        cpuid.get_processor_brand_string().unwrap().as_str(),
+        "Intel(R) Xeon(R) Gold 6252 CPU @ 2.10GHz"
+    );
+    assert_eq!(
+        cpuid.get_extended_state_info().unwrap().iter().count(),
+        CpuId::with_cpuid_fn(reader_with_terminators)
+            .get_extended_state_info()
+            .unwrap()
+            .iter()
+            .count()
+    );
+
+    assert_eq!(
+        snapshot,
+        crate::CpuIdSnapshot::with_cpuid_reader(snapshot.clone())
+    );
+    let mut leafs = snapshot.leafs().clone();
+    leafs.remove(&(0x8000_0008, 0));
+    assert_ne!(snapshot, crate::CpuIdSnapshot::from(leafs));
+}
+
+#[test]
+fn snapshot_diff() {
+    let sparse_reader = |eax: u32, ecx: u32| {
+        let key = (eax as u64) << u32::BITS | ecx as u64;
+        CPUID_VALUE_MAP.get(&key).copied().unwrap_or(CpuIdResult {
+            eax: 0,
+            ebx: 0,
+            ecx: 0,
+            edx: 0,
+        })
+    };
+    let old = crate::CpuIdSnapshot::with_cpuid_reader(sparse_reader);
+
+    let mut builder = crate::CpuIdBuilder::with_cpuid_reader(&old);
+    builder
+        .set_feature(crate::Feature::Avx512f, false)
+        .set_feature(crate::Feature::Hypervisor, true)
+        .set_brand_string("Virtual CPU")
+        // Double the number of L3 sets:
+        .update(0x4, 3, |res| res.ecx = (res.ecx + 1) * 2 - 1);
+    let new = crate::CpuIdSnapshot::from(builder.build());
+
+    let diff = old.diff(&new);
+    assert_eq!(
+        diff.removed_features().collect::<std::vec::Vec<_>>(),
+        [crate::Feature::Avx512f]
+    );
+    assert_eq!(
+        diff.added_features().collect::<std::vec::Vec<_>>(),
+        [crate::Feature::Hypervisor]
+    );
+    assert_eq!(
+        std::format!("{}", diff),
+        "HYPERVISOR: absent → present\n\
+         AVX512F: present → absent\n\
+         Brand string: Intel(R) Xeon(R) Gold 6252 CPU @ 2.10GHz → Virtual CPU\n\
+         L3 size: 36608 KiB → 73216 KiB\n"
+    );
+    // Leafs 1 (hypervisor), 4.3 (L3), 7 (AVX512F) and the brand string:
+    let changed: std::vec::Vec<(u32, u32)> =
+        diff.leafs.iter().map(|c| (c.leaf, c.subleaf)).collect();
+    assert_eq!(
+        changed,
+        [
+            (0x1, 0),
+            (0x4, 3),
+            (0x7, 0),
+            (0x8000_0002, 0),
+            (0x8000_0003, 0),
+            (0x8000_0004, 0)
+        ]
+    );
+}
+
+#[test]
+fn tlb_hierarchy() {
+    use crate::{Associativity, DatType, PageSize};
+
+    let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
+    let tlbs = cpuid.tlb_hierarchy();
+    assert_eq!(tlbs.tlbs.len(), 10);
+
+    let stlb = tlbs.get(PageSize::Size4K, 2, DatType::UnifiedTLB).unwrap();
+    assert_eq!(stlb.entries, 1536);
+    assert_eq!(stlb.associativity, Associativity::NWay(6));
+    assert_eq!(stlb.reach(), 6 << 20);
+
+    let itlb = tlbs
+        .get(PageSize::Size2M, 1, DatType::InstructionTLB)
+        .unwrap();
+    assert_eq!(itlb.entries, 8);
+    assert_eq!(itlb.associativity, Associativity::Full);
+
+    let huge: alloc::vec::Vec<_> = tlbs.page_size(PageSize::Size1G).collect();
+    assert_eq!(huge.len(), 2);
+    assert_eq!(huge[0].typ, DatType::DataTLB);
+    assert_eq!(huge[0].entries, 4);
+    assert_eq!(huge[1].typ, DatType::UnifiedTLB);
+    assert_eq!(huge[1].entries, 16);
+}
+
+#[test]
+fn cache_line_size() {
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
+    assert_eq!(cpuid.cache_line_size(), Some(64));
+}
+
+#[test]
+fn exact_size_iterators() {
+    use crate::{CacheType, TopologyType};
+
+    let cpuid = CpuId::with_cpuid_fn(reader_with_terminators);
+    let mut caches = cpuid.get_cache_parameters().expect("Leaf is supported");
+    assert_eq!(caches.len(), 4);
+    let l3 = caches.next_back().unwrap();
+    assert_eq!(l3.level(), 3);
+    assert_eq!(caches.len(), 3);
+    let l1d = caches.next().unwrap();
+    assert_eq!(l1d.cache_type(), CacheType::Data);
+    assert_eq!(caches.clone().count(), 2);
+    assert_eq!(
+        caches
+            .rev()
+            .map(|c| c.level())
+            .collect::<alloc::vec::Vec<_>>(),
+        [2, 1]
+    );
+
+    let mut levels = cpuid
+        .get_extended_topology_info()
+        .expect("Leaf is supported");
+    assert_eq!(levels.len(), 2);
+    assert_eq!(levels.next_back().unwrap().level_type(), TopologyType::Core);
+}
+
+#[test]
+fn iterators_read_subleafs_once() {
+    use std::cell::Cell;
+
+    // Reads of the cache and topology leafs:
+    let reads = Cell::new(0);
+    let read = |eax, ecx| {
+        if eax == 0x4 || eax == 0xb {
+            reads.set(reads.get() + 1);
+        }
+        reader_with_terminators(eax, ecx)
+    };
+    let cpuid = CpuId::with_cpuid_fn(&read);
+
+    // 4 caches and the terminating subleaf
+    let caches = cpuid.get_cache_parameters().expect("Leaf is supported");
+    assert_eq!(reads.get(), 5);
+    assert_eq!(caches.clone().count(), 4);
+    assert_eq!(caches.rev().count(), 4);
+    assert_eq!(reads.get(), 5);
+
+    let levels = cpuid
+        .get_extended_topology_info()
+        .expect("Leaf is supported");
+    let created = reads.get();
+    assert_eq!(levels.count(), 2);
+    assert_eq!(reads.get(), created);
+}
+
+#[test]
+fn address_bits() {
+    let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
+    assert_eq!(cpuid.physical_address_bits(), 46);
+    assert_eq!(cpuid.linear_address_bits(), 48);
+    // Reserved on Intel, so the physical address bits apply:
+    assert_eq!(cpuid.guest_physical_address_bits(), 46);
+    assert!(!cpuid.supports_5_level_paging());
+}
+
+#[test]
+fn extended_feature_predicates() {
+    let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
+    assert!(cpuid.has_syscall_sysret());
+    assert!(cpuid.has_nx());
+    assert!(cpuid.has_1gib_pages());
+    assert!(cpuid.has_rdtscp());
+    assert!(cpuid.has_64bit_mode());
+}
+
+#[test]
+fn supported_subleafs() {
+    let cpuid = CpuId::with_cpuid_fn(cpuid_reader);
+    // XCR0 components 0-7 and 9, IA32_XSS component 8 (PT):
+    assert!(cpuid.supports_leaf(0xd, 7));
+    assert!(cpuid.supports_leaf(0xd, 8));
+    assert!(cpuid.supports_leaf(0xd, 9));
+    assert!(!cpuid.supports_leaf(0xd, 10));
+    // L3 monitoring, and L3 and MBA allocation:
+    assert!(cpuid.supports_leaf(0xf, 1));
+    assert!(!cpuid.supports_leaf(0xf, 2));
+    assert!(cpuid.supports_leaf(0x10, 1));
+    assert!(!cpuid.supports_leaf(0x10, 2));
+    assert!(cpuid.supports_leaf(0x10, 3));
+    // Processor trace reports one more subleaf:
+    assert!(cpuid.supports_leaf(0x14, 1));
+    assert!(!cpuid.supports_leaf(0x14, 2));
+    // Leafs without subleafs:
+    assert!(cpuid.supports_leaf(0x1, 0));
+    assert!(!cpuid.supports_leaf(0x1, 1));
+}
+
+#[test]
+fn info_structs_compare_registers() {
+    let snapshot = testdata::load("xeon-gold-6252").unwrap();
+    // Another logical processor of the same CPU:
+    let mut builder = CpuIdBuilder::with_cpuid_reader(&snapshot);
+    builder.update(0x1, 0, |res| res.ebx ^= 1 << 24);
+    let other = CpuIdSnapshot::from(builder.build());
+    let (a, b, other) = (snapshot.cpuid(), snapshot.cpuid(), other.cpuid());
+
+    assert_eq!(a.get_feature_info(), b.get_feature_info());
+    assert_ne!(a.get_feature_info(), other.get_feature_info());
+    assert_eq!(
+        a.get_extended_feature_info(),
+        other.get_extended_feature_info()
+    );
+    assert_eq!(a.get_tsc_info(), other.get_tsc_info());
+    // Structs that keep a reader compare only their registers:
+    assert_eq!(a.get_extended_state_info(), other.get_extended_state_info());
+}
+
+#[test]
+fn from_registers() {
+    let snapshot = testdata::load("xeon-gold-6252").unwrap();
+    let cpuid = snapshot.cpuid();
+    let leaf = |leaf, subleaf| snapshot.get(leaf, subleaf).unwrap();
+
+    let vendor = VendorInfo::from_registers(leaf(0x0, 0));
+    assert_eq!(Some(&vendor), cpuid.get_vendor_info().as_ref());
+    assert_eq!(
+        FeatureInfo::from_registers(&vendor, leaf(0x1, 0)),
+        cpuid.get_feature_info().unwrap()
+    );
+    assert_eq!(
+        ThermalPowerInfo::from_registers(leaf(0x6, 0)),
+        cpuid.get_thermal_power_info().unwrap()
+    );
+    assert_eq!(
+        ExtendedFeatures::from_registers(leaf(0x7, 0), res(0, 0, 0, 0), res(0, 0, 0, 0)),
+        cpuid.get_extended_feature_info().unwrap()
+    );
+    assert_eq!(
+        ExtendedProcessorFeatureIdentifiers::from_registers(&vendor, leaf(0x8000_0001, 0)),
+        cpuid
+            .get_extended_processor_and_feature_identifiers()
+            .unwrap()
+    );
+    assert_eq!(
+        ProcessorCapacityAndFeatureInfo::from_registers(leaf(0x8000_0008, 0)),
+        cpuid.get_processor_capacity_feature_info().unwrap()
+    );
+
+    let l1d = CacheParameter::from_registers(leaf(0x4, 0));
+    assert_eq!(Some(l1d), cpuid.get_cache_parameters().unwrap().next());
+    assert_eq!(l1d.level(), 1);
+
+    let smt = ExtendedTopologyLevel::from_registers(leaf(0xb, 0));
+    assert_eq!(smt.level_type(), TopologyType::SMT);
+    assert_eq!(smt.processors(), 2);
+
+    // Arbitrary registers are decoded without panicking.
+    let garbage = res(u32::MAX, u32::MAX, u32::MAX, u32::MAX);
+    assert_eq!(CacheParameter::from_registers(garbage).level(), 7);
+    assert_eq!(
+        ExtendedTopologyLevel::from_registers(garbage).level_type(),
+        TopologyType::Unknown(0xff)
+    );
+}
@@ -0,0 +1,413 @@
+//! A unified view of the TLBs, no matter whether the CPU enumerates them with
+//! the descriptors of LEAF=0x02, the deterministic address translation
+//! parameters of LEAF=0x18 or the AMD TLB leafs (LEAF=0x8000_0005,
+//! 0x8000_0006 and 0x8000_0019).
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{Associativity, CpuId, CpuIdReader, DatType};
+
+/// Page size a TLB holds translations for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PageSize {
+    /// 4 KiB pages.
+    Size4K,
+    /// 2 MiB pages.
+    Size2M,
+    /// 4 MiB pages (32-bit paging without PAE).
+    Size4M,
+    /// 1 GiB pages.
+    Size1G,
+}
+
+impl PageSize {
+    /// All page sizes, from small to large.
+    pub const ALL: [PageSize; 4] = [
+        PageSize::Size4K,
+        PageSize::Size2M,
+        PageSize::Size4M,
+        PageSize::Size1G,
+    ];
+
+    /// Size of a page in bytes.
+    pub fn bytes(&self) -> u64 {
+        match self {
+            PageSize::Size4K => 4 << 10,
+            PageSize::Size2M => 2 << 20,
+            PageSize::Size4M => 4 << 20,
+            PageSize::Size1G => 1 << 30,
+        }
+    }
+}
+
+impl fmt::Display for PageSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            PageSize::Size4K => "4K",
+            PageSize::Size2M => "2M",
+            PageSize::Size4M => "4M",
+            PageSize::Size1G => "1G",
+        };
+        f.write_str(s)
+    }
+}
+
+/// The entries a TLB has for one page size.
+///
+/// A TLB that holds translations for several page sizes is listed once per
+/// page size. Whether the entries are shared between the page sizes depends
+/// on the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Tlb {
+    /// Level of the TLB (starts at 1).
+    pub level: u8,
+    /// Data, instruction or unified TLB.
+    pub typ: DatType,
+    /// The page size.
+    pub page_size: PageSize,
+    /// Number of entries for the page size.
+    pub entries: u32,
+    /// Associativity, [`Associativity::Unknown`] if the CPU doesn't report
+    /// it.
+    pub associativity: Associativity,
+}
+
+impl Tlb {
+    /// Amount of memory the TLB can map without misses, in bytes.
+    pub fn reach(&self) -> u64 {
+        self.entries as u64 * self.page_size.bytes()
+    }
+}
+
+/// All TLBs of the CPU, per page size and level.
+///
+/// # Example
+/// ```
+/// use raw_cpuid::{CpuId, PageSize};
+///
+/// let tlbs = CpuId::new().tlb_hierarchy();
+/// for tlb in tlbs.page_size(PageSize::Size2M) {
+///     println!("L{} {}: {} entries", tlb.level, tlb.typ, tlb.entries);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TlbHierarchy {
+    /// The TLBs, ordered by page size, level and type.
+    pub tlbs: Vec<Tlb>,
+}
+
+impl TlbHierarchy {
+    /// Did the CPU report any TLBs?
+    pub fn is_empty(&self) -> bool {
+        self.tlbs.is_empty()
+    }
+
+    /// The TLBs for the given page size, ordered by level and type.
+    pub fn page_size(&self, page_size: PageSize) -> impl Iterator<Item = &Tlb> {
+        self.tlbs.iter().filter(move |t| t.page_size == page_size)
+    }
+
+    /// The TLB of the given page size, level and type.
+    ///
+    /// If the CPU reports more than one, the first one is returned.
+    pub fn get(&self, page_size: PageSize, level: u8, typ: DatType) -> Option<&Tlb> {
+        self.tlbs
+            .iter()
+            .find(|t| t.page_size == page_size && t.level == level && t.typ == typ)
+    }
+
+    fn push(
+        &mut self,
+        level: u8,
+        typ: DatType,
+        page_size: PageSize,
+        associativity: Associativity,
+        entries: u32,
+    ) {
+        if entries > 0 && associativity != Associativity::Disabled {
+            self.tlbs.push(Tlb {
+                level,
+                typ,
+                page_size,
+                entries,
+                associativity,
+            });
+        }
+    }
+
+    fn sort(&mut self) {
+        self.tlbs
+            .sort_by_key(|t| (t.page_size, t.level, t.typ as u8));
+    }
+}
+
+impl fmt::Display for TlbHierarchy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for tlb in self.tlbs.iter() {
+            writeln!(
+                f,
+                "{} L{} {}: {} entries, {}",
+                tlb.page_size, tlb.level, tlb.typ, tlb.entries, tlb.associativity
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<R: CpuIdReader> CpuId<R> {
+    /// All TLBs of the CPU, per page size and level.
+    ///
+    /// On AMD-compatible CPUs the TLBs are taken from LEAF=0x8000_0005,
+    /// 0x8000_0006 and 0x8000_0019. On other CPUs from LEAF=0x18 or, if the
+    /// CPU doesn't report it, from the descriptors of LEAF=0x02. The
+    /// descriptors don't report levels, all TLBs except the shared
+    /// second-level TLB are listed at level 1.
+    ///
+    /// The AMD leafs report the entries for 2-MB pages, 4-MB pages take two
+    /// of them.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn tlb_hierarchy(&self) -> TlbHierarchy {
+        let mut tlbs = TlbHierarchy::default();
+        if self.is_amd_compatible() {
+            self.amd_tlbs(&mut tlbs);
+        } else {
+            self.dat_tlbs(&mut tlbs);
+            if tlbs.is_empty() {
+                self.descriptor_tlbs(&mut tlbs);
+            }
+        }
+        tlbs.sort();
+        tlbs
+    }
+
+    fn amd_tlbs(&self, tlbs: &mut TlbHierarchy) {
+        use DatType::{DataTLB, InstructionTLB};
+        use PageSize::*;
+
+        if let Some(l1) = self.get_l1_cache_and_tlb_info() {
+            let (dassoc, dsize) = (l1.dtlb_2m_4m_associativity(), l1.dtlb_2m_4m_size());
+            let (iassoc, isize) = (l1.itlb_2m_4m_associativity(), l1.itlb_2m_4m_size());
+            tlbs.push(
+                1,
+                DataTLB,
+                Size4K,
+                l1.dtlb_4k_associativity(),
+                l1.dtlb_4k_size() as u32,
+            );
+            tlbs.push(
+                1,
+                InstructionTLB,
+                Size4K,
+                l1.itlb_4k_associativity(),
+                l1.itlb_4k_size() as u32,
+            );
+            tlbs.push(1, DataTLB, Size2M, dassoc, dsize as u32);
+            tlbs.push(1, InstructionTLB, Size2M, iassoc, isize as u32);
+            tlbs.push(1, DataTLB, Size4M, dassoc, dsize as u32 / 2);
+            tlbs.push(1, InstructionTLB, Size4M, iassoc, isize as u32 / 2);
+        }
+        if let Some(l2) = self.get_l2_l3_cache_and_tlb_info() {
+            let (dassoc, dsize) = (l2.dtlb_2m_4m_associativity(), l2.dtlb_2m_4m_size());
+            let (iassoc, isize) = (l2.itlb_2m_4m_associativity(), l2.itlb_2m_4m_size());
+            tlbs.push(
+                2,
+                DataTLB,
+                Size4K,
+                l2.dtlb_4k_associativity(),
+                l2.dtlb_4k_size() as u32,
+            );
+            tlbs.push(
+                2,
+                InstructionTLB,
+                Size4K,
+                l2.itlb_4k_associativity(),
+                l2.itlb_4k_size() as u32,
+            );
+            tlbs.push(2, DataTLB, Size2M, dassoc, dsize as u32);
+            tlbs.push(2, InstructionTLB, Size2M, iassoc, isize as u32);
+            tlbs.push(2, DataTLB, Size4M, dassoc, dsize as u32 / 2);
+            tlbs.push(2, InstructionTLB, Size4M, iassoc, isize as u32 / 2);
+        }
+        if let Some(g) = self.get_tlb_1g_page_info() {
+            tlbs.push(
+                1,
+                DataTLB,
+                Size1G,
+                g.l1_dtlb_1g_associativity(),
+                g.l1_dtlb_1g_size() as u32,
+            );
+            tlbs.push(
+                1,
+                InstructionTLB,
+                Size1G,
+                g.l1_itlb_1g_associativity(),
+                g.l1_itlb_1g_size() as u32,
+            );
+            tlbs.push(
+                2,
+                DataTLB,
+                Size1G,
+                g.l2_dtlb_1g_associativity(),
+                g.l2_dtlb_1g_size() as u32,
+            );
+            tlbs.push(
+                2,
+                InstructionTLB,
+                Size1G,
+                g.l2_itlb_1g_associativity(),
+                g.l2_itlb_1g_size() as u32,
+            );
+        }
+    }
+
+    fn dat_tlbs(&self, tlbs: &mut TlbHierarchy) {
+        let Some(dat) = self.get_deterministic_address_translation_info() else {
+            return;
+        };
+        for info in dat {
+            let typ = info.cache_type();
+            if !matches!(
+                typ,
+                DatType::DataTLB | DatType::InstructionTLB | DatType::UnifiedTLB
+            ) {
+                continue;
+            }
+            let entries = info.ways() as u32 * info.sets();
+            let page_sizes = [
+                (info.has_4k_entries(), PageSize::Size4K),
+                (info.has_2mb_entries(), PageSize::Size2M),
+                (info.has_4mb_entries(), PageSize::Size4M),
+                (info.has_1gb_entries(), PageSize::Size1G),
+            ];
+            for (_, page_size) in page_sizes.iter().filter(|(has, _)| *has) {
+                tlbs.push(
+                    info.cache_level(),
+                    typ,
+                    *page_size,
+                    info.associativity(),
+                    entries,
+                );
+            }
+        }
+    }
+
+    fn descriptor_tlbs(&self, tlbs: &mut TlbHierarchy) {
+        let Some(descriptors) = self.get_cache_info() else {
+            return;
+        };
+        for descriptor in descriptors {
+            for tlb in descriptor_tlbs(descriptor.num) {
+                for page_size in tlb.page_sizes {
+                    tlbs.push(
+                        tlb.level,
+                        tlb.typ,
+                        *page_size,
+                        tlb.associativity,
+                        tlb.entries,
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// A TLB described by a LEAF=0x02 descriptor.
+struct DescriptorTlb {
+    descriptor: u8,
+    typ: DatType,
+    level: u8,
+    page_sizes: &'static [PageSize],
+    associativity: Associativity,
+    entries: u32,
+}
+
+const fn tlb(
+    descriptor: u8,
+    typ: DatType,
+    level: u8,
+    page_sizes: &'static [PageSize],
+    associativity: Associativity,
+    entries: u32,
+) -> DescriptorTlb {
+    DescriptorTlb {
+        descriptor,
+        typ,
+        level,
+        page_sizes,
+        associativity,
+        entries,
+    }
+}
+
+const K4: &[PageSize] = &[PageSize::Size4K];
+const M2: &[PageSize] = &[PageSize::Size2M];
+const M4: &[PageSize] = &[PageSize::Size4M];
+const G1: &[PageSize] = &[PageSize::Size1G];
+const K4_M4: &[PageSize] = &[PageSize::Size4K, PageSize::Size4M];
+const K4_M2: &[PageSize] = &[PageSize::Size4K, PageSize::Size2M];
+const M2_M4: &[PageSize] = &[PageSize::Size2M, PageSize::Size4M];
+const K4_M2_M4: &[PageSize] = &[PageSize::Size4K, PageSize::Size2M, PageSize::Size4M];
+
+const D: DatType = DatType::DataTLB;
+const I: DatType = DatType::InstructionTLB;
+const U: DatType = DatType::UnifiedTLB;
+
+/// The TLBs of the LEAF=0x02 descriptors (see [`crate::CacheInfo::desc`]).
+///
+/// Descriptors that describe two TLBs (e.g., 0x63) have two entries.
+static DESCRIPTOR_TLBS: &[DescriptorTlb] = &[
+    tlb(0x01, I, 1, K4, Associativity::NWay(4), 32),
+    tlb(0x02, I, 1, M4, Associativity::Full, 2),
+    tlb(0x03, D, 1, K4, Associativity::NWay(4), 64),
+    tlb(0x04, D, 1, M4, Associativity::NWay(4), 8),
+    tlb(0x05, D, 1, M4, Associativity::NWay(4), 32),
+    tlb(0x0B, I, 1, M4, Associativity::NWay(4), 4),
+    tlb(0x4F, I, 1, K4, Associativity::Unknown, 32),
+    tlb(0x50, I, 1, K4_M2_M4, Associativity::Unknown, 64),
+    tlb(0x51, I, 1, K4_M2_M4, Associativity::Unknown, 128),
+    tlb(0x52, I, 1, K4_M2_M4, Associativity::Unknown, 256),
+    tlb(0x55, I, 1, M2_M4, Associativity::Full, 7),
+    tlb(0x56, D, 1, M4, Associativity::NWay(4), 16),
+    tlb(0x57, D, 1, K4, Associativity::NWay(4), 16),
+    tlb(0x59, D, 1, K4, Associativity::Full, 16),
+    tlb(0x5A, D, 1, M2_M4, Associativity::NWay(4), 32),
+    tlb(0x5B, D, 1, K4_M4, Associativity::Unknown, 64),
+    tlb(0x5C, D, 1, K4_M4, Associativity::Unknown, 128),
+    tlb(0x5D, D, 1, K4_M4, Associativity::Unknown, 256),
+    tlb(0x61, I, 1, K4, Associativity::Full, 48),
+    tlb(0x63, D, 1, M2_M4, Associativity::NWay(4), 32),
+    tlb(0x63, D, 1, G1, Associativity::NWay(4), 4),
+    tlb(0x64, D, 1, K4, Associativity::NWay(4), 512),
+    tlb(0x6A, D, 1, K4, Associativity::NWay(8), 64),
+    tlb(0x6B, D, 1, K4, Associativity::NWay(8), 256),
+    tlb(0x6C, D, 1, M2_M4, Associativity::NWay(8), 128),
+    tlb(0x6D, D, 1, G1, Associativity::Full, 16),
+    tlb(0x76, I, 1, M2_M4, Associativity::Full, 8),
+    tlb(0xA0, D, 1, K4, Associativity::Full, 32),
+    tlb(0xB0, I, 1, K4, Associativity::NWay(4), 128),
+    tlb(0xB1, I, 1, M2, Associativity::NWay(4), 8),
+    tlb(0xB1, I, 1, M4, Associativity::NWay(4), 4),
+    tlb(0xB2, I, 1, K4, Associativity::NWay(4), 64),
+    tlb(0xB3, D, 1, K4, Associativity::NWay(4), 128),
+    tlb(0xB4, D, 1, K4, Associativity::NWay(4), 256),
+    tlb(0xB5, I, 1, K4, Associativity::NWay(8), 64),
+    tlb(0xB6, I, 1, K4, Associativity::NWay(8), 128),
+    tlb(0xBA, D, 1, K4, Associativity::NWay(4), 64),
+    tlb(0xC0, D, 1, K4_M4, Associativity::NWay(4), 8),
+    tlb(0xC1, U, 2, K4_M2, Associativity::NWay(8), 1024),
+    tlb(0xC2, D, 1, M2_M4, Associativity::NWay(4), 16),
+    tlb(0xC3, U, 2, K4_M2, Associativity::NWay(6), 1536),
+    tlb(0xC3, U, 2, G1, Associativity::NWay(4), 16),
+    tlb(0xC4, D, 1, M2_M4, Associativity::NWay(4), 32),
+    tlb(0xCA, U, 2, K4, Associativity::NWay(4), 512),
+];
+
+/// The TLBs described by a LEAF=0x02 descriptor.
+fn descriptor_tlbs(num: u8) -> impl Iterator<Item = &'static DescriptorTlb> {
+    DESCRIPTOR_TLBS.iter().filter(move |t| t.descriptor == num)
+}
@@ -0,0 +1,256 @@
+//! Decompose APIC IDs into package, die, core and SMT IDs.
+use crate::{
+    get_bits, CpuId, CpuIdReader, ExtendedTopologyLevel, TopologyType, EAX_EXTENDED_TOPOLOGY_INFO,
+    EAX_EXTENDED_TOPOLOGY_INFO_V2, EAX_PROCESSOR_TOPOLOGY_INFO,
+};
+
+/// Bit layout of the APIC IDs of a CPU, i.e., which bits of an APIC ID
+/// identify the SMT thread, the core, the die and the package.
+///
+/// Module and tile levels (if any) are folded into the core ID.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ApicIdLayout {
+    /// Number of bits to shift an APIC ID right to get the core ID.
+    pub smt_shift: u32,
+    /// Number of bits to shift an APIC ID right to get the die ID.
+    ///
+    /// Equal to `package_shift` if the CPU doesn't enumerate dies.
+    pub die_shift: u32,
+    /// Number of bits to shift an APIC ID right to get the package ID.
+    pub package_shift: u32,
+}
+
+/// An (x2)APIC ID that can be decomposed into package, die, core and SMT IDs.
+///
+/// # Example
+/// ```
+/// use raw_cpuid::CpuId;
+///
+/// let cpuid = CpuId::new();
+/// if let Some(id) = cpuid.apic_id() {
+///     println!(
+///         "APIC#{} (pkg: {}, die: {}, core: {}, smt: {})",
+///         id.id(),
+///         id.package_id(),
+///         id.die_id(),
+///         id.core_id(),
+///         id.smt_id()
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApicId {
+    id: u32,
+    layout: ApicIdLayout,
+}
+
+impl ApicId {
+    /// Creates an APIC ID from a 32-bit x2APIC ID.
+    pub fn new(id: u32, layout: ApicIdLayout) -> Self {
+        Self { id, layout }
+    }
+
+    /// Creates an APIC ID from an 8-bit xAPIC ID, e.g.,
+    /// [`FeatureInfo::initial_local_apic_id`](crate::FeatureInfo::initial_local_apic_id).
+    pub fn from_xapic(id: u8, layout: ApicIdLayout) -> Self {
+        Self::new(id as u32, layout)
+    }
+
+    /// The raw APIC ID.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// The layout used to decompose this APIC ID.
+    pub fn layout(&self) -> ApicIdLayout {
+        self.layout
+    }
+
+    /// ID of the physical package.
+    pub fn package_id(&self) -> u32 {
+        shift_right(self.id, self.layout.package_shift)
+    }
+
+    /// ID of the die within its package (0 if the CPU doesn't enumerate dies).
+    pub fn die_id(&self) -> u32 {
+        self.bits(self.layout.die_shift, self.layout.package_shift)
+    }
+
+    /// ID of the core within its die.
+    pub fn core_id(&self) -> u32 {
+        self.bits(self.layout.smt_shift, self.layout.die_shift)
+    }
+
+    /// ID of the logical processor (SMT thread) within its core.
+    pub fn smt_id(&self) -> u32 {
+        self.bits(0, self.layout.smt_shift)
+    }
+
+    /// Bits `from..to` of the ID.
+    fn bits(&self, from: u32, to: u32) -> u32 {
+        if from >= to || from >= u32::BITS {
+            return 0;
+        }
+        get_bits(self.id, from, core::cmp::min(to, u32::BITS) - 1)
+    }
+}
+
+fn shift_right(value: u32, shift: u32) -> u32 {
+    value.checked_shr(shift).unwrap_or(0)
+}
+
+/// Number of bits needed to represent `count` different IDs.
+fn id_bits(count: usize) -> u32 {
+    if count <= 1 {
+        0
+    } else {
+        usize::BITS - (count - 1).leading_zeros()
+    }
+}
+
+impl<R: CpuIdReader> CpuId<R> {
+    /// Bit layout of the APIC IDs of this CPU.
+    ///
+    /// This uses the V2 extended topology leaf (LEAF=0x1F) or the extended
+    /// topology leaf (LEAF=0x0B) if available. Otherwise the layout is derived
+    /// from the number of logical processors and threads per core (see
+    /// [`CpuId::num_logical_cpus`] and [`CpuId::threads_per_core`]), or
+    /// `Fn8000_0008_ECX[ApicIdSize]` on AMD.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn apic_id_layout(&self) -> Option<ApicIdLayout> {
+        for leaf in [EAX_EXTENDED_TOPOLOGY_INFO_V2, EAX_EXTENDED_TOPOLOGY_INFO] {
+            if let Some(layout) = self.apic_id_layout_from(leaf) {
+                return Some(layout);
+            }
+        }
+
+        let logical = self.num_logical_cpus()?;
+        let threads = self.threads_per_core()?;
+        let amd_apic_id_size = if self.is_amd_compatible() {
+            self.get_processor_capacity_feature_info()
+                .map_or(0, |info| info.apic_id_size() as u32)
+        } else {
+            0
+        };
+        let package_shift = if amd_apic_id_size != 0 {
+            amd_apic_id_size
+        } else {
+            id_bits(logical)
+        };
+
+        Some(ApicIdLayout {
+            smt_shift: id_bits(threads),
+            die_shift: package_shift,
+            package_shift,
+        })
+    }
+
+    fn apic_id_layout_from(&self, leaf: u32) -> Option<ApicIdLayout> {
+        if !self.leaf_is_supported(leaf) {
+            return None;
+        }
+
+        let mut layout = ApicIdLayout::default();
+        let mut has_die = false;
+        let mut valid = false;
+        for subleaf in 0..u8::MAX as u32 {
+            let level = self.topology_level(leaf, subleaf);
+            let shift = level.shift_right_for_next_apic_id();
+            match level.level_type() {
+                TopologyType::Invalid => break,
+                TopologyType::SMT => layout.smt_shift = shift,
+                TopologyType::Die => has_die = true,
+                _ => {}
+            }
+            if !has_die {
+                // Everything below the die level belongs to the core ID.
+                layout.die_shift = shift;
+            }
+            layout.package_shift = shift;
+            valid = true;
+        }
+
+        if !has_die {
+            layout.die_shift = layout.package_shift;
+        }
+        valid.then_some(layout)
+    }
+
+    fn topology_level(&self, leaf: u32, subleaf: u32) -> ExtendedTopologyLevel {
+        let res = self.read.cpuid2(leaf, subleaf);
+        ExtendedTopologyLevel {
+            eax: res.eax,
+            ebx: res.ebx,
+            ecx: res.ecx,
+            edx: res.edx,
+        }
+    }
+
+    /// Does the CPU support simultaneous multithreading (hyper-threading)?
+    ///
+    /// This is the HTT bit (`CPUID.01H:EDX[28]`). It only tells that the
+    /// package *may* contain more than one logical processor: it is also set
+    /// by multi-core CPUs without SMT and stays set if SMT is disabled in the
+    /// firmware. Use [`CpuId::is_smt_enabled`] to find out whether SMT is
+    /// actually enabled.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn is_smt_supported(&self) -> bool {
+        self.get_feature_info().is_some_and(|info| info.has_htt())
+    }
+
+    /// Is simultaneous multithreading (hyper-threading) enabled, i.e., does a
+    /// core run more than one logical processor?
+    ///
+    /// This uses the number of logical processors of the SMT level of the V2
+    /// extended topology leaf (LEAF=0x1F) or the extended topology leaf
+    /// (LEAF=0x0B), and `Fn8000_001E_EBX[ThreadsPerCore]` on AMD. These
+    /// reflect the configuration of the firmware, so SMT disabled by the
+    /// operating system (e.g., with Linux' `nosmt`) still counts as enabled.
+    ///
+    /// Returns `None` if none of these leafs is available, since the legacy
+    /// leafs only report the maximum number of addressable IDs.
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn is_smt_enabled(&self) -> Option<bool> {
+        for leaf in [EAX_EXTENDED_TOPOLOGY_INFO_V2, EAX_EXTENDED_TOPOLOGY_INFO] {
+            if !self.leaf_is_supported(leaf) {
+                continue;
+            }
+            // The first level is the SMT level, if the CPU has one at all.
+            let level = self.topology_level(leaf, 0);
+            match level.level_type() {
+                TopologyType::Invalid => {}
+                TopologyType::SMT => return Some(level.processors() > 1),
+                _ => return Some(false),
+            }
+        }
+
+        let has_topology_extensions = self
+            .get_extended_processor_and_feature_identifiers()
+            .is_some_and(|f| f.has_topology_extensions());
+        if self.is_amd_compatible()
+            && has_topology_extensions
+            && self.leaf_is_supported(EAX_PROCESSOR_TOPOLOGY_INFO)
+        {
+            let res = self.read.cpuid1(EAX_PROCESSOR_TOPOLOGY_INFO);
+            return Some(get_bits(res.ebx, 8, 15) > 0);
+        }
+
+        None
+    }
+
+    /// The APIC ID of the logical processor we're running on (see
+    /// [`CpuId::x2apic_id`]), decomposed with [`CpuId::apic_id_layout`].
+    ///
+    /// # Platforms
+    /// ✅ AMD ✅ Intel
+    pub fn apic_id(&self) -> Option<ApicId> {
+        Some(ApicId::new(self.x2apic_id()?, self.apic_id_layout()?))
+    }
+}
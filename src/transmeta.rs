@@ -0,0 +1,253 @@
+//! Data-structures / interpretation for the Transmeta leafs (>= 0x8086_0000)
+//! of Crusoe and Efficeon processors.
+use core::fmt::{self, Debug, Display, Formatter};
+use core::mem::size_of;
+use core::slice;
+use core::str;
+
+use crate::{get_bits, set_bits, CpuIdResult};
+
+/// Processor information of Transmeta CPUs (LEAF=0x8086_0001).
+///
+/// # Platforms
+/// ❌ AMD ❌ Intel ✅ Transmeta
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TransmetaProcessorInfo {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+    edx: u32,
+}
+
+impl TransmetaProcessorInfo {
+    /// Decodes the registers of LEAF=0x8086_0001.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+            edx: data.edx,
+        }
+    }
+
+    /// Processor signature (family, model and stepping), in the same format
+    /// as EAX of LEAF=0x01.
+    pub fn signature(&self) -> u32 {
+        self.eax
+    }
+
+    /// Hardware revision, one byte per component (e.g., 0x01020304 is
+    /// revision 1.2.3.4).
+    ///
+    /// Newer processors report 0x02000000 here and the actual revision in
+    /// [`TransmetaCmsVersion::processor_revision`].
+    pub fn hardware_revision(&self) -> u32 {
+        self.ebx
+    }
+
+    /// Nominal processor frequency in MHz.
+    pub fn frequency_mhz(&self) -> u32 {
+        self.ecx
+    }
+
+    /// Is the processor running the recovery Code Morphing Software?
+    pub fn has_recovery(&self) -> bool {
+        get_bits(self.edx, 0, 0) == 1
+    }
+
+    /// Is LongRun power management supported?
+    pub fn has_longrun(&self) -> bool {
+        get_bits(self.edx, 1, 1) == 1
+    }
+
+    /// Is the LongRun Table Interface supported?
+    pub fn has_lrti(&self) -> bool {
+        get_bits(self.edx, 3, 3) == 1
+    }
+
+    /// Encode this struct back into the registers of leaf 0x8086_0001.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: self.edx,
+        }
+    }
+
+    /// Set the value reported by [`Self::hardware_revision`].
+    pub fn set_hardware_revision(&mut self, value: u32) {
+        self.ebx = value;
+    }
+
+    /// Set the value reported by [`Self::frequency_mhz`].
+    pub fn set_frequency_mhz(&mut self, value: u32) {
+        self.ecx = value;
+    }
+
+    /// Set or clear the bit reported by [`Self::has_longrun`].
+    pub fn set_longrun(&mut self, bit: bool) {
+        set_bits(&mut self.edx, 1, 1, bit as u32);
+    }
+
+    /// Set or clear the bit reported by [`Self::has_lrti`].
+    pub fn set_lrti(&mut self, bit: bool) {
+        set_bits(&mut self.edx, 3, 3, bit as u32);
+    }
+}
+
+impl Debug for TransmetaProcessorInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransmetaProcessorInfo")
+            .field("signature", &self.signature())
+            .field("hardware_revision", &self.hardware_revision())
+            .field("frequency_mhz", &self.frequency_mhz())
+            .field("has_recovery", &self.has_recovery())
+            .field("has_longrun", &self.has_longrun())
+            .field("has_lrti", &self.has_lrti())
+            .finish()
+    }
+}
+
+/// Code Morphing Software (CMS) version of Transmeta CPUs (LEAF=0x8086_0002).
+///
+/// Formats as `major.minor.patch-build-revision`, like Linux does at boot.
+///
+/// # Platforms
+/// ❌ AMD ❌ Intel ✅ Transmeta
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TransmetaCmsVersion {
+    eax: u32,
+    ebx: u32,
+    ecx: u32,
+}
+
+impl TransmetaCmsVersion {
+    /// Decodes the registers of LEAF=0x8086_0002.
+    pub fn from_registers(data: CpuIdResult) -> Self {
+        Self {
+            eax: data.eax,
+            ebx: data.ebx,
+            ecx: data.ecx,
+        }
+    }
+
+    /// Processor revision of newer processors (which report 0x02000000 in
+    /// [`TransmetaProcessorInfo::hardware_revision`]).
+    pub fn processor_revision(&self) -> u32 {
+        self.eax
+    }
+
+    /// CMS version as `(major, minor, patch, build)`.
+    pub fn cms_version(&self) -> (u8, u8, u8, u8) {
+        (
+            get_bits(self.ebx, 24, 31) as u8,
+            get_bits(self.ebx, 16, 23) as u8,
+            get_bits(self.ebx, 8, 15) as u8,
+            get_bits(self.ebx, 0, 7) as u8,
+        )
+    }
+
+    /// CMS revision within the version of [`Self::cms_version`].
+    pub fn cms_revision(&self) -> u32 {
+        self.ecx
+    }
+
+    /// Encode this struct back into the registers of leaf 0x8086_0002.
+    ///
+    /// EDX is reserved and zero.
+    pub fn as_cpuid_result(&self) -> CpuIdResult {
+        CpuIdResult {
+            eax: self.eax,
+            ebx: self.ebx,
+            ecx: self.ecx,
+            edx: 0,
+        }
+    }
+}
+
+impl Debug for TransmetaCmsVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransmetaCmsVersion")
+            .field("processor_revision", &self.processor_revision())
+            .field("cms_version", &self.cms_version())
+            .field("cms_revision", &self.cms_revision())
+            .finish()
+    }
+}
+
+impl Display for TransmetaCmsVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (major, minor, patch, build) = self.cms_version();
+        write!(
+            f,
+            "{}.{}.{}-{}-{}",
+            major,
+            minor,
+            patch,
+            build,
+            self.cms_revision()
+        )
+    }
+}
+
+/// Information string of the Code Morphing Software on Transmeta CPUs
+/// (LEAF=0x8086_0003..=0x8086_0006), e.g., the CMS build date.
+///
+/// ASCII string up to 64 characters in length.
+///
+/// # Platforms
+/// ❌ AMD ❌ Intel ✅ Transmeta
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TransmetaInfoString {
+    data: [CpuIdResult; 4],
+}
+
+impl TransmetaInfoString {
+    /// Decodes the registers of LEAF=0x8086_0003 to LEAF=0x8086_0006.
+    pub fn from_registers(data: [CpuIdResult; 4]) -> Self {
+        Self { data }
+    }
+
+    /// Return the information string as a rust string.
+    ///
+    /// The string is terminated at the first NUL byte and padding is removed.
+    pub fn as_str(&self) -> &str {
+        // Safety: CpuIdResult is laid out with repr(C), and the array
+        // self.data contains 4 contiguous elements.
+        let slice: &[u8] = unsafe {
+            slice::from_raw_parts(
+                self.data.as_ptr() as *const u8,
+                self.data.len() * size_of::<CpuIdResult>(),
+            )
+        };
+
+        let slice = slice.split(|&x| x == 0).next().unwrap();
+        str::from_utf8(slice)
+            .unwrap_or("Invalid Transmeta Info String")
+            .trim()
+    }
+
+    /// Encode the string back into the registers of leafs 0x8086_0003 to
+    /// 0x8086_0006.
+    pub fn as_cpuid_results(&self) -> [CpuIdResult; 4] {
+        self.data
+    }
+}
+
+impl Debug for TransmetaInfoString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransmetaInfoString")
+            .field("as_str", &self.as_str())
+            .finish()
+    }
+}
+
+impl Display for TransmetaInfoString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,456 @@
+//! Compare the CPUID-derived core, package and cache topology with the view
+//! of Windows (`GetLogicalProcessorInformationEx`), and map the processor
+//! groups of Windows to APIC IDs.
+//!
+//! Windows identifies a logical processor by its processor group and its
+//! number within the group, which is what thread placement APIs (e.g.,
+//! `SetThreadGroupAffinity`) expect.
+use alloc::vec::Vec;
+use core::fmt;
+use core::mem::size_of;
+
+use crate::{ApicId, Associativity, CacheType, CpuId, CpuIdReader};
+
+const RELATION_PROCESSOR_CORE: u32 = 0;
+const RELATION_CACHE: u32 = 2;
+const RELATION_PROCESSOR_PACKAGE: u32 = 3;
+
+/// Set of logical processors within one processor group (`GROUP_AFFINITY`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupAffinity {
+    /// The processor group.
+    pub group: u16,
+    /// Bit `n` is set if logical processor `n` of the group is part of the
+    /// set.
+    pub mask: u64,
+}
+
+impl GroupAffinity {
+    /// Numbers of the logical processors within the group.
+    pub fn numbers(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..64u8).filter(move |n| self.mask & (1 << n) != 0)
+    }
+}
+
+/// A physical core as reported by Windows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsCore {
+    /// Logical processors of the core.
+    pub affinity: Vec<GroupAffinity>,
+    /// Efficiency class of the core, higher values are faster cores on
+    /// hybrid CPUs.
+    pub efficiency_class: u8,
+    /// Does the core run more than one logical processor?
+    pub smt: bool,
+}
+
+/// A cache as reported by Windows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsCache {
+    /// Cache level (starts at 1).
+    pub level: u8,
+    /// Data, instruction or unified cache.
+    pub cache_type: CacheType,
+    /// Size in bytes.
+    pub size: u32,
+    /// Line size in bytes.
+    pub line_size: u16,
+    /// Associativity.
+    pub associativity: Associativity,
+    /// Logical processors that share the cache.
+    pub affinity: Vec<GroupAffinity>,
+}
+
+/// A logical processor identified by its processor group and number, together
+/// with its APIC ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogicalProcessor {
+    /// The processor group.
+    pub group: u16,
+    /// Number of the processor within its group.
+    pub number: u8,
+    /// The APIC ID read by CPUID on this processor.
+    pub apic_id: ApicId,
+}
+
+/// The processor topology as reported by Windows.
+///
+/// # Example
+/// ```no_run
+/// # #[cfg(target_os = "windows")]
+/// # {
+/// use raw_cpuid::{CpuId, WindowsTopology};
+///
+/// let topology = WindowsTopology::from_os().unwrap();
+/// for cpu in topology.processors.iter() {
+///     println!(
+///         "group {} cpu {}: package {} core {}",
+///         cpu.group,
+///         cpu.number,
+///         cpu.apic_id.package_id(),
+///         cpu.apic_id.core_id()
+///     );
+/// }
+/// for mismatch in topology.mismatches(&CpuId::new()) {
+///     println!("{}", mismatch);
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WindowsTopology {
+    /// Physical cores.
+    pub cores: Vec<OsCore>,
+    /// Logical processors of each physical package.
+    pub packages: Vec<Vec<GroupAffinity>>,
+    /// All caches.
+    pub caches: Vec<OsCache>,
+    /// Logical processors with their APIC IDs (empty if the topology was
+    /// parsed without running CPUID on every processor).
+    pub processors: Vec<LogicalProcessor>,
+}
+
+impl WindowsTopology {
+    /// Queries the topology from Windows and reads the APIC ID of every
+    /// logical processor by running CPUID on it.
+    #[cfg(target_os = "windows")]
+    pub fn from_os() -> std::io::Result<Self> {
+        let mut len = 0u32;
+        // Safety: A null buffer with a length of 0 only returns the
+        // required length.
+        unsafe {
+            sys::GetLogicalProcessorInformationEx(
+                sys::RELATION_ALL,
+                core::ptr::null_mut(),
+                &mut len,
+            )
+        };
+
+        // u64 to align the buffer for SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX.
+        let mut buffer = alloc::vec![0u64; (len as usize).div_ceil(8)];
+        // Safety: The buffer is at least `len` bytes long.
+        let ok = unsafe {
+            sys::GetLogicalProcessorInformationEx(
+                sys::RELATION_ALL,
+                buffer.as_mut_ptr() as *mut u8,
+                &mut len,
+            )
+        };
+        if ok == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        // Safety: Windows filled the first `len` bytes of the buffer.
+        let bytes =
+            unsafe { core::slice::from_raw_parts(buffer.as_ptr() as *const u8, len as usize) };
+        let mut topology = Self::parse(bytes);
+        topology.processors = sys::read_apic_ids(&topology.cores)?;
+        Ok(topology)
+    }
+
+    /// Parses the buffer returned by `GetLogicalProcessorInformationEx` (with
+    /// `RelationAll`, or any of the core, package or cache relationships).
+    ///
+    /// Relationships other than cores, packages and caches are skipped, as
+    /// is a truncated entry at the end of the buffer.
+    pub fn parse(buffer: &[u8]) -> Self {
+        let mut topology = WindowsTopology::default();
+
+        let mut offset = 0;
+        while let (Some(relationship), Some(size)) =
+            (read_u32(buffer, offset), read_u32(buffer, offset + 4))
+        {
+            let size = size as usize;
+            let Some(entry) = buffer.get(offset..offset + size) else {
+                break;
+            };
+            if size < 8 {
+                break;
+            }
+
+            match relationship {
+                RELATION_PROCESSOR_CORE => topology.cores.push(OsCore {
+                    affinity: group_masks(entry, 30, 32),
+                    efficiency_class: entry.get(9).copied().unwrap_or(0),
+                    smt: entry.get(8).is_some_and(|flags| flags & 0x1 != 0),
+                }),
+                RELATION_PROCESSOR_PACKAGE => topology.packages.push(group_masks(entry, 30, 32)),
+                RELATION_CACHE => {
+                    let cache_type = match read_u32(entry, 16) {
+                        Some(0) => CacheType::Unified,
+                        Some(1) => CacheType::Instruction,
+                        Some(2) => CacheType::Data,
+                        _ => CacheType::Reserved,
+                    };
+                    topology.caches.push(OsCache {
+                        level: entry.get(8).copied().unwrap_or(0),
+                        cache_type,
+                        size: read_u32(entry, 12).unwrap_or(0),
+                        line_size: read_u16(entry, 10).unwrap_or(0),
+                        associativity: Associativity::for_l1(entry.get(9).copied().unwrap_or(0)),
+                        affinity: group_masks(entry, 38, 40),
+                    });
+                }
+                _ => {}
+            }
+
+            offset += size;
+        }
+
+        topology
+    }
+
+    /// The logical processor with the given group and number.
+    pub fn processor(&self, group: u16, number: u8) -> Option<&LogicalProcessor> {
+        self.processors
+            .iter()
+            .find(|cpu| cpu.group == group && cpu.number == number)
+    }
+
+    /// Compares the topology of Windows with the APIC IDs of
+    /// [`WindowsTopology::processors`] and the cache parameters reported by
+    /// `cpuid`.
+    ///
+    /// Logical processors of a core (or package) must agree on the core (or
+    /// package) ID of their APIC IDs, logical processors that share a cache
+    /// on the cache ID (see [`crate::CacheParameter::cache_id`]), and the
+    /// size, line size and associativity of the caches must match.
+    pub fn mismatches<R: CpuIdReader>(&self, cpuid: &CpuId<R>) -> Vec<WindowsMismatch> {
+        let mut mismatches = Vec::new();
+
+        for core in self.cores.iter() {
+            let ids = self.apic_ids(&core.affinity);
+            let id = |apic: &ApicId| (apic.package_id(), apic.die_id(), apic.core_id());
+            if ids.windows(2).any(|pair| id(&pair[0]) != id(&pair[1])) {
+                mismatches.push(WindowsMismatch::SplitCore(core.affinity.clone()));
+            }
+        }
+
+        for package in self.packages.iter() {
+            let ids = self.apic_ids(package);
+            if ids
+                .windows(2)
+                .any(|pair| pair[0].package_id() != pair[1].package_id())
+            {
+                mismatches.push(WindowsMismatch::SplitPackage(package.clone()));
+            }
+        }
+
+        let caches: Vec<_> = cpuid
+            .get_cache_parameters()
+            .map(|iter| iter.collect())
+            .unwrap_or_default();
+        for os in self.caches.iter() {
+            let Some(cache) = caches
+                .iter()
+                .find(|cache| cache.level() == os.level && cache.cache_type() == os.cache_type)
+            else {
+                mismatches.push(WindowsMismatch::MissingCache {
+                    level: os.level,
+                    cache_type: os.cache_type,
+                });
+                continue;
+            };
+
+            if cache.size() != os.size as usize
+                || cache.coherency_line_size() != os.line_size as usize
+                || cache.associativity() != os.associativity
+            {
+                mismatches.push(WindowsMismatch::CacheProperties {
+                    level: os.level,
+                    cache_type: os.cache_type,
+                });
+            }
+
+            let ids = self.apic_ids(&os.affinity);
+            if ids
+                .windows(2)
+                .any(|pair| cache.cache_id(pair[0].id()) != cache.cache_id(pair[1].id()))
+            {
+                mismatches.push(WindowsMismatch::SplitCache {
+                    level: os.level,
+                    cache_type: os.cache_type,
+                    affinity: os.affinity.clone(),
+                });
+            }
+        }
+
+        mismatches
+    }
+
+    /// APIC IDs of the known logical processors in `affinity`.
+    fn apic_ids(&self, affinity: &[GroupAffinity]) -> Vec<ApicId> {
+        affinity
+            .iter()
+            .flat_map(|set| set.numbers().map(move |number| (set.group, number)))
+            .filter_map(|(group, number)| self.processor(group, number))
+            .map(|cpu| cpu.apic_id)
+            .collect()
+    }
+}
+
+/// A difference between the topology of Windows and CPUID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowsMismatch {
+    /// The logical processors of a core have APIC IDs of different cores.
+    SplitCore(Vec<GroupAffinity>),
+    /// The logical processors of a package have APIC IDs of different
+    /// packages.
+    SplitPackage(Vec<GroupAffinity>),
+    /// Windows reports a cache that isn't reported by CPUID.
+    MissingCache { level: u8, cache_type: CacheType },
+    /// Size, line size or associativity of a cache differ.
+    CacheProperties { level: u8, cache_type: CacheType },
+    /// Logical processors that share a cache have different cache IDs.
+    SplitCache {
+        level: u8,
+        cache_type: CacheType,
+        affinity: Vec<GroupAffinity>,
+    },
+}
+
+impl fmt::Display for WindowsMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WindowsMismatch::SplitCore(affinity) => {
+                write!(f, "Core {:x?} spans several APIC cores", affinity)
+            }
+            WindowsMismatch::SplitPackage(affinity) => {
+                write!(f, "Package {:x?} spans several APIC packages", affinity)
+            }
+            WindowsMismatch::MissingCache { level, cache_type } => {
+                write!(
+                    f,
+                    "L{} {} cache is not reported by CPUID",
+                    level, cache_type
+                )
+            }
+            WindowsMismatch::CacheProperties { level, cache_type } => {
+                write!(f, "L{} {} cache properties differ", level, cache_type)
+            }
+            WindowsMismatch::SplitCache {
+                level,
+                cache_type,
+                affinity,
+            } => write!(
+                f,
+                "L{} {} cache {:x?} spans several cache IDs",
+                level, cache_type, affinity
+            ),
+        }
+    }
+}
+
+/// Reads `GroupCount` at `count_offset` and the `GROUP_AFFINITY` array at
+/// `masks_offset` of an entry.
+///
+/// Older versions of Windows report a single mask and leave the count zero.
+fn group_masks(entry: &[u8], count_offset: usize, masks_offset: usize) -> Vec<GroupAffinity> {
+    // KAFFINITY (usize), Group (u16) and Reserved ([u16; 3])
+    let mask_size = size_of::<usize>();
+    let stride = mask_size + 8;
+    let count = read_u16(entry, count_offset).unwrap_or(0).max(1) as usize;
+
+    (0..count)
+        .map_while(|idx| {
+            let offset = masks_offset + idx * stride;
+            let mask = match mask_size {
+                8 => read_u64(entry, offset)?,
+                _ => read_u32(entry, offset)? as u64,
+            };
+            let group = read_u16(entry, offset + mask_size)?;
+            Some(GroupAffinity { group, mask })
+        })
+        .collect()
+}
+
+fn read_u16(buffer: &[u8], offset: usize) -> Option<u16> {
+    let bytes = buffer.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(buffer: &[u8], offset: usize) -> Option<u32> {
+    let bytes = buffer.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u64(buffer: &[u8], offset: usize) -> Option<u64> {
+    let bytes = buffer.get(offset..offset + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+#[cfg(target_os = "windows")]
+mod sys {
+    use alloc::vec::Vec;
+    use core::ffi::c_void;
+
+    use super::{LogicalProcessor, OsCore};
+    use crate::CpuId;
+
+    pub const RELATION_ALL: u32 = 0xffff;
+
+    #[repr(C)]
+    #[allow(non_camel_case_types)]
+    pub struct GROUP_AFFINITY {
+        pub mask: usize,
+        pub group: u16,
+        pub reserved: [u16; 3],
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        pub fn GetLogicalProcessorInformationEx(
+            relationship: u32,
+            buffer: *mut u8,
+            returned_length: *mut u32,
+        ) -> i32;
+        pub fn GetCurrentThread() -> *mut c_void;
+        pub fn SetThreadGroupAffinity(
+            thread: *mut c_void,
+            affinity: *const GROUP_AFFINITY,
+            previous: *mut GROUP_AFFINITY,
+        ) -> i32;
+    }
+
+    /// Runs CPUID on every logical processor of `cores` (on a thread pinned
+    /// to the processor) to read its APIC ID.
+    pub fn read_apic_ids(cores: &[OsCore]) -> std::io::Result<Vec<LogicalProcessor>> {
+        let mut processors = Vec::new();
+        for set in cores.iter().flat_map(|core| core.affinity.iter()) {
+            for number in set.numbers() {
+                let affinity = GROUP_AFFINITY {
+                    mask: 1 << number,
+                    group: set.group,
+                    reserved: [0; 3],
+                };
+                let apic_id = std::thread::scope(|s| {
+                    s.spawn(|| {
+                        // Safety: `affinity` is a valid GROUP_AFFINITY and the
+                        // previous affinity may be null.
+                        let ok = unsafe {
+                            SetThreadGroupAffinity(
+                                GetCurrentThread(),
+                                &affinity,
+                                core::ptr::null_mut(),
+                            )
+                        };
+                        if ok == 0 {
+                            return Err(std::io::Error::last_os_error());
+                        }
+                        Ok(CpuId::new().apic_id())
+                    })
+                    .join()
+                    .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })?;
+
+                if let Some(apic_id) = apic_id {
+                    processors.push(LogicalProcessor {
+                        group: set.group,
+                        number,
+                        apic_id,
+                    });
+                }
+            }
+        }
+        Ok(processors)
+    }
+}